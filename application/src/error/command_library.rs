@@ -0,0 +1,74 @@
+//! 命令库领域的主错误。
+
+use std::fmt;
+
+use sealantern_interface::error::CommandLibraryServiceError;
+
+/// 命令历史与宏操作失败的应用层主错误。
+///
+/// 携带底层失败细节（source），供应用层日志排查；向
+/// [`CommandLibraryServiceError`] 转换时收敛为分类，不向宿主泄漏敏感信息。
+#[derive(Debug)]
+pub enum CommandLibraryError {
+    /// 指定的实例不存在（无法定位命令库）。
+    InstanceNotFound,
+    /// 指定的宏不存在。
+    MacroNotFound,
+    /// 客户端提供的宏定义或查询参数不合法。
+    InvalidInput,
+    /// 底层命令库或实例查询失败。
+    OperationFailed {
+        /// 底层来源错误。
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl fmt::Display for CommandLibraryError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InstanceNotFound => write!(formatter, "server instance not found"),
+            Self::MacroNotFound => write!(formatter, "command macro not found"),
+            Self::InvalidInput => write!(formatter, "invalid command library input"),
+            Self::OperationFailed { source } => {
+                write!(formatter, "command library operation failed: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CommandLibraryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::OperationFailed { source } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<sealantern_infra::persistence::PersistenceError> for CommandLibraryError {
+    fn from(source: sealantern_infra::persistence::PersistenceError) -> Self {
+        Self::OperationFailed { source: Box::new(source) }
+    }
+}
+
+impl From<sealantern_interface::InstanceServiceError> for CommandLibraryError {
+    fn from(source: sealantern_interface::InstanceServiceError) -> Self {
+        match source {
+            sealantern_interface::InstanceServiceError::InstanceNotFound => Self::InstanceNotFound,
+            sealantern_interface::InstanceServiceError::InvalidInput => Self::InvalidInput,
+            _ => Self::OperationFailed { source: Box::new(source) },
+        }
+    }
+}
+
+/// 应用层主错误 → 接口契约错误的收敛转换。
+impl From<CommandLibraryError> for CommandLibraryServiceError {
+    fn from(error: CommandLibraryError) -> Self {
+        match error {
+            CommandLibraryError::InstanceNotFound => Self::InstanceNotFound,
+            CommandLibraryError::MacroNotFound => Self::MacroNotFound,
+            CommandLibraryError::InvalidInput => Self::InvalidInput,
+            CommandLibraryError::OperationFailed { .. } => Self::OperationFailed,
+        }
+    }
+}
@@ -1,22 +1,108 @@
 //! 配置管理领域的主错误。
-//!
-//! 当前占位：待配置服务接入后补充领域错误与契约转换。
 
 use std::fmt;
 
-/// 配置管理操作失败的应用层主错误（占位）。
+use sealantern_extra::config::{ConfigDocumentError, ConfigRevisionError, ServerPropertiesError};
+use sealantern_infra::persistence::PersistenceError;
+use sealantern_interface::{ConfigServiceError, InstanceServiceError};
+
+/// 配置文件读写与修订操作失败的应用层主错误。
 #[derive(Debug)]
 pub enum ConfigError {
-    /// 该能力尚未实现（占位）。
-    Unsupported,
+    /// 指定的实例不存在。
+    InstanceNotFound,
+    /// 请求参数不合法。
+    InvalidInput { reason: String },
+    /// server.properties 读写或校验失败。
+    Properties { source: ServerPropertiesError },
+    /// YAML / TOML / JSON5 配置文档读写失败。
+    Document { source: ConfigDocumentError },
+    /// 修订库读写或恢复失败。
+    Revision { source: ConfigRevisionError },
+    /// 实例查询等其他操作失败。
+    OperationFailed {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
 }
 
 impl fmt::Display for ConfigError {
     fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Unsupported => write!(formatter, "operation not supported"),
+            Self::InstanceNotFound => write!(formatter, "server instance not found"),
+            Self::InvalidInput { reason } => write!(formatter, "invalid config input: {reason}"),
+            Self::Properties { source } => write!(formatter, "{source}"),
+            Self::Document { source } => write!(formatter, "{source}"),
+            Self::Revision { source } => write!(formatter, "{source}"),
+            Self::OperationFailed { source } => {
+                write!(formatter, "config operation failed: {source}")
+            }
         }
     }
 }
 
-impl std::error::Error for ConfigError {}
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Properties { source } => Some(source),
+            Self::Document { source } => Some(source),
+            Self::Revision { source } => Some(source),
+            Self::OperationFailed { source } => Some(source.as_ref()),
+            Self::InstanceNotFound | Self::InvalidInput { .. } => None,
+        }
+    }
+}
+
+impl From<ServerPropertiesError> for ConfigError {
+    fn from(source: ServerPropertiesError) -> Self {
+        Self::Properties { source }
+    }
+}
+
+impl From<ConfigDocumentError> for ConfigError {
+    fn from(source: ConfigDocumentError) -> Self {
+        Self::Document { source }
+    }
+}
+
+impl From<ConfigRevisionError> for ConfigError {
+    fn from(source: ConfigRevisionError) -> Self {
+        Self::Revision { source }
+    }
+}
+
+impl From<PersistenceError> for ConfigError {
+    fn from(source: PersistenceError) -> Self {
+        Self::Revision { source: source.into() }
+    }
+}
+
+impl From<InstanceServiceError> for ConfigError {
+    fn from(source: InstanceServiceError) -> Self {
+        match source {
+            InstanceServiceError::InstanceNotFound => Self::InstanceNotFound,
+            _ => Self::OperationFailed { source: Box::new(source) },
+        }
+    }
+}
+
+/// 应用层主错误 → 接口契约错误的收敛转换。
+impl From<ConfigError> for ConfigServiceError {
+    fn from(error: ConfigError) -> Self {
+        match error {
+            ConfigError::InstanceNotFound => Self::InstanceNotFound,
+            ConfigError::InvalidInput { .. } => Self::InvalidInput,
+            ConfigError::Revision { source } => match source {
+                ConfigRevisionError::NotFound(_) => Self::RevisionNotFound,
+                ConfigRevisionError::InvalidPath(_) => Self::InvalidInput,
+                ConfigRevisionError::Persistence(PersistenceError::InvalidInput { .. }) => {
+                    Self::InvalidInput
+                }
+                ConfigRevisionError::Persistence(_) | ConfigRevisionError::Write(_) => {
+                    Self::StorageFailed
+                }
+            },
+            ConfigError::Properties { .. } | ConfigError::Document { .. } => Self::StorageFailed,
+            ConfigError::OperationFailed { .. } => Self::OperationFailed,
+        }
+    }
+}
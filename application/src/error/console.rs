@@ -58,6 +58,7 @@ impl From<sealantern_interface::InstanceServiceError> for ConsoleError {
             sealantern_interface::InstanceServiceError::InvalidInput => Self::InvalidInput,
             sealantern_interface::InstanceServiceError::InvalidState
             | sealantern_interface::InstanceServiceError::AlreadyExists
+            | sealantern_interface::InstanceServiceError::PortConflict
             | sealantern_interface::InstanceServiceError::SourceUnavailable
             | sealantern_interface::InstanceServiceError::SourceNotDirectory
             | sealantern_interface::InstanceServiceError::SourceAlreadyImported
@@ -20,6 +20,11 @@ pub enum DownloadError {
         /// 底层来源错误。
         source: Box<dyn std::error::Error + Send + Sync>,
     },
+    /// 下载文件的大小或摘要与预期不符。
+    VerificationFailed {
+        /// 底层校验错误（携带预期与实际值）。
+        source: InfraDownloadError,
+    },
     /// 该能力尚未实现（占位）。
     Unsupported,
 }
@@ -32,6 +37,9 @@ impl fmt::Display for DownloadError {
             Self::OperationFailed { source } => {
                 write!(formatter, "download operation failed: {source}")
             }
+            Self::VerificationFailed { source } => {
+                write!(formatter, "download verification failed: {source}")
+            }
             Self::Unsupported => write!(formatter, "operation not supported"),
         }
     }
@@ -41,6 +49,7 @@ impl std::error::Error for DownloadError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
             Self::OperationFailed { source } => Some(source.as_ref()),
+            Self::VerificationFailed { source } => Some(source),
             _ => None,
         }
     }
@@ -48,7 +57,11 @@ impl std::error::Error for DownloadError {
 
 impl From<InfraDownloadError> for DownloadError {
     fn from(source: InfraDownloadError) -> Self {
-        Self::OperationFailed { source: Box::new(source) }
+        if source.is_integrity_failure() {
+            Self::VerificationFailed { source }
+        } else {
+            Self::OperationFailed { source: Box::new(source) }
+        }
     }
 }
 
@@ -61,6 +74,7 @@ impl From<DownloadError> for DownloadServiceError {
             DownloadError::TaskNotFound => Self::TaskNotFound,
             DownloadError::InvalidInput => Self::InvalidInput,
             DownloadError::OperationFailed { .. } => Self::OperationFailed,
+            DownloadError::VerificationFailed { .. } => Self::VerificationFailed,
             DownloadError::Unsupported => Self::Unsupported,
         }
     }
@@ -0,0 +1,88 @@
+//! 扩展启停领域的主错误。
+
+use std::fmt;
+
+use sealantern_extra::server::ExtensionToggleError;
+use sealantern_interface::{ExtensionServiceError, InstanceServiceError, ServerServiceError};
+
+/// 扩展启停失败的应用层主错误。
+///
+/// 携带底层失败细节（source），供应用层日志排查；向
+/// [`ExtensionServiceError`] 转换时收敛为分类。
+#[derive(Debug)]
+pub enum ExtensionError {
+    /// 指定的实例不存在。
+    InstanceNotFound,
+    /// 服务器运行中，且请求未强制执行。
+    ServerRunning,
+    /// 文件校验或移动失败。
+    Toggle { source: ExtensionToggleError },
+    /// 实例查询、状态查询等其他操作失败。
+    OperationFailed {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl fmt::Display for ExtensionError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InstanceNotFound => write!(formatter, "server instance not found"),
+            Self::ServerRunning => write!(formatter, "server is running"),
+            Self::Toggle { source } => write!(formatter, "{source}"),
+            Self::OperationFailed { source } => {
+                write!(formatter, "extension operation failed: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExtensionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Toggle { source } => Some(source),
+            Self::OperationFailed { source } => Some(source.as_ref()),
+            Self::InstanceNotFound | Self::ServerRunning => None,
+        }
+    }
+}
+
+impl From<ExtensionToggleError> for ExtensionError {
+    fn from(source: ExtensionToggleError) -> Self {
+        Self::Toggle { source }
+    }
+}
+
+impl From<InstanceServiceError> for ExtensionError {
+    fn from(source: InstanceServiceError) -> Self {
+        match source {
+            InstanceServiceError::InstanceNotFound => Self::InstanceNotFound,
+            _ => Self::OperationFailed { source: Box::new(source) },
+        }
+    }
+}
+
+impl From<ServerServiceError> for ExtensionError {
+    fn from(source: ServerServiceError) -> Self {
+        match source {
+            ServerServiceError::InstanceNotFound => Self::InstanceNotFound,
+            _ => Self::OperationFailed { source: Box::new(source) },
+        }
+    }
+}
+
+/// 应用层主错误 → 接口契约错误的收敛转换。
+impl From<ExtensionError> for ExtensionServiceError {
+    fn from(error: ExtensionError) -> Self {
+        match error {
+            ExtensionError::InstanceNotFound => Self::InstanceNotFound,
+            ExtensionError::ServerRunning => Self::ServerRunning,
+            ExtensionError::Toggle { source } => match source {
+                ExtensionToggleError::InvalidFileName => Self::InvalidInput,
+                ExtensionToggleError::NotFound => Self::ExtensionNotFound,
+                ExtensionToggleError::TargetExists { .. } => Self::TargetExists,
+                ExtensionToggleError::Io(_) => Self::StorageFailed,
+            },
+            ExtensionError::OperationFailed { .. } => Self::OperationFailed,
+        }
+    }
+}
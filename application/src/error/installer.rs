@@ -0,0 +1,139 @@
+//! 加载器安装器执行领域的主错误。
+
+use std::fmt;
+
+use sealantern_extra::catalog::CatalogError;
+use sealantern_infra::download::DownloadError;
+use sealantern_interface::{InstallerServiceError, InstanceServiceError, ServerServiceError};
+
+/// 加载器安装器执行失败的应用层主错误。
+///
+/// 开始安装时的拒绝向 [`InstallerServiceError`] 转换并返回给宿主；后台执行中的
+/// 失败以 Display 文本记录到任务信息中。
+#[derive(Debug)]
+pub enum InstallerError {
+    /// 指定的实例不存在。
+    InstanceNotFound,
+    /// 核心类型不需要或不支持安装器。
+    UnsupportedCore(String),
+    /// 缺少游戏版本等必要输入。
+    InvalidInput,
+    /// 服务器运行中。
+    ServerRunning,
+    /// 该实例已有未结束的安装任务。
+    AlreadyInstalling,
+    /// 从目录解析安装器失败。
+    Resolve { source: CatalogError },
+    /// 下载安装器失败。
+    Download { source: DownloadError },
+    /// 本机没有可用的 Java。
+    JavaUnavailable,
+    /// 安装器以非零状态退出；被信号终止时没有退出码。
+    InstallerExited { code: Option<i32> },
+    /// 实例查询、进程执行、写回等其他操作失败。
+    OperationFailed {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl fmt::Display for InstallerError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InstanceNotFound => write!(formatter, "server instance not found"),
+            Self::UnsupportedCore(core_type) => {
+                write!(formatter, "core type {core_type} does not use an installer")
+            }
+            Self::InvalidInput => write!(formatter, "a game version is required"),
+            Self::ServerRunning => write!(formatter, "server is running"),
+            Self::AlreadyInstalling => write!(formatter, "an installation is already in progress"),
+            Self::Resolve { source } => write!(formatter, "failed to resolve installer: {source}"),
+            Self::Download { source } => {
+                write!(formatter, "failed to download installer: {source}")
+            }
+            Self::JavaUnavailable => write!(formatter, "no Java installation was found"),
+            Self::InstallerExited { code: Some(code) } => {
+                write!(formatter, "installer exited with code {code}")
+            }
+            Self::InstallerExited { code: None } => {
+                write!(formatter, "installer was terminated")
+            }
+            Self::OperationFailed { source } => {
+                write!(formatter, "installer operation failed: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InstallerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Resolve { source } => Some(source),
+            Self::Download { source } => Some(source),
+            Self::OperationFailed { source } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<CatalogError> for InstallerError {
+    fn from(source: CatalogError) -> Self {
+        Self::Resolve { source }
+    }
+}
+
+impl From<DownloadError> for InstallerError {
+    fn from(source: DownloadError) -> Self {
+        Self::Download { source }
+    }
+}
+
+impl From<std::io::Error> for InstallerError {
+    fn from(source: std::io::Error) -> Self {
+        Self::OperationFailed { source: Box::new(source) }
+    }
+}
+
+impl From<InstanceServiceError> for InstallerError {
+    fn from(source: InstanceServiceError) -> Self {
+        match source {
+            InstanceServiceError::InstanceNotFound => Self::InstanceNotFound,
+            _ => Self::OperationFailed { source: Box::new(source) },
+        }
+    }
+}
+
+impl From<ServerServiceError> for InstallerError {
+    fn from(source: ServerServiceError) -> Self {
+        match source {
+            ServerServiceError::InstanceNotFound => Self::InstanceNotFound,
+            _ => Self::OperationFailed { source: Box::new(source) },
+        }
+    }
+}
+
+impl From<super::InstanceError> for InstallerError {
+    fn from(source: super::InstanceError) -> Self {
+        match source {
+            super::InstanceError::NotFound => Self::InstanceNotFound,
+            _ => Self::OperationFailed { source: Box::new(source) },
+        }
+    }
+}
+
+/// 应用层主错误 → 接口契约错误的收敛转换。
+impl From<InstallerError> for InstallerServiceError {
+    fn from(error: InstallerError) -> Self {
+        match error {
+            InstallerError::InstanceNotFound => Self::InstanceNotFound,
+            InstallerError::UnsupportedCore(_) => Self::UnsupportedCore,
+            InstallerError::InvalidInput => Self::InvalidInput,
+            InstallerError::ServerRunning => Self::ServerRunning,
+            InstallerError::AlreadyInstalling => Self::AlreadyInstalling,
+            InstallerError::Resolve { .. }
+            | InstallerError::Download { .. }
+            | InstallerError::JavaUnavailable
+            | InstallerError::InstallerExited { .. }
+            | InstallerError::OperationFailed { .. } => Self::OperationFailed,
+        }
+    }
+}
@@ -5,6 +5,7 @@ use std::path::PathBuf;
 
 use sealantern_infra::fs::FsError;
 
+use sealantern_core::instance::PortConflict;
 use sealantern_core::provisioning::{
     ExistingInstanceError, ImportExistingServerError as CoreImportError, SourceDirectoryError,
 };
@@ -20,6 +21,11 @@ pub enum InstanceError {
     NotFound,
     /// 目标实例标识已存在（创建冲突）。
     AlreadyExists,
+    /// 实例端口与其他受管实例或主机上的进程冲突。
+    PortConflict {
+        /// 首个冲突端口及其占用方。
+        conflict: PortConflict,
+    },
     /// 实例数据校验失败（由 core 判定，含具体违规项）。
     Invalid {
         /// 底层校验错误细节。
@@ -75,6 +81,7 @@ impl fmt::Display for InstanceError {
         match self {
             Self::NotFound => write!(formatter, "server instance not found"),
             Self::AlreadyExists => write!(formatter, "server instance already exists"),
+            Self::PortConflict { conflict } => write!(formatter, "port conflict: {conflict}"),
             Self::Invalid { source } => write!(formatter, "instance data is invalid: {source}"),
             Self::InvalidState => {
                 write!(formatter, "server instance is in an invalid state for this operation")
@@ -169,6 +176,7 @@ impl From<InstanceError> for InstanceServiceError {
         match error {
             InstanceError::NotFound => Self::InstanceNotFound,
             InstanceError::AlreadyExists => Self::AlreadyExists,
+            InstanceError::PortConflict { .. } => Self::PortConflict,
             InstanceError::Invalid { .. } => Self::InvalidInput,
             InstanceError::InvalidState => Self::InvalidState,
             InstanceError::InvalidInput => Self::InvalidInput,
@@ -0,0 +1,125 @@
+//! 市场资源安装领域的主错误。
+
+use std::fmt;
+
+use sealantern_extra::market::{InstallError, RollbackError};
+use sealantern_infra::fs::FsError;
+use sealantern_interface::{InstanceServiceError, MarketInstallServiceError};
+
+/// 市场资源安装、卸载失败的应用层主错误。
+///
+/// 携带底层失败细节（source），供应用层日志排查；向
+/// [`MarketInstallServiceError`] 转换时收敛为分类。
+#[derive(Debug)]
+pub enum MarketInstallError {
+    /// 指定的实例不存在。
+    InstanceNotFound,
+    /// 实例上没有该资源的安装记录。
+    ResourceNotInstalled,
+    /// 请求参数不合法。
+    InvalidInput { reason: &'static str },
+    /// 仍有其他已安装资源依赖该资源。
+    DependencyInUse { dependents: Vec<String> },
+    /// 规划、下载或校验失败。
+    Install { source: InstallError },
+    /// 更新回滚失败。
+    Rollback { source: RollbackError },
+    /// 资源文件或安装记录读写失败。
+    StorageFailed { source: FsError },
+    /// 实例查询等其他操作失败。
+    OperationFailed {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl fmt::Display for MarketInstallError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InstanceNotFound => write!(formatter, "server instance not found"),
+            Self::ResourceNotInstalled => {
+                write!(formatter, "resource is not installed on this instance")
+            }
+            Self::InvalidInput { reason } => {
+                write!(formatter, "invalid market install input: {reason}")
+            }
+            Self::DependencyInUse { dependents } => {
+                write!(formatter, "resource is required by {}", dependents.join(", "))
+            }
+            Self::Install { source } => write!(formatter, "{source}"),
+            Self::Rollback { source } => write!(formatter, "update rollback failed: {source}"),
+            Self::StorageFailed { source } => {
+                write!(formatter, "market install storage failed: {source}")
+            }
+            Self::OperationFailed { source } => {
+                write!(formatter, "market install operation failed: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for MarketInstallError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Install { source } => Some(source),
+            Self::Rollback { source } => Some(source),
+            Self::StorageFailed { source } => Some(source),
+            Self::OperationFailed { source } => Some(source.as_ref()),
+            Self::InstanceNotFound
+            | Self::ResourceNotInstalled
+            | Self::InvalidInput { .. }
+            | Self::DependencyInUse { .. } => None,
+        }
+    }
+}
+
+impl From<InstallError> for MarketInstallError {
+    fn from(source: InstallError) -> Self {
+        Self::Install { source }
+    }
+}
+
+impl From<RollbackError> for MarketInstallError {
+    fn from(source: RollbackError) -> Self {
+        Self::Rollback { source }
+    }
+}
+
+impl From<FsError> for MarketInstallError {
+    fn from(source: FsError) -> Self {
+        Self::StorageFailed { source }
+    }
+}
+
+impl From<InstanceServiceError> for MarketInstallError {
+    fn from(source: InstanceServiceError) -> Self {
+        match source {
+            InstanceServiceError::InstanceNotFound => Self::InstanceNotFound,
+            _ => Self::OperationFailed { source: Box::new(source) },
+        }
+    }
+}
+
+/// 应用层主错误 → 接口契约错误的收敛转换。
+impl From<MarketInstallError> for MarketInstallServiceError {
+    fn from(error: MarketInstallError) -> Self {
+        match error {
+            MarketInstallError::InstanceNotFound => Self::InstanceNotFound,
+            MarketInstallError::ResourceNotInstalled => Self::ResourceNotInstalled,
+            MarketInstallError::InvalidInput { .. } => Self::InvalidInput,
+            MarketInstallError::DependencyInUse { .. } => Self::DependencyInUse,
+            MarketInstallError::Install { source } => match source {
+                InstallError::UnsupportedInstance => Self::UnsupportedInstance,
+                InstallError::Blocked(_) => Self::PlanBlocked,
+                InstallError::Market(_) => Self::MarketUnavailable,
+                InstallError::Verification { .. } => Self::VerificationFailed,
+                InstallError::Storage(_) => Self::StorageFailed,
+            },
+            MarketInstallError::Rollback { source } => match source {
+                RollbackError::NotFound | RollbackError::BackupMissing => Self::RollbackUnavailable,
+                RollbackError::Storage(_) => Self::StorageFailed,
+            },
+            MarketInstallError::StorageFailed { .. } => Self::StorageFailed,
+            MarketInstallError::OperationFailed { .. } => Self::OperationFailed,
+        }
+    }
+}
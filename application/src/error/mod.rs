@@ -4,6 +4,8 @@
 //! source 细节供日志排查），并可向 `interface::error` 的契约错误转换，供
 //! tauri / server 等宿主统一消费。
 
+/// 命令历史与宏领域错误。
+pub mod command_library;
 /// 配置管理领域错误。
 pub mod config;
 /// 服务器控制台日志领域错误。
@@ -12,8 +14,16 @@ pub mod console;
 pub mod cron;
 /// 下载任务管理领域错误。
 pub mod download;
+/// 扩展启停领域错误。
+pub mod extension;
+/// 加载器安装器执行领域错误。
+pub mod installer;
 /// 实例管理领域错误。
 pub mod instance;
+/// 市场资源安装领域错误。
+pub mod market_install;
+/// 在线玩家领域错误。
+pub mod player;
 /// 插件管理领域错误。
 pub mod plugin;
 /// 服务器管理领域错误。
@@ -22,16 +32,27 @@ pub mod server;
 pub mod settings;
 /// 系统资源信息领域错误。
 pub mod system;
+/// 实例配置模板领域错误。
+pub mod template;
 /// 应用更新检查领域错误。
 pub mod update;
+/// 服务端核心原地升级领域错误。
+pub mod upgrade;
 
+pub use command_library::CommandLibraryError;
 pub use config::ConfigError;
 pub use console::ConsoleError;
 pub use cron::CronTaskError;
 pub use download::DownloadError;
+pub use extension::ExtensionError;
+pub use installer::InstallerError;
 pub use instance::InstanceError;
+pub use market_install::MarketInstallError;
+pub use player::PlayerError;
 pub use plugin::PluginError;
 pub use server::ServerError;
 pub use settings::SettingsError;
 pub use system::SystemError;
+pub use template::InstanceTemplateError;
 pub use update::UpdateCheckError;
+pub use upgrade::ServerUpgradeError;
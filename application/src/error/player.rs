@@ -0,0 +1,70 @@
+//! 在线玩家领域的主错误。
+
+use std::fmt;
+
+use sealantern_interface::error::PlayerServiceError;
+
+/// 在线玩家查询失败的应用层主错误。
+///
+/// 携带底层失败细节（source），供应用层日志排查；向
+/// [`PlayerServiceError`] 转换时收敛为分类，不向宿主泄漏敏感信息。
+#[derive(Debug)]
+pub enum PlayerError {
+    /// 指定的实例不存在（无法定位会话库）。
+    InstanceNotFound,
+    /// 客户端提供的输入不合法（如玩家名非法、窗口非正）。
+    InvalidInput,
+    /// 底层会话数据库或实例查询失败。
+    OperationFailed {
+        /// 底层来源错误。
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl fmt::Display for PlayerError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InstanceNotFound => write!(formatter, "server instance not found"),
+            Self::InvalidInput => write!(formatter, "invalid player query input"),
+            Self::OperationFailed { source } => {
+                write!(formatter, "player tracking operation failed: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PlayerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::OperationFailed { source } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<sealantern_infra::persistence::PersistenceError> for PlayerError {
+    fn from(source: sealantern_infra::persistence::PersistenceError) -> Self {
+        Self::OperationFailed { source: Box::new(source) }
+    }
+}
+
+impl From<sealantern_interface::InstanceServiceError> for PlayerError {
+    fn from(source: sealantern_interface::InstanceServiceError) -> Self {
+        match source {
+            sealantern_interface::InstanceServiceError::InstanceNotFound => Self::InstanceNotFound,
+            sealantern_interface::InstanceServiceError::InvalidInput => Self::InvalidInput,
+            _ => Self::OperationFailed { source: Box::new(source) },
+        }
+    }
+}
+
+/// 应用层主错误 → 接口契约错误的收敛转换。
+impl From<PlayerError> for PlayerServiceError {
+    fn from(error: PlayerError) -> Self {
+        match error {
+            PlayerError::InstanceNotFound => Self::InstanceNotFound,
+            PlayerError::InvalidInput => Self::InvalidInput,
+            PlayerError::OperationFailed { .. } => Self::OperationFailed,
+        }
+    }
+}
@@ -16,6 +16,8 @@ pub enum ServerError {
     InvalidState,
     /// 客户端提供的输入不合法。
     InvalidInput,
+    /// 命令（或宏展开后的某一行）被插件命令过滤规则拒绝。
+    CommandRejected,
     /// 底层进程 / IO 操作失败。
     OperationFailed {
         /// 底层来源错误。
@@ -38,6 +40,7 @@ impl fmt::Display for ServerError {
                 write!(formatter, "server is in an invalid state for this operation")
             }
             Self::InvalidInput => write!(formatter, "invalid input"),
+            Self::CommandRejected => write!(formatter, "command rejected by command filter"),
             Self::OperationFailed { source } => {
                 write!(formatter, "server operation failed: {source}")
             }
@@ -78,6 +81,7 @@ impl From<ServerError> for ServerServiceError {
             ServerError::InstanceNotFound => Self::InstanceNotFound,
             ServerError::InvalidState => Self::InvalidState,
             ServerError::InvalidInput => Self::InvalidInput,
+            ServerError::CommandRejected => Self::CommandRejected,
             ServerError::OperationFailed { .. } | ServerError::Internal { .. } => {
                 Self::OperationFailed
             }
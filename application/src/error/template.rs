@@ -0,0 +1,169 @@
+//! 实例配置模板领域的主错误。
+
+use std::fmt;
+
+use sealantern_core::instance::{InstanceError as CoreInstanceError, TemplateError};
+use sealantern_extra::backup::BackupError;
+use sealantern_extra::config::{
+    ConfigDocumentError, InstanceTemplateStoreError, ServerPropertiesError,
+};
+use sealantern_infra::fs::FsError;
+use sealantern_interface::{InstanceServiceError, InstanceTemplateServiceError};
+
+/// 实例配置模板操作失败的应用层主错误。
+///
+/// 携带底层失败细节（source），供应用层日志排查；向
+/// [`InstanceTemplateServiceError`] 转换时收敛为分类。
+#[derive(Debug)]
+pub enum InstanceTemplateError {
+    /// 指定模板不存在。
+    TemplateNotFound,
+    /// 指定实例不存在。
+    InstanceNotFound,
+    /// 模板内容或请求参数不合法。
+    InvalidTemplate { source: TemplateError },
+    /// 套用后的实例记录未通过校验（如模板内存与实例现有设置冲突）。
+    InvalidInstance { source: CoreInstanceError },
+    /// 请求参数或模板值不合法（非模板结构问题）。
+    InvalidInput { reason: String },
+    /// 端口策略无法分配可用端口。
+    PortConflict { source: TemplateError },
+    /// 配置文件读取、预览或写入失败。
+    ConfigFailed {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+    /// 模板 JSON 持久化失败。
+    StorageFailed { source: InstanceTemplateStoreError },
+    /// 实例记录、定时任务或备份设置等其他操作失败。
+    OperationFailed {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl fmt::Display for InstanceTemplateError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TemplateNotFound => write!(formatter, "instance template not found"),
+            Self::InstanceNotFound => write!(formatter, "server instance not found"),
+            Self::InvalidTemplate { source } => write!(formatter, "{source}"),
+            Self::InvalidInstance { source } => {
+                write!(formatter, "template conflicts with instance settings: {source}")
+            }
+            Self::InvalidInput { reason } => {
+                write!(formatter, "invalid instance template input: {reason}")
+            }
+            Self::PortConflict { source } => write!(formatter, "{source}"),
+            Self::ConfigFailed { source } => {
+                write!(formatter, "instance template config update failed: {source}")
+            }
+            Self::StorageFailed { source } => write!(formatter, "{source}"),
+            Self::OperationFailed { source } => {
+                write!(formatter, "instance template operation failed: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for InstanceTemplateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidTemplate { source } | Self::PortConflict { source } => Some(source),
+            Self::InvalidInstance { source } => Some(source),
+            Self::ConfigFailed { source } | Self::OperationFailed { source } => {
+                Some(source.as_ref())
+            }
+            Self::StorageFailed { source } => Some(source),
+            Self::TemplateNotFound | Self::InstanceNotFound | Self::InvalidInput { .. } => None,
+        }
+    }
+}
+
+impl From<TemplateError> for InstanceTemplateError {
+    fn from(source: TemplateError) -> Self {
+        match source {
+            TemplateError::FixedPortShared { .. }
+            | TemplateError::PortInUse { .. }
+            | TemplateError::PortsExhausted { .. } => Self::PortConflict { source },
+            _ => Self::InvalidTemplate { source },
+        }
+    }
+}
+
+impl From<CoreInstanceError> for InstanceTemplateError {
+    fn from(source: CoreInstanceError) -> Self {
+        Self::InvalidInstance { source }
+    }
+}
+
+impl From<InstanceTemplateStoreError> for InstanceTemplateError {
+    fn from(source: InstanceTemplateStoreError) -> Self {
+        match source {
+            InstanceTemplateStoreError::NotFound(_) => Self::TemplateNotFound,
+            InstanceTemplateStoreError::Invalid(source) => source.into(),
+            InstanceTemplateStoreError::Storage(_) => Self::StorageFailed { source },
+        }
+    }
+}
+
+impl From<ServerPropertiesError> for InstanceTemplateError {
+    fn from(source: ServerPropertiesError) -> Self {
+        match source {
+            ServerPropertiesError::InvalidValue { reason, .. } => Self::InvalidInput { reason },
+            source => Self::ConfigFailed { source: Box::new(source) },
+        }
+    }
+}
+
+impl From<ConfigDocumentError> for InstanceTemplateError {
+    fn from(source: ConfigDocumentError) -> Self {
+        Self::ConfigFailed { source: Box::new(source) }
+    }
+}
+
+impl From<FsError> for InstanceTemplateError {
+    fn from(source: FsError) -> Self {
+        Self::ConfigFailed { source: Box::new(source) }
+    }
+}
+
+impl From<BackupError> for InstanceTemplateError {
+    fn from(source: BackupError) -> Self {
+        Self::OperationFailed { source: Box::new(source) }
+    }
+}
+
+impl From<crate::error::InstanceError> for InstanceTemplateError {
+    fn from(source: crate::error::InstanceError) -> Self {
+        match source {
+            crate::error::InstanceError::NotFound => Self::InstanceNotFound,
+            crate::error::InstanceError::Invalid { source } => Self::InvalidInstance { source },
+            source => Self::OperationFailed { source: Box::new(source) },
+        }
+    }
+}
+
+impl From<InstanceServiceError> for InstanceTemplateError {
+    fn from(source: InstanceServiceError) -> Self {
+        match source {
+            InstanceServiceError::InstanceNotFound => Self::InstanceNotFound,
+            _ => Self::OperationFailed { source: Box::new(source) },
+        }
+    }
+}
+
+/// 应用层主错误 → 接口契约错误的收敛转换。
+impl From<InstanceTemplateError> for InstanceTemplateServiceError {
+    fn from(error: InstanceTemplateError) -> Self {
+        match error {
+            InstanceTemplateError::TemplateNotFound => Self::TemplateNotFound,
+            InstanceTemplateError::InstanceNotFound => Self::InstanceNotFound,
+            InstanceTemplateError::InvalidTemplate { .. }
+            | InstanceTemplateError::InvalidInstance { .. }
+            | InstanceTemplateError::InvalidInput { .. } => Self::InvalidInput,
+            InstanceTemplateError::PortConflict { .. } => Self::PortConflict,
+            InstanceTemplateError::ConfigFailed { .. } => Self::ConfigFailed,
+            InstanceTemplateError::StorageFailed { .. } => Self::StorageFailed,
+            InstanceTemplateError::OperationFailed { .. } => Self::OperationFailed,
+        }
+    }
+}
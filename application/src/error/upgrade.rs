@@ -0,0 +1,169 @@
+//! 服务端核心原地升级领域的主错误。
+
+use std::fmt;
+
+use sealantern_extra::backup::BackupError;
+use sealantern_extra::catalog::CatalogError;
+use sealantern_infra::download::DownloadError;
+use sealantern_infra::fs::FsError;
+use sealantern_interface::{InstanceServiceError, ServerServiceError, ServerUpgradeServiceError};
+
+/// 服务端核心原地升级失败的应用层主错误。
+#[derive(Debug)]
+pub enum ServerUpgradeError {
+    /// 指定的实例不存在。
+    InstanceNotFound,
+    /// 请求参数不合法（如实例没有记录游戏版本且请求未指定）。
+    InvalidInput,
+    /// 目标构建是安装器，需由安装器服务处理。
+    InstallerCore(String),
+    /// 实例不是以 jar 直接启动，无法确定要替换的服务端文件。
+    UnsupportedLaunch,
+    /// 服务器运行中。
+    ServerRunning,
+    /// 从目录解析目标构建失败。
+    Catalog { source: CatalogError },
+    /// 实例使用的 Java 不满足目标构建的要求。
+    JavaRequirementUnmet { required: u16 },
+    /// 升级前的自动备份失败。
+    Backup { source: BackupError },
+    /// 下载目标构建失败。
+    Download { source: DownloadError },
+    /// 下载文件的大小或摘要与目录不符（来自共享的下载完整性校验）。
+    Verification { source: DownloadError },
+    /// 目录给出的构建文件名不可用。
+    InvalidFileName(String),
+    /// 实例查询、文件替换、写回等其他操作失败。
+    OperationFailed {
+        source: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl fmt::Display for ServerUpgradeError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InstanceNotFound => write!(formatter, "server instance not found"),
+            Self::InvalidInput => write!(formatter, "a game version is required"),
+            Self::InstallerCore(core_type) => {
+                write!(
+                    formatter,
+                    "{core_type} builds are installers and cannot replace the server file"
+                )
+            }
+            Self::UnsupportedLaunch => {
+                write!(formatter, "only instances launched from a server jar can be upgraded")
+            }
+            Self::ServerRunning => write!(formatter, "server is running"),
+            Self::Catalog { source } => {
+                write!(formatter, "failed to resolve target build: {source}")
+            }
+            Self::JavaRequirementUnmet { required } => {
+                write!(formatter, "target build requires Java {required} or newer")
+            }
+            Self::Backup { source } => write!(formatter, "backup before upgrade failed: {source}"),
+            Self::Download { source } => {
+                write!(formatter, "failed to download target build: {source}")
+            }
+            Self::Verification { source } => {
+                write!(formatter, "downloaded build failed verification: {source}")
+            }
+            Self::InvalidFileName(file_name) => {
+                write!(formatter, "target build has an invalid file name {file_name}")
+            }
+            Self::OperationFailed { source } => {
+                write!(formatter, "server upgrade operation failed: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ServerUpgradeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Catalog { source } => Some(source),
+            Self::Backup { source } => Some(source),
+            Self::Download { source } | Self::Verification { source } => Some(source),
+            Self::OperationFailed { source } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl From<CatalogError> for ServerUpgradeError {
+    fn from(source: CatalogError) -> Self {
+        Self::Catalog { source }
+    }
+}
+
+impl From<BackupError> for ServerUpgradeError {
+    fn from(source: BackupError) -> Self {
+        Self::Backup { source }
+    }
+}
+
+impl From<DownloadError> for ServerUpgradeError {
+    fn from(source: DownloadError) -> Self {
+        if source.is_integrity_failure() {
+            Self::Verification { source }
+        } else {
+            Self::Download { source }
+        }
+    }
+}
+
+impl From<FsError> for ServerUpgradeError {
+    fn from(source: FsError) -> Self {
+        Self::OperationFailed { source: Box::new(source) }
+    }
+}
+
+impl From<InstanceServiceError> for ServerUpgradeError {
+    fn from(source: InstanceServiceError) -> Self {
+        match source {
+            InstanceServiceError::InstanceNotFound => Self::InstanceNotFound,
+            _ => Self::OperationFailed { source: Box::new(source) },
+        }
+    }
+}
+
+impl From<ServerServiceError> for ServerUpgradeError {
+    fn from(source: ServerServiceError) -> Self {
+        match source {
+            ServerServiceError::InstanceNotFound => Self::InstanceNotFound,
+            _ => Self::OperationFailed { source: Box::new(source) },
+        }
+    }
+}
+
+impl From<super::InstanceError> for ServerUpgradeError {
+    fn from(source: super::InstanceError) -> Self {
+        match source {
+            super::InstanceError::NotFound => Self::InstanceNotFound,
+            _ => Self::OperationFailed { source: Box::new(source) },
+        }
+    }
+}
+
+/// 应用层主错误 → 接口契约错误的收敛转换。
+impl From<ServerUpgradeError> for ServerUpgradeServiceError {
+    fn from(error: ServerUpgradeError) -> Self {
+        match error {
+            ServerUpgradeError::InstanceNotFound => Self::InstanceNotFound,
+            ServerUpgradeError::InvalidInput => Self::InvalidInput,
+            ServerUpgradeError::InstallerCore(_) | ServerUpgradeError::UnsupportedLaunch => {
+                Self::UnsupportedCore
+            }
+            ServerUpgradeError::ServerRunning => Self::ServerRunning,
+            ServerUpgradeError::Catalog { source: CatalogError::NotFound(_) } => {
+                Self::BuildNotFound
+            }
+            ServerUpgradeError::Catalog { .. } => Self::CatalogUnavailable,
+            ServerUpgradeError::JavaRequirementUnmet { .. } => Self::JavaRequirementUnmet,
+            ServerUpgradeError::Backup { .. } => Self::BackupFailed,
+            ServerUpgradeError::Download { .. }
+            | ServerUpgradeError::Verification { .. }
+            | ServerUpgradeError::InvalidFileName(_) => Self::DownloadFailed,
+            ServerUpgradeError::OperationFailed { .. } => Self::OperationFailed,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -8,19 +8,22 @@ use sealantern_core::app_plugin::{
     CapabilityDispatchError, CapabilityDispatcher, CapabilityInvocation, ExecutionPrincipal,
     PolicyDecision, ScopeKind, capability,
 };
+use sealantern_extra::config::DocumentPatch;
 use sealantern_extra::market::{
     Fetcher, MarketError, MarketSource, ResourceInfo, SearchResult, Version,
 };
 use sealantern_infra::net::{
-    NetworkOrigin, PluginHttpMethod, PluginNetworkAddressPolicy, PluginNetworkExecutor,
+    NetClient, NetworkOrigin, PluginHttpMethod, PluginNetworkAddressPolicy, PluginNetworkExecutor,
     PluginNetworkLimits, PluginNetworkRequest, PluginNetworkScope,
 };
+use sealantern_interface::server::{CaptureOptions, CommandOrigin};
 use sealantern_interface::{InstanceService, ServerService, SystemService};
 use serde::Deserialize;
 use serde_json::Value;
 use tokio::sync::Mutex;
 
 use super::PluginPolicyStore;
+use crate::service::{CoreConfigService, CoreSettingsService};
 
 const MARKET_PAGE_SIZE_LIMIT: u32 = 100;
 const MAX_PLUGIN_NETWORK_IN_FLIGHT: usize = 8;
@@ -44,24 +47,48 @@ pub trait MarketGateway: Send + Sync {
 
 /// 基于既有市场抓取器的只读网关。
 pub struct DefaultMarketGateway {
+    client: NetClient,
     modrinth: Arc<dyn Fetcher>,
     spiget: Arc<dyn Fetcher>,
+    hangar: Arc<dyn Fetcher>,
+    /// 提供 CurseForge API 密钥；未关联时 CurseForge 请求返回配置错误。
+    settings: Option<Arc<CoreSettingsService>>,
 }
 
 impl DefaultMarketGateway {
     pub fn new() -> Result<Self, String> {
-        let client = sealantern_infra::net::NetClient::from_config(&Default::default())
-            .map_err(|error| error.to_string())?;
+        let client =
+            NetClient::from_config(&Default::default()).map_err(|error| error.to_string())?;
         Ok(Self {
             modrinth: Arc::new(sealantern_extra::market::ModrinthFetcher::new(client.clone())),
-            spiget: Arc::new(sealantern_extra::market::SpigetFetcher::new(client)),
+            spiget: Arc::new(sealantern_extra::market::SpigetFetcher::new(client.clone())),
+            hangar: Arc::new(sealantern_extra::market::HangarFetcher::new(client.clone())),
+            client,
+            settings: None,
         })
     }
 
-    fn fetcher(&self, source: MarketSource) -> &Arc<dyn Fetcher> {
+    /// CurseForge 请求使用设置中当前配置的 API 密钥。
+    pub fn with_settings(mut self, settings: Arc<CoreSettingsService>) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    async fn fetcher(&self, source: MarketSource) -> Arc<dyn Fetcher> {
         match source {
-            MarketSource::Modrinth => &self.modrinth,
-            MarketSource::Spiget => &self.spiget,
+            MarketSource::Modrinth => self.modrinth.clone(),
+            MarketSource::Spiget => self.spiget.clone(),
+            MarketSource::CurseForge => {
+                let api_key = match &self.settings {
+                    Some(settings) => settings.curseforge_api_key().await,
+                    None => None,
+                };
+                Arc::new(sealantern_extra::market::CurseForgeFetcher::new(
+                    self.client.clone(),
+                    api_key,
+                ))
+            }
+            MarketSource::Hangar => self.hangar.clone(),
         }
     }
 }
@@ -75,15 +102,18 @@ impl MarketGateway for DefaultMarketGateway {
         page: u32,
         page_size: u32,
     ) -> Result<SearchResult, MarketError> {
-        self.fetcher(source).search(query, page, page_size).await
+        self.fetcher(source)
+            .await
+            .search(query, page, page_size)
+            .await
     }
 
     async fn resource(&self, source: MarketSource, id: &str) -> Result<ResourceInfo, MarketError> {
-        self.fetcher(source).get_resource(id).await
+        self.fetcher(source).await.get_resource(id).await
     }
 
     async fn versions(&self, source: MarketSource, id: &str) -> Result<Vec<Version>, MarketError> {
-        self.fetcher(source).get_resource_versions(id).await
+        self.fetcher(source).await.get_resource_versions(id).await
     }
 }
 
@@ -237,10 +267,15 @@ impl CoreCapabilityDispatcher {
                     .get("command")
                     .and_then(Value::as_str)
                     .ok_or(CapabilityDispatchError::InvalidRequest("server console command"))?;
-                host.server_console(id, command).await
+                host.server_console(id, command, console_capture_options(payload)?)
+                    .await
             }
             "server.config.patch" => {
-                Err(CapabilityDispatchError::Unavailable("server config patch is not implemented"))
+                let id = server_scope(scope)?;
+                let patch = serde_json::from_value(payload.clone()).map_err(|_| {
+                    CapabilityDispatchError::InvalidRequest("server config patch payload")
+                })?;
+                host.server_config_patch(plugin_id, id, patch).await
             }
             "server.file.metadata"
             | "server.file.read"
@@ -387,10 +422,12 @@ pub trait PluginReadHost: Send + Sync {
         capability_id: &str,
         instance_id: &str,
     ) -> Result<Value, CapabilityDispatchError>;
+    /// 发送控制台命令并返回其后捕获到的输出。
     async fn server_console(
         &self,
         instance_id: &str,
         command: &str,
+        options: CaptureOptions,
     ) -> Result<Value, CapabilityDispatchError>;
     async fn scoped_file(
         &self,
@@ -399,6 +436,26 @@ pub trait PluginReadHost: Send + Sync {
         scope: Option<&sealantern_core::app_plugin::ScopeBinding>,
         relative_path: &Path,
     ) -> Result<Value, CapabilityDispatchError>;
+    /// 以插件身份修改实例配置，修订归属该插件。
+    async fn server_config_patch(
+        &self,
+        plugin_id: &str,
+        instance_id: &str,
+        patch: ServerConfigPatch,
+    ) -> Result<Value, CapabilityDispatchError>;
+}
+
+/// `server.config.patch` 的载荷：按键合并 server.properties，或按键路径修改配置文档。
+#[derive(Debug, Deserialize)]
+#[serde(untagged, deny_unknown_fields)]
+pub enum ServerConfigPatch {
+    Properties {
+        values: BTreeMap<String, String>,
+    },
+    Document {
+        path: String,
+        patches: Vec<DocumentPatch>,
+    },
 }
 
 /// 基于既有应用服务的只读宿主适配器。
@@ -406,6 +463,7 @@ pub struct ApplicationPluginReadHost {
     system: Arc<dyn SystemService>,
     instance: Arc<dyn InstanceService>,
     server: Arc<dyn ServerService>,
+    config: Arc<CoreConfigService>,
     plugin_root: PathBuf,
 }
 
@@ -414,12 +472,14 @@ impl ApplicationPluginReadHost {
         system: Arc<dyn SystemService>,
         instance: Arc<dyn InstanceService>,
         server: Arc<dyn ServerService>,
+        config: Arc<CoreConfigService>,
         plugin_root: impl Into<PathBuf>,
     ) -> Self {
         Self {
             system,
             instance,
             server,
+            config,
             plugin_root: plugin_root.into(),
         }
     }
@@ -513,20 +573,94 @@ impl PluginReadHost for ApplicationPluginReadHost {
         &self,
         instance_id: &str,
         command: &str,
+        options: CaptureOptions,
     ) -> Result<Value, CapabilityDispatchError> {
         if command.trim().is_empty() || command.len() > MAX_SERVER_CONSOLE_COMMAND_BYTES {
             return Err(CapabilityDispatchError::InvalidRequest("server console command"));
         }
         let id = sealantern_core::instance::InstanceId::new(instance_id)
             .map_err(|_| CapabilityDispatchError::InvalidRequest("server instance id"))?;
-        self.server
-            .send_command(&id, command)
+        let capture = self
+            .server
+            .send_command_and_capture(&id, command, options)
+            .await
+            .map_err(|error| host_error("server console", error))?;
+        serde_json::to_value(capture)
+            .map_err(|_| CapabilityDispatchError::Failed("host response encoding"))
+    }
+
+    async fn server_config_patch(
+        &self,
+        plugin_id: &str,
+        instance_id: &str,
+        patch: ServerConfigPatch,
+    ) -> Result<Value, CapabilityDispatchError> {
+        let id = sealantern_core::instance::InstanceId::new(instance_id)
+            .map_err(|_| CapabilityDispatchError::InvalidRequest("server instance id"))?;
+        let instance = self
+            .instance
+            .find(&id)
             .await
-            .map(|_| Value::Null)
-            .map_err(|error| host_error("server console", error))
+            .map_err(|error| host_error("instance lookup", error))?
+            .ok_or(CapabilityDispatchError::Unavailable("server instance not found"))?;
+        let principal = ExecutionPrincipal::Plugin(plugin_id.to_owned());
+        write_server_config(
+            &self.config,
+            &principal,
+            &instance.directory,
+            instance.game_version,
+            patch,
+        )
+        .await
     }
 }
 
+/// 以 `principal` 写入服务器目录下的配置，返回文档修改预览（server.properties 返回 `null`）。
+async fn write_server_config(
+    config: &CoreConfigService,
+    principal: &ExecutionPrincipal,
+    directory: &Path,
+    game_version: String,
+    patch: ServerConfigPatch,
+) -> Result<Value, CapabilityDispatchError> {
+    match patch {
+        ServerConfigPatch::Properties { values } => config
+            .write_server_properties(directory, values, Some(game_version), principal)
+            .await
+            .map(|()| Value::Null)
+            .map_err(|error| host_error("server config patch", error)),
+        ServerConfigPatch::Document { path, patches } => {
+            let preview = config
+                .write_document(directory, path, patches, principal)
+                .await
+                .map_err(|error| host_error("server config patch", error))?;
+            serde_json::to_value(preview)
+                .map_err(|_| CapabilityDispatchError::Failed("host response encoding"))
+        }
+    }
+}
+
+/// 读取 `server.console.send` 载荷中可选的输出捕获参数（`timeoutMs` / `quietMs` / `until`）。
+///
+/// 来源固定为插件，使宏展开后的每一行都经过插件命令过滤。
+fn console_capture_options(payload: &Value) -> Result<CaptureOptions, CapabilityDispatchError> {
+    let invalid = || CapabilityDispatchError::InvalidRequest("server console capture options");
+    let millis = |key: &str| match payload.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => value.as_u64().map(Some).ok_or_else(invalid),
+    };
+    let until = match payload.get("until") {
+        None | Some(Value::Null) => None,
+        Some(value) => Some(value.as_str().ok_or_else(invalid)?.to_owned()),
+    };
+    Ok(CaptureOptions {
+        timeout_ms: millis("timeoutMs")?,
+        quiet_ms: millis("quietMs")?,
+        until,
+        origin: CommandOrigin::Plugin,
+    })
+}
+
 fn plugin_bundle_root(
     plugin_root: &Path,
     plugin_id: &str,
@@ -620,6 +754,8 @@ fn market_scope(
     let source = match source {
         "modrinth" => MarketSource::Modrinth,
         "spiget" => MarketSource::Spiget,
+        "curseforge" => MarketSource::CurseForge,
+        "hangar" => MarketSource::Hangar,
         _ => return Err(CapabilityDispatchError::InvalidRequest("market source")),
     };
     (!id.is_empty())
@@ -701,7 +837,12 @@ mod tests {
             Ok(Value::Null)
         }
 
-        async fn server_console(&self, _: &str, _: &str) -> Result<Value, CapabilityDispatchError> {
+        async fn server_console(
+            &self,
+            _: &str,
+            _: &str,
+            _: CaptureOptions,
+        ) -> Result<Value, CapabilityDispatchError> {
             Err(CapabilityDispatchError::Unavailable("not used"))
         }
 
@@ -714,6 +855,15 @@ mod tests {
         ) -> Result<Value, CapabilityDispatchError> {
             Err(CapabilityDispatchError::Unavailable("not used"))
         }
+
+        async fn server_config_patch(
+            &self,
+            _: &str,
+            _: &str,
+            _: ServerConfigPatch,
+        ) -> Result<Value, CapabilityDispatchError> {
+            Err(CapabilityDispatchError::Unavailable("not used"))
+        }
     }
 
     #[async_trait]
@@ -893,6 +1043,42 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn server_config_patch_records_revisions_under_the_plugin() {
+        let root = tempfile::tempdir().unwrap();
+        let server_dir = root.path().join("server");
+        tokio::fs::create_dir_all(&server_dir).await.unwrap();
+        tokio::fs::write(server_dir.join("server.properties"), "motd=original")
+            .await
+            .unwrap();
+        let instance = Arc::new(
+            crate::service::CoreInstanceService::with_path(root.path().join("instances.json"))
+                .await
+                .unwrap(),
+        );
+        let config = CoreConfigService::new(instance);
+        let patch =
+            serde_json::from_value(serde_json::json!({"values": {"motd": "from plugin"}})).unwrap();
+        let principal = ExecutionPrincipal::Plugin("example.plugin".to_string());
+
+        write_server_config(&config, &principal, &server_dir, "1.20.4".to_string(), patch)
+            .await
+            .unwrap();
+
+        let revisions = config
+            .list_revisions_in(&server_dir, Some("server.properties"), None)
+            .await
+            .unwrap();
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].principal_kind, "plugin");
+        assert_eq!(revisions[0].principal_id, "example.plugin");
+        assert!(revisions[0].diff.contains("+motd=from plugin"));
+        assert!(
+            serde_json::from_value::<ServerConfigPatch>(serde_json::json!({"values": {}, "x": 1}))
+                .is_err()
+        );
+    }
+
     #[test]
     fn plugin_bundle_scope_is_bound_to_the_calling_plugin() {
         let root = Path::new("plugins");
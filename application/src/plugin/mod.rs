@@ -6,7 +6,7 @@ mod service;
 
 pub use dispatcher::{
     ApplicationPluginReadHost, CoreCapabilityDispatcher, DefaultMarketGateway, MarketGateway,
-    PluginReadHost,
+    PluginReadHost, ServerConfigPatch,
 };
 pub use policy::{AuditEntry, PluginPolicyError, PluginPolicyStore, SessionApproval, SessionGrant};
 pub use service::{CorePluginService, PluginService, PluginServiceError};
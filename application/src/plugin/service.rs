@@ -12,6 +12,7 @@ use super::{
     CoreCapabilityDispatcher, DefaultMarketGateway, PluginPolicyError, PluginPolicyStore,
     PluginReadHost,
 };
+use crate::service::CoreSettingsService;
 
 /// 应用插件生命周期的宿主入口。
 #[async_trait]
@@ -91,19 +92,23 @@ impl CorePluginService {
         data_dir: impl Into<PathBuf>,
         state_path: impl Into<PathBuf>,
     ) -> Result<Self, PluginServiceError> {
-        Self::open_with_read_host(plugins_dir, data_dir, state_path, None).await
+        Self::open_with_read_host(plugins_dir, data_dir, state_path, None, None).await
     }
 
-    /// 使用明确的只读宿主能力构造服务。
+    /// 使用明确的只读宿主能力构造服务；`settings` 为市场网关提供 CurseForge API 密钥。
     pub async fn open_with_read_host(
         plugins_dir: impl Into<PathBuf>,
         data_dir: impl Into<PathBuf>,
         state_path: impl Into<PathBuf>,
         read_host: Option<Arc<dyn PluginReadHost>>,
+        settings: Option<Arc<CoreSettingsService>>,
     ) -> Result<Self, PluginServiceError> {
         let policy = Arc::new(PluginPolicyStore::open(state_path).await?);
-        let market =
-            Arc::new(DefaultMarketGateway::new().map_err(PluginServiceError::Initialization)?);
+        let market = DefaultMarketGateway::new().map_err(PluginServiceError::Initialization)?;
+        let market = Arc::new(match settings {
+            Some(settings) => market.with_settings(settings),
+            None => market,
+        });
         let dispatcher = CoreCapabilityDispatcher::new(policy.clone(), market);
         let dispatcher = match read_host {
             Some(host) => dispatcher.with_read_host(host),
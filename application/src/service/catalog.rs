@@ -1,76 +1,114 @@
 //! 服务器目录服务实现。
 //!
 //! 实现 [`sealantern_interface::ServerCatalogService`] 能力端口，组合
-//! `extra` 的下载链接管理能力（[`LinkManager`]），向宿主提供可用的
-//! 服务器类型、版本与下载详情查询。
+//! `extra` 的服务器核心目录（[`CachedCatalog`]）：优先查询各核心的上游发布渠道，
+//! 上游不可用时回退到镜像下载链接，结果缓存到磁盘；向宿主提供可用的服务器类型、
+//! 版本、构建与下载详情查询，以及显式刷新。
 //!
-//! 错误分层：配置拉取 / 解析失败统一收敛为
-//! [`ServerCatalogServiceError::OperationFailed`]；指定的类型不存在
+//! 错误分层：上游与镜像均不可用且没有缓存副本时统一收敛为
+//! [`ServerCatalogServiceError::OperationFailed`]；指定的类型、版本不存在
 //! 收敛为 [`ServerCatalogServiceError::NotFound`]。
 
 use async_trait::async_trait;
-use sealantern_extra::download_link::{DownloadLink, LinkError, LinkManager};
+use sealantern_extra::catalog::{CachedCatalog, CatalogBuild, CatalogError, CatalogSnapshot};
+use sealantern_extra::download_link::DownloadLink;
 use sealantern_interface::{ServerCatalogService, ServerCatalogServiceError};
 
-/// 基于 `extra` 下载链接管理的服务器目录服务实现。
-#[derive(Debug, Default)]
-pub struct CoreServerCatalogService;
+/// 基于 `extra` 服务器核心目录的服务器目录服务实现。
+pub struct CoreServerCatalogService {
+    catalog: CachedCatalog,
+}
+
+impl CoreServerCatalogService {
+    /// 使用指定目录构造服务（便于测试注入提供方）。
+    pub fn new(catalog: CachedCatalog) -> Self {
+        Self { catalog }
+    }
+}
+
+impl Default for CoreServerCatalogService {
+    /// 使用全部上游提供方、镜像回退与默认缓存目录构造服务。
+    fn default() -> Self {
+        Self::new(CachedCatalog::global())
+    }
+}
 
 #[async_trait]
 impl ServerCatalogService for CoreServerCatalogService {
     /// 查询可用的服务器类型列表。
     ///
-    /// 类型列表来自远程下载链接配置，配置拉取 / 解析失败时收敛为
+    /// 上游类型在前，镜像独有的类型在后；全部来源不可用且没有缓存副本时收敛为
     /// [`ServerCatalogServiceError::OperationFailed`]。
-    async fn server_types(&self) -> Result<Vec<String>, ServerCatalogServiceError> {
-        LinkManager::get_server_types()
-            .await
-            .map_err(|_| ServerCatalogServiceError::OperationFailed)
+    async fn server_types(
+        &self,
+    ) -> Result<CatalogSnapshot<Vec<String>>, ServerCatalogServiceError> {
+        self.catalog.server_types().await.map_err(map_catalog_error)
     }
     /// 查询指定服务器类型支持的版本列表。
     ///
     /// 类型不存在时收敛为 [`ServerCatalogServiceError::NotFound`]；
-    /// 底层配置拉取 / 解析失败收敛为
+    /// 上游与镜像均不可用且没有缓存副本时收敛为
     /// [`ServerCatalogServiceError::OperationFailed`]。
     async fn versions(
         &self,
         server_type: String,
-    ) -> Result<Vec<String>, ServerCatalogServiceError> {
-        LinkManager::get_versions_by_type(&server_type)
+    ) -> Result<CatalogSnapshot<Vec<String>>, ServerCatalogServiceError> {
+        self.catalog
+            .versions(&server_type)
             .await
-            .map_err(map_link_error)
+            .map_err(map_catalog_error)
     }
-    /// 查询指定服务器类型、指定版本的下载链接。
+    /// 查询指定服务器类型、指定版本的构建列表，从新到旧。
+    async fn builds(
+        &self,
+        server_type: String,
+        server_version: String,
+    ) -> Result<CatalogSnapshot<Vec<CatalogBuild>>, ServerCatalogServiceError> {
+        self.catalog
+            .builds(&server_type, &server_version)
+            .await
+            .map_err(map_catalog_error)
+    }
+    /// 查询指定服务器类型、指定版本推荐构建的下载链接。
     ///
-    /// 类型或版本不存在时收敛为 [`ServerCatalogServiceError::NotFound`]；
-    /// 底层配置拉取 / 解析失败收敛为
-    /// [`ServerCatalogServiceError::OperationFailed`]。
+    /// 推荐构建为最新的正式构建；类型或版本不存在时收敛为
+    /// [`ServerCatalogServiceError::NotFound`]。
     async fn details(
         &self,
         server_type: String,
         server_version: String,
     ) -> Result<DownloadLink, ServerCatalogServiceError> {
-        LinkManager::get_link_by_type_and_version(&server_type, &server_version)
+        let build = self
+            .catalog
+            .latest_build(&server_type, &server_version)
             .await
-            .map_err(map_link_error)
+            .map_err(map_catalog_error)?
+            .data;
+        Ok(DownloadLink::new(build.game_version, build.file_name, build.url))
+    }
+    /// 使缓存的目录数据过期并重新查询服务器类型。
+    ///
+    /// 查询失败时与其他列表查询相同，返回带 `stale` 标记的缓存副本。
+    async fn refresh(&self) -> Result<CatalogSnapshot<Vec<String>>, ServerCatalogServiceError> {
+        self.catalog.refresh().await.map_err(map_catalog_error)
     }
 }
 
-/// 将下载链接查询错误按语义收敛为目录契约错误。
+/// 将目录查询错误按语义收敛为目录契约错误。
 ///
-/// 配置 / 基础设施失败归为操作失败，指定条目缺失归为不存在；
+/// 来源不可用归为操作失败，指定条目缺失归为不存在；
 /// 底层诊断消息在收敛前记录到日志，便于排查目录数据问题。
-fn map_link_error(error: LinkError) -> ServerCatalogServiceError {
+fn map_catalog_error(error: CatalogError) -> ServerCatalogServiceError {
     match &error {
-        LinkError::Config(message) => {
+        CatalogError::Unavailable { .. } => {
             tracing::warn!(
                 target: "sealantern.application.catalog",
-                error = %message,
-                "catalog configuration is unavailable"
+                error = %error,
+                "catalog is unavailable"
             );
             ServerCatalogServiceError::OperationFailed
         }
-        LinkError::NotFound(message) => {
+        CatalogError::NotFound(message) => {
             tracing::debug!(
                 target: "sealantern.application.catalog",
                 error = %message,
@@ -0,0 +1,275 @@
+//! 控制台命令输出捕获。
+//!
+//! 控制台输出与命令之间没有请求关联，这里以日志行号游标界定“命令之后”：
+//! 发送前订阅 [`LogEvent`] 广播并记下日志库当前最大行号，之后只收集行号更大的
+//! 服务器输出。广播消费过慢（`Lagged`）丢失的事件按游标从日志库补读，
+//! 因此结果不会因广播容量而缺行。
+
+use std::time::Duration;
+
+use regex::Regex;
+use sealantern_extra::server::log::{LogSource, read_logs};
+use sealantern_infra::persistence::{PersistenceError, SqliteDatabase};
+use sealantern_interface::console::ConsoleLogLine;
+use sealantern_interface::server::{CaptureCompletion, CaptureOptions, CommandCapture};
+use tokio::sync::broadcast::Receiver;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::Instant;
+
+use super::LogEvent;
+use crate::error::ServerError;
+
+/// 默认整体等待上限。
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(3);
+/// 默认静默期。
+const DEFAULT_QUIET: Duration = Duration::from_millis(500);
+/// 允许的最大整体等待上限。
+const MAX_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 校验后的捕获计划。
+pub(crate) struct CapturePlan {
+    timeout: Duration,
+    quiet: Duration,
+    matcher: Option<Regex>,
+}
+
+impl CapturePlan {
+    /// 校验捕获参数：等待上限与静默期均须为正且不超过上限，匹配须为合法正则。
+    pub(crate) fn from_options(options: &CaptureOptions) -> Result<Self, ServerError> {
+        let timeout = options
+            .timeout_ms
+            .map_or(DEFAULT_TIMEOUT, Duration::from_millis);
+        let quiet = options
+            .quiet_ms
+            .map_or(DEFAULT_QUIET, Duration::from_millis);
+        if timeout.is_zero() || timeout > MAX_TIMEOUT || quiet.is_zero() || quiet > timeout {
+            return Err(ServerError::InvalidInput);
+        }
+        let matcher = options
+            .until
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|_| ServerError::InvalidInput)?;
+        Ok(Self { timeout, quiet, matcher })
+    }
+
+    /// 收集 `instance_id` 在游标 `baseline` 之后的服务器输出，直到匹配、静默或超时。
+    ///
+    /// `receiver` 须在命令发送前订阅，否则发送与订阅之间的输出只能依赖补读。
+    pub(crate) async fn collect(
+        &self,
+        receiver: &mut Receiver<LogEvent>,
+        instance_id: &str,
+        baseline: i64,
+        database: &SqliteDatabase,
+    ) -> CommandCapture {
+        let deadline = Instant::now() + self.timeout;
+        let mut capture = Capture {
+            lines: Vec::new(),
+            cursor: baseline,
+            last_line_at: None,
+        };
+        loop {
+            let wait_until = match capture.last_line_at {
+                Some(last_line_at) => deadline.min(last_line_at + self.quiet),
+                None => deadline,
+            };
+            let received = match tokio::time::timeout_at(wait_until, receiver.recv()).await {
+                Ok(received) => received,
+                Err(_) if wait_until < deadline => return capture.finish(CaptureCompletion::Quiet),
+                Err(_) => return capture.finish(CaptureCompletion::Timeout),
+            };
+            let lines = match received {
+                Ok(event) if event.instance_id == instance_id => vec![event.line],
+                Ok(_) => continue,
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::debug!(
+                        target: "sealantern.application.command_capture",
+                        instance_id,
+                        skipped,
+                        "log broadcast lagged; backfilling captured lines from database"
+                    );
+                    match backfill(database, capture.cursor).await {
+                        Ok(lines) => lines,
+                        Err(error) => {
+                            tracing::warn!(
+                                target: "sealantern.application.command_capture",
+                                instance_id,
+                                error = %error,
+                                "failed to backfill captured lines"
+                            );
+                            continue;
+                        }
+                    }
+                }
+                Err(RecvError::Closed) => return capture.finish(CaptureCompletion::Timeout),
+            };
+            for line in lines {
+                if capture.accept(line) && self.matches(capture.lines.last()) {
+                    return capture.finish(CaptureCompletion::Matched);
+                }
+            }
+        }
+    }
+
+    fn matches(&self, line: Option<&ConsoleLogLine>) -> bool {
+        match (&self.matcher, line) {
+            (Some(matcher), Some(line)) => matcher.is_match(&line.line),
+            _ => false,
+        }
+    }
+}
+
+/// 读取日志库当前最大行号，作为命令发送前的游标（空库为 0）。
+pub(crate) async fn latest_sequence(database: &SqliteDatabase) -> Result<i64, PersistenceError> {
+    Ok(read_logs(database, 0, Some(1))
+        .await?
+        .last()
+        .map_or(0, |line| line.id))
+}
+
+struct Capture {
+    lines: Vec<ConsoleLogLine>,
+    cursor: i64,
+    /// 最近一次收下服务器输出的时刻，静默期由此起算。
+    last_line_at: Option<Instant>,
+}
+
+impl Capture {
+    /// 推进游标并收下游标之后的服务器输出；返回是否新增了一行。
+    fn accept(&mut self, line: ConsoleLogLine) -> bool {
+        if line.sequence <= self.cursor {
+            return false;
+        }
+        self.cursor = line.sequence;
+        if line.source != LogSource::Server.as_str() {
+            return false;
+        }
+        self.lines.push(line);
+        self.last_line_at = Some(Instant::now());
+        true
+    }
+
+    fn finish(self, completion: CaptureCompletion) -> CommandCapture {
+        CommandCapture { lines: self.lines, completion }
+    }
+}
+
+async fn backfill(
+    database: &SqliteDatabase,
+    cursor: i64,
+) -> Result<Vec<ConsoleLogLine>, PersistenceError> {
+    Ok(read_logs(database, cursor, None)
+        .await?
+        .into_iter()
+        .map(|line| ConsoleLogLine {
+            sequence: line.id,
+            timestamp: line.timestamp,
+            source: line.source,
+            line: line.line,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use sealantern_extra::server::log::open_log_database;
+    use tokio::sync::broadcast;
+
+    use super::*;
+
+    fn event(instance_id: &str, sequence: i64, source: &str, line: &str) -> LogEvent {
+        LogEvent {
+            instance_id: instance_id.to_owned(),
+            line: ConsoleLogLine {
+                sequence,
+                timestamp: 0,
+                source: source.to_owned(),
+                line: line.to_owned(),
+            },
+        }
+    }
+
+    fn plan(until: Option<&str>) -> CapturePlan {
+        CapturePlan::from_options(&CaptureOptions {
+            timeout_ms: Some(2_000),
+            quiet_ms: Some(100),
+            until: until.map(str::to_owned),
+            ..CaptureOptions::default()
+        })
+        .expect("valid plan")
+    }
+
+    #[tokio::test]
+    async fn collects_lines_after_cursor_until_matched() {
+        let temp = tempfile::tempdir().expect("临时目录");
+        let database = open_log_database(temp.path()).await.expect("日志库");
+        let (sender, mut receiver) = broadcast::channel(16);
+        for event in [
+            event("a", 5, "server", "before the command"),
+            event("b", 11, "server", "other instance"),
+            event("a", 11, "sealantern", "system line"),
+            event("a", 12, "server", "There are 1 of a max of 20 players online: Steve"),
+            event("a", 13, "server", "after the match"),
+        ] {
+            sender.send(event).expect("send");
+        }
+
+        let capture = plan(Some("players online"))
+            .collect(&mut receiver, "a", 10, &database)
+            .await;
+        assert_eq!(capture.completion, CaptureCompletion::Matched);
+        assert_eq!(capture.lines.len(), 1);
+        assert_eq!(capture.lines[0].sequence, 12);
+    }
+
+    #[tokio::test]
+    async fn ends_on_quiet_period_or_timeout() {
+        let temp = tempfile::tempdir().expect("临时目录");
+        let database = open_log_database(temp.path()).await.expect("日志库");
+        let (sender, mut receiver) = broadcast::channel(16);
+        sender.send(event("a", 1, "server", "Done")).expect("send");
+
+        let capture = plan(None).collect(&mut receiver, "a", 0, &database).await;
+        assert_eq!(capture.completion, CaptureCompletion::Quiet);
+        assert_eq!(capture.lines.len(), 1);
+
+        let quick = CapturePlan::from_options(&CaptureOptions {
+            timeout_ms: Some(50),
+            quiet_ms: Some(10),
+            ..CaptureOptions::default()
+        })
+        .expect("valid plan");
+        let capture = quick.collect(&mut receiver, "a", 1, &database).await;
+        assert_eq!(capture.completion, CaptureCompletion::Timeout);
+        assert!(capture.lines.is_empty());
+        drop(sender);
+    }
+
+    #[test]
+    fn rejects_invalid_options() {
+        let invalid = [
+            CaptureOptions {
+                timeout_ms: Some(0),
+                ..CaptureOptions::default()
+            },
+            CaptureOptions {
+                timeout_ms: Some(120_000),
+                ..CaptureOptions::default()
+            },
+            CaptureOptions {
+                timeout_ms: Some(100),
+                quiet_ms: Some(200),
+                ..CaptureOptions::default()
+            },
+            CaptureOptions {
+                until: Some("(".to_owned()),
+                ..CaptureOptions::default()
+            },
+        ];
+        for options in invalid {
+            assert!(matches!(CapturePlan::from_options(&options), Err(ServerError::InvalidInput)));
+        }
+    }
+}
@@ -0,0 +1,348 @@
+//! 控制台命令历史与宏服务实现。
+//!
+//! 实现 [`sealantern_interface::CommandLibraryService`] 能力端口，按实例目录读写
+//! `extra::server::command` 的命令库；宏的校验与展开复用 `core` 的
+//! [`CommandMacro`]。服务器进程服务在发送控制台命令前调用
+//! [`expand_console_input`] 完成展开。
+//!
+//! 错误分层：内部以应用层主错误 [`CommandLibraryError`] 为源头，暴露
+//! [`CommandLibraryService`] 时统一转为接口契约错误 [`CommandLibraryServiceError`]。
+
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use sealantern_core::instance::{Instance, InstanceId};
+use sealantern_core::server::command_macro::split_invocation;
+use sealantern_core::server::{CommandMacro, CommandMacroKind, ExpandedCommand, MacroStep};
+use sealantern_extra::server::command::{
+    clear_history, delete_macro, find_macro, list_macros, open_command_database, save_macro,
+    search_history,
+};
+use sealantern_infra::persistence::SqliteDatabase;
+use sealantern_interface::command::{
+    CommandHistoryEntry, CommandMacroDefinition, CommandMacroKind as MacroKindModel,
+    CommandMacroStep,
+};
+use sealantern_interface::{CommandLibraryService, CommandLibraryServiceError, InstanceService};
+
+use super::CoreInstanceService;
+use crate::error::{CommandLibraryError, ServerError};
+
+/// 历史默认返回的最近命令数。
+const DEFAULT_HISTORY_LIMIT: i64 = 50;
+/// 单次查询允许的最大历史条数。
+const MAX_HISTORY_LIMIT: i64 = 500;
+
+/// 基于实例目录命令库的命令历史与宏服务实现。
+pub struct CoreCommandLibraryService {
+    instance_service: Arc<CoreInstanceService>,
+}
+
+impl CoreCommandLibraryService {
+    /// 创建命令库服务。
+    pub fn new(instance_service: Arc<CoreInstanceService>) -> Self {
+        Self { instance_service }
+    }
+
+    async fn open_database(&self, id: &InstanceId) -> Result<SqliteDatabase, CommandLibraryError> {
+        let instance: Instance = self
+            .instance_service
+            .find(id)
+            .await
+            .map_err(CommandLibraryError::from)?
+            .ok_or(CommandLibraryError::InstanceNotFound)?;
+        Ok(open_command_database(&instance.directory).await?)
+    }
+}
+
+#[async_trait]
+impl CommandLibraryService for CoreCommandLibraryService {
+    async fn history(
+        &self,
+        id: &InstanceId,
+        query: Option<String>,
+        limit: Option<i64>,
+    ) -> Result<Vec<CommandHistoryEntry>, CommandLibraryServiceError> {
+        let limit = limit.unwrap_or(DEFAULT_HISTORY_LIMIT);
+        if !(1..=MAX_HISTORY_LIMIT).contains(&limit) {
+            return Err(CommandLibraryError::InvalidInput.into());
+        }
+        let database = self.open_database(id).await?;
+        let entries = search_history(&database, query.as_deref(), limit)
+            .await
+            .map_err(CommandLibraryError::from)?;
+        Ok(entries
+            .into_iter()
+            .map(|entry| CommandHistoryEntry {
+                command: entry.command,
+                last_used_at: entry.last_used_at,
+                use_count: entry.use_count,
+            })
+            .collect())
+    }
+
+    async fn clear_history(&self, id: &InstanceId) -> Result<(), CommandLibraryServiceError> {
+        let database = self.open_database(id).await?;
+        clear_history(&database)
+            .await
+            .map_err(CommandLibraryError::from)?;
+        Ok(())
+    }
+
+    async fn macros(
+        &self,
+        id: &InstanceId,
+    ) -> Result<Vec<CommandMacroDefinition>, CommandLibraryServiceError> {
+        let database = self.open_database(id).await?;
+        let macros = list_macros(&database)
+            .await
+            .map_err(CommandLibraryError::from)?;
+        Ok(macros.into_iter().map(to_definition).collect())
+    }
+
+    async fn save_macro(
+        &self,
+        id: &InstanceId,
+        definition: CommandMacroDefinition,
+    ) -> Result<CommandMacroDefinition, CommandLibraryServiceError> {
+        let definition = from_definition(definition);
+        definition
+            .validate()
+            .map_err(|_| CommandLibraryError::InvalidInput)?;
+        let database = self.open_database(id).await?;
+        save_macro(&database, &definition, current_timestamp_secs())
+            .await
+            .map_err(CommandLibraryError::from)?;
+        Ok(to_definition(definition))
+    }
+
+    async fn delete_macro(
+        &self,
+        id: &InstanceId,
+        name: &str,
+    ) -> Result<(), CommandLibraryServiceError> {
+        let database = self.open_database(id).await?;
+        if !delete_macro(&database, name)
+            .await
+            .map_err(CommandLibraryError::from)?
+        {
+            return Err(CommandLibraryError::MacroNotFound.into());
+        }
+        Ok(())
+    }
+}
+
+/// 将一条控制台输入展开为待发送的命令行。
+///
+/// 首词（忽略大小写）命中别名或宏时按定义展开；否则原样作为单行命令返回。
+/// 参数与定义不匹配视为输入错误。
+pub(crate) async fn expand_console_input(
+    database: &SqliteDatabase,
+    input: &str,
+) -> Result<Vec<ExpandedCommand>, ServerError> {
+    let (name, arguments) = split_invocation(input);
+    let definition = find_macro(database, &name.to_ascii_lowercase())
+        .await
+        .map_err(|e| ServerError::OperationFailed { source: Box::new(e) })?;
+    match definition {
+        Some(definition) => definition
+            .expand(arguments)
+            .map_err(|_| ServerError::InvalidInput),
+        None => Ok(vec![ExpandedCommand {
+            command: input.to_owned(),
+            delay: Duration::ZERO,
+        }]),
+    }
+}
+
+fn from_definition(definition: CommandMacroDefinition) -> CommandMacro {
+    CommandMacro {
+        name: definition.name.trim().to_owned(),
+        kind: match definition.kind {
+            MacroKindModel::Alias => CommandMacroKind::Alias,
+            MacroKindModel::Macro => CommandMacroKind::Macro,
+        },
+        params: definition.params,
+        steps: definition
+            .steps
+            .into_iter()
+            .map(|step| MacroStep {
+                command: step.command,
+                delay_ms: step.delay_ms,
+            })
+            .collect(),
+        description: definition.description,
+    }
+}
+
+fn to_definition(definition: CommandMacro) -> CommandMacroDefinition {
+    CommandMacroDefinition {
+        name: definition.name,
+        kind: match definition.kind {
+            CommandMacroKind::Alias => MacroKindModel::Alias,
+            CommandMacroKind::Macro => MacroKindModel::Macro,
+        },
+        params: definition.params,
+        steps: definition
+            .steps
+            .into_iter()
+            .map(|step| CommandMacroStep {
+                command: step.command,
+                delay_ms: step.delay_ms,
+            })
+            .collect(),
+        description: definition.description,
+    }
+}
+
+fn current_timestamp_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use sealantern_core::instance::{InstanceSpec, LocalLaunch, StartupMode};
+    use sealantern_extra::server::command::record_command;
+
+    use super::*;
+
+    fn sample_spec(id: &str, directory: PathBuf) -> InstanceSpec {
+        InstanceSpec {
+            id: InstanceId::new(id).expect("valid id"),
+            name: format!("服务器-{id}"),
+            aliases: Vec::new(),
+            core_type: "paper".into(),
+            core_version: "1.20.4".into(),
+            game_version: "1.20.4".into(),
+            directory: directory.clone(),
+            port: 25565,
+            max_memory_mib: 2048,
+            min_memory_mib: 512,
+            created_at_unix_secs: 0,
+            last_started_at_unix_secs: None,
+            server_metadata: None,
+            launch: LocalLaunch {
+                startup_mode: StartupMode::Jar,
+                startup_target: Some(directory.join("server.jar")),
+                custom_command: None,
+                custom_executable: None,
+                custom_arguments: Vec::new(),
+                java_executable: None,
+                jvm_arguments: Vec::new(),
+            },
+        }
+    }
+
+    async fn service_with_instance() -> (tempfile::TempDir, PathBuf, CoreCommandLibraryService) {
+        let temp = tempfile::tempdir().expect("临时目录应创建成功");
+        let instance_dir = temp.path().join("server-a");
+        std::fs::create_dir_all(&instance_dir).expect("实例目录应创建成功");
+        let instance_service = Arc::new(
+            CoreInstanceService::with_path(temp.path().join("instances.json"))
+                .await
+                .expect("实例服务应创建成功"),
+        );
+        instance_service
+            .create(sample_spec("a", instance_dir.clone()))
+            .await
+            .expect("实例应创建成功");
+        (temp, instance_dir, CoreCommandLibraryService::new(instance_service))
+    }
+
+    fn broadcast_macro() -> CommandMacroDefinition {
+        CommandMacroDefinition {
+            name: "bc".to_owned(),
+            kind: MacroKindModel::Macro,
+            params: vec!["message".to_owned()],
+            steps: vec![
+                CommandMacroStep {
+                    command: "say {message}".to_owned(),
+                    delay_ms: 0,
+                },
+                CommandMacroStep {
+                    command: "title @a actionbar {\"text\":\"{message}\"}".to_owned(),
+                    delay_ms: 0,
+                },
+            ],
+            description: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn saves_validates_and_expands_macros() {
+        let (_temp, instance_dir, service) = service_with_instance().await;
+        let id = InstanceId::new("a").expect("valid id");
+
+        service
+            .save_macro(&id, broadcast_macro())
+            .await
+            .expect("宏应保存成功");
+        let mut invalid = broadcast_macro();
+        invalid.steps[0].command = "say {unknown}".to_owned();
+        assert_eq!(
+            service.save_macro(&id, invalid).await,
+            Err(CommandLibraryServiceError::InvalidInput)
+        );
+        assert_eq!(service.macros(&id).await.expect("列表").len(), 1);
+
+        let database = open_command_database(&instance_dir).await.expect("命令库");
+        let lines = expand_console_input(&database, "BC hello world")
+            .await
+            .expect("展开");
+        let commands: Vec<_> = lines.iter().map(|line| line.command.as_str()).collect();
+        assert_eq!(commands, ["say hello world", "title @a actionbar {\"text\":\"hello world\"}"]);
+        let lines = expand_console_input(&database, "list").await.expect("原样");
+        assert_eq!(lines[0].command, "list");
+        assert!(matches!(
+            expand_console_input(&database, "bc").await,
+            Err(ServerError::InvalidInput)
+        ));
+
+        service.delete_macro(&id, "bc").await.expect("删除");
+        assert_eq!(
+            service.delete_macro(&id, "bc").await,
+            Err(CommandLibraryServiceError::MacroNotFound)
+        );
+    }
+
+    #[tokio::test]
+    async fn history_searches_and_validates_input() {
+        let (_temp, instance_dir, service) = service_with_instance().await;
+        let id = InstanceId::new("a").expect("valid id");
+        let database = open_command_database(&instance_dir).await.expect("命令库");
+        record_command(&database, "whitelist add Steve", 1)
+            .await
+            .expect("记录");
+        record_command(&database, "list", 2).await.expect("记录");
+
+        let entries = service
+            .history(&id, Some("white".to_owned()), None)
+            .await
+            .expect("搜索");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            service.history(&id, None, Some(0)).await,
+            Err(CommandLibraryServiceError::InvalidInput)
+        );
+        let missing = InstanceId::new("missing").expect("valid id");
+        assert_eq!(
+            service.history(&missing, None, None).await,
+            Err(CommandLibraryServiceError::InstanceNotFound)
+        );
+
+        service.clear_history(&id).await.expect("清空");
+        assert!(
+            service
+                .history(&id, None, None)
+                .await
+                .expect("读取")
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,354 @@
+//! 配置文件写入与修订服务实现。
+//!
+//! 所有经 Sea Lantern 写入服务器配置文件的路径（桌面端编辑、模板套用、端口分配）
+//! 都通过本模块写入并记录修订：写入前读取旧内容，写入后读取新内容，交给 `extra`
+//! 的 [`record_revision`] 落库。修订库不可用时只记录日志，不影响已完成的写入。
+//!
+//! [`CoreConfigService`] 同时实现 [`sealantern_interface::ConfigRevisionService`]
+//! 能力端口，按实例提供修订的查询、比较与恢复；桌面端按服务器目录调用同名的
+//! `*_in` 方法。写入与恢复都由调用方传入执行主体，修订按该主体归属。
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use sealantern_core::app_plugin::ExecutionPrincipal;
+use sealantern_core::instance::InstanceId;
+use sealantern_extra::config::server::{
+    diff_revisions, find_revision, list_revisions, open_revision_database, read_config_snapshot,
+    record_revision, restore_revision,
+};
+use sealantern_extra::config::{
+    ConfigDocumentManager, ConfigDocumentPreview, ConfigRevision, ConfigRevisionSummary,
+    DocumentPatch, ServerPropertiesManager,
+};
+use sealantern_interface::{ConfigRevisionService, ConfigServiceError, InstanceService};
+
+use super::CoreInstanceService;
+use crate::error::ConfigError;
+
+/// server.properties 相对服务器目录的路径。
+pub(crate) const SERVER_PROPERTIES_FILE: &str = "server.properties";
+/// 修订列表默认返回的条数。
+const DEFAULT_REVISION_LIMIT: i64 = 50;
+
+/// 一次配置文件编辑：相对服务器目录的文件路径与编辑前后的内容。
+pub(crate) struct ConfigEdit {
+    pub file: String,
+    pub before: Option<String>,
+    pub after: String,
+}
+
+/// 基于实例记录与服务器目录修订库的配置服务实现。
+pub struct CoreConfigService {
+    instance_service: Arc<CoreInstanceService>,
+}
+
+impl CoreConfigService {
+    pub fn new(instance_service: Arc<CoreInstanceService>) -> Self {
+        Self { instance_service }
+    }
+
+    /// 按 `game_version` 的字段模式校验并写入 server.properties，以 `principal` 记录修订。
+    pub async fn write_server_properties(
+        &self,
+        directory: &Path,
+        values: BTreeMap<String, String>,
+        game_version: Option<String>,
+        principal: &ExecutionPrincipal,
+    ) -> Result<(), ConfigError> {
+        let before = read_config_snapshot(directory, SERVER_PROPERTIES_FILE).await;
+        let manager =
+            ServerPropertiesManager::new(directory).with_game_version(game_version.as_deref());
+        run_blocking(move || manager.write(&values)).await??;
+        record_config_edit(directory, SERVER_PROPERTIES_FILE, before, principal).await;
+        Ok(())
+    }
+
+    /// 直接写入 server.properties 原始文本，以 `principal` 记录修订。
+    pub async fn write_server_properties_source(
+        &self,
+        directory: &Path,
+        source: String,
+        principal: &ExecutionPrincipal,
+    ) -> Result<(), ConfigError> {
+        let before = read_config_snapshot(directory, SERVER_PROPERTIES_FILE).await;
+        let manager = ServerPropertiesManager::new(directory);
+        run_blocking(move || manager.write_source(&source)).await??;
+        record_config_edit(directory, SERVER_PROPERTIES_FILE, before, principal).await;
+        Ok(())
+    }
+
+    /// 按键路径修改配置文档并原子写回，以 `principal` 记录修订。
+    pub async fn write_document(
+        &self,
+        directory: &Path,
+        path: String,
+        patches: Vec<DocumentPatch>,
+        principal: &ExecutionPrincipal,
+    ) -> Result<ConfigDocumentPreview, ConfigError> {
+        let before = read_config_snapshot(directory, &path).await;
+        let manager = ConfigDocumentManager::new(directory);
+        let file = path.clone();
+        let preview = run_blocking(move || manager.write(&path, &patches)).await??;
+        record_config_edit(directory, &file, before, principal).await;
+        Ok(preview)
+    }
+
+    /// 列出服务器目录下的修订（时间倒序）。
+    pub async fn list_revisions_in(
+        &self,
+        directory: &Path,
+        file: Option<&str>,
+        limit: Option<i64>,
+    ) -> Result<Vec<ConfigRevisionSummary>, ConfigError> {
+        let database = open_revision_database(directory).await?;
+        Ok(list_revisions(&database, file, limit.unwrap_or(DEFAULT_REVISION_LIMIT)).await?)
+    }
+
+    /// 读取服务器目录下的一条修订及其完整快照。
+    pub async fn get_revision_in(
+        &self,
+        directory: &Path,
+        revision: i64,
+    ) -> Result<Option<ConfigRevision>, ConfigError> {
+        let database = open_revision_database(directory).await?;
+        Ok(find_revision(&database, revision).await?)
+    }
+
+    /// 比较服务器目录下的两条修订。
+    pub async fn diff_revisions_in(
+        &self,
+        directory: &Path,
+        from: i64,
+        to: i64,
+    ) -> Result<String, ConfigError> {
+        let database = open_revision_database(directory).await?;
+        Ok(diff_revisions(&database, from, to).await?)
+    }
+
+    /// 将服务器目录下的配置文件恢复为指定修订，恢复修订归属 `principal`。
+    pub async fn restore_revision_in(
+        &self,
+        directory: &Path,
+        revision: i64,
+        principal: &ExecutionPrincipal,
+    ) -> Result<Option<ConfigRevisionSummary>, ConfigError> {
+        let database = open_revision_database(directory).await?;
+        Ok(
+            restore_revision(&database, directory, revision, principal, current_timestamp_secs())
+                .await?,
+        )
+    }
+
+    async fn directory(&self, id: &InstanceId) -> Result<PathBuf, ConfigError> {
+        Ok(self
+            .instance_service
+            .find(id)
+            .await?
+            .ok_or(ConfigError::InstanceNotFound)?
+            .directory)
+    }
+}
+
+#[async_trait]
+impl ConfigRevisionService for CoreConfigService {
+    async fn list_revisions(
+        &self,
+        id: &InstanceId,
+        file: Option<&str>,
+        limit: Option<i64>,
+    ) -> Result<Vec<ConfigRevisionSummary>, ConfigServiceError> {
+        let directory = self.directory(id).await?;
+        Ok(self.list_revisions_in(&directory, file, limit).await?)
+    }
+
+    async fn get_revision(
+        &self,
+        id: &InstanceId,
+        revision: i64,
+    ) -> Result<ConfigRevision, ConfigServiceError> {
+        let directory = self.directory(id).await?;
+        self.get_revision_in(&directory, revision)
+            .await?
+            .ok_or(ConfigServiceError::RevisionNotFound)
+    }
+
+    async fn diff_revisions(
+        &self,
+        id: &InstanceId,
+        from: i64,
+        to: i64,
+    ) -> Result<String, ConfigServiceError> {
+        let directory = self.directory(id).await?;
+        Ok(self.diff_revisions_in(&directory, from, to).await?)
+    }
+
+    async fn restore_revision(
+        &self,
+        id: &InstanceId,
+        revision: i64,
+        principal: &ExecutionPrincipal,
+    ) -> Result<Option<ConfigRevisionSummary>, ConfigServiceError> {
+        let directory = self.directory(id).await?;
+        Ok(self
+            .restore_revision_in(&directory, revision, principal)
+            .await?)
+    }
+}
+
+/// 记录一次已完成的编辑：读取文件当前内容作为编辑后快照。
+pub(crate) async fn record_config_edit(
+    directory: &Path,
+    file: &str,
+    before: Option<String>,
+    principal: &ExecutionPrincipal,
+) {
+    let Some(after) = read_config_snapshot(directory, file).await else {
+        return;
+    };
+    let edit = ConfigEdit { file: file.to_owned(), before, after };
+    record_config_edits(directory, &[edit], principal).await;
+}
+
+/// 以 `principal` 记录一组已完成的编辑；修订库不可用时只记录日志，不影响已完成的写入。
+pub(crate) async fn record_config_edits(
+    directory: &Path,
+    edits: &[ConfigEdit],
+    principal: &ExecutionPrincipal,
+) {
+    if edits.is_empty() {
+        return;
+    }
+    let database = match open_revision_database(directory).await {
+        Ok(database) => database,
+        Err(error) => {
+            tracing::warn!("记录配置修订失败: {}", error);
+            return;
+        }
+    };
+    let now = current_timestamp_secs();
+    for edit in edits {
+        if let Err(error) = record_revision(
+            &database,
+            &edit.file,
+            principal,
+            edit.before.clone(),
+            edit.after.clone(),
+            now,
+        )
+        .await
+        {
+            tracing::warn!("记录配置修订失败: {} ({})", edit.file, error);
+        }
+    }
+}
+
+async fn run_blocking<T: Send + 'static>(
+    task: impl FnOnce() -> T + Send + 'static,
+) -> Result<T, ConfigError> {
+    tokio::task::spawn_blocking(task)
+        .await
+        .map_err(|source| ConfigError::OperationFailed { source: Box::new(source) })
+}
+
+fn current_timestamp_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use sealantern_core::instance::{InstanceSpec, LocalLaunch, StartupMode};
+
+    use super::*;
+
+    fn sample_spec(directory: PathBuf) -> InstanceSpec {
+        InstanceSpec {
+            id: InstanceId::new("config-revisions").expect("valid id"),
+            name: "配置修订".into(),
+            aliases: Vec::new(),
+            core_type: "paper".into(),
+            core_version: "1.20.4".into(),
+            game_version: "1.20.4".into(),
+            directory: directory.clone(),
+            port: 47634,
+            max_memory_mib: 2048,
+            min_memory_mib: 512,
+            created_at_unix_secs: 0,
+            last_started_at_unix_secs: None,
+            server_metadata: None,
+            launch: LocalLaunch {
+                startup_mode: StartupMode::Jar,
+                startup_target: Some(directory.join("server.jar")),
+                custom_command: None,
+                custom_executable: None,
+                custom_arguments: Vec::new(),
+                java_executable: None,
+                jvm_arguments: Vec::new(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn writes_record_revisions_that_both_hosts_can_restore() {
+        let temp = tempfile::tempdir().expect("临时目录应创建成功");
+        let server_dir = temp.path().join("server");
+        std::fs::create_dir_all(&server_dir).expect("服务器目录应创建成功");
+        std::fs::write(server_dir.join(SERVER_PROPERTIES_FILE), "motd=original")
+            .expect("配置文件应写入成功");
+        let instance_service = Arc::new(
+            CoreInstanceService::with_path(temp.path().join("instances.json"))
+                .await
+                .expect("实例服务应创建成功"),
+        );
+        let instance = instance_service
+            .create(sample_spec(server_dir.clone()))
+            .await
+            .expect("实例应创建成功");
+        let service = CoreConfigService::new(instance_service);
+
+        service
+            .write_server_properties(
+                &server_dir,
+                BTreeMap::from([("motd".to_owned(), "edited".to_owned())]),
+                Some("1.20.4".to_owned()),
+                &ExecutionPrincipal::BuiltInHost,
+            )
+            .await
+            .expect("写入应成功");
+
+        let revisions = service
+            .list_revisions(&instance.id, Some(SERVER_PROPERTIES_FILE), None)
+            .await
+            .expect("修订应可列出");
+        assert_eq!(revisions.len(), 2, "原始内容记为基线，编辑记为新修订");
+        let baseline = revisions[1].id;
+        assert!(revisions[0].diff.contains("+motd=edited"));
+
+        let restored = service
+            .restore_revision(&instance.id, baseline, &ExecutionPrincipal::BuiltInHost)
+            .await
+            .expect("恢复应成功")
+            .expect("内容有变化时应记录恢复修订");
+        assert_eq!(restored.restored_from, Some(baseline));
+        assert_eq!(
+            std::fs::read_to_string(server_dir.join(SERVER_PROPERTIES_FILE)).unwrap(),
+            "motd=original"
+        );
+
+        assert_eq!(
+            service.get_revision(&instance.id, 999).await,
+            Err(ConfigServiceError::RevisionNotFound)
+        );
+        let missing = InstanceId::new("missing").expect("valid id");
+        assert_eq!(
+            service.list_revisions(&missing, None, None).await,
+            Err(ConfigServiceError::InstanceNotFound)
+        );
+    }
+}
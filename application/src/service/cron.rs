@@ -334,7 +334,10 @@ fn run_to_contract(run: ExtraCronTaskRun) -> CronTaskRun {
 mod tests {
     use std::sync::Mutex;
 
-    use sealantern_interface::server::{ServerSnapshot, ServerState};
+    use sealantern_interface::server::{
+        CaptureCompletion, CaptureOptions, CommandCapture, CommandResponse, CommandTransport,
+        ServerProbe, ServerSnapshot, ServerState,
+    };
     use tempfile::tempdir;
 
     use super::*;
@@ -387,6 +390,33 @@ mod tests {
                 .push(format!("command:{}:{command}", id.as_str()));
             Ok(())
         }
+
+        async fn send_command_via(
+            &self,
+            id: &InstanceId,
+            command: &str,
+            transport: CommandTransport,
+        ) -> Result<CommandResponse, ServerServiceError> {
+            self.send_command(id, command).await?;
+            Ok(CommandResponse { transport, output: None })
+        }
+
+        async fn send_command_and_capture(
+            &self,
+            id: &InstanceId,
+            command: &str,
+            _options: CaptureOptions,
+        ) -> Result<CommandCapture, ServerServiceError> {
+            self.send_command(id, command).await?;
+            Ok(CommandCapture {
+                lines: Vec::new(),
+                completion: CaptureCompletion::Quiet,
+            })
+        }
+
+        async fn probe(&self, _id: &InstanceId) -> Result<ServerProbe, ServerServiceError> {
+            Err(ServerServiceError::InvalidState)
+        }
     }
 
     fn draft(action: CronTaskAction) -> CronTaskDraft {
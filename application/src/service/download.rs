@@ -2,22 +2,37 @@
 //!
 //! 实现 [`sealantern_interface::DownloadService`] 能力端口，显式持有
 //! `infra` 的 [`DownloadManager`]（而非被其全局单例反向绑定），管理
-//! 下载任务的创建、进度查询与取消。
+//! 下载任务的创建、进度查询、暂停与恢复、取消与启动时续传。主地址与备用镜像去重后
+//! 一起交给下载管理器，由其按镜像健康统计排序与切换；任务经全局调度器排队，
+//! 同时运行的任务数、连接总数与带宽受全局限制。
 //!
 //! 错误分层：内部以应用层主错误 [`DownloadError`] 为源头，暴露
 //! [`DownloadService`] 时统一转为接口契约错误 [`DownloadServiceError`]。
 
 use async_trait::async_trait;
-use sealantern_infra::download::DownloadManager;
+use sealantern_infra::download::{
+    DownloadErrorKind as InfraDownloadErrorKind, DownloadIntegrity, DownloadManager,
+    DownloadPriority as InfraDownloadPriority, SchedulerLimits, TaskOptions,
+};
+use sealantern_infra::fs::HashAlgorithm;
 use sealantern_infra::net::{ClientProvider, global_client_provider};
 use sealantern_interface::DownloadServiceError;
-use sealantern_interface::download::{DownloadRequest, DownloadTaskInfo, DownloadTaskStatus};
+use sealantern_interface::download::{
+    DownloadErrorKind, DownloadLimits, DownloadPriority, DownloadRequest, DownloadTaskInfo,
+    DownloadTaskStatus, ResumedDownload,
+};
 
 use crate::error::DownloadError;
 
 /// 下载线程数上限（防止资源滥用）。
 const MAX_DOWNLOAD_THREAD_COUNT: usize = 64;
 
+/// 同时运行的任务数上限的最大值。
+const MAX_ACTIVE_TASKS: usize = 32;
+
+/// 全局连接数上限的最大值。
+const MAX_TOTAL_CONNECTIONS: usize = 1024;
+
 /// 基于 `infra` 下载能力的下载任务管理服务实现。
 pub struct CoreDownloadService {
     /// 下载任务管理器（显式持有，非全局单例）。
@@ -65,9 +80,42 @@ impl sealantern_interface::DownloadService for CoreDownloadService {
             return Err(DownloadError::InvalidInput.into());
         }
 
+        let integrity = DownloadIntegrity {
+            size: request.expected_size,
+            sha1: request.sha1,
+            sha256: request.sha256,
+            sha512: request.sha512,
+        };
+        for algorithm in HashAlgorithm::STRONGEST_FIRST {
+            if let Some(digest) = integrity.digest(algorithm)
+                && !is_hex_digest(digest, algorithm)
+            {
+                return Err(DownloadError::InvalidInput.into());
+            }
+        }
+
+        let mut candidates = vec![request.url.trim().to_owned()];
+        for mirror in &request.mirrors {
+            let mirror = mirror.trim();
+            if !mirror.is_empty() && !candidates.iter().any(|known| known == mirror) {
+                candidates.push(mirror.to_owned());
+            }
+        }
+
+        let options = TaskOptions {
+            priority: to_infra_priority(request.priority),
+            bandwidth_limit: request.bandwidth_limit,
+            resumable: true,
+        };
         let (id, _) = self
             .manager
-            .create_with_handle(&request.url, &request.save_path, request.thread_count)
+            .create_scheduled(
+                &candidates,
+                &request.save_path,
+                request.thread_count,
+                integrity,
+                options,
+            )
             .await
             .map_err(DownloadError::from)?;
         Ok(id.to_string())
@@ -79,14 +127,7 @@ impl sealantern_interface::DownloadService for CoreDownloadService {
             return Ok(None);
         };
 
-        Ok(Some(DownloadTaskInfo {
-            id: id.to_string(),
-            total_size: snapshot.total_size,
-            downloaded: snapshot.downloaded,
-            progress: snapshot.progress_percentage,
-            status: to_frontend_status(&snapshot),
-            is_finished: snapshot.is_finished,
-        }))
+        Ok(Some(to_task_info(id, &snapshot)))
     }
 
     async fn cancel(&self, id: &str) -> Result<(), DownloadServiceError> {
@@ -94,6 +135,101 @@ impl sealantern_interface::DownloadService for CoreDownloadService {
         self.manager.cancel(task_id).await;
         Ok(())
     }
+
+    async fn pause(&self, id: &str) -> Result<(), DownloadServiceError> {
+        let task_id = parse_task_id(id)?;
+        let Some(snapshot) = self.manager.get_progress(task_id).await else {
+            return Err(DownloadError::TaskNotFound.into());
+        };
+        if snapshot.is_finished {
+            return Err(DownloadError::InvalidInput.into());
+        }
+        // 任务存在且未结束时，暂停失败只可能是远端不支持断点续传。
+        self.manager
+            .pause(task_id)
+            .await
+            .map_err(|_| DownloadError::Unsupported.into())
+    }
+
+    async fn resume(&self, id: &str) -> Result<DownloadTaskInfo, DownloadServiceError> {
+        let task_id = parse_task_id(id)?;
+        let known = self.manager.get_progress(task_id).await.is_some()
+            || self
+                .manager
+                .incomplete()
+                .await
+                .iter()
+                .any(|record| record.id == task_id);
+        if !known {
+            return Err(DownloadError::TaskNotFound.into());
+        }
+        self.manager
+            .resume(task_id)
+            .await
+            .map_err(DownloadError::from)?;
+        self.poll(id)
+            .await?
+            .ok_or_else(|| DownloadError::TaskNotFound.into())
+    }
+
+    async fn limits(&self) -> Result<DownloadLimits, DownloadServiceError> {
+        Ok(to_contract_limits(self.manager.limits()))
+    }
+
+    async fn set_limits(
+        &self,
+        limits: DownloadLimits,
+    ) -> Result<DownloadLimits, DownloadServiceError> {
+        if !(1..=MAX_ACTIVE_TASKS).contains(&limits.max_active_tasks)
+            || !(1..=MAX_TOTAL_CONNECTIONS).contains(&limits.max_connections)
+        {
+            return Err(DownloadError::InvalidInput.into());
+        }
+        let applied = self
+            .manager
+            .set_limits(SchedulerLimits {
+                max_active_tasks: limits.max_active_tasks,
+                max_connections: limits.max_connections,
+                bandwidth_limit: limits.bandwidth_limit,
+            })
+            .await
+            .map_err(DownloadError::from)?;
+        Ok(to_contract_limits(applied))
+    }
+
+    async fn resume_incomplete(&self) -> Result<Vec<ResumedDownload>, DownloadServiceError> {
+        let mut resumed = Vec::new();
+        for record in self.manager.incomplete().await {
+            let id = record.id.to_string();
+            let task = match self.manager.resume(record.id).await {
+                Ok(status) => match self.manager.get_progress(record.id).await {
+                    Some(snapshot) => to_task_info(&id, &snapshot),
+                    None => to_task_info(&id, &status.snapshot().await),
+                },
+                // 续传记录保留，下次启动再试；本次以错误状态告知宿主。
+                Err(error) => DownloadTaskInfo {
+                    id,
+                    total_size: record.total_size,
+                    downloaded: record.downloaded(),
+                    progress: if record.total_size > 0 {
+                        record.downloaded() as f64 / record.total_size as f64 * 100.0
+                    } else {
+                        0.0
+                    },
+                    status: DownloadTaskStatus::Error(error.to_string()),
+                    error_kind: Some(to_contract_error_kind(error.kind())),
+                    is_finished: true,
+                    queue_position: None,
+                },
+            };
+            resumed.push(ResumedDownload {
+                task,
+                url: record.candidates.first().cloned().unwrap_or_default(),
+                save_path: record.output_path,
+            });
+        }
+        Ok(resumed)
+    }
 }
 
 /// 解析任务 ID 字符串为 UUID。
@@ -101,6 +237,64 @@ fn parse_task_id(id: &str) -> Result<uuid::Uuid, DownloadError> {
     uuid::Uuid::parse_str(id).map_err(|_| DownloadError::InvalidInput)
 }
 
+/// 检查摘要是否为对应算法长度的十六进制字符串。
+fn is_hex_digest(digest: &str, algorithm: HashAlgorithm) -> bool {
+    let expected_len = match algorithm {
+        HashAlgorithm::Sha1 => 40,
+        HashAlgorithm::Sha256 => 64,
+        HashAlgorithm::Sha512 => 128,
+    };
+    digest.len() == expected_len && digest.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+/// 将后端下载快照映射为任务信息。
+fn to_task_info(
+    id: &str,
+    snapshot: &sealantern_infra::download::DownloadSnapshot,
+) -> DownloadTaskInfo {
+    DownloadTaskInfo {
+        id: id.to_string(),
+        total_size: snapshot.total_size,
+        downloaded: snapshot.downloaded,
+        progress: snapshot.progress_percentage,
+        status: to_frontend_status(snapshot),
+        error_kind: snapshot.error_kind.map(to_contract_error_kind),
+        is_finished: snapshot.is_finished,
+        queue_position: snapshot.queue_position,
+    }
+}
+
+/// 将契约优先级映射为下载调度优先级。
+fn to_infra_priority(priority: DownloadPriority) -> InfraDownloadPriority {
+    match priority {
+        DownloadPriority::Low => InfraDownloadPriority::Low,
+        DownloadPriority::Normal => InfraDownloadPriority::Normal,
+        DownloadPriority::High => InfraDownloadPriority::High,
+    }
+}
+
+/// 将下载失败类别映射为契约模型。
+fn to_contract_error_kind(kind: InfraDownloadErrorKind) -> DownloadErrorKind {
+    match kind {
+        InfraDownloadErrorKind::Network => DownloadErrorKind::Network,
+        InfraDownloadErrorKind::Storage => DownloadErrorKind::Storage,
+        InfraDownloadErrorKind::Cancelled => DownloadErrorKind::Cancelled,
+        InfraDownloadErrorKind::RemoteChanged => DownloadErrorKind::RemoteChanged,
+        InfraDownloadErrorKind::SizeMismatch => DownloadErrorKind::SizeMismatch,
+        InfraDownloadErrorKind::ChecksumMismatch => DownloadErrorKind::ChecksumMismatch,
+        InfraDownloadErrorKind::Other => DownloadErrorKind::Other,
+    }
+}
+
+/// 将调度限制映射为契约模型。
+fn to_contract_limits(limits: SchedulerLimits) -> DownloadLimits {
+    DownloadLimits {
+        max_active_tasks: limits.max_active_tasks,
+        max_connections: limits.max_connections,
+        bandwidth_limit: limits.bandwidth_limit,
+    }
+}
+
 /// 将后端下载快照映射为前端任务状态。
 fn to_frontend_status(
     snapshot: &sealantern_infra::download::DownloadSnapshot,
@@ -109,6 +303,10 @@ fn to_frontend_status(
         DownloadTaskStatus::Error(error.clone())
     } else if snapshot.is_finished {
         DownloadTaskStatus::Completed
+    } else if snapshot.paused {
+        DownloadTaskStatus::Paused
+    } else if snapshot.queue_position.is_some() {
+        DownloadTaskStatus::Queued
     } else if snapshot.downloaded > 0 {
         DownloadTaskStatus::Downloading
     } else {
@@ -130,8 +328,15 @@ mod tests {
         }));
         let request = DownloadRequest {
             url: "https://example.com/file.zip".to_owned(),
+            mirrors: Vec::new(),
             save_path: "C:\\temp\\file.zip".to_owned(),
             thread_count: 8,
+            expected_size: None,
+            sha1: None,
+            sha256: None,
+            sha512: None,
+            priority: DownloadPriority::default(),
+            bandwidth_limit: None,
         };
 
         let error = service
@@ -140,4 +345,106 @@ mod tests {
             .expect_err("provider failure must fail");
         assert_eq!(error, DownloadServiceError::OperationFailed);
     }
+
+    #[tokio::test]
+    async fn malformed_digest_is_rejected_before_download() {
+        let service =
+            CoreDownloadService::with_provider(Box::new(|| panic!("校验失败的请求不应获取客户端")));
+        let request = DownloadRequest {
+            url: "https://example.com/server.jar".to_owned(),
+            mirrors: vec!["https://mirror.example.com/server.jar".to_owned()],
+            save_path: "/tmp/server.jar".to_owned(),
+            thread_count: 8,
+            expected_size: Some(1024),
+            sha1: None,
+            sha256: Some("not-a-digest".to_owned()),
+            sha512: None,
+            priority: DownloadPriority::default(),
+            bandwidth_limit: None,
+        };
+
+        let error = service
+            .create(request)
+            .await
+            .expect_err("malformed digest must fail");
+        assert_eq!(error, DownloadServiceError::InvalidInput);
+        assert!(is_hex_digest("A9993E364706816ABA3E25717850C26C9CD0D89D", HashAlgorithm::Sha1));
+    }
+
+    #[tokio::test]
+    async fn unresumable_tasks_are_reported_and_kept_for_the_next_start() {
+        use std::sync::Arc;
+
+        use sealantern_infra::download::{
+            ChunkProgress, DownloadScheduler, RemoteValidator, ResumeRecord, ResumeStore,
+        };
+
+        let dir = std::env::temp_dir()
+            .join(format!("sealantern-application-resume-{}", uuid::Uuid::new_v4()));
+        let store = Arc::new(ResumeStore::new(&dir));
+        let record = ResumeRecord {
+            id: uuid::Uuid::new_v4(),
+            candidates: vec!["https://example.com/modpack.zip".to_owned()],
+            output_path: dir.join("modpack.zip").to_string_lossy().into_owned(),
+            thread_count: 4,
+            total_size: 400,
+            integrity: DownloadIntegrity::default(),
+            validator: RemoteValidator::default(),
+            chunks: vec![ChunkProgress { start: 0, end: 399, offset: 100 }],
+            updated_at_unix_secs: 0,
+        };
+        store.save(&record).await.unwrap();
+        let manager = DownloadManager::with_provider(Box::new(|| {
+            Err(sealantern_infra::net::NetError::Config("模拟离线".into()))
+        }))
+        .with_resume_store(Arc::clone(&store))
+        .with_scheduler(Arc::new(DownloadScheduler::new(SchedulerLimits::default())));
+        let service = CoreDownloadService::with_manager(manager);
+
+        let resumed = service.resume_incomplete().await.expect("list incomplete");
+
+        assert_eq!(resumed.len(), 1);
+        assert_eq!(resumed[0].task.id, record.id.to_string());
+        assert_eq!(resumed[0].task.downloaded, 100);
+        assert!(resumed[0].task.is_finished);
+        assert!(matches!(resumed[0].task.status, DownloadTaskStatus::Error(_)));
+        assert_eq!(resumed[0].save_path, record.output_path);
+        assert!(store.load(record.id).await.is_some());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn limits_are_validated_and_unknown_tasks_cannot_be_paused() {
+        use std::sync::Arc;
+
+        use sealantern_infra::download::{DownloadScheduler, ResumeStore};
+
+        let dir = std::env::temp_dir()
+            .join(format!("sealantern-application-limits-{}", uuid::Uuid::new_v4()));
+        let manager = DownloadManager::with_provider(Box::new(|| panic!("没有任务需要获取客户端")))
+            .with_resume_store(Arc::new(ResumeStore::new(&dir)))
+            .with_scheduler(Arc::new(DownloadScheduler::new(SchedulerLimits::default())));
+        let service = CoreDownloadService::with_manager(manager);
+        let id = uuid::Uuid::new_v4().to_string();
+
+        assert_eq!(service.pause(&id).await, Err(DownloadServiceError::TaskNotFound));
+        assert_eq!(service.resume(&id).await, Err(DownloadServiceError::TaskNotFound));
+
+        let invalid = DownloadLimits {
+            max_active_tasks: 0,
+            max_connections: 8,
+            bandwidth_limit: None,
+        };
+        assert_eq!(service.set_limits(invalid).await, Err(DownloadServiceError::InvalidInput));
+        let applied = service
+            .set_limits(DownloadLimits {
+                max_active_tasks: 2,
+                max_connections: 8,
+                bandwidth_limit: Some(0),
+            })
+            .await
+            .expect("valid limits");
+        assert_eq!(applied.bandwidth_limit, None);
+        assert_eq!(service.limits().await, Ok(applied));
+    }
 }
@@ -0,0 +1,168 @@
+//! 扩展启停服务实现。
+//!
+//! 实现 [`sealantern_interface::ExtensionService`] 能力端口：文件移动由 `extra` 的
+//! [`set_extension_enabled`] 完成，本服务负责定位实例目录，并在服务器未停止时
+//! （启动中、运行中、停止中）拒绝非强制请求。
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sealantern_core::instance::{InstanceExtension, InstanceId};
+use sealantern_extra::server::set_extension_enabled;
+use sealantern_interface::extension::ExtensionToggleRequest;
+use sealantern_interface::server::ServerState;
+use sealantern_interface::{
+    ExtensionService, ExtensionServiceError, InstanceService, ServerService,
+};
+
+use super::CoreInstanceService;
+use crate::error::ExtensionError;
+
+/// 基于实例记录与服务器进程状态的扩展启停服务实现。
+pub struct CoreExtensionService {
+    instance_service: Arc<CoreInstanceService>,
+    server: Arc<dyn ServerService>,
+}
+
+impl CoreExtensionService {
+    pub fn new(instance_service: Arc<CoreInstanceService>, server: Arc<dyn ServerService>) -> Self {
+        Self { instance_service, server }
+    }
+
+    async fn set_enabled_inner(
+        &self,
+        id: &InstanceId,
+        request: ExtensionToggleRequest,
+    ) -> Result<InstanceExtension, ExtensionError> {
+        let instance = self
+            .instance_service
+            .find(id)
+            .await?
+            .ok_or(ExtensionError::InstanceNotFound)?;
+        if !request.force && self.server.status(id).await?.state != ServerState::Stopped {
+            return Err(ExtensionError::ServerRunning);
+        }
+        let extension = tokio::task::spawn_blocking(move || {
+            set_extension_enabled(
+                &instance.directory,
+                request.kind,
+                &request.file_name,
+                request.enabled,
+            )
+        })
+        .await
+        .map_err(|error| ExtensionError::OperationFailed { source: Box::new(error) })??;
+        Ok(extension)
+    }
+}
+
+#[async_trait]
+impl ExtensionService for CoreExtensionService {
+    async fn set_enabled(
+        &self,
+        id: &InstanceId,
+        request: ExtensionToggleRequest,
+    ) -> Result<InstanceExtension, ExtensionServiceError> {
+        self.set_enabled_inner(id, request)
+            .await
+            .map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use sealantern_core::instance::{
+        InstanceExtensionKind, InstanceSpec, LocalLaunch, StartupMode,
+    };
+
+    use super::super::test_support::FakeServerService;
+    use super::*;
+
+    fn sample_spec(directory: PathBuf) -> InstanceSpec {
+        InstanceSpec {
+            id: InstanceId::new("extensions").expect("valid id"),
+            name: "扩展测试".into(),
+            aliases: Vec::new(),
+            core_type: "fabric".into(),
+            core_version: "0.15.11".into(),
+            game_version: "1.20.4".into(),
+            directory: directory.clone(),
+            port: 47633,
+            max_memory_mib: 2048,
+            min_memory_mib: 512,
+            created_at_unix_secs: 0,
+            last_started_at_unix_secs: None,
+            server_metadata: None,
+            launch: LocalLaunch {
+                startup_mode: StartupMode::Jar,
+                startup_target: Some(directory.join("server.jar")),
+                custom_command: None,
+                custom_executable: None,
+                custom_arguments: Vec::new(),
+                java_executable: None,
+                jvm_arguments: Vec::new(),
+            },
+        }
+    }
+
+    fn toggle(file_name: &str, enabled: bool, force: bool) -> ExtensionToggleRequest {
+        ExtensionToggleRequest {
+            kind: InstanceExtensionKind::Mod,
+            file_name: file_name.to_owned(),
+            enabled,
+            force,
+        }
+    }
+
+    #[tokio::test]
+    async fn refuses_while_running_unless_forced() {
+        let temp = tempfile::tempdir().expect("临时目录应创建成功");
+        let server_dir = temp.path().join("server");
+        std::fs::create_dir_all(server_dir.join("mods")).expect("模组目录应创建成功");
+        std::fs::write(server_dir.join("mods/sodium.jar"), "jar").expect("模组文件应写入成功");
+        let instance_service = Arc::new(
+            CoreInstanceService::with_path(temp.path().join("instances.json"))
+                .await
+                .expect("实例服务应创建成功"),
+        );
+        let instance = instance_service
+            .create(sample_spec(server_dir.clone()))
+            .await
+            .expect("实例应创建成功");
+
+        let running = CoreExtensionService::new(
+            instance_service.clone(),
+            Arc::new(FakeServerService::new(ServerState::Running)),
+        );
+        assert_eq!(
+            running
+                .set_enabled(&instance.id, toggle("sodium.jar", false, false))
+                .await,
+            Err(ExtensionServiceError::ServerRunning)
+        );
+        assert!(server_dir.join("mods/sodium.jar").is_file());
+        let disabled = running
+            .set_enabled(&instance.id, toggle("sodium.jar", false, true))
+            .await
+            .expect("强制禁用应成功");
+        assert!(!disabled.enabled);
+
+        let stopped = CoreExtensionService::new(
+            instance_service,
+            Arc::new(FakeServerService::new(ServerState::Stopped)),
+        );
+        let enabled = stopped
+            .set_enabled(&instance.id, toggle("sodium.jar.disabled", true, false))
+            .await
+            .expect("停止时启用应成功");
+        assert_eq!(enabled.file_name, "sodium.jar");
+        assert_eq!(
+            stopped
+                .set_enabled(&instance.id, toggle("missing.jar", true, false))
+                .await,
+            Err(ExtensionServiceError::ExtensionNotFound)
+        );
+    }
+}
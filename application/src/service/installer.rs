@@ -0,0 +1,450 @@
+//! 加载器安装器执行服务实现。
+//!
+//! 实现 [`sealantern_interface::InstallerService`] 能力端口：由 `extra` 的
+//! [`InstallerCatalog`] 解析安装器构建并下载到实例目录，以 `core` 构造的无头命令
+//! 执行安装器；安装器输出经 [`LogRecorder`] 写入实例控制台日志并推送实时事件。
+//! 安装完成后由 [`CoreInstanceService::apply_installed_server`] 重新检查实例目录并
+//! 写回启动配置。
+//!
+//! 安装在后台任务中执行，宿主通过 `status` 轮询阶段与结果。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+use sealantern_core::instance::{Instance, InstanceId};
+use sealantern_core::process::{Daemon, Terminal, TerminalStream};
+use sealantern_core::provisioning::{
+    InstallerCommandRequest, InstallerKind, build_installer_command,
+};
+use sealantern_extra::catalog::{CatalogBuild, InstallerCatalog};
+use sealantern_extra::java::detect_java_installations;
+use sealantern_infra::download::{DownloadManager, DownloadPriority, TaskOptions};
+use sealantern_interface::installer::{InstallerRequest, InstallerStage, InstallerTaskInfo};
+use sealantern_interface::server::ServerState;
+use sealantern_interface::{
+    InstallerService, InstallerServiceError, InstanceService, ServerService,
+};
+
+use super::{CoreInstanceService, LogRecorder};
+use crate::error::InstallerError;
+
+/// 基于目录解析、实例控制台日志与实例记录的安装器执行服务实现。
+pub struct CoreInstallerService {
+    inner: Arc<InstallerRunner>,
+}
+
+/// 后台安装任务共享的状态。
+struct InstallerRunner {
+    instance_service: Arc<CoreInstanceService>,
+    server: Arc<dyn ServerService>,
+    catalog: InstallerCatalog,
+    /// 每个实例最近一次安装任务。
+    tasks: Mutex<HashMap<String, InstallerTaskInfo>>,
+}
+
+/// 一次安装的输入（开始安装时已校验）。
+struct InstallJob {
+    id: InstanceId,
+    instance: Instance,
+    kind: InstallerKind,
+    game_version: String,
+    loader_version: Option<String>,
+    java_path: Option<PathBuf>,
+}
+
+impl CoreInstallerService {
+    /// 使用全部上游目录构造服务。
+    pub fn new(instance_service: Arc<CoreInstanceService>, server: Arc<dyn ServerService>) -> Self {
+        Self::with_catalog(instance_service, server, InstallerCatalog::global())
+    }
+
+    /// 使用指定目录构造服务（便于测试注入）。
+    pub fn with_catalog(
+        instance_service: Arc<CoreInstanceService>,
+        server: Arc<dyn ServerService>,
+        catalog: InstallerCatalog,
+    ) -> Self {
+        Self {
+            inner: Arc::new(InstallerRunner {
+                instance_service,
+                server,
+                catalog,
+                tasks: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    async fn start_inner(
+        &self,
+        id: &InstanceId,
+        request: InstallerRequest,
+    ) -> Result<InstallerTaskInfo, InstallerError> {
+        let instance = self
+            .inner
+            .instance_service
+            .find(id)
+            .await?
+            .ok_or(InstallerError::InstanceNotFound)?;
+        let core_type = non_blank(request.core_type).unwrap_or_else(|| instance.core_type.clone());
+        let kind = InstallerKind::from_core_type(&core_type)
+            .ok_or(InstallerError::UnsupportedCore(core_type))?;
+        let game_version = non_blank(request.game_version)
+            .or_else(|| non_blank(Some(instance.game_version.clone())))
+            .ok_or(InstallerError::InvalidInput)?;
+        if self.inner.server.status(id).await?.state != ServerState::Stopped {
+            return Err(InstallerError::ServerRunning);
+        }
+
+        let info = InstallerTaskInfo {
+            instance_id: id.as_str().to_owned(),
+            stage: InstallerStage::Resolving,
+            core_type: kind.as_str().to_owned(),
+            game_version: game_version.clone(),
+            loader_version: None,
+            installer: None,
+            exit_code: None,
+            adopted_launch_profile: None,
+            error: None,
+            is_finished: false,
+        };
+        {
+            let mut tasks = self.inner.tasks_lock()?;
+            if tasks.get(id.as_str()).is_some_and(|task| !task.is_finished) {
+                return Err(InstallerError::AlreadyInstalling);
+            }
+            tasks.insert(id.as_str().to_owned(), info.clone());
+        }
+
+        let job = InstallJob {
+            id: id.clone(),
+            instance,
+            kind,
+            game_version,
+            loader_version: non_blank(request.loader_version),
+            java_path: request
+                .java_path
+                .filter(|path| !path.as_os_str().is_empty()),
+        };
+        let runner = self.inner.clone();
+        tokio::spawn(async move {
+            let result = runner.install(&job).await;
+            runner.finish(&job.id, result);
+        });
+        Ok(info)
+    }
+}
+
+impl InstallerRunner {
+    fn tasks_lock(
+        &self,
+    ) -> Result<std::sync::MutexGuard<'_, HashMap<String, InstallerTaskInfo>>, InstallerError> {
+        self.tasks
+            .lock()
+            .map_err(|_| InstallerError::OperationFailed {
+                source: Box::new(std::io::Error::other("installer tasks lock poisoned")),
+            })
+    }
+
+    /// 更新实例的安装任务信息。
+    fn update(&self, id: &InstanceId, update: impl FnOnce(&mut InstallerTaskInfo)) {
+        if let Ok(mut tasks) = self.tasks_lock()
+            && let Some(task) = tasks.get_mut(id.as_str())
+        {
+            update(task);
+        }
+    }
+
+    fn finish(&self, id: &InstanceId, result: Result<Option<String>, InstallerError>) {
+        match &result {
+            Ok(profile) => tracing::info!(
+                target: "sealantern.application.installer",
+                instance_id = %id.as_str(),
+                adopted_launch_profile = ?profile,
+                "loader installation completed"
+            ),
+            Err(error) => tracing::warn!(
+                target: "sealantern.application.installer",
+                instance_id = %id.as_str(),
+                error = %error,
+                "loader installation failed"
+            ),
+        }
+        self.update(id, |task| {
+            task.is_finished = true;
+            match result {
+                Ok(profile) => {
+                    task.stage = InstallerStage::Completed;
+                    task.adopted_launch_profile = profile;
+                }
+                Err(error) => {
+                    task.stage = InstallerStage::Failed;
+                    task.error = Some(error.to_string());
+                }
+            }
+        });
+    }
+
+    /// 解析、下载并执行安装器，返回采纳的启动方式。
+    async fn install(&self, job: &InstallJob) -> Result<Option<String>, InstallerError> {
+        let build = self
+            .catalog
+            .resolve(job.kind.as_str(), &job.game_version, job.loader_version.as_deref())
+            .await?;
+        let java = resolve_java(job, &build).await?;
+        self.update(&job.id, |task| {
+            task.stage = InstallerStage::Downloading;
+            task.loader_version = Some(build.build.clone());
+            task.installer = Some(build.file_name.clone());
+        });
+
+        // 文件名来自上游，只取最后一段，避免写到实例目录之外。
+        let file_name = Path::new(&build.file_name)
+            .file_name()
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(format!("{}-installer.jar", job.kind.as_str())));
+        let installer_path = job.instance.directory.join(file_name);
+        let exit = self
+            .download_and_run(job, &build, &java, &installer_path)
+            .await;
+        // 安装器留在目录中会被检查识别为候选服务端，无论成败都删除。
+        if let Err(error) = tokio::fs::remove_file(&installer_path).await
+            && error.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!(
+                target: "sealantern.application.installer",
+                path = %installer_path.display(),
+                error = %error,
+                "failed to remove installer after running it"
+            );
+        }
+        let (code, recorder) = exit?;
+        self.update(&job.id, |task| task.exit_code = code);
+        if code != Some(0) {
+            recorder.append_system_log("安装器执行失败");
+            recorder.shutdown().await;
+            return Err(InstallerError::InstallerExited { code });
+        }
+
+        self.update(&job.id, |task| task.stage = InstallerStage::Inspecting);
+        let result = self
+            .instance_service
+            .apply_installed_server(&job.id, java)
+            .await;
+        match &result {
+            Ok((_, projection)) => {
+                for diagnostic in &projection.diagnostics {
+                    tracing::debug!(
+                        target: "sealantern.application.installer",
+                        instance_id = %job.id.as_str(),
+                        code = %diagnostic.code,
+                        message = %diagnostic.message,
+                        "installed server inspection diagnostic"
+                    );
+                }
+                recorder.append_system_log("安装完成");
+            }
+            Err(_) => recorder.append_system_log("安装完成，但写回启动配置失败"),
+        }
+        recorder.shutdown().await;
+        let (_, projection) = result?;
+        Ok(projection.adopted_launch_profile)
+    }
+
+    /// 下载安装器到实例目录并执行。
+    ///
+    /// 下载经全局下载队列调度，受连接数与带宽限制；大小与摘要按目录给出的
+    /// [`CatalogBuild::integrity`] 校验。
+    async fn download_and_run(
+        &self,
+        job: &InstallJob,
+        build: &CatalogBuild,
+        java: &Path,
+        installer_path: &Path,
+    ) -> Result<(Option<i32>, LogRecorder), InstallerError> {
+        tokio::fs::create_dir_all(&job.instance.directory).await?;
+        // 安装期间服务器必须停止，优先于其他下载。
+        let options = TaskOptions {
+            priority: DownloadPriority::High,
+            ..Default::default()
+        };
+        let (_, status) = DownloadManager::instance()
+            .create_scheduled(
+                std::slice::from_ref(&build.url),
+                &installer_path.to_string_lossy(),
+                8,
+                build.integrity(),
+                options,
+            )
+            .await?;
+        status.wait().await?;
+        self.update(&job.id, |task| task.stage = InstallerStage::Installing);
+        run_installer(job, build, java, installer_path).await
+    }
+}
+
+/// 执行安装器并等待退出，返回退出码与仍在收尾的日志管线。
+async fn run_installer(
+    job: &InstallJob,
+    build: &CatalogBuild,
+    java: &Path,
+    installer_path: &Path,
+) -> Result<(Option<i32>, LogRecorder), InstallerError> {
+    let mut command = build_installer_command(&InstallerCommandRequest {
+        kind: job.kind,
+        java_executable: java,
+        installer_path,
+        working_directory: &job.instance.directory,
+        game_version: &job.game_version,
+        loader_version: &build.build,
+    });
+    command.stdin(std::process::Stdio::null());
+    command.stdout(std::process::Stdio::piped());
+    command.stderr(std::process::Stdio::piped());
+    let mut daemon = Daemon::spawn(&mut command)?;
+    let mut terminal = Terminal::from_daemon_with_input(&mut daemon, false);
+    let recorder = LogRecorder::start_with_banner(
+        job.id.as_str(),
+        &job.instance.directory,
+        terminal.take_output(TerminalStream::Stdout),
+        terminal.take_output(TerminalStream::Stderr),
+        true,
+        None,
+        &format!("正在执行安装器 {}...", build.file_name),
+    )
+    .await;
+    let status = tokio::task::spawn_blocking(move || daemon.wait())
+        .await
+        .map_err(|error| InstallerError::OperationFailed { source: Box::new(error) })?;
+    match status {
+        Ok(status) => Ok((status.code(), recorder)),
+        Err(error) => {
+            recorder.shutdown().await;
+            Err(error.into())
+        }
+    }
+}
+
+/// 选择执行安装器的 Java：请求指定的、实例配置的，否则从本机探测结果中选择
+/// 满足构建最低要求的最低主版本（旧版加载器常不兼容新版 Java），没有满足要求的
+/// 时取最高版本。
+async fn resolve_java(job: &InstallJob, build: &CatalogBuild) -> Result<PathBuf, InstallerError> {
+    if let Some(java) = job
+        .java_path
+        .clone()
+        .or_else(|| job.instance.launch.java_executable.clone())
+    {
+        return Ok(java);
+    }
+    let installations = tokio::task::spawn_blocking(detect_java_installations)
+        .await
+        .map_err(|error| InstallerError::OperationFailed { source: Box::new(error) })?;
+    let required = build.java_major.map(u32::from).unwrap_or(0);
+    installations
+        .iter()
+        .filter(|java| java.major_version >= required)
+        .min_by_key(|java| java.major_version)
+        .or(installations.first())
+        .map(|java| PathBuf::from(&java.path))
+        .ok_or(InstallerError::JavaUnavailable)
+}
+
+fn non_blank(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty())
+}
+
+#[async_trait]
+impl InstallerService for CoreInstallerService {
+    async fn start(
+        &self,
+        id: &InstanceId,
+        request: InstallerRequest,
+    ) -> Result<InstallerTaskInfo, InstallerServiceError> {
+        self.start_inner(id, request).await.map_err(Into::into)
+    }
+
+    async fn status(
+        &self,
+        id: &InstanceId,
+    ) -> Result<Option<InstallerTaskInfo>, InstallerServiceError> {
+        Ok(self.inner.tasks_lock()?.get(id.as_str()).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sealantern_core::instance::{InstanceSpec, LocalLaunch, StartupMode};
+
+    use super::super::test_support::FakeServerService;
+    use super::*;
+
+    fn sample_spec(directory: PathBuf) -> InstanceSpec {
+        InstanceSpec {
+            id: InstanceId::new("installer").expect("valid id"),
+            name: "安装器测试".into(),
+            aliases: Vec::new(),
+            core_type: "vanilla".into(),
+            core_version: "1.20.4".into(),
+            game_version: "1.20.4".into(),
+            directory: directory.clone(),
+            port: 47634,
+            max_memory_mib: 2048,
+            min_memory_mib: 512,
+            created_at_unix_secs: 0,
+            last_started_at_unix_secs: None,
+            server_metadata: None,
+            launch: LocalLaunch {
+                startup_mode: StartupMode::Jar,
+                startup_target: Some(directory.join("server.jar")),
+                custom_command: None,
+                custom_executable: None,
+                custom_arguments: Vec::new(),
+                java_executable: None,
+                jvm_arguments: Vec::new(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_unsupported_cores_and_running_servers() {
+        let temp = tempfile::tempdir().expect("临时目录应创建成功");
+        let instance_service = Arc::new(
+            CoreInstanceService::with_path(temp.path().join("instances.json"))
+                .await
+                .expect("实例服务应创建成功"),
+        );
+        let instance = instance_service
+            .create(sample_spec(temp.path().join("server")))
+            .await
+            .expect("实例应创建成功");
+
+        let stopped = CoreInstallerService::new(
+            instance_service.clone(),
+            Arc::new(FakeServerService::new(ServerState::Stopped)),
+        );
+        assert_eq!(
+            stopped
+                .start(&instance.id, InstallerRequest::default())
+                .await
+                .map(|_| ()),
+            Err(InstallerServiceError::UnsupportedCore)
+        );
+
+        let running = CoreInstallerService::new(
+            instance_service,
+            Arc::new(FakeServerService::new(ServerState::Running)),
+        );
+        let request = InstallerRequest {
+            core_type: Some("forge".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            running.start(&instance.id, request).await.map(|_| ()),
+            Err(InstallerServiceError::ServerRunning)
+        );
+        assert_eq!(running.status(&instance.id).await, Ok(None));
+    }
+}
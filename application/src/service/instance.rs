@@ -10,19 +10,35 @@
 //! 收敛；暴露 [`InstanceService`] 时统一转为接口契约错误 [`InstanceServiceError`]。
 
 use async_trait::async_trait;
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::sync::{OnceLock, Weak};
 
-use sealantern_core::instance::{Instance, InstanceId, InstanceSpec};
+use sealantern_core::app_plugin::ExecutionPrincipal;
+use sealantern_core::instance::{
+    Instance, InstanceExtensionReport, InstanceId, InstanceSpec, InstanceTemplate, PortBinding,
+    PortConflict, PortKind, PortProtocol, PortRegistry,
+};
 use sealantern_core::provisioning::{
     ImportExistingServerRequest, ImportModpackError as CoreModpackError, ImportModpackRequest,
-    SourceType, build_import_spec, infer_source_type, plan_existing_instance, plan_import_modpack,
-    source_directories_equal, validate_source_directory,
+    InstallerKind, ServerInspectionProjection, SourceType, apply_core_upgrade,
+    apply_installed_server, apply_modpack_versions, apply_server_pack_inspection,
+    build_import_spec, infer_source_type, plan_existing_instance, plan_import_modpack,
+    read_curseforge_manifest, source_directories_equal, validate_source_directory,
+};
+use sealantern_extra::config::server::read_config_snapshot;
+use sealantern_extra::config::{InstanceRegistry, ServerPropertiesManager};
+use sealantern_extra::market::{
+    CurseForgeFetcher, ModrinthFetcher, install_curseforge_pack, install_mrpack,
 };
-use sealantern_extra::config::InstanceRegistry;
-use sealantern_infra::archive::extract_zip;
+use sealantern_extra::server::{read_instance_extensions, read_port_bindings};
+use sealantern_infra::archive::{ArchiveError, extract_zip};
+use sealantern_infra::net::{tcp_port_in_use, udp_port_in_use};
 use sealantern_infra::platform::get_app_data_dir;
-use sealantern_interface::{InstanceService, InstanceServiceError};
+use sealantern_interface::installer::InstallerRequest;
+use sealantern_interface::{InstallerService, InstanceService, InstanceServiceError};
 
+use super::config::{SERVER_PROPERTIES_FILE, record_config_edit};
 use crate::error::InstanceError;
 
 /// 实例列表数据文件名，置于应用数据根目录。
@@ -30,9 +46,20 @@ use crate::error::InstanceError;
 /// 沿用历史版本使用的文件名，以保证旧数据文件可被读取迁移。
 const INSTANCES_FILE: &str = "sea_lantern_servers.json";
 
+/// 创建实例时对端口冲突的处理方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PortPolicy {
+    /// 存在冲突即拒绝创建：手动创建时端口由用户显式填写。
+    Reject,
+    /// 游戏端口与 RCON 端口冲突时改用下一个可用端口并写回 server.properties（导入）。
+    AutoAssign,
+}
+
 /// 基于 `core` + `extra` 的实例管理宿主能力实现。
 pub struct CoreInstanceService {
     registry: tokio::sync::Mutex<InstanceRegistry>,
+    /// 导入带加载器的整合包后用于安装加载器服务端的安装器服务（由装配层注入）。
+    loader_installer: OnceLock<Weak<dyn InstallerService>>,
 }
 
 impl CoreInstanceService {
@@ -47,9 +74,44 @@ impl CoreInstanceService {
         let registry = InstanceRegistry::load(path).await?;
         Ok(Self {
             registry: tokio::sync::Mutex::new(registry),
+            loader_installer: OnceLock::new(),
         })
     }
 
+    /// 注入导入整合包后安装加载器所用的安装器服务；只有第一次注入生效。
+    ///
+    /// 安装器服务本身持有实例服务，这里只保留弱引用以免循环引用。
+    pub fn set_loader_installer(&self, installer: Weak<dyn InstallerService>) {
+        let _ = self.loader_installer.set(installer);
+    }
+
+    /// 为刚导入的整合包实例在后台安装加载器服务端。
+    ///
+    /// 实例核心不需要安装器或未注入安装器服务时不做任何事；开始安装失败只记录日志，
+    /// 不影响已完成的导入（用户可在实例页重新安装）。
+    async fn install_pack_loader(&self, instance: &Instance, java_path: PathBuf) {
+        if InstallerKind::from_core_type(&instance.core_type).is_none() {
+            return;
+        }
+        let Some(installer) = self.loader_installer.get().and_then(Weak::upgrade) else {
+            return;
+        };
+        let request = InstallerRequest {
+            core_type: Some(instance.core_type.clone()),
+            game_version: Some(instance.game_version.clone()),
+            loader_version: Some(instance.core_version.clone()),
+            java_path: Some(java_path),
+        };
+        if let Err(error) = installer.start(&instance.id, request).await {
+            tracing::warn!(
+                target: "sealantern.application.instance",
+                instance_id = %instance.id.as_str(),
+                error = %error,
+                "failed to start loader installation for imported modpack"
+            );
+        }
+    }
+
     /// 更新实例的最后启动时间（服务器进程成功拉起后调用）。
     pub async fn update_last_started(&self, id: &InstanceId) -> Result<(), InstanceError> {
         let now = std::time::SystemTime::now()
@@ -66,8 +128,78 @@ impl CoreInstanceService {
         Ok(())
     }
 
-    /// 内部创建实例：core 校验规范化后持久化登记，返回应用层主错误。
-    async fn create_inner(&self, spec: InstanceSpec) -> Result<Instance, InstanceError> {
+    /// 在持锁状态下把模板的启动默认值与分配的端口套用到实例记录并写回。
+    ///
+    /// 基于注册表中的最新记录计算，避免覆盖预览之后发生的其他修改。
+    pub async fn apply_template_launch(
+        &self,
+        id: &InstanceId,
+        template: &InstanceTemplate,
+        port: u16,
+    ) -> Result<Instance, InstanceError> {
+        let mut registry = self.registry.lock().await;
+        let current = registry.get(id).ok_or(InstanceError::NotFound)?;
+        let updated = template.apply_launch(current, port)?;
+        if &updated != current {
+            registry.save_instance(&updated).await?;
+        }
+        Ok(updated)
+    }
+
+    /// 重新检查安装器执行后的实例目录，在持锁状态下采纳生成的启动方式与安装时
+    /// 使用的 Java，刷新核心与游戏版本及元数据快照并写回。
+    ///
+    /// 目录检查为同步文件系统扫描，经 `spawn_blocking` 调度到阻塞线程池。
+    /// 返回更新后的实例与检查投影（诊断与采纳的启动方式）。
+    pub async fn apply_installed_server(
+        &self,
+        id: &InstanceId,
+        java_executable: PathBuf,
+    ) -> Result<(Instance, ServerInspectionProjection), InstanceError> {
+        let mut registry = self.registry.lock().await;
+        let mut spec = registry.get(id).ok_or(InstanceError::NotFound)?.spec();
+        let (spec, projection) = tokio::task::spawn_blocking(move || {
+            let projection = apply_installed_server(&mut spec, &java_executable, None);
+            (spec, projection)
+        })
+        .await
+        .map_err(|_| InstanceError::InspectionPanicked)?;
+        let updated = Instance::new(spec)?;
+        registry.save_instance(&updated).await?;
+        Ok((updated, projection))
+    }
+
+    /// 以替换后的服务端文件更新实例：在持锁状态下改写启动目标、核心与游戏版本，
+    /// 并重新检查新文件刷新元数据快照后写回。
+    ///
+    /// 文件检查为同步 IO，经 `spawn_blocking` 调度到阻塞线程池。
+    pub async fn apply_core_upgrade(
+        &self,
+        id: &InstanceId,
+        server_jar: PathBuf,
+        core_version: String,
+        game_version: String,
+    ) -> Result<(Instance, ServerInspectionProjection), InstanceError> {
+        let mut registry = self.registry.lock().await;
+        let mut spec = registry.get(id).ok_or(InstanceError::NotFound)?.spec();
+        let (spec, projection) = tokio::task::spawn_blocking(move || {
+            let projection =
+                apply_core_upgrade(&mut spec, &server_jar, &core_version, &game_version, None);
+            (spec, projection)
+        })
+        .await
+        .map_err(|_| InstanceError::InspectionPanicked)?;
+        let updated = Instance::new(spec)?;
+        registry.save_instance(&updated).await?;
+        Ok((updated, projection))
+    }
+
+    /// 内部创建实例：core 校验规范化 → 去重 → 端口检查后持久化登记，返回应用层主错误。
+    async fn create_inner(
+        &self,
+        spec: InstanceSpec,
+        ports: PortPolicy,
+    ) -> Result<Instance, InstanceError> {
         let instance = Instance::new(spec)?;
         let mut registry = self.registry.lock().await;
 
@@ -84,6 +216,24 @@ impl CoreInstanceService {
             return Err(InstanceError::AlreadyExists);
         }
 
+        // 读取配置文件与探测端口均为阻塞 IO；持锁期间完成，避免并发创建抢到同一端口
+        let others = registry.list().to_vec();
+        let directory = instance.directory.clone();
+        let before = read_config_snapshot(&directory, SERVER_PROPERTIES_FILE).await;
+        let instance = tokio::task::spawn_blocking(move || resolve_ports(instance, &others, ports))
+            .await
+            .map_err(|_| InstanceError::Internal("port check task failed".to_owned()))??;
+        // 冲突端口改写了 server.properties 时与其他写入路径一样记录修订
+        if read_config_snapshot(&directory, SERVER_PROPERTIES_FILE).await != before {
+            record_config_edit(
+                &directory,
+                SERVER_PROPERTIES_FILE,
+                before,
+                &ExecutionPrincipal::BuiltInHost,
+            )
+            .await;
+        }
+
         registry.save_instance(&instance).await?;
         Ok(instance)
     }
@@ -156,12 +306,111 @@ impl CoreInstanceService {
 
         let plan = plan_existing_instance(import_request).map_err(InstanceError::from)?;
 
-        self.create(plan.instance.spec())
+        self.create_inner(plan.instance.spec(), PortPolicy::AutoAssign)
             .await
-            .map_err(|e| InstanceError::ImportCreateFailed { source: e })
+            .map_err(|error| match error {
+                error @ InstanceError::PortConflict { .. } => error,
+                error => InstanceError::ImportCreateFailed { source: error.into() },
+            })
+    }
+}
+
+/// 主机上是否已有其他进程占用该端口。
+pub(crate) fn host_port_in_use(binding: PortBinding) -> bool {
+    match binding.kind.protocol() {
+        PortProtocol::Tcp => tcp_port_in_use(binding.port),
+        PortProtocol::Udp => udp_port_in_use(binding.port),
+    }
+}
+
+/// 读取各实例配置的端口并登记。
+pub(crate) fn port_registry(instances: &[Instance]) -> PortRegistry {
+    let mut registry = PortRegistry::new();
+    for instance in instances {
+        registry
+            .insert(instance.id.clone(), read_port_bindings(&instance.directory, instance.port));
+    }
+    registry
+}
+
+/// 检查新实例的端口；按策略拒绝或自动改用可用端口，返回端口可能更新后的实例。
+fn resolve_ports(
+    mut instance: Instance,
+    others: &[Instance],
+    policy: PortPolicy,
+) -> Result<Instance, InstanceError> {
+    let registry = port_registry(others);
+    let bindings = read_port_bindings(&instance.directory, instance.port);
+    let conflicts = registry.check(&instance.id, &bindings, host_port_in_use);
+    if conflicts.is_empty() {
+        return Ok(instance);
+    }
+    if policy == PortPolicy::Reject {
+        return Err(port_conflict(conflicts));
+    }
+
+    // 逐个改写冲突的游戏 / RCON 端口；已选定的新端口同样计入占用，避免彼此撞车
+    let mut taken = registry.clone();
+    let mut assigned = bindings.clone();
+    taken.insert(instance.id.clone(), assigned.clone());
+    let mut updates = BTreeMap::new();
+    for binding in &bindings {
+        let key = match binding.kind {
+            PortKind::Server => "server-port",
+            PortKind::Rcon => "rcon.port",
+            PortKind::Query | PortKind::ProxyListener => continue,
+        };
+        let conflicting = conflicts.iter().any(|conflict| {
+            conflict.port == binding.port && conflict.protocol == binding.kind.protocol()
+        });
+        if !conflicting {
+            continue;
+        }
+        let Some(port) =
+            taken.next_free(binding.port.saturating_add(1), binding.kind, host_port_in_use)
+        else {
+            continue;
+        };
+        assigned.push(PortBinding { kind: binding.kind, port });
+        taken.insert(instance.id.clone(), assigned.clone());
+        updates.insert(key.to_owned(), port.to_string());
+        if binding.kind == PortKind::Server {
+            instance.port = port;
+        }
+    }
+    if !updates.is_empty() {
+        ServerPropertiesManager::new(&instance.directory)
+            .write(&updates)
+            .map_err(|error| {
+                tracing::warn!(
+                    target: "sealantern.application.instance",
+                    error = %error,
+                    "failed to write reassigned ports"
+                );
+                InstanceError::ImportFailed
+            })?;
+        tracing::info!(
+            target: "sealantern.application.instance",
+            instance_id = %instance.id.as_str(),
+            ports = ?updates,
+            "reassigned conflicting ports"
+        );
+    }
+
+    // 未能改写的冲突（查询端口、代理监听等）仍然拒绝
+    let bindings = read_port_bindings(&instance.directory, instance.port);
+    let conflicts = registry.check(&instance.id, &bindings, host_port_in_use);
+    if conflicts.is_empty() {
+        Ok(instance)
+    } else {
+        Err(port_conflict(conflicts))
     }
 }
 
+fn port_conflict(mut conflicts: Vec<PortConflict>) -> InstanceError {
+    InstanceError::PortConflict { conflict: conflicts.swap_remove(0) }
+}
+
 #[async_trait]
 impl InstanceService for CoreInstanceService {
     async fn list(&self) -> Result<Vec<Instance>, InstanceServiceError> {
@@ -175,7 +424,9 @@ impl InstanceService for CoreInstanceService {
     }
 
     async fn create(&self, spec: InstanceSpec) -> Result<Instance, InstanceServiceError> {
-        self.create_inner(spec).await.map_err(Into::into)
+        self.create_inner(spec, PortPolicy::Reject)
+            .await
+            .map_err(Into::into)
     }
 
     async fn delete(&self, id: &InstanceId) -> Result<(), InstanceServiceError> {
@@ -190,6 +441,28 @@ impl InstanceService for CoreInstanceService {
         self.update_path_inner(id, path).await.map_err(Into::into)
     }
 
+    async fn port_conflicts(&self) -> Result<Vec<PortConflict>, InstanceServiceError> {
+        let instances = self.list().await?;
+        tokio::task::spawn_blocking(move || port_registry(&instances).conflicts())
+            .await
+            .map_err(|_| InstanceServiceError::OperationFailed)
+    }
+
+    async fn extensions(
+        &self,
+        id: &InstanceId,
+    ) -> Result<InstanceExtensionReport, InstanceServiceError> {
+        let instance = self
+            .find(id)
+            .await?
+            .ok_or(InstanceServiceError::InstanceNotFound)?;
+        tokio::task::spawn_blocking(move || {
+            InstanceExtensionReport::new(read_instance_extensions(&instance.directory))
+        })
+        .await
+        .map_err(|_| InstanceServiceError::OperationFailed)
+    }
+
     async fn import_existing_server(
         &self,
         request: ImportExistingServerRequest,
@@ -207,7 +480,7 @@ impl InstanceService for CoreInstanceService {
         request: ImportModpackRequest,
     ) -> Result<Instance, InstanceServiceError> {
         // 规划导入，构建实例规格（不执行文件操作）。
-        let result = plan_import_modpack(&request).map_err(|error| {
+        let mut result = plan_import_modpack(&request).map_err(|error| {
             tracing::warn!(
                 target: "sealantern.application.instance",
                 error = %error,
@@ -222,12 +495,63 @@ impl InstanceService for CoreInstanceService {
             }
         })?;
 
+        // 客户端整合包只带模组，加载器服务端需在注册实例后另行安装。
+        let mut needs_loader = false;
         match infer_source_type(&request.modpack_path) {
             SourceType::Archive => {
-                // 解压是耗时同步 IO，放到 blocking 线程执行。
                 let archive = request.modpack_path.clone();
-                let destination = result.directory.clone();
-                tokio::task::spawn_blocking(move || extract_zip(archive, destination))
+                let manifest =
+                    tokio::task::spawn_blocking(move || read_curseforge_manifest(&archive))
+                        .await
+                        .map_err(|join_error| {
+                            tracing::error!(
+                                target: "sealantern.application.instance",
+                                error = %join_error,
+                                "modpack manifest task failed"
+                            );
+                            InstanceError::ImportFailed
+                        })?
+                        .map_err(|error| {
+                            tracing::warn!(
+                                target: "sealantern.application.instance",
+                                error = %error,
+                                "modpack manifest is unusable"
+                            );
+                            InstanceError::ImportFailed
+                        })?;
+                if manifest.is_some() {
+                    // CurseForge 客户端整合包：按清单下载服务端需要的模组。
+                    let fetcher = CurseForgeFetcher::global(request.curseforge_api_key.clone());
+                    let install =
+                        install_curseforge_pack(&fetcher, &request.modpack_path, &result.directory)
+                            .await
+                            .map_err(|error| {
+                                tracing::warn!(
+                                    target: "sealantern.application.instance",
+                                    error = %error,
+                                    "CurseForge modpack install failed"
+                                );
+                                InstanceError::ImportFailed
+                            })?;
+                    let manifest = install.manifest;
+                    needs_loader = manifest.loader().is_some();
+                    apply_modpack_versions(
+                        &mut result.spec,
+                        &request,
+                        manifest.loader(),
+                        Some(&manifest.minecraft.version),
+                    );
+                } else {
+                    // 现成的服务端包：解压后经服务端检查识别核心、版本与启动方式。
+                    // 解压与检查是耗时同步 IO，放到 blocking 线程执行。
+                    let archive = request.modpack_path.clone();
+                    let mut spec = result.spec.clone();
+                    let pack_request = request.clone();
+                    result.spec = tokio::task::spawn_blocking(move || {
+                        extract_zip(archive, &spec.directory)?;
+                        apply_server_pack_inspection(&mut spec, &pack_request);
+                        Ok::<_, ArchiveError>(spec)
+                    })
                     .await
                     .map_err(|join_error| {
                         tracing::error!(
@@ -245,6 +569,31 @@ impl InstanceService for CoreInstanceService {
                         );
                         InstanceError::ImportFailed
                     })?;
+                }
+            }
+            SourceType::Mrpack => {
+                // 按索引下载并校验文件，再应用覆盖目录。
+                let index = install_mrpack(
+                    &ModrinthFetcher::global(),
+                    &request.modpack_path,
+                    &result.directory,
+                )
+                .await
+                .map_err(|error| {
+                    tracing::warn!(
+                        target: "sealantern.application.instance",
+                        error = %error,
+                        "mrpack install failed"
+                    );
+                    InstanceError::ImportFailed
+                })?;
+                needs_loader = index.loader().is_some();
+                apply_modpack_versions(
+                    &mut result.spec,
+                    &request,
+                    index.loader(),
+                    index.game_version(),
+                );
             }
             SourceType::JarFile => {
                 // 创建运行目录并复制 jar。
@@ -273,7 +622,13 @@ impl InstanceService for CoreInstanceService {
         }
 
         // 注册实例。
-        self.create_inner(result.spec).await.map_err(Into::into)
+        let instance = self
+            .create_inner(result.spec, PortPolicy::AutoAssign)
+            .await?;
+        if needs_loader {
+            self.install_pack_loader(&instance, request.java_path).await;
+        }
+        Ok(instance)
     }
 }
 
@@ -282,11 +637,14 @@ mod tests {
     use std::fs::{self, File};
     use std::io::Write;
     use std::path::{Path, PathBuf};
+    use std::sync::{Arc, Mutex};
 
     use sealantern_core::instance::{LocalLaunch, StartupMode};
     use sealantern_core::provisioning::ImportExistingServerRequest;
+    use sealantern_extra::config::server::{list_revisions, open_revision_database};
 
-    use sealantern_interface::InstanceServiceError;
+    use sealantern_interface::installer::InstallerTaskInfo;
+    use sealantern_interface::{InstallerServiceError, InstanceServiceError};
 
     use super::*;
 
@@ -422,6 +780,82 @@ mod tests {
         let _ = std::fs::remove_dir_all(path.parent().unwrap());
     }
 
+    #[tokio::test]
+    async fn create_rejects_and_import_reassigns_conflicting_ports() {
+        let root = tempfile::tempdir().expect("temp dir");
+        let service = CoreInstanceService::with_path(root.path().join("servers.json"))
+            .await
+            .expect("load service");
+        let spec = |id: &str| {
+            let mut spec = sample_spec(id);
+            spec.directory = root.path().join(id);
+            spec.launch.startup_target = Some(spec.directory.join("server.jar"));
+            spec.port = 47611;
+            spec
+        };
+
+        service.create(spec("a")).await.expect("first create");
+        assert_eq!(service.create(spec("b")).await, Err(InstanceServiceError::PortConflict));
+        let mut other_port = spec("b");
+        other_port.port = 47621;
+        service
+            .create(other_port)
+            .await
+            .expect("create on a free port");
+
+        // 导入时不拒绝，而是改用下一个可用端口并写回 server.properties
+        let source = root.path().join("imported");
+        fs::create_dir_all(&source).expect("create source dir");
+        write_test_jar(
+            &source.join("server.jar"),
+            "Manifest-Version: 1.0\r\nMain-Class: com.example.DemoServer\r\n\r\n",
+        );
+        fs::write(source.join("server.properties"), "server-port=47611\nmotd=imported\n")
+            .expect("write properties");
+        let imported = service
+            .import_existing_server(import_request(source.clone(), None))
+            .await
+            .expect("import should succeed");
+        assert_ne!(imported.port, 47611);
+        let properties = fs::read_to_string(source.join("server.properties")).expect("read");
+        assert!(properties.contains(&format!("server-port={}", imported.port)));
+        assert!(properties.contains("motd=imported"));
+        // 端口改写与其他配置写入一样记录修订：导入前内容为基线，改写为新修订
+        let database = open_revision_database(&source)
+            .await
+            .expect("revision database");
+        let revisions = list_revisions(&database, Some(SERVER_PROPERTIES_FILE), 10)
+            .await
+            .expect("revisions");
+        assert_eq!(revisions.len(), 2);
+        assert!(
+            revisions[0]
+                .diff
+                .contains(&format!("+server-port={}", imported.port))
+        );
+
+        assert!(
+            service
+                .port_conflicts()
+                .await
+                .expect("conflicts")
+                .is_empty()
+        );
+
+        // 创建后手动改出的冲突随实例列表返回，只标注在相关实例上
+        fs::create_dir_all(root.path().join("b")).expect("create b dir");
+        fs::write(root.path().join("b/server.properties"), "server-port=47611\n")
+            .expect("write properties");
+        let entries = service.list_entries().await.expect("list entries");
+        let conflicted: Vec<_> = entries
+            .iter()
+            .filter(|entry| !entry.port_conflicts.is_empty())
+            .map(|entry| entry.instance.id.as_str())
+            .collect();
+        assert_eq!(conflicted, ["a", "b"]);
+        assert_eq!(entries[0].port_conflicts[0].port, 47611);
+    }
+
     /// 写一个最小可识别服务器 jar：含 `Main-Class` 清单即可被通用检测器识别为可启动 jar。
     fn write_test_jar(path: &Path, manifest: &str) {
         use zip::write::FileOptions;
@@ -515,6 +949,7 @@ mod tests {
             mc_version: Some("1.20.4".into()),
             custom_command: None,
             run_path: std::env::temp_dir().join("sealantern-modpack-run"),
+            curseforge_api_key: None,
         };
 
         let instance = service
@@ -530,4 +965,88 @@ mod tests {
         let _ = std::fs::remove_dir_all(&source);
         let _ = std::fs::remove_dir_all(std::env::temp_dir().join("sealantern-modpack-run"));
     }
+
+    /// 记录收到的安装请求，随后以失败返回的安装器服务。
+    #[derive(Default)]
+    struct RecordingInstaller {
+        requests: Mutex<Vec<(String, InstallerRequest)>>,
+    }
+
+    #[async_trait]
+    impl InstallerService for RecordingInstaller {
+        async fn start(
+            &self,
+            id: &InstanceId,
+            request: InstallerRequest,
+        ) -> Result<InstallerTaskInfo, InstallerServiceError> {
+            self.requests
+                .lock()
+                .expect("requests lock")
+                .push((id.as_str().to_owned(), request));
+            Err(InstallerServiceError::OperationFailed)
+        }
+
+        async fn status(
+            &self,
+            _id: &InstanceId,
+        ) -> Result<Option<InstallerTaskInfo>, InstallerServiceError> {
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn import_mrpack_starts_the_loader_installer() {
+        let root = tempfile::tempdir().expect("temp dir");
+        let pack = root.path().join("pack");
+        fs::create_dir_all(&pack).expect("create pack dir");
+        fs::write(
+            pack.join("modrinth.index.json"),
+            r#"{
+                "formatVersion": 1,
+                "game": "minecraft",
+                "versionId": "1.0.0",
+                "name": "Example",
+                "files": [],
+                "dependencies": { "minecraft": "1.20.1", "fabric-loader": "0.15.11" }
+            }"#,
+        )
+        .expect("write index");
+        let archive = root.path().join("Example.mrpack");
+        sealantern_infra::archive::create_zip(&pack, &archive).expect("create mrpack");
+        let service = CoreInstanceService::with_path(root.path().join("servers.json"))
+            .await
+            .expect("load service");
+        let recorder = Arc::new(RecordingInstaller::default());
+        let installer: Arc<dyn InstallerService> = recorder.clone();
+        service.set_loader_installer(Arc::downgrade(&installer));
+
+        let request = ImportModpackRequest {
+            name: "加载器整合包".into(),
+            modpack_path: archive,
+            java_path: PathBuf::from("java"),
+            max_memory: 2048,
+            min_memory: 1024,
+            port: 25565,
+            startup_mode: "jar".into(),
+            startup_file_path: Some(PathBuf::from("server.jar")),
+            core_type: None,
+            mc_version: None,
+            custom_command: None,
+            run_path: root.path().join("run"),
+            curseforge_api_key: None,
+        };
+        let instance = service
+            .import_modpack(request)
+            .await
+            .expect("安装器启动失败不影响导入");
+
+        assert_eq!(instance.core_type, "fabric");
+        let requests = recorder.requests.lock().expect("requests lock");
+        assert_eq!(requests.len(), 1);
+        let (id, request) = &requests[0];
+        assert_eq!(id, instance.id.as_str());
+        assert_eq!(request.core_type.as_deref(), Some("fabric"));
+        assert_eq!(request.game_version.as_deref(), Some("1.20.1"));
+        assert_eq!(request.loader_version.as_deref(), Some("0.15.11"));
+    }
 }
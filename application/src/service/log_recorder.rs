@@ -9,6 +9,9 @@
 //! [`subscribe_log_events`] 广播，tauri 与 axum 等宿主各自订阅并转成
 //! 自己的传输（前端事件 / SSE）。订阅方消费慢导致的事件丢失可由
 //! `ConsoleService::logs(since)` 拉取补漏。
+//!
+//! 提供 [`PlayerSessionFeed`] 时，每行进程输出（空行过滤之前）同时投递给
+//! 在线玩家跟踪管线，由其解析玩家事件并维护会话。
 
 use sealantern_core::process::TerminalOutput;
 use sealantern_core::process::read_output_lines;
@@ -19,6 +22,8 @@ use std::path::Path;
 use std::sync::OnceLock;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use super::PlayerSessionFeed;
+
 /// 广播通道容量；消费慢时丢弃旧事件，调用方可拉取补漏。
 const LOG_EVENT_CHANNEL_CAPACITY: usize = 1024;
 
@@ -53,11 +58,12 @@ fn publish_log_event(event: LogEvent) {
 
 /// 服务器日志记录管线句柄。
 ///
-/// 持有写入器与读取任务；`shutdown` 收敛写入器（flush 剩余批次），
-/// 读取任务在进程退出（EOF）后自行结束。
+/// 持有写入器与读取任务；`shutdown` 收敛写入器（flush 剩余批次）与
+/// 玩家跟踪，读取任务在进程退出（EOF）后自行结束。
 pub struct LogRecorder {
     instance_id: String,
     writer: Option<LogWriter>,
+    players: Option<PlayerSessionFeed>,
     readers: Vec<tokio::task::JoinHandle<()>>,
 }
 
@@ -69,12 +75,37 @@ impl LogRecorder {
     ///
     /// `drop_empty_line` 为 `true` 时丢弃空白行（进程输出的空行通常无信息量）；
     /// 为 `false` 时原样保留（部分服务器可能输出有意义的空行）。
+    ///
+    /// `players` 提供时，进程输出行同时投递给在线玩家跟踪管线。
     pub async fn start(
         instance_id: impl Into<String>,
         directory: &Path,
         stdout: Option<TerminalOutput>,
         stderr: Option<TerminalOutput>,
         drop_empty_line: bool,
+        players: Option<PlayerSessionFeed>,
+    ) -> Self {
+        Self::start_with_banner(
+            instance_id,
+            directory,
+            stdout,
+            stderr,
+            drop_empty_line,
+            players,
+            "服务器启动中...",
+        )
+        .await
+    }
+
+    /// 同 [`start`](Self::start)，以 `banner` 作为启动说明日志（如安装器执行）。
+    pub async fn start_with_banner(
+        instance_id: impl Into<String>,
+        directory: &Path,
+        stdout: Option<TerminalOutput>,
+        stderr: Option<TerminalOutput>,
+        drop_empty_line: bool,
+        players: Option<PlayerSessionFeed>,
+        banner: &str,
     ) -> Self {
         let instance_id = instance_id.into();
         let database = match open_log_database(directory).await {
@@ -96,8 +127,12 @@ impl LogRecorder {
         for output in [stdout, stderr].into_iter().flatten() {
             let instance_id = instance_id.clone();
             let writer = writer.clone();
+            let players = players.clone();
             readers.push(tokio::task::spawn_blocking(move || {
                 let _ = read_output_lines(output, |line| {
+                    if let Some(players) = &players {
+                        players.observe(line);
+                    }
                     let line = line.to_string();
                     if drop_empty_line && line.trim().is_empty() {
                         return;
@@ -131,7 +166,7 @@ impl LogRecorder {
         if let Some(writer) = &writer {
             let instance_id = instance_id.clone();
             let timestamp = current_timestamp_secs();
-            let line = "服务器启动中...".to_owned();
+            let line = banner.to_owned();
             writer.submit(
                 LogSource::SeaLantern,
                 line.clone(),
@@ -149,7 +184,7 @@ impl LogRecorder {
             );
         }
 
-        Self { instance_id, writer, readers }
+        Self { instance_id, writer, players, readers }
     }
 
     /// 追加一条 Sea Lantern 来源日志（如停止 / 错误说明），
@@ -178,13 +213,16 @@ impl LogRecorder {
         );
     }
 
-    /// 收敛管线：flush 剩余日志批次并结束写入任务。
+    /// 收敛管线：flush 剩余日志批次并结束写入任务，收尾在线玩家会话。
     ///
     /// 读取任务依赖进程退出后的 EOF 自行结束，无需在此等待。
     pub async fn shutdown(mut self) {
         if let Some(writer) = self.writer.take() {
             writer.shutdown().await;
         }
+        if let Some(players) = self.players.take() {
+            players.shutdown().await;
+        }
         self.readers.clear();
     }
 }
@@ -231,7 +269,8 @@ mod tests {
         let stdout = terminal.take_output(TerminalStream::Stdout);
 
         let mut receiver = subscribe_log_events();
-        let recorder = LogRecorder::start("server-a", directory.path(), stdout, None, true).await;
+        let recorder =
+            LogRecorder::start("server-a", directory.path(), stdout, None, true, None).await;
         // 给读取任务调度窗口，确保进程输出被读取后再收敛。
         tokio::time::sleep(std::time::Duration::from_millis(200)).await;
         let _ = daemon.wait().expect("子进程应正常退出");
@@ -0,0 +1,652 @@
+//! 市场资源安装服务实现。
+//!
+//! 实现 [`sealantern_interface::MarketInstallService`] 能力端口：安装目标由实例记录
+//! 与服务端元数据推断，规划与下载校验复用 `extra::market::install`，安装记录保存在
+//! 服务器目录下（见 `extra::market::installed`）。更新检查、替换与回滚复用
+//! `extra::market::update`。同一服务内的安装、卸载、更新与回滚串行执行，
+//! 避免并发修改同一份记录与同一目录。
+//!
+//! 错误分层：内部以应用层主错误 [`MarketInstallError`] 为源头，暴露
+//! [`MarketInstallService`] 时统一转为接口契约错误 [`MarketInstallServiceError`]。
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sealantern_core::instance::{Instance, InstanceId};
+use sealantern_extra::market::{
+    CurseForgeFetcher, ExtensionUpdate, Fetcher, HangarFetcher, InstallPlan, InstallTarget,
+    InstalledResource, MarketSource, ModrinthFetcher, SpigetFetcher, UpdateRollback, apply_updates,
+    check_updates, dependents, execute_install, file_conflicts, plan_install,
+    read_installed_resources, read_update_rollbacks, record_installed_resources,
+    record_update_rollbacks, remove_installed_resource, rollback_update,
+};
+use sealantern_infra::fs::remove_if_exists;
+use sealantern_interface::market::{
+    MarketExtensionFile, MarketInstallRequest, MarketUpdateRequest,
+};
+use sealantern_interface::{InstanceService, MarketInstallService, MarketInstallServiceError};
+
+use super::{CoreInstanceService, CoreSettingsService};
+use crate::error::MarketInstallError;
+
+/// 基于各市场抓取器的资源安装服务实现。
+pub struct CoreMarketInstallService {
+    instance_service: Arc<CoreInstanceService>,
+    fetchers: Vec<(MarketSource, Arc<dyn Fetcher>)>,
+    /// 提供 CurseForge API 密钥；每次操作按当前设置构造 CurseForge 抓取器。
+    settings: Option<Arc<CoreSettingsService>>,
+    operation_lock: tokio::sync::Mutex<()>,
+}
+
+impl CoreMarketInstallService {
+    /// 使用全局网络客户端的各市场抓取器构造服务；CurseForge API 密钥取自设置。
+    pub fn new(
+        instance_service: Arc<CoreInstanceService>,
+        settings: Arc<CoreSettingsService>,
+    ) -> Self {
+        let mut service = Self::with_fetchers(
+            instance_service,
+            vec![
+                (MarketSource::Modrinth, Arc::new(ModrinthFetcher::global())),
+                (MarketSource::Spiget, Arc::new(SpigetFetcher::global())),
+                (MarketSource::Hangar, Arc::new(HangarFetcher::global())),
+            ],
+        );
+        service.settings = Some(settings);
+        service
+    }
+
+    /// 使用指定的抓取器构造服务（便于测试注入）。
+    pub fn with_fetchers(
+        instance_service: Arc<CoreInstanceService>,
+        fetchers: Vec<(MarketSource, Arc<dyn Fetcher>)>,
+    ) -> Self {
+        Self {
+            instance_service,
+            fetchers,
+            settings: None,
+            operation_lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// 按设置中当前的 API 密钥构造 CurseForge 抓取器；未关联设置时返回 `None`。
+    async fn curseforge_fetcher(&self) -> Option<CurseForgeFetcher> {
+        let settings = self.settings.as_ref()?;
+        Some(CurseForgeFetcher::global(settings.curseforge_api_key().await))
+    }
+
+    /// 当前可用的全部抓取器。
+    async fn fetchers(&self) -> Vec<(MarketSource, Arc<dyn Fetcher>)> {
+        let mut fetchers = self.fetchers.clone();
+        if let Some(curseforge) = self.curseforge_fetcher().await {
+            fetchers.push((MarketSource::CurseForge, Arc::new(curseforge)));
+        }
+        fetchers
+    }
+
+    async fn fetcher(&self, source: MarketSource) -> Result<Arc<dyn Fetcher>, MarketInstallError> {
+        self.fetchers()
+            .await
+            .into_iter()
+            .find(|(candidate, _)| *candidate == source)
+            .map(|(_, fetcher)| fetcher)
+            .ok_or(MarketInstallError::InvalidInput { reason: "market source is not available" })
+    }
+
+    async fn find_instance(&self, id: &InstanceId) -> Result<Instance, MarketInstallError> {
+        self.instance_service
+            .find(id)
+            .await?
+            .ok_or(MarketInstallError::InstanceNotFound)
+    }
+
+    async fn plan_inner(
+        &self,
+        instance: &Instance,
+        request: &MarketInstallRequest,
+        installed: &[InstalledResource],
+    ) -> Result<InstallPlan, MarketInstallError> {
+        let project_id = request.project_id.trim();
+        if project_id.is_empty() {
+            return Err(MarketInstallError::InvalidInput { reason: "project id is empty" });
+        }
+        let version_id = request
+            .version_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|version_id| !version_id.is_empty());
+        let mut plan = plan_install(
+            self.fetcher(request.source).await?.as_ref(),
+            request.source,
+            &InstallTarget::for_instance(instance),
+            installed,
+            project_id,
+            version_id,
+        )
+        .await?;
+        // 预览同样报告会覆盖手动放入文件的步骤
+        let conflicts = file_conflicts(&plan, &instance.directory, installed).await;
+        plan.issues.extend(conflicts);
+        Ok(plan)
+    }
+
+    async fn install_inner(
+        &self,
+        instance_id: &InstanceId,
+        request: MarketInstallRequest,
+    ) -> Result<Vec<InstalledResource>, MarketInstallError> {
+        let instance = self.find_instance(instance_id).await?;
+        let _guard = self.operation_lock.lock().await;
+        let installed = read_installed_resources(&instance.directory).await?;
+        let plan = self.plan_inner(&instance, &request, &installed).await?;
+        let fetcher = self.fetcher(request.source).await?;
+        let records =
+            execute_install(fetcher.as_ref(), &plan, &instance.directory, &installed).await?;
+        record_installed_resources(&instance.directory, records.clone()).await?;
+        Ok(records)
+    }
+
+    async fn uninstall_inner(
+        &self,
+        instance_id: &InstanceId,
+        source: MarketSource,
+        project_id: &str,
+    ) -> Result<Vec<InstalledResource>, MarketInstallError> {
+        let instance = self.find_instance(instance_id).await?;
+        let _guard = self.operation_lock.lock().await;
+        let mut remaining = read_installed_resources(&instance.directory).await?;
+        if !remaining
+            .iter()
+            .any(|resource| resource.is(source, project_id))
+        {
+            return Err(MarketInstallError::ResourceNotInstalled);
+        }
+        let blocking = dependents(&remaining, source, project_id);
+        if !blocking.is_empty() {
+            return Err(MarketInstallError::DependencyInUse {
+                dependents: blocking
+                    .into_iter()
+                    .map(|resource| resource.project_id.clone())
+                    .collect(),
+            });
+        }
+
+        // 先卸载目标，再依次清理不再被任何资源需要的自动安装依赖
+        let mut removed = Vec::new();
+        let mut candidates = vec![project_id.to_owned()];
+        while let Some(candidate) = candidates.pop() {
+            let Some(index) = remaining
+                .iter()
+                .position(|resource| resource.is(source, &candidate))
+            else {
+                continue;
+            };
+            let resource = &remaining[index];
+            let is_target = candidate == project_id;
+            if !is_target
+                && (resource.explicit || !dependents(&remaining, source, &candidate).is_empty())
+            {
+                continue;
+            }
+            let resource = remaining.remove(index);
+            remove_if_exists(instance.directory.join(resource.relative_path())).await?;
+            remove_installed_resource(&instance.directory, source, &candidate).await?;
+            candidates.extend(resource.dependencies.iter().cloned());
+            removed.push(resource);
+        }
+        Ok(removed)
+    }
+
+    async fn check_updates_inner(
+        &self,
+        instance: &Instance,
+        installed: &[InstalledResource],
+    ) -> Result<Vec<ExtensionUpdate>, MarketInstallError> {
+        Ok(check_updates(
+            &self.fetchers().await,
+            &InstallTarget::for_instance(instance),
+            &instance.directory,
+            installed,
+        )
+        .await?)
+    }
+
+    async fn update_inner(
+        &self,
+        instance_id: &InstanceId,
+        request: MarketUpdateRequest,
+    ) -> Result<Vec<UpdateRollback>, MarketInstallError> {
+        if request.files.is_empty() {
+            return Err(MarketInstallError::InvalidInput { reason: "no files selected" });
+        }
+        let instance = self.find_instance(instance_id).await?;
+        let _guard = self.operation_lock.lock().await;
+        let installed = read_installed_resources(&instance.directory).await?;
+        // 以服务端重新检查的结果为准，不信任客户端提交的下载地址
+        let selected: Vec<ExtensionUpdate> = self
+            .check_updates_inner(&instance, &installed)
+            .await?
+            .into_iter()
+            .filter(|update| {
+                request
+                    .files
+                    .iter()
+                    .any(|file| update.is_file(file.directory, &file.file_name))
+            })
+            .collect();
+        let applied =
+            apply_updates(&self.fetchers().await, &instance.directory, &selected, &installed)
+                .await?;
+        record_installed_resources(&instance.directory, applied.records).await?;
+        record_update_rollbacks(&instance.directory, applied.rollbacks.clone()).await?;
+        Ok(applied.rollbacks)
+    }
+
+    async fn rollback_inner(
+        &self,
+        instance_id: &InstanceId,
+        file: MarketExtensionFile,
+    ) -> Result<UpdateRollback, MarketInstallError> {
+        let instance = self.find_instance(instance_id).await?;
+        let _guard = self.operation_lock.lock().await;
+        let rollback =
+            rollback_update(&instance.directory, file.directory, &file.file_name).await?;
+        if let Some(previous) = &rollback.previous_record {
+            record_installed_resources(&instance.directory, vec![previous.clone()]).await?;
+        }
+        Ok(rollback)
+    }
+}
+
+#[async_trait]
+impl MarketInstallService for CoreMarketInstallService {
+    async fn plan(
+        &self,
+        instance_id: &InstanceId,
+        request: MarketInstallRequest,
+    ) -> Result<InstallPlan, MarketInstallServiceError> {
+        let instance = self.find_instance(instance_id).await?;
+        let installed = read_installed_resources(&instance.directory)
+            .await
+            .map_err(MarketInstallError::from)?;
+        Ok(self.plan_inner(&instance, &request, &installed).await?)
+    }
+
+    async fn install(
+        &self,
+        instance_id: &InstanceId,
+        request: MarketInstallRequest,
+    ) -> Result<Vec<InstalledResource>, MarketInstallServiceError> {
+        Ok(self.install_inner(instance_id, request).await?)
+    }
+
+    async fn installed(
+        &self,
+        instance_id: &InstanceId,
+    ) -> Result<Vec<InstalledResource>, MarketInstallServiceError> {
+        let instance = self.find_instance(instance_id).await?;
+        Ok(read_installed_resources(&instance.directory)
+            .await
+            .map_err(MarketInstallError::from)?)
+    }
+
+    async fn uninstall(
+        &self,
+        instance_id: &InstanceId,
+        source: MarketSource,
+        project_id: &str,
+    ) -> Result<Vec<InstalledResource>, MarketInstallServiceError> {
+        Ok(self
+            .uninstall_inner(instance_id, source, project_id.trim())
+            .await?)
+    }
+
+    async fn check_updates(
+        &self,
+        instance_id: &InstanceId,
+    ) -> Result<Vec<ExtensionUpdate>, MarketInstallServiceError> {
+        let instance = self.find_instance(instance_id).await?;
+        let installed = read_installed_resources(&instance.directory)
+            .await
+            .map_err(MarketInstallError::from)?;
+        Ok(self.check_updates_inner(&instance, &installed).await?)
+    }
+
+    async fn update(
+        &self,
+        instance_id: &InstanceId,
+        request: MarketUpdateRequest,
+    ) -> Result<Vec<UpdateRollback>, MarketInstallServiceError> {
+        Ok(self.update_inner(instance_id, request).await?)
+    }
+
+    async fn update_rollbacks(
+        &self,
+        instance_id: &InstanceId,
+    ) -> Result<Vec<UpdateRollback>, MarketInstallServiceError> {
+        let instance = self.find_instance(instance_id).await?;
+        Ok(read_update_rollbacks(&instance.directory)
+            .await
+            .map_err(MarketInstallError::from)?)
+    }
+
+    async fn rollback_update(
+        &self,
+        instance_id: &InstanceId,
+        file: MarketExtensionFile,
+    ) -> Result<UpdateRollback, MarketInstallServiceError> {
+        Ok(self.rollback_inner(instance_id, file).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+    use std::path::PathBuf;
+
+    use sealantern_core::instance::{InstanceSpec, LocalLaunch, StartupMode};
+    use sealantern_extra::config::SettingsManager;
+    use sealantern_extra::market::{
+        DependencyKind, MarketError, MarketResource, ResourceInfo, SearchResult, Version,
+        VersionDependency, VersionFile,
+    };
+    use sealantern_extra::models::{NullablePatch, PartialAppSettings};
+    use sealantern_infra::download::DownloadStatus;
+    use sealantern_interface::SettingsService;
+
+    use super::super::network_settings::NoopNetworkSettingsRuntime;
+    use super::*;
+
+    /// 内存中的市场：每个项目一个版本，文件内容为项目 ID。
+    struct FakeFetcher {
+        versions: HashMap<&'static str, Version>,
+    }
+
+    impl FakeFetcher {
+        fn new(projects: &[(&'static str, &[&str])]) -> Self {
+            Self::release(projects, 1)
+        }
+
+        /// 每个项目的唯一版本为第 `release` 次发布。
+        fn release(projects: &[(&'static str, &[&str])], release: u32) -> Self {
+            let versions = projects
+                .iter()
+                .map(|(project_id, dependencies)| {
+                    let version = Version {
+                        id: format!("{project_id}-{release}"),
+                        name: "1.0".to_owned(),
+                        version_number: "1.0".to_owned(),
+                        game_versions: vec!["1.20.4".to_owned()],
+                        loaders: vec!["paper".to_owned()],
+                        downloads: 0,
+                        files: vec![VersionFile {
+                            url: format!("https://cdn.example/{release}/{project_id}"),
+                            filename: format!("{project_id}.jar"),
+                            size: project_id.len() as u64,
+                            primary: true,
+                            hashes: BTreeMap::new(),
+                        }],
+                        dependencies: dependencies
+                            .iter()
+                            .map(|dependency| VersionDependency {
+                                project_id: Some((*dependency).to_owned()),
+                                version_id: None,
+                                name: None,
+                                kind: DependencyKind::Required,
+                            })
+                            .collect(),
+                    };
+                    (*project_id, version)
+                })
+                .collect();
+            Self { versions }
+        }
+    }
+
+    #[async_trait]
+    impl Fetcher for FakeFetcher {
+        async fn search(&self, _: &str, _: u32, _: u32) -> Result<SearchResult, MarketError> {
+            unimplemented!()
+        }
+
+        async fn get_resource(&self, _: &str) -> Result<ResourceInfo, MarketError> {
+            unimplemented!()
+        }
+
+        async fn get_resource_versions(&self, id: &str) -> Result<Vec<Version>, MarketError> {
+            Ok(self.versions.get(id).cloned().into_iter().collect())
+        }
+
+        async fn download_resource(
+            &self,
+            url: &str,
+            destination: &str,
+        ) -> Result<Arc<DownloadStatus>, MarketError> {
+            let project_id = url.rsplit('/').next().unwrap();
+            tokio::fs::write(destination, project_id).await.unwrap();
+            let status = Arc::new(DownloadStatus::new(0));
+            status.mark_completed();
+            Ok(status)
+        }
+
+        async fn get_random_resources(&self, _: u32) -> Result<Vec<MarketResource>, MarketError> {
+            unimplemented!()
+        }
+    }
+
+    fn sample_spec(directory: PathBuf, port: u16) -> InstanceSpec {
+        InstanceSpec {
+            id: InstanceId::new("market").expect("valid id"),
+            name: "市场测试".into(),
+            aliases: Vec::new(),
+            core_type: "paper".into(),
+            core_version: "1.20.4".into(),
+            game_version: "1.20.4".into(),
+            directory: directory.clone(),
+            port,
+            max_memory_mib: 2048,
+            min_memory_mib: 512,
+            created_at_unix_secs: 0,
+            last_started_at_unix_secs: None,
+            server_metadata: None,
+            launch: LocalLaunch {
+                startup_mode: StartupMode::Jar,
+                startup_target: Some(directory.join("server.jar")),
+                custom_command: None,
+                custom_executable: None,
+                custom_arguments: Vec::new(),
+                java_executable: None,
+                jvm_arguments: Vec::new(),
+            },
+        }
+    }
+
+    fn request(project_id: &str) -> MarketInstallRequest {
+        MarketInstallRequest {
+            source: MarketSource::Hangar,
+            project_id: project_id.to_owned(),
+            version_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn installs_records_and_uninstalls_with_orphaned_dependencies() {
+        let temp = tempfile::tempdir().expect("临时目录应创建成功");
+        let server_dir = temp.path().join("server");
+        std::fs::create_dir_all(&server_dir).expect("实例目录应创建成功");
+        let instance_service = Arc::new(
+            CoreInstanceService::with_path(temp.path().join("instances.json"))
+                .await
+                .expect("实例服务应创建成功"),
+        );
+        let instance = instance_service
+            .create(sample_spec(server_dir.clone(), 47631))
+            .await
+            .expect("实例应创建成功");
+        let fetcher = FakeFetcher::new(&[
+            ("addon", &["core-api"]),
+            ("other", &["core-api"]),
+            ("core-api", &["libs"]),
+            ("libs", &[]),
+        ]);
+        let service = CoreMarketInstallService::with_fetchers(
+            instance_service,
+            vec![(MarketSource::Hangar, Arc::new(fetcher))],
+        );
+        let id = &instance.id;
+
+        let plan = service
+            .plan(id, request("addon"))
+            .await
+            .expect("应生成计划");
+        assert_eq!(plan.steps.len(), 3);
+        assert!(!server_dir.join("plugins").exists());
+
+        let records = service.install(id, request("addon")).await.expect("应安装");
+        assert_eq!(records.len(), 3);
+        assert_eq!(
+            std::fs::read_to_string(server_dir.join("plugins/core-api.jar")).unwrap(),
+            "core-api"
+        );
+        // 已安装的依赖不会重复下载
+        let records = service.install(id, request("other")).await.expect("应安装");
+        assert_eq!(records.len(), 1);
+        assert_eq!(service.installed(id).await.unwrap().len(), 4);
+
+        assert_eq!(
+            service
+                .uninstall(id, MarketSource::Hangar, "core-api")
+                .await,
+            Err(MarketInstallServiceError::DependencyInUse)
+        );
+        let removed = service
+            .uninstall(id, MarketSource::Hangar, "addon")
+            .await
+            .expect("应卸载");
+        assert_eq!(removed.len(), 1);
+        assert!(!server_dir.join("plugins/addon.jar").exists());
+
+        // 最后一个依赖方卸载后，自动安装的依赖链一并清理
+        let removed = service
+            .uninstall(id, MarketSource::Hangar, "other")
+            .await
+            .expect("应卸载");
+        assert_eq!(
+            removed
+                .iter()
+                .map(|resource| resource.project_id.as_str())
+                .collect::<Vec<_>>(),
+            ["other", "core-api", "libs"]
+        );
+        assert!(service.installed(id).await.unwrap().is_empty());
+        assert_eq!(
+            service.uninstall(id, MarketSource::Hangar, "other").await,
+            Err(MarketInstallServiceError::ResourceNotInstalled)
+        );
+        assert_eq!(
+            service.install(id, request(" ")).await,
+            Err(MarketInstallServiceError::InvalidInput)
+        );
+    }
+
+    #[tokio::test]
+    async fn updates_recorded_resources_and_rolls_back() {
+        let temp = tempfile::tempdir().expect("临时目录应创建成功");
+        let server_dir = temp.path().join("server");
+        std::fs::create_dir_all(&server_dir).expect("实例目录应创建成功");
+        let instance_service = Arc::new(
+            CoreInstanceService::with_path(temp.path().join("instances.json"))
+                .await
+                .expect("实例服务应创建成功"),
+        );
+        let instance = instance_service
+            .create(sample_spec(server_dir.clone(), 47632))
+            .await
+            .expect("实例应创建成功");
+        let id = &instance.id;
+        let projects: &[(&'static str, &[&str])] = &[("solo", &[])];
+        let service = CoreMarketInstallService::with_fetchers(
+            instance_service.clone(),
+            vec![(MarketSource::Hangar, Arc::new(FakeFetcher::new(projects)))],
+        );
+        service.install(id, request("solo")).await.expect("应安装");
+        std::fs::write(server_dir.join("plugins/manual.jar"), "manual").unwrap();
+
+        let updates = service.check_updates(id).await.expect("应检查更新");
+        assert_eq!(updates.len(), 2);
+        assert!(updates.iter().all(|update| !update.update_available));
+
+        // 市场发布新版本后，安装记录中的资源可更新
+        let service = CoreMarketInstallService::with_fetchers(
+            instance_service,
+            vec![(MarketSource::Hangar, Arc::new(FakeFetcher::release(projects, 2)))],
+        );
+        let updates = service.check_updates(id).await.expect("应检查更新");
+        let solo = updates
+            .iter()
+            .find(|update| update.file_name == "solo.jar")
+            .unwrap();
+        assert!(solo.update_available);
+        assert_eq!(solo.latest.as_ref().unwrap().version_id, "solo-2");
+        assert_eq!(
+            service
+                .update(id, MarketUpdateRequest { files: Vec::new() })
+                .await,
+            Err(MarketInstallServiceError::InvalidInput)
+        );
+
+        let file = MarketExtensionFile {
+            directory: solo.directory,
+            file_name: "solo.jar".to_owned(),
+        };
+        let rollbacks = service
+            .update(id, MarketUpdateRequest { files: vec![file.clone()] })
+            .await
+            .expect("应更新");
+        assert_eq!(rollbacks.len(), 1);
+        assert_eq!(service.installed(id).await.unwrap()[0].version_id, "solo-2");
+        assert_eq!(service.update_rollbacks(id).await.unwrap().len(), 1);
+
+        let rollback = service
+            .rollback_update(id, file.clone())
+            .await
+            .expect("应回滚");
+        assert_eq!(rollback.previous_file_name, "solo.jar");
+        assert_eq!(service.installed(id).await.unwrap()[0].version_id, "solo-1");
+        assert!(server_dir.join("plugins/solo.jar").exists());
+        assert_eq!(
+            service.rollback_update(id, file).await,
+            Err(MarketInstallServiceError::RollbackUnavailable)
+        );
+    }
+
+    #[tokio::test]
+    async fn curseforge_fetcher_uses_the_configured_api_key() {
+        let temp = tempfile::tempdir().expect("临时目录应创建成功");
+        let manager = SettingsManager::load(temp.path().join("settings.json"))
+            .await
+            .expect("设置应加载");
+        let settings = Arc::new(CoreSettingsService::with_manager_and_runtime(
+            manager,
+            Arc::new(NoopNetworkSettingsRuntime),
+        ));
+        let instance_service = Arc::new(
+            CoreInstanceService::with_path(temp.path().join("instances.json"))
+                .await
+                .expect("实例服务应创建成功"),
+        );
+        let service = CoreMarketInstallService::new(instance_service, settings.clone());
+        assert!(!service.curseforge_fetcher().await.unwrap().is_configured());
+
+        settings
+            .update_partial(PartialAppSettings {
+                curseforge_api_key: NullablePatch::Set(Some("cf-key".to_owned())),
+                ..Default::default()
+            })
+            .await
+            .expect("设置应更新");
+        // 密钥在设置中修改后无需重建服务即生效
+        assert!(service.curseforge_fetcher().await.unwrap().is_configured());
+        assert!(service.fetcher(MarketSource::CurseForge).await.is_ok());
+    }
+}
@@ -3,38 +3,64 @@
 //! 存放各类宿主能力的默认实现（如 [`CoreInstanceService`]、[`CoreSystemService`]、
 //! [`CoreServerService`]、[`CoreDownloadService`]、[`CoreCronTaskService`]、
 //! [`CoreJavaService`]、[`CoreServerCatalogService`]、[`CoreProvisioningService`]、
-//! [`CoreOnlineTunnelService`]、[`CoreUpdateInstallService`]），实现
+//! [`CoreOnlineTunnelService`]、[`CoreUpdateInstallService`]、[`CorePlayerService`]、
+//! [`CoreCommandLibraryService`]、[`CoreConfigService`]、[`CoreInstanceTemplateService`]、
+//! [`CoreMarketInstallService`]、[`CoreExtensionService`]、[`CoreInstallerService`]、
+//! [`CoreServerUpgradeService`]），实现
 //! `interface` 的能力端口，由 `services` 装配层组装进全局容器。
 
 mod catalog;
+mod command_capture;
+mod command_library;
+mod config;
 mod console;
 mod cron;
 mod download;
+mod extension;
+mod installer;
 mod instance;
 mod java;
 mod log_recorder;
+mod market_install;
 mod network_settings;
 mod online_tunnel;
+mod player;
+mod player_tracker;
 mod provisioning;
 mod proxy_monitoring;
 mod server;
 mod settings;
 mod system;
+mod template;
+#[cfg(test)]
+mod test_support;
 mod update;
 mod update_install;
+mod upgrade;
 
 pub use catalog::CoreServerCatalogService;
+pub use command_library::CoreCommandLibraryService;
+pub use config::CoreConfigService;
 pub use console::CoreConsoleService;
 pub use cron::CoreCronTaskService;
 pub use download::CoreDownloadService;
+pub use extension::CoreExtensionService;
+pub use installer::CoreInstallerService;
 pub use instance::CoreInstanceService;
 pub use java::CoreJavaService;
 pub use log_recorder::{LogEvent, LogRecorder, subscribe_log_events};
+pub use market_install::CoreMarketInstallService;
 pub use online_tunnel::CoreOnlineTunnelService;
+pub use player::CorePlayerService;
+pub use player_tracker::{
+    PlayerEvent, PlayerEventKind, PlayerSessionFeed, PlayerTracker, subscribe_player_events,
+};
 pub use provisioning::CoreProvisioningService;
 pub use proxy_monitoring::ProxyMonitoringService;
 pub use server::CoreServerService;
 pub use settings::CoreSettingsService;
 pub use system::CoreSystemService;
+pub use template::CoreInstanceTemplateService;
 pub use update::CoreUpdateCheckService;
 pub use update_install::CoreUpdateInstallService;
+pub use upgrade::CoreServerUpgradeService;
@@ -0,0 +1,181 @@
+//! 在线玩家服务实现。
+//!
+//! 实现 [`sealantern_interface::PlayerService`] 能力端口：在线集合来自
+//! [`PlayerTracker`]（由服务器进程服务在启动时挂接），会话历史按实例目录
+//! 读取 `extra::server::player` 的会话库。
+//!
+//! 错误分层：内部以应用层主错误 [`PlayerError`] 为源头，暴露
+//! [`PlayerService`] 时统一转为接口契约错误 [`PlayerServiceError`]。
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sealantern_core::instance::{Instance, InstanceId, PlayerName};
+use sealantern_extra::server::player::{open_player_database, read_sessions};
+use sealantern_interface::player::{OnlinePlayer, PlayerSessionRecord};
+use sealantern_interface::{InstanceService, PlayerService, PlayerServiceError};
+
+use super::{CoreInstanceService, PlayerTracker};
+use crate::error::PlayerError;
+
+/// 会话历史默认返回的最近会话数。
+const DEFAULT_SESSION_LIMIT: i64 = 100;
+/// 单次查询允许的最大会话数。
+const MAX_SESSION_LIMIT: i64 = 1000;
+
+/// 基于控制台事件跟踪的在线玩家服务实现。
+pub struct CorePlayerService {
+    instance_service: Arc<CoreInstanceService>,
+    tracker: Arc<PlayerTracker>,
+}
+
+impl CorePlayerService {
+    /// 创建共享指定在线集合的玩家服务。
+    pub fn new(instance_service: Arc<CoreInstanceService>, tracker: Arc<PlayerTracker>) -> Self {
+        Self { instance_service, tracker }
+    }
+
+    async fn find_instance(&self, id: &InstanceId) -> Result<Instance, PlayerError> {
+        self.instance_service
+            .find(id)
+            .await
+            .map_err(PlayerError::from)?
+            .ok_or(PlayerError::InstanceNotFound)
+    }
+}
+
+#[async_trait]
+impl PlayerService for CorePlayerService {
+    async fn online(&self, id: &InstanceId) -> Result<Vec<OnlinePlayer>, PlayerServiceError> {
+        self.find_instance(id).await?;
+        Ok(self.tracker.online(id.as_str()))
+    }
+
+    async fn sessions(
+        &self,
+        id: &InstanceId,
+        player: Option<String>,
+        limit: Option<i64>,
+    ) -> Result<Vec<PlayerSessionRecord>, PlayerServiceError> {
+        let limit = limit.unwrap_or(DEFAULT_SESSION_LIMIT);
+        if !(1..=MAX_SESSION_LIMIT).contains(&limit) {
+            return Err(PlayerError::InvalidInput.into());
+        }
+        let player = player
+            .map(PlayerName::new)
+            .transpose()
+            .map_err(|_| PlayerError::InvalidInput)?;
+
+        let instance = self.find_instance(id).await?;
+        let database = open_player_database(&instance.directory)
+            .await
+            .map_err(PlayerError::from)?;
+        let sessions = read_sessions(&database, player.as_ref().map(PlayerName::as_str), limit)
+            .await
+            .map_err(PlayerError::from)?;
+
+        Ok(sessions
+            .into_iter()
+            .map(|session| PlayerSessionRecord {
+                id: session.id,
+                player: session.player,
+                uuid: session.uuid,
+                joined_at: session.joined_at,
+                left_at: session.left_at,
+                duration_secs: session.duration_secs,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use sealantern_core::instance::{InstanceSpec, LocalLaunch, StartupMode};
+    use sealantern_extra::server::player::{close_session, open_session};
+
+    use super::*;
+
+    fn sample_spec(id: &str, directory: PathBuf) -> InstanceSpec {
+        InstanceSpec {
+            id: InstanceId::new(id).expect("valid id"),
+            name: format!("服务器-{id}"),
+            aliases: Vec::new(),
+            core_type: "paper".into(),
+            core_version: "1.20.4".into(),
+            game_version: "1.20.4".into(),
+            directory: directory.clone(),
+            port: 25565,
+            max_memory_mib: 2048,
+            min_memory_mib: 512,
+            created_at_unix_secs: 0,
+            last_started_at_unix_secs: None,
+            server_metadata: None,
+            launch: LocalLaunch {
+                startup_mode: StartupMode::Jar,
+                startup_target: Some(directory.join("server.jar")),
+                custom_command: None,
+                custom_executable: None,
+                custom_arguments: Vec::new(),
+                java_executable: None,
+                jvm_arguments: Vec::new(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn sessions_reads_history_and_validates_input() {
+        let temp = tempfile::tempdir().expect("临时目录应创建成功");
+        let instance_dir = temp.path().join("server-a");
+        std::fs::create_dir_all(&instance_dir).expect("实例目录应创建成功");
+        let instance_service = Arc::new(
+            CoreInstanceService::with_path(temp.path().join("instances.json"))
+                .await
+                .expect("实例服务应创建成功"),
+        );
+        instance_service
+            .create(sample_spec("a", instance_dir.clone()))
+            .await
+            .expect("实例应创建成功");
+
+        let database = open_player_database(&instance_dir)
+            .await
+            .expect("会话库应初始化");
+        let id = open_session(&database, "Steve", None, 100)
+            .await
+            .expect("开启会话");
+        close_session(&database, id, 160).await.expect("收尾会话");
+        open_session(&database, "Alex", None, 200)
+            .await
+            .expect("开启会话");
+
+        let service = CorePlayerService::new(instance_service, Arc::new(PlayerTracker::new()));
+        let id = InstanceId::new("a").expect("valid id");
+
+        let sessions = service
+            .sessions(&id, Some("Steve".to_owned()), None)
+            .await
+            .expect("读取应成功");
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].duration_secs, Some(60));
+
+        assert!(service.online(&id).await.expect("在线查询").is_empty());
+        assert!(matches!(
+            service
+                .sessions(&id, Some("bad name".to_owned()), None)
+                .await,
+            Err(PlayerServiceError::InvalidInput)
+        ));
+        assert!(matches!(
+            service.sessions(&id, None, Some(0)).await,
+            Err(PlayerServiceError::InvalidInput)
+        ));
+        assert!(matches!(
+            service
+                .online(&InstanceId::new("missing").expect("valid id"))
+                .await,
+            Err(PlayerServiceError::InstanceNotFound)
+        ));
+    }
+}
@@ -0,0 +1,423 @@
+//! 在线玩家跟踪管线。
+//!
+//! 挂在日志记录管线（[`LogRecorder`](super::LogRecorder)）旁路：读取任务把
+//! 每行进程输出交给 [`PlayerSessionFeed`]，由单个异步任务用 `core` 的
+//! [`PlayerEventParser`] 解析加入 / 离开、`list` 输出与 UUID 解析行，维护
+//! 实例的在线集合，并通过 `extra::server::player` 持久化会话历史。
+//!
+//! 在线集合由 [`PlayerTracker`] 按实例汇总供查询；变更以
+//! [`subscribe_player_events`] 广播，与 `subscribe_log_events` 并列，
+//! 宿主各自订阅并转成自己的传输。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use sealantern_core::server::{PlayerConsoleEvent, PlayerEventParser};
+use sealantern_extra::server::player::{
+    close_open_sessions, close_session, open_player_database, open_session, update_session_uuid,
+};
+use sealantern_infra::persistence::SqliteDatabase;
+use sealantern_interface::player::OnlinePlayer;
+use serde::Serialize;
+
+/// 广播通道容量；消费慢时丢弃旧事件，调用方可查询在线集合补齐。
+const PLAYER_EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// 在线玩家变更类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerEventKind {
+    /// 玩家加入（含 `list` 对账补入的玩家）。
+    Joined,
+    /// 玩家离开（含 `list` 对账移除与服务器停止时的收尾）。
+    Left,
+}
+
+/// 在线玩家变更事件。
+#[derive(Debug, Clone, Serialize)]
+pub struct PlayerEvent {
+    /// 所属实例 ID。
+    pub instance_id: String,
+    /// 变更类型。
+    pub kind: PlayerEventKind,
+    /// 玩家名称。
+    pub name: String,
+    /// 玩家 UUID；未解析时为 `None`。
+    pub uuid: Option<String>,
+    /// 事件时刻（Unix 秒）。
+    pub timestamp: i64,
+    /// 变更后的在线人数。
+    pub online_count: usize,
+}
+
+static PLAYER_EVENT_BROADCAST: OnceLock<tokio::sync::broadcast::Sender<PlayerEvent>> =
+    OnceLock::new();
+
+/// 订阅全局在线玩家事件流。
+///
+/// 每次调用返回一个独立的接收端；多个宿主（tauri / axum）可各自订阅。
+pub fn subscribe_player_events() -> tokio::sync::broadcast::Receiver<PlayerEvent> {
+    PLAYER_EVENT_BROADCAST
+        .get_or_init(|| {
+            let (sender, _receiver) =
+                tokio::sync::broadcast::channel(PLAYER_EVENT_CHANNEL_CAPACITY);
+            sender
+        })
+        .subscribe()
+}
+
+fn publish_player_event(event: PlayerEvent) {
+    if let Some(sender) = PLAYER_EVENT_BROADCAST.get() {
+        let _ = sender.send(event);
+    }
+}
+
+/// 各实例在线集合的汇总视图。
+///
+/// 只保存对外快照；会话行号等内部状态由各实例的跟踪任务独占。
+#[derive(Default)]
+pub struct PlayerTracker {
+    online: Mutex<HashMap<String, Vec<OnlinePlayer>>>,
+}
+
+impl PlayerTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 为一次服务器运行启动玩家跟踪任务，返回供读取任务投递输出行的句柄。
+    ///
+    /// 会话库打开失败时降级为只维护内存在线集合，并记录错误日志。
+    pub fn attach(
+        self: &Arc<Self>,
+        instance_id: impl Into<String>,
+        directory: &Path,
+    ) -> PlayerSessionFeed {
+        let instance_id = instance_id.into();
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let handle = tokio::spawn(run_session_task(
+            self.clone(),
+            instance_id,
+            directory.to_path_buf(),
+            receiver,
+        ));
+        PlayerSessionFeed {
+            sender,
+            handle: Arc::new(Mutex::new(Some(handle))),
+        }
+    }
+
+    /// 当前在线玩家，按加入时刻升序。
+    pub fn online(&self, instance_id: &str) -> Vec<OnlinePlayer> {
+        self.online
+            .lock()
+            .map(|online| online.get(instance_id).cloned().unwrap_or_default())
+            .unwrap_or_default()
+    }
+
+    fn publish_snapshot(&self, instance_id: &str, players: &HashMap<String, TrackedPlayer>) {
+        let mut snapshot: Vec<OnlinePlayer> = players
+            .iter()
+            .map(|(name, player)| OnlinePlayer {
+                name: name.clone(),
+                uuid: player.uuid.clone(),
+                joined_at: player.joined_at,
+            })
+            .collect();
+        snapshot.sort_by(|left, right| {
+            left.joined_at
+                .cmp(&right.joined_at)
+                .then_with(|| left.name.cmp(&right.name))
+        });
+        if let Ok(mut online) = self.online.lock() {
+            if snapshot.is_empty() {
+                online.remove(instance_id);
+            } else {
+                online.insert(instance_id.to_owned(), snapshot);
+            }
+        }
+    }
+}
+
+/// 跟踪任务接收的命令。
+enum FeedCommand {
+    Line(String),
+    Shutdown,
+}
+
+/// 单次服务器运行的玩家跟踪句柄。
+///
+/// 投递为同步调用（无界通道），可在阻塞读取任务中直接使用；句柄可克隆
+/// 共享给 stdout / stderr 两个读取任务。
+#[derive(Clone)]
+pub struct PlayerSessionFeed {
+    sender: tokio::sync::mpsc::UnboundedSender<FeedCommand>,
+    handle: Arc<Mutex<Option<tokio::task::JoinHandle<()>>>>,
+}
+
+impl PlayerSessionFeed {
+    /// 投递一行进程输出（立即返回）。
+    pub fn observe(&self, line: &str) {
+        let _ = self.sender.send(FeedCommand::Line(line.to_owned()));
+    }
+
+    /// 收敛跟踪任务：剩余在线玩家全部按离开处理并收尾会话。
+    pub async fn shutdown(self) {
+        let _ = self.sender.send(FeedCommand::Shutdown);
+        let handle = self.handle.lock().ok().and_then(|mut handle| handle.take());
+        if let Some(handle) = handle {
+            let _ = handle.await;
+        }
+    }
+}
+
+/// 跟踪任务内的在线玩家状态。
+struct TrackedPlayer {
+    uuid: Option<String>,
+    joined_at: i64,
+    session_id: Option<i64>,
+}
+
+/// 单实例跟踪任务的可变状态。
+struct SessionState {
+    tracker: Arc<PlayerTracker>,
+    instance_id: String,
+    database: Option<SqliteDatabase>,
+    players: HashMap<String, TrackedPlayer>,
+    /// 已解析但尚未加入的玩家 UUID（UUID 行早于加入行输出）。
+    pending_uuids: HashMap<String, String>,
+}
+
+async fn run_session_task(
+    tracker: Arc<PlayerTracker>,
+    instance_id: String,
+    directory: PathBuf,
+    mut receiver: tokio::sync::mpsc::UnboundedReceiver<FeedCommand>,
+) {
+    let database = match open_player_database(&directory).await {
+        Ok(database) => {
+            // 上次异常退出遗留的会话无法得知离开时刻，按本次启动时刻收尾。
+            if let Err(error) = close_open_sessions(&database, current_timestamp_secs()).await {
+                tracing::warn!(
+                    target: "sealantern.application.player_tracker",
+                    instance_id,
+                    error = %error,
+                    "failed to close stale player sessions"
+                );
+            }
+            Some(database)
+        }
+        Err(error) => {
+            tracing::error!(
+                target: "sealantern.application.player_tracker",
+                instance_id,
+                directory = %directory.display(),
+                error = %error,
+                "failed to open player session database; sessions are not persisted"
+            );
+            None
+        }
+    };
+    let mut state = SessionState {
+        tracker,
+        instance_id,
+        database,
+        players: HashMap::new(),
+        pending_uuids: HashMap::new(),
+    };
+    let mut parser = PlayerEventParser::new();
+
+    while let Some(FeedCommand::Line(line)) = receiver.recv().await {
+        if let Some(event) = parser.feed(&line) {
+            state.apply(event).await;
+        }
+    }
+
+    // 服务器停止：剩余在线玩家全部离开。
+    let names: Vec<String> = state.players.keys().cloned().collect();
+    for name in names {
+        state.leave(&name).await;
+    }
+}
+
+impl SessionState {
+    async fn apply(&mut self, event: PlayerConsoleEvent) {
+        match event {
+            PlayerConsoleEvent::Joined { name } => self.join(name.as_str()).await,
+            PlayerConsoleEvent::Left { name } => self.leave(name.as_str()).await,
+            PlayerConsoleEvent::UuidResolved { name, uuid } => {
+                self.resolve_uuid(name.as_str(), uuid).await
+            }
+            PlayerConsoleEvent::OnlineList { players, .. } => {
+                let listed: Vec<String> = players
+                    .iter()
+                    .map(|name| name.as_str().to_owned())
+                    .collect();
+                let departed: Vec<String> = self
+                    .players
+                    .keys()
+                    .filter(|name| !listed.contains(name))
+                    .cloned()
+                    .collect();
+                for name in departed {
+                    self.leave(&name).await;
+                }
+                for name in listed {
+                    self.join(&name).await;
+                }
+            }
+        }
+    }
+
+    async fn join(&mut self, name: &str) {
+        if self.players.contains_key(name) {
+            return;
+        }
+        let timestamp = current_timestamp_secs();
+        let uuid = self.pending_uuids.remove(name);
+        let session_id = match &self.database {
+            Some(database) => open_session(database, name, uuid.as_deref(), timestamp)
+                .await
+                .inspect_err(|error| self.report_store_error("open player session", error))
+                .ok(),
+            None => None,
+        };
+        self.players.insert(
+            name.to_owned(),
+            TrackedPlayer {
+                uuid: uuid.clone(),
+                joined_at: timestamp,
+                session_id,
+            },
+        );
+        self.publish(PlayerEventKind::Joined, name, uuid, timestamp);
+    }
+
+    async fn leave(&mut self, name: &str) {
+        let Some(player) = self.players.remove(name) else {
+            return;
+        };
+        let timestamp = current_timestamp_secs();
+        if let (Some(database), Some(session_id)) = (&self.database, player.session_id)
+            && let Err(error) = close_session(database, session_id, timestamp).await
+        {
+            self.report_store_error("close player session", &error);
+        }
+        self.publish(PlayerEventKind::Left, name, player.uuid, timestamp);
+    }
+
+    async fn resolve_uuid(&mut self, name: &str, uuid: String) {
+        let Some(player) = self.players.get_mut(name) else {
+            self.pending_uuids.insert(name.to_owned(), uuid);
+            return;
+        };
+        player.uuid = Some(uuid.clone());
+        if let (Some(database), Some(session_id)) = (&self.database, player.session_id)
+            && let Err(error) = update_session_uuid(database, session_id, &uuid).await
+        {
+            self.report_store_error("update player session uuid", &error);
+        }
+        self.tracker
+            .publish_snapshot(&self.instance_id, &self.players);
+    }
+
+    fn publish(&self, kind: PlayerEventKind, name: &str, uuid: Option<String>, timestamp: i64) {
+        self.tracker
+            .publish_snapshot(&self.instance_id, &self.players);
+        publish_player_event(PlayerEvent {
+            instance_id: self.instance_id.clone(),
+            kind,
+            name: name.to_owned(),
+            uuid,
+            timestamp,
+            online_count: self.players.len(),
+        });
+    }
+
+    fn report_store_error(&self, operation: &'static str, error: &dyn std::fmt::Display) {
+        tracing::warn!(
+            target: "sealantern.application.player_tracker",
+            instance_id = %self.instance_id,
+            operation,
+            error = %error,
+            "player session store operation failed"
+        );
+    }
+}
+
+/// 当前 Unix 时间戳（秒）。
+fn current_timestamp_secs() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use sealantern_extra::server::player::{open_player_database, read_sessions};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn feed_tracks_online_set_and_persists_sessions() {
+        let directory = tempfile::tempdir().expect("临时目录应创建成功");
+        let tracker = Arc::new(PlayerTracker::new());
+        let mut receiver = subscribe_player_events();
+        let feed = tracker.attach("players-a", directory.path());
+
+        feed.observe("[10:00:00] [User Authenticator #1/INFO]: UUID of player Steve is 069a79f4-44e9-4726-a5be-fca90e38aaf5");
+        feed.observe("[10:00:00] [Server thread/INFO]: Steve joined the game");
+        feed.observe("[10:00:01] [Server thread/INFO]: Alex joined the game");
+        feed.observe("[10:00:02] [Server thread/INFO]: Alex left the game");
+        feed.observe("[10:00:03] [Server thread/INFO]: There are 2 of a max of 20 players online: Steve, Notch");
+
+        // 等待任务处理完投递的行：Steve、Alex 加入，Alex 离开，Notch 对账补入。
+        let mut kinds = Vec::new();
+        while kinds.len() < 4 {
+            let event = tokio::time::timeout(std::time::Duration::from_secs(2), receiver.recv())
+                .await
+                .expect("事件应及时广播")
+                .expect("广播不应关闭");
+            if event.instance_id == "players-a" {
+                kinds.push((event.kind, event.name));
+            }
+        }
+        assert_eq!(
+            kinds,
+            [
+                (PlayerEventKind::Joined, "Steve".to_owned()),
+                (PlayerEventKind::Joined, "Alex".to_owned()),
+                (PlayerEventKind::Left, "Alex".to_owned()),
+                (PlayerEventKind::Joined, "Notch".to_owned()),
+            ]
+        );
+        let mut online: Vec<String> = tracker
+            .online("players-a")
+            .into_iter()
+            .map(|player| player.name)
+            .collect();
+        online.sort();
+        assert_eq!(online, ["Notch", "Steve"]);
+        let steve = tracker
+            .online("players-a")
+            .into_iter()
+            .find(|player| player.name == "Steve")
+            .expect("Steve 应在线");
+        assert_eq!(steve.uuid.as_deref(), Some("069a79f4-44e9-4726-a5be-fca90e38aaf5"));
+
+        feed.shutdown().await;
+        assert!(tracker.online("players-a").is_empty(), "停止后在线集合应清空");
+
+        let database = open_player_database(directory.path())
+            .await
+            .expect("会话库应存在");
+        let sessions = read_sessions(&database, None, 10)
+            .await
+            .expect("读取应成功");
+        assert_eq!(sessions.len(), 3);
+        assert!(sessions.iter().all(|session| session.left_at.is_some()));
+    }
+}
@@ -24,19 +24,36 @@ use sealantern_core::process::{
     CommandBuildMode, CommandBuildRequest, Daemon, JavaEnvironment, Terminal, TerminalStream,
     WindowsConsoleEncoding, build_command,
 };
+use sealantern_core::server::{CommandFilter, command_macro::root_command};
+use sealantern_extra::config::ServerPropertiesManager;
 use sealantern_extra::java::{JavaInfo, detect_java_installations};
-use sealantern_interface::server::{ServerSnapshot, ServerState};
+use sealantern_extra::server::command::{open_command_database, record_command};
+use sealantern_extra::server::log::open_log_database;
+use sealantern_extra::server::probe;
+use sealantern_extra::server::rcon::{RconClient, RconError, read_rcon_settings};
+use sealantern_interface::server::{
+    CaptureOptions, CommandCapture, CommandOrigin, CommandResponse, CommandTransport, ProbePlayer,
+    ServerProbe, ServerQueryInfo, ServerSnapshot, ServerState,
+};
 use sealantern_interface::{InstanceService, ServerService, ServerServiceError, SettingsService};
 use tokio::sync::{Mutex as AsyncMutex, OwnedMutexGuard};
 
 use crate::error::ServerError;
 
-use super::{CoreInstanceService, CoreSettingsService, LogRecorder};
+use super::command_capture::{CapturePlan, latest_sequence};
+use super::command_library::expand_console_input;
+use super::{
+    CoreInstanceService, CoreSettingsService, LogRecorder, PlayerTracker, subscribe_log_events,
+};
 
 /// 优雅停止时等待进程退出的最长时长。
 const STOP_GRACEFUL_TIMEOUT: Duration = Duration::from_secs(10);
 /// 状态轮询间隔。
 const POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// 状态探测（SLP / Query 各自）的超时。
+const PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+/// RCON 连接、认证与单条命令的超时。
+const RCON_TIMEOUT: Duration = Duration::from_secs(5);
 
 /// 一个受管服务器进程：守护进程 + 已转移的标准流终端 + 日志记录管线。
 struct ManagedProcess {
@@ -113,6 +130,8 @@ pub struct CoreServerService {
     /// Java”的空列表）时填充，之后复用，避免每次启动都扫描文件系统。`spawn_blocking`
     /// 任务被取消或 panic（探测任务本身失败）时不写入缓存，下次启动可重试。
     java_installations: Mutex<Option<Vec<JavaInfo>>>,
+    /// 各实例在线玩家集合（由日志管线旁路的跟踪任务维护）。
+    player_tracker: Arc<PlayerTracker>,
 }
 
 impl CoreServerService {
@@ -129,9 +148,15 @@ impl CoreServerService {
             stopping: Mutex::new(HashSet::new()),
             lifecycle_locks: Mutex::new(HashMap::new()),
             java_installations: Mutex::new(None),
+            player_tracker: Arc::new(PlayerTracker::new()),
         }
     }
 
+    /// 在线玩家集合的共享句柄（供玩家服务查询）。
+    pub fn player_tracker(&self) -> &Arc<PlayerTracker> {
+        &self.player_tracker
+    }
+
     async fn lock_lifecycle(&self, id: &InstanceId) -> Result<OwnedMutexGuard<()>, ServerError> {
         let lock = {
             let mut locks = self
@@ -430,7 +455,159 @@ impl ServerService for CoreServerService {
     }
 
     async fn send_command(&self, id: &InstanceId, command: &str) -> Result<(), ServerServiceError> {
-        self.send_command_inner(id, command).await
+        self.dispatch_console_input(id, command, CommandOrigin::Operator)
+            .await
+    }
+
+    async fn send_command_via(
+        &self,
+        id: &InstanceId,
+        command: &str,
+        transport: CommandTransport,
+    ) -> Result<CommandResponse, ServerServiceError> {
+        let output = match transport {
+            CommandTransport::Stdin => {
+                self.dispatch_console_input(id, command, CommandOrigin::Operator)
+                    .await?;
+                None
+            }
+            CommandTransport::Rcon => {
+                let instance = self.find_instance(id).await?;
+                Some(send_command_over_rcon(&instance, command).await?)
+            }
+        };
+        Ok(CommandResponse { transport, output })
+    }
+
+    async fn send_command_and_capture(
+        &self,
+        id: &InstanceId,
+        command: &str,
+        options: CaptureOptions,
+    ) -> Result<CommandCapture, ServerServiceError> {
+        let plan = CapturePlan::from_options(&options)?;
+        let instance = self.find_instance(id).await?;
+        let database = open_log_database(&instance.directory)
+            .await
+            .map_err(|e| ServerError::OperationFailed { source: Box::new(e) })?;
+
+        // 先订阅再取游标、发送，保证命令之后的输出不会落在订阅之前。
+        let mut receiver = subscribe_log_events();
+        let baseline = latest_sequence(&database)
+            .await
+            .map_err(|e| ServerError::OperationFailed { source: Box::new(e) })?;
+        self.dispatch_console_input(id, command, options.origin)
+            .await?;
+        Ok(plan
+            .collect(&mut receiver, id.as_str(), baseline, &database)
+            .await)
+    }
+
+    async fn probe(&self, id: &InstanceId) -> Result<ServerProbe, ServerServiceError> {
+        let instance = self.find_instance(id).await?;
+        if self.status_for_instance(&instance)?.state == ServerState::Stopped {
+            return Err(ServerError::InvalidState.into());
+        }
+        let target = ProbeTarget::resolve(&instance);
+
+        let status = probe::ping(&target.host, target.port, PROBE_TIMEOUT)
+            .await
+            .map_err(|e| ServerError::OperationFailed { source: Box::new(e) })?;
+        // Query 只是补充信息，失败不影响状态探测结果。
+        let query = match target.query_port {
+            Some(port) => match probe::query(&target.host, port, PROBE_TIMEOUT).await {
+                Ok(query) => Some(ServerQueryInfo {
+                    port,
+                    game_type: query.game_type,
+                    map: query.map,
+                    plugins: query.plugins,
+                    players: query.players,
+                }),
+                Err(error) => {
+                    tracing::debug!(
+                        target: "sealantern.application.server",
+                        instance_id = id.as_str(),
+                        error = %error,
+                        "server query probe failed"
+                    );
+                    None
+                }
+            },
+            None => None,
+        };
+
+        Ok(ServerProbe {
+            instance_id: id.as_str().to_owned(),
+            host: target.host,
+            port: target.port,
+            motd: status.motd,
+            version: status.version_name,
+            protocol: status.protocol,
+            online_players: status.online_players,
+            max_players: status.max_players,
+            sample: status
+                .sample
+                .into_iter()
+                .map(|player| ProbePlayer { name: player.name, id: player.id })
+                .collect(),
+            latency_ms: status.latency_ms,
+            legacy: status.legacy,
+            query,
+        })
+    }
+}
+
+/// 经 RCON 执行一条命令并返回响应文本。
+///
+/// 每次调用独立建连并认证：控制台命令频率低，省去连接保活与断线重连的状态。
+async fn send_command_over_rcon(instance: &Instance, command: &str) -> Result<String, ServerError> {
+    let settings = read_rcon_settings(&instance.directory)
+        .map_err(|e| ServerError::OperationFailed { source: Box::new(e) })?
+        .ok_or(ServerError::InvalidState)?;
+    let host = ProbeTarget::resolve(instance).host;
+    let rcon_failed = |error: RconError| match error {
+        RconError::CommandTooLong => ServerError::InvalidInput,
+        error => ServerError::OperationFailed { source: Box::new(error) },
+    };
+
+    let mut client = RconClient::connect(&host, settings.port, &settings.password, RCON_TIMEOUT)
+        .await
+        .map_err(rcon_failed)?;
+    client.execute(command).await.map_err(rcon_failed)
+}
+
+/// 状态探测的目标地址，取自实例目录下的 server.properties（RCON 复用其主机解析）。
+struct ProbeTarget {
+    host: String,
+    port: u16,
+    /// 开启 `enable-query` 时的查询端口。
+    query_port: Option<u16>,
+}
+
+impl ProbeTarget {
+    /// 监听地址为空或通配时探测本机回环；端口缺省回退到实例记录。
+    fn resolve(instance: &Instance) -> Self {
+        let raw = ServerPropertiesManager::new(&instance.directory)
+            .read()
+            .map(|properties| properties.raw)
+            .unwrap_or_default();
+        let host = raw
+            .get("server-ip")
+            .map(|ip| ip.trim())
+            .filter(|ip| !ip.is_empty() && *ip != "0.0.0.0" && *ip != "::")
+            .unwrap_or("127.0.0.1")
+            .to_owned();
+        let port = raw
+            .get("server-port")
+            .and_then(|port| port.trim().parse().ok())
+            .unwrap_or(instance.port);
+        let query_port =
+            (raw.get("enable-query").map(|value| value.trim()) == Some("true")).then(|| {
+                raw.get("query.port")
+                    .and_then(|port| port.trim().parse().ok())
+                    .unwrap_or(port)
+            });
+        Self { host, port, query_port }
     }
 }
 
@@ -530,12 +707,16 @@ impl CoreServerService {
             .await
             .map(|settings| settings.console_drop_empty_line)
             .unwrap_or(true);
+        let players = self
+            .player_tracker
+            .attach(id_str.clone(), &instance.directory);
         let recorder = LogRecorder::start(
             id_str.clone(),
             &instance.directory,
             stdout,
             stderr,
             drop_empty_line,
+            Some(players),
         )
         .await;
         if let Some(managed) = self.processes_lock()?.get_mut(&id_str) {
@@ -552,7 +733,7 @@ impl CoreServerService {
         let id_str = id.as_str().to_string();
 
         // 优雅停止：向控制台发送 stop，等待退出。
-        self.send_command_inner(id, "stop").await?;
+        self.write_console_line(id, "stop")?;
         self.mark_stopping(&id_str);
 
         let deadline = Instant::now() + STOP_GRACEFUL_TIMEOUT;
@@ -622,17 +803,76 @@ impl CoreServerService {
 
     /// 为重启流程请求优雅停止，不等待退出或执行超时强杀。
     async fn request_stop_for_restart(&self, id: &InstanceId) -> Result<(), ServerServiceError> {
-        self.send_command_inner(id, "stop").await?;
+        self.write_console_line(id, "stop")?;
         self.mark_stopping(id.as_str());
         Ok(())
     }
 
-    /// 向服务器控制台发送命令（内部实现）。
-    async fn send_command_inner(
+    /// 展开并发送一条控制台输入。
+    ///
+    /// 首词命中实例的别名或宏时按定义展开，否则原样发送；插件来源的输入逐行
+    /// 校验插件命令过滤，任一行被拒绝则整条输入都不发送。操作者输入在发送后
+    /// 记入命令历史，记录失败只告警不影响发送结果。
+    async fn dispatch_console_input(
         &self,
         id: &InstanceId,
-        command: &str,
+        input: &str,
+        origin: CommandOrigin,
     ) -> Result<(), ServerServiceError> {
+        let instance = self.find_instance(id).await?;
+        let database = open_command_database(&instance.directory)
+            .await
+            .map_err(|e| ServerError::OperationFailed { source: Box::new(e) })?;
+        let lines = expand_console_input(&database, input).await?;
+
+        if origin == CommandOrigin::Plugin {
+            let filter = self
+                .settings_service
+                .get()
+                .await
+                .map(|settings| {
+                    CommandFilter::new(
+                        &settings.plugin_allowed_commands,
+                        &settings.plugin_blocked_commands,
+                    )
+                })
+                .map_err(|e| ServerError::OperationFailed { source: Box::new(e) })?;
+            if let Some(line) = lines.iter().find(|line| !filter.permits(&line.command)) {
+                tracing::warn!(
+                    target: "sealantern.application.server",
+                    instance_id = id.as_str(),
+                    command = %root_command(&line.command),
+                    "plugin console command rejected by command filter"
+                );
+                return Err(ServerError::CommandRejected.into());
+            }
+        }
+
+        for line in &lines {
+            if !line.delay.is_zero() {
+                tokio::time::sleep(line.delay).await;
+            }
+            self.write_console_line(id, &line.command)?;
+        }
+
+        if origin == CommandOrigin::Operator
+            && let Err(error) =
+                record_command(&database, input, current_timestamp_secs() as i64).await
+        {
+            tracing::warn!(
+                target: "sealantern.application.server",
+                instance_id = id.as_str(),
+                error = %error,
+                "failed to record console command history"
+            );
+        }
+        Ok(())
+    }
+
+    /// 向服务器控制台写入一行原始命令（不展开宏、不过滤、不记历史）。
+    ///
+    /// 停止与重启直接走这里，避免用户定义的同名别名改变内置行为。
+    fn write_console_line(&self, id: &InstanceId, command: &str) -> Result<(), ServerServiceError> {
         let id_str = id.as_str().to_string();
         let mut processes = self.processes_lock()?;
         let Some(managed) = processes.get_mut(&id_str) else {
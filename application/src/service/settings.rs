@@ -58,7 +58,7 @@ impl CoreSettingsService {
     }
 
     #[cfg(test)]
-    fn with_manager_and_runtime(
+    pub(super) fn with_manager_and_runtime(
         manager: SettingsManager,
         network_runtime: Arc<dyn NetworkSettingsRuntime>,
     ) -> Self {
@@ -100,6 +100,14 @@ impl CoreSettingsService {
             .map_err(Self::contract_error)
     }
 
+    /// 当前配置的 CurseForge API 密钥；设置读取失败时视为未配置。
+    pub async fn curseforge_api_key(&self) -> Option<String> {
+        self.get()
+            .await
+            .ok()
+            .and_then(|settings| settings.curseforge_api_key)
+    }
+
     async fn lock_synchronized_manager(
         &self,
     ) -> Result<tokio::sync::MutexGuard<'_, SettingsManager>, SettingsError> {
@@ -0,0 +1,789 @@
+//! 实例配置模板服务实现。
+//!
+//! 实现 [`sealantern_interface::InstanceTemplateService`] 能力端口：模板由
+//! `extra` 的 [`InstanceTemplateStore`] 以 JSON 持久化，校验与端口分配复用
+//! `core` 的 [`InstanceTemplate`]。批量套用时先为每个实例生成计划——
+//! server.properties 经 [`ServerPropertiesManager::preview_write`]、其他配置文档经
+//! [`ConfigDocumentManager::preview`] 得到修改后的文本——预览直接返回计划的差异，
+//! 套用则写入同一份文本，保证所见即所写。
+//!
+//! 套用按实例逐个进行：实例记录 → 配置文件（并记录修订）→ 定时任务 → 备份设置。
+//! 单个实例失败时在其报告中记录错误并继续处理其余实例；同一实例内已完成的步骤
+//! 不回滚，重新套用同一模板是幂等的。
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sealantern_core::app_plugin::ExecutionPrincipal;
+use sealantern_core::instance::{
+    Instance, InstanceId, InstanceTemplate, TemplateCronAction, TemplateCronTask,
+};
+use sealantern_extra::backup::{BackupContentType, BackupSettings, BackupSettingsManager};
+use sealantern_extra::config::server::unified_diff;
+use sealantern_extra::config::{
+    ConfigDocumentManager, DocumentPatch, InstanceTemplateStore, ServerPropertiesManager,
+};
+use sealantern_extra::server::read_port_bindings;
+use sealantern_infra::fs::write_atomic;
+use sealantern_infra::platform::get_app_data_dir;
+use sealantern_interface::cron::{CronTask, CronTaskAction, CronTaskDraft};
+use sealantern_interface::template::{
+    TemplateFieldChange, TemplateFileDiff, TemplateInstanceReport,
+};
+use sealantern_interface::{
+    CronTaskService, InstanceService, InstanceTemplateService, InstanceTemplateServiceError,
+};
+
+use super::CoreInstanceService;
+use super::config::{ConfigEdit, SERVER_PROPERTIES_FILE, record_config_edits};
+use super::instance::{host_port_in_use, port_registry};
+use crate::error::InstanceTemplateError;
+
+/// 模板 JSON 文件名，置于应用数据根目录。
+const INSTANCE_TEMPLATES_FILE: &str = "instance_templates.json";
+
+/// 单个配置文件的修改计划。
+struct PlannedFile {
+    file: String,
+    before: Option<String>,
+    after: String,
+    diff: String,
+}
+
+/// 模板套用到单个实例的完整计划。
+struct InstancePlan {
+    before: Instance,
+    after: Instance,
+    files: Vec<PlannedFile>,
+    cron_tasks: Vec<TemplateCronTask>,
+    /// 有变化时为（当前设置，套用后设置）。
+    backup: Option<(BackupSettings, BackupSettings)>,
+}
+
+/// 基于 JSON 模板存储与实例 / 定时任务 / 备份设置服务的模板服务实现。
+pub struct CoreInstanceTemplateService {
+    path: PathBuf,
+    /// 备份设置目录；`None` 时使用应用数据目录下的默认位置。
+    backup_settings_dir: Option<PathBuf>,
+    store: tokio::sync::OnceCell<tokio::sync::Mutex<InstanceTemplateStore>>,
+    instance_service: Arc<CoreInstanceService>,
+    cron: Arc<dyn CronTaskService>,
+}
+
+impl CoreInstanceTemplateService {
+    /// 使用应用数据目录中的默认 JSON 文件构造服务。
+    pub fn new(instance_service: Arc<CoreInstanceService>, cron: Arc<dyn CronTaskService>) -> Self {
+        Self::with_paths(
+            get_app_data_dir().join(INSTANCE_TEMPLATES_FILE),
+            None,
+            instance_service,
+            cron,
+        )
+    }
+
+    /// 使用指定模板文件与备份设置目录构造服务，实际加载延迟到首次调用。
+    pub fn with_paths(
+        path: impl Into<PathBuf>,
+        backup_settings_dir: Option<PathBuf>,
+        instance_service: Arc<CoreInstanceService>,
+        cron: Arc<dyn CronTaskService>,
+    ) -> Self {
+        Self {
+            path: path.into(),
+            backup_settings_dir,
+            store: tokio::sync::OnceCell::new(),
+            instance_service,
+            cron,
+        }
+    }
+
+    async fn store(
+        &self,
+    ) -> Result<&tokio::sync::Mutex<InstanceTemplateStore>, InstanceTemplateError> {
+        self.store
+            .get_or_try_init(|| async {
+                InstanceTemplateStore::load(&self.path)
+                    .await
+                    .map(tokio::sync::Mutex::new)
+            })
+            .await
+            .map_err(InstanceTemplateError::from)
+    }
+
+    async fn find_template(&self, id: &str) -> Result<InstanceTemplate, InstanceTemplateError> {
+        let store = self.store().await?.lock().await;
+        store
+            .get(id)
+            .cloned()
+            .ok_or(InstanceTemplateError::TemplateNotFound)
+    }
+
+    fn backup_settings(&self) -> Result<BackupSettingsManager, InstanceTemplateError> {
+        let manager = match &self.backup_settings_dir {
+            Some(directory) => BackupSettingsManager::with_dir(directory),
+            None => BackupSettingsManager::new(),
+        }?;
+        Ok(manager)
+    }
+
+    /// 为每个目标实例生成套用计划，结果与 `instance_ids` 顺序一致。
+    ///
+    /// 端口分配针对整批实例进行，分配失败时整批失败；其余失败只影响对应实例。
+    async fn plan(
+        &self,
+        template: &Arc<InstanceTemplate>,
+        instance_ids: &[InstanceId],
+    ) -> Result<Vec<(InstanceId, Result<InstancePlan, InstanceTemplateError>)>, InstanceTemplateError>
+    {
+        let mut seen = HashSet::new();
+        if instance_ids.is_empty() || !instance_ids.iter().all(|id| seen.insert(id)) {
+            return Err(InstanceTemplateError::InvalidInput {
+                reason: "instance ids must be non-empty and unique".to_owned(),
+            });
+        }
+
+        let instances = self.instance_service.list().await?;
+        let ports = tokio::task::spawn_blocking({
+            let template = template.clone();
+            let instances = instances.clone();
+            let instance_ids = instance_ids.to_vec();
+            move || assign_ports(&template, &instances, &instance_ids)
+        })
+        .await
+        .map_err(|source| InstanceTemplateError::OperationFailed { source: Box::new(source) })??;
+        let existing_tasks =
+            self.cron
+                .list()
+                .await
+                .map_err(|source| InstanceTemplateError::OperationFailed {
+                    source: Box::new(source),
+                })?;
+
+        let mut plans = Vec::with_capacity(instance_ids.len());
+        for id in instance_ids {
+            let target = instances.iter().find(|instance| &instance.id == id);
+            let plan = match (target, ports.get(id)) {
+                (Some(instance), Some(&port)) => {
+                    self.plan_instance(template, instance.clone(), port, &existing_tasks)
+                        .await
+                }
+                _ => Err(InstanceTemplateError::InstanceNotFound),
+            };
+            plans.push((id.clone(), plan));
+        }
+        Ok(plans)
+    }
+
+    async fn plan_instance(
+        &self,
+        template: &Arc<InstanceTemplate>,
+        instance: Instance,
+        port: u16,
+        existing_tasks: &[CronTask],
+    ) -> Result<InstancePlan, InstanceTemplateError> {
+        let after = template.apply_launch(&instance, port)?;
+        let backup_settings = match template.backup {
+            Some(_) => Some(self.backup_settings()?),
+            None => None,
+        };
+        let (files, backup) = tokio::task::spawn_blocking({
+            let template = template.clone();
+            let instance = after.clone();
+            move || {
+                let files = plan_files(&template, &instance)?;
+                let backup = match (&template.backup, backup_settings) {
+                    (Some(policy), Some(manager)) => {
+                        let before = manager.get_backup_settings(instance.id.as_str())?;
+                        let after = backup_with_policy(&before, policy)?;
+                        (!backup_changes(&before, &after).is_empty()).then_some((before, after))
+                    }
+                    _ => None,
+                };
+                Ok::<_, InstanceTemplateError>((files, backup))
+            }
+        })
+        .await
+        .map_err(|source| InstanceTemplateError::OperationFailed { source: Box::new(source) })??;
+
+        let cron_tasks = template
+            .cron_tasks
+            .iter()
+            .filter(|task| {
+                !existing_tasks.iter().any(|existing| {
+                    existing.server_id == instance.id.as_str() && existing.name == task.name.trim()
+                })
+            })
+            .cloned()
+            .collect();
+
+        Ok(InstancePlan {
+            before: instance,
+            after,
+            files,
+            cron_tasks,
+            backup,
+        })
+    }
+
+    async fn apply_plan(
+        &self,
+        template: &InstanceTemplate,
+        plan: &InstancePlan,
+    ) -> Result<(), InstanceTemplateError> {
+        let id = &plan.after.id;
+        self.instance_service
+            .apply_template_launch(id, template, plan.after.port)
+            .await?;
+
+        let directory = &plan.after.directory;
+        for file in &plan.files {
+            write_atomic(directory.join(&file.file), file.after.as_bytes()).await?;
+        }
+        let edits: Vec<_> = plan
+            .files
+            .iter()
+            .map(|file| ConfigEdit {
+                file: file.file.clone(),
+                before: file.before.clone(),
+                after: file.after.clone(),
+            })
+            .collect();
+        record_config_edits(directory, &edits, &ExecutionPrincipal::BuiltInHost).await;
+
+        for task in &plan.cron_tasks {
+            let draft = CronTaskDraft {
+                name: task.name.trim().to_owned(),
+                server_id: id.as_str().to_owned(),
+                cron_expression: task.cron_expression.clone(),
+                action: match &task.action {
+                    TemplateCronAction::Restart => CronTaskAction::Restart,
+                    TemplateCronAction::Command { command } => {
+                        CronTaskAction::Command { command: command.clone() }
+                    }
+                },
+                enabled: task.enabled,
+            };
+            self.cron.create(draft).await.map_err(|source| {
+                InstanceTemplateError::OperationFailed { source: Box::new(source) }
+            })?;
+        }
+
+        if let Some((_, settings)) = &plan.backup {
+            let manager = self.backup_settings()?;
+            let id = id.as_str().to_owned();
+            let settings = settings.clone();
+            tokio::task::spawn_blocking(move || manager.update_backup_settings(&id, settings))
+                .await
+                .map_err(|source| InstanceTemplateError::OperationFailed {
+                    source: Box::new(source),
+                })??;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl InstanceTemplateService for CoreInstanceTemplateService {
+    async fn list(&self) -> Result<Vec<InstanceTemplate>, InstanceTemplateServiceError> {
+        let store = self.store().await?.lock().await;
+        Ok(store.templates().to_vec())
+    }
+
+    async fn save(
+        &self,
+        template: InstanceTemplate,
+    ) -> Result<InstanceTemplate, InstanceTemplateServiceError> {
+        let mut store = self.store().await?.lock().await;
+        Ok(store
+            .save(template)
+            .await
+            .map_err(InstanceTemplateError::from)?)
+    }
+
+    async fn delete(&self, id: &str) -> Result<(), InstanceTemplateServiceError> {
+        let mut store = self.store().await?.lock().await;
+        Ok(store
+            .delete(id)
+            .await
+            .map_err(InstanceTemplateError::from)?)
+    }
+
+    async fn preview_apply(
+        &self,
+        id: &str,
+        instance_ids: &[InstanceId],
+    ) -> Result<Vec<TemplateInstanceReport>, InstanceTemplateServiceError> {
+        let template = Arc::new(self.find_template(id).await?);
+        let plans = self.plan(&template, instance_ids).await?;
+        Ok(plans
+            .into_iter()
+            .map(|(id, plan)| match plan {
+                Ok(plan) => report(&plan, None),
+                Err(error) => failed_report(&id, error),
+            })
+            .collect())
+    }
+
+    async fn apply(
+        &self,
+        id: &str,
+        instance_ids: &[InstanceId],
+    ) -> Result<Vec<TemplateInstanceReport>, InstanceTemplateServiceError> {
+        let template = Arc::new(self.find_template(id).await?);
+        let plans = self.plan(&template, instance_ids).await?;
+        let mut reports = Vec::with_capacity(plans.len());
+        for (id, plan) in plans {
+            let report = match plan {
+                Ok(plan) => match self.apply_plan(&template, &plan).await {
+                    Ok(()) => report(&plan, None),
+                    Err(error) => {
+                        log_apply_failure(&id, &error);
+                        report(&plan, Some(error.into()))
+                    }
+                },
+                Err(error) => failed_report(&id, error),
+            };
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+}
+
+/// 读取各实例配置的全部端口，经端口登记表为目标实例分配游戏端口。
+///
+/// 返回按实例 ID 索引的端口；不存在的实例不参与分配。
+fn assign_ports(
+    template: &InstanceTemplate,
+    instances: &[Instance],
+    instance_ids: &[InstanceId],
+) -> Result<HashMap<InstanceId, u16>, InstanceTemplateError> {
+    let (targets, others): (Vec<_>, Vec<_>) = instances
+        .iter()
+        .cloned()
+        .partition(|instance| instance_ids.contains(&instance.id));
+    let targets: Vec<_> = instance_ids
+        .iter()
+        .filter_map(|id| targets.iter().find(|instance| &instance.id == id))
+        .map(|instance| (instance, read_port_bindings(&instance.directory, instance.port)))
+        .collect();
+    let ports = template.assign_ports(&targets, &port_registry(&others), host_port_in_use)?;
+    Ok(targets
+        .iter()
+        .map(|(instance, _)| instance.id.clone())
+        .zip(ports)
+        .collect())
+}
+
+/// 计算实例配置文件的修改，只保留有实际变化的文件。
+fn plan_files(
+    template: &InstanceTemplate,
+    instance: &Instance,
+) -> Result<Vec<PlannedFile>, InstanceTemplateError> {
+    let directory = &instance.directory;
+    let mut files = Vec::new();
+
+    let properties = template.properties_for_port(instance.port);
+    if !properties.is_empty() {
+        let manager = ServerPropertiesManager::new(directory)
+            .with_game_version(Some(instance.game_version.as_str()));
+        let before = manager.read_source().ok();
+        let after = manager.preview_write(&properties)?;
+        push_if_changed(&mut files, SERVER_PROPERTIES_FILE, before, after);
+    }
+
+    let documents = ConfigDocumentManager::new(directory);
+    for (file, patches) in template.document_patches() {
+        let patches: Vec<DocumentPatch> = patches
+            .into_iter()
+            .map(|patch| DocumentPatch {
+                path: patch.path.clone(),
+                value: patch.value.clone(),
+            })
+            .collect();
+        let before = documents.read(file)?.source;
+        let after = documents.preview(file, &patches)?.source;
+        push_if_changed(&mut files, file, Some(before), after);
+    }
+    Ok(files)
+}
+
+fn push_if_changed(
+    files: &mut Vec<PlannedFile>,
+    file: &str,
+    before: Option<String>,
+    after: String,
+) {
+    let diff = unified_diff(
+        before.as_deref().unwrap_or_default(),
+        &after,
+        &format!("a/{file}"),
+        &format!("b/{file}"),
+    );
+    if !diff.is_empty() {
+        files.push(PlannedFile {
+            file: file.to_owned(),
+            before,
+            after,
+            diff,
+        });
+    }
+}
+
+fn backup_with_policy(
+    current: &BackupSettings,
+    policy: &sealantern_core::instance::TemplateBackupPolicy,
+) -> Result<BackupSettings, InstanceTemplateError> {
+    let contents = policy
+        .auto_backup_contents
+        .iter()
+        .map(|content| {
+            serde_json::from_value::<BackupContentType>(serde_json::Value::String(content.clone()))
+                .map_err(|_| InstanceTemplateError::InvalidInput {
+                    reason: format!("unknown backup content: {content}"),
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut settings = current.clone();
+    settings.max_backups = policy.max_backups;
+    settings.auto_backup_enabled = policy.auto_backup_enabled;
+    settings.auto_backup_interval = policy.auto_backup_interval_hours;
+    settings.auto_backup_contents = contents;
+    Ok(settings)
+}
+
+fn change(field: &str, before: String, after: String) -> Option<TemplateFieldChange> {
+    (before != after).then(|| TemplateFieldChange { field: field.to_owned(), before, after })
+}
+
+fn launch_changes(before: &Instance, after: &Instance) -> Vec<TemplateFieldChange> {
+    let java = |instance: &Instance| {
+        instance
+            .launch
+            .java_executable
+            .as_ref()
+            .map(|path| path.display().to_string())
+            .unwrap_or_default()
+    };
+    [
+        change("port", before.port.to_string(), after.port.to_string()),
+        change(
+            "max_memory_mib",
+            before.max_memory_mib.to_string(),
+            after.max_memory_mib.to_string(),
+        ),
+        change(
+            "min_memory_mib",
+            before.min_memory_mib.to_string(),
+            after.min_memory_mib.to_string(),
+        ),
+        change(
+            "jvm_arguments",
+            before.launch.jvm_arguments.join(" "),
+            after.launch.jvm_arguments.join(" "),
+        ),
+        change("java_executable", java(before), java(after)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+fn backup_changes(before: &BackupSettings, after: &BackupSettings) -> Vec<TemplateFieldChange> {
+    let contents = |settings: &BackupSettings| {
+        settings
+            .auto_backup_contents
+            .iter()
+            .map(|content| {
+                serde_json::to_value(content)
+                    .ok()
+                    .and_then(|value| value.as_str().map(str::to_owned))
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    [
+        change("max_backups", before.max_backups.to_string(), after.max_backups.to_string()),
+        change(
+            "auto_backup_enabled",
+            before.auto_backup_enabled.to_string(),
+            after.auto_backup_enabled.to_string(),
+        ),
+        change(
+            "auto_backup_interval",
+            before.auto_backup_interval.to_string(),
+            after.auto_backup_interval.to_string(),
+        ),
+        change("auto_backup_contents", contents(before), contents(after)),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+fn report(
+    plan: &InstancePlan,
+    error: Option<InstanceTemplateServiceError>,
+) -> TemplateInstanceReport {
+    TemplateInstanceReport {
+        instance_id: plan.before.id.as_str().to_owned(),
+        instance_name: plan.before.name.clone(),
+        launch_changes: launch_changes(&plan.before, &plan.after),
+        file_diffs: plan
+            .files
+            .iter()
+            .map(|file| TemplateFileDiff {
+                file: file.file.clone(),
+                diff: file.diff.clone(),
+            })
+            .collect(),
+        cron_tasks: plan
+            .cron_tasks
+            .iter()
+            .map(|task| task.name.trim().to_owned())
+            .collect(),
+        backup_changes: plan
+            .backup
+            .as_ref()
+            .map(|(before, after)| backup_changes(before, after))
+            .unwrap_or_default(),
+        error,
+    }
+}
+
+fn failed_report(id: &InstanceId, error: InstanceTemplateError) -> TemplateInstanceReport {
+    log_apply_failure(id, &error);
+    TemplateInstanceReport {
+        instance_id: id.as_str().to_owned(),
+        instance_name: String::new(),
+        launch_changes: Vec::new(),
+        file_diffs: Vec::new(),
+        cron_tasks: Vec::new(),
+        backup_changes: Vec::new(),
+        error: Some(error.into()),
+    }
+}
+
+fn log_apply_failure(id: &InstanceId, error: &InstanceTemplateError) {
+    tracing::warn!(
+        target: "sealantern.application.template",
+        instance_id = id.as_str(),
+        error = %error,
+        "instance template could not be applied"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use sealantern_core::instance::{InstanceSpec, LocalLaunch, StartupMode};
+    use sealantern_interface::CronTaskServiceError;
+    use sealantern_interface::cron::CronTaskRun;
+
+    use super::*;
+
+    /// 只记录创建请求的内存定时任务服务。
+    #[derive(Default)]
+    struct MemoryCronTasks {
+        tasks: Mutex<Vec<CronTask>>,
+    }
+
+    #[async_trait]
+    impl CronTaskService for MemoryCronTasks {
+        async fn list(&self) -> Result<Vec<CronTask>, CronTaskServiceError> {
+            Ok(self.tasks.lock().unwrap().clone())
+        }
+
+        async fn create(&self, draft: CronTaskDraft) -> Result<CronTask, CronTaskServiceError> {
+            let task = CronTask {
+                id: draft.name.clone(),
+                name: draft.name,
+                server_id: draft.server_id,
+                cron_expression: draft.cron_expression,
+                action: draft.action,
+                enabled: draft.enabled,
+                last_run_at: None,
+                next_run_at: None,
+                last_error: None,
+            };
+            self.tasks.lock().unwrap().push(task.clone());
+            Ok(task)
+        }
+
+        async fn update(
+            &self,
+            _id: &str,
+            _draft: CronTaskDraft,
+        ) -> Result<CronTask, CronTaskServiceError> {
+            Err(CronTaskServiceError::Unsupported)
+        }
+
+        async fn delete(&self, _id: &str) -> Result<(), CronTaskServiceError> {
+            Err(CronTaskServiceError::Unsupported)
+        }
+
+        async fn set_enabled(
+            &self,
+            _id: &str,
+            _enabled: bool,
+        ) -> Result<CronTask, CronTaskServiceError> {
+            Err(CronTaskServiceError::Unsupported)
+        }
+
+        async fn run_now(&self, _id: &str) -> Result<CronTaskRun, CronTaskServiceError> {
+            Err(CronTaskServiceError::Unsupported)
+        }
+    }
+
+    fn sample_spec(id: &str, directory: PathBuf, port: u16) -> InstanceSpec {
+        InstanceSpec {
+            id: InstanceId::new(id).expect("valid id"),
+            name: format!("活动服-{id}"),
+            aliases: Vec::new(),
+            core_type: "paper".into(),
+            core_version: "1.21.4".into(),
+            game_version: "1.21.4".into(),
+            directory: directory.clone(),
+            port,
+            max_memory_mib: 2048,
+            min_memory_mib: 512,
+            created_at_unix_secs: 0,
+            last_started_at_unix_secs: None,
+            server_metadata: None,
+            launch: LocalLaunch {
+                startup_mode: StartupMode::Jar,
+                startup_target: Some(directory.join("server.jar")),
+                custom_command: None,
+                custom_executable: None,
+                custom_arguments: Vec::new(),
+                java_executable: None,
+                jvm_arguments: Vec::new(),
+            },
+        }
+    }
+
+    fn event_template() -> InstanceTemplate {
+        serde_json::from_value(serde_json::json!({
+            "name": "活动服",
+            "launch": {
+                "max_memory_mib": 4096,
+                "jvm_arguments": ["-XX:+UseG1GC"],
+                "port": { "kind": "sequential", "start": 30000 }
+            },
+            "properties": { "max-players": "50", "motd": "Event" },
+            "documents": [
+                { "file": "config/paper-global.yml", "path": ["chunk-loading", "max-concurrent-sends"], "value": 4 }
+            ],
+            "cron_tasks": [
+                { "name": "nightly-restart", "cron_expression": "0 0 4 * * *", "action": { "kind": "restart" } }
+            ],
+            "backup": {
+                "max_backups": 5,
+                "auto_backup_enabled": true,
+                "auto_backup_interval_hours": 6,
+                "auto_backup_contents": ["world", "config"]
+            }
+        }))
+        .expect("模板 JSON 应合法")
+    }
+
+    #[tokio::test]
+    async fn previews_then_applies_template_to_several_instances() {
+        let temp = tempfile::tempdir().expect("临时目录应创建成功");
+        let instance_service = Arc::new(
+            CoreInstanceService::with_path(temp.path().join("instances.json"))
+                .await
+                .expect("实例服务应创建成功"),
+        );
+        for (id, port) in [("a", 25565), ("b", 25566), ("other", 30001)] {
+            let directory = temp.path().join(id);
+            std::fs::create_dir_all(directory.join("config")).unwrap();
+            std::fs::write(
+                directory.join("server.properties"),
+                format!("#Minecraft server properties\nmax-players=20\nserver-port={port}\n"),
+            )
+            .unwrap();
+            std::fs::write(
+                directory.join("config/paper-global.yml"),
+                "chunk-loading:\n  max-concurrent-sends: 2 # 并发区块发送\n",
+            )
+            .unwrap();
+            instance_service
+                .create(sample_spec(id, directory, port))
+                .await
+                .expect("实例应创建成功");
+        }
+        let cron = Arc::new(MemoryCronTasks::default());
+        let service = CoreInstanceTemplateService::with_paths(
+            temp.path().join("templates.json"),
+            Some(temp.path().join("backup_settings")),
+            instance_service.clone(),
+            cron.clone(),
+        );
+        let template = service
+            .save(event_template())
+            .await
+            .expect("模板应保存成功");
+        let targets = [InstanceId::new("a").unwrap(), InstanceId::new("b").unwrap()];
+
+        let previews = service.preview_apply(&template.id, &targets).await.unwrap();
+        assert_eq!(previews.len(), 2);
+        let second = &previews[1];
+        assert_eq!(second.error, None);
+        // 30001 已被其他实例占用
+        assert!(second.launch_changes.contains(&TemplateFieldChange {
+            field: "port".to_owned(),
+            before: "25566".to_owned(),
+            after: "30002".to_owned(),
+        }));
+        let files: Vec<&str> = second
+            .file_diffs
+            .iter()
+            .map(|diff| diff.file.as_str())
+            .collect();
+        assert_eq!(files, ["server.properties", "config/paper-global.yml"]);
+        let properties_diff = &second.file_diffs[0].diff;
+        assert!(properties_diff.contains("-max-players=20\n"));
+        assert!(properties_diff.contains("+max-players=50\n"));
+        assert!(properties_diff.contains("+server-port=30002\n"));
+        assert_eq!(second.cron_tasks, ["nightly-restart"]);
+        assert!(!second.backup_changes.is_empty());
+        let untouched = std::fs::read_to_string(temp.path().join("b/server.properties")).unwrap();
+        assert!(untouched.contains("max-players=20"));
+
+        let reports = service.apply(&template.id, &targets).await.unwrap();
+        assert!(reports.iter().all(|report| report.error.is_none()));
+        let instance = instance_service
+            .find(&targets[1])
+            .await
+            .unwrap()
+            .expect("实例应存在");
+        assert_eq!((instance.port, instance.max_memory_mib), (30002, 4096));
+        let properties = std::fs::read_to_string(temp.path().join("b/server.properties")).unwrap();
+        assert!(properties.contains("max-players=50") && properties.contains("server-port=30002"));
+        let document =
+            std::fs::read_to_string(temp.path().join("b/config/paper-global.yml")).unwrap();
+        assert!(document.contains("max-concurrent-sends: 4 # 并发区块发送"));
+        assert_eq!(cron.tasks.lock().unwrap().len(), 2);
+
+        // 再次套用同一模板不产生任何变化
+        let previews = service.preview_apply(&template.id, &targets).await.unwrap();
+        assert!(previews.iter().all(|report| {
+            report.launch_changes.is_empty()
+                && report.file_diffs.is_empty()
+                && report.cron_tasks.is_empty()
+                && report.backup_changes.is_empty()
+        }));
+
+        let missing = [InstanceId::new("missing").unwrap()];
+        let reports = service.preview_apply(&template.id, &missing).await.unwrap();
+        assert_eq!(reports[0].error, Some(InstanceTemplateServiceError::InstanceNotFound));
+        assert_eq!(
+            service.preview_apply("unknown", &targets).await,
+            Err(InstanceTemplateServiceError::TemplateNotFound)
+        );
+    }
+}
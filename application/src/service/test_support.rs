@@ -0,0 +1,101 @@
+//! 服务测试共用的替身实现。
+
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use sealantern_core::instance::InstanceId;
+use sealantern_interface::server::{
+    CaptureCompletion, CaptureOptions, CommandCapture, CommandResponse, CommandTransport,
+    ServerProbe, ServerSnapshot, ServerState,
+};
+use sealantern_interface::{ServerService, ServerServiceError};
+
+/// 报告固定状态并记录调用的服务器服务。
+///
+/// 启停与命令操作总是成功，按 `restart:<id>`、`command:<id>:<command>` 的形式记录在
+/// `calls` 中；探测返回 [`ServerServiceError::InvalidState`]。
+pub(crate) struct FakeServerService {
+    pub(crate) state: ServerState,
+    pub(crate) calls: Mutex<Vec<String>>,
+}
+
+impl FakeServerService {
+    pub(crate) fn new(state: ServerState) -> Self {
+        Self { state, calls: Mutex::new(Vec::new()) }
+    }
+
+    fn record(&self, call: String) {
+        self.calls.lock().expect("calls lock").push(call);
+    }
+}
+
+impl Default for FakeServerService {
+    fn default() -> Self {
+        Self::new(ServerState::Stopped)
+    }
+}
+
+#[async_trait]
+impl ServerService for FakeServerService {
+    async fn status(&self, id: &InstanceId) -> Result<ServerSnapshot, ServerServiceError> {
+        Ok(ServerSnapshot {
+            instance_id: id.as_str().to_owned(),
+            state: self.state,
+            pid: None,
+            uptime_secs: None,
+            error_message: None,
+        })
+    }
+
+    async fn start(&self, id: &InstanceId) -> Result<(), ServerServiceError> {
+        self.record(format!("start:{}", id.as_str()));
+        Ok(())
+    }
+
+    async fn restart(&self, id: &InstanceId) -> Result<(), ServerServiceError> {
+        self.record(format!("restart:{}", id.as_str()));
+        Ok(())
+    }
+
+    async fn stop(&self, id: &InstanceId) -> Result<(), ServerServiceError> {
+        self.record(format!("stop:{}", id.as_str()));
+        Ok(())
+    }
+
+    async fn force_stop(&self, id: &InstanceId) -> Result<(), ServerServiceError> {
+        self.record(format!("force_stop:{}", id.as_str()));
+        Ok(())
+    }
+
+    async fn send_command(&self, id: &InstanceId, command: &str) -> Result<(), ServerServiceError> {
+        self.record(format!("command:{}:{command}", id.as_str()));
+        Ok(())
+    }
+
+    async fn send_command_via(
+        &self,
+        id: &InstanceId,
+        command: &str,
+        transport: CommandTransport,
+    ) -> Result<CommandResponse, ServerServiceError> {
+        self.send_command(id, command).await?;
+        Ok(CommandResponse { transport, output: None })
+    }
+
+    async fn send_command_and_capture(
+        &self,
+        id: &InstanceId,
+        command: &str,
+        _options: CaptureOptions,
+    ) -> Result<CommandCapture, ServerServiceError> {
+        self.send_command(id, command).await?;
+        Ok(CommandCapture {
+            lines: Vec::new(),
+            completion: CaptureCompletion::Quiet,
+        })
+    }
+
+    async fn probe(&self, _id: &InstanceId) -> Result<ServerProbe, ServerServiceError> {
+        Err(ServerServiceError::InvalidState)
+    }
+}
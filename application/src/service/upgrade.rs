@@ -0,0 +1,460 @@
+//! 服务端核心原地升级服务实现。
+//!
+//! 实现 [`sealantern_interface::ServerUpgradeService`] 能力端口：目标构建由 `extra` 的
+//! [`CachedCatalog`] 解析，扩展声明的游戏版本由 `core` 的
+//! [`extension_version_mismatches`] 检查，Java 要求与本机探测结果比较。升级时先以
+//! [`BackupManager`] 冷备份整个实例目录，再下载并校验新构建、替换服务端文件，最后由
+//! [`CoreInstanceService::apply_core_upgrade`] 写回版本并刷新服务端元数据。
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sealantern_core::instance::{Instance, InstanceId, StartupMode};
+use sealantern_core::provisioning::extension_version_mismatches;
+use sealantern_extra::backup::{
+    BackupContentType, BackupFormat, BackupItem, BackupManager, CompressionLevel,
+    CreateBackupRequest,
+};
+use sealantern_extra::catalog::{BuildArtifact, CachedCatalog, CatalogBuild};
+use sealantern_extra::java::{JavaInfo, detect_java_installations};
+use sealantern_extra::server::read_instance_extensions;
+use sealantern_infra::download::{DownloadManager, DownloadPriority, TaskOptions};
+use sealantern_infra::fs::remove_if_exists;
+use sealantern_interface::server::ServerState;
+use sealantern_interface::upgrade::{
+    ServerUpgradeOutcome, ServerUpgradePlan, ServerUpgradeRequest, UpgradeJavaCheck,
+};
+use sealantern_interface::{
+    InstanceService, ServerService, ServerUpgradeService, ServerUpgradeServiceError,
+};
+
+use super::CoreInstanceService;
+use crate::error::ServerUpgradeError;
+
+/// 基于目录、实例备份与实例记录的服务端核心原地升级服务实现。
+pub struct CoreServerUpgradeService {
+    instance_service: Arc<CoreInstanceService>,
+    server: Arc<dyn ServerService>,
+    catalog: CachedCatalog,
+    /// 备份目录；为 `None` 时使用应用数据目录下的默认备份目录。
+    backups_dir: Option<PathBuf>,
+}
+
+impl CoreServerUpgradeService {
+    /// 使用全部上游目录与默认备份目录构造服务。
+    pub fn new(instance_service: Arc<CoreInstanceService>, server: Arc<dyn ServerService>) -> Self {
+        Self::with_parts(instance_service, server, CachedCatalog::global(), None)
+    }
+
+    /// 使用指定目录与备份目录构造服务（便于测试注入）。
+    pub fn with_parts(
+        instance_service: Arc<CoreInstanceService>,
+        server: Arc<dyn ServerService>,
+        catalog: CachedCatalog,
+        backups_dir: Option<PathBuf>,
+    ) -> Self {
+        Self {
+            instance_service,
+            server,
+            catalog,
+            backups_dir,
+        }
+    }
+
+    async fn plan_inner(
+        &self,
+        id: &InstanceId,
+        request: &ServerUpgradeRequest,
+    ) -> Result<(Instance, ServerUpgradePlan), ServerUpgradeError> {
+        let instance = self
+            .instance_service
+            .find(id)
+            .await?
+            .ok_or(ServerUpgradeError::InstanceNotFound)?;
+        if instance.launch.startup_mode != StartupMode::Jar {
+            return Err(ServerUpgradeError::UnsupportedLaunch);
+        }
+        let game_version = non_blank(request.game_version.as_deref())
+            .or_else(|| non_blank(Some(&instance.game_version)))
+            .ok_or(ServerUpgradeError::InvalidInput)?;
+        let snapshot = self
+            .catalog
+            .build(&instance.core_type, &game_version, request.build.as_deref())
+            .await?;
+        let target = snapshot.data;
+        if target.artifact == BuildArtifact::Installer {
+            return Err(ServerUpgradeError::InstallerCore(instance.core_type.clone()));
+        }
+
+        let directory = instance.directory.clone();
+        let (extensions, installations) = tokio::task::spawn_blocking(move || {
+            (read_instance_extensions(&directory), detect_java_installations())
+        })
+        .await
+        .map_err(|error| ServerUpgradeError::OperationFailed { source: Box::new(error) })?;
+        let plan = ServerUpgradePlan {
+            instance_id: id.as_str().to_owned(),
+            core_type: instance.core_type.clone(),
+            current_core_version: instance.core_version.clone(),
+            current_game_version: instance.game_version.clone(),
+            java: check_java(
+                target.java_major,
+                instance.launch.java_executable.as_deref(),
+                &installations,
+            ),
+            extension_warnings: extension_version_mismatches(&extensions, &target.game_version),
+            target,
+            catalog_stale: snapshot.stale,
+        };
+        Ok((instance, plan))
+    }
+
+    async fn upgrade_inner(
+        &self,
+        id: &InstanceId,
+        request: ServerUpgradeRequest,
+    ) -> Result<ServerUpgradeOutcome, ServerUpgradeError> {
+        let (instance, plan) = self.plan_inner(id, &request).await?;
+        if self.server.status(id).await?.state != ServerState::Stopped {
+            return Err(ServerUpgradeError::ServerRunning);
+        }
+        if !plan.java.satisfied
+            && !request.force
+            && let Some(required) = plan.java.required_major
+        {
+            return Err(ServerUpgradeError::JavaRequirementUnmet { required });
+        }
+
+        let backup = self.backup(&instance, &plan.target).await?;
+        let server_jar = download_build(&instance.directory, &plan.target).await?;
+        // 旧服务端文件已在备份中，与新文件不同名时删除，避免检查时出现两个候选。
+        if let Some(previous) = &instance.launch.startup_target {
+            let previous = instance.directory.join(previous);
+            if previous != server_jar {
+                remove_if_exists(&previous).await?;
+            }
+        }
+
+        let (instance, projection) = self
+            .instance_service
+            .apply_core_upgrade(
+                id,
+                server_jar,
+                plan.target.build.clone(),
+                plan.target.game_version.clone(),
+            )
+            .await?;
+        tracing::info!(
+            target: "sealantern.application.upgrade",
+            instance_id = %id.as_str(),
+            from = %plan.current_core_version,
+            to = %plan.target.build,
+            game_version = %plan.target.game_version,
+            backup_id = %backup.id,
+            diagnostics = projection.diagnostics.len(),
+            "server core upgraded"
+        );
+        Ok(ServerUpgradeOutcome { plan, backup, instance })
+    }
+
+    /// 冷备份整个实例目录（调用方已确认服务器停止）。
+    async fn backup(
+        &self,
+        instance: &Instance,
+        target: &CatalogBuild,
+    ) -> Result<BackupItem, ServerUpgradeError> {
+        let backups_dir = self.backups_dir.clone();
+        let directory = instance.directory.clone();
+        let request = CreateBackupRequest {
+            server_id: instance.id.as_str().to_owned(),
+            contents: vec![BackupContentType::Core],
+            format: BackupFormat::Zip,
+            compression_level: CompressionLevel::default(),
+            name: Some(format!(
+                "升级前备份 {} {} → {}",
+                instance.core_type, instance.core_version, target.build
+            )),
+        };
+        let backup = tokio::task::spawn_blocking(move || {
+            let manager = match backups_dir {
+                Some(backups_dir) => BackupManager::with_dir(backups_dir)?,
+                None => BackupManager::new()?,
+            };
+            manager.create_backup(request, &directory, |_| true)
+        })
+        .await
+        .map_err(|error| ServerUpgradeError::OperationFailed { source: Box::new(error) })??;
+        Ok(backup)
+    }
+}
+
+/// 下载目标构建到实例目录并校验，返回服务端文件路径。
+///
+/// 下载经全局下载队列调度，受连接数与带宽限制；大小与摘要由下载模块按目录给出的
+/// [`CatalogBuild::integrity`] 校验，通过后才覆盖同名文件。
+async fn download_build(
+    directory: &Path,
+    build: &CatalogBuild,
+) -> Result<PathBuf, ServerUpgradeError> {
+    // 文件名来自上游，只取最后一段，避免写到实例目录之外。
+    let file_name = Path::new(&build.file_name)
+        .file_name()
+        .ok_or_else(|| ServerUpgradeError::InvalidFileName(build.file_name.clone()))?;
+    let destination = directory.join(file_name);
+    // 升级期间服务器处于停止状态，优先于其他下载。
+    let options = TaskOptions {
+        priority: DownloadPriority::High,
+        ..Default::default()
+    };
+    let (_, status) = DownloadManager::instance()
+        .create_scheduled(
+            std::slice::from_ref(&build.url),
+            &destination.to_string_lossy(),
+            8,
+            build.integrity(),
+            options,
+        )
+        .await?;
+    status.wait().await?;
+    Ok(destination)
+}
+
+/// 比较目标构建的 Java 要求与实例启动时使用的 Java。
+///
+/// 实例未配置 Java 时，服务器启动会回退到本机最高版本（探测结果按主版本降序），
+/// 这里按同样的规则确定当前 Java。
+fn check_java(
+    required: Option<u16>,
+    configured: Option<&Path>,
+    installations: &[JavaInfo],
+) -> UpgradeJavaCheck {
+    let current_path = configured
+        .map(|path| path.to_string_lossy().into_owned())
+        .or_else(|| installations.first().map(|java| java.path.clone()));
+    let current_major = current_path.as_deref().and_then(|current| {
+        installations
+            .iter()
+            .find(|java| Path::new(&java.path) == Path::new(current))
+            .map(|java| java.major_version)
+    });
+    let satisfied = match required {
+        None => true,
+        Some(required) => current_major.is_some_and(|major| major >= u32::from(required)),
+    };
+    let suggested_path = match required {
+        Some(required) if !satisfied => installations
+            .iter()
+            .filter(|java| java.major_version >= u32::from(required))
+            .min_by_key(|java| java.major_version)
+            .map(|java| java.path.clone()),
+        _ => None,
+    };
+    UpgradeJavaCheck {
+        required_major: required,
+        current_path,
+        current_major,
+        satisfied,
+        suggested_path,
+    }
+}
+
+fn non_blank(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_owned)
+}
+
+#[async_trait]
+impl ServerUpgradeService for CoreServerUpgradeService {
+    async fn plan(
+        &self,
+        id: &InstanceId,
+        request: ServerUpgradeRequest,
+    ) -> Result<ServerUpgradePlan, ServerUpgradeServiceError> {
+        self.plan_inner(id, &request)
+            .await
+            .map(|(_, plan)| plan)
+            .map_err(Into::into)
+    }
+
+    async fn upgrade(
+        &self,
+        id: &InstanceId,
+        request: ServerUpgradeRequest,
+    ) -> Result<ServerUpgradeOutcome, ServerUpgradeServiceError> {
+        self.upgrade_inner(id, request).await.map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    use sealantern_core::instance::{InstanceSpec, LocalLaunch};
+    use sealantern_extra::catalog::{BuildChannel, CatalogError, CatalogProvider, ServerCatalog};
+
+    use super::super::test_support::FakeServerService;
+    use super::*;
+
+    /// 只提供 Forge 安装器构建的目录。
+    struct ForgeProvider;
+
+    #[async_trait]
+    impl CatalogProvider for ForgeProvider {
+        fn name(&self) -> &'static str {
+            "forge"
+        }
+
+        async fn server_types(&self) -> Result<Vec<String>, CatalogError> {
+            Ok(vec!["forge".to_owned()])
+        }
+
+        async fn versions(&self, _: &str) -> Result<Vec<String>, CatalogError> {
+            Ok(vec!["1.20.1".to_owned()])
+        }
+
+        async fn builds(
+            &self,
+            _server_type: &str,
+            game_version: &str,
+        ) -> Result<Vec<CatalogBuild>, CatalogError> {
+            Ok(vec![CatalogBuild {
+                server_type: "forge".to_owned(),
+                game_version: game_version.to_owned(),
+                build: format!("{game_version}-47.3.0"),
+                channel: BuildChannel::Stable,
+                artifact: BuildArtifact::Installer,
+                url: "https://example.invalid/forge-installer.jar".to_owned(),
+                file_name: "forge-installer.jar".to_owned(),
+                size: None,
+                hashes: BTreeMap::new(),
+                java_major: Some(17),
+                provider: "forge".to_owned(),
+            }])
+        }
+    }
+
+    fn sample_spec(id: &str, directory: PathBuf, startup_mode: StartupMode) -> InstanceSpec {
+        InstanceSpec {
+            id: InstanceId::new(id).expect("valid id"),
+            name: "升级测试".into(),
+            aliases: Vec::new(),
+            core_type: "forge".into(),
+            core_version: "1.20.1-47.2.0".into(),
+            game_version: "1.20.1".into(),
+            directory: directory.clone(),
+            port: match startup_mode {
+                StartupMode::Jar => 47635,
+                _ => 47636,
+            },
+            max_memory_mib: 2048,
+            min_memory_mib: 512,
+            created_at_unix_secs: 0,
+            last_started_at_unix_secs: None,
+            server_metadata: None,
+            launch: LocalLaunch {
+                startup_mode,
+                startup_target: Some(directory.join(match startup_mode {
+                    StartupMode::Shell => "run.sh",
+                    _ => "server.jar",
+                })),
+                custom_command: None,
+                custom_executable: None,
+                custom_arguments: Vec::new(),
+                java_executable: None,
+                jvm_arguments: Vec::new(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_installer_builds_and_script_launches() {
+        let temp = tempfile::tempdir().expect("临时目录应创建成功");
+        let instance_service = Arc::new(
+            CoreInstanceService::with_path(temp.path().join("instances.json"))
+                .await
+                .expect("实例服务应创建成功"),
+        );
+        let jar = instance_service
+            .create(sample_spec("forge-jar", temp.path().join("jar"), StartupMode::Jar))
+            .await
+            .expect("实例应创建成功");
+        let script = instance_service
+            .create(sample_spec("forge-script", temp.path().join("script"), StartupMode::Shell))
+            .await
+            .expect("实例应创建成功");
+        let catalog = CachedCatalog::new(
+            ServerCatalog::new(vec![Arc::new(ForgeProvider)], None),
+            None,
+            Duration::from_secs(60),
+        );
+        let service = CoreServerUpgradeService::with_parts(
+            instance_service,
+            Arc::new(FakeServerService::default()),
+            catalog,
+            Some(temp.path().join("backups")),
+        );
+
+        assert_eq!(
+            service.plan(&jar.id, ServerUpgradeRequest::default()).await,
+            Err(ServerUpgradeServiceError::UnsupportedCore)
+        );
+        assert_eq!(
+            service
+                .upgrade(&script.id, ServerUpgradeRequest::default())
+                .await
+                .map(|_| ()),
+            Err(ServerUpgradeServiceError::UnsupportedCore)
+        );
+        let missing = ServerUpgradeRequest {
+            build: Some("99.0.0".to_owned()),
+            ..Default::default()
+        };
+        assert_eq!(
+            service.plan(&jar.id, missing).await,
+            Err(ServerUpgradeServiceError::BuildNotFound)
+        );
+        assert!(!temp.path().join("backups").exists());
+    }
+
+    fn java(path: &str, major_version: u32) -> JavaInfo {
+        JavaInfo {
+            path: path.to_owned(),
+            version: format!("{major_version}.0.1"),
+            vendor: String::new(),
+            is_64bit: true,
+            major_version,
+            confidence: 100,
+        }
+    }
+
+    #[test]
+    fn checks_the_java_the_server_would_launch_with() {
+        let installations = [
+            java("/opt/java25/bin/java", 25),
+            java("/opt/java21/bin/java", 21),
+            java("/opt/java17/bin/java", 17),
+        ];
+
+        let configured =
+            check_java(Some(21), Some(Path::new("/opt/java17/bin/java")), &installations);
+        assert!(!configured.satisfied);
+        assert_eq!(configured.current_major, Some(17));
+        assert_eq!(configured.suggested_path.as_deref(), Some("/opt/java21/bin/java"));
+
+        let fallback = check_java(Some(21), None, &installations);
+        assert!(fallback.satisfied);
+        assert_eq!(fallback.current_path.as_deref(), Some("/opt/java25/bin/java"));
+        assert_eq!(fallback.suggested_path, None);
+
+        let unknown = check_java(Some(17), Some(Path::new("/custom/java")), &installations);
+        assert!(!unknown.satisfied);
+        assert_eq!(unknown.current_major, None);
+        assert_eq!(unknown.suggested_path.as_deref(), Some("/opt/java17/bin/java"));
+
+        assert!(check_java(None, None, &[]).satisfied);
+    }
+}
@@ -17,12 +17,14 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use crate::error::InstanceError;
 use crate::plugin::{ApplicationPluginReadHost, CorePluginService, PluginServiceError};
 use crate::service::{
-    CoreConsoleService, CoreCronTaskService, CoreDownloadService, CoreInstanceService,
-    CoreJavaService, CoreOnlineTunnelService, CoreProvisioningService, CoreServerCatalogService,
-    CoreServerService, CoreSettingsService, CoreSystemService, CoreUpdateCheckService,
-    CoreUpdateInstallService, ProxyMonitoringService,
+    CoreCommandLibraryService, CoreConfigService, CoreConsoleService, CoreCronTaskService,
+    CoreDownloadService, CoreExtensionService, CoreInstallerService, CoreInstanceService,
+    CoreInstanceTemplateService, CoreJavaService, CoreMarketInstallService,
+    CoreOnlineTunnelService, CorePlayerService, CoreProvisioningService, CoreServerCatalogService,
+    CoreServerService, CoreServerUpgradeService, CoreSettingsService, CoreSystemService,
+    CoreUpdateCheckService, CoreUpdateInstallService, ProxyMonitoringService,
 };
-use sealantern_interface::OnlineTunnelService;
+use sealantern_interface::{InstallerService, OnlineTunnelService};
 
 /// 真正的全局服务容器（进程级单例，内部为异步锁 + 可配置）。
 #[derive(Clone)]
@@ -53,8 +55,24 @@ pub struct AppServicesInner {
     pub server: Arc<CoreServerService>,
     /// 服务器控制台日志服务。
     pub console: Arc<CoreConsoleService>,
+    /// 在线玩家与会话历史服务。
+    pub player: Arc<CorePlayerService>,
+    /// 控制台命令历史与宏服务。
+    pub command_library: Arc<CoreCommandLibraryService>,
+    /// 配置文件写入与修订服务。
+    pub config: Arc<CoreConfigService>,
     /// 服务器定时任务服务。
     pub cron: Arc<CoreCronTaskService>,
+    /// 实例配置模板服务。
+    pub template: Arc<CoreInstanceTemplateService>,
+    /// 市场资源安装服务。
+    pub market_install: Arc<CoreMarketInstallService>,
+    /// 扩展启停服务。
+    pub extension: Arc<CoreExtensionService>,
+    /// 加载器安装器执行服务。
+    pub installer: Arc<CoreInstallerService>,
+    /// 服务端核心原地升级服务。
+    pub upgrade: Arc<CoreServerUpgradeService>,
     /// 设置信息服务。
     pub settings: Arc<CoreSettingsService>,
     /// 系统代理轮询服务。
@@ -81,18 +99,39 @@ impl AppServices {
         let instance = Arc::new(instance);
         let settings = Arc::new(CoreSettingsService::new());
         let server = Arc::new(CoreServerService::new(instance.clone(), settings.clone()));
+        let cron = Arc::new(CoreCronTaskService::new(server.clone()));
+        let installer = Arc::new(CoreInstallerService::new(instance.clone(), server.clone()));
+        let loader_installer: Arc<dyn InstallerService> = installer.clone();
+        instance.set_loader_installer(Arc::downgrade(&loader_installer));
         Self {
             inner: Arc::new(AppServicesInner {
                 background_started: AtomicBool::new(false),
                 download: Arc::new(CoreDownloadService::new()),
                 console: Arc::new(CoreConsoleService::new(instance.clone())),
-                cron: Arc::new(CoreCronTaskService::new(server.clone())),
+                player: Arc::new(CorePlayerService::new(
+                    instance.clone(),
+                    server.player_tracker().clone(),
+                )),
+                command_library: Arc::new(CoreCommandLibraryService::new(instance.clone())),
+                config: Arc::new(CoreConfigService::new(instance.clone())),
+                template: Arc::new(CoreInstanceTemplateService::new(
+                    instance.clone(),
+                    cron.clone(),
+                )),
+                market_install: Arc::new(CoreMarketInstallService::new(
+                    instance.clone(),
+                    settings.clone(),
+                )),
+                extension: Arc::new(CoreExtensionService::new(instance.clone(), server.clone())),
+                installer,
+                upgrade: Arc::new(CoreServerUpgradeService::new(instance.clone(), server.clone())),
+                cron,
                 system: Arc::new(CoreSystemService::new(instance.clone(), server.clone())),
                 server,
                 instance,
                 java: Arc::new(CoreJavaService),
                 online_tunnel: Arc::new(CoreOnlineTunnelService::default()),
-                catalog: Arc::new(CoreServerCatalogService),
+                catalog: Arc::new(CoreServerCatalogService::default()),
                 provisioning: Arc::new(CoreProvisioningService),
                 settings,
                 proxy_monitoring: Arc::new(ProxyMonitoringService::new()),
@@ -217,6 +256,87 @@ impl AppServices {
         Ok(Self::get().await?.console().clone())
     }
 
+    /// 访问在线玩家服务（`Arc` 共享句柄，clone 廉价）。
+    pub fn player(&self) -> &Arc<CorePlayerService> {
+        &self.inner.player
+    }
+
+    /// 便捷访问入口：一步拿到在线玩家服务的共享句柄（惰性初始化 + 可替换）。
+    pub async fn player_service() -> Result<Arc<CorePlayerService>, InstanceError> {
+        Ok(Self::get().await?.player().clone())
+    }
+
+    /// 访问控制台命令历史与宏服务（`Arc` 共享句柄，clone 廉价）。
+    pub fn command_library(&self) -> &Arc<CoreCommandLibraryService> {
+        &self.inner.command_library
+    }
+
+    /// 便捷访问入口：一步拿到命令历史与宏服务的共享句柄（惰性初始化 + 可替换）。
+    pub async fn command_library_service() -> Result<Arc<CoreCommandLibraryService>, InstanceError>
+    {
+        Ok(Self::get().await?.command_library().clone())
+    }
+
+    /// 访问配置文件写入与修订服务（`Arc` 共享句柄，clone 廉价）。
+    pub fn config(&self) -> &Arc<CoreConfigService> {
+        &self.inner.config
+    }
+
+    /// 便捷访问入口：一步拿到配置文件写入与修订服务的共享句柄（惰性初始化 + 可替换）。
+    pub async fn config_service() -> Result<Arc<CoreConfigService>, InstanceError> {
+        Ok(Self::get().await?.config().clone())
+    }
+
+    /// 访问实例配置模板服务（`Arc` 共享句柄，clone 廉价）。
+    pub fn template(&self) -> &Arc<CoreInstanceTemplateService> {
+        &self.inner.template
+    }
+
+    /// 便捷访问入口：一步拿到实例配置模板服务的共享句柄（惰性初始化 + 可替换）。
+    pub async fn template_service() -> Result<Arc<CoreInstanceTemplateService>, InstanceError> {
+        Ok(Self::get().await?.template().clone())
+    }
+
+    /// 访问市场资源安装服务（`Arc` 共享句柄，clone 廉价）。
+    pub fn market_install(&self) -> &Arc<CoreMarketInstallService> {
+        &self.inner.market_install
+    }
+
+    /// 便捷访问入口：一步拿到市场资源安装服务的共享句柄（惰性初始化 + 可替换）。
+    pub async fn market_install_service() -> Result<Arc<CoreMarketInstallService>, InstanceError> {
+        Ok(Self::get().await?.market_install().clone())
+    }
+
+    /// 访问扩展启停服务（`Arc` 共享句柄，clone 廉价）。
+    pub fn extension(&self) -> &Arc<CoreExtensionService> {
+        &self.inner.extension
+    }
+
+    /// 便捷访问入口：一步拿到扩展启停服务的共享句柄（惰性初始化 + 可替换）。
+    pub async fn extension_service() -> Result<Arc<CoreExtensionService>, InstanceError> {
+        Ok(Self::get().await?.extension().clone())
+    }
+
+    /// 访问加载器安装器执行服务（`Arc` 共享句柄，clone 廉价）。
+    pub fn installer(&self) -> &Arc<CoreInstallerService> {
+        &self.inner.installer
+    }
+
+    /// 便捷访问入口：一步拿到加载器安装器执行服务的共享句柄（惰性初始化 + 可替换）。
+    pub async fn installer_service() -> Result<Arc<CoreInstallerService>, InstanceError> {
+        Ok(Self::get().await?.installer().clone())
+    }
+
+    /// 访问服务端核心原地升级服务（`Arc` 共享句柄，clone 廉价）。
+    pub fn upgrade(&self) -> &Arc<CoreServerUpgradeService> {
+        &self.inner.upgrade
+    }
+
+    /// 便捷访问入口：一步拿到服务端核心原地升级服务的共享句柄（惰性初始化 + 可替换）。
+    pub async fn upgrade_service() -> Result<Arc<CoreServerUpgradeService>, InstanceError> {
+        Ok(Self::get().await?.upgrade().clone())
+    }
+
     /// 访问设置信息服务（`Arc` 共享句柄，clone 廉价）。
     pub fn settings(&self) -> &Arc<CoreSettingsService> {
         &self.inner.settings
@@ -304,6 +424,8 @@ impl AppServices {
         let system = self.inner.system.clone();
         let instance = self.inner.instance.clone();
         let server = self.inner.server.clone();
+        let config = self.inner.config.clone();
+        let settings = self.inner.settings.clone();
         self.inner
             .plugin
             .get_or_try_init(move || async move {
@@ -312,7 +434,10 @@ impl AppServices {
                     &root,
                     root.join("data"),
                     root.join("plugin-state.sqlite"),
-                    Some(Arc::new(ApplicationPluginReadHost::new(system, instance, server, &root))),
+                    Some(Arc::new(ApplicationPluginReadHost::new(
+                        system, instance, server, config, &root,
+                    ))),
+                    Some(settings),
                 )
                 .await
                 .map(Arc::new)
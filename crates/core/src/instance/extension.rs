@@ -1,20 +1,90 @@
+//! 实例扩展（插件、模组、数据包）的描述与一致性检查。
+//!
+//! 扩展文件内的元数据（`plugin.yml`、`fabric.mod.json`、`mods.toml` 等）由上层解析为
+//! [`ExtensionMetadata`]；[`extension_issues`] 在此基础上找出重复的扩展 ID 与缺失的
+//! 必需依赖。本模块只做纯计算。
+
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+use serde::{Deserialize, Serialize};
+
 /// 实例目录中可管理的扩展类型。
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum InstanceExtensionKind {
     Plugin,
     Mod,
     Datapack,
 }
 
+/// 扩展元数据的来源格式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtensionMetadataFormat {
+    /// Bukkit 系 `plugin.yml`。
+    Bukkit,
+    /// Paper `paper-plugin.yml`。
+    Paper,
+    /// Fabric `fabric.mod.json`。
+    Fabric,
+    /// Quilt `quilt.mod.json`。
+    Quilt,
+    /// Forge `META-INF/mods.toml`。
+    Forge,
+    /// NeoForge `META-INF/neoforge.mods.toml`。
+    NeoForge,
+    /// Velocity `velocity-plugin.json`。
+    Velocity,
+}
+
+/// 扩展声明的依赖。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtensionDependency {
+    pub id: String,
+    /// 缺失时扩展无法加载。
+    pub required: bool,
+    /// 声明的版本范围，原样保留各格式的写法。
+    #[serde(default)]
+    pub version_range: Option<String>,
+}
+
+/// 从扩展文件内读取的元数据。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtensionMetadata {
+    pub format: ExtensionMetadataFormat,
+    /// 扩展 ID；Bukkit 系插件为插件名。
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub authors: Vec<String>,
+    /// 对其他扩展的依赖，不含游戏本体与加载器。
+    #[serde(default)]
+    pub dependencies: Vec<ExtensionDependency>,
+    /// 该扩展额外提供的 ID（Fabric / Quilt 的 `provides`）。
+    #[serde(default)]
+    pub provides: Vec<String>,
+    /// 支持的游戏版本范围（或 Bukkit 系的 `api-version`）。
+    #[serde(default)]
+    pub game_versions: Option<String>,
+    /// 支持的加载器版本范围。
+    #[serde(default)]
+    pub loader_versions: Option<String>,
+}
+
 /// 单个实例扩展的只读描述。
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct InstanceExtension {
     pub kind: InstanceExtensionKind,
     pub file_name: String,
     pub path: PathBuf,
     pub enabled: bool,
+    /// 文件内的元数据；无法识别或读取失败时为 `None`。
+    #[serde(default)]
+    pub metadata: Option<ExtensionMetadata>,
 }
 
 impl InstanceExtension {
@@ -31,7 +101,121 @@ impl InstanceExtension {
         if path.as_os_str().is_empty() {
             return Err(InstanceExtensionError::EmptyPath);
         }
-        Ok(Self { kind, file_name, path, enabled })
+        Ok(Self {
+            kind,
+            file_name,
+            path,
+            enabled,
+            metadata: None,
+        })
+    }
+
+    /// 附加从文件内读取的元数据。
+    pub fn with_metadata(mut self, metadata: Option<ExtensionMetadata>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+}
+
+/// 扩展之间的一致性问题。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExtensionIssue {
+    /// 多个已启用的文件声明了同一扩展 ID，通常是新旧版本并存。
+    DuplicateId {
+        extension_kind: InstanceExtensionKind,
+        id: String,
+        file_names: Vec<String>,
+    },
+    /// 已启用的扩展缺少必需依赖。
+    MissingDependency {
+        file_name: String,
+        id: String,
+        dependency: ExtensionDependency,
+    },
+}
+
+/// 由游戏本体或加载器提供、不会以扩展文件出现的依赖 ID。
+const PLATFORM_DEPENDENCY_IDS: &[&str] = &[
+    "minecraft",
+    "java",
+    "fabricloader",
+    "fabric-loader",
+    "quilt_loader",
+    "forge",
+    "neoforge",
+    "velocity",
+];
+
+/// 找出已启用扩展之间的重复 ID 与缺失的必需依赖。
+///
+/// 插件与模组分别检查；ID 比较不区分大小写（Bukkit 系插件名不区分大小写）。
+pub fn extension_issues(extensions: &[InstanceExtension]) -> Vec<ExtensionIssue> {
+    let mut issues = Vec::new();
+    for kind in [InstanceExtensionKind::Plugin, InstanceExtensionKind::Mod] {
+        let enabled: Vec<(&InstanceExtension, &ExtensionMetadata)> = extensions
+            .iter()
+            .filter(|extension| extension.enabled && extension.kind == kind)
+            .filter_map(|extension| Some((extension, extension.metadata.as_ref()?)))
+            .collect();
+
+        let mut by_id: BTreeMap<String, (String, Vec<String>)> = BTreeMap::new();
+        for (extension, metadata) in &enabled {
+            by_id
+                .entry(metadata.id.to_ascii_lowercase())
+                .or_insert_with(|| (metadata.id.clone(), Vec::new()))
+                .1
+                .push(extension.file_name.clone());
+        }
+        for (id, file_names) in by_id.values() {
+            if file_names.len() > 1 {
+                issues.push(ExtensionIssue::DuplicateId {
+                    extension_kind: kind,
+                    id: id.clone(),
+                    file_names: file_names.clone(),
+                });
+            }
+        }
+
+        let available = |id: &str| {
+            PLATFORM_DEPENDENCY_IDS
+                .iter()
+                .any(|platform| platform.eq_ignore_ascii_case(id))
+                || enabled.iter().any(|(_, metadata)| {
+                    metadata.id.eq_ignore_ascii_case(id)
+                        || metadata
+                            .provides
+                            .iter()
+                            .any(|provided| provided.eq_ignore_ascii_case(id))
+                })
+        };
+        for (extension, metadata) in &enabled {
+            for dependency in &metadata.dependencies {
+                if dependency.required && !available(&dependency.id) {
+                    issues.push(ExtensionIssue::MissingDependency {
+                        file_name: extension.file_name.clone(),
+                        id: metadata.id.clone(),
+                        dependency: dependency.clone(),
+                    });
+                }
+            }
+        }
+    }
+    issues
+}
+
+/// 实例扩展列表及其一致性问题。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstanceExtensionReport {
+    pub extensions: Vec<InstanceExtension>,
+    pub issues: Vec<ExtensionIssue>,
+}
+
+impl InstanceExtensionReport {
+    /// 汇总扩展列表并计算一致性问题。
+    pub fn new(extensions: Vec<InstanceExtension>) -> Self {
+        let issues = extension_issues(&extensions);
+        Self { extensions, issues }
     }
 }
 
@@ -51,3 +235,89 @@ impl std::fmt::Display for InstanceExtensionError {
 }
 
 impl std::error::Error for InstanceExtensionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extension(
+        kind: InstanceExtensionKind,
+        file_name: &str,
+        id: &str,
+        dependencies: &[(&str, bool)],
+        enabled: bool,
+    ) -> InstanceExtension {
+        InstanceExtension::new(kind, file_name, PathBuf::from(file_name), enabled)
+            .unwrap()
+            .with_metadata(Some(ExtensionMetadata {
+                format: ExtensionMetadataFormat::Fabric,
+                id: id.to_owned(),
+                name: None,
+                version: None,
+                authors: Vec::new(),
+                dependencies: dependencies
+                    .iter()
+                    .map(|(id, required)| ExtensionDependency {
+                        id: (*id).to_owned(),
+                        required: *required,
+                        version_range: None,
+                    })
+                    .collect(),
+                provides: if id == "fabric-api" {
+                    vec!["fabric".to_owned()]
+                } else {
+                    Vec::new()
+                },
+                game_versions: None,
+                loader_versions: None,
+            }))
+    }
+
+    #[test]
+    fn reports_duplicates_and_missing_required_dependencies() {
+        use InstanceExtensionKind::{Mod, Plugin};
+        let extensions = vec![
+            extension(Mod, "sodium-0.5.jar", "sodium", &[("fabric", true)], true),
+            extension(Mod, "sodium-0.6.jar", "Sodium", &[], true),
+            extension(Mod, "fabric-api.jar", "fabric-api", &[("minecraft", true)], true),
+            extension(Mod, "iris.jar", "iris", &[("sodium", true), ("modmenu", false)], true),
+            extension(Mod, "addon.jar", "addon", &[("lithium", true)], true),
+            // 已禁用的文件既不参与重复检查，也不满足依赖
+            extension(Mod, "lithium.jar", "lithium", &[], false),
+            extension(Mod, "sodium-old.jar", "sodium", &[], false),
+            // 插件与模组分开检查
+            extension(Plugin, "Essentials.jar", "Essentials", &[("Vault", true)], true),
+            extension(Plugin, "sodium.jar", "sodium", &[], true),
+        ];
+
+        let report = InstanceExtensionReport::new(extensions);
+        assert_eq!(
+            report.issues,
+            vec![
+                ExtensionIssue::MissingDependency {
+                    file_name: "Essentials.jar".to_owned(),
+                    id: "Essentials".to_owned(),
+                    dependency: ExtensionDependency {
+                        id: "Vault".to_owned(),
+                        required: true,
+                        version_range: None,
+                    },
+                },
+                ExtensionIssue::DuplicateId {
+                    extension_kind: Mod,
+                    id: "sodium".to_owned(),
+                    file_names: vec!["sodium-0.5.jar".to_owned(), "sodium-0.6.jar".to_owned()],
+                },
+                ExtensionIssue::MissingDependency {
+                    file_name: "addon.jar".to_owned(),
+                    id: "addon".to_owned(),
+                    dependency: ExtensionDependency {
+                        id: "lithium".to_owned(),
+                        required: true,
+                        version_range: None,
+                    },
+                },
+            ]
+        );
+    }
+}
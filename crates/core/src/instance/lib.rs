@@ -4,10 +4,16 @@ pub mod import;
 pub mod lifecycle;
 pub mod model;
 pub mod player;
+pub mod port;
 pub mod repository;
 pub mod server_metadata;
+pub mod template;
 
-pub use extension::{InstanceExtension, InstanceExtensionError, InstanceExtensionKind};
+pub use extension::{
+    ExtensionDependency, ExtensionIssue, ExtensionMetadata, ExtensionMetadataFormat,
+    InstanceExtension, InstanceExtensionError, InstanceExtensionKind, InstanceExtensionReport,
+    extension_issues,
+};
 pub use identity::InstanceIdentity;
 pub use import::{InstanceImportError, InstanceImportPlan, InstanceImportRequest, plan_import};
 pub use lifecycle::{
@@ -17,6 +23,9 @@ pub use lifecycle::{
 };
 pub use model::{Instance, InstanceError, InstanceId, InstanceSpec, LocalLaunch, StartupMode};
 pub use player::{PlayerName, PlayerNameError, PlayerSnapshot};
+pub use port::{
+    InstanceListEntry, PortBinding, PortConflict, PortHolder, PortKind, PortProtocol, PortRegistry,
+};
 pub use repository::InstanceRepository;
 pub use server_metadata::{
     SERVER_METADATA_SNAPSHOT_SCHEMA_VERSION, ServerMetadataComponent, ServerMetadataDiagnostic,
@@ -24,3 +33,7 @@ pub use server_metadata::{
     ServerMetadataMinecraft, ServerMetadataSnapshot, ServerMetadataSnapshotValidity,
     ServerMetadataSubject, ServerMetadataSubjectKind,
 };
+pub use template::{
+    InstanceTemplate, PortStrategy, TemplateBackupPolicy, TemplateCronAction, TemplateCronTask,
+    TemplateDocumentPatch, TemplateError, TemplateLaunchDefaults,
+};
@@ -0,0 +1,316 @@
+//! 实例端口登记与冲突检测。
+//!
+//! 一个实例可能占用多个端口：游戏端口（`server-port`）、查询端口（`query.port`，
+//! UDP）、RCON 端口（`rcon.port`）以及代理端的监听端口。[`PortRegistry`] 汇总
+//! 全部受管实例的端口，找出实例之间的冲突，并为新实例挑选可用端口。端口的
+//! 读取（配置文件解析）与主机占用探测由上层完成，本模块只做纯计算。
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::model::{Instance, InstanceId};
+
+/// 端口用途。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PortKind {
+    /// 游戏端口（`server-port`）。
+    Server,
+    /// 查询端口（`query.port`）。
+    Query,
+    /// RCON 端口（`rcon.port`）。
+    Rcon,
+    /// 代理端（Velocity / BungeeCord）的监听端口。
+    ProxyListener,
+}
+
+impl PortKind {
+    /// 端口使用的传输协议；只有相同协议的端口才会互相冲突。
+    pub const fn protocol(self) -> PortProtocol {
+        match self {
+            Self::Query => PortProtocol::Udp,
+            Self::Server | Self::Rcon | Self::ProxyListener => PortProtocol::Tcp,
+        }
+    }
+}
+
+/// 传输协议。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PortProtocol {
+    Tcp,
+    Udp,
+}
+
+/// 实例占用的一个端口。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortBinding {
+    pub kind: PortKind,
+    pub port: u16,
+}
+
+/// 占用某端口的实例。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortHolder {
+    pub instance_id: InstanceId,
+    pub kind: PortKind,
+}
+
+/// 同一协议下被多处占用的端口。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PortConflict {
+    pub port: u16,
+    pub protocol: PortProtocol,
+    /// 占用该端口的全部实例；仅主机占用时为待检查的实例自身。
+    pub holders: Vec<PortHolder>,
+    /// 主机上已有其他进程占用该端口。
+    #[serde(default)]
+    pub host_in_use: bool,
+}
+
+impl fmt::Display for PortConflict {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let holders = self
+            .holders
+            .iter()
+            .map(|holder| holder.instance_id.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(formatter, "port {} is used by {holders}", self.port)?;
+        if self.host_in_use {
+            write!(formatter, " and another process on the host")?;
+        }
+        Ok(())
+    }
+}
+
+/// 实例列表中的一项：实例记录与其涉及的端口冲突。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstanceListEntry {
+    #[serde(flatten)]
+    pub instance: Instance,
+    #[serde(default)]
+    pub port_conflicts: Vec<PortConflict>,
+}
+
+impl InstanceListEntry {
+    /// 为每个实例挑出其作为占用方之一的冲突。
+    pub fn collect(instances: Vec<Instance>, conflicts: &[PortConflict]) -> Vec<Self> {
+        instances
+            .into_iter()
+            .map(|instance| {
+                let port_conflicts = conflicts
+                    .iter()
+                    .filter(|conflict| {
+                        conflict
+                            .holders
+                            .iter()
+                            .any(|holder| holder.instance_id == instance.id)
+                    })
+                    .cloned()
+                    .collect();
+                Self { instance, port_conflicts }
+            })
+            .collect()
+    }
+}
+
+/// 全部受管实例的端口登记表。
+#[derive(Debug, Clone, Default)]
+pub struct PortRegistry {
+    entries: Vec<(InstanceId, Vec<PortBinding>)>,
+}
+
+impl PortRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 登记实例占用的端口；重复登记同一实例时覆盖。
+    pub fn insert(&mut self, instance_id: InstanceId, bindings: Vec<PortBinding>) {
+        self.remove(&instance_id);
+        self.entries.push((instance_id, bindings));
+    }
+
+    /// 移除实例的端口登记。
+    pub fn remove(&mut self, instance_id: &InstanceId) {
+        self.entries.retain(|(id, _)| id != instance_id);
+    }
+
+    /// 已登记实例之间的全部端口冲突，按端口排序。
+    ///
+    /// 同一实例的两个用途共用一个端口（如 `rcon.port` 等于 `server-port`）同样视为冲突。
+    pub fn conflicts(&self) -> Vec<PortConflict> {
+        let mut holders: BTreeMap<(u16, PortProtocol), Vec<PortHolder>> = BTreeMap::new();
+        for (instance_id, bindings) in &self.entries {
+            for binding in bindings {
+                holders
+                    .entry((binding.port, binding.kind.protocol()))
+                    .or_default()
+                    .push(PortHolder {
+                        instance_id: instance_id.clone(),
+                        kind: binding.kind,
+                    });
+            }
+        }
+        holders
+            .into_iter()
+            .filter(|(_, holders)| holders.len() > 1)
+            .map(|((port, protocol), holders)| PortConflict {
+                port,
+                protocol,
+                holders,
+                host_in_use: false,
+            })
+            .collect()
+    }
+
+    /// 检查候选实例的端口是否与其他已登记实例冲突（忽略同 ID 的旧登记）。
+    ///
+    /// `host_in_use` 判断端口是否已被主机上的其他进程占用。
+    pub fn check(
+        &self,
+        instance_id: &InstanceId,
+        bindings: &[PortBinding],
+        host_in_use: impl Fn(PortBinding) -> bool,
+    ) -> Vec<PortConflict> {
+        let mut candidate = self.clone();
+        candidate.insert(instance_id.clone(), bindings.to_vec());
+        let internal = candidate.conflicts();
+
+        let mut conflicts = Vec::new();
+        for binding in bindings {
+            let protocol = binding.kind.protocol();
+            if conflicts.iter().any(|conflict: &PortConflict| {
+                conflict.port == binding.port && conflict.protocol == protocol
+            }) {
+                continue;
+            }
+            let holders = internal
+                .iter()
+                .find(|conflict| conflict.port == binding.port && conflict.protocol == protocol)
+                .map(|conflict| conflict.holders.clone());
+            let host_in_use = host_in_use(*binding);
+            if holders.is_none() && !host_in_use {
+                continue;
+            }
+            conflicts.push(PortConflict {
+                port: binding.port,
+                protocol,
+                holders: holders.unwrap_or_else(|| {
+                    vec![PortHolder {
+                        instance_id: instance_id.clone(),
+                        kind: binding.kind,
+                    }]
+                }),
+                host_in_use,
+            });
+        }
+        conflicts
+    }
+
+    /// 从 `start` 起寻找未被任何实例占用、且主机可用的端口。
+    pub fn next_free(
+        &self,
+        start: u16,
+        kind: PortKind,
+        host_in_use: impl Fn(PortBinding) -> bool,
+    ) -> Option<u16> {
+        let protocol = kind.protocol();
+        (start.max(1)..=u16::MAX).find(|&port| {
+            let taken = self.entries.iter().any(|(_, bindings)| {
+                bindings
+                    .iter()
+                    .any(|binding| binding.port == port && binding.kind.protocol() == protocol)
+            });
+            !taken && !host_in_use(PortBinding { kind, port })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(value: &str) -> InstanceId {
+        InstanceId::new(value).unwrap()
+    }
+
+    fn binding(kind: PortKind, port: u16) -> PortBinding {
+        PortBinding { kind, port }
+    }
+
+    #[test]
+    fn detects_conflicts_per_protocol() {
+        let mut registry = PortRegistry::new();
+        registry.insert(
+            id("lobby"),
+            vec![binding(PortKind::Server, 25565), binding(PortKind::Query, 25565)],
+        );
+        registry.insert(
+            id("survival"),
+            vec![binding(PortKind::Server, 25566), binding(PortKind::Rcon, 25575)],
+        );
+        // 查询端口走 UDP，与同号的游戏端口不冲突
+        assert!(registry.conflicts().is_empty());
+
+        registry.insert(
+            id("event"),
+            vec![binding(PortKind::Server, 25566), binding(PortKind::Rcon, 25575)],
+        );
+        let conflicts = registry.conflicts();
+        assert_eq!(
+            conflicts
+                .iter()
+                .map(|conflict| conflict.port)
+                .collect::<Vec<_>>(),
+            [25566, 25575]
+        );
+        assert_eq!(
+            conflicts[0].holders,
+            [
+                PortHolder {
+                    instance_id: id("survival"),
+                    kind: PortKind::Server
+                },
+                PortHolder {
+                    instance_id: id("event"),
+                    kind: PortKind::Server
+                },
+            ]
+        );
+
+        registry.remove(&id("event"));
+        assert!(registry.conflicts().is_empty());
+    }
+
+    #[test]
+    fn checks_candidates_against_instances_and_host() {
+        let mut registry = PortRegistry::new();
+        registry.insert(id("lobby"), vec![binding(PortKind::Server, 25565)]);
+        let host = |binding: PortBinding| binding.port == 25567;
+
+        let conflicts = registry.check(&id("new"), &[binding(PortKind::Server, 25565)], host);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].holders.len(), 2);
+        assert!(!conflicts[0].host_in_use);
+
+        let conflicts = registry.check(&id("new"), &[binding(PortKind::Server, 25567)], host);
+        assert!(conflicts[0].host_in_use);
+        assert_eq!(conflicts[0].holders[0].instance_id, id("new"));
+
+        // 重新检查已登记的实例时忽略它自己的旧记录
+        assert!(
+            registry
+                .check(&id("lobby"), &[binding(PortKind::Server, 25565)], host)
+                .is_empty()
+        );
+
+        assert_eq!(registry.next_free(25565, PortKind::Server, host), Some(25566));
+        registry.insert(id("other"), vec![binding(PortKind::ProxyListener, 25566)]);
+        assert_eq!(registry.next_free(25565, PortKind::Server, host), Some(25568));
+        assert_eq!(registry.next_free(u16::MAX, PortKind::Server, |_| true), None);
+    }
+}
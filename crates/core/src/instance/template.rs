@@ -0,0 +1,509 @@
+//! 实例配置模板。
+//!
+//! 模板描述可批量套用到多个已有实例的配置：内存、JVM 参数、Java 路径与端口
+//! 策略等启动默认值，server.properties 与其他配置文档的修改，以及定时任务和
+//! 备份策略。本模块只负责模板校验与纯计算（端口分配、套用后的实例记录），
+//! 文件读写与定时任务 / 备份设置的落地由应用层完成。
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::model::{Instance, InstanceError};
+use super::port::{PortBinding, PortKind, PortRegistry};
+
+/// 模板名称最大长度（字符数）。
+pub const MAX_TEMPLATE_NAME_LENGTH: usize = 64;
+
+/// server.properties 中的端口键；端口策略生效时随实例端口一并写入。
+pub const SERVER_PORT_PROPERTY: &str = "server-port";
+
+/// 套用模板时的端口分配策略。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PortStrategy {
+    /// 保留各实例现有端口。
+    #[default]
+    Keep,
+    /// 使用固定端口，只能套用到单个实例。
+    Fixed { port: u16 },
+    /// 从 `start` 起按实例顺序分配未被其他实例占用的端口。
+    Sequential { start: u16 },
+}
+
+/// 模板中的启动默认值；`None` 表示保留实例现有值。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateLaunchDefaults {
+    #[serde(default)]
+    pub max_memory_mib: Option<u32>,
+    #[serde(default)]
+    pub min_memory_mib: Option<u32>,
+    #[serde(default)]
+    pub jvm_arguments: Option<Vec<String>>,
+    #[serde(default)]
+    pub java_executable: Option<PathBuf>,
+    #[serde(default)]
+    pub port: PortStrategy,
+}
+
+/// 对配置文档（YAML / TOML / JSON5）中单个键路径的修改。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TemplateDocumentPatch {
+    /// 相对服务器根目录的文件路径，使用 `/` 分隔
+    pub file: String,
+    pub path: Vec<String>,
+    /// 新值，仅支持布尔、数字、字符串与 null
+    pub value: Value,
+}
+
+/// 定时任务的服务器动作。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TemplateCronAction {
+    Restart,
+    Command { command: String },
+}
+
+/// 套用时为每个实例创建的定时任务；同名任务已存在时跳过。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateCronTask {
+    pub name: String,
+    pub cron_expression: String,
+    pub action: TemplateCronAction,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+/// 套用时写入的备份策略。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TemplateBackupPolicy {
+    pub max_backups: u32,
+    pub auto_backup_enabled: bool,
+    /// 自动备份间隔（小时）
+    pub auto_backup_interval_hours: u32,
+    /// 自动备份内容：`core`、`config`、`plugins`、`world`、`logs`
+    pub auto_backup_contents: Vec<String>,
+}
+
+/// 可复用的实例配置模板。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstanceTemplate {
+    /// 模板 ID；保存新模板时留空由存储分配。
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub launch: TemplateLaunchDefaults,
+    /// 写入 server.properties 的键值
+    #[serde(default)]
+    pub properties: BTreeMap<String, String>,
+    #[serde(default)]
+    pub documents: Vec<TemplateDocumentPatch>,
+    #[serde(default)]
+    pub cron_tasks: Vec<TemplateCronTask>,
+    #[serde(default)]
+    pub backup: Option<TemplateBackupPolicy>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl InstanceTemplate {
+    /// 规范化名称并校验模板自身的一致性（不涉及具体实例）。
+    pub fn normalize_and_validate(&mut self) -> Result<(), TemplateError> {
+        self.name = self.name.trim().to_string();
+        if self.name.is_empty() || self.name.chars().count() > MAX_TEMPLATE_NAME_LENGTH {
+            return Err(TemplateError::InvalidName);
+        }
+
+        let launch = &self.launch;
+        if launch.max_memory_mib == Some(0) {
+            return Err(TemplateError::InvalidLaunch("maximum memory must be positive"));
+        }
+        if let (Some(min), Some(max)) = (launch.min_memory_mib, launch.max_memory_mib)
+            && min > max
+        {
+            return Err(TemplateError::InvalidLaunch("minimum memory exceeds maximum memory"));
+        }
+        if matches!(
+            launch.port,
+            PortStrategy::Fixed { port: 0 } | PortStrategy::Sequential { start: 0 }
+        ) {
+            return Err(TemplateError::InvalidLaunch("port 0 is not supported"));
+        }
+
+        if self.properties.contains_key(SERVER_PORT_PROPERTY) {
+            return Err(TemplateError::InvalidProperty(SERVER_PORT_PROPERTY.to_string()));
+        }
+        if let Some(key) = self.properties.iter().find_map(|(key, value)| {
+            (key.trim().is_empty() || key != key.trim() || value.contains(['\n', '\r']))
+                .then(|| key.clone())
+        }) {
+            return Err(TemplateError::InvalidProperty(key));
+        }
+
+        for patch in &self.documents {
+            let file = patch.file.trim();
+            if file.is_empty()
+                || file.ends_with("server.properties")
+                || patch.path.is_empty()
+                || patch.path.iter().any(|segment| segment.is_empty())
+                || patch.value.is_array()
+                || patch.value.is_object()
+            {
+                return Err(TemplateError::InvalidDocumentPatch(patch.file.clone()));
+            }
+        }
+
+        let mut cron_names = BTreeSet::new();
+        for task in &self.cron_tasks {
+            let name = task.name.trim();
+            if name.is_empty()
+                || task.cron_expression.trim().is_empty()
+                || matches!(&task.action, TemplateCronAction::Command { command } if command.trim().is_empty())
+                || !cron_names.insert(name)
+            {
+                return Err(TemplateError::InvalidCronTask(task.name.clone()));
+            }
+        }
+
+        if let Some(backup) = &self.backup
+            && (backup.max_backups == 0 || backup.auto_backup_interval_hours == 0)
+        {
+            return Err(TemplateError::InvalidBackupPolicy);
+        }
+        Ok(())
+    }
+
+    /// 按端口策略为 `targets` 依次分配游戏端口。
+    ///
+    /// `targets` 为各目标实例及其当前占用的全部端口（游戏、查询、RCON 等），
+    /// `registry` 登记其余受管实例的端口，`host_in_use` 判断端口是否已被主机上的
+    /// 其他进程占用。新端口不会与其他实例或目标实例自身的任何同协议端口冲突。
+    /// 返回值与 `targets` 一一对应。
+    pub fn assign_ports(
+        &self,
+        targets: &[(&Instance, Vec<PortBinding>)],
+        registry: &PortRegistry,
+        host_in_use: impl Fn(PortBinding) -> bool,
+    ) -> Result<Vec<u16>, TemplateError> {
+        // 目标实例的非游戏端口保持不变，先计入占用，避免新游戏端口与之撞车
+        let mut taken = registry.clone();
+        for (instance, bindings) in targets {
+            taken.insert(instance.id.clone(), without_server_port(bindings));
+        }
+
+        match self.launch.port {
+            PortStrategy::Keep => Ok(targets.iter().map(|(instance, _)| instance.port).collect()),
+            PortStrategy::Fixed { port } => {
+                if targets.len() > 1 {
+                    return Err(TemplateError::FixedPortShared { port });
+                }
+                for (instance, bindings) in targets {
+                    let candidate = with_server_port(bindings, port);
+                    // 实例当前就在使用该端口时，主机占用来自它自己
+                    let conflicts = taken.check(&instance.id, &candidate, |binding| {
+                        binding.port != instance.port && host_in_use(binding)
+                    });
+                    if conflicts.iter().any(|conflict| {
+                        conflict.port == port && conflict.protocol == PortKind::Server.protocol()
+                    }) {
+                        return Err(TemplateError::PortInUse { port });
+                    }
+                }
+                Ok(targets.iter().map(|_| port).collect())
+            }
+            PortStrategy::Sequential { start } => {
+                let mut next = start;
+                let mut ports = Vec::with_capacity(targets.len());
+                for (instance, bindings) in targets {
+                    let port = taken
+                        .next_free(next, PortKind::Server, &host_in_use)
+                        .ok_or(TemplateError::PortsExhausted { start })?;
+                    // 已分配的端口登记在 `taken` 中，停在 u16::MAX 时不会重复分配
+                    next = port.saturating_add(1);
+                    taken.insert(instance.id.clone(), with_server_port(bindings, port));
+                    ports.push(port);
+                }
+                Ok(ports)
+            }
+        }
+    }
+
+    /// 返回套用启动默认值与端口后的实例记录，并复用实例字段校验。
+    pub fn apply_launch(&self, instance: &Instance, port: u16) -> Result<Instance, InstanceError> {
+        let launch = &self.launch;
+        let mut updated = instance.clone();
+        updated.port = port;
+        if let Some(max) = launch.max_memory_mib {
+            updated.max_memory_mib = max;
+        }
+        if let Some(min) = launch.min_memory_mib {
+            updated.min_memory_mib = min;
+        }
+        if let Some(arguments) = &launch.jvm_arguments {
+            updated.launch.jvm_arguments = arguments.clone();
+        }
+        if let Some(java) = &launch.java_executable {
+            updated.launch.java_executable =
+                Some(java.clone()).filter(|path| !path.as_os_str().is_empty());
+        }
+        updated.validate()?;
+        Ok(updated)
+    }
+
+    /// 返回写入 server.properties 的键值；端口策略生效时附带 `server-port`。
+    pub fn properties_for_port(&self, port: u16) -> BTreeMap<String, String> {
+        let mut properties = self.properties.clone();
+        if self.launch.port != PortStrategy::Keep {
+            properties.insert(SERVER_PORT_PROPERTY.to_string(), port.to_string());
+        }
+        properties
+    }
+
+    /// 按文件分组文档修改，同一文件内保持声明顺序。
+    pub fn document_patches(&self) -> BTreeMap<&str, Vec<&TemplateDocumentPatch>> {
+        let mut grouped: BTreeMap<&str, Vec<&TemplateDocumentPatch>> = BTreeMap::new();
+        for patch in &self.documents {
+            grouped.entry(patch.file.trim()).or_default().push(patch);
+        }
+        grouped
+    }
+}
+
+/// 去掉游戏端口后的端口列表。
+fn without_server_port(bindings: &[PortBinding]) -> Vec<PortBinding> {
+    bindings
+        .iter()
+        .copied()
+        .filter(|binding| binding.kind != PortKind::Server)
+        .collect()
+}
+
+/// 将游戏端口替换为 `port` 后的端口列表。
+fn with_server_port(bindings: &[PortBinding], port: u16) -> Vec<PortBinding> {
+    let mut updated = without_server_port(bindings);
+    updated.insert(0, PortBinding { kind: PortKind::Server, port });
+    updated
+}
+
+/// 模板校验或套用失败。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    InvalidName,
+    InvalidLaunch(&'static str),
+    InvalidProperty(String),
+    InvalidDocumentPatch(String),
+    InvalidCronTask(String),
+    InvalidBackupPolicy,
+    FixedPortShared { port: u16 },
+    PortInUse { port: u16 },
+    PortsExhausted { start: u16 },
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidName => {
+                write!(formatter, "template name must be 1-{MAX_TEMPLATE_NAME_LENGTH} characters")
+            }
+            Self::InvalidLaunch(reason) => write!(formatter, "invalid launch defaults: {reason}"),
+            Self::InvalidProperty(key) => {
+                write!(formatter, "invalid server.properties entry in template: {key}")
+            }
+            Self::InvalidDocumentPatch(file) => {
+                write!(formatter, "invalid config document patch for {file}")
+            }
+            Self::InvalidCronTask(name) => {
+                write!(formatter, "invalid cron task in template: {name}")
+            }
+            Self::InvalidBackupPolicy => write!(formatter, "invalid backup policy in template"),
+            Self::FixedPortShared { port } => {
+                write!(formatter, "fixed port {port} cannot be applied to more than one instance")
+            }
+            Self::PortInUse { port } => {
+                write!(formatter, "port {port} is used by another instance")
+            }
+            Self::PortsExhausted { start } => {
+                write!(formatter, "no free port available from {start}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instance::{InstanceId, InstanceSpec, LocalLaunch, StartupMode};
+
+    fn instance(id: &str, port: u16) -> Instance {
+        Instance::new(InstanceSpec {
+            id: InstanceId::new(id).unwrap(),
+            name: id.to_string(),
+            aliases: Vec::new(),
+            core_type: "paper".to_string(),
+            core_version: "1.21.4".to_string(),
+            game_version: "1.21.4".to_string(),
+            directory: PathBuf::from(format!("/srv/{id}")),
+            port,
+            max_memory_mib: 2048,
+            min_memory_mib: 1024,
+            created_at_unix_secs: 0,
+            last_started_at_unix_secs: None,
+            server_metadata: None,
+            launch: LocalLaunch {
+                startup_mode: StartupMode::Jar,
+                startup_target: Some(PathBuf::from("server.jar")),
+                custom_command: None,
+                custom_executable: None,
+                custom_arguments: Vec::new(),
+                java_executable: None,
+                jvm_arguments: Vec::new(),
+            },
+        })
+        .unwrap()
+    }
+
+    fn binding(kind: PortKind, port: u16) -> PortBinding {
+        PortBinding { kind, port }
+    }
+
+    fn event_template(port: PortStrategy) -> InstanceTemplate {
+        InstanceTemplate {
+            id: String::new(),
+            name: " 活动服 ".to_string(),
+            description: String::new(),
+            launch: TemplateLaunchDefaults {
+                max_memory_mib: Some(4096),
+                min_memory_mib: None,
+                jvm_arguments: Some(vec!["-XX:+UseG1GC".to_string()]),
+                java_executable: None,
+                port,
+            },
+            properties: BTreeMap::from([("max-players".to_string(), "50".to_string())]),
+            documents: Vec::new(),
+            cron_tasks: Vec::new(),
+            backup: None,
+        }
+    }
+
+    #[test]
+    fn sequential_ports_skip_occupied_and_sync_properties() {
+        let mut template = event_template(PortStrategy::Sequential { start: 30000 });
+        template.normalize_and_validate().unwrap();
+        assert_eq!(template.name, "活动服");
+
+        let instances = [instance("a", 25565), instance("b", 25566), instance("c", 25567)];
+        // b 的 RCON 端口占用 30003，其他实例占用 30001 / 30002，主机占用 30005
+        let targets: Vec<_> = instances
+            .iter()
+            .map(|instance| {
+                let mut bindings = vec![binding(PortKind::Server, instance.port)];
+                if instance.id.as_str() == "b" {
+                    bindings.push(binding(PortKind::Rcon, 30003));
+                }
+                (instance, bindings)
+            })
+            .collect();
+        let mut registry = PortRegistry::new();
+        registry.insert(
+            InstanceId::new("other").unwrap(),
+            vec![binding(PortKind::Server, 30001), binding(PortKind::Rcon, 30002)],
+        );
+        assert_eq!(
+            template
+                .assign_ports(&targets, &registry, |binding| binding.port == 30005)
+                .unwrap(),
+            [30000, 30004, 30006]
+        );
+
+        let updated = template.apply_launch(&instances[1], 30003).unwrap();
+        assert_eq!(
+            (updated.port, updated.max_memory_mib, updated.min_memory_mib),
+            (30003, 4096, 1024)
+        );
+        assert_eq!(updated.launch.jvm_arguments, ["-XX:+UseG1GC"]);
+        assert_eq!(
+            template
+                .properties_for_port(30003)
+                .get(SERVER_PORT_PROPERTY)
+                .map(String::as_str),
+            Some("30003")
+        );
+
+        let near_end = event_template(PortStrategy::Sequential { start: u16::MAX });
+        assert_eq!(
+            near_end.assign_ports(&targets[..2], &PortRegistry::new(), |_| false),
+            Err(TemplateError::PortsExhausted { start: u16::MAX })
+        );
+    }
+
+    #[test]
+    fn rejects_inconsistent_templates_and_shared_fixed_ports() {
+        let fixed = event_template(PortStrategy::Fixed { port: 25565 });
+        let instances = [instance("a", 25565), instance("b", 25566)];
+        let targets: Vec<_> = instances
+            .iter()
+            .map(|instance| (instance, vec![binding(PortKind::Server, instance.port)]))
+            .collect();
+        assert_eq!(
+            fixed.assign_ports(&targets, &PortRegistry::new(), |_| false),
+            Err(TemplateError::FixedPortShared { port: 25565 })
+        );
+        // 实例自身正在使用该端口时不算主机占用
+        assert_eq!(
+            fixed.assign_ports(&targets[..1], &PortRegistry::new(), |_| true),
+            Ok(vec![25565])
+        );
+        let mut registry = PortRegistry::new();
+        registry.insert(InstanceId::new("other").unwrap(), vec![binding(PortKind::Rcon, 25565)]);
+        assert_eq!(
+            fixed.assign_ports(&targets[..1], &registry, |_| false),
+            Err(TemplateError::PortInUse { port: 25565 })
+        );
+
+        let mut invalid = event_template(PortStrategy::Keep);
+        invalid.launch.min_memory_mib = Some(8192);
+        assert!(matches!(invalid.normalize_and_validate(), Err(TemplateError::InvalidLaunch(_))));
+
+        let mut invalid = event_template(PortStrategy::Keep);
+        invalid
+            .properties
+            .insert(SERVER_PORT_PROPERTY.to_string(), "1".to_string());
+        assert!(invalid.normalize_and_validate().is_err());
+
+        let mut invalid = event_template(PortStrategy::Keep);
+        invalid.cron_tasks = vec![
+            TemplateCronTask {
+                name: "restart".to_string(),
+                cron_expression: "0 0 4 * * *".to_string(),
+                action: TemplateCronAction::Restart,
+                enabled: true,
+            };
+            2
+        ];
+        assert_eq!(
+            invalid.normalize_and_validate(),
+            Err(TemplateError::InvalidCronTask("restart".to_string()))
+        );
+
+        // 模板内存与实例现有最小内存冲突时由实例校验拒绝
+        let mut small = event_template(PortStrategy::Keep);
+        small.launch.max_memory_mib = Some(512);
+        assert_eq!(
+            small.apply_launch(&instances[0], 25565),
+            Err(InstanceError::InvalidMemoryRange {
+                min_memory_mib: 1024,
+                max_memory_mib: 512
+            })
+        );
+    }
+}
@@ -0,0 +1,277 @@
+//! CurseForge 整合包清单（`manifest.json`）。
+//!
+//! CurseForge 客户端整合包是含 `manifest.json` 与覆盖目录的 zip：模组本体不在包内，
+//! 清单只记录 `projectID` / `fileID`，由上层通过 CurseForge API 解析出下载地址与摘要。
+//! 本模块只解析清单、识别加载器与判断客户端专用模组，不发起网络请求。
+//!
+//! 不含清单的 zip 视为现成的服务端包，由服务端检查识别核心与启动方式。
+
+use std::fmt;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use super::CoreKind;
+use super::import_modpack::ModpackLoader;
+use super::server_inspection::{InspectionOptions, read_archive_entries};
+
+/// 包内清单文件名。
+pub const CURSEFORGE_MANIFEST_FILE: &str = "manifest.json";
+
+/// 清单中 Minecraft 整合包的 `manifestType`。
+const MODPACK_MANIFEST_TYPE: &str = "minecraftModpack";
+/// 支持的清单格式版本。
+const SUPPORTED_MANIFEST_VERSION: u32 = 1;
+/// 清单未声明覆盖目录时的默认值。
+const DEFAULT_OVERRIDES_DIR: &str = "overrides";
+
+/// `modLoaders[].id` 前缀 → 加载器核心类型。
+const LOADER_PREFIXES: [(&str, CoreKind); 4] = [
+    ("neoforge", CoreKind::NeoForge),
+    ("forge", CoreKind::Forge),
+    ("fabric", CoreKind::Fabric),
+    ("quilt", CoreKind::Quilt),
+];
+
+/// 常见的客户端专用模组（文件名前缀，小写）；CurseForge 未标注运行环境时据此跳过。
+const KNOWN_CLIENT_ONLY_MODS: &[&str] = &[
+    "optifine",
+    "oculus",
+    "iris",
+    "sodium",
+    "embeddium",
+    "rubidium",
+    "entityculling",
+    "betterf3",
+    "legendarytooltips",
+    "fancymenu",
+    "drippyloadingscreen",
+    "notenoughanimations",
+];
+
+/// `manifest.json` 的内容。
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeManifest {
+    pub minecraft: CurseForgeMinecraft,
+    pub manifest_type: String,
+    pub manifest_version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub version: Option<String>,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default)]
+    pub files: Vec<CurseForgeManifestFile>,
+    /// 覆盖目录，相对整合包根目录。
+    #[serde(default = "default_overrides_dir")]
+    pub overrides: String,
+}
+
+/// 清单的 `minecraft` 段。
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseForgeMinecraft {
+    pub version: String,
+    #[serde(default)]
+    pub mod_loaders: Vec<CurseForgeModLoader>,
+}
+
+/// 加载器条目，`id` 形如 `forge-47.2.0`、`fabric-0.15.11`。
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct CurseForgeModLoader {
+    pub id: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+/// 清单中的一个文件。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct CurseForgeManifestFile {
+    #[serde(rename = "projectID")]
+    pub project_id: u64,
+    #[serde(rename = "fileID")]
+    pub file_id: u64,
+    /// 缺省为必需；可选文件不安装。
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+/// 清单无法使用的原因。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CurseForgeManifestError {
+    /// 整合包无法读取。
+    Unreadable(String),
+    /// JSON 结构不合法。
+    Invalid(String),
+    /// 不是 Minecraft 整合包清单。
+    UnsupportedType(String),
+    /// 不支持的 `manifestVersion`。
+    UnsupportedVersion(u32),
+}
+
+impl fmt::Display for CurseForgeManifestError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unreadable(message) => write!(formatter, "unreadable modpack: {message}"),
+            Self::Invalid(message) => {
+                write!(formatter, "invalid {CURSEFORGE_MANIFEST_FILE}: {message}")
+            }
+            Self::UnsupportedType(kind) => write!(formatter, "unsupported manifest type {kind}"),
+            Self::UnsupportedVersion(version) => {
+                write!(formatter, "unsupported manifest version {version}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CurseForgeManifestError {}
+
+fn default_overrides_dir() -> String {
+    DEFAULT_OVERRIDES_DIR.to_owned()
+}
+
+fn default_required() -> bool {
+    true
+}
+
+impl CurseForgeManifest {
+    /// 解析并校验清单：清单类型与格式版本。
+    pub fn parse(content: &str) -> Result<Self, CurseForgeManifestError> {
+        let manifest: Self = serde_json::from_str(content)
+            .map_err(|error| CurseForgeManifestError::Invalid(error.to_string()))?;
+        if manifest.manifest_type != MODPACK_MANIFEST_TYPE {
+            return Err(CurseForgeManifestError::UnsupportedType(manifest.manifest_type));
+        }
+        if manifest.manifest_version != SUPPORTED_MANIFEST_VERSION {
+            return Err(CurseForgeManifestError::UnsupportedVersion(manifest.manifest_version));
+        }
+        Ok(manifest)
+    }
+
+    /// 需要安装的文件（`required` 为真）。
+    pub fn required_files(&self) -> impl Iterator<Item = &CurseForgeManifestFile> {
+        self.files.iter().filter(|file| file.required)
+    }
+
+    /// 整合包使用的模组加载器，优先取 `primary` 条目；无可识别条目时为 `None`。
+    pub fn loader(&self) -> Option<ModpackLoader> {
+        let loaders = &self.minecraft.mod_loaders;
+        loaders
+            .iter()
+            .filter(|loader| loader.primary)
+            .chain(loaders.iter().filter(|loader| !loader.primary))
+            .find_map(|loader| parse_loader_id(&loader.id))
+    }
+}
+
+fn parse_loader_id(id: &str) -> Option<ModpackLoader> {
+    let (prefix, version) = id.trim().split_once('-')?;
+    let kind = LOADER_PREFIXES
+        .iter()
+        .find(|(candidate, _)| prefix.eq_ignore_ascii_case(candidate))
+        .map(|(_, kind)| *kind)?;
+    (!version.is_empty()).then(|| ModpackLoader { kind, version: version.to_owned() })
+}
+
+/// 判断模组是否只用于客户端。
+///
+/// `environments` 为平台标注的运行环境（`client`、`server`，大小写不敏感）；只标注了
+/// 客户端时直接判定，未标注时按 [`KNOWN_CLIENT_ONLY_MODS`] 匹配文件名前缀。
+pub fn is_client_only_mod(file_name: &str, environments: &[String]) -> bool {
+    let has = |side: &str| {
+        environments
+            .iter()
+            .any(|env| env.eq_ignore_ascii_case(side))
+    };
+    if has("client") || has("server") {
+        return !has("server");
+    }
+    let name = file_name.to_ascii_lowercase();
+    KNOWN_CLIENT_ONLY_MODS.iter().any(|known| {
+        name.strip_prefix(known)
+            .is_some_and(|rest| rest.starts_with(['-', '_', '.', '+']))
+    })
+}
+
+/// 读取 zip 根目录下的 CurseForge 清单。
+///
+/// 没有 `manifest.json`，或其中不是 CurseForge 清单（无 `manifestType`）时返回 `None`，
+/// 调用方应按现成的服务端包处理。
+pub fn read_curseforge_manifest(
+    archive: &Path,
+) -> Result<Option<CurseForgeManifest>, CurseForgeManifestError> {
+    let entries =
+        read_archive_entries(archive, &[CURSEFORGE_MANIFEST_FILE], &InspectionOptions::default())
+            .map_err(|error| CurseForgeManifestError::Unreadable(error.to_string()))?;
+    let Some(content) = entries.entries.get(CURSEFORGE_MANIFEST_FILE) else {
+        return Ok(None);
+    };
+    let content = String::from_utf8_lossy(content);
+    let is_curseforge = serde_json::from_str::<serde_json::Value>(&content)
+        .is_ok_and(|value| value.get("manifestType").is_some());
+    if !is_curseforge {
+        return Ok(None);
+    }
+    CurseForgeManifest::parse(&content).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"{
+        "minecraft": {
+            "version": "1.20.1",
+            "modLoaders": [
+                { "id": "fabric-0.15.11", "primary": false },
+                { "id": "forge-47.2.0", "primary": true }
+            ]
+        },
+        "manifestType": "minecraftModpack",
+        "manifestVersion": 1,
+        "name": "Example Pack",
+        "version": "1.0.0",
+        "files": [
+            { "projectID": 238222, "fileID": 5101366, "required": true },
+            { "projectID": 306612, "fileID": 4712866, "required": false },
+            { "projectID": 419699, "fileID": 4600000 }
+        ]
+    }"#;
+
+    #[test]
+    fn parses_files_and_primary_loader() {
+        let manifest = CurseForgeManifest::parse(MANIFEST).unwrap();
+        let file_ids: Vec<_> = manifest.required_files().map(|file| file.file_id).collect();
+        assert_eq!(file_ids, [5101366, 4600000]);
+        assert_eq!(manifest.overrides, "overrides");
+        assert_eq!(
+            manifest.loader(),
+            Some(ModpackLoader {
+                kind: CoreKind::Forge,
+                version: "47.2.0".to_owned()
+            })
+        );
+
+        let other = MANIFEST.replace("minecraftModpack", "minecraftWorld");
+        assert_eq!(
+            CurseForgeManifest::parse(&other),
+            Err(CurseForgeManifestError::UnsupportedType("minecraftWorld".to_owned()))
+        );
+    }
+
+    #[test]
+    fn detects_client_only_mods() {
+        let tags = |values: &[&str]| {
+            values
+                .iter()
+                .map(|value| (*value).to_owned())
+                .collect::<Vec<_>>()
+        };
+        assert!(is_client_only_mod("jei.jar", &tags(&["Client"])));
+        assert!(!is_client_only_mod("jei.jar", &tags(&["Client", "Server"])));
+        assert!(is_client_only_mod("oculus-mc1.20.1-1.6.9.jar", &[]));
+        assert!(!is_client_only_mod("oculus-mc1.20.1-1.6.9.jar", &tags(&["Server"])));
+        assert!(!is_client_only_mod("irisshaders-compat.jar", &[]));
+    }
+}
@@ -311,7 +311,7 @@ fn adopt_best_launch(
     Some(candidate.profile_id.clone())
 }
 
-fn platform_matches_host(platform: LaunchPlatform) -> bool {
+pub(super) fn platform_matches_host(platform: LaunchPlatform) -> bool {
     match platform {
         LaunchPlatform::Any => true,
         LaunchPlatform::Windows => cfg!(target_os = "windows"),
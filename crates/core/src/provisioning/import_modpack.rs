@@ -1,7 +1,9 @@
 //! 整合包导入核心逻辑。
 //!
-//! 支持三种来源：
-//! - zip/tar.gz/tgz 压缩包：解压到 run_path
+//! 支持四种来源：
+//! - zip/tar.gz/tgz 压缩包：CurseForge 客户端整合包（含 `manifest.json`）按清单下载
+//!   文件并应用覆盖目录，其余视为现成的服务端包，解压后经服务端检查识别核心
+//! - Modrinth `.mrpack`：按索引下载文件并应用覆盖目录到 run_path
 //! - jar 单文件：复制到 run_path
 //! - 文件夹：直接引用原路径
 
@@ -10,6 +12,12 @@ use std::time::{SystemTime, UNIX_EPOCH};
 
 use uuid::Uuid;
 
+use super::CoreKind;
+use super::import_metadata::{
+    LaunchProfilePolicy, ServerInspectionProjectionOptions, apply_server_inspection,
+    inspect_and_apply_import_metadata,
+};
+use super::server_inspection::{InspectionDiagnostic, InspectionOptions};
 use crate::instance::{InstanceError, InstanceId, InstanceSpec, LocalLaunch, StartupMode};
 
 /// 来源类型枚举。
@@ -17,6 +25,8 @@ use crate::instance::{InstanceError, InstanceId, InstanceSpec, LocalLaunch, Star
 pub enum SourceType {
     /// zip/tar.gz/tgz 压缩包。
     Archive,
+    /// Modrinth 整合包（`.mrpack`）。
+    Mrpack,
     /// 单个 jar 文件。
     JarFile,
     /// 已存在的文件夹。
@@ -39,6 +49,7 @@ pub fn infer_source_type(path: &Path) -> SourceType {
 
     match ext.as_deref() {
         Some("zip") => SourceType::Archive,
+        Some("mrpack") => SourceType::Mrpack,
         Some("jar") => SourceType::JarFile,
         _ => SourceType::Folder,
     }
@@ -50,7 +61,7 @@ pub fn infer_source_type(path: &Path) -> SourceType {
 pub struct ImportModpackRequest {
     /// 实例名称。
     pub name: String,
-    /// 整合包来源（zip 路径或文件夹路径）。
+    /// 整合包来源（zip、`.mrpack`、jar 或文件夹路径）。
     pub modpack_path: PathBuf,
     /// Java 可执行文件路径。
     pub java_path: PathBuf,
@@ -72,6 +83,16 @@ pub struct ImportModpackRequest {
     pub custom_command: Option<String>,
     /// 目标运行目录。
     pub run_path: PathBuf,
+    /// 解析 CurseForge 整合包清单所用的 API 密钥。
+    #[serde(default)]
+    pub curseforge_api_key: Option<String>,
+}
+
+/// 整合包声明的模组加载器。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModpackLoader {
+    pub kind: CoreKind,
+    pub version: String,
 }
 
 /// 整合包导入错误。
@@ -159,6 +180,63 @@ pub fn build_instance_spec(
     })
 }
 
+/// 用整合包声明的加载器与游戏版本补全实例规格。
+///
+/// 请求显式指定的核心类型与 Minecraft 版本优先；加载器版本总是取自整合包。
+pub fn apply_modpack_versions(
+    spec: &mut InstanceSpec,
+    request: &ImportModpackRequest,
+    loader: Option<ModpackLoader>,
+    game_version: Option<&str>,
+) {
+    if let Some(loader) = loader {
+        if request.core_type.is_none() {
+            spec.core_type = loader.kind.as_str().to_owned();
+        }
+        spec.core_version = loader.version;
+    }
+    if request.mc_version.is_none()
+        && let Some(game_version) = game_version
+    {
+        spec.game_version = game_version.to_owned();
+    }
+}
+
+/// 用服务端检查结果补全现成服务端包的实例规格。
+///
+/// 请求未指定启动文件时采纳最佳启动候选；请求显式指定的核心类型与 Minecraft 版本
+/// 优先于检查结果。检查失败时保留原值，返回的诊断说明原因。
+pub fn apply_server_pack_inspection(
+    spec: &mut InstanceSpec,
+    request: &ImportModpackRequest,
+) -> Vec<InspectionDiagnostic> {
+    let launch_profile_policy = if request.startup_file_path.is_some() {
+        LaunchProfilePolicy::PreserveExisting
+    } else {
+        LaunchProfilePolicy::AdoptBestCompatible
+    };
+    let directory = spec.directory.clone();
+    let diagnostics = match inspect_and_apply_import_metadata(
+        spec,
+        &directory,
+        &InspectionOptions::default(),
+        &ServerInspectionProjectionOptions {
+            launch_profile_policy,
+            inspected_at_unix_secs: None,
+        },
+    ) {
+        Ok(projection) => projection.diagnostics,
+        Err(error) => apply_server_inspection(spec, Err(&error)),
+    };
+    if let Some(core_type) = &request.core_type {
+        spec.core_type = core_type.clone();
+    }
+    if let Some(mc_version) = &request.mc_version {
+        spec.game_version = mc_version.clone();
+    }
+    diagnostics
+}
+
 /// 规划整合包导入。
 ///
 /// 根据来源类型计算目标目录和启动目标，返回构建好的 `InstanceSpec`。
@@ -169,8 +247,8 @@ pub fn plan_import_modpack(
     let source_type = infer_source_type(&request.modpack_path);
 
     let (directory, startup_target) = match source_type {
-        SourceType::Archive => {
-            // 解压到 run_path（调用者负责实际解压）
+        SourceType::Archive | SourceType::Mrpack => {
+            // 解压 / 下载到 run_path（调用者负责实际操作）
             let target = request
                 .startup_file_path
                 .as_ref()
@@ -206,11 +284,74 @@ pub fn plan_import_modpack(
 mod tests {
     use super::*;
 
+    fn request(core_type: Option<&str>, mc_version: Option<&str>) -> ImportModpackRequest {
+        ImportModpackRequest {
+            name: "pack".into(),
+            modpack_path: PathBuf::from("/packs/pack.mrpack"),
+            java_path: PathBuf::from("java"),
+            max_memory: 4096,
+            min_memory: 1024,
+            port: 25565,
+            startup_mode: "jar".into(),
+            startup_file_path: None,
+            core_type: core_type.map(Into::into),
+            mc_version: mc_version.map(Into::into),
+            custom_command: None,
+            run_path: PathBuf::from("/servers/pack"),
+            curseforge_api_key: None,
+        }
+    }
+
+    #[test]
+    fn modpack_versions_fill_unset_fields() {
+        let loader = ModpackLoader {
+            kind: CoreKind::NeoForge,
+            version: "20.4.80".into(),
+        };
+
+        let unset = request(None, None);
+        let mut spec = plan_import_modpack(&unset).unwrap().spec;
+        apply_modpack_versions(&mut spec, &unset, Some(loader.clone()), Some("1.20.4"));
+        assert_eq!(
+            (spec.core_type.as_str(), spec.core_version.as_str(), spec.game_version.as_str()),
+            ("neoforge", "20.4.80", "1.20.4")
+        );
+
+        let explicit = request(Some("arclight-neoforge"), Some("1.20.2"));
+        let mut spec = plan_import_modpack(&explicit).unwrap().spec;
+        apply_modpack_versions(&mut spec, &explicit, Some(loader), Some("1.20.4"));
+        assert_eq!(
+            (spec.core_type.as_str(), spec.core_version.as_str(), spec.game_version.as_str()),
+            ("arclight-neoforge", "20.4.80", "1.20.2")
+        );
+    }
+
+    #[test]
+    fn server_pack_inspection_keeps_explicit_versions() {
+        let directory =
+            std::env::temp_dir().join(format!("sealantern-server-pack-{}", Uuid::new_v4()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let mut explicit = request(Some("paper"), Some("1.20.4"));
+        explicit.run_path = directory.clone();
+        explicit.modpack_path = PathBuf::from("/packs/server-pack.zip");
+        let mut spec = plan_import_modpack(&explicit).unwrap().spec;
+
+        apply_server_pack_inspection(&mut spec, &explicit);
+
+        assert_eq!((spec.core_type.as_str(), spec.game_version.as_str()), ("paper", "1.20.4"));
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
     #[test]
     fn infer_source_type_detects_zip() {
         assert_eq!(infer_source_type(Path::new("/path/to/modpack.zip")), SourceType::Archive);
     }
 
+    #[test]
+    fn infer_source_type_detects_mrpack() {
+        assert_eq!(infer_source_type(Path::new("/path/to/Pack 1.2.MRPACK")), SourceType::Mrpack);
+    }
+
     #[test]
     fn infer_source_type_detects_jar() {
         assert_eq!(infer_source_type(Path::new("/path/to/server.jar")), SourceType::JarFile);
@@ -0,0 +1,383 @@
+//! 加载器安装器的无头执行与安装后的启动配置。
+//!
+//! Forge、NeoForge 与 Fabric 发布的是安装器：需要先在实例目录中执行安装器生成
+//! 服务端，才能得到可启动的文件。本模块只构造安装器命令、投影安装结果，不负责
+//! 下载安装器与运行进程。
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::instance::{InstanceSpec, LocalLaunch, StartupMode};
+use crate::process::{JavaEnvironment, apply_java_environment};
+
+use super::core_parsing::CoreKind;
+use super::import_metadata::{
+    LaunchProfilePolicy, ServerInspectionProjection, ServerInspectionProjectionOptions,
+    apply_server_inspection, apply_server_inspection_with_options, platform_matches_host,
+};
+use super::server_inspection::{
+    DiagnosticSeverity, InspectionDiagnostic, InspectionOptions, LaunchTarget,
+    ServerInspectionReport, inspect_server_artifact,
+};
+
+/// Forge 安装器在实例目录中生成的 JVM 参数文件，由用户维护内存等参数。
+const USER_JVM_ARGS_FILE: &str = "user_jvm_args.txt";
+
+/// 可无头执行的安装器类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallerKind {
+    Forge,
+    NeoForge,
+    Fabric,
+}
+
+impl InstallerKind {
+    /// 由实例核心类型得到安装器类型；不需要安装器的核心返回 `None`。
+    pub fn from_core_type(core_type: &str) -> Option<Self> {
+        match CoreKind::from_filename(core_type.trim()) {
+            CoreKind::Forge => Some(Self::Forge),
+            CoreKind::NeoForge => Some(Self::NeoForge),
+            CoreKind::Fabric => Some(Self::Fabric),
+            _ => None,
+        }
+    }
+
+    /// 对应的核心类型标识（与目录查询使用的服务器类型一致）。
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Forge => CoreKind::Forge.as_str(),
+            Self::NeoForge => CoreKind::NeoForge.as_str(),
+            Self::Fabric => CoreKind::Fabric.as_str(),
+        }
+    }
+}
+
+/// 构造安装器命令的输入。
+#[derive(Debug)]
+pub struct InstallerCommandRequest<'a> {
+    pub kind: InstallerKind,
+    pub java_executable: &'a Path,
+    pub installer_path: &'a Path,
+    /// 安装目标，同时作为安装器进程的工作目录。
+    pub working_directory: &'a Path,
+    pub game_version: &'a str,
+    /// 加载器版本；Fabric 安装器据此选择加载器，Forge 与 NeoForge 的版本由安装器本身决定。
+    pub loader_version: &'a str,
+}
+
+/// 构造无头执行安装器的命令。
+///
+/// - Forge / NeoForge：`java -jar <installer> --installServer`，安装到工作目录；
+/// - Fabric：`java -jar <installer> server -mcversion <game> -loader <loader>
+///   -downloadMinecraft -dir <directory>`。
+///
+/// Java 可执行文件带目录时注入 `JAVA_HOME` 与 `PATH`，与脚本启动保持一致。
+pub fn build_installer_command(request: &InstallerCommandRequest<'_>) -> Command {
+    let mut command = Command::new(request.java_executable);
+    command.arg("-jar");
+    command.arg(request.installer_path);
+    match request.kind {
+        InstallerKind::Forge | InstallerKind::NeoForge => {
+            command.arg("--installServer");
+        }
+        InstallerKind::Fabric => {
+            command.args(["server", "-mcversion", request.game_version]);
+            command.args(["-loader", request.loader_version]);
+            command.arg("-downloadMinecraft");
+            command.arg("-dir");
+            command.arg(request.working_directory);
+        }
+    }
+    if let Ok(java_environment) = JavaEnvironment::from_java_executable(request.java_executable) {
+        apply_java_environment(&mut command, &java_environment);
+    }
+    command.current_dir(request.working_directory);
+    command
+}
+
+/// 重新检查安装完成的实例目录，采纳生成的启动方式并使用安装时的 Java。
+///
+/// 已有的自定义启动配置会被替换：安装器生成了新的服务端，旧配置不再适用。
+/// 优先采纳 `run.sh` / `run.bat` 等可表示的候选；只有参数文件
+/// （`@libraries/.../unix_args.txt`）时，改为以 Java 直接加载参数文件的自定义启动。
+/// 没有任何可用候选时保留原启动配置。
+pub fn apply_installed_server(
+    spec: &mut InstanceSpec,
+    java_executable: &Path,
+    inspected_at_unix_secs: Option<u64>,
+) -> ServerInspectionProjection {
+    let previous_launch = spec.launch.clone();
+    spec.launch.custom_command = None;
+    spec.launch.custom_executable = None;
+    spec.launch.custom_arguments.clear();
+
+    let directory = spec.directory.clone();
+    let options = InspectionOptions {
+        compute_sha256: true,
+        ..InspectionOptions::default()
+    };
+    let report = match inspect_server_artifact(&directory, &options) {
+        Ok(report) => report,
+        Err(error) => {
+            spec.launch = previous_launch;
+            return ServerInspectionProjection {
+                diagnostics: apply_server_inspection(spec, Err(&error)),
+                launch_candidates: Vec::new(),
+                adopted_launch_profile: None,
+            };
+        }
+    };
+    let mut projection = apply_server_inspection_with_options(
+        spec,
+        Ok(&report),
+        &ServerInspectionProjectionOptions {
+            launch_profile_policy: LaunchProfilePolicy::AdoptBestCompatible,
+            inspected_at_unix_secs,
+        },
+    );
+
+    if projection.adopted_launch_profile.is_none() {
+        match argument_files_launch(&report, &directory, java_executable, &previous_launch) {
+            Some((profile_id, launch)) => {
+                projection.diagnostics.push(InspectionDiagnostic {
+                    severity: DiagnosticSeverity::Info,
+                    code: "launch_profile_argument_files_adopted".to_string(),
+                    message: format!(
+                        "launch profile {profile_id}: argument files are launched directly with Java"
+                    ),
+                    evidence: Vec::new(),
+                });
+                spec.launch = launch;
+                projection.adopted_launch_profile = Some(profile_id);
+            }
+            None => spec.launch = previous_launch,
+        }
+    }
+    spec.launch.java_executable = Some(java_executable.to_path_buf());
+    projection
+}
+
+/// 由当前平台的参数文件候选构造自定义启动：`java [JVM 参数] @user_jvm_args.txt @<参数文件> nogui`。
+fn argument_files_launch(
+    report: &ServerInspectionReport,
+    directory: &Path,
+    java_executable: &Path,
+    previous_launch: &LocalLaunch,
+) -> Option<(String, LocalLaunch)> {
+    let (profile, paths) = report
+        .launches
+        .iter()
+        .filter(|launch| platform_matches_host(launch.value.platform))
+        .max_by_key(|launch| launch.confidence)
+        .and_then(|launch| match &launch.value.target {
+            LaunchTarget::ArgumentFiles { paths } if !paths.is_empty() => {
+                Some((&launch.value, paths))
+            }
+            _ => None,
+        })?;
+
+    let mut arguments = previous_launch.jvm_arguments.clone();
+    if directory.join(USER_JVM_ARGS_FILE).is_file() {
+        arguments.push(format!("@{USER_JVM_ARGS_FILE}"));
+    }
+    arguments.extend(
+        paths
+            .iter()
+            .map(|path| format!("@{}", argument_file_path(directory, path))),
+    );
+    arguments.push("nogui".to_string());
+
+    Some((
+        profile.id.clone(),
+        LocalLaunch {
+            startup_mode: StartupMode::Custom,
+            startup_target: None,
+            custom_command: None,
+            custom_executable: Some(java_executable.to_path_buf()),
+            custom_arguments: arguments,
+            java_executable: Some(java_executable.to_path_buf()),
+            jvm_arguments: previous_launch.jvm_arguments.clone(),
+        },
+    ))
+}
+
+/// 参数文件相对实例目录的路径；Java 的 `@` 参数以工作目录为基准，分隔符统一为 `/`。
+fn argument_file_path(directory: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(directory).unwrap_or(path);
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::OsStr;
+    use std::fs;
+    use std::path::PathBuf;
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::instance::InstanceId;
+
+    fn spec(directory: PathBuf) -> InstanceSpec {
+        InstanceSpec {
+            id: InstanceId::new("installed").unwrap(),
+            name: "installed".into(),
+            aliases: Vec::new(),
+            core_type: "forge".into(),
+            core_version: String::new(),
+            game_version: "1.20.1".into(),
+            directory,
+            port: 25565,
+            max_memory_mib: 4096,
+            min_memory_mib: 1024,
+            created_at_unix_secs: 0,
+            last_started_at_unix_secs: None,
+            server_metadata: None,
+            launch: LocalLaunch {
+                startup_mode: StartupMode::Custom,
+                startup_target: None,
+                custom_command: Some("java -jar forge-installer.jar --installServer".into()),
+                custom_executable: None,
+                custom_arguments: Vec::new(),
+                java_executable: None,
+                jvm_arguments: vec!["-XX:+UseG1GC".into()],
+            },
+        }
+    }
+
+    /// 模拟 Forge 1.17+ 安装结果：`libraries/.../unix_args.txt`，可选 `run.sh`。
+    fn forge_installation(with_script: bool) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("sealantern-installer-{}", Uuid::new_v4()));
+        let version_directory = directory.join("libraries/net/minecraftforge/forge/1.20.1-47.2.0");
+        fs::create_dir_all(&version_directory).unwrap();
+        fs::write(
+            version_directory.join("unix_args.txt"),
+            "-p libraries/cpw/mods/bootstraplauncher.jar\n--launchTarget forgeserver\n",
+        )
+        .unwrap();
+        fs::write(directory.join(USER_JVM_ARGS_FILE), "# -Xmx4G\n").unwrap();
+        if with_script {
+            fs::write(
+                directory.join("run.sh"),
+                "#!/usr/bin/env sh\njava @user_jvm_args.txt @libraries/net/minecraftforge/forge/1.20.1-47.2.0/unix_args.txt \"$@\"\n",
+            )
+            .unwrap();
+        }
+        directory
+    }
+
+    #[test]
+    fn maps_loader_core_types() {
+        assert_eq!(InstallerKind::from_core_type("forge"), Some(InstallerKind::Forge));
+        assert_eq!(InstallerKind::from_core_type("NeoForge"), Some(InstallerKind::NeoForge));
+        assert_eq!(InstallerKind::from_core_type("fabric"), Some(InstallerKind::Fabric));
+        assert_eq!(InstallerKind::from_core_type("paper"), None);
+    }
+
+    #[test]
+    fn builds_headless_installer_commands() {
+        let directory = Path::new("/servers/modded");
+        let mut request = InstallerCommandRequest {
+            kind: InstallerKind::Forge,
+            java_executable: Path::new("/opt/jdk-17/bin/java"),
+            installer_path: Path::new("/servers/modded/forge-1.20.1-47.2.0-installer.jar"),
+            working_directory: directory,
+            game_version: "1.20.1",
+            loader_version: "1.20.1-47.2.0",
+        };
+
+        let forge = build_installer_command(&request);
+        assert_eq!(forge.get_program(), "/opt/jdk-17/bin/java");
+        assert_eq!(
+            forge.get_args().collect::<Vec<_>>(),
+            ["-jar", "/servers/modded/forge-1.20.1-47.2.0-installer.jar", "--installServer"]
+        );
+        assert_eq!(forge.get_current_dir(), Some(directory));
+        assert!(
+            forge
+                .get_envs()
+                .any(|(key, value)| key == "JAVA_HOME" && value == Some(OsStr::new("/opt/jdk-17")))
+        );
+
+        request.kind = InstallerKind::Fabric;
+        request.installer_path = Path::new("fabric-installer-1.0.1.jar");
+        request.loader_version = "0.16.9";
+        let fabric = build_installer_command(&request);
+        assert_eq!(
+            fabric.get_args().collect::<Vec<_>>(),
+            [
+                "-jar",
+                "fabric-installer-1.0.1.jar",
+                "server",
+                "-mcversion",
+                "1.20.1",
+                "-loader",
+                "0.16.9",
+                "-downloadMinecraft",
+                "-dir",
+                "/servers/modded",
+            ]
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn adopts_generated_run_script_with_installer_java() {
+        let directory = forge_installation(true);
+        let mut spec = spec(directory.clone());
+        let java = Path::new("/opt/jdk-17/bin/java");
+
+        let projection = apply_installed_server(&mut spec, java, Some(1));
+
+        fs::remove_dir_all(&directory).unwrap();
+        assert!(projection.adopted_launch_profile.is_some());
+        assert_eq!(spec.launch.startup_mode, StartupMode::Shell);
+        assert_eq!(spec.launch.startup_target, Some(directory.join("run.sh")));
+        assert_eq!(spec.launch.custom_command, None);
+        assert_eq!(spec.launch.java_executable.as_deref(), Some(java));
+        assert_eq!(spec.core_type, "forge");
+        assert!(spec.server_metadata.is_some());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn launches_argument_files_directly_without_a_script() {
+        let directory = forge_installation(false);
+        let mut spec = spec(directory.clone());
+        let java = Path::new("/opt/jdk-17/bin/java");
+
+        apply_installed_server(&mut spec, java, Some(1));
+
+        fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(spec.launch.startup_mode, StartupMode::Custom);
+        assert_eq!(spec.launch.custom_command, None);
+        assert_eq!(spec.launch.custom_executable.as_deref(), Some(java));
+        assert_eq!(
+            spec.launch.custom_arguments,
+            [
+                "-XX:+UseG1GC",
+                "@user_jvm_args.txt",
+                "@libraries/net/minecraftforge/forge/1.20.1-47.2.0/unix_args.txt",
+                "nogui",
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_previous_launch_when_nothing_was_installed() {
+        let directory =
+            std::env::temp_dir().join(format!("sealantern-installer-{}", Uuid::new_v4()));
+        fs::create_dir_all(&directory).unwrap();
+        let mut spec = spec(directory.clone());
+        let previous = spec.launch.clone();
+        let java = Path::new("/opt/jdk-17/bin/java");
+
+        let projection = apply_installed_server(&mut spec, java, Some(1));
+
+        fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(projection.adopted_launch_profile, None);
+        assert_eq!(spec.launch.custom_command, previous.custom_command);
+        assert_eq!(spec.launch.java_executable.as_deref(), Some(java));
+    }
+}
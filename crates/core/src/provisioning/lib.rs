@@ -1,22 +1,30 @@
 pub mod copy;
 pub mod core_parsing;
 pub mod create;
+pub mod curseforge_pack;
 pub mod existing;
 pub mod import_metadata;
 pub mod import_modpack;
+pub mod installer;
 mod launch_adapter;
 pub mod modpack;
+pub mod mrpack;
 pub mod run_dir;
 #[path = "server_inspection/lib.rs"]
 pub mod server_inspection;
 pub mod source;
 pub mod startup_parsing;
+pub mod upgrade;
 
 pub use copy::{CopyInstanceError, CopyInstancePlan, CopyInstanceRequest, plan_copy};
 pub use core_parsing::{
     CoreFileInfo, CoreKind, CoreParseError, inspect_core_file, inspect_core_filename,
 };
 pub use create::{CreateInstanceError, CreateInstancePlan, plan_create};
+pub use curseforge_pack::{
+    CURSEFORGE_MANIFEST_FILE, CurseForgeManifest, CurseForgeManifestError, CurseForgeManifestFile,
+    CurseForgeMinecraft, CurseForgeModLoader, is_client_only_mod, read_curseforge_manifest,
+};
 pub use existing::{
     ExistingInstanceError, ImportExistingServerError, ImportExistingServerRequest,
     build_import_spec, plan_existing_instance,
@@ -27,18 +35,31 @@ pub use import_metadata::{
     apply_server_inspection_with_options, inspect_and_apply_import_metadata,
 };
 pub use import_modpack::{
-    ImportModpackError, ImportModpackRequest, ImportModpackResult, SourceType, build_instance_spec,
-    infer_source_type, plan_import_modpack,
+    ImportModpackError, ImportModpackRequest, ImportModpackResult, ModpackLoader, SourceType,
+    apply_modpack_versions, apply_server_pack_inspection, build_instance_spec, infer_source_type,
+    plan_import_modpack,
+};
+pub use installer::{
+    InstallerCommandRequest, InstallerKind, apply_installed_server, build_installer_command,
 };
 pub use modpack::{
     ModpackProvisionError, ModpackProvisionPlan, ModpackProvisionRequest, plan_modpack,
 };
+pub use mrpack::{
+    MRPACK_INDEX_FILE, MRPACK_OVERRIDES_DIR, MRPACK_SERVER_OVERRIDES_DIR, MrpackEnv, MrpackFile,
+    MrpackIndex, MrpackIndexError, MrpackSupport,
+};
 pub use run_dir::{RunDirectoryError, RunDirectoryState, resolve_run_directory};
 pub use server_inspection::{
-    InspectionOptions, ServerInspectionError, ServerInspectionReport, inspect_server_artifact,
+    ArchiveEntries, InspectionOptions, ServerInspectionError, ServerInspectionReport,
+    inspect_server_artifact, read_archive_entries,
 };
 pub use source::{SourceDirectoryError, source_directories_equal, validate_source_directory};
 pub use startup_parsing::{
     JavaLaunch, StartupParseError, StartupScriptInfo, StartupScriptKind,
     parse_startup_script_content, parse_startup_script_file,
 };
+pub use upgrade::{
+    ExtensionVersionMismatch, apply_core_upgrade, extension_version_mismatches,
+    game_version_matches,
+};
@@ -0,0 +1,235 @@
+//! Modrinth 整合包（`.mrpack`）索引。
+//!
+//! `.mrpack` 是只含 `modrinth.index.json` 与覆盖目录的 zip：模组本体不在包内，
+//! 由索引 `files[]` 给出下载地址与摘要。本模块只解析与校验索引，下载、摘要校验
+//! 与覆盖目录的应用由上层完成。
+//!
+//! 覆盖目录按 `overrides/` → `server-overrides/` 的顺序应用，后者覆盖前者。
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use serde::Deserialize;
+
+use super::CoreKind;
+use super::import_modpack::ModpackLoader;
+
+/// 包内索引文件名。
+pub const MRPACK_INDEX_FILE: &str = "modrinth.index.json";
+/// 客户端与服务端共用的覆盖目录。
+pub const MRPACK_OVERRIDES_DIR: &str = "overrides";
+/// 仅服务端的覆盖目录，在 [`MRPACK_OVERRIDES_DIR`] 之后应用。
+pub const MRPACK_SERVER_OVERRIDES_DIR: &str = "server-overrides";
+
+/// 支持的索引格式版本。
+const SUPPORTED_FORMAT_VERSION: u32 = 1;
+/// 服务端下载前必须至少提供其一的摘要算法。
+const REQUIRED_HASHES: [&str; 2] = ["sha512", "sha1"];
+
+/// 索引依赖键 → 加载器核心类型，按优先级排列。
+const LOADER_DEPENDENCIES: [(&str, CoreKind); 4] = [
+    ("neoforge", CoreKind::NeoForge),
+    ("forge", CoreKind::Forge),
+    ("quilt-loader", CoreKind::Quilt),
+    ("fabric-loader", CoreKind::Fabric),
+];
+
+/// `modrinth.index.json` 的内容。
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrpackIndex {
+    pub format_version: u32,
+    pub game: String,
+    pub version_id: String,
+    pub name: String,
+    #[serde(default)]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub files: Vec<MrpackFile>,
+    /// 依赖键（`minecraft`、`fabric-loader` 等）→ 版本。
+    #[serde(default)]
+    pub dependencies: BTreeMap<String, String>,
+}
+
+/// 索引中需要下载的一个文件。
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrpackFile {
+    /// 相对实例目录的目标路径。
+    pub path: String,
+    /// 算法名（`sha1`、`sha512`）→ 十六进制摘要。
+    pub hashes: BTreeMap<String, String>,
+    /// 缺省时客户端与服务端均需要。
+    #[serde(default)]
+    pub env: Option<MrpackEnv>,
+    /// 候选下载地址，按顺序尝试。
+    pub downloads: Vec<String>,
+    #[serde(default)]
+    pub file_size: u64,
+}
+
+/// 文件在客户端与服务端上的需求。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct MrpackEnv {
+    pub client: MrpackSupport,
+    pub server: MrpackSupport,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MrpackSupport {
+    Required,
+    Optional,
+    Unsupported,
+}
+
+impl MrpackFile {
+    /// 服务端是否需要该文件（`env.server` 不为 `unsupported`）。
+    pub fn is_server_side(&self) -> bool {
+        self.env
+            .is_none_or(|env| env.server != MrpackSupport::Unsupported)
+    }
+
+    /// 用于校验的最强摘要：（算法名，十六进制摘要）。
+    pub fn verification_hash(&self) -> Option<(&'static str, &str)> {
+        REQUIRED_HASHES.into_iter().find_map(|algorithm| {
+            let hash = self.hashes.get(algorithm)?;
+            (!hash.trim().is_empty()).then_some((algorithm, hash.as_str()))
+        })
+    }
+}
+
+/// 索引无法使用的原因。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MrpackIndexError {
+    /// JSON 结构不合法。
+    Invalid(String),
+    /// 不支持的 `formatVersion`。
+    UnsupportedFormat(u32),
+    /// 不是 Minecraft 整合包。
+    UnsupportedGame(String),
+    /// 服务端文件缺少下载地址。
+    MissingDownload { path: String },
+    /// 服务端文件没有 sha1 或 sha512 摘要，无法校验。
+    MissingHash { path: String },
+}
+
+impl fmt::Display for MrpackIndexError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(message) => write!(formatter, "invalid {MRPACK_INDEX_FILE}: {message}"),
+            Self::UnsupportedFormat(version) => {
+                write!(formatter, "unsupported mrpack format version {version}")
+            }
+            Self::UnsupportedGame(game) => write!(formatter, "unsupported mrpack game {game}"),
+            Self::MissingDownload { path } => write!(formatter, "no download for {path}"),
+            Self::MissingHash { path } => write!(formatter, "no sha1 or sha512 hash for {path}"),
+        }
+    }
+}
+
+impl std::error::Error for MrpackIndexError {}
+
+impl MrpackIndex {
+    /// 解析并校验索引：格式版本、游戏类型，以及服务端文件的下载地址与摘要。
+    pub fn parse(content: &str) -> Result<Self, MrpackIndexError> {
+        let index: Self = serde_json::from_str(content)
+            .map_err(|error| MrpackIndexError::Invalid(error.to_string()))?;
+        if index.format_version != SUPPORTED_FORMAT_VERSION {
+            return Err(MrpackIndexError::UnsupportedFormat(index.format_version));
+        }
+        if index.game != "minecraft" {
+            return Err(MrpackIndexError::UnsupportedGame(index.game));
+        }
+        for file in index.server_files() {
+            if file.downloads.is_empty() {
+                return Err(MrpackIndexError::MissingDownload { path: file.path.clone() });
+            }
+            if file.verification_hash().is_none() {
+                return Err(MrpackIndexError::MissingHash { path: file.path.clone() });
+            }
+        }
+        Ok(index)
+    }
+
+    /// 服务端需要下载的文件。
+    pub fn server_files(&self) -> impl Iterator<Item = &MrpackFile> {
+        self.files.iter().filter(|file| file.is_server_side())
+    }
+
+    /// 整合包对应的 Minecraft 版本。
+    pub fn game_version(&self) -> Option<&str> {
+        self.dependencies.get("minecraft").map(String::as_str)
+    }
+
+    /// 整合包使用的模组加载器；只依赖 `minecraft` 时为 `None`。
+    pub fn loader(&self) -> Option<ModpackLoader> {
+        LOADER_DEPENDENCIES.iter().find_map(|(key, kind)| {
+            let version = self.dependencies.get(*key)?;
+            Some(ModpackLoader { kind: *kind, version: version.clone() })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INDEX: &str = r#"{
+        "formatVersion": 1,
+        "game": "minecraft",
+        "versionId": "1.2.0",
+        "name": "Example Pack",
+        "files": [
+            {
+                "path": "mods/lithium.jar",
+                "hashes": { "sha1": "aa", "sha512": "bb" },
+                "env": { "client": "required", "server": "required" },
+                "downloads": ["https://cdn.modrinth.com/data/lithium.jar"],
+                "fileSize": 10
+            },
+            {
+                "path": "mods/sodium.jar",
+                "hashes": { "sha1": "cc" },
+                "env": { "client": "required", "server": "unsupported" },
+                "downloads": []
+            },
+            {
+                "path": "config/shared.toml",
+                "hashes": { "sha1": "dd" },
+                "downloads": ["https://cdn.modrinth.com/data/shared.toml"]
+            }
+        ],
+        "dependencies": { "minecraft": "1.20.1", "fabric-loader": "0.15.11" }
+    }"#;
+
+    #[test]
+    fn parses_server_files_and_loader() {
+        let index = MrpackIndex::parse(INDEX).unwrap();
+        let paths: Vec<_> = index
+            .server_files()
+            .map(|file| file.path.as_str())
+            .collect();
+        assert_eq!(paths, ["mods/lithium.jar", "config/shared.toml"]);
+        assert_eq!(index.files[0].verification_hash(), Some(("sha512", "bb")));
+        assert_eq!(index.game_version(), Some("1.20.1"));
+        assert_eq!(
+            index.loader(),
+            Some(ModpackLoader {
+                kind: CoreKind::Fabric,
+                version: "0.15.11".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unverifiable_server_files() {
+        let unhashed = INDEX.replace(r#""hashes": { "sha1": "dd" }"#, r#""hashes": {}"#);
+        assert_eq!(
+            MrpackIndex::parse(&unhashed),
+            Err(MrpackIndexError::MissingHash { path: "config/shared.toml".to_owned() })
+        );
+        let future = INDEX.replace(r#""formatVersion": 1"#, r#""formatVersion": 2"#);
+        assert_eq!(MrpackIndex::parse(&future), Err(MrpackIndexError::UnsupportedFormat(2)));
+    }
+}
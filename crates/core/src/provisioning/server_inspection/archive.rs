@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
@@ -38,6 +39,14 @@ pub(super) struct ArchiveMetadata {
     pub(super) diagnostics: Vec<InspectionDiagnostic>,
 }
 
+/// 按名称读取的归档条目。
+#[derive(Debug, Clone, Default)]
+pub struct ArchiveEntries {
+    /// 请求的条目名 → 条目内容。
+    pub entries: BTreeMap<String, Vec<u8>>,
+    pub diagnostics: Vec<InspectionDiagnostic>,
+}
+
 pub(super) fn read_metadata(
     path: &Path,
     options: &InspectionOptions,
@@ -51,18 +60,7 @@ pub(super) fn read_metadata_with_budget(
     options: &InspectionOptions,
     consumed: &mut u64,
 ) -> Result<ArchiveMetadata, ServerInspectionError> {
-    let file = File::open(path)
-        .map_err(|source| ServerInspectionError::Open { path: path.to_path_buf(), source })?;
-    let mut archive = ZipArchive::new(file)
-        .map_err(|source| ServerInspectionError::Archive { path: path.to_path_buf(), source })?;
-    if archive.len() > options.max_archive_entries {
-        return Err(ServerInspectionError::TooManyArchiveEntries {
-            path: path.to_path_buf(),
-            count: archive.len(),
-            limit: options.max_archive_entries,
-        });
-    }
-
+    let mut archive = open_archive(path, options)?;
     let mut diagnostics = Vec::new();
     let manifest = read_optional_entry(
         &mut archive,
@@ -178,6 +176,50 @@ pub(super) fn read_metadata_with_budget(
     })
 }
 
+/// 按名称读取归档中的若干条目，条目名不区分大小写，缺失的条目不出现在结果中。
+///
+/// 与服务端检查共用条目数量与单条目、总字节预算；超出预算的条目以诊断报告并跳过。
+pub(super) fn read_entries(
+    path: &Path,
+    names: &[&str],
+    options: &InspectionOptions,
+) -> Result<ArchiveEntries, ServerInspectionError> {
+    let mut archive = open_archive(path, options)?;
+    let mut consumed = 0;
+    let mut entries = ArchiveEntries::default();
+    for name in names {
+        if let Some(bytes) = read_optional_entry(
+            &mut archive,
+            path,
+            name,
+            options,
+            &mut consumed,
+            &mut entries.diagnostics,
+        )? {
+            entries.entries.insert((*name).to_string(), bytes);
+        }
+    }
+    Ok(entries)
+}
+
+fn open_archive(
+    path: &Path,
+    options: &InspectionOptions,
+) -> Result<ZipArchive<File>, ServerInspectionError> {
+    let file = File::open(path)
+        .map_err(|source| ServerInspectionError::Open { path: path.to_path_buf(), source })?;
+    let archive = ZipArchive::new(file)
+        .map_err(|source| ServerInspectionError::Archive { path: path.to_path_buf(), source })?;
+    if archive.len() > options.max_archive_entries {
+        return Err(ServerInspectionError::TooManyArchiveEntries {
+            path: path.to_path_buf(),
+            count: archive.len(),
+            limit: options.max_archive_entries,
+        });
+    }
+    Ok(archive)
+}
+
 fn read_optional_entry(
     archive: &mut ZipArchive<File>,
     path: &Path,
@@ -16,6 +16,7 @@ use std::time::UNIX_EPOCH;
 
 use sha2::{Digest, Sha256};
 
+pub use archive::ArchiveEntries;
 pub use error::ServerInspectionError;
 pub use model::*;
 
@@ -59,6 +60,16 @@ impl Default for InspectionOptions {
     }
 }
 
+/// 读取 JAR / ZIP 归档中的指定条目（如插件与模组的描述文件），遵守与服务端检查相同的资源预算。
+pub fn read_archive_entries(
+    path: &Path,
+    names: &[&str],
+    options: &InspectionOptions,
+) -> Result<ArchiveEntries, ServerInspectionError> {
+    validate_options(options)?;
+    archive::read_entries(path, names, options)
+}
+
 /// 检查单个服务端文件或安装目录，不执行其中的任何内容。
 pub fn inspect_server_artifact(
     path: &Path,
@@ -804,7 +815,7 @@ mod tests {
     use super::{
         ArtifactFormat, ArtifactRole, DetectionTarget, InspectionOptions, InspectionSubjectKind,
         LaunchPlatform, LaunchTarget, ReleaseChannel, ServerCategory, ServerComponentKind,
-        ServerEcosystem, ServerInspectionError, inspect_server_artifact,
+        ServerEcosystem, ServerInspectionError, inspect_server_artifact, read_archive_entries,
     };
 
     fn temporary_path(suffix: &str) -> PathBuf {
@@ -2285,4 +2296,35 @@ mod tests {
                 .any(|diagnostic| diagnostic.code == "optional_metadata_unreadable")
         );
     }
+
+    #[test]
+    fn reads_requested_archive_entries_within_budget() {
+        let path = temporary_path("entries.jar");
+        write_test_jar_entries(
+            &path,
+            &[
+                ("plugin.yml", "name: Example\n"),
+                ("META-INF/MODS.TOML", "modLoader = \"javafml\"\n"),
+                ("large.json", "{\"padding\": \"0123456789\"}"),
+            ],
+        );
+        let options = InspectionOptions {
+            max_metadata_entry_bytes: 24,
+            ..InspectionOptions::default()
+        };
+
+        let entries = read_archive_entries(
+            &path,
+            &["plugin.yml", "META-INF/mods.toml", "large.json", "fabric.mod.json"],
+            &options,
+        )
+        .expect("read entries");
+        assert_eq!(entries.entries["plugin.yml"], b"name: Example\n");
+        // 条目名不区分大小写，结果以请求的名称为键
+        assert!(entries.entries.contains_key("META-INF/mods.toml"));
+        assert!(!entries.entries.contains_key("large.json"));
+        assert_eq!(entries.entries.len(), 2);
+        assert_eq!(entries.diagnostics[0].code, "metadata_entry_too_large");
+        fs::remove_file(path).expect("remove test JAR");
+    }
 }
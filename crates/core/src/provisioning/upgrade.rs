@@ -0,0 +1,376 @@
+//! 服务端核心原地升级的纯计算部分。
+//!
+//! 升级前检查已启用扩展声明的游戏版本是否覆盖目标版本；替换服务端文件后重新检查
+//! 新文件，刷新实例的服务端元数据摘要。下载、备份与 Java 检测由上层负责。
+
+use std::cmp::Ordering;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::instance::{
+    ExtensionMetadataFormat, InstanceExtension, InstanceExtensionKind, InstanceSpec, StartupMode,
+};
+
+use super::import_metadata::{
+    ServerInspectionProjection, ServerInspectionProjectionOptions, apply_server_inspection,
+    apply_server_inspection_with_options,
+};
+use super::server_inspection::{InspectionOptions, inspect_server_artifact};
+
+/// 声明的游戏版本不包含升级目标版本的扩展。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtensionVersionMismatch {
+    pub kind: InstanceExtensionKind,
+    pub file_name: String,
+    pub id: String,
+    /// 扩展声明的游戏版本范围（或 Bukkit 系的 `api-version`），原样保留。
+    pub declared_game_versions: String,
+}
+
+/// 找出声明的游戏版本不包含 `game_version` 的已启用扩展。
+///
+/// 未声明游戏版本、声明无法解析或目标为快照版本时不报告，只给出确定不匹配的扩展。
+pub fn extension_version_mismatches(
+    extensions: &[InstanceExtension],
+    game_version: &str,
+) -> Vec<ExtensionVersionMismatch> {
+    extensions
+        .iter()
+        .filter(|extension| extension.enabled)
+        .filter_map(|extension| {
+            let metadata = extension.metadata.as_ref()?;
+            let declared = metadata.game_versions.as_deref()?;
+            match game_version_matches(metadata.format, declared, game_version) {
+                Some(false) => Some(ExtensionVersionMismatch {
+                    kind: extension.kind,
+                    file_name: extension.file_name.clone(),
+                    id: metadata.id.clone(),
+                    declared_game_versions: declared.to_string(),
+                }),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// 判断扩展声明的游戏版本是否包含 `game_version`；无法判断时返回 `None`。
+///
+/// - Bukkit / Paper：`api-version` 为插件要求的最低 API 版本；
+/// - Fabric / Quilt：`>=1.20 <1.21`、`~1.20.1`、`1.20.x`、`1.20.1 || 1.20.2` 等写法；
+/// - Forge / NeoForge：Maven 区间 `[1.20.1,1.21)`，多个区间以逗号并列。
+pub fn game_version_matches(
+    format: ExtensionMetadataFormat,
+    declared: &str,
+    game_version: &str,
+) -> Option<bool> {
+    let version = release_parts(game_version)?;
+    match format {
+        ExtensionMetadataFormat::Bukkit | ExtensionMetadataFormat::Paper => {
+            Some(compare_parts(&release_parts(declared)?, &version) != Ordering::Greater)
+        }
+        ExtensionMetadataFormat::Fabric | ExtensionMetadataFormat::Quilt => {
+            semver_range_matches(declared, &version)
+        }
+        ExtensionMetadataFormat::Forge | ExtensionMetadataFormat::NeoForge => {
+            maven_range_matches(declared, &version)
+        }
+        ExtensionMetadataFormat::Velocity => None,
+    }
+}
+
+/// 以新的服务端文件更新实例：启动目标改为 `server_jar`，写入目录给出的版本，
+/// 并重新检查新文件刷新服务端元数据摘要。
+///
+/// 检查结果只用于元数据：核心类型保持不变，核心与游戏版本以调用方给出的为准，
+/// 已有的启动参数与 Java 配置保留。
+pub fn apply_core_upgrade(
+    spec: &mut InstanceSpec,
+    server_jar: &Path,
+    core_version: &str,
+    game_version: &str,
+    inspected_at_unix_secs: Option<u64>,
+) -> ServerInspectionProjection {
+    let core_type = spec.core_type.clone();
+    spec.launch.startup_mode = StartupMode::Jar;
+    spec.launch.startup_target = Some(server_jar.to_path_buf());
+
+    let options = InspectionOptions {
+        compute_sha256: true,
+        ..InspectionOptions::default()
+    };
+    let projection = match inspect_server_artifact(server_jar, &options) {
+        Ok(report) => apply_server_inspection_with_options(
+            spec,
+            Ok(&report),
+            &ServerInspectionProjectionOptions {
+                inspected_at_unix_secs,
+                ..Default::default()
+            },
+        ),
+        Err(error) => ServerInspectionProjection {
+            diagnostics: apply_server_inspection(spec, Err(&error)),
+            launch_candidates: Vec::new(),
+            adopted_launch_profile: None,
+        },
+    };
+    spec.core_type = core_type;
+    spec.core_version = core_version.to_string();
+    spec.game_version = game_version.to_string();
+    projection
+}
+
+/// 解析正式版本号的数字段（`1.21.4`、`1.21.4-rc1` 取 `[1, 21, 4]`）；快照等非数字版本返回 `None`。
+fn release_parts(version: &str) -> Option<Vec<u64>> {
+    let release = version
+        .trim()
+        .split(['-', '+'])
+        .next()
+        .filter(|release| !release.is_empty())?;
+    release.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// 按数字段比较，缺少的段视为 0（`1.20` 等于 `1.20.0`）。
+fn compare_parts(left: &[u64], right: &[u64]) -> Ordering {
+    let len = left.len().max(right.len());
+    (0..len)
+        .map(|index| {
+            let left = left.get(index).copied().unwrap_or(0);
+            let right = right.get(index).copied().unwrap_or(0);
+            left.cmp(&right)
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Fabric / Quilt 的版本约束：`||` 分隔的备选中任一满足即可，备选内空格分隔的条件须全部满足。
+fn semver_range_matches(declared: &str, version: &[u64]) -> Option<bool> {
+    let mut matched = false;
+    for alternative in declared.split("||") {
+        let mut all = true;
+        for predicate in alternative.split_whitespace() {
+            all &= semver_predicate_matches(predicate, version)?;
+        }
+        matched |= all;
+    }
+    Some(matched)
+}
+
+fn semver_predicate_matches(predicate: &str, version: &[u64]) -> Option<bool> {
+    if predicate == "*" {
+        return Some(true);
+    }
+    let (operator, bound) = [">=", "<=", ">", "<", "=", "~", "^"]
+        .into_iter()
+        .find_map(|operator| Some((operator, predicate.strip_prefix(operator)?)))
+        .unwrap_or(("", predicate));
+
+    // `1.20.x` 只比较通配符之前的段。
+    let bound = bound.split(['-', '+']).next().unwrap_or(bound);
+    let mut parts = Vec::new();
+    let mut wildcard = false;
+    for part in bound.split('.') {
+        if matches!(part, "x" | "X" | "*") {
+            wildcard = true;
+            break;
+        }
+        parts.push(part.parse::<u64>().ok()?);
+    }
+    if parts.is_empty() {
+        return if wildcard { Some(true) } else { None };
+    }
+    let ordering = compare_parts(version, &parts);
+    let prefix_matches = version.len() >= parts.len() && version[..parts.len()] == parts[..];
+    Some(match operator {
+        ">=" => ordering != Ordering::Less,
+        "<=" => ordering != Ordering::Greater || (wildcard && prefix_matches),
+        ">" => ordering == Ordering::Greater && !(wildcard && prefix_matches),
+        "<" => ordering == Ordering::Less && !(wildcard && prefix_matches),
+        "~" => {
+            let fixed = parts.len().clamp(1, 2);
+            ordering != Ordering::Less && version.get(..fixed) == parts.get(..fixed)
+        }
+        "^" => ordering != Ordering::Less && version.first() == parts.first(),
+        _ if wildcard => prefix_matches,
+        _ => ordering == Ordering::Equal,
+    })
+}
+
+/// Maven 版本区间：`[a,b)`、`(,b]`、`[a]`，多个区间以逗号并列；不带括号的版本按精确匹配。
+fn maven_range_matches(declared: &str, version: &[u64]) -> Option<bool> {
+    let declared = declared.trim();
+    if !declared.starts_with(['[', '(']) {
+        return Some(compare_parts(version, &release_parts(declared)?) == Ordering::Equal);
+    }
+    let mut matched = false;
+    let mut rest = declared;
+    while !rest.is_empty() {
+        if !rest.starts_with(['[', '(']) {
+            return None;
+        }
+        let close = rest.find([']', ')'])?;
+        let (open_inclusive, close_inclusive) =
+            (rest.starts_with('['), &rest[close..=close] == "]");
+        let body = &rest[1..close];
+        matched |= match body.split_once(',') {
+            None => compare_parts(version, &release_parts(body)?) == Ordering::Equal,
+            Some((lower, upper)) => {
+                let lower_ok = match lower.trim() {
+                    "" => true,
+                    lower => match compare_parts(version, &release_parts(lower)?) {
+                        Ordering::Greater => true,
+                        Ordering::Equal => open_inclusive,
+                        Ordering::Less => false,
+                    },
+                };
+                let upper_ok = match upper.trim() {
+                    "" => true,
+                    upper => match compare_parts(version, &release_parts(upper)?) {
+                        Ordering::Less => true,
+                        Ordering::Equal => close_inclusive,
+                        Ordering::Greater => false,
+                    },
+                };
+                lower_ok && upper_ok
+            }
+        };
+        rest = rest[close + 1..].trim_start_matches([',', ' ']);
+    }
+    Some(matched)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::io::Write;
+    use std::path::PathBuf;
+
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::instance::{ExtensionMetadata, InstanceId, LocalLaunch};
+
+    fn matches(format: ExtensionMetadataFormat, declared: &str, version: &str) -> Option<bool> {
+        game_version_matches(format, declared, version)
+    }
+
+    #[test]
+    fn matches_declared_game_version_ranges() {
+        use ExtensionMetadataFormat::{Bukkit, Fabric, Forge, Velocity};
+
+        assert_eq!(matches(Bukkit, "1.20", "1.21.4"), Some(true));
+        assert_eq!(matches(Bukkit, "1.21.5", "1.21.4"), Some(false));
+
+        assert_eq!(matches(Fabric, "*", "1.21.4"), Some(true));
+        assert_eq!(matches(Fabric, ">=1.21.3", "1.21.4"), Some(true));
+        assert_eq!(matches(Fabric, ">=1.20 <1.21", "1.21.4"), Some(false));
+        assert_eq!(matches(Fabric, "~1.21.3", "1.21.4"), Some(true));
+        assert_eq!(matches(Fabric, "~1.20.1", "1.21.4"), Some(false));
+        assert_eq!(matches(Fabric, "1.21.x", "1.21.4"), Some(true));
+        assert_eq!(matches(Fabric, "1.21.3 || 1.21.4", "1.21.4"), Some(true));
+        assert_eq!(matches(Fabric, "1.21.3", "1.21.4"), Some(false));
+        assert_eq!(matches(Fabric, "<=1.21.x", "1.21.4"), Some(true));
+
+        assert_eq!(matches(Forge, "[1.20.1,1.21)", "1.20.6"), Some(true));
+        assert_eq!(matches(Forge, "[1.20.1,1.21)", "1.21"), Some(false));
+        assert_eq!(matches(Forge, "[1.21]", "1.21.0"), Some(true));
+        assert_eq!(matches(Forge, "(,1.20.1],[1.21,)", "1.21.4"), Some(true));
+        assert_eq!(matches(Forge, "(,1.20.1],[1.21,)", "1.20.4"), Some(false));
+
+        assert_eq!(matches(Fabric, ">=1.21", "24w14a"), None);
+        assert_eq!(matches(Forge, "[1.20,", "1.21"), None);
+        assert_eq!(matches(Velocity, "3.3.0", "1.21.4"), None);
+    }
+
+    fn extension(file_name: &str, declared: Option<&str>, enabled: bool) -> InstanceExtension {
+        InstanceExtension {
+            kind: InstanceExtensionKind::Mod,
+            file_name: file_name.to_string(),
+            path: PathBuf::from("mods").join(file_name),
+            enabled,
+            metadata: Some(ExtensionMetadata {
+                format: ExtensionMetadataFormat::Fabric,
+                id: file_name.trim_end_matches(".jar").to_string(),
+                name: None,
+                version: None,
+                authors: Vec::new(),
+                dependencies: Vec::new(),
+                provides: Vec::new(),
+                game_versions: declared.map(str::to_string),
+                loader_versions: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn reports_only_enabled_extensions_that_exclude_the_target() {
+        let extensions = vec![
+            extension("sodium.jar", Some("~1.21.3"), true),
+            extension("lithium.jar", Some("1.21.3"), true),
+            extension("old.jar", Some("1.20.1"), false),
+            extension("any.jar", None, true),
+        ];
+
+        let mismatches = extension_version_mismatches(&extensions, "1.21.4");
+
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].file_name, "lithium.jar");
+        assert_eq!(mismatches[0].id, "lithium");
+        assert_eq!(mismatches[0].declared_game_versions, "1.21.3");
+    }
+
+    #[test]
+    fn upgrade_keeps_core_type_and_uses_catalog_versions() {
+        let directory = std::env::temp_dir().join(format!("sealantern-upgrade-{}", Uuid::new_v4()));
+        fs::create_dir_all(&directory).unwrap();
+        let jar = directory.join("paper-1.21.4-15.jar");
+        let mut archive = zip::ZipWriter::new(fs::File::create(&jar).unwrap());
+        for (name, content) in [
+            ("META-INF/MANIFEST.MF", "Main-Class: io.papermc.paperclip.Main\r\n\r\n"),
+            ("META-INF/versions.list", "hash\t1.21.4\t1.21.4/custom-fork-1.21.4.jar\n"),
+        ] {
+            archive
+                .start_file(name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            archive.write_all(content.as_bytes()).unwrap();
+        }
+        archive.finish().unwrap();
+        let mut spec = InstanceSpec {
+            id: InstanceId::new("upgrade").expect("valid id"),
+            name: "升级".to_string(),
+            aliases: Vec::new(),
+            core_type: "paper".to_string(),
+            core_version: "1.21.3-82".to_string(),
+            game_version: "1.21.3".to_string(),
+            directory: directory.clone(),
+            port: 25565,
+            max_memory_mib: 2048,
+            min_memory_mib: 512,
+            created_at_unix_secs: 0,
+            last_started_at_unix_secs: None,
+            server_metadata: None,
+            launch: LocalLaunch {
+                startup_mode: StartupMode::Jar,
+                startup_target: Some(directory.join("paper-1.21.3-82.jar")),
+                custom_command: None,
+                custom_executable: None,
+                custom_arguments: Vec::new(),
+                java_executable: Some(PathBuf::from("/opt/java21/bin/java")),
+                jvm_arguments: vec!["-XX:+UseG1GC".to_string()],
+            },
+        };
+
+        apply_core_upgrade(&mut spec, &jar, "15", "1.21.4", Some(42));
+
+        assert_eq!(spec.core_type, "paper");
+        assert_eq!(spec.core_version, "15");
+        assert_eq!(spec.game_version, "1.21.4");
+        assert_eq!(spec.launch.startup_target.as_deref(), Some(jar.as_path()));
+        assert_eq!(spec.launch.java_executable, Some(PathBuf::from("/opt/java21/bin/java")));
+        assert_eq!(spec.launch.jvm_arguments, ["-XX:+UseG1GC"]);
+        let metadata = spec.server_metadata.expect("应刷新服务端元数据");
+        assert_eq!(metadata.inspected_at_unix_secs, 42);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
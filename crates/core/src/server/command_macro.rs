@@ -0,0 +1,406 @@
+//! 控制台命令别名与宏的展开，以及按根命令的过滤。
+//!
+//! 别名把一个短名映射为一条命令前缀，调用时的参数原样追加在后；宏是带
+//! `{参数}` 占位符与可选延迟的命令序列，调用参数按声明顺序绑定，最后一个参数
+//! 吸收剩余全部文本（便于 `say {message}` 这类带空格的参数）。展开结果不再
+//! 二次展开，避免别名之间互相引用形成循环。
+
+use std::fmt;
+use std::time::Duration;
+
+/// 宏名称最大长度。
+pub const MAX_MACRO_NAME_LENGTH: usize = 32;
+/// 单个宏允许的最大步骤数。
+pub const MAX_MACRO_STEPS: usize = 32;
+/// 单个步骤允许的最大延迟（毫秒）。
+pub const MAX_STEP_DELAY_MS: u64 = 60_000;
+/// 单个宏全部步骤的延迟总和上限（毫秒），避免一次调用长时间占用请求。
+pub const MAX_TOTAL_DELAY_MS: u64 = 300_000;
+
+/// 命令宏的种类。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandMacroKind {
+    /// 单条命令前缀，调用参数追加在后。
+    Alias,
+    /// 带占位符与延迟的命令序列。
+    Macro,
+}
+
+impl CommandMacroKind {
+    /// 持久化使用的种类标识。
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Alias => "alias",
+            Self::Macro => "macro",
+        }
+    }
+
+    /// 从持久化标识解析种类。
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "alias" => Some(Self::Alias),
+            "macro" => Some(Self::Macro),
+            _ => None,
+        }
+    }
+}
+
+/// 宏中的一步。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacroStep {
+    /// 命令模板（可含 `{参数}` 占位符）。
+    pub command: String,
+    /// 发送本步前等待的毫秒数。
+    pub delay_ms: u64,
+}
+
+/// 用户定义的别名或宏。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandMacro {
+    /// 调用名（小写 ASCII 字母、数字、`-`、`_`）。
+    pub name: String,
+    pub kind: CommandMacroKind,
+    /// 按顺序绑定调用参数的参数名；别名恒为空。
+    pub params: Vec<String>,
+    pub steps: Vec<MacroStep>,
+    pub description: String,
+}
+
+/// 展开后待发送的一行命令。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpandedCommand {
+    pub command: String,
+    /// 发送本行前需等待的时长。
+    pub delay: Duration,
+}
+
+/// 宏定义或调用不合法。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MacroError {
+    /// 名称为空、过长或含非法字符。
+    InvalidName,
+    /// 步骤为空或超过上限。
+    InvalidSteps,
+    /// 步骤命令为空或含换行等控制字符。
+    InvalidCommand,
+    /// 单步或总延迟超过上限。
+    DelayTooLong,
+    /// 别名只能有一步且不带参数与延迟。
+    InvalidAlias,
+    /// 参数名不合法或重复。
+    InvalidParameter(String),
+    /// 模板引用了未声明的参数。
+    UnknownPlaceholder(String),
+    /// 调用时缺少参数。
+    MissingArgument(String),
+    /// 宏未声明参数，调用时却提供了参数。
+    UnexpectedArguments,
+}
+
+impl fmt::Display for MacroError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidName => write!(formatter, "invalid macro name"),
+            Self::InvalidSteps => {
+                write!(formatter, "macro must have between 1 and {MAX_MACRO_STEPS} steps")
+            }
+            Self::InvalidCommand => write!(formatter, "macro step must be a single-line command"),
+            Self::DelayTooLong => {
+                write!(
+                    formatter,
+                    "macro delay exceeds {MAX_STEP_DELAY_MS} ms per step or {MAX_TOTAL_DELAY_MS} ms in total"
+                )
+            }
+            Self::InvalidAlias => {
+                write!(formatter, "alias must be a single step without parameters or delay")
+            }
+            Self::InvalidParameter(name) => write!(formatter, "invalid macro parameter `{name}`"),
+            Self::UnknownPlaceholder(name) => write!(formatter, "unknown placeholder `{{{name}}}`"),
+            Self::MissingArgument(name) => write!(formatter, "missing argument `{name}`"),
+            Self::UnexpectedArguments => write!(formatter, "macro takes no arguments"),
+        }
+    }
+}
+
+impl std::error::Error for MacroError {}
+
+impl CommandMacro {
+    /// 校验宏定义（保存前调用）。
+    pub fn validate(&self) -> Result<(), MacroError> {
+        if !is_valid_identifier(&self.name, MAX_MACRO_NAME_LENGTH)
+            || self.name.bytes().any(|byte| byte.is_ascii_uppercase())
+        {
+            return Err(MacroError::InvalidName);
+        }
+        if self.steps.is_empty() || self.steps.len() > MAX_MACRO_STEPS {
+            return Err(MacroError::InvalidSteps);
+        }
+        for (index, param) in self.params.iter().enumerate() {
+            if !is_valid_identifier(param, MAX_MACRO_NAME_LENGTH)
+                || self.params[..index].contains(param)
+            {
+                return Err(MacroError::InvalidParameter(param.clone()));
+            }
+        }
+        for step in &self.steps {
+            if step.command.trim().is_empty() || step.command.chars().any(char::is_control) {
+                return Err(MacroError::InvalidCommand);
+            }
+            if step.delay_ms > MAX_STEP_DELAY_MS {
+                return Err(MacroError::DelayTooLong);
+            }
+            if self.kind == CommandMacroKind::Macro {
+                for placeholder in placeholders(&step.command) {
+                    if !self.params.iter().any(|param| param == placeholder) {
+                        return Err(MacroError::UnknownPlaceholder(placeholder.to_owned()));
+                    }
+                }
+            }
+        }
+        if self.steps.iter().map(|step| step.delay_ms).sum::<u64>() > MAX_TOTAL_DELAY_MS {
+            return Err(MacroError::DelayTooLong);
+        }
+        if self.kind == CommandMacroKind::Alias
+            && (self.steps.len() != 1 || !self.params.is_empty() || self.steps[0].delay_ms != 0)
+        {
+            return Err(MacroError::InvalidAlias);
+        }
+        Ok(())
+    }
+
+    /// 以调用参数（调用名之后的全部文本）展开为待发送的命令行。
+    pub fn expand(&self, arguments: &str) -> Result<Vec<ExpandedCommand>, MacroError> {
+        let arguments = arguments.trim();
+        match self.kind {
+            CommandMacroKind::Alias => {
+                let mut command = self.steps[0].command.trim_end().to_owned();
+                if !arguments.is_empty() {
+                    command.push(' ');
+                    command.push_str(arguments);
+                }
+                Ok(vec![ExpandedCommand { command, delay: Duration::ZERO }])
+            }
+            CommandMacroKind::Macro => {
+                let bindings = self.bind(arguments)?;
+                Ok(self
+                    .steps
+                    .iter()
+                    .map(|step| ExpandedCommand {
+                        command: substitute(&step.command, &bindings),
+                        delay: Duration::from_millis(step.delay_ms),
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    fn bind<'a>(&'a self, arguments: &'a str) -> Result<Vec<(&'a str, &'a str)>, MacroError> {
+        if self.params.is_empty() {
+            return if arguments.is_empty() {
+                Ok(Vec::new())
+            } else {
+                Err(MacroError::UnexpectedArguments)
+            };
+        }
+        let mut bindings = Vec::with_capacity(self.params.len());
+        let mut rest = arguments;
+        for (index, param) in self.params.iter().enumerate() {
+            let value = if index + 1 == self.params.len() {
+                rest
+            } else {
+                let (value, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+                rest = tail.trim_start();
+                value
+            };
+            if value.is_empty() {
+                return Err(MacroError::MissingArgument(param.clone()));
+            }
+            bindings.push((param.as_str(), value));
+        }
+        Ok(bindings)
+    }
+}
+
+/// 拆分控制台输入为调用名与其后的参数文本。
+pub fn split_invocation(input: &str) -> (&str, &str) {
+    let input = input.trim();
+    input
+        .split_once(char::is_whitespace)
+        .map_or((input, ""), |(name, rest)| (name, rest.trim_start()))
+}
+
+/// 按根命令放行或拦截控制台命令。
+///
+/// 根命令取首个词，忽略大小写、前导 `/` 与 `minecraft:` 命名空间。拦截表优先；
+/// 允许表为空表示不限制。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandFilter {
+    allowed: Vec<String>,
+    blocked: Vec<String>,
+}
+
+impl CommandFilter {
+    pub fn new(allowed: &[String], blocked: &[String]) -> Self {
+        let normalize = |commands: &[String]| {
+            commands
+                .iter()
+                .map(|command| root_command(command))
+                .filter(|command| !command.is_empty())
+                .collect()
+        };
+        Self {
+            allowed: normalize(allowed),
+            blocked: normalize(blocked),
+        }
+    }
+
+    /// 命令是否被放行。
+    pub fn permits(&self, command: &str) -> bool {
+        let root = root_command(command);
+        !self.blocked.contains(&root) && (self.allowed.is_empty() || self.allowed.contains(&root))
+    }
+}
+
+/// 提取命令的规范化根命令。
+pub fn root_command(command: &str) -> String {
+    let (root, _) = split_invocation(command);
+    let root = root.trim_start_matches('/').to_ascii_lowercase();
+    match root.strip_prefix("minecraft:") {
+        Some(stripped) => stripped.to_owned(),
+        None => root,
+    }
+}
+
+fn is_valid_identifier(value: &str, max_length: usize) -> bool {
+    !value.is_empty()
+        && value.len() <= max_length
+        && value
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_'))
+}
+
+/// 枚举模板中的 `{参数}` 占位符；不构成标识符的花括号（JSON、NBT）原样保留。
+fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    template.match_indices('{').filter_map(|(start, _)| {
+        let rest = &template[start + 1..];
+        let end = rest.find('}')?;
+        let name = &rest[..end];
+        is_valid_identifier(name, MAX_MACRO_NAME_LENGTH).then_some(name)
+    })
+}
+
+fn substitute(template: &str, bindings: &[(&str, &str)]) -> String {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let bound = after.find('}').and_then(|end| {
+            let name = &after[..end];
+            bindings
+                .iter()
+                .find(|(param, _)| *param == name)
+                .map(|(_, value)| (*value, end))
+        });
+        match bound {
+            Some((value, end)) => {
+                output.push_str(value);
+                rest = &after[end + 1..];
+            }
+            None => {
+                output.push('{');
+                rest = after;
+            }
+        }
+    }
+    output.push_str(rest);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn step(command: &str, delay_ms: u64) -> MacroStep {
+        MacroStep { command: command.to_owned(), delay_ms }
+    }
+
+    fn restart_warning() -> CommandMacro {
+        CommandMacro {
+            name: "warn-restart".to_owned(),
+            kind: CommandMacroKind::Macro,
+            params: vec!["minutes".to_owned(), "reason".to_owned()],
+            steps: vec![
+                step("say Restart in {minutes} minutes: {reason}", 0),
+                step("tellraw @a {\"text\":\"{reason}\"}", 1_000),
+                step("save-all", 5_000),
+            ],
+            description: String::new(),
+        }
+    }
+
+    #[test]
+    fn expands_macro_with_placeholders_and_delays() {
+        let definition = restart_warning();
+        definition.validate().unwrap();
+        let lines = definition.expand("5 server update now").unwrap();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].command, "say Restart in 5 minutes: server update now");
+        assert_eq!(lines[1].command, "tellraw @a {\"text\":\"server update now\"}");
+        assert_eq!(lines[2].delay, Duration::from_millis(5_000));
+        assert_eq!(definition.expand("5"), Err(MacroError::MissingArgument("reason".to_owned())));
+    }
+
+    #[test]
+    fn alias_appends_arguments() {
+        let alias = CommandMacro {
+            name: "gmc".to_owned(),
+            kind: CommandMacroKind::Alias,
+            params: Vec::new(),
+            steps: vec![step("gamemode creative", 0)],
+            description: String::new(),
+        };
+        alias.validate().unwrap();
+        assert_eq!(alias.expand("Steve").unwrap()[0].command, "gamemode creative Steve");
+        assert_eq!(alias.expand("").unwrap()[0].command, "gamemode creative");
+    }
+
+    #[test]
+    fn validation_rejects_bad_definitions() {
+        let mut definition = restart_warning();
+        definition.name = "Bad Name".to_owned();
+        assert_eq!(definition.validate(), Err(MacroError::InvalidName));
+
+        let mut definition = restart_warning();
+        definition.steps.push(step("kick {player}", 0));
+        assert_eq!(definition.validate(), Err(MacroError::UnknownPlaceholder("player".to_owned())));
+
+        let mut definition = restart_warning();
+        definition.steps[0].command = "say a\nstop".to_owned();
+        assert_eq!(definition.validate(), Err(MacroError::InvalidCommand));
+
+        let mut definition = restart_warning();
+        definition.kind = CommandMacroKind::Alias;
+        definition.params.clear();
+        definition.steps.truncate(1);
+        definition.steps[0].command = "say hi".to_owned();
+        definition.steps[0].delay_ms = 10;
+        assert_eq!(definition.validate(), Err(MacroError::InvalidAlias));
+    }
+
+    #[test]
+    fn filter_matches_normalized_root_commands() {
+        let filter = CommandFilter::new(&["say".to_owned(), "/List".to_owned()], &[]);
+        assert!(filter.permits("say hello"));
+        assert!(filter.permits("list"));
+        assert!(filter.permits("minecraft:say hi"));
+        assert!(!filter.permits("stop"));
+
+        let filter = CommandFilter::new(&[], &["op".to_owned()]);
+        assert!(!filter.permits("/op Steve"));
+        assert!(filter.permits("deop Steve"));
+        assert!(CommandFilter::default().permits("anything"));
+    }
+}
@@ -1,3 +1,9 @@
+pub mod command_macro;
+pub mod player_events;
 pub mod status;
 
+pub use command_macro::{
+    CommandFilter, CommandMacro, CommandMacroKind, ExpandedCommand, MacroError, MacroStep,
+};
+pub use player_events::{PlayerConsoleEvent, PlayerEventParser};
 pub use status::{ServerProcessState, ServerStatus};
@@ -0,0 +1,257 @@
+//! 服务器控制台输出中的玩家事件解析。
+//!
+//! 从原版 / Bukkit 系 / Forge 系服务端的标准控制台行中识别玩家加入、离开、
+//! UUID 解析与 `list` 命令输出，无需安装任何服务端插件。解析器是纯同步状态机，
+//! 只依赖逐行输入，执行上下文与事件落库由上层决定。
+
+use crate::instance::PlayerName;
+
+/// 从单行（或 `list` 的两行输出）中识别出的玩家事件。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayerConsoleEvent {
+    /// 玩家加入服务器。
+    Joined { name: PlayerName },
+    /// 玩家离开服务器。
+    Left { name: PlayerName },
+    /// 登录验证阶段解析出的玩家 UUID（早于加入事件输出）。
+    UuidResolved { name: PlayerName, uuid: String },
+    /// `list` 命令输出的在线玩家全集。
+    OnlineList {
+        online: u32,
+        max: u32,
+        players: Vec<PlayerName>,
+    },
+}
+
+/// 控制台玩家事件解析器。
+///
+/// 旧版本（1.12 及以前）的 `list` 输出把人数与玩家名拆成两行，解析器记住
+/// 待补全的人数行，并以紧随其后的一行作为玩家名单；其余事件均为单行。
+#[derive(Debug, Default)]
+pub struct PlayerEventParser {
+    pending_list: Option<(u32, u32)>,
+}
+
+impl PlayerEventParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 输入一行控制台输出，返回识别出的玩家事件。
+    pub fn feed(&mut self, line: &str) -> Option<PlayerConsoleEvent> {
+        let cleaned = strip_ansi(line);
+        let message = strip_log_header(&cleaned).trim();
+
+        if let Some((online, max)) = self.pending_list.take() {
+            if online == 0 || message.is_empty() {
+                return Some(PlayerConsoleEvent::OnlineList { online, max, players: Vec::new() });
+            }
+            return Some(PlayerConsoleEvent::OnlineList {
+                online,
+                max,
+                players: parse_player_names(message),
+            });
+        }
+
+        if let Some(name) = message.strip_suffix(" joined the game") {
+            return parse_player_name(name).map(|name| PlayerConsoleEvent::Joined { name });
+        }
+        if let Some(name) = message.strip_suffix(" left the game") {
+            return parse_player_name(name).map(|name| PlayerConsoleEvent::Left { name });
+        }
+        if let Some(rest) = message.strip_prefix("UUID of player ") {
+            let (name, uuid) = rest.split_once(" is ")?;
+            let name = PlayerName::new(name).ok()?;
+            let uuid = uuid.trim();
+            if !is_uuid(uuid) {
+                return None;
+            }
+            return Some(PlayerConsoleEvent::UuidResolved {
+                name,
+                uuid: uuid.to_ascii_lowercase(),
+            });
+        }
+        if let Some(rest) = message.strip_prefix("There are ") {
+            return self.parse_list_header(rest);
+        }
+        None
+    }
+
+    fn parse_list_header(&mut self, rest: &str) -> Option<PlayerConsoleEvent> {
+        // 1.13+：`There are 2 of a max of 20 players online: Steve, Alex`
+        if let Some((online, tail)) = rest.split_once(" of a max of ") {
+            let online = online.trim().parse().ok()?;
+            let (max, names) = tail.split_once(" players online:")?;
+            let max = max.trim().parse().ok()?;
+            return Some(PlayerConsoleEvent::OnlineList {
+                online,
+                max,
+                players: parse_player_names(names),
+            });
+        }
+        // 1.12 及以前：`There are 2/20 players online:`，玩家名单在下一行。
+        let (counts, names) = rest.split_once(" players online:")?;
+        let (online, max) = counts.split_once('/')?;
+        let online = online.trim().parse().ok()?;
+        let max = max.trim().parse().ok()?;
+        if names.trim().is_empty() {
+            self.pending_list = Some((online, max));
+            return None;
+        }
+        Some(PlayerConsoleEvent::OnlineList {
+            online,
+            max,
+            players: parse_player_names(names),
+        })
+    }
+}
+
+/// 去掉 `[时间] [线程/级别]:`、`[时间 级别]:` 与 Forge 追加的 `[logger]:` 前缀。
+///
+/// 只剥离行首连续的方括号段，聊天内容中出现的 `]: ` 不受影响。
+fn strip_log_header(line: &str) -> &str {
+    let mut rest = line.trim_start();
+    if !rest.starts_with('[') {
+        return rest;
+    }
+    while let Some(stripped) = rest.strip_prefix('[') {
+        let Some(end) = stripped.find(']') else {
+            return rest;
+        };
+        rest = stripped[end + 1..].trim_start();
+        if let Some(message) = rest.strip_prefix(':') {
+            return message;
+        }
+    }
+    rest
+}
+
+/// 移除 ANSI 颜色转义序列（部分服务端在控制台输出彩色日志）。
+fn strip_ansi(line: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut characters = line.chars().peekable();
+    while let Some(character) = characters.next() {
+        if character == '\u{1b}' && characters.peek() == Some(&'[') {
+            characters.next();
+            for code in characters.by_ref() {
+                if code.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+            continue;
+        }
+        output.push(character);
+    }
+    output
+}
+
+/// 解析事件行中的玩家名，兼容改名后的 `Name (formerly known as Old)` 形式。
+fn parse_player_name(raw: &str) -> Option<PlayerName> {
+    let raw = raw
+        .split_once(" (formerly known as ")
+        .map_or(raw, |(name, _)| name);
+    PlayerName::new(raw).ok()
+}
+
+fn parse_player_names(raw: &str) -> Vec<PlayerName> {
+    raw.split(',')
+        .filter_map(|name| PlayerName::new(name).ok())
+        .collect()
+}
+
+fn is_uuid(value: &str) -> bool {
+    let groups: Vec<&str> = value.split('-').collect();
+    groups.len() == 5
+        && groups.iter().zip([8, 4, 4, 4, 12]).all(|(group, length)| {
+            group.len() == length && group.chars().all(|c| c.is_ascii_hexdigit())
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PlayerConsoleEvent, PlayerEventParser};
+    use crate::instance::PlayerName;
+
+    fn name(value: &str) -> PlayerName {
+        PlayerName::new(value).unwrap()
+    }
+
+    #[test]
+    fn parses_join_and_leave_across_log_layouts() {
+        let mut parser = PlayerEventParser::new();
+        assert_eq!(
+            parser.feed("[12:00:01] [Server thread/INFO]: Steve joined the game"),
+            Some(PlayerConsoleEvent::Joined { name: name("Steve") })
+        );
+        assert_eq!(
+            parser.feed("[12:00:02 INFO]: Alex left the game"),
+            Some(PlayerConsoleEvent::Left { name: name("Alex") })
+        );
+        assert_eq!(
+            parser.feed(
+                "[12:00:03] [Server thread/INFO] [minecraft/MinecraftServer]: Dev_1 joined the game"
+            ),
+            Some(PlayerConsoleEvent::Joined { name: name("Dev_1") })
+        );
+        assert_eq!(
+            parser.feed("[12:00:04 INFO]: New (formerly known as Old) joined the game"),
+            Some(PlayerConsoleEvent::Joined { name: name("New") })
+        );
+        assert_eq!(
+            parser.feed("\u{1b}[33m[12:00:05 INFO]: Steve left the game\u{1b}[0m"),
+            Some(PlayerConsoleEvent::Left { name: name("Steve") })
+        );
+    }
+
+    #[test]
+    fn chat_messages_cannot_spoof_join_lines() {
+        let mut parser = PlayerEventParser::new();
+        assert_eq!(parser.feed("[12:00:01 INFO]: <Steve> Alex joined the game"), None);
+        assert_eq!(parser.feed("[12:00:01 INFO]: [Server] Alex left the game"), None);
+    }
+
+    #[test]
+    fn parses_uuid_resolution() {
+        let mut parser = PlayerEventParser::new();
+        assert_eq!(
+            parser.feed(
+                "[12:00:00] [User Authenticator #1/INFO]: UUID of player Steve is 069A79F4-44E9-4726-A5BE-FCA90E38AAF5"
+            ),
+            Some(PlayerConsoleEvent::UuidResolved {
+                name: name("Steve"),
+                uuid: "069a79f4-44e9-4726-a5be-fca90e38aaf5".to_owned(),
+            })
+        );
+        assert_eq!(parser.feed("[12:00:00 INFO]: UUID of player Steve is nope"), None);
+    }
+
+    #[test]
+    fn parses_single_and_two_line_list_output() {
+        let mut parser = PlayerEventParser::new();
+        assert_eq!(
+            parser.feed("[12:00:00 INFO]: There are 2 of a max of 20 players online: Steve, Alex"),
+            Some(PlayerConsoleEvent::OnlineList {
+                online: 2,
+                max: 20,
+                players: vec![name("Steve"), name("Alex")],
+            })
+        );
+        assert_eq!(
+            parser.feed("[12:00:00 INFO]: There are 0 of a max of 20 players online: "),
+            Some(PlayerConsoleEvent::OnlineList { online: 0, max: 20, players: Vec::new() })
+        );
+
+        assert_eq!(
+            parser.feed("[12:00:00] [Server thread/INFO]: There are 1/10 players online:"),
+            None
+        );
+        assert_eq!(
+            parser.feed("[12:00:00] [Server thread/INFO]: Steve"),
+            Some(PlayerConsoleEvent::OnlineList {
+                online: 1,
+                max: 10,
+                players: vec![name("Steve")]
+            })
+        );
+    }
+}
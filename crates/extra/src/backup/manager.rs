@@ -18,8 +18,12 @@ pub struct BackupManager {
 impl BackupManager {
     /// 创建新的备份管理器
     pub fn new() -> BackupResult<Self> {
-        let app_data_dir = get_app_data_dir();
-        let backups_dir = app_data_dir.join("backups");
+        Self::with_dir(get_app_data_dir().join("backups"))
+    }
+
+    /// 使用指定备份目录创建备份管理器
+    pub fn with_dir(backups_dir: impl Into<PathBuf>) -> BackupResult<Self> {
+        let backups_dir = backups_dir.into();
 
         // 确保备份目录存在
         fs::create_dir_all(&backups_dir)
@@ -15,8 +15,12 @@ pub struct BackupSettingsManager {
 impl BackupSettingsManager {
     /// 创建新的备份设置管理器
     pub fn new() -> BackupResult<Self> {
-        let app_data_dir = get_app_data_dir();
-        let settings_dir = app_data_dir.join("backup_settings");
+        Self::with_dir(get_app_data_dir().join("backup_settings"))
+    }
+
+    /// 使用指定设置目录创建管理器（便于测试注入）
+    pub fn with_dir(settings_dir: impl Into<PathBuf>) -> BackupResult<Self> {
+        let settings_dir = settings_dir.into();
 
         // 确保设置目录存在
         fs::create_dir_all(&settings_dir)
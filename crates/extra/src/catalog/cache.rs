@@ -0,0 +1,414 @@
+//! 目录查询的磁盘缓存。
+//!
+//! [`CachedCatalog`] 把每次成功的查询结果写入 [`FileCache`]，在 TTL 内直接复用；
+//! 过期或显式刷新后重新查询上游。上游与镜像都不可用时返回最后一次成功的结果，
+//! 并以 [`CatalogSnapshot::stale`] 标记，使离线时仍能用已知的构建创建服务器。
+
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use sealantern_infra::fs::{DataLimit, FileCache};
+
+use crate::download_link::LinkManager;
+
+use super::models::{CatalogBuild, CatalogError};
+use super::provider::{ServerCatalog, select_build};
+
+/// 缓存条目的默认有效期。
+pub const CATALOG_CACHE_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// 单个缓存条目的读取上限（原版版本清单约数百 KB）。
+const CACHE_ENTRY_LIMIT: DataLimit = DataLimit::new(16 * 1024 * 1024);
+
+/// 目录查询结果及其来源时间。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CatalogSnapshot<T> {
+    pub data: T,
+    /// 为真表示查询失败，`data` 是缓存中最后一次成功的结果。
+    pub stale: bool,
+    /// 数据获取时间（Unix 秒）。
+    pub fetched_at: u64,
+}
+
+impl<T> CatalogSnapshot<T> {
+    /// 转换数据并保留来源信息。
+    pub fn map<U>(self, map: impl FnOnce(T) -> U) -> CatalogSnapshot<U> {
+        CatalogSnapshot {
+            data: map(self.data),
+            stale: self.stale,
+            fetched_at: self.fetched_at,
+        }
+    }
+}
+
+/// 写入磁盘的缓存条目。
+#[derive(Serialize, Deserialize)]
+struct CacheEntry<T> {
+    fetched_at: u64,
+    /// 获取时间（Unix 毫秒），与显式刷新时间比较；旧格式条目缺省为 0。
+    #[serde(default)]
+    fetched_at_ms: u64,
+    data: T,
+}
+
+impl<T> CacheEntry<T> {
+    fn new(data: T) -> Self {
+        let now = unix_now_ms();
+        Self {
+            fetched_at: now / 1000,
+            fetched_at_ms: now,
+            data,
+        }
+    }
+}
+
+/// 带磁盘缓存的服务器核心目录。
+pub struct CachedCatalog {
+    catalog: ServerCatalog,
+    /// 缓存目录不可用时为 `None`，此时每次都直接查询。
+    cache: Option<FileCache>,
+    ttl: Duration,
+    /// 最近一次显式刷新的时间（Unix 毫秒），不晚于它获取的条目视为过期。
+    ///
+    /// 按秒比较时，同一秒内先写入、后刷新的条目会被误判为刷新后获取。
+    refreshed_at_ms: AtomicU64,
+}
+
+impl CachedCatalog {
+    /// 使用指定目录、缓存与有效期构造（便于测试注入）。
+    pub fn new(catalog: ServerCatalog, cache: Option<FileCache>, ttl: Duration) -> Self {
+        Self {
+            catalog,
+            cache,
+            ttl,
+            refreshed_at_ms: AtomicU64::new(0),
+        }
+    }
+
+    /// 使用全部上游提供方与默认缓存目录构造（生产装配推荐）。
+    pub fn global() -> Self {
+        let cache = match FileCache::new(catalog_cache_dir()) {
+            Ok(cache) => Some(cache),
+            Err(error) => {
+                tracing::warn!(
+                    target: "sealantern.extra.catalog",
+                    error = %error,
+                    "catalog cache is unavailable; querying without cache"
+                );
+                None
+            }
+        };
+        Self::new(ServerCatalog::global(), cache, CATALOG_CACHE_TTL)
+    }
+
+    /// 全部可用的核心类型。
+    pub async fn server_types(&self) -> Result<CatalogSnapshot<Vec<String>>, CatalogError> {
+        self.cached(cache_key(&["server_types"]), || self.catalog.server_types())
+            .await
+    }
+
+    /// 指定核心类型的可用版本，从新到旧。
+    pub async fn versions(
+        &self,
+        server_type: &str,
+    ) -> Result<CatalogSnapshot<Vec<String>>, CatalogError> {
+        self.cached(cache_key(&["versions", server_type]), || self.catalog.versions(server_type))
+            .await
+    }
+
+    /// 指定核心类型与版本的构建，从新到旧。
+    pub async fn builds(
+        &self,
+        server_type: &str,
+        game_version: &str,
+    ) -> Result<CatalogSnapshot<Vec<CatalogBuild>>, CatalogError> {
+        self.cached(cache_key(&["builds", server_type, game_version]), || {
+            self.catalog.builds(server_type, game_version)
+        })
+        .await
+    }
+
+    /// 推荐的构建：最新的正式构建，没有正式构建时取最新构建。
+    pub async fn latest_build(
+        &self,
+        server_type: &str,
+        game_version: &str,
+    ) -> Result<CatalogSnapshot<CatalogBuild>, CatalogError> {
+        self.build(server_type, game_version, None).await
+    }
+
+    /// 指定构建号的构建；`build` 为空时为推荐构建。
+    ///
+    /// 构建号也可只写最后一个 `-` 之后的部分（Forge 的 `47.2.0` 匹配 `1.20.1-47.2.0`）。
+    pub async fn build(
+        &self,
+        server_type: &str,
+        game_version: &str,
+        build: Option<&str>,
+    ) -> Result<CatalogSnapshot<CatalogBuild>, CatalogError> {
+        let snapshot = self.builds(server_type, game_version).await?;
+        let (stale, fetched_at) = (snapshot.stale, snapshot.fetched_at);
+        let selected = select_build(snapshot.data, build).ok_or_else(|| {
+            CatalogError::NotFound(match build.map(str::trim).filter(|build| !build.is_empty()) {
+                Some(build) => format!("no {server_type} build {build} for {game_version}"),
+                None => format!("no {server_type} builds for {game_version}"),
+            })
+        })?;
+        Ok(CatalogSnapshot { data: selected, stale, fetched_at })
+    }
+
+    /// 使已缓存的结果全部过期并重新查询核心类型。
+    ///
+    /// 缓存条目保留在磁盘上，之后的查询失败时仍可作为离线副本返回。
+    pub async fn refresh(&self) -> Result<CatalogSnapshot<Vec<String>>, CatalogError> {
+        self.refreshed_at_ms.store(unix_now_ms(), Ordering::SeqCst);
+        LinkManager::invalidate().await;
+        self.server_types().await
+    }
+
+    /// 读取未过期的缓存，否则查询并写回；查询不可用时退回任意时间的缓存。
+    async fn cached<T, F, Fut>(
+        &self,
+        key: String,
+        query: F,
+    ) -> Result<CatalogSnapshot<T>, CatalogError>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, CatalogError>>,
+    {
+        let Some(cache) = &self.cache else {
+            return query().await.map(|data| CatalogSnapshot {
+                data,
+                stale: false,
+                fetched_at: unix_now_ms() / 1000,
+            });
+        };
+
+        let refreshed_at_ms = self.refreshed_at_ms.load(Ordering::SeqCst);
+        if let Some(entry) =
+            read_entry::<T>(cache.get_fresh(&key, CACHE_ENTRY_LIMIT, self.ttl).await)
+            && entry.fetched_at_ms > refreshed_at_ms
+        {
+            return Ok(CatalogSnapshot {
+                data: entry.data,
+                stale: false,
+                fetched_at: entry.fetched_at,
+            });
+        }
+
+        match query().await {
+            Ok(data) => {
+                let entry = CacheEntry::new(data);
+                write_entry(cache, &key, &entry).await;
+                Ok(CatalogSnapshot {
+                    data: entry.data,
+                    stale: false,
+                    fetched_at: entry.fetched_at,
+                })
+            }
+            Err(error @ CatalogError::Unavailable { .. }) => {
+                match read_entry::<T>(cache.get(&key, CACHE_ENTRY_LIMIT).await) {
+                    Some(entry) => {
+                        tracing::info!(
+                            target: "sealantern.extra.catalog",
+                            key = %key,
+                            fetched_at = entry.fetched_at,
+                            error = %error,
+                            "catalog is unavailable; serving stale cache"
+                        );
+                        Ok(CatalogSnapshot {
+                            data: entry.data,
+                            stale: true,
+                            fetched_at: entry.fetched_at,
+                        })
+                    }
+                    None => Err(error),
+                }
+            }
+            Err(error) => Err(error),
+        }
+    }
+}
+
+/// 解析缓存读取结果；读取失败或内容损坏时视为未命中。
+fn read_entry<T: DeserializeOwned>(
+    read: Result<Option<Vec<u8>>, sealantern_infra::fs::FsError>,
+) -> Option<CacheEntry<T>> {
+    let bytes = read.ok().flatten()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+async fn write_entry<T: Serialize>(cache: &FileCache, key: &str, entry: &CacheEntry<T>) {
+    let result = match serde_json::to_vec(entry) {
+        Ok(bytes) => cache
+            .put(key, &bytes)
+            .await
+            .map_err(|error| error.to_string()),
+        Err(error) => Err(error.to_string()),
+    };
+    if let Err(error) = result {
+        tracing::warn!(
+            target: "sealantern.extra.catalog",
+            key,
+            error = %error,
+            "failed to write catalog cache entry"
+        );
+    }
+}
+
+/// 由查询参数生成缓存键；参数中路径不安全的字符替换为 `_`。
+fn cache_key(parts: &[&str]) -> String {
+    let mut key = parts
+        .iter()
+        .map(|part| {
+            part.to_ascii_lowercase()
+                .chars()
+                .map(|c| {
+                    if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                        c
+                    } else {
+                        '_'
+                    }
+                })
+                .collect::<String>()
+                .replace("..", "__")
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    key.push_str(".json");
+    key
+}
+
+fn catalog_cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("com.fpsz.sea-lantern")
+        .join("catalog")
+}
+
+fn unix_now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicBool;
+
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::catalog::provider::CatalogProvider;
+
+    /// 可切换在线状态的提供方，记录查询次数。
+    struct FlakyProvider {
+        online: Arc<AtomicBool>,
+        queries: Arc<AtomicU64>,
+    }
+
+    #[async_trait]
+    impl CatalogProvider for FlakyProvider {
+        fn name(&self) -> &'static str {
+            "flaky"
+        }
+
+        async fn server_types(&self) -> Result<Vec<String>, CatalogError> {
+            Ok(vec!["paper".to_owned(), "folia".to_owned()])
+        }
+
+        async fn versions(&self, _: &str) -> Result<Vec<String>, CatalogError> {
+            self.queries.fetch_add(1, Ordering::SeqCst);
+            if self.online.load(Ordering::SeqCst) {
+                Ok(vec!["1.21.4".to_owned()])
+            } else {
+                Err(CatalogError::unavailable("flaky", "offline"))
+            }
+        }
+
+        async fn builds(&self, _: &str, _: &str) -> Result<Vec<CatalogBuild>, CatalogError> {
+            Err(CatalogError::NotFound("no builds".to_owned()))
+        }
+    }
+
+    fn catalog(
+        root: &std::path::Path,
+        ttl: Duration,
+    ) -> (CachedCatalog, Arc<AtomicBool>, Arc<AtomicU64>) {
+        let online = Arc::new(AtomicBool::new(true));
+        let queries = Arc::new(AtomicU64::new(0));
+        let provider = FlakyProvider {
+            online: online.clone(),
+            queries: queries.clone(),
+        };
+        let cached = CachedCatalog::new(
+            ServerCatalog::new(vec![Arc::new(provider)], None),
+            Some(FileCache::new(root).unwrap()),
+            ttl,
+        );
+        (cached, online, queries)
+    }
+
+    #[tokio::test]
+    async fn reuses_fresh_entries_until_refresh() {
+        let root = tempfile::tempdir().unwrap();
+        let (cached, _, queries) = catalog(root.path(), CATALOG_CACHE_TTL);
+
+        let first = cached.versions("Paper").await.unwrap();
+        let second = cached.versions("paper").await.unwrap();
+        assert_eq!(second.data, ["1.21.4"]);
+        assert!(!first.stale && !second.stale);
+        assert_eq!(queries.load(Ordering::SeqCst), 1);
+
+        // 同一秒内写入后立即刷新，条目也应过期。
+        cached.refresh().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(2)).await;
+        cached.versions("paper").await.unwrap();
+        assert_eq!(queries.load(Ordering::SeqCst), 2);
+
+        cached.versions("paper").await.unwrap();
+        assert_eq!(queries.load(Ordering::SeqCst), 2, "刷新后获取的条目应继续复用");
+    }
+
+    #[tokio::test]
+    async fn serves_stale_copy_when_offline() {
+        let root = tempfile::tempdir().unwrap();
+        let (cached, online, queries) = catalog(root.path(), Duration::ZERO);
+
+        let fetched = cached.versions("paper").await.unwrap();
+        online.store(false, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let offline = cached.versions("paper").await.unwrap();
+        assert!(offline.stale);
+        assert_eq!(offline.data, fetched.data);
+        assert_eq!(offline.fetched_at, fetched.fetched_at);
+        assert_eq!(queries.load(Ordering::SeqCst), 2);
+
+        // 从未成功过的查询没有离线副本。
+        assert!(matches!(
+            cached.versions("folia").await.unwrap_err(),
+            CatalogError::Unavailable { .. }
+        ));
+        // 条目缺失不回退到缓存。
+        assert!(matches!(
+            cached.builds("paper", "1.21.4").await.unwrap_err(),
+            CatalogError::NotFound(_)
+        ));
+    }
+
+    #[test]
+    fn sanitizes_cache_keys() {
+        assert_eq!(cache_key(&["builds", "Paper", "1.21.4"]), "builds/paper/1.21.4.json");
+        assert_eq!(cache_key(&["versions", "../x y"]), "versions/___x_y.json");
+    }
+}
@@ -0,0 +1,214 @@
+//! Fabric 目录（meta.fabricmc.net v2）。
+//!
+//! 每个加载器版本对应一个构建，下载地址为 Fabric 服务端启动器 jar，可直接运行。
+//! 需要完整安装（下载原版服务端并生成启动器）时，[`FabricProvider::installer`]
+//! 返回 Fabric 安装器构建。
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use sealantern_infra::net::{ClientProvider, NetClient};
+
+use super::models::{BuildArtifact, BuildChannel, CatalogBuild, CatalogError, java_requirement};
+use super::provider::{CatalogProvider, get_json, owned_types, unknown_type};
+
+const FABRIC_META_BASE: &str = "https://meta.fabricmc.net/v2";
+/// 安装器 jar 所在的 Maven 制品目录。
+const FABRIC_INSTALLER_BASE: &str = "https://maven.fabricmc.net/net/fabricmc/fabric-installer";
+
+const PROVIDER: &str = "fabric";
+const SERVER_TYPE: &str = "fabric";
+
+/// 游戏版本与安装器版本条目，从新到旧。
+#[derive(Deserialize)]
+struct FabricVersion {
+    version: String,
+    stable: bool,
+}
+
+/// `GET /versions/loader/{game}` 的元素，从新到旧。
+#[derive(Deserialize)]
+struct FabricLoaderEntry {
+    loader: FabricVersion,
+}
+
+/// Fabric 目录。
+pub struct FabricProvider {
+    client_provider: ClientProvider,
+}
+
+impl FabricProvider {
+    /// 使用全局客户端获取器构造。
+    pub fn global() -> Self {
+        Self::with_provider(sealantern_infra::net::global_client_provider())
+    }
+
+    /// 使用客户端获取器构造，每次请求前获取当前客户端。
+    pub fn with_provider(client_provider: ClientProvider) -> Self {
+        Self { client_provider }
+    }
+
+    /// 使用固定客户端构造。
+    pub fn new(client: NetClient) -> Self {
+        Self::with_provider(Box::new(move || Ok(client.clone())))
+    }
+}
+
+#[async_trait]
+impl CatalogProvider for FabricProvider {
+    fn name(&self) -> &'static str {
+        PROVIDER
+    }
+
+    async fn server_types(&self) -> Result<Vec<String>, CatalogError> {
+        Ok(owned_types(&[SERVER_TYPE]))
+    }
+
+    async fn versions(&self, server_type: &str) -> Result<Vec<String>, CatalogError> {
+        if !server_type.eq_ignore_ascii_case(SERVER_TYPE) {
+            return Err(unknown_type(server_type));
+        }
+        let url = format!("{FABRIC_META_BASE}/versions/game");
+        let versions: Vec<FabricVersion> = get_json(&self.client_provider, PROVIDER, &url).await?;
+        Ok(versions
+            .into_iter()
+            .map(|version| version.version)
+            .collect())
+    }
+
+    async fn builds(
+        &self,
+        server_type: &str,
+        game_version: &str,
+    ) -> Result<Vec<CatalogBuild>, CatalogError> {
+        if !server_type.eq_ignore_ascii_case(SERVER_TYPE) {
+            return Err(unknown_type(server_type));
+        }
+        let loaders_url = format!("{FABRIC_META_BASE}/versions/loader/{game_version}");
+        let loaders: Vec<FabricLoaderEntry> =
+            get_json(&self.client_provider, PROVIDER, &loaders_url).await?;
+        let installer = self.latest_installer().await?;
+        Ok(map_loaders(game_version, &installer, loaders))
+    }
+}
+
+impl FabricProvider {
+    /// 返回安装指定加载器版本所用的 Fabric 安装器构建。
+    ///
+    /// 构建的 `build` 为加载器版本，下载地址为最新正式版安装器 jar。
+    pub async fn installer(
+        &self,
+        game_version: &str,
+        loader_version: &str,
+    ) -> Result<CatalogBuild, CatalogError> {
+        let installer = self.latest_installer().await?;
+        Ok(installer_build(game_version, loader_version, &installer))
+    }
+
+    /// 最新的正式版安装器版本，没有正式版时为最新版本。
+    async fn latest_installer(&self) -> Result<String, CatalogError> {
+        let installers_url = format!("{FABRIC_META_BASE}/versions/installer");
+        let installers: Vec<FabricVersion> =
+            get_json(&self.client_provider, PROVIDER, &installers_url).await?;
+        let index = installers
+            .iter()
+            .position(|installer| installer.stable)
+            .unwrap_or(0);
+        installers
+            .into_iter()
+            .nth(index)
+            .map(|installer| installer.version)
+            .ok_or_else(|| CatalogError::unavailable(PROVIDER, "no Fabric installer versions"))
+    }
+}
+
+fn installer_build(game_version: &str, loader_version: &str, installer: &str) -> CatalogBuild {
+    CatalogBuild {
+        server_type: SERVER_TYPE.to_owned(),
+        game_version: game_version.to_owned(),
+        build: loader_version.to_owned(),
+        channel: BuildChannel::Stable,
+        artifact: BuildArtifact::Installer,
+        url: format!("{FABRIC_INSTALLER_BASE}/{installer}/fabric-installer-{installer}.jar"),
+        file_name: format!("fabric-installer-{installer}.jar"),
+        size: None,
+        hashes: BTreeMap::new(),
+        java_major: java_requirement(game_version),
+        provider: PROVIDER.to_owned(),
+    }
+}
+
+fn map_loaders(
+    game_version: &str,
+    installer: &str,
+    loaders: Vec<FabricLoaderEntry>,
+) -> Vec<CatalogBuild> {
+    loaders
+        .into_iter()
+        .map(|entry| {
+            let loader = entry.loader.version;
+            CatalogBuild {
+                server_type: SERVER_TYPE.to_owned(),
+                game_version: game_version.to_owned(),
+                url: format!(
+                    "{FABRIC_META_BASE}/versions/loader/{game_version}/{loader}/{installer}/server/jar"
+                ),
+                file_name: format!(
+                    "fabric-server-mc.{game_version}-loader.{loader}-launcher.{installer}.jar"
+                ),
+                build: loader,
+                channel: if entry.loader.stable {
+                    BuildChannel::Stable
+                } else {
+                    BuildChannel::Beta
+                },
+                artifact: BuildArtifact::ServerJar,
+                size: None,
+                hashes: BTreeMap::new(),
+                java_major: java_requirement(game_version),
+                provider: PROVIDER.to_owned(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_loaders_to_server_launcher_builds() {
+        let loaders: Vec<FabricLoaderEntry> = serde_json::from_str(
+            r#"[
+                { "loader": { "version": "0.16.10", "stable": false }, "intermediary": {} },
+                { "loader": { "version": "0.16.9", "stable": true }, "intermediary": {} }
+            ]"#,
+        )
+        .unwrap();
+
+        let builds = map_loaders("1.21.4", "1.0.1", loaders);
+
+        assert_eq!(builds[0].channel, BuildChannel::Beta);
+        assert_eq!(builds[1].build, "0.16.9");
+        assert_eq!(
+            builds[1].url,
+            "https://meta.fabricmc.net/v2/versions/loader/1.21.4/0.16.9/1.0.1/server/jar"
+        );
+        assert_eq!(builds[1].file_name, "fabric-server-mc.1.21.4-loader.0.16.9-launcher.1.0.1.jar");
+    }
+
+    #[test]
+    fn installer_build_carries_loader_version() {
+        let build = installer_build("1.21.4", "0.16.9", "1.0.1");
+
+        assert_eq!(build.artifact, BuildArtifact::Installer);
+        assert_eq!(build.build, "0.16.9");
+        assert_eq!(
+            build.url,
+            "https://maven.fabricmc.net/net/fabricmc/fabric-installer/1.0.1/fabric-installer-1.0.1.jar"
+        );
+        assert_eq!(build.file_name, "fabric-installer-1.0.1.jar");
+    }
+}
@@ -0,0 +1,47 @@
+//! 加载器安装器的解析：Forge、NeoForge 与 Fabric。
+
+use super::cache::CachedCatalog;
+use super::fabric::FabricProvider;
+use super::models::{CatalogBuild, CatalogError};
+
+/// 为加载器核心解析要下载并执行的安装器构建。
+///
+/// Forge 与 NeoForge 的目录构建本身就是安装器；Fabric 目录提供的是服务端启动器，
+/// 先从目录选出加载器版本，再换成对应的 Fabric 安装器。
+pub struct InstallerCatalog {
+    catalog: CachedCatalog,
+    fabric: FabricProvider,
+}
+
+impl InstallerCatalog {
+    /// 使用指定目录与 Fabric 提供方构造（便于测试注入）。
+    pub fn new(catalog: CachedCatalog, fabric: FabricProvider) -> Self {
+        Self { catalog, fabric }
+    }
+
+    /// 使用全部上游提供方与默认缓存目录构造（生产装配推荐）。
+    pub fn global() -> Self {
+        Self::new(CachedCatalog::global(), FabricProvider::global())
+    }
+
+    /// 解析指定核心类型、游戏版本的安装器构建。
+    ///
+    /// `loader_version` 为空时选推荐构建；指定时按构建号或加载器版本匹配
+    /// （Forge 构建号形如 `1.20.1-47.2.0`，也接受 `47.2.0`）。
+    pub async fn resolve(
+        &self,
+        server_type: &str,
+        game_version: &str,
+        loader_version: Option<&str>,
+    ) -> Result<CatalogBuild, CatalogError> {
+        let build = self
+            .catalog
+            .build(server_type, game_version, loader_version)
+            .await?
+            .data;
+        if server_type.eq_ignore_ascii_case("fabric") {
+            return self.fabric.installer(game_version, &build.build).await;
+        }
+        Ok(build)
+    }
+}
@@ -0,0 +1,230 @@
+//! Forge / NeoForge 目录（Maven 仓库的 `maven-metadata.xml`）。
+//!
+//! 两者发布的都是安装器，构建的 [`BuildArtifact`] 为 `Installer`。
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+
+use sealantern_infra::net::{ClientProvider, NetClient};
+
+use super::models::{BuildArtifact, BuildChannel, CatalogBuild, CatalogError, java_requirement};
+use super::provider::{CatalogProvider, compare_versions, get_text, owned_types, unknown_type};
+
+/// 发布在 Maven 仓库中的加载器。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MavenLoader {
+    /// 版本形如 `1.20.1-47.2.0`，前缀即 Minecraft 版本。
+    Forge,
+    /// 版本形如 `21.1.77`、`20.4.80-beta`，前两段对应 Minecraft `1.21.1`、`1.20.4`。
+    NeoForge,
+}
+
+impl MavenLoader {
+    fn server_type(self) -> &'static str {
+        match self {
+            Self::Forge => "forge",
+            Self::NeoForge => "neoforge",
+        }
+    }
+
+    /// 制品目录地址（以 `/` 结尾）。
+    fn artifact_base(self) -> &'static str {
+        match self {
+            Self::Forge => "https://maven.minecraftforge.net/net/minecraftforge/forge/",
+            Self::NeoForge => "https://maven.neoforged.net/releases/net/neoforged/neoforge/",
+        }
+    }
+
+    /// 加载器版本对应的 Minecraft 版本；无法识别时为 `None`。
+    fn game_version(self, version: &str) -> Option<String> {
+        match self {
+            Self::Forge => version.split_once('-').map(|(game, _)| game.to_owned()),
+            Self::NeoForge => {
+                let mut parts = version.split(['.', '-']);
+                let major: u32 = parts.next()?.parse().ok()?;
+                let minor: u32 = parts.next()?.parse().ok()?;
+                Some(if minor == 0 {
+                    format!("1.{major}")
+                } else {
+                    format!("1.{major}.{minor}")
+                })
+            }
+        }
+    }
+
+    fn channel(self, version: &str) -> BuildChannel {
+        let version = version.to_ascii_lowercase();
+        if version.contains("alpha") {
+            BuildChannel::Alpha
+        } else if version.contains("beta") {
+            BuildChannel::Beta
+        } else {
+            BuildChannel::Stable
+        }
+    }
+}
+
+/// Forge 或 NeoForge 目录。
+pub struct MavenProvider {
+    loader: MavenLoader,
+    client_provider: ClientProvider,
+}
+
+impl MavenProvider {
+    /// 使用全局客户端获取器构造。
+    pub fn global(loader: MavenLoader) -> Self {
+        Self::with_provider(loader, sealantern_infra::net::global_client_provider())
+    }
+
+    /// 使用客户端获取器构造，每次请求前获取当前客户端。
+    pub fn with_provider(loader: MavenLoader, client_provider: ClientProvider) -> Self {
+        Self { loader, client_provider }
+    }
+
+    /// 使用固定客户端构造。
+    pub fn new(loader: MavenLoader, client: NetClient) -> Self {
+        Self::with_provider(loader, Box::new(move || Ok(client.clone())))
+    }
+
+    fn check_type(&self, server_type: &str) -> Result<(), CatalogError> {
+        if server_type.eq_ignore_ascii_case(self.loader.server_type()) {
+            Ok(())
+        } else {
+            Err(unknown_type(server_type))
+        }
+    }
+
+    /// 仓库中的全部加载器版本，按元数据顺序（从旧到新）。
+    async fn loader_versions(&self) -> Result<Vec<String>, CatalogError> {
+        let url = format!("{}maven-metadata.xml", self.loader.artifact_base());
+        let metadata = get_text(&self.client_provider, self.name(), &url).await?;
+        Ok(parse_metadata_versions(&metadata))
+    }
+}
+
+#[async_trait]
+impl CatalogProvider for MavenProvider {
+    fn name(&self) -> &'static str {
+        self.loader.server_type()
+    }
+
+    async fn server_types(&self) -> Result<Vec<String>, CatalogError> {
+        Ok(owned_types(&[self.loader.server_type()]))
+    }
+
+    async fn versions(&self, server_type: &str) -> Result<Vec<String>, CatalogError> {
+        self.check_type(server_type)?;
+        let mut versions: Vec<String> = Vec::new();
+        for version in self.loader_versions().await? {
+            if let Some(game) = self.loader.game_version(&version)
+                && !versions.contains(&game)
+            {
+                versions.push(game);
+            }
+        }
+        versions.sort_by(|left, right| compare_versions(right, left));
+        Ok(versions)
+    }
+
+    async fn builds(
+        &self,
+        server_type: &str,
+        game_version: &str,
+    ) -> Result<Vec<CatalogBuild>, CatalogError> {
+        self.check_type(server_type)?;
+        let versions = self.loader_versions().await?;
+        Ok(map_builds(self.loader, game_version, versions))
+    }
+}
+
+/// 取出 `<versioning><versions>` 中的全部 `<version>` 文本。
+fn parse_metadata_versions(metadata: &str) -> Vec<String> {
+    metadata
+        .split("<version>")
+        .skip(1)
+        .filter_map(|rest| rest.split_once("</version>"))
+        .map(|(version, _)| version.trim().to_owned())
+        .filter(|version| !version.is_empty())
+        .collect()
+}
+
+/// 指定 Minecraft 版本的构建，从新到旧。
+fn map_builds(loader: MavenLoader, game_version: &str, versions: Vec<String>) -> Vec<CatalogBuild> {
+    let server_type = loader.server_type();
+    let mut builds: Vec<String> = versions
+        .into_iter()
+        .filter(|version| loader.game_version(version).as_deref() == Some(game_version))
+        .collect();
+    builds.sort_by(|left, right| compare_versions(right, left));
+    builds
+        .into_iter()
+        .map(|version| CatalogBuild {
+            server_type: server_type.to_owned(),
+            game_version: game_version.to_owned(),
+            url: format!(
+                "{}{version}/{server_type}-{version}-installer.jar",
+                loader.artifact_base()
+            ),
+            file_name: format!("{server_type}-{version}-installer.jar"),
+            channel: loader.channel(&version),
+            build: version,
+            artifact: BuildArtifact::Installer,
+            size: None,
+            hashes: BTreeMap::new(),
+            java_major: java_requirement(game_version),
+            provider: server_type.to_owned(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METADATA: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<metadata>
+  <groupId>net.neoforged</groupId>
+  <artifactId>neoforge</artifactId>
+  <versioning>
+    <latest>21.1.77</latest>
+    <release>21.1.77</release>
+    <versions>
+      <version>20.4.80-beta</version>
+      <version>20.4.237</version>
+      <version>21.0.167</version>
+      <version>21.1.9</version>
+      <version>21.1.77</version>
+    </versions>
+  </versioning>
+</metadata>"#;
+
+    #[test]
+    fn maps_neoforge_versions_to_game_versions() {
+        let versions = parse_metadata_versions(METADATA);
+        assert_eq!(versions.len(), 5);
+
+        let builds = map_builds(MavenLoader::NeoForge, "1.20.4", versions.clone());
+        let ids: Vec<_> = builds.iter().map(|build| build.build.as_str()).collect();
+        assert_eq!(ids, ["20.4.237", "20.4.80-beta"]);
+        assert_eq!(builds[1].channel, BuildChannel::Beta);
+        assert_eq!(builds[0].artifact, BuildArtifact::Installer);
+        assert_eq!(
+            builds[0].url,
+            "https://maven.neoforged.net/releases/net/neoforged/neoforge/20.4.237/neoforge-20.4.237-installer.jar"
+        );
+
+        let builds = map_builds(MavenLoader::NeoForge, "1.21.1", versions.clone());
+        assert_eq!(builds[0].build, "21.1.77");
+        assert_eq!(map_builds(MavenLoader::NeoForge, "1.21", versions).len(), 1);
+    }
+
+    #[test]
+    fn maps_forge_versions_by_prefix() {
+        let versions = vec!["1.20.1-47.1.0".to_owned(), "1.20.1-47.2.0".to_owned()];
+        let builds = map_builds(MavenLoader::Forge, "1.20.1", versions);
+        assert_eq!(builds[0].build, "1.20.1-47.2.0");
+        assert_eq!(builds[0].file_name, "forge-1.20.1-47.2.0-installer.jar");
+        assert_eq!(builds[0].java_major, Some(17));
+    }
+}
@@ -0,0 +1,72 @@
+//! 镜像目录：包装 [`LinkManager`] 的下载链接配置，作为上游不可用时的回退。
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+
+use crate::download_link::{DownloadLink, LinkError, LinkManager};
+
+use super::models::{BuildArtifact, BuildChannel, CatalogBuild, CatalogError, java_requirement};
+use super::provider::CatalogProvider;
+
+const PROVIDER: &str = "mirror";
+
+/// 镜像目录；每个版本只有一个构建，不含摘要。
+#[derive(Debug, Default)]
+pub struct MirrorProvider;
+
+fn map_link_error(error: LinkError) -> CatalogError {
+    match error {
+        LinkError::Config(message) => CatalogError::unavailable(PROVIDER, message),
+        LinkError::NotFound(message) => CatalogError::NotFound(message),
+    }
+}
+
+#[async_trait]
+impl CatalogProvider for MirrorProvider {
+    fn name(&self) -> &'static str {
+        PROVIDER
+    }
+
+    async fn server_types(&self) -> Result<Vec<String>, CatalogError> {
+        LinkManager::get_server_types()
+            .await
+            .map_err(map_link_error)
+    }
+
+    async fn versions(&self, server_type: &str) -> Result<Vec<String>, CatalogError> {
+        // 镜像配置按从旧到新排列，与上游约定相反。
+        let mut versions = LinkManager::get_versions_by_type(server_type)
+            .await
+            .map_err(map_link_error)?;
+        versions.reverse();
+        Ok(versions)
+    }
+
+    async fn builds(
+        &self,
+        server_type: &str,
+        game_version: &str,
+    ) -> Result<Vec<CatalogBuild>, CatalogError> {
+        let link = LinkManager::get_link_by_type_and_version(server_type, game_version)
+            .await
+            .map_err(map_link_error)?;
+        Ok(vec![map_link(server_type, link)])
+    }
+}
+
+fn map_link(server_type: &str, link: DownloadLink) -> CatalogBuild {
+    CatalogBuild {
+        server_type: server_type.to_owned(),
+        java_major: java_requirement(&link.version),
+        game_version: link.version.clone(),
+        build: link.version,
+        channel: BuildChannel::Stable,
+        artifact: BuildArtifact::ServerJar,
+        url: link.url,
+        file_name: link.file_name,
+        size: None,
+        hashes: BTreeMap::new(),
+        provider: PROVIDER.to_owned(),
+    }
+}
@@ -0,0 +1,54 @@
+//! 服务器核心目录
+//!
+//! 直接查询各核心的上游发布渠道，得到带渠道、摘要与 Java 要求的构建列表：
+//!
+//! - 原版：Mojang piston-meta 版本清单；
+//! - Paper、Folia、Velocity：PaperMC Fill API；
+//! - Purpur：api.purpurmc.org；
+//! - Fabric：meta.fabricmc.net；
+//! - Forge、NeoForge：Maven 仓库元数据（安装器）。
+//!
+//! 上游不可用或未覆盖的核心类型回退到 [`LinkManager`](crate::download_link::LinkManager)
+//! 维护的镜像下载链接。查询结果经 [`CachedCatalog`] 缓存到磁盘，离线时返回带过期标记的
+//! 最后一份结果。[`InstallerCatalog`] 为 Forge、NeoForge 与 Fabric 解析需要执行的安装器。
+
+mod cache;
+mod fabric;
+mod installer;
+mod maven;
+mod mirror;
+mod models;
+mod mojang;
+mod papermc;
+mod provider;
+mod purpur;
+
+use std::sync::Arc;
+
+pub use cache::{CATALOG_CACHE_TTL, CachedCatalog, CatalogSnapshot};
+pub use fabric::FabricProvider;
+pub use installer::InstallerCatalog;
+pub use maven::{MavenLoader, MavenProvider};
+pub use mirror::MirrorProvider;
+pub use models::{BuildArtifact, BuildChannel, CatalogBuild, CatalogError, java_requirement};
+pub use mojang::MojangProvider;
+pub use papermc::PaperMcProvider;
+pub use provider::{CatalogProvider, ServerCatalog};
+pub use purpur::PurpurProvider;
+
+impl ServerCatalog {
+    /// 使用全局客户端的全部上游提供方，以镜像为回退（生产装配推荐）。
+    pub fn global() -> Self {
+        Self::new(
+            vec![
+                Arc::new(MojangProvider::global()),
+                Arc::new(PaperMcProvider::global()),
+                Arc::new(PurpurProvider::global()),
+                Arc::new(FabricProvider::global()),
+                Arc::new(MavenProvider::global(MavenLoader::Forge)),
+                Arc::new(MavenProvider::global(MavenLoader::NeoForge)),
+            ],
+            Some(Arc::new(MirrorProvider)),
+        )
+    }
+}
@@ -0,0 +1,140 @@
+//! 服务器核心目录的数据模型。
+//!
+//! 各上游提供方返回的构建统一映射为 [`CatalogBuild`]；字段使用 snake_case，
+//! 与 [`DownloadLink`](crate::models::DownloadLink) 一致。
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use sealantern_infra::download::DownloadIntegrity;
+use serde::{Deserialize, Serialize};
+
+/// 构建的发布渠道。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildChannel {
+    /// 正式版 / 推荐构建。
+    Stable,
+    /// 测试版、实验性构建。
+    Beta,
+    /// 早期构建，可能不可用。
+    Alpha,
+    /// Minecraft 快照版本。
+    Snapshot,
+}
+
+/// 下载得到的文件类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BuildArtifact {
+    /// 可直接启动的服务端 jar。
+    ServerJar,
+    /// 需先执行以生成服务端的安装器（Forge、NeoForge）。
+    Installer,
+}
+
+/// 某服务器核心类型在某游戏版本下的一个构建。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct CatalogBuild {
+    /// 服务器核心类型（小写，如 `paper`、`neoforge`）。
+    pub server_type: String,
+    /// Minecraft 版本。
+    pub game_version: String,
+    /// 构建号或加载器版本；原版与镜像条目为游戏版本本身。
+    pub build: String,
+    pub channel: BuildChannel,
+    pub artifact: BuildArtifact,
+    pub url: String,
+    pub file_name: String,
+    /// 文件大小（字节），上游未提供时为 `None`。
+    pub size: Option<u64>,
+    /// 上游提供的摘要，键为小写算法名（`sha256`、`sha1`、`md5`），值为十六进制摘要。
+    #[serde(default)]
+    pub hashes: BTreeMap<String, String>,
+    /// 运行所需的最低 Java 主版本；上游未声明时按游戏版本推断，无法推断时为 `None`。
+    pub java_major: Option<u16>,
+    /// 提供该构建的目录来源（如 `papermc`、`mirror`）。
+    pub provider: String,
+}
+
+impl CatalogBuild {
+    /// 上游提供的 SHA-256 摘要。
+    pub fn sha256(&self) -> Option<&str> {
+        self.hashes.get("sha256").map(String::as_str)
+    }
+
+    /// 下载该构建时用于校验的大小与摘要（`md5` 等不支持的算法被忽略）。
+    pub fn integrity(&self) -> DownloadIntegrity {
+        DownloadIntegrity {
+            size: self.size,
+            sha1: self.hashes.get("sha1").cloned(),
+            sha256: self.hashes.get("sha256").cloned(),
+            sha512: self.hashes.get("sha512").cloned(),
+        }
+    }
+}
+
+/// 目录查询失败的原因。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CatalogError {
+    /// 上游不可用或响应无法解析。
+    Unavailable {
+        provider: &'static str,
+        message: String,
+    },
+    /// 指定的核心类型、版本或构建不存在。
+    NotFound(String),
+}
+
+impl CatalogError {
+    pub(crate) fn unavailable(provider: &'static str, message: impl fmt::Display) -> Self {
+        Self::Unavailable { provider, message: message.to_string() }
+    }
+}
+
+impl fmt::Display for CatalogError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unavailable { provider, message } => {
+                write!(formatter, "{provider} catalog is unavailable: {message}")
+            }
+            Self::NotFound(message) => formatter.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {}
+
+/// 按 Minecraft 版本推断运行所需的最低 Java 主版本。
+///
+/// 只识别 `1.x[.y]` 形式的正式版本；快照等其他格式返回 `None`。
+pub fn java_requirement(game_version: &str) -> Option<u16> {
+    let mut parts = game_version.strip_prefix("1.")?.split('.');
+    let minor: u32 = parts.next()?.parse().ok()?;
+    let patch: u32 = match parts.next() {
+        Some(patch) => patch.parse().ok()?,
+        None => 0,
+    };
+    Some(match (minor, patch) {
+        (..=16, _) => 8,
+        (17, _) => 16,
+        (18 | 19, _) | (20, ..=4) => 17,
+        _ => 21,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infers_java_requirement_from_game_version() {
+        assert_eq!(java_requirement("1.12.2"), Some(8));
+        assert_eq!(java_requirement("1.17.1"), Some(16));
+        assert_eq!(java_requirement("1.20.4"), Some(17));
+        assert_eq!(java_requirement("1.20.5"), Some(21));
+        assert_eq!(java_requirement("1.21"), Some(21));
+        assert_eq!(java_requirement("24w14a"), None);
+    }
+}
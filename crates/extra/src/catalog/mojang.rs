@@ -0,0 +1,182 @@
+//! Mojang 原版服务端目录（piston-meta 版本清单）。
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use sealantern_infra::net::{ClientProvider, NetClient};
+
+use super::models::{BuildArtifact, BuildChannel, CatalogBuild, CatalogError, java_requirement};
+use super::provider::{CatalogProvider, get_json, owned_types, unknown_type};
+
+/// 版本清单地址。
+const VERSION_MANIFEST_URL: &str =
+    "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
+
+const PROVIDER: &str = "mojang";
+const SERVER_TYPE: &str = "vanilla";
+
+#[derive(Deserialize)]
+struct VersionManifest {
+    versions: Vec<ManifestVersion>,
+}
+
+#[derive(Deserialize)]
+struct ManifestVersion {
+    id: String,
+    #[serde(rename = "type")]
+    kind: String,
+    url: String,
+}
+
+/// 单个版本的元数据，只取服务端下载与 Java 要求。
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VersionMeta {
+    #[serde(default)]
+    downloads: BTreeMap<String, MetaDownload>,
+    #[serde(default)]
+    java_version: Option<MetaJava>,
+}
+
+#[derive(Deserialize)]
+struct MetaDownload {
+    sha1: String,
+    size: u64,
+    url: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MetaJava {
+    major_version: u16,
+}
+
+/// Mojang 原版服务端目录，核心类型为 `vanilla`。
+pub struct MojangProvider {
+    client_provider: ClientProvider,
+}
+
+impl MojangProvider {
+    /// 使用全局客户端获取器构造。
+    pub fn global() -> Self {
+        Self::with_provider(sealantern_infra::net::global_client_provider())
+    }
+
+    /// 使用客户端获取器构造，每次请求前获取当前客户端。
+    pub fn with_provider(client_provider: ClientProvider) -> Self {
+        Self { client_provider }
+    }
+
+    /// 使用固定客户端构造。
+    pub fn new(client: NetClient) -> Self {
+        Self::with_provider(Box::new(move || Ok(client.clone())))
+    }
+
+    async fn manifest(&self) -> Result<VersionManifest, CatalogError> {
+        get_json(&self.client_provider, PROVIDER, VERSION_MANIFEST_URL).await
+    }
+}
+
+#[async_trait]
+impl CatalogProvider for MojangProvider {
+    fn name(&self) -> &'static str {
+        PROVIDER
+    }
+
+    async fn server_types(&self) -> Result<Vec<String>, CatalogError> {
+        Ok(owned_types(&[SERVER_TYPE]))
+    }
+
+    async fn versions(&self, server_type: &str) -> Result<Vec<String>, CatalogError> {
+        if !server_type.eq_ignore_ascii_case(SERVER_TYPE) {
+            return Err(unknown_type(server_type));
+        }
+        let manifest = self.manifest().await?;
+        Ok(manifest
+            .versions
+            .into_iter()
+            .map(|version| version.id)
+            .collect())
+    }
+
+    async fn builds(
+        &self,
+        server_type: &str,
+        game_version: &str,
+    ) -> Result<Vec<CatalogBuild>, CatalogError> {
+        if !server_type.eq_ignore_ascii_case(SERVER_TYPE) {
+            return Err(unknown_type(server_type));
+        }
+        let manifest = self.manifest().await?;
+        let version = manifest
+            .versions
+            .into_iter()
+            .find(|version| version.id == game_version)
+            .ok_or_else(|| {
+                CatalogError::NotFound(format!("Version {game_version} not found for vanilla"))
+            })?;
+        let meta: VersionMeta = get_json(&self.client_provider, PROVIDER, &version.url).await?;
+        Ok(map_version_meta(&version, meta).into_iter().collect())
+    }
+}
+
+/// 版本元数据 → 构建；没有服务端下载（极早期版本）时为 `None`。
+fn map_version_meta(version: &ManifestVersion, mut meta: VersionMeta) -> Option<CatalogBuild> {
+    let server = meta.downloads.remove("server")?;
+    let channel = match version.kind.as_str() {
+        "release" => BuildChannel::Stable,
+        "old_beta" => BuildChannel::Beta,
+        "old_alpha" => BuildChannel::Alpha,
+        _ => BuildChannel::Snapshot,
+    };
+    Some(CatalogBuild {
+        server_type: SERVER_TYPE.to_owned(),
+        game_version: version.id.clone(),
+        build: version.id.clone(),
+        channel,
+        artifact: BuildArtifact::ServerJar,
+        url: server.url,
+        file_name: format!("minecraft_server.{}.jar", version.id),
+        size: Some(server.size),
+        hashes: BTreeMap::from([("sha1".to_owned(), server.sha1)]),
+        java_major: meta
+            .java_version
+            .map(|java| java.major_version)
+            .or_else(|| java_requirement(&version.id)),
+        provider: PROVIDER.to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_server_download_and_java_version() {
+        let version = ManifestVersion {
+            id: "24w14a".to_owned(),
+            kind: "snapshot".to_owned(),
+            url: "https://piston-meta.mojang.com/v1/packages/abc/24w14a.json".to_owned(),
+        };
+        let meta: VersionMeta = serde_json::from_str(
+            r#"{
+                "downloads": {
+                    "client": { "sha1": "c0", "size": 1, "url": "https://example/client.jar" },
+                    "server": { "sha1": "5e", "size": 51627615, "url": "https://example/server.jar" }
+                },
+                "javaVersion": { "component": "java-runtime-delta", "majorVersion": 21 }
+            }"#,
+        )
+        .unwrap();
+
+        let build = map_version_meta(&version, meta).unwrap();
+
+        assert_eq!(build.channel, BuildChannel::Snapshot);
+        assert_eq!(build.java_major, Some(21));
+        assert_eq!(build.hashes["sha1"], "5e");
+        assert_eq!(build.size, Some(51627615));
+        assert_eq!(build.file_name, "minecraft_server.24w14a.jar");
+    }
+}
@@ -0,0 +1,211 @@
+//! PaperMC 目录（Fill v3 API）：Paper、Folia 与 Velocity。
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use sealantern_infra::net::{ClientProvider, NetClient};
+
+use super::models::{BuildArtifact, BuildChannel, CatalogBuild, CatalogError, java_requirement};
+use super::provider::{CatalogProvider, compare_versions, get_json, owned_types, unknown_type};
+
+/// Fill API 基础地址。
+const FILL_BASE: &str = "https://fill.papermc.io/v3";
+/// 服务端 jar 在 `downloads` 中的键。
+const SERVER_DOWNLOAD: &str = "server:default";
+
+const PROVIDER: &str = "papermc";
+const PROJECTS: [&str; 3] = ["paper", "folia", "velocity"];
+
+/// `GET /projects/{project}`：版本按大版本分组。
+#[derive(Deserialize)]
+struct FillProject {
+    versions: BTreeMap<String, Vec<String>>,
+}
+
+/// `GET /projects/{project}/versions/{version}`。
+#[derive(Deserialize)]
+struct FillVersion {
+    version: FillVersionInfo,
+}
+
+#[derive(Deserialize)]
+struct FillVersionInfo {
+    #[serde(default)]
+    java: Option<FillJava>,
+}
+
+#[derive(Deserialize)]
+struct FillJava {
+    version: FillJavaVersion,
+}
+
+#[derive(Deserialize)]
+struct FillJavaVersion {
+    minimum: u16,
+}
+
+/// `GET /projects/{project}/versions/{version}/builds` 的元素，从新到旧。
+#[derive(Deserialize)]
+struct FillBuild {
+    id: u64,
+    channel: String,
+    #[serde(default)]
+    downloads: BTreeMap<String, FillDownload>,
+}
+
+#[derive(Deserialize)]
+struct FillDownload {
+    name: String,
+    #[serde(default)]
+    checksums: BTreeMap<String, String>,
+    #[serde(default)]
+    size: Option<u64>,
+    url: String,
+}
+
+/// PaperMC 项目目录。
+pub struct PaperMcProvider {
+    client_provider: ClientProvider,
+}
+
+impl PaperMcProvider {
+    /// 使用全局客户端获取器构造。
+    pub fn global() -> Self {
+        Self::with_provider(sealantern_infra::net::global_client_provider())
+    }
+
+    /// 使用客户端获取器构造，每次请求前获取当前客户端。
+    pub fn with_provider(client_provider: ClientProvider) -> Self {
+        Self { client_provider }
+    }
+
+    /// 使用固定客户端构造。
+    pub fn new(client: NetClient) -> Self {
+        Self::with_provider(Box::new(move || Ok(client.clone())))
+    }
+}
+
+fn project_of(server_type: &str) -> Result<&'static str, CatalogError> {
+    PROJECTS
+        .into_iter()
+        .find(|project| project.eq_ignore_ascii_case(server_type))
+        .ok_or_else(|| unknown_type(server_type))
+}
+
+#[async_trait]
+impl CatalogProvider for PaperMcProvider {
+    fn name(&self) -> &'static str {
+        PROVIDER
+    }
+
+    async fn server_types(&self) -> Result<Vec<String>, CatalogError> {
+        Ok(owned_types(&PROJECTS))
+    }
+
+    async fn versions(&self, server_type: &str) -> Result<Vec<String>, CatalogError> {
+        let project = project_of(server_type)?;
+        let url = format!("{FILL_BASE}/projects/{project}");
+        let response: FillProject = get_json(&self.client_provider, PROVIDER, &url).await?;
+        Ok(flatten_versions(response))
+    }
+
+    async fn builds(
+        &self,
+        server_type: &str,
+        game_version: &str,
+    ) -> Result<Vec<CatalogBuild>, CatalogError> {
+        let project = project_of(server_type)?;
+        let version_url = format!("{FILL_BASE}/projects/{project}/versions/{game_version}");
+        let version: FillVersion = get_json(&self.client_provider, PROVIDER, &version_url).await?;
+        let builds: Vec<FillBuild> =
+            get_json(&self.client_provider, PROVIDER, &format!("{version_url}/builds")).await?;
+        let java_major = version
+            .version
+            .java
+            .map(|java| java.version.minimum)
+            .or_else(|| java_requirement(game_version));
+        Ok(builds
+            .into_iter()
+            .filter_map(|build| map_build(project, game_version, java_major, build))
+            .collect())
+    }
+}
+
+/// 展平分组并按版本号从新到旧排列（JSON 对象的键序不可依赖）。
+fn flatten_versions(project: FillProject) -> Vec<String> {
+    let mut versions: Vec<String> = project.versions.into_values().flatten().collect();
+    versions.sort_by(|left, right| compare_versions(right, left));
+    versions
+}
+
+fn map_build(
+    project: &str,
+    game_version: &str,
+    java_major: Option<u16>,
+    mut build: FillBuild,
+) -> Option<CatalogBuild> {
+    let download = build.downloads.remove(SERVER_DOWNLOAD)?;
+    let channel = match build.channel.to_ascii_uppercase().as_str() {
+        "STABLE" | "RECOMMENDED" => BuildChannel::Stable,
+        "BETA" => BuildChannel::Beta,
+        _ => BuildChannel::Alpha,
+    };
+    Some(CatalogBuild {
+        server_type: project.to_owned(),
+        game_version: game_version.to_owned(),
+        build: build.id.to_string(),
+        channel,
+        artifact: BuildArtifact::ServerJar,
+        url: download.url,
+        file_name: download.name,
+        size: download.size,
+        hashes: download.checksums,
+        java_major,
+        provider: PROVIDER.to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sorts_versions_across_families() {
+        let project: FillProject = serde_json::from_str(
+            r#"{ "project": { "id": "paper" }, "versions": {
+                "1.20": ["1.20.6", "1.20"], "1.21": ["1.21.4", "1.21.10", "1.21"]
+            } }"#,
+        )
+        .unwrap();
+        assert_eq!(flatten_versions(project), ["1.21.10", "1.21.4", "1.21", "1.20.6", "1.20"]);
+    }
+
+    #[test]
+    fn maps_builds_with_checksums_and_channel() {
+        let builds: Vec<FillBuild> = serde_json::from_str(
+            r#"[
+                { "id": 232, "channel": "BETA", "downloads": { "server:default": {
+                    "name": "paper-1.21.4-232.jar",
+                    "checksums": { "sha256": "ab12" },
+                    "size": 51200000,
+                    "url": "https://fill-data.papermc.io/v1/objects/ab12/paper-1.21.4-232.jar"
+                } } },
+                { "id": 231, "channel": "STABLE", "downloads": {} }
+            ]"#,
+        )
+        .unwrap();
+
+        let mapped: Vec<_> = builds
+            .into_iter()
+            .filter_map(|build| map_build("paper", "1.21.4", Some(21), build))
+            .collect();
+
+        assert_eq!(mapped.len(), 1);
+        assert_eq!(mapped[0].build, "232");
+        assert_eq!(mapped[0].channel, BuildChannel::Beta);
+        assert_eq!(mapped[0].sha256(), Some("ab12"));
+        assert_eq!(mapped[0].java_major, Some(21));
+    }
+}
@@ -0,0 +1,390 @@
+//! 目录提供方 trait 与带镜像回退的聚合目录。
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+
+use sealantern_infra::download::{DownloadError, fetch_to_string};
+use sealantern_infra::net::ClientProvider;
+
+use super::models::{BuildChannel, CatalogBuild, CatalogError};
+
+/// 一个服务器核心目录来源（上游 API 或镜像）。
+///
+/// 版本与构建均按从新到旧排列；核心类型名使用小写。
+#[async_trait]
+pub trait CatalogProvider: Send + Sync {
+    /// 提供方名称，写入 [`CatalogBuild::provider`] 与日志。
+    fn name(&self) -> &'static str;
+
+    /// 提供的服务器核心类型。
+    async fn server_types(&self) -> Result<Vec<String>, CatalogError>;
+
+    /// 指定核心类型的可用 Minecraft 版本。
+    async fn versions(&self, server_type: &str) -> Result<Vec<String>, CatalogError>;
+
+    /// 指定核心类型与 Minecraft 版本的构建。
+    async fn builds(
+        &self,
+        server_type: &str,
+        game_version: &str,
+    ) -> Result<Vec<CatalogBuild>, CatalogError>;
+}
+
+/// 由多个上游提供方组成的目录，上游失败时回退到镜像。
+///
+/// 核心类型由声明它的第一个上游提供方负责；上游请求失败（网络、解析或条目缺失）
+/// 时改查回退提供方，没有上游声明的类型直接查回退提供方。
+pub struct ServerCatalog {
+    providers: Vec<Arc<dyn CatalogProvider>>,
+    fallback: Option<Arc<dyn CatalogProvider>>,
+}
+
+impl ServerCatalog {
+    /// 使用指定的上游提供方与回退提供方构造目录（便于测试注入）。
+    pub fn new(
+        providers: Vec<Arc<dyn CatalogProvider>>,
+        fallback: Option<Arc<dyn CatalogProvider>>,
+    ) -> Self {
+        Self { providers, fallback }
+    }
+
+    /// 全部可用的核心类型：上游类型在前，镜像独有的类型在后。
+    pub async fn server_types(&self) -> Result<Vec<String>, CatalogError> {
+        let mut types: Vec<String> = Vec::new();
+        let mut last_error = None;
+        for provider in self.providers.iter().chain(&self.fallback) {
+            match provider.server_types().await {
+                Ok(provided) => {
+                    for server_type in provided {
+                        if !types
+                            .iter()
+                            .any(|known| known.eq_ignore_ascii_case(&server_type))
+                        {
+                            types.push(server_type);
+                        }
+                    }
+                }
+                Err(error) => {
+                    warn_provider_failed(provider.name(), &error);
+                    last_error = Some(error);
+                }
+            }
+        }
+        match last_error {
+            Some(error) if types.is_empty() => Err(error),
+            _ => Ok(types),
+        }
+    }
+
+    /// 指定核心类型的可用版本。
+    pub async fn versions(&self, server_type: &str) -> Result<Vec<String>, CatalogError> {
+        let upstream = match self.provider_for(server_type).await {
+            Some(provider) => match provider.versions(server_type).await {
+                Ok(versions) => return Ok(versions),
+                Err(error) => {
+                    warn_provider_failed(provider.name(), &error);
+                    Some(error)
+                }
+            },
+            None => None,
+        };
+        self.query_fallback(upstream, server_type, |fallback| async move {
+            fallback.versions(server_type).await
+        })
+        .await
+    }
+
+    /// 指定核心类型与版本的构建。
+    pub async fn builds(
+        &self,
+        server_type: &str,
+        game_version: &str,
+    ) -> Result<Vec<CatalogBuild>, CatalogError> {
+        let upstream = match self.provider_for(server_type).await {
+            Some(provider) => match provider.builds(server_type, game_version).await {
+                Ok(builds) if !builds.is_empty() => return Ok(builds),
+                Ok(_) => Some(CatalogError::NotFound(format!(
+                    "no {server_type} builds for {game_version}"
+                ))),
+                Err(error) => {
+                    warn_provider_failed(provider.name(), &error);
+                    Some(error)
+                }
+            },
+            None => None,
+        };
+        self.query_fallback(upstream, server_type, |fallback| async move {
+            fallback.builds(server_type, game_version).await
+        })
+        .await
+    }
+
+    /// 推荐的构建：最新的正式构建，没有正式构建时取最新构建。
+    pub async fn latest_build(
+        &self,
+        server_type: &str,
+        game_version: &str,
+    ) -> Result<CatalogBuild, CatalogError> {
+        let builds = self.builds(server_type, game_version).await?;
+        recommended_build(builds).ok_or_else(|| {
+            CatalogError::NotFound(format!("no {server_type} builds for {game_version}"))
+        })
+    }
+
+    /// 声明了该核心类型的第一个上游提供方。
+    async fn provider_for(&self, server_type: &str) -> Option<&Arc<dyn CatalogProvider>> {
+        for provider in &self.providers {
+            let Ok(types) = provider.server_types().await else {
+                continue;
+            };
+            if types
+                .iter()
+                .any(|known| known.eq_ignore_ascii_case(server_type))
+            {
+                return Some(provider);
+            }
+        }
+        None
+    }
+
+    async fn query_fallback<'a, T, F, Fut>(
+        &'a self,
+        upstream: Option<CatalogError>,
+        server_type: &str,
+        query: F,
+    ) -> Result<T, CatalogError>
+    where
+        F: FnOnce(&'a Arc<dyn CatalogProvider>) -> Fut,
+        Fut: Future<Output = Result<T, CatalogError>>,
+    {
+        match (&self.fallback, upstream) {
+            (Some(fallback), upstream) => query(fallback).await.map_err(|error| {
+                // 镜像也没有该条目时，优先报告上游的失败原因。
+                match (error, upstream) {
+                    (CatalogError::NotFound(_), Some(upstream)) => upstream,
+                    (error, _) => error,
+                }
+            }),
+            (None, Some(upstream)) => Err(upstream),
+            (None, None) => Err(CatalogError::NotFound(format!("Type {server_type} not found"))),
+        }
+    }
+}
+
+/// 从新到旧排列的构建中选出推荐构建：最新的正式构建，否则为最新构建。
+pub(crate) fn recommended_build(builds: Vec<CatalogBuild>) -> Option<CatalogBuild> {
+    let latest = builds
+        .iter()
+        .position(|build| build.channel == BuildChannel::Stable)
+        .unwrap_or(0);
+    builds.into_iter().nth(latest)
+}
+
+/// 从新到旧排列的构建中选出指定构建或推荐构建。
+///
+/// 按完整构建号匹配，也接受构建号最后一个 `-` 之后的部分
+/// （Forge 构建号形如 `1.20.1-47.2.0`，也可写作 `47.2.0`）。
+pub(crate) fn select_build(builds: Vec<CatalogBuild>, build: Option<&str>) -> Option<CatalogBuild> {
+    let Some(requested) = build.map(str::trim).filter(|build| !build.is_empty()) else {
+        return recommended_build(builds);
+    };
+    builds.into_iter().find(|build| {
+        build.build == requested
+            || build
+                .build
+                .rsplit_once('-')
+                .is_some_and(|(_, suffix)| suffix == requested)
+    })
+}
+
+fn warn_provider_failed(provider: &'static str, error: &CatalogError) {
+    tracing::warn!(
+        target: "sealantern.extra.catalog",
+        provider,
+        error = %error,
+        "catalog provider request failed"
+    );
+}
+
+/// 请求文本响应；404 映射为 [`CatalogError::NotFound`]。
+pub(crate) async fn get_text(
+    client_provider: &ClientProvider,
+    provider: &'static str,
+    url: &str,
+) -> Result<String, CatalogError> {
+    let client = client_provider().map_err(|error| CatalogError::unavailable(provider, error))?;
+    fetch_to_string(&client, url)
+        .await
+        .map_err(|error| match error {
+            DownloadError::Response(404, _) => CatalogError::NotFound(format!("{url} not found")),
+            error => CatalogError::unavailable(provider, error),
+        })
+}
+
+/// 请求并反序列化 JSON 响应。
+pub(crate) async fn get_json<T: DeserializeOwned>(
+    client_provider: &ClientProvider,
+    provider: &'static str,
+    url: &str,
+) -> Result<T, CatalogError> {
+    let body = get_text(client_provider, provider, url).await?;
+    serde_json::from_str(&body).map_err(|error| CatalogError::unavailable(provider, error))
+}
+
+/// 生成 `server_types` 的固定类型列表。
+pub(crate) fn owned_types(types: &[&str]) -> Vec<String> {
+    types.iter().map(|value| (*value).to_owned()).collect()
+}
+
+/// 按数字段比较版本号（`1.21.4` > `1.21` > `1.20.6`），非数字部分不参与比较。
+pub(crate) fn compare_versions(left: &str, right: &str) -> Ordering {
+    let parts = |version: &str| -> Vec<u64> {
+        version
+            .split(|c: char| !c.is_ascii_digit())
+            .filter_map(|part| part.parse().ok())
+            .collect()
+    };
+    parts(left).cmp(&parts(right))
+}
+
+/// 核心类型不属于该提供方时的错误。
+pub(crate) fn unknown_type(server_type: &str) -> CatalogError {
+    CatalogError::NotFound(format!("Type {server_type} not found"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::atomic::{self, AtomicUsize};
+
+    use super::*;
+    use crate::catalog::models::BuildArtifact;
+
+    /// 内存中的提供方；`failing` 为真时所有查询返回不可用。
+    struct FakeProvider {
+        name: &'static str,
+        types: Vec<String>,
+        failing: bool,
+        build_queries: AtomicUsize,
+    }
+
+    impl FakeProvider {
+        fn new(name: &'static str, types: &[&str], failing: bool) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                types: owned_types(types),
+                failing,
+                build_queries: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl CatalogProvider for FakeProvider {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        async fn server_types(&self) -> Result<Vec<String>, CatalogError> {
+            Ok(self.types.clone())
+        }
+
+        async fn versions(&self, _: &str) -> Result<Vec<String>, CatalogError> {
+            if self.failing {
+                return Err(CatalogError::unavailable(self.name, "offline"));
+            }
+            Ok(vec!["1.21.4".to_owned()])
+        }
+
+        async fn builds(
+            &self,
+            server_type: &str,
+            game_version: &str,
+        ) -> Result<Vec<CatalogBuild>, CatalogError> {
+            self.build_queries.fetch_add(1, atomic::Ordering::SeqCst);
+            if self.failing {
+                return Err(CatalogError::unavailable(self.name, "offline"));
+            }
+            let build = |build: &str, channel| CatalogBuild {
+                server_type: server_type.to_owned(),
+                game_version: game_version.to_owned(),
+                build: build.to_owned(),
+                channel,
+                artifact: BuildArtifact::ServerJar,
+                url: format!("https://{}/{build}.jar", self.name),
+                file_name: format!("{build}.jar"),
+                size: None,
+                hashes: BTreeMap::new(),
+                java_major: Some(21),
+                provider: self.name.to_owned(),
+            };
+            Ok(vec![build("2", BuildChannel::Beta), build("1", BuildChannel::Stable)])
+        }
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_mirror_when_upstream_fails() {
+        let paper = FakeProvider::new("papermc", &["paper", "folia"], true);
+        let mirror = FakeProvider::new("mirror", &["Paper", "Mohist"], false);
+        let catalog = ServerCatalog::new(vec![paper.clone()], Some(mirror.clone()));
+
+        assert_eq!(catalog.server_types().await.unwrap(), ["paper", "folia", "Mohist"]);
+        let latest = catalog.latest_build("paper", "1.21.4").await.unwrap();
+        assert_eq!((latest.provider.as_str(), latest.build.as_str()), ("mirror", "1"));
+        assert_eq!(paper.build_queries.load(atomic::Ordering::SeqCst), 1);
+
+        // 没有上游声明的类型直接查镜像。
+        catalog.builds("Mohist", "1.20.1").await.unwrap();
+        assert_eq!(paper.build_queries.load(atomic::Ordering::SeqCst), 1);
+        assert_eq!(mirror.build_queries.load(atomic::Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn reports_upstream_error_without_fallback() {
+        let paper = FakeProvider::new("papermc", &["paper"], true);
+        let catalog = ServerCatalog::new(vec![paper], None);
+
+        let error = catalog.versions("paper").await.unwrap_err();
+        assert!(matches!(error, CatalogError::Unavailable { provider: "papermc", .. }));
+        assert!(matches!(
+            catalog.versions("spigot").await.unwrap_err(),
+            CatalogError::NotFound(_)
+        ));
+    }
+
+    fn forge_build(version: &str, channel: BuildChannel) -> CatalogBuild {
+        CatalogBuild {
+            server_type: "forge".to_owned(),
+            game_version: "1.20.1".to_owned(),
+            build: version.to_owned(),
+            channel,
+            artifact: BuildArtifact::Installer,
+            url: format!("https://example.invalid/forge-{version}-installer.jar"),
+            file_name: format!("forge-{version}-installer.jar"),
+            size: None,
+            hashes: BTreeMap::new(),
+            java_major: Some(17),
+            provider: "forge".to_owned(),
+        }
+    }
+
+    #[test]
+    fn selects_requested_or_recommended_build() {
+        let builds = vec![
+            forge_build("1.20.1-47.3.1", BuildChannel::Beta),
+            forge_build("1.20.1-47.3.0", BuildChannel::Stable),
+            forge_build("1.20.1-47.2.0", BuildChannel::Stable),
+        ];
+
+        let recommended = select_build(builds.clone(), None).unwrap();
+        assert_eq!(recommended.build, "1.20.1-47.3.0");
+        let by_loader = select_build(builds.clone(), Some("47.2.0")).unwrap();
+        assert_eq!(by_loader.build, "1.20.1-47.2.0");
+        let by_build = select_build(builds.clone(), Some("1.20.1-47.3.1")).unwrap();
+        assert_eq!(by_build.build, "1.20.1-47.3.1");
+        assert!(select_build(builds, Some("46.0.0")).is_none());
+    }
+}
@@ -0,0 +1,154 @@
+//! Purpur 目录（api.purpurmc.org v2）。
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use sealantern_infra::net::{ClientProvider, NetClient};
+
+use super::models::{BuildArtifact, BuildChannel, CatalogBuild, CatalogError, java_requirement};
+use super::provider::{CatalogProvider, get_json, owned_types, unknown_type};
+
+const PURPUR_BASE: &str = "https://api.purpurmc.org/v2/purpur";
+
+const PROVIDER: &str = "purpur";
+const SERVER_TYPE: &str = "purpur";
+
+/// `GET /v2/purpur`：版本从旧到新。
+#[derive(Deserialize)]
+struct PurpurProject {
+    versions: Vec<String>,
+}
+
+/// `GET /v2/purpur/{version}?detailed=true`。
+#[derive(Deserialize)]
+struct PurpurVersion {
+    builds: PurpurBuilds,
+}
+
+#[derive(Deserialize)]
+struct PurpurBuilds {
+    /// 从旧到新。
+    all: Vec<PurpurBuild>,
+}
+
+#[derive(Deserialize)]
+struct PurpurBuild {
+    build: String,
+    #[serde(default)]
+    result: Option<String>,
+    #[serde(default)]
+    md5: Option<String>,
+}
+
+/// Purpur 目录。
+pub struct PurpurProvider {
+    client_provider: ClientProvider,
+}
+
+impl PurpurProvider {
+    /// 使用全局客户端获取器构造。
+    pub fn global() -> Self {
+        Self::with_provider(sealantern_infra::net::global_client_provider())
+    }
+
+    /// 使用客户端获取器构造，每次请求前获取当前客户端。
+    pub fn with_provider(client_provider: ClientProvider) -> Self {
+        Self { client_provider }
+    }
+
+    /// 使用固定客户端构造。
+    pub fn new(client: NetClient) -> Self {
+        Self::with_provider(Box::new(move || Ok(client.clone())))
+    }
+}
+
+#[async_trait]
+impl CatalogProvider for PurpurProvider {
+    fn name(&self) -> &'static str {
+        PROVIDER
+    }
+
+    async fn server_types(&self) -> Result<Vec<String>, CatalogError> {
+        Ok(owned_types(&[SERVER_TYPE]))
+    }
+
+    async fn versions(&self, server_type: &str) -> Result<Vec<String>, CatalogError> {
+        if !server_type.eq_ignore_ascii_case(SERVER_TYPE) {
+            return Err(unknown_type(server_type));
+        }
+        let project: PurpurProject = get_json(&self.client_provider, PROVIDER, PURPUR_BASE).await?;
+        Ok(project.versions.into_iter().rev().collect())
+    }
+
+    async fn builds(
+        &self,
+        server_type: &str,
+        game_version: &str,
+    ) -> Result<Vec<CatalogBuild>, CatalogError> {
+        if !server_type.eq_ignore_ascii_case(SERVER_TYPE) {
+            return Err(unknown_type(server_type));
+        }
+        let url = format!("{PURPUR_BASE}/{game_version}?detailed=true");
+        let version: PurpurVersion = get_json(&self.client_provider, PROVIDER, &url).await?;
+        Ok(map_builds(game_version, version))
+    }
+}
+
+/// 构建从新到旧排列，跳过构建失败的条目。
+fn map_builds(game_version: &str, version: PurpurVersion) -> Vec<CatalogBuild> {
+    version
+        .builds
+        .all
+        .into_iter()
+        .rev()
+        .filter(|build| {
+            build
+                .result
+                .as_deref()
+                .is_none_or(|result| result.eq_ignore_ascii_case("SUCCESS"))
+        })
+        .map(|build| CatalogBuild {
+            server_type: SERVER_TYPE.to_owned(),
+            game_version: game_version.to_owned(),
+            url: format!("{PURPUR_BASE}/{game_version}/{}/download", build.build),
+            file_name: format!("purpur-{game_version}-{}.jar", build.build),
+            build: build.build,
+            channel: BuildChannel::Stable,
+            artifact: BuildArtifact::ServerJar,
+            size: None,
+            hashes: build
+                .md5
+                .map(|md5| BTreeMap::from([("md5".to_owned(), md5)]))
+                .unwrap_or_default(),
+            java_major: java_requirement(game_version),
+            provider: PROVIDER.to_owned(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_successful_builds_newest_first() {
+        let version: PurpurVersion = serde_json::from_str(
+            r#"{ "project": "purpur", "version": "1.21.4", "builds": { "all": [
+                { "build": "2380", "result": "SUCCESS", "md5": "aa" },
+                { "build": "2381", "result": "FAILURE", "md5": "bb" },
+                { "build": "2382", "result": "SUCCESS", "md5": "cc" }
+            ] } }"#,
+        )
+        .unwrap();
+
+        let builds = map_builds("1.21.4", version);
+
+        let ids: Vec<_> = builds.iter().map(|build| build.build.as_str()).collect();
+        assert_eq!(ids, ["2382", "2380"]);
+        assert_eq!(builds[0].url, "https://api.purpurmc.org/v2/purpur/1.21.4/2382/download");
+        assert_eq!(builds[0].hashes["md5"], "cc");
+        assert_eq!(builds[0].java_major, Some(21));
+    }
+}
@@ -1,6 +1,7 @@
 pub mod data_migration;
 pub mod sealantern;
 pub mod server;
+pub mod template;
 
 pub use sealantern::InstanceRegistry;
 #[allow(deprecated)]
@@ -9,8 +10,15 @@ pub use sealantern::types::{
     SettingsGroup, StartupMode, UpdateResult,
 };
 pub use sealantern::{SettingsError, SettingsManager};
+pub use template::{InstanceTemplateList, InstanceTemplateStore, InstanceTemplateStoreError};
 
-pub use server::{ConfigEntry, ServerProperties, ServerPropertiesError, ServerPropertiesManager};
+pub use server::{
+    ConfigDocument, ConfigDocumentError, ConfigDocumentFile, ConfigDocumentManager,
+    ConfigDocumentPreview, ConfigDocumentView, ConfigEntry, ConfigRevision, ConfigRevisionError,
+    ConfigRevisionSummary, DiffLine, DiffLineKind, DocumentEntry, DocumentFormat, DocumentPatch,
+    GameVersion, PropertiesSchema, PropertySchema, PropertyType, ServerProperties,
+    ServerPropertiesError, ServerPropertiesManager,
+};
 
 /// 解析应用数据目录，优先使用环境变量 `SEALANTERN_DATA_DIR`。
 pub use sealantern_infra::platform::get_app_data_dir as resolve_data_dir;
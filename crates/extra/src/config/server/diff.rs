@@ -0,0 +1,169 @@
+//! 配置文本的按行差异
+//!
+//! 以最长公共子序列求出增删行，供配置文档预览与修订历史使用；
+//! 修订历史以统一差异格式（unified diff）保存。
+
+/// 单行比较结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum LineOp<'a> {
+    Equal(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// 统一差异中每个变更块保留的上下文行数
+const CONTEXT_LINES: usize = 3;
+
+/// 按行比较两段文本，返回覆盖两侧全部行的操作序列
+pub(super) fn line_ops<'a>(before: &'a str, after: &'a str) -> Vec<LineOp<'a>> {
+    let old: Vec<&str> = before.lines().collect();
+    let new: Vec<&str> = after.lines().collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    // lengths[i][j]：old_middle[i..] 与 new_middle[j..] 的最长公共子序列长度
+    let width = new_middle.len() + 1;
+    let mut lengths = vec![0u32; (old_middle.len() + 1) * width];
+    for i in (0..old_middle.len()).rev() {
+        for j in (0..new_middle.len()).rev() {
+            lengths[i * width + j] = if old_middle[i] == new_middle[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut ops: Vec<LineOp> = old[..prefix]
+        .iter()
+        .map(|line| LineOp::Equal(line))
+        .collect();
+    let (mut i, mut j) = (0, 0);
+    while i < old_middle.len() && j < new_middle.len() {
+        if old_middle[i] == new_middle[j] {
+            ops.push(LineOp::Equal(old_middle[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            ops.push(LineOp::Removed(old_middle[i]));
+            i += 1;
+        } else {
+            ops.push(LineOp::Added(new_middle[j]));
+            j += 1;
+        }
+    }
+    ops.extend(old_middle[i..].iter().map(|line| LineOp::Removed(line)));
+    ops.extend(new_middle[j..].iter().map(|line| LineOp::Added(line)));
+    ops.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|line| LineOp::Equal(line)),
+    );
+    ops
+}
+
+/// 生成统一差异格式文本；两侧相同时返回空字符串
+pub fn unified_diff(before: &str, after: &str, from_label: &str, to_label: &str) -> String {
+    let ops = line_ops(before, after);
+    let changes: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| !matches!(op, LineOp::Equal(_)))
+        .map(|(index, _)| index)
+        .collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    // 每个操作之前已消耗的旧 / 新行数
+    let mut positions = Vec::with_capacity(ops.len());
+    let (mut old_line, mut new_line) = (0, 0);
+    for op in &ops {
+        positions.push((old_line, new_line));
+        match op {
+            LineOp::Equal(_) => {
+                old_line += 1;
+                new_line += 1;
+            }
+            LineOp::Removed(_) => old_line += 1,
+            LineOp::Added(_) => new_line += 1,
+        }
+    }
+
+    let mut output = format!("--- {from_label}\n+++ {to_label}\n");
+    let mut index = 0;
+    while index < changes.len() {
+        let start = changes[index].saturating_sub(CONTEXT_LINES);
+        let mut last = changes[index];
+        while index + 1 < changes.len() && changes[index + 1] <= last + 2 * CONTEXT_LINES + 1 {
+            index += 1;
+            last = changes[index];
+        }
+        index += 1;
+        let end = (last + CONTEXT_LINES + 1).min(ops.len());
+
+        let hunk = &ops[start..end];
+        let old_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, LineOp::Added(_)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|op| !matches!(op, LineOp::Removed(_)))
+            .count();
+        let (old_start, new_start) = positions[start];
+        let range = |start: usize, count: usize| {
+            if count == 0 {
+                format!("{start},0")
+            } else {
+                format!("{},{count}", start + 1)
+            }
+        };
+        output.push_str(&format!(
+            "@@ -{} +{} @@\n",
+            range(old_start, old_count),
+            range(new_start, new_count)
+        ));
+        for op in hunk {
+            let (marker, text) = match op {
+                LineOp::Equal(text) => (' ', text),
+                LineOp::Removed(text) => ('-', text),
+                LineOp::Added(text) => ('+', text),
+            };
+            output.push(marker);
+            output.push_str(text);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unified_diff_groups_changes_into_hunks() {
+        let before: String = (1..=20).map(|line| format!("line {line}\n")).collect();
+        let after = before
+            .replace("line 2\n", "line two\n")
+            .replace("line 18\n", "")
+            .replace("line 20\n", "line 20\nline 21\n");
+
+        assert_eq!(
+            unified_diff(&before, &after, "a/server.properties", "b/server.properties"),
+            "--- a/server.properties\n+++ b/server.properties\n\
+             @@ -1,5 +1,5 @@\n line 1\n-line 2\n+line two\n line 3\n line 4\n line 5\n\
+             @@ -15,6 +15,6 @@\n line 15\n line 16\n line 17\n-line 18\n line 19\n line 20\n+line 21\n"
+        );
+        assert_eq!(unified_diff("a\n", "a\n", "x", "y"), "");
+        assert_eq!(unified_diff("", "a\n", "x", "y"), "--- x\n+++ y\n@@ -0,0 +1,1 @@\n+a\n");
+    }
+}
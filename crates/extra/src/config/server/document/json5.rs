@@ -0,0 +1,560 @@
+//! JSON5 配置文档（同样用于普通 `.json`）
+//!
+//! 自带一个记录每个值字节范围的解析器：支持注释、单引号字符串、未加引号的键、
+//! 十六进制数与尾随逗号。修改时只替换目标值的文本片段，新键插入到所在对象的
+//! 最后一个成员之后并沿用其缩进。
+
+use serde_json::{Map, Number, Value};
+
+use super::{ConfigDocumentError, Node};
+
+/// 带字节范围的值
+struct Spanned {
+    value: Json5,
+    start: usize,
+    end: usize,
+}
+
+enum Json5 {
+    Object { members: Vec<Member>, close: usize },
+    Array(Vec<Spanned>),
+    Scalar { value: Value, single_quoted: bool },
+}
+
+struct Member {
+    key: String,
+    key_start: usize,
+    quoted_key: bool,
+    value: Spanned,
+}
+
+pub(super) fn tree(source: &str) -> Result<Node, ConfigDocumentError> {
+    let root = Parser::parse(source)?;
+    match root.value {
+        Json5::Object { .. } => Ok(node(root)),
+        _ => Err(ConfigDocumentError::Parse("JSON5 文档根节点必须是对象".to_string())),
+    }
+}
+
+pub(super) fn patch(
+    source: &str,
+    path: &[String],
+    value: &Value,
+) -> Result<String, ConfigDocumentError> {
+    let dotted = path.join(".");
+    let root = Parser::parse(source)?;
+    let (key, parents) = path.split_last().expect("键路径非空");
+    let mut object = &root;
+    for segment in parents {
+        object = find_member(object, segment)
+            .map(|member| &member.value)
+            .filter(|value| matches!(value.value, Json5::Object { .. }))
+            .ok_or_else(|| ConfigDocumentError::PathNotFound(dotted.clone()))?;
+    }
+    let Json5::Object { members, close } = &object.value else {
+        return Err(ConfigDocumentError::InvalidPatch {
+            path: dotted,
+            reason: "父级不是对象".to_string(),
+        });
+    };
+
+    let mut patched = source.to_string();
+    if let Some(member) = find_member(object, key) {
+        let Json5::Scalar { single_quoted, .. } = member.value.value else {
+            return Err(ConfigDocumentError::InvalidPatch {
+                path: dotted,
+                reason: "目标不是标量值".to_string(),
+            });
+        };
+        patched.replace_range(member.value.start..member.value.end, &render(value, single_quoted));
+        return Ok(patched);
+    }
+
+    let bare_keys = members.last().is_some_and(|member| !member.quoted_key);
+    let entry = format!("{}: {}", render_key(key, bare_keys), render(value, false));
+    let Some(last) = members.last() else {
+        let inner = &source[object.start + 1..*close];
+        if inner.contains('\n') {
+            let indent = format!("{}  ", line_indent(source, object.start));
+            let newline = if source.contains("\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            };
+            patched.insert_str(object.start + 1, &format!("{newline}{indent}{entry}"));
+        } else {
+            patched.replace_range(object.start + 1..*close, &format!(" {entry} "));
+        }
+        return Ok(patched);
+    };
+
+    let after_value = skip_trivia(source, last.value.end).unwrap_or(source.len());
+    let trailing_comma = source[after_value..].starts_with(',');
+    let anchor = if trailing_comma {
+        after_value + 1
+    } else {
+        last.value.end
+    };
+    let line_end = source[anchor..]
+        .find('\n')
+        .map_or(source.len(), |offset| anchor + offset);
+    let line_end = if source[..line_end].ends_with('\r') {
+        line_end - 1
+    } else {
+        line_end
+    };
+
+    if *close < line_end {
+        // 单行对象
+        if trailing_comma {
+            patched.insert_str(anchor, &format!(" {entry},"));
+        } else {
+            patched.insert_str(anchor, &format!(", {entry}"));
+        }
+    } else {
+        let indent = line_indent(source, last.key_start);
+        let newline = if source.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        if trailing_comma {
+            patched.insert_str(line_end, &format!("{newline}{indent}{entry},"));
+        } else {
+            patched.insert_str(line_end, &format!("{newline}{indent}{entry}"));
+            patched.insert(last.value.end, ',');
+        }
+    }
+    Ok(patched)
+}
+
+/// 同名键以最后一个为准，与常见 JSON 解析器一致
+fn find_member<'a>(object: &'a Spanned, key: &str) -> Option<&'a Member> {
+    match &object.value {
+        Json5::Object { members, .. } => members.iter().rev().find(|member| member.key == key),
+        _ => None,
+    }
+}
+
+fn line_indent(source: &str, position: usize) -> &str {
+    let line_start = source[..position].rfind('\n').map_or(0, |index| index + 1);
+    let line = &source[line_start..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// 跳过空白与注释，返回下一个有效字符的位置；块注释未闭合时返回 `None`
+fn skip_trivia(source: &str, mut position: usize) -> Option<usize> {
+    loop {
+        let rest = &source[position..];
+        let trimmed = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '\u{feff}');
+        position += rest.len() - trimmed.len();
+        if trimmed.starts_with("//") {
+            position += trimmed.find('\n').unwrap_or(trimmed.len());
+        } else if let Some(comment) = trimmed.strip_prefix("/*") {
+            position += comment.find("*/")? + 4;
+        } else {
+            return Some(position);
+        }
+    }
+}
+
+fn render(value: &Value, single_quoted: bool) -> String {
+    match value {
+        Value::String(text) if single_quoted => {
+            let mut rendered = String::from("'");
+            for c in text.chars() {
+                match c {
+                    '\\' => rendered.push_str("\\\\"),
+                    '\'' => rendered.push_str("\\'"),
+                    '\n' => rendered.push_str("\\n"),
+                    '\r' => rendered.push_str("\\r"),
+                    '\t' => rendered.push_str("\\t"),
+                    c if c.is_control() => rendered.push_str(&format!("\\u{:04x}", c as u32)),
+                    c => rendered.push(c),
+                }
+            }
+            rendered.push('\'');
+            rendered
+        }
+        other => other.to_string(),
+    }
+}
+
+fn render_key(key: &str, bare: bool) -> String {
+    if bare && is_identifier(key) {
+        key.to_string()
+    } else {
+        serde_json::to_string(key).expect("字符串可序列化")
+    }
+}
+
+fn is_identifier(key: &str) -> bool {
+    key.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '$')
+        && key
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+fn node(spanned: Spanned) -> Node {
+    match spanned.value {
+        Json5::Object { members, .. } => Node::Map(
+            members
+                .into_iter()
+                .map(|member| (member.key, node(member.value)))
+                .collect(),
+        ),
+        other => Node::Leaf(to_json(other)),
+    }
+}
+
+fn to_json(value: Json5) -> Value {
+    match value {
+        Json5::Object { members, .. } => Value::Object(
+            members
+                .into_iter()
+                .map(|member| (member.key, to_json(member.value.value)))
+                .collect::<Map<_, _>>(),
+        ),
+        Json5::Array(items) => {
+            Value::Array(items.into_iter().map(|item| to_json(item.value)).collect())
+        }
+        Json5::Scalar { value, .. } => value,
+    }
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn parse(source: &'a str) -> Result<Spanned, ConfigDocumentError> {
+        let mut parser = Self { source, position: 0 };
+        parser.skip_trivia()?;
+        let value = parser.value()?;
+        parser.skip_trivia()?;
+        if parser.position < source.len() {
+            return Err(parser.error("文档末尾存在多余内容"));
+        }
+        Ok(value)
+    }
+
+    fn error(&self, message: &str) -> ConfigDocumentError {
+        let line = self.source[..self.position].matches('\n').count() + 1;
+        ConfigDocumentError::Parse(format!("第 {line} 行: {message}"))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.position..].chars().next()
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    fn skip_trivia(&mut self) -> Result<(), ConfigDocumentError> {
+        match skip_trivia(self.source, self.position) {
+            Some(position) => {
+                self.position = position;
+                Ok(())
+            }
+            None => Err(self.error("块注释未闭合")),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), ConfigDocumentError> {
+        if self.peek() == Some(expected) {
+            self.position += expected.len_utf8();
+            Ok(())
+        } else {
+            Err(self.error(&format!("此处应为 `{expected}`")))
+        }
+    }
+
+    fn value(&mut self) -> Result<Spanned, ConfigDocumentError> {
+        let start = self.position;
+        let value = match self.peek() {
+            Some('{') => self.object()?,
+            Some('[') => self.array()?,
+            Some(quote @ ('"' | '\'')) => Json5::Scalar {
+                value: Value::String(self.string(quote)?),
+                single_quoted: quote == '\'',
+            },
+            Some(_) => Json5::Scalar {
+                value: self.literal()?,
+                single_quoted: false,
+            },
+            None => return Err(self.error("缺少值")),
+        };
+        Ok(Spanned { value, start, end: self.position })
+    }
+
+    fn object(&mut self) -> Result<Json5, ConfigDocumentError> {
+        self.expect('{')?;
+        let mut members = Vec::new();
+        loop {
+            self.skip_trivia()?;
+            if self.peek() == Some('}') {
+                break;
+            }
+            let key_start = self.position;
+            let (key, quoted_key) = match self.peek() {
+                Some(quote @ ('"' | '\'')) => (self.string(quote)?, true),
+                _ => (self.identifier()?, false),
+            };
+            self.skip_trivia()?;
+            self.expect(':')?;
+            self.skip_trivia()?;
+            let value = self.value()?;
+            members.push(Member { key, key_start, quoted_key, value });
+            self.skip_trivia()?;
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') => break,
+                _ => return Err(self.error("对象成员之间缺少 `,`")),
+            }
+        }
+        let close = self.position;
+        self.position += 1;
+        Ok(Json5::Object { members, close })
+    }
+
+    fn array(&mut self) -> Result<Json5, ConfigDocumentError> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        loop {
+            self.skip_trivia()?;
+            if self.peek() == Some(']') {
+                break;
+            }
+            items.push(self.value()?);
+            self.skip_trivia()?;
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(']') => break,
+                _ => return Err(self.error("数组元素之间缺少 `,`")),
+            }
+        }
+        self.position += 1;
+        Ok(Json5::Array(items))
+    }
+
+    fn identifier(&mut self) -> Result<String, ConfigDocumentError> {
+        let rest = self.rest();
+        let length = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+            .unwrap_or(rest.len());
+        if length == 0 || rest.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(self.error("无效的键"));
+        }
+        self.position += length;
+        Ok(rest[..length].to_string())
+    }
+
+    fn string(&mut self, quote: char) -> Result<String, ConfigDocumentError> {
+        self.position += 1;
+        let mut text = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                c if c == quote => {
+                    self.position += offset + 1;
+                    return Ok(text);
+                }
+                '\n' | '\r' => break,
+                '\\' => {
+                    let Some((_, escaped)) = chars.next() else {
+                        break;
+                    };
+                    match escaped {
+                        'b' => text.push('\u{8}'),
+                        'f' => text.push('\u{c}'),
+                        'n' => text.push('\n'),
+                        'r' => text.push('\r'),
+                        't' => text.push('\t'),
+                        'v' => text.push('\u{b}'),
+                        '0' => text.push('\0'),
+                        // 行延续
+                        '\n' | '\u{2028}' | '\u{2029}' => {}
+                        '\r' => {
+                            if chars.clone().next().is_some_and(|(_, next)| next == '\n') {
+                                chars.next();
+                            }
+                        }
+                        'x' | 'u' => {
+                            let digits = if escaped == 'x' { 2 } else { 4 };
+                            let hex: String = chars.by_ref().take(digits).map(|(_, c)| c).collect();
+                            let code = u32::from_str_radix(&hex, 16)
+                                .map_err(|_| self.error("无效的转义序列"))?;
+                            let code = if (0xD800..0xDC00).contains(&code) {
+                                let low: String =
+                                    chars.by_ref().skip(2).take(4).map(|(_, c)| c).collect();
+                                let low = u32::from_str_radix(&low, 16)
+                                    .map_err(|_| self.error("无效的代理对"))?;
+                                0x10000
+                                    + ((code - 0xD800) << 10)
+                                    + (low.wrapping_sub(0xDC00) & 0x3FF)
+                            } else {
+                                code
+                            };
+                            text.push(
+                                char::from_u32(code).ok_or_else(|| self.error("无效的转义序列"))?,
+                            );
+                        }
+                        other => text.push(other),
+                    }
+                }
+                c => text.push(c),
+            }
+        }
+        Err(self.error("字符串未闭合"))
+    }
+
+    fn literal(&mut self) -> Result<Value, ConfigDocumentError> {
+        let rest = self.rest();
+        let length = rest
+            .find(|c: char| !(c.is_alphanumeric() || matches!(c, '+' | '-' | '.' | '_')))
+            .unwrap_or(rest.len());
+        let token = &rest[..length];
+        let value = match token {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            "null" => Value::Null,
+            _ => number(token).ok_or_else(|| self.error(&format!("无法识别的值 `{token}`")))?,
+        };
+        self.position += length;
+        Ok(value)
+    }
+}
+
+/// 解析 JSON5 数字；无穷大与 NaN 以字符串形式呈现
+fn number(token: &str) -> Option<Value> {
+    let (negative, unsigned) = match token.as_bytes().first()? {
+        b'-' => (true, &token[1..]),
+        b'+' => (false, &token[1..]),
+        _ => (false, token),
+    };
+    if matches!(unsigned, "Infinity" | "NaN") {
+        return Some(Value::String(token.to_string()));
+    }
+    if let Some(hex) = unsigned
+        .strip_prefix("0x")
+        .or_else(|| unsigned.strip_prefix("0X"))
+    {
+        let magnitude = i64::from_str_radix(hex, 16).ok()?;
+        return Some(Value::Number((if negative { -magnitude } else { magnitude }).into()));
+    }
+    if !unsigned.starts_with(|c: char| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+    if !unsigned.contains(['.', 'e', 'E'])
+        && let Ok(integer) = unsigned.parse::<i64>()
+    {
+        return Some(Value::Number((if negative { -integer } else { integer }).into()));
+    }
+    let float: f64 = unsigned.parse().ok()?;
+    Number::from_f64(if negative { -float } else { float }).map(Value::Number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfigDocument, DocumentFormat};
+    use super::*;
+
+    const CONFIG: &str = "// 插件配置\n\
+{\n\
+\x20 enabled: true, // 总开关\n\
+\x20 name: 'lobby',\n\
+\x20 limits: {\n\
+\x20   maxPlayers: 0x20,\n\
+\x20   ratio: .5,\n\
+\x20 },\n\
+\x20 /* 频道 */\n\
+\x20 channels: ['global', \"local\"],\n\
+\x20 nested: { a: 1 },\n\
+\x20 \"empty\": {}\n\
+}\n";
+
+    fn path(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_json5_syntax() {
+        let document = ConfigDocument::parse(DocumentFormat::Json5, CONFIG).unwrap();
+        let entries = document.entries().unwrap();
+        let values: Vec<(String, Value)> = entries
+            .into_iter()
+            .map(|entry| (entry.path.join("."), entry.value))
+            .collect();
+        assert_eq!(
+            values,
+            [
+                ("enabled".to_string(), Value::Bool(true)),
+                ("name".to_string(), Value::from("lobby")),
+                ("limits.maxPlayers".to_string(), Value::from(32)),
+                ("limits.ratio".to_string(), Value::from(0.5)),
+                ("channels".to_string(), serde_json::json!(["global", "local"])),
+                ("nested.a".to_string(), Value::from(1)),
+                ("empty".to_string(), serde_json::json!({})),
+            ]
+        );
+        assert!(ConfigDocument::parse(DocumentFormat::Json5, "{ a: 1 /* x }").is_err());
+        assert!(ConfigDocument::parse(DocumentFormat::Json5, "{ a: 'x\n' }").is_err());
+        assert!(ConfigDocument::parse(DocumentFormat::Json5, "[1]").is_err());
+    }
+
+    #[test]
+    fn patches_and_inserts_keeping_comments() {
+        let mut document = ConfigDocument::parse(DocumentFormat::Json5, CONFIG).unwrap();
+        document
+            .patch(&path(&["enabled"]), &Value::Bool(false))
+            .unwrap();
+        document
+            .patch(&path(&["name"]), &Value::from("hub's"))
+            .unwrap();
+        document
+            .patch(&path(&["limits", "maxPlayers"]), &Value::from(64))
+            .unwrap();
+        document
+            .patch(&path(&["limits", "motd"]), &Value::from("hi"))
+            .unwrap();
+        document
+            .patch(&path(&["nested", "b"]), &Value::from(2))
+            .unwrap();
+        document
+            .patch(&path(&["empty", "x"]), &Value::Null)
+            .unwrap();
+        document
+            .patch(&path(&["debug"]), &Value::Bool(true))
+            .unwrap();
+
+        assert_eq!(
+            document.source(),
+            "// 插件配置\n\
+{\n\
+\x20 enabled: false, // 总开关\n\
+\x20 name: 'hub\\'s',\n\
+\x20 limits: {\n\
+\x20   maxPlayers: 64,\n\
+\x20   ratio: .5,\n\
+\x20   motd: \"hi\",\n\
+\x20 },\n\
+\x20 /* 频道 */\n\
+\x20 channels: ['global', \"local\"],\n\
+\x20 nested: { a: 1, b: 2 },\n\
+\x20 \"empty\": { \"x\": null },\n\
+\x20 \"debug\": true\n\
+}\n"
+        );
+        assert!(
+            document
+                .patch(&path(&["channels"]), &Value::from(1))
+                .is_err()
+        );
+        assert!(matches!(
+            document.patch(&path(&["missing", "key"]), &Value::from(1)),
+            Err(ConfigDocumentError::PathNotFound(_))
+        ));
+    }
+}
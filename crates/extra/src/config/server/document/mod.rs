@@ -0,0 +1,540 @@
+//! 服务端通用配置文档（YAML / TOML / JSON5）
+//!
+//! 覆盖 `bukkit.yml`、`spigot.yml`、`config/paper-*.yml`、`velocity.toml`、
+//! Forge `*-server.toml` 等文件：按键路径读取与修改标量值，写入前可预览差异。
+//! 修改只替换目标值所在的文本片段，注释、空行与键顺序保持不变；写入通过同级
+//! 临时文件原子替换。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sealantern_infra::fs::{FsError, SafeRelativePath, write_atomic_blocking};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::debug;
+
+use super::diff::{LineOp, line_ops};
+
+mod json5;
+mod toml;
+mod yaml;
+
+/// 单个配置文档允许读取的最大字节数
+const MAX_DOCUMENT_BYTES: u64 = 4 * 1024 * 1024;
+
+/// 发现配置文档时扫描的目录及递归深度（相对服务器根目录）
+const DISCOVERY_ROOTS: &[(&str, usize)] =
+    &[("", 0), ("config", 2), ("plugins", 1), ("world/serverconfig", 0)];
+
+/// 服务器根目录下由服务端自己维护的 JSON 数据文件，不是配置，发现时跳过
+const RUNTIME_DATA_FILES: &[&str] = &[
+    "banned-ips.json",
+    "banned-players.json",
+    "ops.json",
+    "usercache.json",
+    "usernamecache.json",
+    "version_history.json",
+    "whitelist.json",
+];
+
+/// 配置文档格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentFormat {
+    Yaml,
+    Toml,
+    Json5,
+}
+
+impl DocumentFormat {
+    /// 按扩展名识别格式；`.json` 按 JSON5 处理（JSON 是其子集）
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "yml" | "yaml" => Some(Self::Yaml),
+            "toml" => Some(Self::Toml),
+            "json5" | "json" => Some(Self::Json5),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Yaml => "yaml",
+            Self::Toml => "toml",
+            Self::Json5 => "json5",
+        }
+    }
+}
+
+/// 展开后的叶子配置项
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentEntry {
+    /// 从根到该值的键路径
+    pub path: Vec<String>,
+    pub value: Value,
+    /// boolean / number / string / null / array / object
+    pub value_type: String,
+}
+
+/// 对单个键路径的修改
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentPatch {
+    pub path: Vec<String>,
+    /// 新值，仅支持布尔、数字、字符串与 null（TOML 不支持 null）
+    pub value: Value,
+}
+
+/// 可编辑的配置文件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigDocumentFile {
+    /// 相对服务器根目录的路径，使用 `/` 分隔
+    pub path: String,
+    pub format: DocumentFormat,
+    pub size: u64,
+}
+
+/// 读取到的配置文档
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigDocumentView {
+    pub path: String,
+    pub format: DocumentFormat,
+    pub entries: Vec<DocumentEntry>,
+    pub source: String,
+}
+
+/// 差异行类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffLineKind {
+    Added,
+    Removed,
+}
+
+/// 修改前后的一行差异（行号从 1 开始）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub old_line: Option<usize>,
+    pub new_line: Option<usize>,
+    pub text: String,
+}
+
+/// 应用修改后的预览
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigDocumentPreview {
+    pub path: String,
+    pub format: DocumentFormat,
+    /// 修改后的完整文本
+    pub source: String,
+    pub diff: Vec<DiffLine>,
+}
+
+/// 按格式解析的配置文档
+#[derive(Debug, Clone)]
+pub struct ConfigDocument {
+    format: DocumentFormat,
+    source: String,
+}
+
+/// 格式解析器产出的有序树，用于展开叶子配置项
+pub(super) enum Node {
+    Map(Vec<(String, Node)>),
+    Leaf(Value),
+}
+
+impl ConfigDocument {
+    /// 解析文本；语法错误时返回 [`ConfigDocumentError::Parse`]
+    pub fn parse(
+        format: DocumentFormat,
+        source: impl Into<String>,
+    ) -> Result<Self, ConfigDocumentError> {
+        let document = Self { format, source: source.into() };
+        document.tree()?;
+        Ok(document)
+    }
+
+    pub fn format(&self) -> DocumentFormat {
+        self.format
+    }
+
+    /// 当前文本
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    fn tree(&self) -> Result<Node, ConfigDocumentError> {
+        match self.format {
+            DocumentFormat::Yaml => yaml::tree(&self.source),
+            DocumentFormat::Toml => toml::tree(&self.source),
+            DocumentFormat::Json5 => json5::tree(&self.source),
+        }
+    }
+
+    /// 按文档顺序展开的叶子配置项；映射逐层展开，数组作为整体值
+    pub fn entries(&self) -> Result<Vec<DocumentEntry>, ConfigDocumentError> {
+        let mut entries = Vec::new();
+        flatten(self.tree()?, &mut Vec::new(), &mut entries);
+        Ok(entries)
+    }
+
+    /// 读取键路径上的叶子值
+    pub fn get(&self, path: &[String]) -> Result<Option<Value>, ConfigDocumentError> {
+        Ok(self
+            .entries()?
+            .into_iter()
+            .find(|entry| entry.path == path)
+            .map(|entry| entry.value))
+    }
+
+    /// 修改或新增一个标量值
+    ///
+    /// 新键只能加入已存在的映射，不会自动创建中间层级。修改后重新解析并回读目标值，
+    /// 结果不一致时拒绝修改，保证不会写出被破坏的文件。
+    pub fn patch(&mut self, path: &[String], value: &Value) -> Result<(), ConfigDocumentError> {
+        if path.is_empty() || path.iter().any(|segment| segment.is_empty()) {
+            return Err(ConfigDocumentError::InvalidPatch {
+                path: path.join("."),
+                reason: "键路径不能为空".to_string(),
+            });
+        }
+        if !matches!(value, Value::Bool(_) | Value::Number(_) | Value::String(_) | Value::Null) {
+            return Err(ConfigDocumentError::InvalidPatch {
+                path: path.join("."),
+                reason: "只支持修改布尔、数字或字符串值".to_string(),
+            });
+        }
+        let source = match self.format {
+            DocumentFormat::Yaml => yaml::patch(&self.source, path, value)?,
+            DocumentFormat::Toml => toml::patch(&self.source, path, value)?,
+            DocumentFormat::Json5 => json5::patch(&self.source, path, value)?,
+        };
+        let patched = Self::parse(self.format, source)?;
+        let written = patched.get(path)?;
+        if !written
+            .as_ref()
+            .is_some_and(|written| values_match(written, value))
+        {
+            return Err(ConfigDocumentError::InvalidPatch {
+                path: path.join("."),
+                reason: "修改后回读的值与期望不一致".to_string(),
+            });
+        }
+        *self = patched;
+        Ok(())
+    }
+
+    /// 依次应用多个修改
+    pub fn apply(&mut self, patches: &[DocumentPatch]) -> Result<(), ConfigDocumentError> {
+        for patch in patches {
+            self.patch(&patch.path, &patch.value)?;
+        }
+        Ok(())
+    }
+}
+
+/// 服务器目录下通用配置文档的管理器
+pub struct ConfigDocumentManager {
+    server_path: PathBuf,
+}
+
+impl ConfigDocumentManager {
+    pub fn new(server_path: impl AsRef<Path>) -> Self {
+        Self {
+            server_path: server_path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// 列出服务器根目录、`config/`、`plugins/` 与 `world/serverconfig/` 下可编辑的配置文件
+    pub fn discover(&self) -> Result<Vec<ConfigDocumentFile>, ConfigDocumentError> {
+        let mut files = Vec::new();
+        for (root, depth) in DISCOVERY_ROOTS {
+            let directory = self.server_path.join(root);
+            if directory.is_dir() {
+                collect_documents(&directory, root, *depth, &mut files)?;
+            }
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        Ok(files)
+    }
+
+    /// 读取并解析配置文档
+    pub fn read(&self, relative: &str) -> Result<ConfigDocumentView, ConfigDocumentError> {
+        let document = self.load(relative)?;
+        Ok(ConfigDocumentView {
+            path: relative.to_string(),
+            format: document.format,
+            entries: document.entries()?,
+            source: document.source,
+        })
+    }
+
+    /// 预览应用修改后的文本与行差异，不写入磁盘
+    pub fn preview(
+        &self,
+        relative: &str,
+        patches: &[DocumentPatch],
+    ) -> Result<ConfigDocumentPreview, ConfigDocumentError> {
+        let original = self.load(relative)?;
+        let mut document = original.clone();
+        document.apply(patches)?;
+        Ok(ConfigDocumentPreview {
+            path: relative.to_string(),
+            format: document.format,
+            diff: diff_lines(&original.source, &document.source),
+            source: document.source,
+        })
+    }
+
+    /// 应用修改并原子写回，返回写入内容的预览
+    pub fn write(
+        &self,
+        relative: &str,
+        patches: &[DocumentPatch],
+    ) -> Result<ConfigDocumentPreview, ConfigDocumentError> {
+        let preview = self.preview(relative, patches)?;
+        if !preview.diff.is_empty() {
+            write_atomic_blocking(self.resolve(relative)?.1, preview.source.as_bytes())?;
+            debug!("写入配置文档成功: {}，变更 {} 行", relative, preview.diff.len());
+        }
+        Ok(preview)
+    }
+
+    fn resolve(&self, relative: &str) -> Result<(DocumentFormat, PathBuf), ConfigDocumentError> {
+        let path = SafeRelativePath::parse(relative)
+            .map_err(|_| ConfigDocumentError::InvalidPath(relative.to_string()))?;
+        let format = DocumentFormat::from_path(&path)
+            .ok_or_else(|| ConfigDocumentError::UnsupportedFormat(relative.to_string()))?;
+        Ok((format, self.server_path.join(path)))
+    }
+
+    fn load(&self, relative: &str) -> Result<ConfigDocument, ConfigDocumentError> {
+        let (format, path) = self.resolve(relative)?;
+        let size = fs::metadata(&path)?.len();
+        if size > MAX_DOCUMENT_BYTES {
+            return Err(ConfigDocumentError::TooLarge { path: relative.to_string(), size });
+        }
+        ConfigDocument::parse(format, fs::read_to_string(&path)?)
+    }
+}
+
+fn collect_documents(
+    directory: &Path,
+    relative: &str,
+    depth: usize,
+    files: &mut Vec<ConfigDocumentFile>,
+) -> Result<(), ConfigDocumentError> {
+    for entry in fs::read_dir(directory)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let path = if relative.is_empty() {
+            name
+        } else {
+            format!("{relative}/{name}")
+        };
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            if depth > 0 {
+                collect_documents(&entry.path(), &path, depth - 1, files)?;
+            }
+        } else if file_type.is_file()
+            && !(relative.is_empty() && RUNTIME_DATA_FILES.contains(&path.as_str()))
+            && let Some(format) = DocumentFormat::from_path(&path)
+        {
+            files.push(ConfigDocumentFile {
+                size: entry.metadata()?.len(),
+                path,
+                format,
+            });
+        }
+    }
+    Ok(())
+}
+
+fn flatten(node: Node, path: &mut Vec<String>, entries: &mut Vec<DocumentEntry>) {
+    match node {
+        Node::Map(children) if !children.is_empty() || path.is_empty() => {
+            for (key, child) in children {
+                path.push(key);
+                flatten(child, path, entries);
+                path.pop();
+            }
+        }
+        Node::Map(_) => entries.push(DocumentEntry {
+            path: path.clone(),
+            value: Value::Object(Default::default()),
+            value_type: "object".to_string(),
+        }),
+        Node::Leaf(value) => entries.push(DocumentEntry {
+            path: path.clone(),
+            value_type: value_type(&value).to_string(),
+            value,
+        }),
+    }
+}
+
+fn value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// 数字按数值比较（`1` 与 `1.0` 视为相同）
+fn values_match(written: &Value, expected: &Value) -> bool {
+    match (written, expected) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        _ => written == expected,
+    }
+}
+
+/// 修改前后的增删行
+fn diff_lines(before: &str, after: &str) -> Vec<DiffLine> {
+    let (mut old_line, mut new_line) = (0, 0);
+    let mut diff = Vec::new();
+    for op in line_ops(before, after) {
+        match op {
+            LineOp::Equal(_) => {
+                old_line += 1;
+                new_line += 1;
+            }
+            LineOp::Removed(text) => {
+                old_line += 1;
+                diff.push(DiffLine {
+                    kind: DiffLineKind::Removed,
+                    old_line: Some(old_line),
+                    new_line: None,
+                    text: text.to_string(),
+                });
+            }
+            LineOp::Added(text) => {
+                new_line += 1;
+                diff.push(DiffLine {
+                    kind: DiffLineKind::Added,
+                    old_line: None,
+                    new_line: Some(new_line),
+                    text: text.to_string(),
+                });
+            }
+        }
+    }
+    diff
+}
+
+/// 配置文档处理错误
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigDocumentError {
+    #[error("IO 错误: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("写入失败: {0}")]
+    Write(#[from] FsError),
+
+    #[error("配置文件路径无效: {0}")]
+    InvalidPath(String),
+
+    #[error("不支持的配置文件格式: {0}")]
+    UnsupportedFormat(String),
+
+    #[error("配置文件过大: {path}（{size} 字节）")]
+    TooLarge { path: String, size: u64 },
+
+    #[error("解析错误: {0}")]
+    Parse(String),
+
+    #[error("键路径不存在: {0}")]
+    PathNotFound(String),
+
+    #[error("无法修改 {path}: {reason}")]
+    InvalidPatch { path: String, reason: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[test]
+    fn diff_reports_changed_and_added_lines() {
+        let diff = diff_lines("a\nb\nc\n", "a\nB\nc\nd\n");
+        assert_eq!(
+            diff.iter()
+                .map(|line| (line.kind, line.old_line, line.new_line, line.text.as_str()))
+                .collect::<Vec<_>>(),
+            [
+                (DiffLineKind::Removed, Some(2), None, "b"),
+                (DiffLineKind::Added, None, Some(2), "B"),
+                (DiffLineKind::Added, None, Some(4), "d"),
+            ]
+        );
+        assert!(diff_lines("same\n", "same\n").is_empty());
+    }
+
+    #[test]
+    fn manager_discovers_previews_and_writes_documents() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        fs::create_dir_all(root.join("config")).unwrap();
+        fs::create_dir_all(root.join("world/serverconfig")).unwrap();
+        fs::write(root.join("spigot.yml"), "# Spigot\nsettings:\n  debug: false # 调试\n").unwrap();
+        fs::write(root.join("config/paper-global.yml"), "_version: 28\n").unwrap();
+        fs::write(root.join("world/serverconfig/forge-server.toml"), "[server]\n").unwrap();
+        fs::write(root.join("server.properties"), "motd=x\n").unwrap();
+        fs::write(root.join("ops.json"), "[]").unwrap();
+        fs::write(root.join("usercache.json"), "[]").unwrap();
+
+        let manager = ConfigDocumentManager::new(root);
+        let files: Vec<_> = manager
+            .discover()
+            .unwrap()
+            .into_iter()
+            .map(|file| file.path)
+            .collect();
+        assert_eq!(
+            files,
+            ["config/paper-global.yml", "spigot.yml", "world/serverconfig/forge-server.toml"]
+        );
+
+        let patches = [DocumentPatch {
+            path: path(&["settings", "debug"]),
+            value: Value::Bool(true),
+        }];
+        let preview = manager.preview("spigot.yml", &patches).unwrap();
+        assert_eq!(preview.diff.len(), 2);
+        assert_eq!(preview.diff[1].text, "  debug: true # 调试");
+        assert!(
+            fs::read_to_string(root.join("spigot.yml"))
+                .unwrap()
+                .contains("debug: false")
+        );
+
+        manager.write("spigot.yml", &patches).unwrap();
+        let view = manager.read("spigot.yml").unwrap();
+        assert_eq!(view.source, "# Spigot\nsettings:\n  debug: true # 调试\n");
+        assert_eq!(view.entries[0].path, path(&["settings", "debug"]));
+
+        let missing = [DocumentPatch {
+            path: path(&["nope", "debug"]),
+            value: Value::Bool(true),
+        }];
+        assert!(matches!(
+            manager.write("spigot.yml", &missing),
+            Err(ConfigDocumentError::PathNotFound(_))
+        ));
+        assert!(matches!(
+            manager.read("../spigot.yml"),
+            Err(ConfigDocumentError::InvalidPath(_))
+        ));
+        assert!(matches!(
+            manager.read("server.properties"),
+            Err(ConfigDocumentError::UnsupportedFormat(_))
+        ));
+    }
+}
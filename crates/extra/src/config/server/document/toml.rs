@@ -0,0 +1,183 @@
+//! TOML 配置文档（`velocity.toml`、Forge `*-server.toml` 等）
+//!
+//! 基于 `toml_edit` 的格式保留文档模型：替换值时沿用原值的前后缀装饰，
+//! 行尾注释与缩进保持不变。
+
+use serde_json::{Number, Value};
+use toml_edit::{DocumentMut, Item, TableLike};
+
+use super::{ConfigDocumentError, Node};
+
+pub(super) fn tree(source: &str) -> Result<Node, ConfigDocumentError> {
+    let document = parse(source)?;
+    Ok(table_node(document.as_table()))
+}
+
+pub(super) fn patch(
+    source: &str,
+    path: &[String],
+    value: &Value,
+) -> Result<String, ConfigDocumentError> {
+    let dotted = path.join(".");
+    let replacement = match value {
+        Value::Bool(value) => toml_edit::Value::from(*value),
+        Value::Number(number) => match number.as_i64() {
+            Some(integer) => toml_edit::Value::from(integer),
+            None => toml_edit::Value::from(number.as_f64().unwrap_or_default()),
+        },
+        Value::String(text) => toml_edit::Value::from(text.as_str()),
+        _ => {
+            return Err(ConfigDocumentError::InvalidPatch {
+                path: dotted,
+                reason: "TOML 不支持 null".to_string(),
+            });
+        }
+    };
+
+    let mut document = parse(source)?;
+    let (key, parents) = path.split_last().expect("键路径非空");
+    let mut table: &mut dyn TableLike = document.as_table_mut();
+    for segment in parents {
+        table = table
+            .get_mut(segment)
+            .and_then(Item::as_table_like_mut)
+            .ok_or_else(|| ConfigDocumentError::PathNotFound(dotted.clone()))?;
+    }
+    match table.get_mut(key) {
+        Some(Item::Value(current)) if !current.is_array() && !current.is_inline_table() => {
+            let decor = current.decor().clone();
+            *current = replacement;
+            *current.decor_mut() = decor;
+        }
+        Some(_) => {
+            return Err(ConfigDocumentError::InvalidPatch {
+                path: dotted,
+                reason: "目标不是标量值".to_string(),
+            });
+        }
+        None => {
+            table.insert(key, Item::Value(replacement));
+        }
+    }
+    Ok(document.to_string())
+}
+
+fn parse(source: &str) -> Result<DocumentMut, ConfigDocumentError> {
+    source
+        .parse::<DocumentMut>()
+        .map_err(|error| ConfigDocumentError::Parse(error.to_string()))
+}
+
+fn table_node(table: &dyn TableLike) -> Node {
+    Node::Map(
+        table
+            .iter()
+            .filter_map(|(key, item)| {
+                let node = match item {
+                    Item::None => return None,
+                    Item::Value(toml_edit::Value::InlineTable(inline)) => table_node(inline),
+                    Item::Value(value) => Node::Leaf(to_json(value)),
+                    Item::Table(table) => table_node(table),
+                    Item::ArrayOfTables(array) => Node::Leaf(Value::Array(
+                        array.iter().map(|table| table_json(table)).collect(),
+                    )),
+                };
+                Some((key.to_string(), node))
+            })
+            .collect(),
+    )
+}
+
+fn table_json(table: &dyn TableLike) -> Value {
+    Value::Object(
+        table
+            .iter()
+            .filter_map(|(key, item)| {
+                let value = match item {
+                    Item::None => return None,
+                    Item::Value(value) => to_json(value),
+                    Item::Table(table) => table_json(table),
+                    Item::ArrayOfTables(array) => {
+                        Value::Array(array.iter().map(|table| table_json(table)).collect())
+                    }
+                };
+                Some((key.to_string(), value))
+            })
+            .collect(),
+    )
+}
+
+fn to_json(value: &toml_edit::Value) -> Value {
+    match value {
+        toml_edit::Value::String(text) => Value::String(text.value().clone()),
+        toml_edit::Value::Integer(integer) => Value::Number((*integer.value()).into()),
+        toml_edit::Value::Float(float) => Number::from_f64(*float.value())
+            .map(Value::Number)
+            .unwrap_or_else(|| Value::String(float.value().to_string())),
+        toml_edit::Value::Boolean(boolean) => Value::Bool(*boolean.value()),
+        toml_edit::Value::Datetime(datetime) => Value::String(datetime.value().to_string()),
+        toml_edit::Value::Array(array) => Value::Array(array.iter().map(to_json).collect()),
+        toml_edit::Value::InlineTable(inline) => table_json(inline),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfigDocument, DocumentFormat};
+    use super::*;
+
+    const VELOCITY: &str = r#"# Config version. Do not change this
+config-version = "2.7"
+
+# What port should the proxy be bound to?
+bind = "0.0.0.0:25577"
+show-max-players = 500 # 显示的最大人数
+
+[servers]
+lobby = "127.0.0.1:30066"
+try = ["lobby"]
+
+[advanced]
+compression-threshold = 256
+"#;
+
+    fn path(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[test]
+    fn patches_values_and_keeps_comments() {
+        let mut document = ConfigDocument::parse(DocumentFormat::Toml, VELOCITY).unwrap();
+        assert_eq!(
+            document.get(&path(&["servers", "try"])).unwrap(),
+            Some(serde_json::json!(["lobby"]))
+        );
+
+        document
+            .patch(&path(&["show-max-players"]), &Value::from(100))
+            .unwrap();
+        document
+            .patch(&path(&["servers", "survival"]), &Value::from("127.0.0.1:30067"))
+            .unwrap();
+        document
+            .patch(&path(&["advanced", "compression-threshold"]), &Value::from(-1))
+            .unwrap();
+
+        let source = document.source();
+        assert!(source.contains("# What port should the proxy be bound to?\n"));
+        assert!(source.contains("show-max-players = 100 # 显示的最大人数\n"));
+        assert!(source.contains("survival = \"127.0.0.1:30067\"\n"));
+        assert!(source.contains("compression-threshold = -1\n"));
+
+        assert!(
+            document
+                .patch(&path(&["servers", "try"]), &Value::from("x"))
+                .is_err()
+        );
+        assert!(document.patch(&path(&["bind"]), &Value::Null).is_err());
+        assert!(matches!(
+            document.patch(&path(&["missing", "key"]), &Value::from(1)),
+            Err(ConfigDocumentError::PathNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,511 @@
+//! YAML 配置文档（`bukkit.yml`、`spigot.yml`、`config/paper-*.yml` 等）
+//!
+//! 读取交给 `serde_yaml`；修改时按行与缩进定位块映射中的键，只替换值所在的
+//! 文本片段，行尾注释与其余内容原样保留。序列、块标量、锚点与流式集合内部的值
+//! 不支持修改。
+
+use serde_json::{Number, Value};
+use serde_yaml::Value as Yaml;
+
+use super::{ConfigDocumentError, Node};
+
+/// 可定位的键所在行
+struct KeyLine {
+    line: usize,
+    indent: usize,
+    path: Vec<String>,
+    kind: KeyValue,
+    /// 值在整个文本中的字节范围；值为空时为冒号之后的位置
+    value_start: usize,
+    value_end: usize,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum KeyValue {
+    /// 值为空，子项（如有）在后续缩进更深的行
+    Block,
+    Plain,
+    SingleQuoted,
+    DoubleQuoted,
+    /// 块标量、流式集合、锚点、别名或带标签的值
+    Unsupported,
+}
+
+/// 一行文本：起始偏移与去掉换行符的内容
+struct Line<'a> {
+    start: usize,
+    text: &'a str,
+}
+
+pub(super) fn tree(source: &str) -> Result<Node, ConfigDocumentError> {
+    if source.trim().is_empty() {
+        return Ok(Node::Map(Vec::new()));
+    }
+    let value: Yaml = serde_yaml::from_str(source)
+        .map_err(|error| ConfigDocumentError::Parse(error.to_string()))?;
+    match value {
+        Yaml::Null => Ok(Node::Map(Vec::new())),
+        Yaml::Mapping(_) => Ok(node(value)),
+        _ => Err(ConfigDocumentError::Parse("YAML 文档根节点必须是映射".to_string())),
+    }
+}
+
+pub(super) fn patch(
+    source: &str,
+    path: &[String],
+    value: &Value,
+) -> Result<String, ConfigDocumentError> {
+    let dotted = path.join(".");
+    let lines = split_lines(source);
+    let keys = scan(&lines);
+
+    if let Some(target) = keys.iter().find(|key| key.path == path) {
+        let has_children = block_lines(&lines, target).next().is_some();
+        let quote = match target.kind {
+            KeyValue::Block if !has_children => KeyValue::Plain,
+            KeyValue::Plain | KeyValue::SingleQuoted | KeyValue::DoubleQuoted => target.kind,
+            _ => {
+                return Err(ConfigDocumentError::InvalidPatch {
+                    path: dotted,
+                    reason: "目标不是标量值".to_string(),
+                });
+            }
+        };
+        let mut rendered = render(value, quote);
+        if target.value_start == target.value_end {
+            rendered.insert(0, ' ');
+        }
+        let mut patched = source.to_string();
+        patched.replace_range(target.value_start..target.value_end, &rendered);
+        return Ok(patched);
+    }
+
+    let (key, parents) = path.split_last().expect("键路径非空");
+    let newline = if source.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let entry = |indent: usize| {
+        format!("{}{}: {}", " ".repeat(indent), render_key(key), render(value, KeyValue::Plain))
+    };
+
+    if parents.is_empty() {
+        let indent = keys
+            .iter()
+            .find(|key| key.path.len() == 1)
+            .map_or(0, |key| key.indent);
+        let mut patched = source.to_string();
+        if !patched.is_empty() && !patched.ends_with('\n') {
+            patched.push_str(newline);
+        }
+        patched.push_str(&entry(indent));
+        patched.push_str(newline);
+        return Ok(patched);
+    }
+
+    let parent = keys
+        .iter()
+        .find(|key| key.path == parents)
+        .ok_or_else(|| ConfigDocumentError::PathNotFound(dotted.clone()))?;
+    let children: Vec<&Line> = block_lines(&lines, parent).collect();
+    let is_sequence = children.first().is_some_and(|line| {
+        let content = line.text.trim_start();
+        content == "-" || content.starts_with("- ")
+    });
+    if parent.kind != KeyValue::Block || is_sequence {
+        return Err(ConfigDocumentError::InvalidPatch {
+            path: dotted,
+            reason: "父级不是映射".to_string(),
+        });
+    }
+    let indent = keys
+        .iter()
+        .find(|key| {
+            key.line > parent.line
+                && key.path.len() == path.len()
+                && key.path[..parents.len()] == *parents
+        })
+        .map_or(parent.indent + 2, |key| key.indent);
+    // 新键追加在父级块的最后一个非空行之后
+    let anchor = line_end(children.last().copied().unwrap_or(&lines[parent.line]));
+
+    let mut patched = source.to_string();
+    patched.insert_str(anchor, &format!("{newline}{}", entry(indent)));
+    Ok(patched)
+}
+
+fn split_lines(source: &str) -> Vec<Line<'_>> {
+    let mut start = 0;
+    source
+        .split_inclusive('\n')
+        .map(|raw| {
+            let line = Line {
+                start,
+                text: raw.trim_end_matches(['\n', '\r']),
+            };
+            start += raw.len();
+            line
+        })
+        .collect()
+}
+
+fn line_end(line: &Line) -> usize {
+    line.start + line.text.len()
+}
+
+fn indent_of(text: &str) -> usize {
+    text.len() - text.trim_start_matches(' ').len()
+}
+
+/// 键之后属于其值的非空行（缩进更深，或同缩进的序列项）
+fn block_lines<'a>(lines: &'a [Line<'a>], key: &KeyLine) -> impl Iterator<Item = &'a Line<'a>> {
+    let indent = key.indent;
+    let mut sequence_allowed = true;
+    lines[key.line + 1..]
+        .iter()
+        .filter(|line| !line.text.trim().is_empty())
+        .take_while(move |line| {
+            let content = line.text.trim_start_matches(' ');
+            let line_indent = indent_of(line.text);
+            let is_item = content == "-" || content.starts_with("- ");
+            let inside =
+                line_indent > indent || (sequence_allowed && line_indent == indent && is_item);
+            sequence_allowed &= is_item || line_indent > indent;
+            inside
+        })
+}
+
+/// 扫描块映射中的所有键及其完整路径
+fn scan(lines: &[Line]) -> Vec<KeyLine> {
+    let mut keys = Vec::new();
+    let mut stack: Vec<(usize, String)> = Vec::new();
+    // 该缩进之下的行属于块标量、序列项或跨行流式集合，不参与定位
+    let mut opaque_indent: Option<usize> = None;
+
+    for (index, line) in lines.iter().enumerate() {
+        let content = line.text.trim_start_matches(' ');
+        let indent = indent_of(line.text);
+        if content.trim().is_empty() {
+            continue;
+        }
+        if let Some(opaque) = opaque_indent {
+            if indent > opaque {
+                continue;
+            }
+            opaque_indent = None;
+        }
+        if content.starts_with('#') || content.starts_with("---") || content.starts_with("...") {
+            continue;
+        }
+        while stack.last().is_some_and(|(level, _)| *level >= indent) {
+            stack.pop();
+        }
+        if content == "-" || content.starts_with("- ") {
+            opaque_indent = Some(indent);
+            continue;
+        }
+        let Some((key, after_colon)) = split_key(content) else {
+            opaque_indent = Some(indent);
+            continue;
+        };
+
+        let rest = &content[after_colon..];
+        let value_offset = rest.len() - rest.trim_start().len();
+        let value_text = value_span(rest.trim_start());
+        let value_start = line.start + indent + after_colon + value_offset;
+        let (kind, value_start, value_end) = if value_text.is_empty() {
+            let colon = line.start + indent + after_colon;
+            (KeyValue::Block, colon, colon)
+        } else {
+            let kind = match value_text.as_bytes()[0] {
+                b'\'' => KeyValue::SingleQuoted,
+                b'"' => KeyValue::DoubleQuoted,
+                b'|' | b'>' | b'[' | b'{' | b'&' | b'*' | b'!' => KeyValue::Unsupported,
+                _ => KeyValue::Plain,
+            };
+            (kind, value_start, value_start + value_text.len())
+        };
+        if kind == KeyValue::Unsupported {
+            opaque_indent = Some(indent);
+        }
+
+        let mut path: Vec<String> = stack.iter().map(|(_, key)| key.clone()).collect();
+        path.push(key.clone());
+        keys.push(KeyLine {
+            line: index,
+            indent,
+            path,
+            kind,
+            value_start,
+            value_end,
+        });
+        if kind == KeyValue::Block {
+            stack.push((indent, key));
+        }
+    }
+    keys
+}
+
+/// 拆出行首的键，返回键与冒号之后的偏移
+fn split_key(content: &str) -> Option<(String, usize)> {
+    let bytes = content.as_bytes();
+    let (key, end) = match bytes[0] {
+        b'"' => {
+            let end = quoted_end(content, b'"')?;
+            (serde_json::from_str::<String>(&content[..end]).ok()?, end)
+        }
+        b'\'' => {
+            let end = quoted_end(content, b'\'')?;
+            (content[1..end - 1].replace("''", "'"), end)
+        }
+        b'?' | b'[' | b'{' | b'&' | b'*' | b'!' | b'|' | b'>' => return None,
+        _ => {
+            let colon = (0..bytes.len()).find(|&i| {
+                bytes[i] == b':'
+                    && bytes
+                        .get(i + 1)
+                        .is_none_or(|next| *next == b' ' || *next == b'\t')
+            })?;
+            let key = content[..colon].trim_end();
+            if key.contains(" #") {
+                return None;
+            }
+            return Some((key.to_string(), colon + 1));
+        }
+    };
+    let rest = &content[end..];
+    let colon = rest.len() - rest.trim_start().len();
+    (rest[colon..].starts_with(':')).then_some((key, end + colon + 1))
+}
+
+/// 引号字符串结束位置（含结束引号）
+fn quoted_end(text: &str, quote: u8) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut index = 1;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' if quote == b'"' => index += 2,
+            b'\'' if quote == b'\'' && bytes.get(index + 1) == Some(&b'\'') => index += 2,
+            byte if byte == quote => return Some(index + 1),
+            _ => index += 1,
+        }
+    }
+    None
+}
+
+/// 去掉行尾注释后的值文本
+fn value_span(value: &str) -> &str {
+    let bytes = value.as_bytes();
+    let start = match bytes.first() {
+        Some(&quote @ (b'"' | b'\'')) => quoted_end(value, quote).unwrap_or(value.len()),
+        _ => 0,
+    };
+    let comment = (start..bytes.len())
+        .find(|&i| bytes[i] == b'#' && (i == 0 || bytes[i - 1] == b' ' || bytes[i - 1] == b'\t'))
+        .unwrap_or(bytes.len());
+    value[..comment].trim_end()
+}
+
+fn render(value: &Value, quote: KeyValue) -> String {
+    match value {
+        Value::String(text) => match quote {
+            KeyValue::SingleQuoted if !text.chars().any(char::is_control) => {
+                format!("'{}'", text.replace('\'', "''"))
+            }
+            KeyValue::Plain if is_plain_safe(text) => text.clone(),
+            _ => serde_json::to_string(text).expect("字符串可序列化"),
+        },
+        other => other.to_string(),
+    }
+}
+
+fn render_key(key: &str) -> String {
+    if is_plain_safe(key) {
+        key.to_string()
+    } else {
+        serde_json::to_string(key).expect("字符串可序列化")
+    }
+}
+
+/// 字符串能否不加引号书写且被解析回同一个字符串
+fn is_plain_safe(text: &str) -> bool {
+    !text.is_empty()
+        && text.trim() == text
+        && !text.chars().any(char::is_control)
+        && !text.starts_with(|c: char| "-?:,[]{}#&*!|>'\"%@`".contains(c))
+        && !text.contains(": ")
+        && !text.contains(" #")
+        && !text.ends_with(':')
+        && serde_yaml::from_str::<Yaml>(text).is_ok_and(|parsed| parsed.as_str() == Some(text))
+}
+
+fn node(value: Yaml) -> Node {
+    match value {
+        Yaml::Mapping(mapping) => Node::Map(
+            mapping
+                .into_iter()
+                .map(|(key, value)| (key_string(&key), node(value)))
+                .collect(),
+        ),
+        Yaml::Tagged(tagged) => node(tagged.value),
+        other => Node::Leaf(to_json(other)),
+    }
+}
+
+fn key_string(key: &Yaml) -> String {
+    match key {
+        Yaml::String(text) => text.clone(),
+        Yaml::Bool(value) => value.to_string(),
+        Yaml::Number(number) => number.to_string(),
+        Yaml::Null => "null".to_string(),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+fn to_json(value: Yaml) -> Value {
+    match value {
+        Yaml::Null => Value::Null,
+        Yaml::Bool(value) => Value::Bool(value),
+        Yaml::Number(number) => {
+            if let Some(integer) = number.as_i64() {
+                Value::Number(integer.into())
+            } else if let Some(integer) = number.as_u64() {
+                Value::Number(integer.into())
+            } else {
+                let float = number.as_f64().unwrap_or_default();
+                Number::from_f64(float)
+                    .map_or_else(|| Value::String(number.to_string()), Value::Number)
+            }
+        }
+        Yaml::String(text) => Value::String(text),
+        Yaml::Sequence(items) => Value::Array(items.into_iter().map(to_json).collect()),
+        Yaml::Mapping(mapping) => Value::Object(
+            mapping
+                .into_iter()
+                .map(|(key, value)| (key_string(&key), to_json(value)))
+                .collect(),
+        ),
+        Yaml::Tagged(tagged) => to_json(tagged.value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{ConfigDocument, DocumentFormat};
+    use super::*;
+
+    const SPIGOT: &str = "# This is the main configuration file for Spigot.\n\
+settings:\n\
+\x20 debug: false # 调试输出\n\
+\x20 timeout-time: 60\n\
+\x20 restart-script: './start.sh'\n\
+\x20 attribute:\n\
+\x20   maxHealth:\n\
+\x20     max: 1024.0\n\
+commands:\n\
+\x20 spam-exclusions:\n\
+\x20 - /skill\n\
+\x20 log: true\n\
+messages:\n\
+\x20 whitelist: \"You are not whitelisted on this server!\"\n\
+world-settings:\n\
+\x20 default: {}\n";
+
+    fn path(keys: &[&str]) -> Vec<String> {
+        keys.iter().map(|key| key.to_string()).collect()
+    }
+
+    #[test]
+    fn reads_nested_entries_in_document_order() {
+        let document = ConfigDocument::parse(DocumentFormat::Yaml, SPIGOT).unwrap();
+        let entries = document.entries().unwrap();
+        let paths: Vec<String> = entries.iter().map(|entry| entry.path.join(".")).collect();
+        assert_eq!(
+            paths,
+            [
+                "settings.debug",
+                "settings.timeout-time",
+                "settings.restart-script",
+                "settings.attribute.maxHealth.max",
+                "commands.spam-exclusions",
+                "commands.log",
+                "messages.whitelist",
+                "world-settings.default",
+            ]
+        );
+        assert_eq!(entries[0].value_type, "boolean");
+        assert_eq!(entries[4].value, serde_json::json!(["/skill"]));
+        assert_eq!(entries[7].value_type, "object");
+    }
+
+    #[test]
+    fn patches_scalars_and_keeps_comments_and_quotes() {
+        let mut document = ConfigDocument::parse(DocumentFormat::Yaml, SPIGOT).unwrap();
+        document
+            .patch(&path(&["settings", "debug"]), &Value::Bool(true))
+            .unwrap();
+        document
+            .patch(&path(&["settings", "restart-script"]), &Value::from("./run.sh"))
+            .unwrap();
+        document
+            .patch(&path(&["settings", "attribute", "maxHealth", "max"]), &Value::from(2048.0))
+            .unwrap();
+        document
+            .patch(&path(&["messages", "whitelist"]), &Value::from("Not \"invited\""))
+            .unwrap();
+        document
+            .patch(&path(&["commands", "log"]), &Value::from("yes: really"))
+            .unwrap();
+
+        let source = document.source();
+        assert!(source.starts_with("# This is the main configuration file for Spigot.\n"));
+        assert!(source.contains("  debug: true # 调试输出\n"));
+        assert!(source.contains("  restart-script: './run.sh'\n"));
+        assert!(source.contains("      max: 2048.0\n"));
+        assert!(source.contains("  whitelist: \"Not \\\"invited\\\"\"\n"));
+        assert!(source.contains("  log: \"yes: really\"\n"));
+
+        assert!(
+            document
+                .patch(&path(&["settings"]), &Value::from(1))
+                .is_err()
+        );
+        assert!(
+            document
+                .patch(&path(&["commands", "spam-exclusions"]), &Value::from(1))
+                .is_err()
+        );
+        assert!(
+            document
+                .patch(&path(&["world-settings", "default"]), &Value::from(1))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn inserts_new_keys_under_existing_mappings() {
+        let mut document = ConfigDocument::parse(DocumentFormat::Yaml, SPIGOT).unwrap();
+        document
+            .patch(&path(&["settings", "bungeecord"]), &Value::Bool(true))
+            .unwrap();
+        document
+            .patch(&path(&["commands", "silent-commandblock-console"]), &Value::Bool(false))
+            .unwrap();
+        document
+            .patch(&path(&["config-version"]), &Value::from(12))
+            .unwrap();
+
+        let source = document.source();
+        assert!(source.contains("      max: 1024.0\n  bungeecord: true\ncommands:\n"));
+        assert!(source.contains("  log: true\n  silent-commandblock-console: false\nmessages:\n"));
+        assert!(source.ends_with("  default: {}\nconfig-version: 12\n"));
+        assert!(matches!(
+            document.patch(&path(&["missing", "key"]), &Value::from(1)),
+            Err(ConfigDocumentError::PathNotFound(_))
+        ));
+    }
+}
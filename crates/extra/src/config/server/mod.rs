@@ -1,6 +1,9 @@
 //! Server.properties 配置文件管理
 //!
-//! 提供读取、写入、解析 server.properties 文件的能力
+//! 提供读取、写入、解析 server.properties 文件的能力；字段按游戏版本的
+//! [`PropertiesSchema`] 标注类型与说明，写入前校验取值。其余 YAML / TOML / JSON5
+//! 配置文件由 [`ConfigDocumentManager`] 按键路径编辑；经 Sea Lantern 的编辑记入
+//! 修订历史（[`record_revision`]）
 
 use std::collections::BTreeMap;
 use std::fs;
@@ -9,6 +12,24 @@ use std::path::Path;
 use serde::{Deserialize, Serialize};
 use tracing::debug;
 
+mod diff;
+mod document;
+mod revision;
+mod schema;
+
+pub use diff::unified_diff;
+pub use document::{
+    ConfigDocument, ConfigDocumentError, ConfigDocumentFile, ConfigDocumentManager,
+    ConfigDocumentPreview, ConfigDocumentView, DiffLine, DiffLineKind, DocumentEntry,
+    DocumentFormat, DocumentPatch,
+};
+pub use revision::{
+    ConfigRevision, ConfigRevisionError, ConfigRevisionSummary, EXTERNAL_PRINCIPAL_KIND,
+    MAX_REVISIONS_PER_FILE, REVISION_DATABASE_FILE, diff_revisions, find_revision, list_revisions,
+    open_revision_database, read_config_snapshot, record_revision, restore_revision,
+};
+pub use schema::{GameVersion, PropertiesSchema, PropertySchema, PropertyType};
+
 /// 配置条目信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigEntry {
@@ -18,6 +39,67 @@ pub struct ConfigEntry {
     pub value_type: String,
     pub default_value: String,
     pub category: String,
+    /// 枚举类型的可选值
+    #[serde(default)]
+    pub allowed_values: Vec<String>,
+    /// 整数类型的下限
+    #[serde(default)]
+    pub min: Option<i64>,
+    /// 整数类型的上限
+    #[serde(default)]
+    pub max: Option<i64>,
+    /// 修改后是否需要重启服务器
+    #[serde(default)]
+    pub restart_required: bool,
+    /// 该版本的字段模式中没有此键（拼写错误或插件 / 旧版本遗留）
+    #[serde(default)]
+    pub unknown: bool,
+    /// 当前值不符合字段模式时的原因
+    #[serde(default)]
+    pub invalid_reason: Option<String>,
+}
+
+impl ConfigEntry {
+    /// 按字段模式标注一个键值
+    fn annotate(schema: &PropertiesSchema, key: &str, value: &str) -> Self {
+        let Some(property) = schema.get(key) else {
+            return Self {
+                key: key.to_string(),
+                value: value.to_string(),
+                description: String::new(),
+                value_type: PropertyType::String.as_str().to_string(),
+                default_value: String::new(),
+                category: "other".to_string(),
+                allowed_values: Vec::new(),
+                min: None,
+                max: None,
+                restart_required: true,
+                unknown: true,
+                invalid_reason: None,
+            };
+        };
+        let (allowed_values, min, max) = match property.value_type {
+            PropertyType::Enum(options) | PropertyType::IndexedEnum(options) => {
+                (options.iter().map(|option| option.to_string()).collect(), None, None)
+            }
+            PropertyType::Integer { min, max } => (Vec::new(), Some(min), Some(max)),
+            PropertyType::Boolean | PropertyType::String => (Vec::new(), None, None),
+        };
+        Self {
+            key: key.to_string(),
+            value: value.to_string(),
+            description: property.description.to_string(),
+            value_type: property.value_type.as_str().to_string(),
+            default_value: property.default_value.to_string(),
+            category: property.category.to_string(),
+            allowed_values,
+            min,
+            max,
+            restart_required: property.restart_required,
+            unknown: false,
+            invalid_reason: property.validate(value).err(),
+        }
+    }
 }
 
 /// 服务器配置文件结构
@@ -25,18 +107,67 @@ pub struct ConfigEntry {
 pub struct ServerProperties {
     pub entries: Vec<ConfigEntry>,
     pub raw: BTreeMap<String, String>,
+    /// 字段模式中不存在的键
+    #[serde(default)]
+    pub unknown_keys: Vec<String>,
+}
+
+impl ServerProperties {
+    fn annotated(schema: &PropertiesSchema, raw: BTreeMap<String, String>) -> Self {
+        let entries: Vec<ConfigEntry> = raw
+            .iter()
+            .map(|(key, value)| ConfigEntry::annotate(schema, key, value))
+            .collect();
+        let unknown_keys = entries
+            .iter()
+            .filter(|entry| entry.unknown)
+            .map(|entry| entry.key.clone())
+            .collect();
+        Self { entries, raw, unknown_keys }
+    }
 }
 
 /// server.properties 文件管理器
 pub struct ServerPropertiesManager {
     server_path: std::path::PathBuf,
+    schema: PropertiesSchema,
 }
 
 impl ServerPropertiesManager {
+    /// 按最新版本的字段模式管理 server.properties
     pub fn new(server_path: impl AsRef<Path>) -> Self {
         Self {
             server_path: server_path.as_ref().to_path_buf(),
+            schema: PropertiesSchema::default(),
+        }
+    }
+
+    /// 按指定游戏版本的字段模式管理 server.properties（版本无法识别时按最新版本）
+    pub fn with_game_version(mut self, game_version: Option<&str>) -> Self {
+        self.schema = PropertiesSchema::for_version(game_version);
+        self
+    }
+
+    /// 校验待写入的值：已知字段必须符合字段模式，未知字段仅记录日志
+    fn validate(
+        schema: &PropertiesSchema,
+        values: &BTreeMap<String, String>,
+    ) -> Result<(), ServerPropertiesError> {
+        for (key, value) in values {
+            if value.contains(['\n', '\r']) {
+                return Err(ServerPropertiesError::InvalidValue {
+                    key: key.clone(),
+                    reason: format!("{key} 不能包含换行"),
+                });
+            }
+            match schema.get(key) {
+                Some(property) => property.validate(value).map_err(|reason| {
+                    ServerPropertiesError::InvalidValue { key: key.clone(), reason }
+                })?,
+                None => debug!("server.properties 写入未知字段: {}", key),
+            }
         }
+        Ok(())
     }
 
     /// 获取 server.properties 文件路径
@@ -50,31 +181,20 @@ impl ServerPropertiesManager {
 
         if !file_path.exists() {
             debug!("server.properties 文件不存在: {:?}", file_path);
-            return Ok(ServerProperties { entries: vec![], raw: BTreeMap::new() });
+            return Ok(ServerProperties::annotated(&self.schema, BTreeMap::new()));
         }
 
         let content = fs::read_to_string(&file_path)?;
         let raw = parse_properties(&content)?;
 
-        // 将原始配置转换为条目列表
-        let entries = raw
-            .iter()
-            .map(|(key, value)| ConfigEntry {
-                key: key.clone(),
-                value: value.clone(),
-                description: String::new(),
-                value_type: "string".to_string(),
-                default_value: String::new(),
-                category: "general".to_string(),
-            })
-            .collect();
-
         debug!("读取 server.properties 成功，共 {} 项", raw.len());
-        Ok(ServerProperties { entries, raw })
+        // 将原始配置转换为按字段模式标注的条目列表
+        Ok(ServerProperties::annotated(&self.schema, raw))
     }
 
     /// 写入服务器配置文件
     pub fn write(&self, values: &BTreeMap<String, String>) -> Result<(), ServerPropertiesError> {
+        Self::validate(&self.schema, values)?;
         let file_path = self.properties_file();
 
         // 如果文件存在，先读取现有内容以保留注释和顺序
@@ -119,22 +239,13 @@ impl ServerPropertiesManager {
         Ok(())
     }
 
-    /// 解析原始文本
-    pub fn parse_source(source: &str) -> Result<ServerProperties, ServerPropertiesError> {
+    /// 解析原始文本，按 `game_version` 的字段模式标注（缺省按最新版本）
+    pub fn parse_source(
+        source: &str,
+        game_version: Option<&str>,
+    ) -> Result<ServerProperties, ServerPropertiesError> {
         let raw = parse_properties(source)?;
-        let entries = raw
-            .iter()
-            .map(|(key, value)| ConfigEntry {
-                key: key.clone(),
-                value: value.clone(),
-                description: String::new(),
-                value_type: "string".to_string(),
-                default_value: String::new(),
-                category: "general".to_string(),
-            })
-            .collect();
-
-        Ok(ServerProperties { entries, raw })
+        Ok(ServerProperties::annotated(&PropertiesSchema::for_version(game_version), raw))
     }
 
     /// 预览写入后的文本
@@ -142,6 +253,7 @@ impl ServerPropertiesManager {
         &self,
         values: &BTreeMap<String, String>,
     ) -> Result<String, ServerPropertiesError> {
+        Self::validate(&self.schema, values)?;
         let file_path = self.properties_file();
 
         let mut lines = if file_path.exists() {
@@ -164,11 +276,13 @@ impl ServerPropertiesManager {
         Ok(lines.join("\n"))
     }
 
-    /// 从源码预览写入
+    /// 从源码预览写入，按 `game_version` 的字段模式校验（缺省按最新版本）
     pub fn preview_write_from_source(
         source: &str,
         values: &BTreeMap<String, String>,
+        game_version: Option<&str>,
     ) -> Result<String, ServerPropertiesError> {
+        Self::validate(&PropertiesSchema::for_version(game_version), values)?;
         let mut lines = source.lines().map(|s| s.to_string()).collect::<Vec<_>>();
 
         for (key, value) in values {
@@ -231,6 +345,9 @@ pub enum ServerPropertiesError {
 
     #[error("解析错误: {0}")]
     Parse(String),
+
+    #[error("配置值无效: {reason}")]
+    InvalidValue { key: String, reason: String },
 }
 
 #[cfg(test)]
@@ -248,4 +365,56 @@ max-players=20
         assert_eq!(result.get("server-port"), Some(&"25565".to_string()));
         assert_eq!(result.get("max-players"), Some(&"20".to_string()));
     }
+
+    #[test]
+    fn annotates_entries_and_flags_unknown_keys() {
+        let properties = ServerPropertiesManager::parse_source(
+            "max-players=abc\nonline-mode=true\nmax-playrs=10\n",
+            None,
+        )
+        .unwrap();
+        let entry = |key: &str| {
+            properties
+                .entries
+                .iter()
+                .find(|entry| entry.key == key)
+                .unwrap()
+        };
+
+        assert_eq!(entry("online-mode").value_type, "boolean");
+        assert_eq!(entry("online-mode").category, "player");
+        assert!(entry("online-mode").invalid_reason.is_none());
+        assert_eq!(entry("max-players").min, Some(0));
+        assert!(entry("max-players").invalid_reason.is_some());
+        assert!(entry("max-playrs").unknown);
+        assert_eq!(properties.unknown_keys, ["max-playrs"]);
+    }
+
+    #[test]
+    fn rejects_invalid_values_on_write() {
+        let temp = tempfile::tempdir().unwrap();
+        let manager = ServerPropertiesManager::new(temp.path());
+        let values = BTreeMap::from([("max-players".to_string(), "abc".to_string())]);
+        assert!(matches!(
+            manager.preview_write(&values),
+            Err(ServerPropertiesError::InvalidValue { key, .. }) if key == "max-players"
+        ));
+        assert!(manager.write(&values).is_err());
+        assert!(!temp.path().join("server.properties").exists());
+
+        let legacy = ServerPropertiesManager::new(temp.path()).with_game_version(Some("1.12.2"));
+        let values = BTreeMap::from([("gamemode".to_string(), "creative".to_string())]);
+        assert!(legacy.write(&values).is_err());
+        manager.write(&values).unwrap();
+
+        let values = BTreeMap::from([("custom-plugin-key".to_string(), "x".to_string())]);
+        manager.write(&values).unwrap();
+        assert!(
+            manager
+                .read()
+                .unwrap()
+                .unknown_keys
+                .contains(&"custom-plugin-key".to_string())
+        );
+    }
 }
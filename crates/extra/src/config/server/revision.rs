@@ -0,0 +1,473 @@
+//! 配置文件修订历史
+//!
+//! 每次通过 Sea Lantern 编辑服务器配置文件时保存一份完整快照，连同操作主体、
+//! 时间与相对上一修订的统一差异。编辑前发现文件已被外部改动（手工编辑、服务器
+//! 自身改写）时，先补记一条外部修订，使差异始终能追溯到真实的改动来源。
+//! 修订保存在服务器目录下的 SQLite 库中，每个文件保留最近
+//! [`MAX_REVISIONS_PER_FILE`] 条。
+
+use std::path::{Path, PathBuf};
+
+use sealantern_core::app_plugin::ExecutionPrincipal;
+use sealantern_infra::fs::{FsError, SafeRelativePath, write_atomic};
+use sealantern_infra::persistence::{PersistenceError, SqlValue, SqliteDatabase};
+use serde::{Deserialize, Serialize};
+
+use super::diff::unified_diff;
+
+/// 修订库文件名（存放在服务器目录下）。
+pub const REVISION_DATABASE_FILE: &str = "sea_lantern_config_history.sqlite";
+
+/// 每个配置文件保留的修订条数上限。
+pub const MAX_REVISIONS_PER_FILE: i64 = 100;
+
+/// 外部改动的主体类型（非经 Sea Lantern 的编辑）。
+pub const EXTERNAL_PRINCIPAL_KIND: &str = "external";
+
+/// 修订库建表语句（幂等）。
+const REVISION_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS config_revisions (\
+     id INTEGER PRIMARY KEY AUTOINCREMENT,\
+     file TEXT NOT NULL,\
+     principal_kind TEXT NOT NULL,\
+     principal_id TEXT NOT NULL,\
+     created_at INTEGER NOT NULL,\
+     snapshot TEXT NOT NULL,\
+     diff TEXT NOT NULL,\
+     restored_from INTEGER\
+ );\
+ CREATE INDEX IF NOT EXISTS config_revisions_file ON config_revisions (file, id);";
+
+const SUMMARY_COLUMNS: &str =
+    "id, file, principal_kind, principal_id, created_at, diff, restored_from";
+
+/// 修订摘要（不含快照）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigRevisionSummary {
+    pub id: i64,
+    /// 相对服务器目录的文件路径
+    pub file: String,
+    /// 操作主体类型：`built_in_host`、`plugin`、`agent_session` 或 `external`
+    pub principal_kind: String,
+    pub principal_id: String,
+    /// 记录时刻（Unix 秒）
+    pub created_at: i64,
+    /// 相对同一文件上一修订的统一差异
+    pub diff: String,
+    /// 由恢复操作产生时，被恢复的修订 ID
+    pub restored_from: Option<i64>,
+}
+
+/// 含完整快照的修订。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigRevision {
+    #[serde(flatten)]
+    pub summary: ConfigRevisionSummary,
+    pub snapshot: String,
+}
+
+/// 打开（或创建）服务器修订库，并确保表结构存在。
+pub async fn open_revision_database(
+    server_path: &Path,
+) -> Result<SqliteDatabase, PersistenceError> {
+    SqliteDatabase::open_with_schema(server_path.join(REVISION_DATABASE_FILE), REVISION_SCHEMA)
+        .await
+}
+
+/// 读取配置文件当前内容作为编辑前快照；文件不存在或不可读时返回 `None`。
+pub async fn read_config_snapshot(server_path: &Path, file: &str) -> Option<String> {
+    let path = resolve_config_file(server_path, file).ok()?;
+    tokio::fs::read_to_string(path).await.ok()
+}
+
+/// 记录一次编辑，返回新修订；内容与上一修订相同时不记录。
+///
+/// `before` 为编辑前读取的文件内容，与最新修订不一致时先补记一条外部修订。
+pub async fn record_revision(
+    database: &SqliteDatabase,
+    file: &str,
+    principal: &ExecutionPrincipal,
+    before: Option<String>,
+    after: String,
+    created_at: i64,
+) -> Result<Option<ConfigRevisionSummary>, ConfigRevisionError> {
+    record(database, file, principal, before, after, created_at, None).await
+}
+
+async fn record(
+    database: &SqliteDatabase,
+    file: &str,
+    principal: &ExecutionPrincipal,
+    before: Option<String>,
+    after: String,
+    created_at: i64,
+    restored_from: Option<i64>,
+) -> Result<Option<ConfigRevisionSummary>, ConfigRevisionError> {
+    SafeRelativePath::parse(file).map_err(|_| ConfigRevisionError::InvalidPath(file.to_owned()))?;
+    let file = file.to_owned();
+    let kind = principal.kind().to_owned();
+    let id = principal.id().to_owned();
+    let revision = database
+        .write("record config revision", move |transaction| {
+            let mut previous: Option<String> = transaction
+                .query_row(
+                    "SELECT snapshot FROM config_revisions WHERE file = ?1 ORDER BY id DESC LIMIT 1",
+                    [&file],
+                    |row| row.get(0),
+                )
+                .map(Some)
+                .or_else(|error| match error {
+                    rusqlite::Error::QueryReturnedNoRows => Ok(None),
+                    other => Err(other),
+                })?;
+
+            if let Some(before) = before
+                && previous.as_ref() != Some(&before)
+            {
+                // 首次编辑时把原始内容记为基线，之后则是两次编辑之间的外部改动
+                if previous.is_some() || before != after {
+                    insert_revision(
+                        transaction,
+                        &file,
+                        (EXTERNAL_PRINCIPAL_KIND, ""),
+                        created_at,
+                        previous.as_deref().unwrap_or_default(),
+                        &before,
+                        None,
+                    )?;
+                }
+                previous = Some(before);
+            }
+            if previous.as_deref() == Some(after.as_str()) {
+                return Ok(None);
+            }
+            let revision = insert_revision(
+                transaction,
+                &file,
+                (&kind, &id),
+                created_at,
+                previous.as_deref().unwrap_or_default(),
+                &after,
+                restored_from,
+            )?;
+            prune(transaction, &file)?;
+            Ok(Some(revision))
+        })
+        .await?;
+    Ok(revision)
+}
+
+/// 按时间倒序列出修订；`file` 提供时只列出该文件的修订。
+pub async fn list_revisions(
+    database: &SqliteDatabase,
+    file: Option<&str>,
+    limit: i64,
+) -> Result<Vec<ConfigRevisionSummary>, ConfigRevisionError> {
+    if limit <= 0 {
+        return Err(ConfigRevisionError::Persistence(PersistenceError::InvalidInput {
+            reason: format!("limit must be positive, got {limit}"),
+        }));
+    }
+    let revisions =
+        match file {
+            Some(file) => {
+                database
+                    .query(
+                        format!(
+                            "SELECT {SUMMARY_COLUMNS} FROM config_revisions WHERE file = ?1 \
+                         ORDER BY id DESC LIMIT ?2"
+                        ),
+                        [SqlValue::Text(file.to_owned()), SqlValue::Integer(limit)],
+                        map_summary,
+                    )
+                    .await?
+            }
+            None => database
+                .query(
+                    format!(
+                        "SELECT {SUMMARY_COLUMNS} FROM config_revisions ORDER BY id DESC LIMIT ?1"
+                    ),
+                    [SqlValue::Integer(limit)],
+                    map_summary,
+                )
+                .await?,
+        };
+    Ok(revisions)
+}
+
+/// 读取含快照的修订。
+pub async fn find_revision(
+    database: &SqliteDatabase,
+    id: i64,
+) -> Result<Option<ConfigRevision>, ConfigRevisionError> {
+    Ok(database
+        .query_one(
+            format!("SELECT {SUMMARY_COLUMNS}, snapshot FROM config_revisions WHERE id = ?1"),
+            [SqlValue::Integer(id)],
+            |row| {
+                Ok(ConfigRevision {
+                    summary: map_summary(row)?,
+                    snapshot: row.get(7)?,
+                })
+            },
+        )
+        .await?)
+}
+
+/// 比较任意两条修订的快照，返回统一差异。
+pub async fn diff_revisions(
+    database: &SqliteDatabase,
+    from: i64,
+    to: i64,
+) -> Result<String, ConfigRevisionError> {
+    let from = find_revision(database, from)
+        .await?
+        .ok_or(ConfigRevisionError::NotFound(from))?;
+    let to = find_revision(database, to)
+        .await?
+        .ok_or(ConfigRevisionError::NotFound(to))?;
+    Ok(unified_diff(
+        &from.snapshot,
+        &to.snapshot,
+        &format!("{}@{}", from.summary.file, from.summary.id),
+        &format!("{}@{}", to.summary.file, to.summary.id),
+    ))
+}
+
+/// 将文件恢复为指定修订的快照（原子写入），并记录一条恢复修订。
+///
+/// 当前内容已与该快照相同时不写入，返回 `None`。
+pub async fn restore_revision(
+    database: &SqliteDatabase,
+    server_path: &Path,
+    id: i64,
+    principal: &ExecutionPrincipal,
+    created_at: i64,
+) -> Result<Option<ConfigRevisionSummary>, ConfigRevisionError> {
+    let revision = find_revision(database, id)
+        .await?
+        .ok_or(ConfigRevisionError::NotFound(id))?;
+    let file = revision.summary.file;
+    let before = read_config_snapshot(server_path, &file).await;
+    if before.as_ref() == Some(&revision.snapshot) {
+        return Ok(None);
+    }
+    write_atomic(resolve_config_file(server_path, &file)?, revision.snapshot.as_bytes()).await?;
+
+    record(database, &file, principal, before, revision.snapshot, created_at, Some(id)).await
+}
+
+fn resolve_config_file(server_path: &Path, file: &str) -> Result<PathBuf, ConfigRevisionError> {
+    let relative = SafeRelativePath::parse(file)
+        .map_err(|_| ConfigRevisionError::InvalidPath(file.to_owned()))?;
+    Ok(server_path.join(relative))
+}
+
+fn insert_revision(
+    transaction: &rusqlite::Transaction<'_>,
+    file: &str,
+    (principal_kind, principal_id): (&str, &str),
+    created_at: i64,
+    previous: &str,
+    snapshot: &str,
+    restored_from: Option<i64>,
+) -> rusqlite::Result<ConfigRevisionSummary> {
+    let diff = unified_diff(previous, snapshot, &format!("a/{file}"), &format!("b/{file}"));
+    transaction.execute(
+        "INSERT INTO config_revisions \
+         (file, principal_kind, principal_id, created_at, snapshot, diff, restored_from) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        rusqlite::params![
+            file,
+            principal_kind,
+            principal_id,
+            created_at,
+            snapshot,
+            diff,
+            restored_from
+        ],
+    )?;
+    Ok(ConfigRevisionSummary {
+        id: transaction.last_insert_rowid(),
+        file: file.to_owned(),
+        principal_kind: principal_kind.to_owned(),
+        principal_id: principal_id.to_owned(),
+        created_at,
+        diff,
+        restored_from,
+    })
+}
+
+fn prune(transaction: &rusqlite::Transaction<'_>, file: &str) -> rusqlite::Result<()> {
+    transaction.execute(
+        "DELETE FROM config_revisions WHERE file = ?1 AND id NOT IN (\
+         SELECT id FROM config_revisions WHERE file = ?1 ORDER BY id DESC LIMIT ?2)",
+        rusqlite::params![file, MAX_REVISIONS_PER_FILE],
+    )?;
+    Ok(())
+}
+
+fn map_summary(row: &rusqlite::Row<'_>) -> rusqlite::Result<ConfigRevisionSummary> {
+    Ok(ConfigRevisionSummary {
+        id: row.get(0)?,
+        file: row.get(1)?,
+        principal_kind: row.get(2)?,
+        principal_id: row.get(3)?,
+        created_at: row.get(4)?,
+        diff: row.get(5)?,
+        restored_from: row.get(6)?,
+    })
+}
+
+/// 修订历史处理错误
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigRevisionError {
+    #[error("修订库错误: {0}")]
+    Persistence(#[from] PersistenceError),
+
+    #[error("写入失败: {0}")]
+    Write(#[from] FsError),
+
+    #[error("配置文件路径无效: {0}")]
+    InvalidPath(String),
+
+    #[error("修订不存在: {0}")]
+    NotFound(i64),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOST: ExecutionPrincipal = ExecutionPrincipal::BuiltInHost;
+
+    #[tokio::test]
+    async fn records_external_changes_and_skips_unchanged_writes() {
+        let directory = tempfile::tempdir().expect("临时目录应创建成功");
+        let database = open_revision_database(directory.path())
+            .await
+            .expect("修订库应初始化成功");
+
+        let first = record_revision(
+            &database,
+            "server.properties",
+            &HOST,
+            Some("online-mode=true\n".to_owned()),
+            "online-mode=false\n".to_owned(),
+            10,
+        )
+        .await
+        .expect("记录")
+        .expect("内容有变化");
+        assert_eq!(first.principal_kind, "built_in_host");
+        assert!(
+            first
+                .diff
+                .contains("-online-mode=true\n+online-mode=false\n")
+        );
+
+        let unchanged = record_revision(
+            &database,
+            "server.properties",
+            &HOST,
+            Some("online-mode=false\n".to_owned()),
+            "online-mode=false\n".to_owned(),
+            11,
+        )
+        .await
+        .expect("记录");
+        assert!(unchanged.is_none());
+
+        // 服务器自身改写过文件后再编辑
+        let plugin = ExecutionPrincipal::Plugin("example.plugin".to_owned());
+        record_revision(
+            &database,
+            "server.properties",
+            &plugin,
+            Some("online-mode=false\nmotd=x\n".to_owned()),
+            "online-mode=false\nmotd=y\n".to_owned(),
+            12,
+        )
+        .await
+        .expect("记录");
+
+        let revisions = list_revisions(&database, Some("server.properties"), 10)
+            .await
+            .expect("列表");
+        let authors: Vec<_> = revisions
+            .iter()
+            .map(|revision| revision.principal_kind.as_str())
+            .collect();
+        assert_eq!(
+            authors,
+            ["plugin", EXTERNAL_PRINCIPAL_KIND, "built_in_host", EXTERNAL_PRINCIPAL_KIND]
+        );
+        assert_eq!(
+            revisions[3].diff,
+            "--- a/server.properties\n+++ b/server.properties\n@@ -0,0 +1,1 @@\n+online-mode=true\n"
+        );
+        assert!(revisions[1].diff.contains("+motd=x\n"));
+        assert!(
+            list_revisions(&database, Some("bukkit.yml"), 10)
+                .await
+                .expect("列表")
+                .is_empty()
+        );
+        assert!(list_revisions(&database, None, 0).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn diffs_and_restores_revisions() {
+        let directory = tempfile::tempdir().expect("临时目录应创建成功");
+        let database = open_revision_database(directory.path())
+            .await
+            .expect("修订库应初始化成功");
+        let file = directory.path().join("spigot.yml");
+        std::fs::write(&file, "debug: true\n").expect("写入");
+
+        let first =
+            record_revision(&database, "spigot.yml", &HOST, None, "debug: false\n".to_owned(), 1)
+                .await
+                .expect("记录")
+                .expect("首条修订");
+        let second = record_revision(
+            &database,
+            "spigot.yml",
+            &HOST,
+            Some("debug: false\n".to_owned()),
+            "debug: true\n".to_owned(),
+            2,
+        )
+        .await
+        .expect("记录")
+        .expect("第二条修订");
+
+        assert_eq!(
+            diff_revisions(&database, first.id, second.id)
+                .await
+                .expect("差异"),
+            format!(
+                "--- spigot.yml@{}\n+++ spigot.yml@{}\n@@ -1,1 +1,1 @@\n-debug: false\n+debug: true\n",
+                first.id, second.id
+            )
+        );
+        assert!(matches!(
+            diff_revisions(&database, first.id, 999).await,
+            Err(ConfigRevisionError::NotFound(999))
+        ));
+
+        let restored = restore_revision(&database, directory.path(), first.id, &HOST, 3)
+            .await
+            .expect("恢复")
+            .expect("内容有变化");
+        assert_eq!(restored.restored_from, Some(first.id));
+        assert_eq!(std::fs::read_to_string(&file).expect("读取"), "debug: false\n");
+        assert!(
+            restore_revision(&database, directory.path(), first.id, &HOST, 4)
+                .await
+                .expect("恢复")
+                .is_none()
+        );
+        let latest = list_revisions(&database, None, 1).await.expect("列表");
+        assert_eq!(latest[0].restored_from, Some(first.id));
+    }
+}
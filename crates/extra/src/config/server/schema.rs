@@ -0,0 +1,363 @@
+//! server.properties 字段模式
+//!
+//! 按 Minecraft 版本区间描述原版 server.properties 的字段：类型、取值范围或枚举、
+//! 默认值、分类、说明以及修改后是否需要重启。同一键在不同版本的含义或默认值
+//! 可能不同（如 1.14 之前 `gamemode` / `difficulty` 只接受数字），因此以
+//! `[since, until)` 区间登记多条定义，按实例的游戏版本挑选生效的一条。
+
+use std::fmt;
+
+/// 可比较的 Minecraft 正式版版本号（`主.次.修订`）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GameVersion(pub u32, pub u32, pub u32);
+
+impl GameVersion {
+    /// 解析 `1.20.4`、`1.21` 这类正式版版本号；快照、预览版等无法比较的版本返回 `None`。
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut parts = value.trim().split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        let patch = match parts.next() {
+            Some(patch) => patch.parse().ok()?,
+            None => 0,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self(major, minor, patch))
+    }
+}
+
+impl fmt::Display for GameVersion {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}.{}.{}", self.0, self.1, self.2)
+    }
+}
+
+/// 字段值类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PropertyType {
+    Boolean,
+    /// 闭区间整数。
+    Integer {
+        min: i64,
+        max: i64,
+    },
+    /// 只能取列出的值之一（忽略大小写）。
+    Enum(&'static [&'static str]),
+    /// 列出的值之一或其序号（服务端对旧存档的数字写法保持兼容）。
+    IndexedEnum(&'static [&'static str]),
+    String,
+}
+
+impl PropertyType {
+    /// 写入 [`ConfigEntry::value_type`](super::ConfigEntry::value_type) 的类型名。
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Boolean => "boolean",
+            Self::Integer { .. } => "number",
+            Self::Enum(_) | Self::IndexedEnum(_) => "enum",
+            Self::String => "string",
+        }
+    }
+}
+
+/// 一个字段在某个版本区间内的定义。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PropertySchema {
+    pub key: &'static str,
+    pub value_type: PropertyType,
+    pub default_value: &'static str,
+    /// 与前端分类标签一致：network / player / game / world / performance / display / other。
+    pub category: &'static str,
+    pub description: &'static str,
+    /// 修改后需重启服务器才能生效；`false` 表示可经控制台命令在运行时同步。
+    pub restart_required: bool,
+    /// 首个包含该定义的版本（含）。
+    pub since: Option<GameVersion>,
+    /// 首个不再使用该定义的版本（不含）。
+    pub until: Option<GameVersion>,
+}
+
+impl PropertySchema {
+    fn applies_to(&self, version: GameVersion) -> bool {
+        self.since.is_none_or(|since| version >= since)
+            && self.until.is_none_or(|until| version < until)
+    }
+
+    /// 校验取值，失败时返回原因。
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        let value = value.trim();
+        match self.value_type {
+            // 服务端以 `Boolean.parseBoolean` 读取，不区分大小写。
+            PropertyType::Boolean
+                if value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false") =>
+            {
+                Ok(())
+            }
+            PropertyType::Boolean => Err(format!("{} 只能是 true 或 false", self.key)),
+            PropertyType::Integer { min, max } => match value.parse::<i64>() {
+                Ok(number) if (min..=max).contains(&number) => Ok(()),
+                Ok(_) => Err(format!("{} 必须在 {min} 到 {max} 之间", self.key)),
+                Err(_) => Err(format!("{} 需要填写整数", self.key)),
+            },
+            PropertyType::Enum(options) | PropertyType::IndexedEnum(options) => {
+                let indexed = matches!(self.value_type, PropertyType::IndexedEnum(_))
+                    && value
+                        .parse::<usize>()
+                        .is_ok_and(|index| index < options.len());
+                if indexed
+                    || options
+                        .iter()
+                        .any(|option| option.eq_ignore_ascii_case(value))
+                {
+                    Ok(())
+                } else {
+                    Err(format!("{} 只能是 {} 之一", self.key, options.join(" / ")))
+                }
+            }
+            PropertyType::String => Ok(()),
+        }
+    }
+}
+
+/// 某个游戏版本下生效的字段集合。
+#[derive(Debug, Clone)]
+pub struct PropertiesSchema {
+    version: Option<GameVersion>,
+    properties: Vec<&'static PropertySchema>,
+}
+
+impl PropertiesSchema {
+    /// 挑选 `game_version` 下生效的字段；版本缺省或无法解析时按最新版本处理。
+    pub fn for_version(game_version: Option<&str>) -> Self {
+        let version = game_version.and_then(GameVersion::parse);
+        let properties = PROPERTIES
+            .iter()
+            .filter(|schema| match version {
+                Some(version) => schema.applies_to(version),
+                None => schema.until.is_none(),
+            })
+            .collect();
+        Self { version, properties }
+    }
+
+    /// 模式对应的游戏版本；`None` 表示最新版本。
+    pub fn version(&self) -> Option<GameVersion> {
+        self.version
+    }
+
+    /// 查找字段定义；不在该版本模式中的键返回 `None`。
+    pub fn get(&self, key: &str) -> Option<&'static PropertySchema> {
+        self.properties
+            .iter()
+            .copied()
+            .find(|schema| schema.key == key)
+    }
+
+    /// 按登记顺序遍历字段定义。
+    pub fn iter(&self) -> impl Iterator<Item = &'static PropertySchema> + '_ {
+        self.properties.iter().copied()
+    }
+}
+
+impl Default for PropertiesSchema {
+    fn default() -> Self {
+        Self::for_version(None)
+    }
+}
+
+const PORT: PropertyType = PropertyType::Integer { min: 1, max: 65_535 };
+const NON_NEGATIVE: PropertyType = PropertyType::Integer { min: 0, max: i32::MAX as i64 };
+const GAMEMODES: &[&str] = &["survival", "creative", "adventure", "spectator"];
+const DIFFICULTIES: &[&str] = &["peaceful", "easy", "normal", "hard"];
+
+/// 登记一条跨全部版本、修改后需重启的定义。
+const fn property(
+    key: &'static str,
+    value_type: PropertyType,
+    default_value: &'static str,
+    category: &'static str,
+    description: &'static str,
+) -> PropertySchema {
+    PropertySchema {
+        key,
+        value_type,
+        default_value,
+        category,
+        description,
+        restart_required: true,
+        since: None,
+        until: None,
+    }
+}
+
+impl PropertySchema {
+    /// 定义自该版本起生效。
+    const fn since(mut self, major: u32, minor: u32, patch: u32) -> Self {
+        self.since = Some(GameVersion(major, minor, patch));
+        self
+    }
+
+    /// 定义自该版本起失效。
+    const fn until(mut self, major: u32, minor: u32, patch: u32) -> Self {
+        self.until = Some(GameVersion(major, minor, patch));
+        self
+    }
+
+    /// 可经控制台命令在运行时同步，修改后无需重启。
+    const fn live(mut self) -> Self {
+        self.restart_required = false;
+        self
+    }
+}
+
+/// 原版 Java 版 server.properties 字段表（按键名排序，同键按版本先后）。
+static PROPERTIES: &[PropertySchema] = &[
+    property("accepts-transfers", PropertyType::Boolean, "false", "network", "是否接受其他服务器经 transfer 数据包转移过来的玩家").since(1, 20, 5),
+    property("allow-flight", PropertyType::Boolean, "false", "player", "是否允许生存模式玩家飞行（关闭时会踢出疑似飞行的玩家）"),
+    property("allow-nether", PropertyType::Boolean, "true", "world", "是否允许玩家进入下界"),
+    property("announce-player-achievements", PropertyType::Boolean, "true", "other", "是否在聊天栏广播玩家获得的成就").until(1, 12, 0),
+    property("broadcast-console-to-ops", PropertyType::Boolean, "true", "other", "是否向在线管理员广播控制台命令的输出"),
+    property("broadcast-rcon-to-ops", PropertyType::Boolean, "true", "other", "是否向在线管理员广播 RCON 命令的输出"),
+    property("bug-report-link", PropertyType::String, "", "other", "断开连接界面中“报告服务器问题”的链接").since(1, 21, 0),
+    property("difficulty", PropertyType::Integer { min: 0, max: 3 }, "1", "game", "游戏难度（0 和平 / 1 简单 / 2 普通 / 3 困难），可用 /difficulty 运行时修改").until(1, 14, 0).live(),
+    property("difficulty", PropertyType::IndexedEnum(DIFFICULTIES), "easy", "game", "游戏难度，可用 /difficulty 运行时修改").since(1, 14, 0).live(),
+    property("enable-command-block", PropertyType::Boolean, "false", "game", "是否启用命令方块"),
+    property("enable-jmx-monitoring", PropertyType::Boolean, "false", "performance", "是否通过 JMX 暴露服务器 tick 时间等监控指标").since(1, 16, 0),
+    property("enable-query", PropertyType::Boolean, "false", "network", "是否开启 GameSpy4 查询协议"),
+    property("enable-rcon", PropertyType::Boolean, "false", "network", "是否开启 RCON 远程控制台"),
+    property("enable-status", PropertyType::Boolean, "true", "network", "是否在服务器列表中显示为在线").since(1, 16, 0),
+    property("enforce-secure-profile", PropertyType::Boolean, "true", "player", "是否要求玩家使用 Mojang 签名的公钥聊天").since(1, 19, 0),
+    property("enforce-whitelist", PropertyType::Boolean, "false", "player", "白名单重载时是否踢出不在白名单中的在线玩家").live(),
+    property("entity-broadcast-range-percentage", PropertyType::Integer { min: 10, max: 1000 }, "100", "performance", "实体对客户端可见距离的百分比").since(1, 16, 0),
+    property("force-gamemode", PropertyType::Boolean, "false", "game", "玩家加入时是否强制切换为默认游戏模式"),
+    property("function-permission-level", PropertyType::Integer { min: 1, max: 4 }, "2", "other", "数据包函数执行时的默认权限等级").since(1, 14, 4),
+    property("gamemode", PropertyType::Integer { min: 0, max: 3 }, "0", "game", "默认游戏模式（0 生存 / 1 创造 / 2 冒险 / 3 旁观）").until(1, 14, 0),
+    property("gamemode", PropertyType::IndexedEnum(GAMEMODES), "survival", "game", "默认游戏模式").since(1, 14, 0),
+    property("generate-structures", PropertyType::Boolean, "true", "world", "生成新区块时是否生成村庄等结构"),
+    property("generator-settings", PropertyType::String, "{}", "world", "自定义世界生成器设置"),
+    property("hardcore", PropertyType::Boolean, "false", "game", "是否为极限模式（玩家死亡后变为旁观者）"),
+    property("hide-online-players", PropertyType::Boolean, "false", "player", "是否在状态查询中隐藏在线玩家列表").since(1, 18, 0),
+    property("initial-disabled-packs", PropertyType::String, "", "world", "创建世界时默认禁用的数据包（逗号分隔）").since(1, 19, 3),
+    property("initial-enabled-packs", PropertyType::String, "vanilla", "world", "创建世界时默认启用的数据包（逗号分隔）").since(1, 19, 3),
+    property("level-name", PropertyType::String, "world", "world", "世界存档目录名"),
+    property("level-seed", PropertyType::String, "", "world", "世界种子，留空为随机"),
+    property("level-type", PropertyType::String, "default", "world", "世界类型（default / flat / largeBiomes / amplified 等）").until(1, 19, 0),
+    property("level-type", PropertyType::String, "minecraft:normal", "world", "世界预设（minecraft:normal / minecraft:flat / minecraft:large_biomes / minecraft:amplified 等）").since(1, 19, 0),
+    property("log-ips", PropertyType::Boolean, "true", "other", "玩家加入时是否在日志中记录其 IP").since(1, 20, 2),
+    property("max-build-height", PropertyType::Integer { min: 1, max: 256 }, "256", "world", "允许建造的最大高度").until(1, 17, 0),
+    property("max-chained-neighbor-updates", PropertyType::Integer { min: -1, max: i32::MAX as i64 }, "1000000", "performance", "连锁方块更新的最大次数，负数表示不限制").since(1, 19, 0),
+    property("max-players", NON_NEGATIVE, "20", "player", "最大在线玩家数"),
+    property("max-tick-time", PropertyType::Integer { min: -1, max: i64::MAX }, "60000", "performance", "单个 tick 超过该毫秒数时看门狗终止服务器，-1 表示禁用"),
+    property("max-world-size", PropertyType::Integer { min: 1, max: 29_999_984 }, "29999984", "world", "世界边界的最大半径（方块）"),
+    property("motd", PropertyType::String, "A Minecraft Server", "display", "服务器列表中显示的简介"),
+    property("network-compression-threshold", PropertyType::Integer { min: -1, max: i32::MAX as i64 }, "256", "network", "数据包超过该字节数时压缩，-1 表示不压缩"),
+    property("online-mode", PropertyType::Boolean, "true", "player", "是否通过 Mojang 验证玩家账号（正版验证）"),
+    property("op-permission-level", PropertyType::Integer { min: 0, max: 4 }, "4", "player", "/op 授予的默认权限等级"),
+    property("pause-when-empty-seconds", PropertyType::Integer { min: -1, max: i32::MAX as i64 }, "60", "performance", "无玩家在线多少秒后暂停世界运算，负数表示不暂停").since(1, 21, 2),
+    property("player-idle-timeout", NON_NEGATIVE, "0", "player", "玩家挂机多少分钟后踢出，0 表示不踢出"),
+    property("prevent-proxy-connections", PropertyType::Boolean, "false", "network", "是否拒绝经代理连接、IP 与 Mojang 验证不一致的玩家"),
+    property("pvp", PropertyType::Boolean, "true", "game", "是否允许玩家之间互相伤害"),
+    property("query.port", PORT, "25565", "network", "GameSpy4 查询协议监听的 UDP 端口"),
+    property("rate-limit", NON_NEGATIVE, "0", "network", "每秒允许客户端发送的最大数据包数，0 表示不限制"),
+    property("rcon.password", PropertyType::String, "", "network", "RCON 密码，为空时 RCON 不会启动"),
+    property("rcon.port", PORT, "25575", "network", "RCON 监听的 TCP 端口"),
+    property("region-file-compression", PropertyType::Enum(&["deflate", "lz4", "none"]), "deflate", "performance", "区域文件的压缩算法").since(1, 20, 5),
+    property("require-resource-pack", PropertyType::Boolean, "false", "display", "是否要求玩家必须接受服务器资源包").since(1, 17, 0),
+    property("resource-pack", PropertyType::String, "", "display", "服务器资源包的下载地址"),
+    property("resource-pack-id", PropertyType::String, "", "display", "服务器资源包的 UUID").since(1, 20, 3),
+    property("resource-pack-prompt", PropertyType::String, "", "display", "提示玩家下载资源包时显示的文字").since(1, 17, 0),
+    property("resource-pack-sha1", PropertyType::String, "", "display", "服务器资源包的 SHA-1 校验值"),
+    property("server-ip", PropertyType::String, "", "network", "服务器绑定的 IP，留空表示监听全部地址"),
+    property("server-port", PORT, "25565", "network", "服务器监听的 TCP 端口"),
+    property("simulation-distance", PropertyType::Integer { min: 3, max: 32 }, "10", "performance", "实体与方块运算的区块半径").since(1, 18, 0),
+    property("snooper-enabled", PropertyType::Boolean, "true", "other", "是否向 Mojang 发送匿名使用数据").until(1, 18, 0),
+    property("spawn-animals", PropertyType::Boolean, "true", "game", "是否生成动物").until(1, 21, 2),
+    property("spawn-monsters", PropertyType::Boolean, "true", "game", "是否生成敌对生物"),
+    property("spawn-npcs", PropertyType::Boolean, "true", "game", "是否生成村民").until(1, 21, 2),
+    property("spawn-protection", NON_NEGATIVE, "16", "world", "出生点保护半径（方块），0 表示不保护"),
+    property("sync-chunk-writes", PropertyType::Boolean, "true", "performance", "是否同步写入区块文件").since(1, 16, 0),
+    property("text-filtering-config", PropertyType::String, "", "other", "聊天文本过滤服务的配置").since(1, 17, 0),
+    property("use-native-transport", PropertyType::Boolean, "true", "performance", "是否在 Linux 上使用 epoll 优化网络"),
+    property("view-distance", PropertyType::Integer { min: 2, max: 32 }, "10", "performance", "向客户端发送的区块半径").until(1, 18, 0),
+    property("view-distance", PropertyType::Integer { min: 3, max: 32 }, "10", "performance", "向客户端发送的区块半径").since(1, 18, 0),
+    property("white-list", PropertyType::Boolean, "false", "player", "是否启用白名单，可用 /whitelist on|off 运行时切换").live(),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_definitions_by_version() {
+        let legacy = PropertiesSchema::for_version(Some("1.12.2"));
+        assert_eq!(legacy.get("gamemode").unwrap().default_value, "0");
+        assert!(legacy.get("gamemode").unwrap().validate("1").is_ok());
+        assert!(
+            legacy
+                .get("gamemode")
+                .unwrap()
+                .validate("creative")
+                .is_err()
+        );
+        assert!(legacy.get("simulation-distance").is_none());
+        assert!(legacy.get("snooper-enabled").is_some());
+
+        let modern = PropertiesSchema::for_version(Some("1.21"));
+        assert_eq!(modern.get("gamemode").unwrap().default_value, "survival");
+        assert!(modern.get("gamemode").unwrap().validate("Creative").is_ok());
+        assert!(modern.get("gamemode").unwrap().validate("1").is_ok());
+        assert!(modern.get("gamemode").unwrap().validate("4").is_err());
+        assert!(modern.get("simulation-distance").is_some());
+        assert!(modern.get("snooper-enabled").is_none());
+
+        let latest = PropertiesSchema::default();
+        assert!(latest.get("spawn-animals").is_none());
+        assert_eq!(latest.version(), None);
+        assert_eq!(PropertiesSchema::for_version(Some("24w14a")).version(), None);
+    }
+
+    #[test]
+    fn every_version_has_at_most_one_definition_per_key() {
+        for version in
+            ["1.8.9", "1.12.2", "1.14.4", "1.16.5", "1.18.2", "1.19.4", "1.20.6", "1.21.4"]
+        {
+            let schema = PropertiesSchema::for_version(Some(version));
+            let mut keys: Vec<_> = schema.iter().map(|property| property.key).collect();
+            let total = keys.len();
+            keys.dedup();
+            assert_eq!(keys.len(), total, "{version} 存在重复定义");
+        }
+    }
+
+    #[test]
+    fn validates_values() {
+        let schema = PropertiesSchema::default();
+        let max_players = schema.get("max-players").unwrap();
+        assert!(max_players.validate("20").is_ok());
+        assert!(max_players.validate("abc").is_err());
+        assert!(max_players.validate("-1").is_err());
+        assert!(
+            schema
+                .get("server-port")
+                .unwrap()
+                .validate("70000")
+                .is_err()
+        );
+        let online_mode = schema.get("online-mode").unwrap();
+        assert!(online_mode.validate("yes").is_err());
+        for value in ["TRUE", "False", "tRuE"] {
+            assert!(online_mode.validate(value).is_ok(), "{value}");
+        }
+        assert!(schema.get("motd").unwrap().validate("任意文本").is_ok());
+    }
+
+    #[test]
+    fn parses_game_versions() {
+        assert_eq!(GameVersion::parse("1.20.4"), Some(GameVersion(1, 20, 4)));
+        assert_eq!(GameVersion::parse("1.21"), Some(GameVersion(1, 21, 0)));
+        assert_eq!(GameVersion::parse("1.20.5-pre1"), None);
+        assert!(GameVersion(1, 9, 0) < GameVersion(1, 10, 0));
+    }
+}
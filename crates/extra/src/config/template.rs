@@ -0,0 +1,176 @@
+//! 实例配置模板存储
+//!
+//! 模板以 JSON 文件保存在应用数据目录中；校验规则由
+//! [`InstanceTemplate::normalize_and_validate`] 提供，本模块只负责持久化与 ID 分配。
+
+use std::fmt;
+use std::path::PathBuf;
+
+use sealantern_core::instance::{InstanceTemplate, TemplateError};
+use sealantern_infra::fs::FsError;
+use sealantern_infra::persistence::ConfigFile;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// 模板持久化文件的根对象。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstanceTemplateList {
+    pub templates: Vec<InstanceTemplate>,
+}
+
+/// 模板存储错误。
+#[derive(Debug)]
+pub enum InstanceTemplateStoreError {
+    Storage(FsError),
+    NotFound(String),
+    Invalid(TemplateError),
+}
+
+impl fmt::Display for InstanceTemplateStoreError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Storage(error) => write!(formatter, "template storage failed: {error}"),
+            Self::NotFound(id) => write!(formatter, "instance template not found: {id}"),
+            Self::Invalid(error) => write!(formatter, "invalid instance template: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for InstanceTemplateStoreError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Storage(error) => Some(error),
+            Self::Invalid(error) => Some(error),
+            Self::NotFound(_) => None,
+        }
+    }
+}
+
+impl From<FsError> for InstanceTemplateStoreError {
+    fn from(error: FsError) -> Self {
+        Self::Storage(error)
+    }
+}
+
+/// 基于 JSON 文件的模板存储。
+pub struct InstanceTemplateStore {
+    config: ConfigFile<InstanceTemplateList>,
+}
+
+impl InstanceTemplateStore {
+    /// 从 JSON 文件加载模板；文件不存在时创建空列表。
+    pub async fn load(path: impl Into<PathBuf>) -> Result<Self, InstanceTemplateStoreError> {
+        let config = ConfigFile::load_or_create(path, InstanceTemplateList::default()).await?;
+        Ok(Self { config })
+    }
+
+    /// 按保存顺序返回全部模板。
+    pub fn templates(&self) -> &[InstanceTemplate] {
+        &self.config.get().templates
+    }
+
+    /// 按 ID 查找模板。
+    pub fn get(&self, id: &str) -> Option<&InstanceTemplate> {
+        self.templates().iter().find(|template| template.id == id)
+    }
+
+    /// 校验并保存模板：ID 为空时新建并分配 ID，否则覆盖同 ID 的模板。
+    pub async fn save(
+        &mut self,
+        mut template: InstanceTemplate,
+    ) -> Result<InstanceTemplate, InstanceTemplateStoreError> {
+        template
+            .normalize_and_validate()
+            .map_err(InstanceTemplateStoreError::Invalid)?;
+        template.id = template.id.trim().to_owned();
+
+        let previous = self.config.get().clone();
+        if template.id.is_empty() {
+            template.id = Uuid::new_v4().to_string();
+            self.config
+                .update(|list| list.templates.push(template.clone()));
+        } else {
+            let index = self
+                .templates()
+                .iter()
+                .position(|existing| existing.id == template.id)
+                .ok_or_else(|| InstanceTemplateStoreError::NotFound(template.id.clone()))?;
+            self.config
+                .update(|list| list.templates[index] = template.clone());
+        }
+        self.persist_or_restore(previous).await?;
+        Ok(template)
+    }
+
+    /// 删除模板。
+    pub async fn delete(&mut self, id: &str) -> Result<(), InstanceTemplateStoreError> {
+        let previous = self.config.get().clone();
+        self.config
+            .update(|list| list.templates.retain(|template| template.id != id));
+        if self.templates().len() == previous.templates.len() {
+            return Err(InstanceTemplateStoreError::NotFound(id.to_owned()));
+        }
+        self.persist_or_restore(previous).await
+    }
+
+    async fn persist_or_restore(
+        &mut self,
+        previous: InstanceTemplateList,
+    ) -> Result<(), InstanceTemplateStoreError> {
+        if let Err(error) = self.config.save(false).await {
+            self.config.set(previous);
+            return Err(error.into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn template(name: &str) -> InstanceTemplate {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "launch": { "max_memory_mib": 4096, "port": { "kind": "sequential", "start": 30000 } },
+            "properties": { "max-players": "50" }
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn saves_updates_and_deletes_templates() {
+        let directory = tempdir().unwrap();
+        let path = directory.path().join("templates.json");
+        let mut store = InstanceTemplateStore::load(&path).await.unwrap();
+
+        let mut saved = store.save(template(" 活动服 ")).await.unwrap();
+        assert!(!saved.id.is_empty());
+        assert_eq!(saved.name, "活动服");
+
+        saved.description = "夏季活动".to_owned();
+        store.save(saved.clone()).await.unwrap();
+        let reloaded = InstanceTemplateStore::load(&path).await.unwrap();
+        assert_eq!(reloaded.templates(), [saved.clone()]);
+
+        let mut unknown = template("other");
+        unknown.id = "missing".to_owned();
+        assert!(matches!(
+            store.save(unknown).await,
+            Err(InstanceTemplateStoreError::NotFound(_))
+        ));
+        assert!(matches!(
+            store.save(template(" ")).await,
+            Err(InstanceTemplateStoreError::Invalid(TemplateError::InvalidName))
+        ));
+
+        store.delete(&saved.id).await.unwrap();
+        assert!(store.templates().is_empty());
+        assert!(matches!(
+            store.delete(&saved.id).await,
+            Err(InstanceTemplateStoreError::NotFound(_))
+        ));
+    }
+}
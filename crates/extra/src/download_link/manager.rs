@@ -3,7 +3,8 @@
 use super::{BaseDownloadLinks, DownloadLink, TypeDownloadLinks};
 use sealantern_infra::download::fetch_to_bytes;
 use serde_json::Value;
-use tokio::sync::{Mutex, OnceCell};
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
 
 /// 下载链接配置 URL
 const DOWNLOAD_LINK_LIST_URL: &str = "https://cnb.cool/SeaLantern-studio/ServerCore-Mirror/-/releases/download/26.02.27/jar_lfs_links.json";
@@ -30,27 +31,31 @@ impl std::fmt::Display for LinkError {
 
 impl std::error::Error for LinkError {}
 
-static DOWNLOAD_LINKS: OnceCell<BaseDownloadLinks> = OnceCell::const_new();
+static DOWNLOAD_LINKS: RwLock<Option<Arc<BaseDownloadLinks>>> = RwLock::const_new(None);
 static INIT_LOCK: Mutex<()> = Mutex::const_new(());
 
 /// 下载链接管理器
 pub struct LinkManager;
 
 impl LinkManager {
-    /// 获取下载链接数据（懒加载）
-    pub async fn get() -> Result<&'static BaseDownloadLinks, LinkError> {
-        if DOWNLOAD_LINKS.get().is_none() {
-            let _guard = INIT_LOCK.lock().await;
-            // 双重检查：在锁内再次确认
-            if DOWNLOAD_LINKS.get().is_none() {
-                let links = Self::init().await?;
-                DOWNLOAD_LINKS.set(links).ok();
-            }
+    /// 获取下载链接数据（懒加载，成功后保留到 [`invalidate`](Self::invalidate)）
+    pub async fn get() -> Result<Arc<BaseDownloadLinks>, LinkError> {
+        if let Some(links) = DOWNLOAD_LINKS.read().await.as_ref() {
+            return Ok(Arc::clone(links));
+        }
+        let _guard = INIT_LOCK.lock().await;
+        // 双重检查：在锁内再次确认
+        if let Some(links) = DOWNLOAD_LINKS.read().await.as_ref() {
+            return Ok(Arc::clone(links));
         }
+        let links = Arc::new(Self::init().await?);
+        *DOWNLOAD_LINKS.write().await = Some(Arc::clone(&links));
+        Ok(links)
+    }
 
-        DOWNLOAD_LINKS
-            .get()
-            .ok_or_else(|| LinkError::Config("download links not initialized".to_string()))
+    /// 丢弃已加载的配置，下次查询时重新拉取。
+    pub async fn invalidate() {
+        *DOWNLOAD_LINKS.write().await = None;
     }
 
     /// 从远程加载下载链接配置
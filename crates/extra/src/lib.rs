@@ -2,6 +2,7 @@
 
 pub mod app_plugin;
 pub mod backup;
+pub mod catalog;
 pub mod config;
 pub mod download_link;
 pub mod java;
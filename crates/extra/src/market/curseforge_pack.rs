@@ -0,0 +1,291 @@
+//! CurseForge 客户端整合包的服务端安装。
+//!
+//! [`install_curseforge_pack`] 解压整合包后按 `manifest.json` 的文件 ID 经
+//! [`Fetcher::files_by_ids`] 解析下载地址，跳过客户端专用模组，把其余文件下载并校验后
+//! 放入 `mods/`，最后应用清单声明的覆盖目录。与 `.mrpack` 安装相同，任一文件失败时
+//! 不改动目标目录。
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+
+use sealantern_core::provisioning::{
+    CURSEFORGE_MANIFEST_FILE, CurseForgeManifest, CurseForgeManifestError, is_client_only_mod,
+};
+use sealantern_infra::archive::ArchiveError;
+use sealantern_infra::fs::{FsError, SafeRelativePath, ensure_dir};
+
+use crate::market::fetcher::Fetcher;
+use crate::market::install::{InstallError, is_plain_file_name};
+use crate::market::mrpack::{apply_overrides, download_verified, move_downloads, stage_pack};
+
+/// 整合包文件的安装目录，相对服务器目录。
+const MODS_DIR: &str = "mods";
+
+/// 整合包安装结果。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CurseForgePackInstall {
+    pub manifest: CurseForgeManifest,
+    /// 作为客户端专用模组跳过的文件名。
+    pub skipped: Vec<String>,
+}
+
+/// 安装 CurseForge 整合包失败的原因。
+#[derive(Debug)]
+pub enum CurseForgePackError {
+    /// 整合包不是可安全解压的 zip。
+    Archive(ArchiveError),
+    /// 整合包缺少清单或清单无法使用。
+    Manifest(CurseForgeManifestError),
+    /// CurseForge 没有返回清单中的文件。
+    UnresolvedFile { file_id: u64 },
+    /// 文件名包含路径分隔符等不安全内容。
+    UnsafeFileName { file_name: String },
+    /// 文件查询、下载或校验失败。
+    Install(InstallError),
+    /// 文件读写失败，或覆盖目录路径不安全。
+    Storage(FsError),
+}
+
+impl fmt::Display for CurseForgePackError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Archive(error) => write!(formatter, "{error}"),
+            Self::Manifest(error) => write!(formatter, "{error}"),
+            Self::UnresolvedFile { file_id } => {
+                write!(formatter, "CurseForge did not return file {file_id}")
+            }
+            Self::UnsafeFileName { file_name } => {
+                write!(formatter, "unsafe modpack file name {file_name}")
+            }
+            Self::Install(error) => write!(formatter, "{error}"),
+            Self::Storage(error) => write!(formatter, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for CurseForgePackError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Archive(error) => Some(error),
+            Self::Manifest(error) => Some(error),
+            Self::Install(error) => Some(error),
+            Self::Storage(error) => Some(error),
+            Self::UnresolvedFile { .. } | Self::UnsafeFileName { .. } => None,
+        }
+    }
+}
+
+impl From<ArchiveError> for CurseForgePackError {
+    fn from(error: ArchiveError) -> Self {
+        Self::Archive(error)
+    }
+}
+
+impl From<CurseForgeManifestError> for CurseForgePackError {
+    fn from(error: CurseForgeManifestError) -> Self {
+        Self::Manifest(error)
+    }
+}
+
+impl From<InstallError> for CurseForgePackError {
+    fn from(error: InstallError) -> Self {
+        match error {
+            InstallError::Storage(error) => Self::Storage(error),
+            error => Self::Install(error),
+        }
+    }
+}
+
+impl From<FsError> for CurseForgePackError {
+    fn from(error: FsError) -> Self {
+        Self::Storage(error)
+    }
+}
+
+/// 把 CurseForge 整合包安装到 `destination`，返回清单与跳过的客户端专用模组。
+///
+/// `fetcher` 需支持按文件 ID 查询（生产环境为携带 API 密钥的 CurseForge 获取器）。
+pub async fn install_curseforge_pack(
+    fetcher: &dyn Fetcher,
+    archive: &Path,
+    destination: &Path,
+) -> Result<CurseForgePackInstall, CurseForgePackError> {
+    let (staging, pack_dir) = stage_pack::<CurseForgePackError>(archive, destination).await?;
+    let manifest_path = pack_dir.join(CURSEFORGE_MANIFEST_FILE);
+    let content = tokio::fs::read_to_string(&manifest_path)
+        .await
+        .map_err(|source| FsError::Io {
+            operation: "read CurseForge manifest",
+            path: manifest_path,
+            source,
+        })?;
+    let manifest = CurseForgeManifest::parse(&content)?;
+    let overrides = SafeRelativePath::parse(&manifest.overrides)?;
+
+    let file_ids: Vec<String> = manifest
+        .required_files()
+        .map(|file| file.file_id.to_string())
+        .collect();
+    let mut resolved: HashMap<String, _> = if file_ids.is_empty() {
+        HashMap::new()
+    } else {
+        fetcher
+            .files_by_ids(&file_ids)
+            .await
+            .map_err(InstallError::from)?
+            .into_iter()
+            .map(|file| (file.file_id.clone(), file))
+            .collect()
+    };
+
+    // 先确认清单中的文件全部可解析，再开始下载。
+    let mut wanted = Vec::new();
+    let mut skipped = Vec::new();
+    for entry in manifest.required_files() {
+        let file = resolved
+            .remove(&entry.file_id.to_string())
+            .ok_or(CurseForgePackError::UnresolvedFile { file_id: entry.file_id })?;
+        if !is_plain_file_name(&file.file.filename) {
+            return Err(CurseForgePackError::UnsafeFileName { file_name: file.file.filename });
+        }
+        if is_client_only_mod(&file.file.filename, &file.environments) {
+            skipped.push(file.file.filename);
+        } else {
+            wanted.push(file);
+        }
+    }
+
+    let downloads_dir = staging.path().join("downloads");
+    ensure_dir(&downloads_dir).await?;
+    let mut downloaded = Vec::new();
+    for file in wanted {
+        let partial = downloads_dir.join(format!("{}.part", file.file_id));
+        download_verified(fetcher, std::slice::from_ref(&file.file.url), &file.file, &partial)
+            .await?;
+        downloaded.push((partial, destination.join(MODS_DIR).join(&file.file.filename)));
+    }
+    move_downloads(downloaded, destination).await?;
+    apply_overrides(vec![pack_dir.join(overrides.as_path())], destination).await?;
+
+    if !skipped.is_empty() {
+        tracing::info!(
+            target: "sealantern.extra.market",
+            skipped = ?skipped,
+            "skipped client-only mods from CurseForge modpack"
+        );
+    }
+    Ok(CurseForgePackInstall { manifest, skipped })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    use sealantern_infra::archive::create_zip;
+
+    use super::*;
+    use crate::market::fetcher::{IdentifiedFile, MemoryFetcher, VersionFile};
+
+    fn identified(file_id: &str, file_name: &str, environments: &[&str]) -> IdentifiedFile {
+        IdentifiedFile {
+            project_id: format!("project-{file_id}"),
+            file_id: file_id.to_owned(),
+            file: VersionFile {
+                url: format!("https://edge.example/{file_name}"),
+                filename: file_name.to_owned(),
+                size: file_name.len() as u64,
+                primary: true,
+                hashes: BTreeMap::new(),
+            },
+            environments: environments
+                .iter()
+                .map(|value| (*value).to_owned())
+                .collect(),
+        }
+    }
+
+    fn write_pack(root: &Path) -> PathBuf {
+        let pack = root.join("pack-src");
+        std::fs::create_dir_all(pack.join("overrides/config")).unwrap();
+        std::fs::write(
+            pack.join(CURSEFORGE_MANIFEST_FILE),
+            r#"{
+                "minecraft": { "version": "1.20.1", "modLoaders": [{ "id": "forge-47.2.0", "primary": true }] },
+                "manifestType": "minecraftModpack",
+                "manifestVersion": 1,
+                "name": "Example",
+                "files": [
+                    { "projectID": 1, "fileID": 11, "required": true },
+                    { "projectID": 2, "fileID": 22, "required": true },
+                    { "projectID": 3, "fileID": 33, "required": true },
+                    { "projectID": 4, "fileID": 44, "required": false }
+                ],
+                "overrides": "overrides"
+            }"#,
+        )
+        .unwrap();
+        std::fs::write(pack.join("overrides/config/jei.toml"), "enabled = true").unwrap();
+        let archive = root.join("Example.zip");
+        create_zip(&pack, &archive).unwrap();
+        archive
+    }
+
+    #[tokio::test]
+    async fn installs_server_mods_and_skips_client_only() {
+        let root = tempfile::tempdir().unwrap();
+        let archive = write_pack(root.path());
+        let files = vec![
+            identified("11", "jei.jar", &["client", "server"]),
+            identified("22", "zoomify.jar", &["client"]),
+            identified("33", "oculus-mc1.20.1-1.6.9.jar", &[]),
+            identified("44", "optional.jar", &["server"]),
+        ];
+        let contents = files
+            .iter()
+            .map(|file| (file.file.url.clone(), file.file.filename.clone().into_bytes()))
+            .collect();
+        let fetcher = MemoryFetcher {
+            files,
+            contents,
+            ..MemoryFetcher::default()
+        };
+        let destination = root.path().join("server");
+
+        let install = install_curseforge_pack(&fetcher, &archive, &destination)
+            .await
+            .unwrap();
+
+        assert_eq!(install.manifest.minecraft.version, "1.20.1");
+        assert_eq!(install.skipped, ["zoomify.jar", "oculus-mc1.20.1-1.6.9.jar"]);
+        let mut mods: Vec<_> = std::fs::read_dir(destination.join(MODS_DIR))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        mods.sort();
+        assert_eq!(mods, ["jei.jar"]);
+        assert_eq!(
+            std::fs::read_to_string(destination.join("config/jei.toml")).unwrap(),
+            "enabled = true"
+        );
+    }
+
+    #[tokio::test]
+    async fn unresolved_file_leaves_destination_untouched() {
+        let root = tempfile::tempdir().unwrap();
+        let archive = write_pack(root.path());
+        let fetcher = MemoryFetcher {
+            files: vec![identified("11", "jei.jar", &["server"])],
+            ..MemoryFetcher::default()
+        };
+        let destination = root.path().join("server");
+
+        let error = install_curseforge_pack(&fetcher, &archive, &destination)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, CurseForgePackError::UnresolvedFile { file_id: 22 }));
+        assert!(!destination.exists());
+    }
+}
@@ -0,0 +1,653 @@
+//! CurseForge 市场数据获取器。
+//!
+//! 对应 [CurseForge Core API v1](https://docs.curseforge.com/rest-api/)，用于
+//! 搜索和获取 Minecraft 模组、插件与整合包。所有请求均需在 `x-api-key` 头中
+//! 携带 API 密钥；未配置密钥时各方法直接返回 [`MarketError::Config`]，不发出请求。
+//!
+//! # 关于字段命名
+//!
+//! CurseForge 响应字段使用 **camelCase**（如 `downloadCount`、`gameVersions`），
+//! 因此反序列化结构体统一标注 `#[serde(rename_all = "camelCase")]`。响应体的
+//! 有效载荷均包裹在 `data` 字段中，列表接口另附 `pagination`。
+//!
+//! # 版本与加载器
+//!
+//! 文件的 `gameVersions` 把游戏版本（`1.20.1`）、加载器（`Forge`、`Fabric`）
+//! 与运行环境（`Client`、`Server`）混在同一数组里，映射时按内容拆分。
+//!
+//! # 按文件 ID 查询
+//!
+//! 整合包清单只记录 `projectID` / `fileID`，[`Fetcher::files_by_ids`] 经
+//! `POST /mods/files` 批量解析出下载地址、摘要与运行环境。
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use sealantern_infra::net::{ClientProvider, NetClient};
+
+use crate::market::error::MarketError;
+use crate::market::fetcher;
+use crate::market::fetcher::Fetcher;
+use crate::market::fetcher::models::{
+    DependencyKind, IdentifiedFile, VersionDependency, VersionFile,
+};
+use crate::market::models::*;
+use crate::observability;
+
+/// CurseForge API 的基础 URL。
+const CURSEFORGE_BASE: &str = "https://api.curseforge.com/v1";
+
+/// Minecraft 在 CurseForge 上的游戏 ID。
+const MINECRAFT_GAME_ID: u32 = 432;
+
+/// 单页最多返回的条目数（API 上限为 50）。
+const MAX_PAGE_SIZE: u32 = 50;
+
+/// 拉取版本列表时最多翻的页数，避免超大项目无限翻页。
+const MAX_FILE_PAGES: u32 = 20;
+
+/// 分页参数 `index + pageSize` 的上限。
+const MAX_SEARCH_WINDOW: u32 = 10_000;
+
+/// 单次 `POST /mods/files` 请求携带的文件 ID 上限。
+const MAX_FILE_IDS_PER_REQUEST: usize = 100;
+
+// ─── CurseForge API 响应结构体 ───────────────────────────────────────────
+
+/// 单对象接口的响应包装。
+#[derive(Deserialize)]
+struct CurseForgeData<T> {
+    data: T,
+}
+
+/// 列表接口的响应包装。
+#[derive(Deserialize)]
+struct CurseForgePage<T> {
+    data: Vec<T>,
+    pagination: CurseForgePagination,
+}
+
+/// 分页元数据。
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgePagination {
+    index: u64,
+    page_size: u64,
+    total_count: u64,
+}
+
+/// 项目（`GET /mods/{modId}` 与搜索结果）。
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeMod {
+    id: u64,
+    name: String,
+    summary: String,
+    /// API 以 JSON number 返回，可能带小数部分。
+    download_count: f64,
+    class_id: Option<u32>,
+    logo: Option<CurseForgeLogo>,
+    #[serde(default)]
+    latest_files_indexes: Vec<CurseForgeFileIndex>,
+}
+
+/// 项目图标。
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeLogo {
+    url: String,
+}
+
+/// 项目各游戏版本下的最新文件索引。
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeFileIndex {
+    game_version: String,
+    mod_loader: Option<u32>,
+}
+
+/// 项目文件（`GET /mods/{modId}/files`），即一个版本。
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeFile {
+    id: u64,
+    mod_id: u64,
+    display_name: String,
+    file_name: String,
+    file_length: u64,
+    download_count: f64,
+    /// 作者关闭第三方分发时为 `null`。
+    download_url: Option<String>,
+    #[serde(default)]
+    game_versions: Vec<String>,
+    #[serde(default)]
+    dependencies: Vec<CurseForgeDependency>,
+    #[serde(default)]
+    hashes: Vec<CurseForgeFileHash>,
+}
+
+/// `POST /mods/files` 的请求体。
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeFileIds<'a> {
+    file_ids: &'a [u64],
+}
+
+/// 文件摘要；`algo` 为 1 表示 SHA-1，2 表示 MD5。
+#[derive(Deserialize)]
+struct CurseForgeFileHash {
+    value: String,
+    algo: u32,
+}
+
+/// 文件声明的依赖。
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseForgeDependency {
+    mod_id: u64,
+    relation_type: u32,
+}
+
+// ─── CurseForgeFetcher ───────────────────────────────────────────────────
+
+/// 基于 CurseForge API 的资源获取器。
+///
+/// 与其他获取器一样持有客户端获取器（provider），另持有 API 密钥。
+pub struct CurseForgeFetcher {
+    client_provider: ClientProvider,
+    api_key: Option<String>,
+}
+
+impl CurseForgeFetcher {
+    /// 使用全局客户端获取器构造获取器（生产装配推荐）。
+    ///
+    /// # Parameters
+    /// - `api_key`: CurseForge API 密钥；为空时获取器不可用。
+    pub fn global(api_key: Option<String>) -> Self {
+        Self::with_provider(sealantern_infra::net::global_client_provider(), api_key)
+    }
+
+    /// 使用客户端获取器构造获取器，每次请求前调用以获取当前全局客户端。
+    ///
+    /// # Parameters
+    /// - `client_provider`: 返回当前 `NetClient` 的获取器。
+    /// - `api_key`: CurseForge API 密钥；为空或纯空白时视为未配置。
+    ///
+    /// # Returns
+    /// 返回初始化完成的 `CurseForgeFetcher`。
+    pub fn with_provider(client_provider: ClientProvider, api_key: Option<String>) -> Self {
+        let api_key = api_key
+            .map(|key| key.trim().to_owned())
+            .filter(|key| !key.is_empty());
+        Self { client_provider, api_key }
+    }
+
+    /// 使用固定客户端创建 `CurseForgeFetcher`（测试注入）。
+    pub fn new(client: NetClient, api_key: Option<String>) -> Self {
+        Self::with_provider(Box::new(move || Ok(client.clone())), api_key)
+    }
+
+    /// 是否已配置 API 密钥。
+    pub fn is_configured(&self) -> bool {
+        self.api_key.is_some()
+    }
+
+    /// 携带 API 密钥发送 GET 请求并反序列化响应。
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        operation: &'static str,
+    ) -> Result<T, MarketError> {
+        let api_key = self
+            .api_key
+            .as_deref()
+            .ok_or_else(|| MarketError::config("CurseForge API key is not configured"))?;
+        let client = (self.client_provider)().map_err(|e| MarketError::config(e.to_string()))?;
+        let resp = client
+            .get(url)
+            .map_err(|e| MarketError::config(e.to_string()))?
+            .header("User-Agent", super::USER_AGENT)
+            .header("x-api-key", api_key)
+            .send()
+            .await
+            .map_err(|e| MarketError::http(operation, "curseforge", e.to_string()))?;
+        resp.json()
+            .await
+            .map_err(|e| MarketError::json(operation, "curseforge", e.to_string()))
+    }
+
+    /// 携带 API 密钥发送 JSON POST 请求并反序列化响应。
+    async fn post_json<B: Serialize + Sync, T: DeserializeOwned>(
+        &self,
+        url: &str,
+        body: &B,
+        operation: &'static str,
+    ) -> Result<T, MarketError> {
+        let api_key = self
+            .api_key
+            .as_deref()
+            .ok_or_else(|| MarketError::config("CurseForge API key is not configured"))?;
+        let client = (self.client_provider)().map_err(|e| MarketError::config(e.to_string()))?;
+        let resp = client
+            .post(url)
+            .map_err(|e| MarketError::config(e.to_string()))?
+            .header("User-Agent", super::USER_AGENT)
+            .header("x-api-key", api_key)
+            .json(body)
+            .map_err(|e| MarketError::config(e.to_string()))?
+            .send()
+            .await
+            .map_err(|e| MarketError::http(operation, "curseforge", e.to_string()))?;
+        resp.json()
+            .await
+            .map_err(|e| MarketError::json(operation, "curseforge", e.to_string()))
+    }
+
+    /// 按下载量排序搜索，`index` 为结果偏移量。
+    async fn search_page(
+        &self,
+        query: &str,
+        index: u32,
+        page_size: u32,
+        operation: &'static str,
+    ) -> Result<CurseForgePage<CurseForgeMod>, MarketError> {
+        let url = format!(
+            "{}/mods/search?gameId={}&searchFilter={}&sortField=6&sortOrder=desc&index={}&pageSize={}",
+            CURSEFORGE_BASE,
+            MINECRAFT_GAME_ID,
+            urlencoding::encode(query),
+            index,
+            page_size
+        );
+        self.get_json(&url, operation).await
+    }
+}
+
+#[async_trait]
+impl Fetcher for CurseForgeFetcher {
+    /// 在 CurseForge 中搜索 Minecraft 资源。
+    ///
+    /// 调用 `GET /mods/search?gameId=432&searchFilter={query}&index={offset}&pageSize={page_size}`，
+    /// 按下载量降序。`page_size` 超过 API 上限（50）时按上限截断。
+    async fn search(
+        &self,
+        query: &str,
+        page: u32,
+        page_size: u32,
+    ) -> Result<SearchResult, MarketError> {
+        if page == 0 {
+            return Err(MarketError::config("page must be 1 or greater"));
+        }
+        let page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+        let index = (page - 1).saturating_mul(page_size);
+        if index.saturating_add(page_size) > MAX_SEARCH_WINDOW {
+            return Err(MarketError::config(
+                "CurseForge only pages through the first 10000 results",
+            ));
+        }
+        observability::market_search_started(query, page, page_size, "curseforge");
+
+        let response = self
+            .search_page(query, index, page_size, "search resources")
+            .await?;
+        let result = search_result(response);
+        observability::market_search_completed(query, result.total, "curseforge");
+        Ok(result)
+    }
+
+    /// 获取项目详情。
+    ///
+    /// 调用 `GET /mods/{id}`。游戏版本与加载器取自 `latestFilesIndexes`，
+    /// 资源类型由 `classId` 推断。
+    async fn get_resource(&self, id: &str) -> Result<ResourceInfo, MarketError> {
+        let url = format!("{}/mods/{}", CURSEFORGE_BASE, urlencoding::encode(id));
+        let response: CurseForgeData<CurseForgeMod> =
+            self.get_json(&url, "get resource details").await?;
+        let info = resource_info(response.data);
+        observability::market_resource_fetched(id, &info.name, "curseforge");
+        Ok(info)
+    }
+
+    /// 获取项目的全部文件（版本），按发布时间倒序。
+    ///
+    /// 调用 `GET /mods/{id}/files` 并逐页拉取，最多 [`MAX_FILE_PAGES`] 页。
+    async fn get_resource_versions(&self, id: &str) -> Result<Vec<Version>, MarketError> {
+        let mut versions = Vec::new();
+        let mut index = 0;
+        for _ in 0..MAX_FILE_PAGES {
+            let url = format!(
+                "{}/mods/{}/files?index={}&pageSize={}",
+                CURSEFORGE_BASE,
+                urlencoding::encode(id),
+                index,
+                MAX_PAGE_SIZE
+            );
+            let page: CurseForgePage<CurseForgeFile> =
+                self.get_json(&url, "get resource versions").await?;
+            let fetched = page.data.len() as u64;
+            versions.extend(page.data.into_iter().map(version));
+            index = page.pagination.index + fetched;
+            if fetched == 0 || index >= page.pagination.total_count {
+                break;
+            }
+        }
+        observability::market_versions_fetched(id, versions.len(), "curseforge");
+        Ok(versions)
+    }
+
+    /// 下载资源文件，委托给 `fetcher::download_file`。
+    async fn download_resource(
+        &self,
+        url: &str,
+        destination: &str,
+    ) -> Result<Arc<sealantern_infra::download::DownloadStatus>, MarketError> {
+        observability::market_download_started(url, "curseforge");
+        fetcher::download_file(url, destination).await
+    }
+
+    /// 按文件 ID 批量获取文件。
+    ///
+    /// 调用 `POST /mods/files`，每次最多 [`MAX_FILE_IDS_PER_REQUEST`] 个 ID。
+    async fn files_by_ids(&self, file_ids: &[String]) -> Result<Vec<IdentifiedFile>, MarketError> {
+        let file_ids = file_ids
+            .iter()
+            .map(|id| {
+                id.parse::<u64>()
+                    .map_err(|_| MarketError::config(format!("invalid CurseForge file ID {id}")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let url = format!("{CURSEFORGE_BASE}/mods/files");
+        let mut files = Vec::with_capacity(file_ids.len());
+        for chunk in file_ids.chunks(MAX_FILE_IDS_PER_REQUEST) {
+            let response: CurseForgeData<Vec<CurseForgeFile>> = self
+                .post_json(&url, &CurseForgeFileIds { file_ids: chunk }, "get files by ID")
+                .await?;
+            files.extend(response.data.into_iter().map(identified_file));
+        }
+        Ok(files)
+    }
+
+    /// CurseForge 没有随机接口，从热门列表中随机取一页模拟。
+    async fn get_random_resources(&self, count: u32) -> Result<Vec<MarketResource>, MarketError> {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let limit = count.clamp(1, 8);
+        let index = (seed % 100) as u32 * limit;
+        let response = self
+            .search_page("", index, limit, "get random resources")
+            .await?;
+        Ok(response.data.into_iter().map(market_resource).collect())
+    }
+}
+
+// ─── 响应映射 ────────────────────────────────────────────────────────────
+
+fn search_result(page: CurseForgePage<CurseForgeMod>) -> SearchResult {
+    SearchResult {
+        total: page.pagination.total_count,
+        offset: page.pagination.index,
+        limit: page.pagination.page_size,
+        resources: page.data.into_iter().map(market_resource).collect(),
+    }
+}
+
+fn market_resource(project: CurseForgeMod) -> MarketResource {
+    MarketResource {
+        id: project.id.to_string(),
+        name: project.name,
+        description: project.summary,
+        download_count: project.download_count as u64,
+        version_count: 0,
+        source: MarketSource::CurseForge,
+    }
+}
+
+fn resource_info(project: CurseForgeMod) -> ResourceInfo {
+    let mut game_versions = Vec::new();
+    let mut loaders = Vec::new();
+    for index in &project.latest_files_indexes {
+        push_unique(&mut game_versions, index.game_version.clone());
+        if let Some(loader) = index.mod_loader.and_then(loader_name) {
+            push_unique(&mut loaders, loader.to_owned());
+        }
+    }
+    ResourceInfo {
+        id: project.id.to_string(),
+        name: project.name,
+        description: project.summary,
+        download_count: project.download_count as u64,
+        source: MarketSource::CurseForge,
+        icon_url: project.logo.map(|logo| logo.url),
+        game_versions,
+        loaders,
+        resource_type: resource_type(project.class_id).to_owned(),
+        external: false,
+        download_url: String::new(),
+    }
+}
+
+fn version(file: CurseForgeFile) -> Version {
+    let mut game_versions = Vec::new();
+    let mut loaders = Vec::new();
+    for tag in &file.game_versions {
+        if tag.starts_with(|c: char| c.is_ascii_digit()) {
+            push_unique(&mut game_versions, tag.clone());
+        } else if is_loader_tag(tag) {
+            push_unique(&mut loaders, tag.to_ascii_lowercase());
+        }
+    }
+    Version {
+        id: file.id.to_string(),
+        name: file.display_name.clone(),
+        version_number: file.display_name.clone(),
+        game_versions,
+        loaders,
+        downloads: file.download_count as u64,
+        files: vec![version_file(&file)],
+        dependencies: file
+            .dependencies
+            .into_iter()
+            .filter_map(|dependency| {
+                let kind = match dependency.relation_type {
+                    1 => DependencyKind::Embedded,
+                    2 => DependencyKind::Optional,
+                    3 => DependencyKind::Required,
+                    5 => DependencyKind::Incompatible,
+                    // 4 = Tool、6 = Include，与安装无关
+                    _ => return None,
+                };
+                Some(VersionDependency {
+                    project_id: Some(dependency.mod_id.to_string()),
+                    version_id: None,
+                    name: None,
+                    kind,
+                })
+            })
+            .collect(),
+    }
+}
+
+fn identified_file(file: CurseForgeFile) -> IdentifiedFile {
+    let environments = file
+        .game_versions
+        .iter()
+        .filter(|tag| tag.eq_ignore_ascii_case("client") || tag.eq_ignore_ascii_case("server"))
+        .map(|tag| tag.to_ascii_lowercase())
+        .collect();
+    IdentifiedFile {
+        project_id: file.mod_id.to_string(),
+        file_id: file.id.to_string(),
+        file: version_file(&file),
+        environments,
+    }
+}
+
+fn version_file(file: &CurseForgeFile) -> VersionFile {
+    let url = file
+        .download_url
+        .clone()
+        .unwrap_or_else(|| edge_download_url(file.id, &file.file_name));
+    VersionFile {
+        url,
+        filename: file.file_name.clone(),
+        size: file.file_length,
+        primary: true,
+        hashes: file
+            .hashes
+            .iter()
+            .filter_map(|hash| {
+                let algorithm = match hash.algo {
+                    1 => "sha1",
+                    2 => "md5",
+                    _ => return None,
+                };
+                Some((algorithm.to_owned(), hash.value.to_ascii_lowercase()))
+            })
+            .collect(),
+    }
+}
+
+/// `modLoader` 枚举值对应的加载器名称。
+fn loader_name(loader: u32) -> Option<&'static str> {
+    match loader {
+        1 => Some("forge"),
+        2 => Some("cauldron"),
+        3 => Some("liteloader"),
+        4 => Some("fabric"),
+        5 => Some("quilt"),
+        6 => Some("neoforge"),
+        _ => None,
+    }
+}
+
+fn is_loader_tag(tag: &str) -> bool {
+    ["forge", "neoforge", "fabric", "quilt", "liteloader", "cauldron", "bukkit"]
+        .iter()
+        .any(|loader| tag.eq_ignore_ascii_case(loader))
+}
+
+/// `classId` 对应的资源类型。
+fn resource_type(class_id: Option<u32>) -> &'static str {
+    match class_id {
+        Some(5) => "plugin",
+        Some(12) => "resourcepack",
+        Some(17) => "world",
+        Some(4471) => "modpack",
+        Some(6552) => "shader",
+        Some(6945) => "datapack",
+        _ => "mod",
+    }
+}
+
+/// 作者关闭第三方分发时 `downloadUrl` 为空，改用 CDN 的固定路径规则。
+fn edge_download_url(file_id: u64, file_name: &str) -> String {
+    format!(
+        "https://edge.forgecdn.net/files/{}/{}/{}",
+        file_id / 1000,
+        file_id % 1000,
+        urlencoding::encode(file_name)
+    )
+}
+
+fn push_unique(values: &mut Vec<String>, value: String) {
+    if !values.contains(&value) {
+        values.push(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_recorded_search_response() {
+        let page: CurseForgePage<CurseForgeMod> =
+            serde_json::from_str(include_str!("fixtures/curseforge_search.json")).unwrap();
+        let result = search_result(page);
+        assert_eq!(result.total, 12_345);
+        assert_eq!(result.offset, 0);
+        assert_eq!(result.limit, 2);
+        assert_eq!(result.resources.len(), 2);
+        assert_eq!(result.resources[0].id, "238222");
+        assert_eq!(result.resources[0].name, "Just Enough Items (JEI)");
+        assert_eq!(result.resources[0].download_count, 350_123_456);
+        assert_eq!(result.resources[0].source, MarketSource::CurseForge);
+    }
+
+    #[test]
+    fn maps_recorded_project_response() {
+        let response: CurseForgeData<CurseForgeMod> =
+            serde_json::from_str(include_str!("fixtures/curseforge_mod.json")).unwrap();
+        let info = resource_info(response.data);
+        assert_eq!(info.id, "238222");
+        assert_eq!(info.resource_type, "mod");
+        assert_eq!(info.game_versions, ["1.20.1", "1.19.2"]);
+        assert_eq!(info.loaders, ["forge", "fabric", "neoforge"]);
+        assert_eq!(
+            info.icon_url.as_deref(),
+            Some("https://media.forgecdn.net/avatars/29/69/635838945588716414.jpeg")
+        );
+    }
+
+    #[test]
+    fn maps_recorded_files_response() {
+        let page: CurseForgePage<CurseForgeFile> =
+            serde_json::from_str(include_str!("fixtures/curseforge_files.json")).unwrap();
+        assert_eq!(page.pagination.total_count, 2);
+        let versions: Vec<Version> = page.data.into_iter().map(version).collect();
+
+        assert_eq!(versions[0].version_number, "jei-1.20.1-forge-15.3.0.4");
+        assert_eq!(versions[0].game_versions, ["1.20.1"]);
+        assert_eq!(versions[0].loaders, ["forge", "neoforge"]);
+        assert_eq!(versions[0].files[0].size, 1_302_188);
+        assert_eq!(versions[0].files[0].hashes["sha1"], "0d6c6a1b8c0a7d3bbd6f8f2f0c4d9f6e1f1a2b3c");
+        assert_eq!(versions[0].files[0].hashes.len(), 2);
+        assert_eq!(
+            versions[0].dependencies,
+            [VersionDependency {
+                project_id: Some("306612".to_owned()),
+                version_id: None,
+                name: None,
+                kind: DependencyKind::Required,
+            }]
+        );
+
+        // 关闭第三方分发的文件回退到 CDN 路径
+        assert_eq!(
+            versions[1].files[0].url,
+            "https://edge.forgecdn.net/files/4712/866/jei-1.20.1-fabric-15.2.0.27.jar"
+        );
+        assert_eq!(versions[1].loaders, ["fabric"]);
+        assert_eq!(versions[1].dependencies[0].kind, DependencyKind::Optional);
+    }
+
+    #[test]
+    fn maps_files_by_id_with_environments() {
+        let page: CurseForgePage<CurseForgeFile> =
+            serde_json::from_str(include_str!("fixtures/curseforge_files.json")).unwrap();
+        let files: Vec<IdentifiedFile> = page.data.into_iter().map(identified_file).collect();
+
+        assert_eq!(files[0].project_id, "238222");
+        assert_eq!(files[0].file_id, "5101366");
+        assert_eq!(files[0].file.filename, "jei-1.20.1-forge-15.3.0.4.jar");
+        assert_eq!(files[0].environments, ["server", "client"]);
+        assert!(files[1].environments.is_empty());
+    }
+
+    #[tokio::test]
+    async fn missing_api_key_fails_without_network() {
+        let fetcher = CurseForgeFetcher::with_provider(
+            Box::new(|| -> Result<NetClient, sealantern_infra::net::NetError> {
+                panic!("未配置密钥时不应获取客户端")
+            }),
+            Some("   ".to_owned()),
+        );
+        let error = fetcher.get_resource("238222").await.unwrap_err();
+        assert!(matches!(error, MarketError::Config(_)));
+    }
+}
@@ -0,0 +1,486 @@
+//! Hangar 市场数据获取器。
+//!
+//! 对应 [Hangar API v1](https://hangar.papermc.io/api-docs)，即 PaperMC 官方的
+//! 插件平台，覆盖 Paper、Velocity 与 Waterfall 插件。项目以 slug 标识，
+//! 资源 ID 直接使用 slug。
+//!
+//! # 关于字段命名
+//!
+//! Hangar 响应字段使用 **camelCase**（如 `avatarUrl`、`pluginDependencies`），
+//! 平台名为全大写的映射键（`PAPER`、`VELOCITY`），映射为加载器时统一转小写。
+//! 同一版本可为每个平台提供独立文件，也可能只给出外部下载链接。
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+use sealantern_infra::net::{ClientProvider, NetClient};
+
+use crate::market::error::MarketError;
+use crate::market::fetcher;
+use crate::market::fetcher::Fetcher;
+use crate::market::fetcher::models::{DependencyKind, VersionDependency, VersionFile};
+use crate::market::models::*;
+use crate::observability;
+
+/// Hangar API 的基础 URL。
+const HANGAR_BASE: &str = "https://hangar.papermc.io/api/v1";
+
+/// 单页最多返回的条目数（API 上限为 25）。
+const MAX_PAGE_SIZE: u32 = 25;
+
+/// 拉取版本列表时最多翻的页数。
+const MAX_VERSION_PAGES: u32 = 20;
+
+// ─── Hangar API 响应结构体 ───────────────────────────────────────────────
+
+/// 列表接口的响应包装。
+#[derive(Deserialize)]
+struct HangarPage<T> {
+    pagination: HangarPagination,
+    result: Vec<T>,
+}
+
+/// 分页元数据。
+#[derive(Deserialize)]
+struct HangarPagination {
+    limit: u64,
+    offset: u64,
+    count: u64,
+}
+
+/// 项目（`GET /projects/{slug}` 与搜索结果）。
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HangarProject {
+    name: String,
+    namespace: HangarNamespace,
+    stats: HangarProjectStats,
+    description: Option<String>,
+    avatar_url: Option<String>,
+    /// 平台 → 支持的游戏版本（或版本区间）。
+    #[serde(default)]
+    supported_platforms: BTreeMap<String, Vec<String>>,
+}
+
+/// 项目命名空间。
+#[derive(Deserialize)]
+struct HangarNamespace {
+    slug: String,
+}
+
+/// 项目统计。
+#[derive(Deserialize)]
+struct HangarProjectStats {
+    downloads: u64,
+}
+
+/// 项目的单个版本（`GET /projects/{slug}/versions`）。
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HangarVersion {
+    id: u64,
+    name: String,
+    stats: HangarVersionStats,
+    /// 平台 → 该平台的下载信息。
+    #[serde(default)]
+    downloads: BTreeMap<String, HangarDownload>,
+    #[serde(default)]
+    plugin_dependencies: BTreeMap<String, Vec<HangarPluginDependency>>,
+    #[serde(default)]
+    platform_dependencies: BTreeMap<String, Vec<String>>,
+}
+
+/// 版本统计。
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HangarVersionStats {
+    total_downloads: u64,
+}
+
+/// 某平台的下载信息；托管在外部时只有 `externalUrl`。
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HangarDownload {
+    file_info: Option<HangarFileInfo>,
+    external_url: Option<String>,
+    download_url: Option<String>,
+}
+
+/// 托管文件的元数据。
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HangarFileInfo {
+    name: String,
+    size_bytes: u64,
+    sha256_hash: Option<String>,
+}
+
+/// 插件依赖；`projectId` 为空表示依赖不在 Hangar 上。
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct HangarPluginDependency {
+    name: String,
+    required: bool,
+    project_id: Option<u64>,
+}
+
+// ─── HangarFetcher ───────────────────────────────────────────────────────
+
+/// 基于 Hangar API 的资源获取器。
+///
+/// 持有客户端获取器（provider），每次请求前获取当前全局客户端，
+/// 避免缓存固定客户端导致代理更新不生效。
+pub struct HangarFetcher {
+    client_provider: ClientProvider,
+}
+
+impl HangarFetcher {
+    /// 使用全局客户端获取器构造获取器（生产装配推荐）。
+    pub fn global() -> Self {
+        Self::with_provider(sealantern_infra::net::global_client_provider())
+    }
+
+    /// 使用客户端获取器构造获取器，每次请求前调用以获取当前全局客户端。
+    ///
+    /// # Parameters
+    /// - `client_provider`: 返回当前 `NetClient` 的获取器。
+    ///
+    /// # Returns
+    /// 返回初始化完成的 `HangarFetcher`。
+    pub fn with_provider(client_provider: ClientProvider) -> Self {
+        Self { client_provider }
+    }
+
+    /// 使用固定客户端创建 `HangarFetcher`（测试注入）。
+    pub fn new(client: NetClient) -> Self {
+        Self::with_provider(Box::new(move || Ok(client.clone())))
+    }
+
+    /// 发送 GET 请求并反序列化响应。
+    async fn get_json<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        operation: &'static str,
+    ) -> Result<T, MarketError> {
+        let client = (self.client_provider)().map_err(|e| MarketError::config(e.to_string()))?;
+        let resp = client
+            .get(url)
+            .map_err(|e| MarketError::config(e.to_string()))?
+            .header("User-Agent", super::USER_AGENT)
+            .send()
+            .await
+            .map_err(|e| MarketError::http(operation, "hangar", e.to_string()))?;
+        resp.json()
+            .await
+            .map_err(|e| MarketError::json(operation, "hangar", e.to_string()))
+    }
+
+    /// 按下载量降序列出项目。
+    async fn projects(
+        &self,
+        query: &str,
+        offset: u32,
+        limit: u32,
+        operation: &'static str,
+    ) -> Result<HangarPage<HangarProject>, MarketError> {
+        let url = format!(
+            "{}/projects?q={}&sort=-downloads&limit={}&offset={}",
+            HANGAR_BASE,
+            urlencoding::encode(query),
+            limit,
+            offset
+        );
+        self.get_json(&url, operation).await
+    }
+}
+
+#[async_trait]
+impl Fetcher for HangarFetcher {
+    /// 在 Hangar 中搜索插件。
+    ///
+    /// 调用 `GET /projects?q={query}&limit={page_size}&offset={offset}`，
+    /// `page_size` 超过 API 上限（25）时按上限截断。
+    async fn search(
+        &self,
+        query: &str,
+        page: u32,
+        page_size: u32,
+    ) -> Result<SearchResult, MarketError> {
+        if page == 0 {
+            return Err(MarketError::config("page must be 1 or greater"));
+        }
+        let page_size = page_size.clamp(1, MAX_PAGE_SIZE);
+        observability::market_search_started(query, page, page_size, "hangar");
+        let offset = (page - 1).saturating_mul(page_size);
+
+        let response = self
+            .projects(query, offset, page_size, "search resources")
+            .await?;
+        let result = search_result(response);
+        observability::market_search_completed(query, result.total, "hangar");
+        Ok(result)
+    }
+
+    /// 获取项目详情。
+    ///
+    /// 调用 `GET /projects/{slug}`；加载器与游戏版本取自 `supportedPlatforms`。
+    async fn get_resource(&self, id: &str) -> Result<ResourceInfo, MarketError> {
+        let url = format!("{}/projects/{}", HANGAR_BASE, urlencoding::encode(id));
+        let project: HangarProject = self.get_json(&url, "get resource details").await?;
+        let info = resource_info(project);
+        observability::market_resource_fetched(id, &info.name, "hangar");
+        Ok(info)
+    }
+
+    /// 获取项目的全部版本，逐页拉取，最多 [`MAX_VERSION_PAGES`] 页。
+    async fn get_resource_versions(&self, id: &str) -> Result<Vec<Version>, MarketError> {
+        let mut versions = Vec::new();
+        let mut offset = 0;
+        for _ in 0..MAX_VERSION_PAGES {
+            let url = format!(
+                "{}/projects/{}/versions?limit={}&offset={}",
+                HANGAR_BASE,
+                urlencoding::encode(id),
+                MAX_PAGE_SIZE,
+                offset
+            );
+            let page: HangarPage<HangarVersion> =
+                self.get_json(&url, "get resource versions").await?;
+            let fetched = page.result.len() as u64;
+            versions.extend(page.result.into_iter().map(version));
+            offset = page.pagination.offset + fetched;
+            if fetched == 0 || offset >= page.pagination.count {
+                break;
+            }
+        }
+        observability::market_versions_fetched(id, versions.len(), "hangar");
+        Ok(versions)
+    }
+
+    /// 下载资源文件，委托给 `fetcher::download_file`。
+    async fn download_resource(
+        &self,
+        url: &str,
+        destination: &str,
+    ) -> Result<Arc<sealantern_infra::download::DownloadStatus>, MarketError> {
+        observability::market_download_started(url, "hangar");
+        fetcher::download_file(url, destination).await
+    }
+
+    /// Hangar 没有随机接口，从热门列表中随机取一页模拟。
+    async fn get_random_resources(&self, count: u32) -> Result<Vec<MarketResource>, MarketError> {
+        let seed = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        let limit = count.clamp(1, 8);
+        let offset = (seed % 50) as u32 * limit;
+        let response = self
+            .projects("", offset, limit, "get random resources")
+            .await?;
+        Ok(response.result.into_iter().map(market_resource).collect())
+    }
+}
+
+// ─── 响应映射 ────────────────────────────────────────────────────────────
+
+fn search_result(page: HangarPage<HangarProject>) -> SearchResult {
+    SearchResult {
+        total: page.pagination.count,
+        offset: page.pagination.offset,
+        limit: page.pagination.limit,
+        resources: page.result.into_iter().map(market_resource).collect(),
+    }
+}
+
+fn market_resource(project: HangarProject) -> MarketResource {
+    MarketResource {
+        id: project.namespace.slug,
+        name: project.name,
+        description: project.description.unwrap_or_default(),
+        download_count: project.stats.downloads,
+        version_count: 0,
+        source: MarketSource::Hangar,
+    }
+}
+
+fn resource_info(project: HangarProject) -> ResourceInfo {
+    let loaders = project
+        .supported_platforms
+        .keys()
+        .map(|platform| platform.to_ascii_lowercase())
+        .collect();
+    let mut game_versions: Vec<String> = Vec::new();
+    for version in project.supported_platforms.into_values().flatten() {
+        if !game_versions.contains(&version) {
+            game_versions.push(version);
+        }
+    }
+    ResourceInfo {
+        id: project.namespace.slug,
+        name: project.name,
+        description: project.description.unwrap_or_default(),
+        download_count: project.stats.downloads,
+        source: MarketSource::Hangar,
+        icon_url: project.avatar_url,
+        game_versions,
+        loaders,
+        resource_type: "plugin".to_owned(),
+        external: false,
+        download_url: String::new(),
+    }
+}
+
+fn version(version: HangarVersion) -> Version {
+    let loaders = version
+        .downloads
+        .keys()
+        .map(|platform| platform.to_ascii_lowercase())
+        .collect();
+
+    let mut game_versions: Vec<String> = Vec::new();
+    for game_version in version.platform_dependencies.into_values().flatten() {
+        if !game_versions.contains(&game_version) {
+            game_versions.push(game_version);
+        }
+    }
+
+    // 多个平台共用同一文件时只保留一份；Paper 的文件排在最前作为主文件
+    let mut downloads: Vec<(String, HangarDownload)> = version.downloads.into_iter().collect();
+    downloads.sort_by_key(|(platform, _)| platform != "PAPER");
+    let mut files: Vec<VersionFile> = Vec::new();
+    for (_, download) in downloads {
+        let Some(url) = download.download_url.or(download.external_url) else {
+            continue;
+        };
+        if files.iter().any(|file| file.url == url) {
+            continue;
+        }
+        let (filename, size, hashes) = match download.file_info {
+            Some(info) => (
+                info.name,
+                info.size_bytes,
+                info.sha256_hash
+                    .map(|hash| BTreeMap::from([("sha256".to_owned(), hash.to_ascii_lowercase())]))
+                    .unwrap_or_default(),
+            ),
+            None => (url_file_name(&url), 0, BTreeMap::new()),
+        };
+        let primary = files.is_empty();
+        files.push(VersionFile { url, filename, size, primary, hashes });
+    }
+
+    let mut dependencies: Vec<VersionDependency> = Vec::new();
+    for dependency in version.plugin_dependencies.into_values().flatten() {
+        if dependencies
+            .iter()
+            .any(|existing| existing.name.as_deref() == Some(dependency.name.as_str()))
+        {
+            continue;
+        }
+        dependencies.push(VersionDependency {
+            project_id: dependency.project_id.map(|id| id.to_string()),
+            version_id: None,
+            name: Some(dependency.name),
+            kind: if dependency.required {
+                DependencyKind::Required
+            } else {
+                DependencyKind::Optional
+            },
+        });
+    }
+
+    Version {
+        id: version.id.to_string(),
+        name: version.name.clone(),
+        version_number: version.name,
+        game_versions,
+        loaders,
+        downloads: version.stats.total_downloads,
+        files,
+        dependencies,
+    }
+}
+
+/// 外部链接没有文件信息时，取 URL 最后一段作为文件名。
+fn url_file_name(url: &str) -> String {
+    url.split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("download.jar")
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_recorded_search_response() {
+        let page: HangarPage<HangarProject> =
+            serde_json::from_str(include_str!("fixtures/hangar_projects.json")).unwrap();
+        let result = search_result(page);
+        assert_eq!(result.total, 1_234);
+        assert_eq!(result.limit, 2);
+        assert_eq!(result.resources.len(), 2);
+        assert_eq!(result.resources[0].id, "ViaVersion");
+        assert_eq!(result.resources[0].download_count, 2_345_678);
+        assert_eq!(result.resources[1].description, "");
+        assert_eq!(result.resources[1].source, MarketSource::Hangar);
+    }
+
+    #[test]
+    fn maps_recorded_project_response() {
+        let project: HangarProject =
+            serde_json::from_str(include_str!("fixtures/hangar_project.json")).unwrap();
+        let info = resource_info(project);
+        assert_eq!(info.id, "ViaVersion");
+        assert_eq!(info.loaders, ["paper", "velocity", "waterfall"]);
+        assert_eq!(info.game_versions, ["1.8-1.21", "3.3", "1.20"]);
+        assert_eq!(info.resource_type, "plugin");
+        assert!(info.icon_url.is_some());
+    }
+
+    #[test]
+    fn maps_recorded_versions_response() {
+        let page: HangarPage<HangarVersion> =
+            serde_json::from_str(include_str!("fixtures/hangar_versions.json")).unwrap();
+        let versions: Vec<Version> = page.result.into_iter().map(version).collect();
+
+        let hosted = &versions[0];
+        assert_eq!(hosted.version_number, "5.0.1");
+        assert_eq!(hosted.loaders, ["paper", "velocity", "waterfall"]);
+        assert_eq!(hosted.game_versions, ["1.8", "1.20.6", "1.21", "3.3"]);
+        // Paper 与 Waterfall 共用同一文件，只保留一份并作为主文件
+        assert_eq!(hosted.files.len(), 2);
+        assert!(hosted.files[0].primary);
+        assert_eq!(hosted.files[0].filename, "ViaVersion-5.0.1.jar");
+        assert_eq!(hosted.files[0].size, 5_271_453);
+        assert_eq!(
+            hosted.files[0].hashes["sha256"],
+            "2f1c9a7d5b0e3f4a6c8d9e0f1a2b3c4d5e6f7a8b9c0d1e2f3a4b5c6d7e8f9a0b"
+        );
+        assert_eq!(
+            hosted.dependencies,
+            [VersionDependency {
+                project_id: Some("42".to_owned()),
+                version_id: None,
+                name: Some("ViaBackwards".to_owned()),
+                kind: DependencyKind::Optional,
+            }]
+        );
+
+        let external = &versions[1];
+        assert_eq!(external.files.len(), 1);
+        assert_eq!(external.files[0].filename, "ViaVersion-4.10.2.jar");
+        assert_eq!(external.files[0].size, 0);
+        assert!(external.files[0].hashes.is_empty());
+        assert_eq!(external.dependencies[0].kind, DependencyKind::Required);
+        assert_eq!(external.dependencies[0].project_id, None);
+    }
+}
@@ -0,0 +1,170 @@
+//! 测试用的内存市场抓取器。
+//!
+//! 市场安装、更新检查与整合包导入的测试共用 [`MemoryFetcher`]：版本列表、下载内容与
+//! 按文件 ID 查询的文件都保存在内存中，不访问网络。只在测试中编译。
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use sealantern_infra::download::DownloadStatus;
+use sealantern_infra::fs::HashAlgorithm;
+use sha2::Digest;
+
+use super::{Fetcher, HashedVersion, IdentifiedFile};
+use crate::market::MarketError;
+use crate::market::models::{MarketResource, ResourceInfo, SearchResult, Version};
+
+/// 内存中的市场。
+#[derive(Debug, Default)]
+pub struct MemoryFetcher {
+    /// 项目 ID → 版本列表（新到旧）。
+    pub versions: HashMap<String, Vec<Version>>,
+    /// 下载地址 → 文件内容；未知地址视为下载失败。
+    pub contents: HashMap<String, Vec<u8>>,
+    /// 可按文件 ID 查询的文件（CurseForge 整合包清单）。
+    pub files: Vec<IdentifiedFile>,
+    /// 是否支持按 SHA-512 摘要反查版本（Modrinth 风格）。
+    pub hash_lookup: bool,
+    /// 查询版本列表时返回网络错误的项目。
+    pub failing: HashSet<String>,
+}
+
+impl MemoryFetcher {
+    /// 登记项目的一个版本；尚无内容的文件以下载地址末段作为内容。
+    pub fn insert_version(&mut self, project_id: &str, version: Version) {
+        for file in &version.files {
+            let tail = file.url.rsplit('/').next().unwrap_or_default();
+            self.contents
+                .entry(file.url.clone())
+                .or_insert_with(|| tail.as_bytes().to_vec());
+        }
+        self.versions
+            .entry(project_id.to_owned())
+            .or_default()
+            .push(version);
+    }
+
+    /// 按 SHA-512 摘要找到内容对应的项目与版本。
+    fn find_by_hash(&self, hash: &str) -> Option<(&String, &Version)> {
+        let (url, _) = self
+            .contents
+            .iter()
+            .find(|(_, content)| format!("{:x}", sha2::Sha512::digest(content)) == hash)?;
+        self.versions.iter().find_map(|(project_id, versions)| {
+            let version = versions
+                .iter()
+                .find(|version| version.files.iter().any(|file| &file.url == url))?;
+            Some((project_id, version))
+        })
+    }
+
+    fn lookup_hashes<'a>(
+        &'a self,
+        algorithm: HashAlgorithm,
+        hashes: &[String],
+        pick: impl Fn(&'a String, &'a Version) -> Option<&'a Version>,
+    ) -> HashMap<String, HashedVersion> {
+        if !self.hash_lookup || algorithm != HashAlgorithm::Sha512 {
+            return HashMap::new();
+        }
+        hashes
+            .iter()
+            .filter_map(|hash| {
+                let (project_id, current) = self.find_by_hash(hash)?;
+                let version = pick(project_id, current)?;
+                Some((
+                    hash.clone(),
+                    HashedVersion {
+                        project_id: project_id.clone(),
+                        version: version.clone(),
+                    },
+                ))
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl Fetcher for MemoryFetcher {
+    async fn search(&self, _: &str, _: u32, _: u32) -> Result<SearchResult, MarketError> {
+        Err(MarketError::config("memory fetcher does not support search"))
+    }
+
+    async fn get_resource(&self, id: &str) -> Result<ResourceInfo, MarketError> {
+        Err(MarketError::NotFound { resource: id.to_owned() })
+    }
+
+    async fn get_resource_versions(&self, id: &str) -> Result<Vec<Version>, MarketError> {
+        if self.failing.contains(id) {
+            return Err(MarketError::Http {
+                operation: "fetch versions",
+                source: format!("{id} is unreachable"),
+            });
+        }
+        self.versions
+            .get(id)
+            .cloned()
+            .ok_or_else(|| MarketError::NotFound { resource: id.to_owned() })
+    }
+
+    async fn download_resource(
+        &self,
+        url: &str,
+        destination: &str,
+    ) -> Result<Arc<DownloadStatus>, MarketError> {
+        let content = self
+            .contents
+            .get(url)
+            .ok_or_else(|| MarketError::download(format!("{url} is unreachable")))?;
+        tokio::fs::write(destination, content)
+            .await
+            .map_err(|error| MarketError::download(error.to_string()))?;
+        let status = Arc::new(DownloadStatus::new(content.len() as u64));
+        status.mark_completed();
+        Ok(status)
+    }
+
+    async fn get_random_resources(&self, _: u32) -> Result<Vec<MarketResource>, MarketError> {
+        Ok(Vec::new())
+    }
+
+    async fn versions_by_hashes(
+        &self,
+        algorithm: HashAlgorithm,
+        hashes: &[String],
+    ) -> Result<HashMap<String, HashedVersion>, MarketError> {
+        Ok(self.lookup_hashes(algorithm, hashes, |_, current| Some(current)))
+    }
+
+    async fn latest_versions_by_hashes(
+        &self,
+        algorithm: HashAlgorithm,
+        hashes: &[String],
+        _loaders: &[String],
+        game_versions: &[String],
+    ) -> Result<HashMap<String, HashedVersion>, MarketError> {
+        Ok(self.lookup_hashes(algorithm, hashes, |project_id, _| {
+            self.versions[project_id].iter().find(|version| {
+                game_versions.is_empty()
+                    || version
+                        .game_versions
+                        .iter()
+                        .any(|game_version| game_versions.contains(game_version))
+            })
+        }))
+    }
+
+    async fn files_by_ids(&self, file_ids: &[String]) -> Result<Vec<IdentifiedFile>, MarketError> {
+        Ok(self
+            .files
+            .iter()
+            .filter(|file| file_ids.contains(&file.file_id))
+            .cloned()
+            .collect())
+    }
+
+    fn version_page_url(&self, project_id: &str, version_id: &str) -> Option<String> {
+        Some(format!("https://market.example/{project_id}/{version_id}"))
+    }
+}
@@ -1,8 +1,12 @@
 //! 资源获取器（Fetcher）模块。
 //!
-//! 提供 [`Fetcher`] trait 定义以及各平台的实现（Spigot、Modrinth）。
+//! 提供 [`Fetcher`] trait 定义以及各平台的实现（Spigot、Modrinth、CurseForge、Hangar）。
 //! 还包含通用的 `download_file` 辅助函数，供各平台实现复用。
 
+mod curseforge;
+mod hangar;
+#[cfg(test)]
+pub(crate) mod memory;
 pub mod models;
 mod modrinth;
 mod spiget;
@@ -10,10 +14,14 @@ pub mod traits;
 
 use std::sync::Arc;
 
-pub use models::VersionFile;
+pub use models::{DependencyKind, HashedVersion, IdentifiedFile, VersionDependency, VersionFile};
 pub use traits::Fetcher;
 
 use crate::market::MarketError;
+pub use curseforge::CurseForgeFetcher;
+pub use hangar::HangarFetcher;
+#[cfg(test)]
+pub(crate) use memory::MemoryFetcher;
 pub use modrinth::ModrinthFetcher;
 pub use spiget::SpigetFetcher;
 
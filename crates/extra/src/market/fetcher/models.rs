@@ -1,15 +1,20 @@
 //! 资源获取器（Fetcher）模块的数据模型。
 //!
 //! 定义了从资源平台获取到的文件级数据结构，如 [`VersionFile`]，用于描述
-//! 某个版本关联的具体文件信息（下载链接、文件名、大小等）。
+//! 某个版本关联的具体文件信息（下载链接、文件名、大小等），按文件摘要反查到的
+//! 版本 [`HashedVersion`]，以及按文件 ID 查到的 [`IdentifiedFile`]。
+
+use std::collections::BTreeMap;
 
 use serde::{Deserialize, Serialize};
 
+use crate::market::models::Version;
+
 /// 版本关联的文件信息。
 ///
 /// 每个版本可能包含一个或多个文件（例如主 jar 包、API jar 包等），
-/// 该结构描述了其中单个文件的下载地址、名称、大小以及是否为默认下载文件。
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// 该结构描述了其中单个文件的下载地址、名称、大小、摘要以及是否为默认下载文件。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct VersionFile {
     /// 文件的下载 URL。
     pub url: String,
@@ -25,4 +30,70 @@ pub struct VersionFile {
     /// 当同一版本包含多个文件时，`primary = true` 表示该文件是用户
     /// 通常应下载的那个（如插件本体）。
     pub primary: bool,
+
+    /// 平台提供的文件摘要，键为小写算法名（如 `sha1`、`sha256`、`sha512`），值为十六进制摘要。
+    #[serde(default)]
+    pub hashes: BTreeMap<String, String>,
+}
+
+/// 版本依赖的关系类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyKind {
+    /// 必需依赖，缺失时资源无法加载。
+    Required,
+
+    /// 可选依赖，存在时启用额外功能。
+    Optional,
+
+    /// 已内嵌在资源文件中，无需单独安装。
+    Embedded,
+
+    /// 与该资源不兼容，不能同时安装。
+    Incompatible,
+}
+
+/// 版本声明的依赖项。
+///
+/// 不同平台提供的依赖信息粒度不同：Modrinth 可能锁定到具体版本，
+/// CurseForge 只给出项目 ID，Hangar 的外部依赖只有名称与外链。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionDependency {
+    /// 依赖资源在同一平台上的 ID；平台外的依赖为 `None`。
+    pub project_id: Option<String>,
+
+    /// 锁定的依赖版本 ID，未锁定时为 `None`。
+    pub version_id: Option<String>,
+
+    /// 依赖资源的名称，平台未提供时为 `None`。
+    pub name: Option<String>,
+
+    /// 依赖关系类型。
+    pub kind: DependencyKind,
+}
+
+/// 按文件摘要查到的版本及其所属项目。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HashedVersion {
+    /// 版本所属项目在平台上的 ID。
+    pub project_id: String,
+
+    /// 摘要对应的版本。
+    pub version: Version,
+}
+
+/// 按文件 ID 查到的文件及其运行环境。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IdentifiedFile {
+    /// 文件所属项目在平台上的 ID。
+    pub project_id: String,
+
+    /// 文件（版本）在平台上的 ID。
+    pub file_id: String,
+
+    /// 文件的下载地址、名称、大小与摘要。
+    pub file: VersionFile,
+
+    /// 平台标注的运行环境（小写的 `client`、`server`），未标注时为空。
+    pub environments: Vec<String>,
 }
@@ -11,23 +11,30 @@
 //! `total_hits`、`game_versions`），因此本模块中的反序列化结构体直接使用
 //! 同名 Rust 字段，无需 `#[serde(rename_all)]` 转换。
 
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
+use sealantern_infra::fs::HashAlgorithm;
 use sealantern_infra::net::{ClientProvider, NetClient};
 
 use crate::market::error::MarketError;
 use crate::market::fetcher;
 use crate::market::fetcher::Fetcher;
-use crate::market::fetcher::models::VersionFile;
+use crate::market::fetcher::models::{
+    DependencyKind, HashedVersion, VersionDependency, VersionFile,
+};
 use crate::market::models::*;
 use crate::observability;
 
 /// Modrinth API 的基础 URL。
 const MODRINTH_BASE: &str = "https://api.modrinth.com/v2";
 
+/// Modrinth 网站的基础 URL，用于拼接版本页面地址。
+const MODRINTH_SITE: &str = "https://modrinth.com";
+
 // ─── Modrinth API 响应结构体 ─────────────────────────────────────────────
 //
 // 以下结构体用于通过 `#[derive(Deserialize)]` 自动反序列化 Modrinth API
@@ -70,12 +77,24 @@ struct ModrinthProject {
 #[derive(Deserialize)]
 struct ModrinthVersion {
     id: String,
+    project_id: String,
     name: String,
     version_number: String,
     game_versions: Vec<String>,
     loaders: Vec<String>,
     downloads: u64,
     files: Vec<ModrinthVersionFile>,
+    #[serde(default)]
+    dependencies: Vec<ModrinthDependency>,
+}
+
+/// 版本声明的依赖项。
+#[derive(Deserialize)]
+struct ModrinthDependency {
+    version_id: Option<String>,
+    project_id: Option<String>,
+    file_name: Option<String>,
+    dependency_type: String,
 }
 
 /// 版本中关联的文件信息。
@@ -85,6 +104,26 @@ struct ModrinthVersionFile {
     filename: String,
     size: u64,
     primary: bool,
+    #[serde(default)]
+    hashes: BTreeMap<String, String>,
+}
+
+/// 按摘要反查版本 (`POST /version_files`) 的请求体。
+#[derive(Serialize)]
+struct ModrinthHashesRequest<'a> {
+    hashes: &'a [String],
+    algorithm: &'static str,
+}
+
+/// 按摘要查询最新版本 (`POST /version_files/update`) 的请求体。
+#[derive(Serialize)]
+struct ModrinthHashesUpdateRequest<'a> {
+    hashes: &'a [String],
+    algorithm: &'static str,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    loaders: &'a [String],
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    game_versions: &'a [String],
 }
 
 // ─── ModrinthFetcher ─────────────────────────────────────────────────────
@@ -277,34 +316,7 @@ impl Fetcher for ModrinthFetcher {
             .await
             .map_err(|e| MarketError::json("parse version list", "modrinth", e.to_string()))?;
 
-        // 将 Modrinth 版本转换为内部的 Version
-        let result: Vec<Version> = versions
-            .into_iter()
-            .map(|v| {
-                let files: Vec<VersionFile> = v
-                    .files
-                    .into_iter()
-                    .map(|f| VersionFile {
-                        url: f.url,
-                        filename: f.filename,
-                        size: f.size,
-                        primary: f.primary,
-                    })
-                    .collect();
-
-                Version {
-                    id: v.id,
-                    name: v.name,
-                    version_number: v.version_number,
-                    game_versions: v.game_versions,
-                    loaders: v.loaders,
-                    downloads: v.downloads,
-                    files,
-                }
-            })
-            .collect();
-
-        Ok(result)
+        Ok(versions.into_iter().map(version).collect())
     }
 
     /// 下载资源文件。
@@ -326,6 +338,65 @@ impl Fetcher for ModrinthFetcher {
         Ok(status)
     }
 
+    /// 按文件摘要批量反查版本。
+    ///
+    /// 调用 `POST /version_files`，Modrinth 支持 `sha1` 与 `sha512`；
+    /// 其他算法直接返回空表。
+    async fn versions_by_hashes(
+        &self,
+        algorithm: HashAlgorithm,
+        hashes: &[String],
+    ) -> Result<HashMap<String, HashedVersion>, MarketError> {
+        let Some(algorithm) = hash_algorithm_name(algorithm) else {
+            return Ok(HashMap::new());
+        };
+        if hashes.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let url = format!("{}/version_files", MODRINTH_BASE);
+        self.post_version_files(
+            &url,
+            &ModrinthHashesRequest { hashes, algorithm },
+            "look up versions by hash",
+        )
+        .await
+    }
+
+    /// 按文件摘要批量查询兼容的最新版本。
+    ///
+    /// 调用 `POST /version_files/update`，由 Modrinth 按 `loaders` 与
+    /// `game_versions` 过滤出各项目最新的版本。
+    async fn latest_versions_by_hashes(
+        &self,
+        algorithm: HashAlgorithm,
+        hashes: &[String],
+        loaders: &[String],
+        game_versions: &[String],
+    ) -> Result<HashMap<String, HashedVersion>, MarketError> {
+        let Some(algorithm) = hash_algorithm_name(algorithm) else {
+            return Ok(HashMap::new());
+        };
+        if hashes.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let url = format!("{}/version_files/update", MODRINTH_BASE);
+        self.post_version_files(
+            &url,
+            &ModrinthHashesUpdateRequest {
+                hashes,
+                algorithm,
+                loaders,
+                game_versions,
+            },
+            "look up latest versions by hash",
+        )
+        .await
+    }
+
+    fn version_page_url(&self, project_id: &str, version_id: &str) -> Option<String> {
+        Some(format!("{}/project/{}/version/{}", MODRINTH_SITE, project_id, version_id))
+    }
+
     async fn get_random_resources(&self, count: u32) -> Result<Vec<MarketResource>, MarketError> {
         let limit = count.min(10);
         let url = format!("{}/projects_random?count={}", MODRINTH_BASE, limit);
@@ -355,6 +426,95 @@ impl Fetcher for ModrinthFetcher {
     }
 }
 
+impl ModrinthFetcher {
+    /// 发送按摘要查询的 POST 请求，并把响应转换为以摘要为键的版本表。
+    async fn post_version_files<B: Serialize + Sync>(
+        &self,
+        url: &str,
+        body: &B,
+        operation: &'static str,
+    ) -> Result<HashMap<String, HashedVersion>, MarketError> {
+        let client = (self.client_provider)().map_err(|e| MarketError::config(e.to_string()))?;
+        let resp = client
+            .post(url)
+            .map_err(|e| MarketError::config(e.to_string()))?
+            .header("User-Agent", super::USER_AGENT)
+            .json(body)
+            .map_err(|e| MarketError::config(e.to_string()))?
+            .send()
+            .await
+            .map_err(|e| MarketError::http(operation, "modrinth", e.to_string()))?;
+        let versions: HashMap<String, ModrinthVersion> = resp
+            .json()
+            .await
+            .map_err(|e| MarketError::json(operation, "modrinth", e.to_string()))?;
+        Ok(hashed_versions(versions))
+    }
+}
+
+/// Modrinth 支持的摘要算法名。
+fn hash_algorithm_name(algorithm: HashAlgorithm) -> Option<&'static str> {
+    match algorithm {
+        HashAlgorithm::Sha1 | HashAlgorithm::Sha512 => Some(algorithm.name()),
+        HashAlgorithm::Sha256 => None,
+    }
+}
+
+/// 把按摘要查询的响应转换为以摘要为键的版本表。
+fn hashed_versions(versions: HashMap<String, ModrinthVersion>) -> HashMap<String, HashedVersion> {
+    versions
+        .into_iter()
+        .map(|(hash, v)| {
+            let project_id = v.project_id.clone();
+            (hash.to_ascii_lowercase(), HashedVersion { project_id, version: version(v) })
+        })
+        .collect()
+}
+
+/// 将 Modrinth 版本转换为内部的 [`Version`]。
+fn version(v: ModrinthVersion) -> Version {
+    let files: Vec<VersionFile> = v
+        .files
+        .into_iter()
+        .map(|f| VersionFile {
+            url: f.url,
+            filename: f.filename,
+            size: f.size,
+            primary: f.primary,
+            hashes: f.hashes,
+        })
+        .collect();
+
+    Version {
+        id: v.id,
+        name: v.name,
+        version_number: v.version_number,
+        game_versions: v.game_versions,
+        loaders: v.loaders,
+        downloads: v.downloads,
+        files,
+        dependencies: v
+            .dependencies
+            .into_iter()
+            .filter_map(|dependency| {
+                let kind = match dependency.dependency_type.as_str() {
+                    "required" => DependencyKind::Required,
+                    "optional" => DependencyKind::Optional,
+                    "embedded" => DependencyKind::Embedded,
+                    "incompatible" => DependencyKind::Incompatible,
+                    _ => return None,
+                };
+                Some(VersionDependency {
+                    project_id: dependency.project_id,
+                    version_id: dependency.version_id,
+                    name: dependency.file_name,
+                    kind,
+                })
+            })
+            .collect(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::market::fetcher::Fetcher;
@@ -412,6 +572,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn maps_recorded_version_files_response() {
+        let versions: HashMap<String, ModrinthVersion> =
+            serde_json::from_str(include_str!("fixtures/modrinth_version_files.json")).unwrap();
+        let versions = hashed_versions(versions);
+        // 响应中的摘要键统一转为小写，与本地计算的摘要对齐
+        let found = &versions["c2f8b8b2a56e1c3d0f4e9a7b6d5c4b3a2f1e0d9c"];
+        assert_eq!(found.project_id, "AANobbMI");
+        assert_eq!(found.version.id, "OihdIimA");
+        assert_eq!(found.version.version_number, "mc1.20.1-0.5.11");
+        assert_eq!(
+            found.version.files[0].hashes["sha1"],
+            "c2f8b8b2a56e1c3d0f4e9a7b6d5c4b3a2f1e0d9c"
+        );
+        assert_eq!(found.version.dependencies[0].kind, DependencyKind::Required);
+        assert_eq!(
+            test_fetcher().version_page_url(&found.project_id, &found.version.id),
+            Some("https://modrinth.com/project/AANobbMI/version/OihdIimA".to_owned())
+        );
+    }
+
+    #[tokio::test]
+    async fn unsupported_hash_algorithm_skips_request() {
+        let fetcher = ModrinthFetcher::with_provider(Box::new(|| {
+            Err(sealantern_infra::net::NetError::Config("模拟获取客户端失败".into()))
+        }));
+        let hashes = ["00".to_owned()];
+        assert!(
+            fetcher
+                .versions_by_hashes(HashAlgorithm::Sha256, &hashes)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let error = fetcher
+            .versions_by_hashes(HashAlgorithm::Sha1, &hashes)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, MarketError::Config(_)));
+    }
+
     #[tokio::test]
     async fn provider_failure_propagates_without_network() {
         // 假 provider：每次请求前被调用并返回失败，验证请求不会发出网络调用。
@@ -225,7 +225,7 @@ impl Fetcher for SpigetFetcher {
 
     /// 获取指定资源的所有版本列表。
     ///
-    /// 调用 `GET /resources/{id}/versions?size=100`。
+    /// 调用 `GET /resources/{id}/versions?size=100&sort=-releaseDate`，按发布时间从新到旧排列。
     /// Spiget 响应可能有两种格式：直接返回数组 `[...]`，或包裹在 `{"value": [...]}` 中。
     /// 本方法先尝试 `SpigetVersionList`（带 value 包裹），失败则回退到 `Vec<SpigetVersion>`。
     ///
@@ -235,7 +235,7 @@ impl Fetcher for SpigetFetcher {
     /// # Returns
     /// 版本对象列表，每个版本包含名称、下载量等信息。
     async fn get_resource_versions(&self, id: &str) -> Result<Vec<Version>, MarketError> {
-        let url = format!("{}/resources/{}/versions?size=100", SPIGET_BASE, id);
+        let url = format!("{}/resources/{}/versions?size=100&sort=-releaseDate", SPIGET_BASE, id);
         let client = (self.client_provider)().map_err(|e| MarketError::config(e.to_string()))?;
         let resp = client
             .get(&url)
@@ -270,6 +270,7 @@ impl Fetcher for SpigetFetcher {
                 loaders: vec!["spigot".to_string()],
                 downloads: v.downloads as u64,
                 files: vec![],
+                dependencies: vec![],
             })
             .collect();
 
@@ -329,6 +330,11 @@ impl Fetcher for SpigetFetcher {
             })
             .collect())
     }
+
+    /// SpigotMC 资源的更新日志页；Spiget 版本没有独立页面，统一指向资源的更新列表。
+    fn version_page_url(&self, project_id: &str, _version_id: &str) -> Option<String> {
+        Some(format!("https://www.spigotmc.org/resources/{}/updates", project_id))
+    }
 }
 
 /// 根据资源信息构建 Spiget 下载 URL。
@@ -2,11 +2,14 @@
 //!
 //! 定义 [`Fetcher`] trait，作为不同资源平台的统一数据获取接口。
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use sealantern_infra::fs::HashAlgorithm;
 
 use crate::market::error::MarketError;
+use crate::market::fetcher::models::{HashedVersion, IdentifiedFile};
 use crate::market::models::{MarketResource, ResourceInfo, SearchResult, Version};
 
 /// 统一资源获取器 trait，定义了对接不同资源平台所需的核心操作。
@@ -46,7 +49,7 @@ pub trait Fetcher: Send + Sync {
     /// - `id` — 资源在对应平台上的唯一标识符
     ///
     /// # Returns
-    /// 返回版本列表 [`Vec<Version>`]，每个版本包含版本号、发布日期、下载链接等信息。
+    /// 返回版本列表 [`Vec<Version>`]，按发布时间从新到旧排列，每个版本包含版本号、发布日期、下载链接等信息。
     async fn get_resource_versions(&self, id: &str) -> Result<Vec<Version>, MarketError>;
 
     /// 从指定 URL 下载资源到本地路径。
@@ -73,4 +76,62 @@ pub trait Fetcher: Send + Sync {
     /// # Returns
     /// 返回资源列表，实际数量可能少于 `count`。
     async fn get_random_resources(&self, count: u32) -> Result<Vec<MarketResource>, MarketError>;
+
+    /// 按文件摘要批量反查版本。
+    ///
+    /// 平台不支持按摘要查询时返回空表（默认实现）。
+    ///
+    /// # Parameters
+    /// - `algorithm` — 摘要算法
+    /// - `hashes` — 小写十六进制摘要列表
+    ///
+    /// # Returns
+    /// 以查询摘要为键的版本表，未收录的摘要不出现在结果中。
+    async fn versions_by_hashes(
+        &self,
+        _algorithm: HashAlgorithm,
+        _hashes: &[String],
+    ) -> Result<HashMap<String, HashedVersion>, MarketError> {
+        Ok(HashMap::new())
+    }
+
+    /// 按文件摘要批量查询所属项目中兼容指定加载器与游戏版本的最新版本。
+    ///
+    /// 平台不支持按摘要查询时返回空表（默认实现）。
+    ///
+    /// # Parameters
+    /// - `algorithm` — 摘要算法
+    /// - `hashes` — 小写十六进制摘要列表
+    /// - `loaders` — 可接受的加载器/平台，为空表示不限
+    /// - `game_versions` — 可接受的游戏版本，为空表示不限
+    ///
+    /// # Returns
+    /// 以查询摘要为键的最新版本表。
+    async fn latest_versions_by_hashes(
+        &self,
+        _algorithm: HashAlgorithm,
+        _hashes: &[String],
+        _loaders: &[String],
+        _game_versions: &[String],
+    ) -> Result<HashMap<String, HashedVersion>, MarketError> {
+        Ok(HashMap::new())
+    }
+
+    /// 按文件 ID 批量获取文件，供只记录文件 ID 的整合包清单解析下载地址。
+    ///
+    /// 平台不支持按文件 ID 查询时返回空列表（默认实现）。
+    ///
+    /// # Parameters
+    /// - `file_ids` — 文件 ID 列表
+    ///
+    /// # Returns
+    /// 查到的文件，顺序不保证与 `file_ids` 一致，不存在的 ID 不出现在结果中。
+    async fn files_by_ids(&self, _file_ids: &[String]) -> Result<Vec<IdentifiedFile>, MarketError> {
+        Ok(Vec::new())
+    }
+
+    /// 版本在平台网站上的页面地址（含更新日志），平台无稳定地址时为 `None`（默认实现）。
+    fn version_page_url(&self, _project_id: &str, _version_id: &str) -> Option<String> {
+        None
+    }
 }
@@ -0,0 +1,970 @@
+//! 市场资源安装规划与执行。
+//!
+//! 安装分两步：[`plan_install`] 按实例的游戏版本与加载器为目标资源挑选版本，递归解析
+//! 必需依赖并标记不兼容项，得到 [`InstallPlan`]；[`execute_install`] 先把计划中的文件
+//! 全部下载为 `.part` 并校验大小与摘要，全部通过后才移入 `plugins/` 或 `mods/`，
+//! 任一文件失败时不改动服务器目录；目标位置已有不受安装记录管理的同名文件时
+//! 拒绝安装（见 [`file_conflicts`]）。安装记录由调用方经
+//! [`record_installed_resources`](crate::market::installed::record_installed_resources) 写入。
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use sealantern_core::instance::{Instance, ServerMetadataSnapshot};
+use sealantern_infra::download::DownloadStatus;
+use sealantern_infra::fs::{
+    FsError, HashAlgorithm, ensure_dir, file_digest_hex, file_size, remove_if_exists,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::market::error::MarketError;
+use crate::market::fetcher::{DependencyKind, Fetcher, VersionFile};
+use crate::market::installed::{InstallDirectory, InstalledResource};
+use crate::market::models::{MarketSource, Version};
+
+/// 安装到 `mods/` 的加载器；其余加载器安装到 `plugins/`。
+const MOD_LOADERS: &[&str] = &["fabric", "quilt", "forge", "neoforge"];
+
+/// 等待下载完成时的轮询间隔。
+const DOWNLOAD_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// 安装后为落盘文件记录的摘要，供之后按文件哈希查询更新。
+pub(crate) const RECORDED_HASHES: [HashAlgorithm; 2] = [HashAlgorithm::Sha1, HashAlgorithm::Sha512];
+
+/// 安装目标实例的游戏版本与可加载平台。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstallTarget {
+    /// 实例的 Minecraft 版本；未知时不按游戏版本筛选。
+    pub game_version: Option<String>,
+    /// 实例可加载的平台（市场使用的加载器名），按优先级排列。
+    pub loaders: Vec<String>,
+}
+
+impl InstallTarget {
+    /// 从实例记录推断安装目标，优先使用服务端检查得到的元数据。
+    pub fn for_instance(instance: &Instance) -> Self {
+        Self::detect(&instance.core_type, &instance.game_version, instance.server_metadata.as_ref())
+    }
+
+    /// 由核心类型、游戏版本与可选的服务端元数据推断安装目标。
+    ///
+    /// 加载器依次取自元数据中的实现、生态与模组加载器组件，最后是核心类型；每个名称
+    /// 再展开为它能加载的市场平台（如 Purpur 也能加载 Paper、Spigot 与 Bukkit 插件）。
+    pub fn detect(
+        core_type: &str,
+        game_version: &str,
+        metadata: Option<&ServerMetadataSnapshot>,
+    ) -> Self {
+        let game_version = metadata
+            .and_then(|metadata| metadata.minecraft.as_ref())
+            .and_then(|minecraft| minecraft.version.clone())
+            .unwrap_or_else(|| game_version.to_owned());
+        let game_version = Some(game_version.trim().to_owned()).filter(|value| !value.is_empty());
+
+        let mut names = Vec::new();
+        if let Some(metadata) = metadata {
+            if let Some(identity) = &metadata.identity {
+                names.push(identity.implementation_key.clone());
+                names.extend(identity.ecosystems.iter().cloned());
+            }
+            names.extend(
+                metadata
+                    .components
+                    .iter()
+                    .filter(|component| component.kind == "mod_loader")
+                    .map(|component| component.key.clone()),
+            );
+        }
+        names.push(core_type.to_owned());
+
+        let mut loaders: Vec<String> = Vec::new();
+        for name in names {
+            for loader in compatible_loaders(&name.trim().to_ascii_lowercase()) {
+                if !loaders.iter().any(|existing| existing == loader) {
+                    loaders.push((*loader).to_owned());
+                }
+            }
+        }
+        Self { game_version, loaders }
+    }
+
+    /// 版本是否适用于该实例。
+    ///
+    /// 平台未声明加载器或游戏版本时视为不限制（如 Spiget 的版本列表）。
+    pub fn accepts(&self, version: &Version) -> bool {
+        let loader_matches = version.loaders.is_empty()
+            || version
+                .loaders
+                .iter()
+                .any(|loader| self.loaders.contains(&loader.to_ascii_lowercase()));
+        let game_version_matches = match &self.game_version {
+            None => true,
+            Some(game_version) => {
+                version.game_versions.is_empty()
+                    || version
+                        .game_versions
+                        .iter()
+                        .any(|supported| game_version_matches(supported, game_version))
+            }
+        };
+        loader_matches && game_version_matches
+    }
+
+    /// 版本文件应安装到的目录：取实例按优先级第一个被该版本支持的加载器判断。
+    pub fn directory_for(&self, version: &Version) -> InstallDirectory {
+        let loader = self
+            .loaders
+            .iter()
+            .find(|loader| {
+                version
+                    .loaders
+                    .iter()
+                    .any(|supported| supported.eq_ignore_ascii_case(loader))
+            })
+            .or_else(|| self.loaders.first());
+        match loader {
+            Some(loader) if MOD_LOADERS.contains(&loader.as_str()) => InstallDirectory::Mods,
+            _ => InstallDirectory::Plugins,
+        }
+    }
+}
+
+/// 服务端实现或生态名能加载的市场平台。
+fn compatible_loaders(name: &str) -> &'static [&'static str] {
+    match name {
+        "paper" => &["paper", "spigot", "bukkit"],
+        "purpur" => &["purpur", "paper", "spigot", "bukkit"],
+        "folia" => &["folia"],
+        "spigot" => &["spigot", "bukkit"],
+        "bukkit" | "craftbukkit" => &["bukkit"],
+        "velocity" => &["velocity"],
+        "bungee" | "bungeecord" => &["bungeecord"],
+        "waterfall" => &["waterfall", "bungeecord"],
+        "sponge" => &["sponge"],
+        "fabric" => &["fabric"],
+        "quilt" => &["quilt", "fabric"],
+        "forge" => &["forge"],
+        "neoforge" => &["neoforge"],
+        _ => &[],
+    }
+}
+
+/// 平台声明的游戏版本是否覆盖实例版本；支持 `1.20.x` 形式的通配。
+fn game_version_matches(supported: &str, game_version: &str) -> bool {
+    match supported.strip_suffix(".x") {
+        Some(prefix) => {
+            game_version == prefix
+                || game_version
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with('.'))
+        }
+        None => supported == game_version,
+    }
+}
+
+/// 在按发布时间从新到旧排列的版本中挑选最新的适用版本。
+pub fn select_version<'a>(versions: &'a [Version], target: &InstallTarget) -> Option<&'a Version> {
+    versions.iter().find(|version| target.accepts(version))
+}
+
+/// 计划中的一个待安装文件。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstallStep {
+    pub project_id: String,
+    pub version_id: String,
+    pub version_number: String,
+    pub file: VersionFile,
+    pub directory: InstallDirectory,
+    /// 把它作为必需依赖引入的项目；用户直接请求的资源为 `None`。
+    pub required_by: Option<String>,
+    /// 该版本的必需依赖（同一平台的项目 ID）。
+    pub dependencies: Vec<String>,
+    /// 该版本声明不兼容的项目 ID。
+    pub incompatible_with: Vec<String>,
+}
+
+/// 规划时发现的问题。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InstallIssue {
+    /// 没有适用于实例游戏版本与加载器的版本。
+    NoCompatibleVersion { project_id: String },
+    /// 指定（或依赖锁定）的版本不存在。
+    VersionNotFound {
+        project_id: String,
+        version_id: String,
+    },
+    /// 版本没有可直接下载的文件（如外部托管）。
+    NoDownloadableFile {
+        project_id: String,
+        version_id: String,
+    },
+    /// 计划或已安装的资源互不兼容。
+    Incompatible {
+        project_id: String,
+        conflicts_with: String,
+    },
+    /// 目标目录已有同名、但不属于任何安装记录的文件（如手动放入的插件），
+    /// 安装会覆盖它。
+    FileConflict {
+        project_id: String,
+        file_name: String,
+    },
+    /// 必需依赖不在同一平台上，需要手动安装；不阻止安装。
+    UnresolvedDependency {
+        required_by: String,
+        name: Option<String>,
+    },
+}
+
+impl InstallIssue {
+    /// 该问题是否阻止执行安装。
+    pub fn is_blocking(&self) -> bool {
+        !matches!(self, Self::UnresolvedDependency { .. })
+    }
+}
+
+impl fmt::Display for InstallIssue {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoCompatibleVersion { project_id } => {
+                write!(formatter, "{project_id} has no version for this instance")
+            }
+            Self::VersionNotFound { project_id, version_id } => {
+                write!(formatter, "{project_id} has no version {version_id}")
+            }
+            Self::NoDownloadableFile { project_id, version_id } => {
+                write!(formatter, "{project_id} version {version_id} has no downloadable file")
+            }
+            Self::Incompatible { project_id, conflicts_with } => {
+                write!(formatter, "{project_id} is incompatible with {conflicts_with}")
+            }
+            Self::FileConflict { project_id, file_name } => write!(
+                formatter,
+                "{file_name} for {project_id} already exists and was not installed from the market"
+            ),
+            Self::UnresolvedDependency { required_by, name } => write!(
+                formatter,
+                "{required_by} requires {} from another source",
+                name.as_deref().unwrap_or("an unknown dependency")
+            ),
+        }
+    }
+}
+
+/// 安装计划：按解析顺序排列的文件与发现的问题。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstallPlan {
+    pub source: MarketSource,
+    pub target: InstallTarget,
+    pub steps: Vec<InstallStep>,
+    pub issues: Vec<InstallIssue>,
+}
+
+impl InstallPlan {
+    /// 是否存在阻止安装的问题。
+    pub fn is_blocked(&self) -> bool {
+        self.issues.iter().any(InstallIssue::is_blocking)
+    }
+}
+
+/// 安装规划或执行失败。
+#[derive(Debug)]
+pub enum InstallError {
+    /// 无法识别实例能加载的插件 / 模组平台（如原版服务端）。
+    UnsupportedInstance,
+    /// 计划存在阻止安装的问题。
+    Blocked(Vec<InstallIssue>),
+    /// 市场请求或下载失败。
+    Market(MarketError),
+    /// 下载的文件大小或摘要与平台声明不符。
+    Verification { file_name: String, reason: String },
+    /// 文件读写失败。
+    Storage(FsError),
+}
+
+impl fmt::Display for InstallError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedInstance => {
+                write!(formatter, "instance does not support plugins or mods")
+            }
+            Self::Blocked(issues) => {
+                let issues = issues
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                write!(formatter, "install plan is blocked: {issues}")
+            }
+            Self::Market(error) => write!(formatter, "{error}"),
+            Self::Verification { file_name, reason } => {
+                write!(formatter, "downloaded file {file_name} failed verification: {reason}")
+            }
+            Self::Storage(error) => write!(formatter, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for InstallError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Market(error) => Some(error),
+            Self::Storage(error) => Some(error),
+            Self::UnsupportedInstance | Self::Blocked(_) | Self::Verification { .. } => None,
+        }
+    }
+}
+
+impl From<MarketError> for InstallError {
+    fn from(error: MarketError) -> Self {
+        Self::Market(error)
+    }
+}
+
+impl From<FsError> for InstallError {
+    fn from(error: FsError) -> Self {
+        Self::Storage(error)
+    }
+}
+
+/// 为资源及其必需依赖生成安装计划。
+///
+/// `version_id` 为空时挑选最新的适用版本。已安装（`installed` 中同一来源）的依赖不会
+/// 重复安装，但其不兼容声明参与检查；被请求的资源本身总会重新规划，用于升级或重装。
+pub async fn plan_install(
+    fetcher: &dyn Fetcher,
+    source: MarketSource,
+    target: &InstallTarget,
+    installed: &[InstalledResource],
+    project_id: &str,
+    version_id: Option<&str>,
+) -> Result<InstallPlan, InstallError> {
+    if target.loaders.is_empty() {
+        return Err(InstallError::UnsupportedInstance);
+    }
+
+    let mut steps: Vec<InstallStep> = Vec::new();
+    let mut issues = Vec::new();
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::from([(project_id.to_owned(), version_id.map(str::to_owned), None)]);
+
+    while let Some((project_id, pinned, required_by)) = queue.pop_front() {
+        if !seen.insert(project_id.clone()) {
+            continue;
+        }
+        if required_by.is_some()
+            && installed
+                .iter()
+                .any(|resource| resource.is(source, &project_id))
+        {
+            continue;
+        }
+
+        let versions = fetcher.get_resource_versions(&project_id).await?;
+        let version = match &pinned {
+            Some(version_id) => versions.iter().find(|version| &version.id == version_id),
+            None => select_version(&versions, target),
+        };
+        let Some(version) = version else {
+            issues.push(match pinned {
+                Some(version_id) => InstallIssue::VersionNotFound { project_id, version_id },
+                None => InstallIssue::NoCompatibleVersion { project_id },
+            });
+            continue;
+        };
+        let Some(file) = install_file(fetcher, source, &project_id, version).await? else {
+            issues.push(InstallIssue::NoDownloadableFile {
+                project_id,
+                version_id: version.id.clone(),
+            });
+            continue;
+        };
+
+        let mut dependencies = Vec::new();
+        let mut incompatible_with = Vec::new();
+        for dependency in &version.dependencies {
+            match (dependency.kind, &dependency.project_id) {
+                (DependencyKind::Required, Some(dependency_id)) => {
+                    if !dependencies.contains(dependency_id) {
+                        dependencies.push(dependency_id.clone());
+                    }
+                    queue.push_back((
+                        dependency_id.clone(),
+                        dependency.version_id.clone(),
+                        Some(project_id.clone()),
+                    ));
+                }
+                (DependencyKind::Required, None) => {
+                    issues.push(InstallIssue::UnresolvedDependency {
+                        required_by: project_id.clone(),
+                        name: dependency.name.clone(),
+                    });
+                }
+                (DependencyKind::Incompatible, Some(dependency_id)) => {
+                    incompatible_with.push(dependency_id.clone());
+                }
+                _ => {}
+            }
+        }
+
+        steps.push(InstallStep {
+            directory: target.directory_for(version),
+            version_id: version.id.clone(),
+            version_number: version.version_number.clone(),
+            file,
+            project_id,
+            required_by,
+            dependencies,
+            incompatible_with,
+        });
+    }
+
+    issues.extend(incompatibilities(source, &steps, installed));
+    Ok(InstallPlan {
+        source,
+        target: target.clone(),
+        steps,
+        issues,
+    })
+}
+
+/// 版本要安装的文件：主文件优先。
+///
+/// Spiget 的版本不带文件，此时回退到资源的最新下载地址；外部托管的资源无法直接下载。
+pub(crate) async fn install_file(
+    fetcher: &dyn Fetcher,
+    source: MarketSource,
+    project_id: &str,
+    version: &Version,
+) -> Result<Option<VersionFile>, MarketError> {
+    let file = match version
+        .files
+        .iter()
+        .find(|file| file.primary)
+        .or_else(|| version.files.first())
+    {
+        Some(file) => Some(file.clone()),
+        None if source == MarketSource::Spiget => {
+            let resource = fetcher.get_resource(project_id).await?;
+            (!resource.external && !resource.download_url.is_empty()).then(|| VersionFile {
+                url: resource.download_url,
+                filename: format!("{}.jar", sanitize_file_stem(&resource.name)),
+                size: 0,
+                primary: true,
+                hashes: Default::default(),
+            })
+        }
+        None => None,
+    };
+    Ok(file.filter(|file| is_plain_file_name(&file.filename)))
+}
+
+/// 把资源名转换为可用作文件名的形式。
+fn sanitize_file_stem(name: &str) -> String {
+    let stem: String = name
+        .trim()
+        .chars()
+        .map(|ch| {
+            if ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.') {
+                ch
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let stem = stem.trim_matches('.');
+    if stem.is_empty() {
+        "plugin".to_owned()
+    } else {
+        stem.to_owned()
+    }
+}
+
+/// 平台给出的文件名是否为不含目录的普通文件名。
+pub(crate) fn is_plain_file_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with('.')
+        && !name.contains(['/', '\\'])
+        && Path::new(name).file_name().and_then(|file| file.to_str()) == Some(name)
+}
+
+/// 计划内、以及计划与已安装资源之间声明的不兼容。
+fn incompatibilities(
+    source: MarketSource,
+    steps: &[InstallStep],
+    installed: &[InstalledResource],
+) -> Vec<InstallIssue> {
+    // 被计划重装的记录以计划中的新版本为准
+    let remaining: Vec<&InstalledResource> = installed
+        .iter()
+        .filter(|resource| {
+            resource.source == source
+                && !steps
+                    .iter()
+                    .any(|step| step.project_id == resource.project_id)
+        })
+        .collect();
+    let present = |project_id: &str| {
+        steps.iter().any(|step| step.project_id == project_id)
+            || remaining
+                .iter()
+                .any(|resource| resource.project_id == project_id)
+    };
+
+    let mut issues = Vec::new();
+    for step in steps {
+        for other in &step.incompatible_with {
+            if present(other) {
+                issues.push(InstallIssue::Incompatible {
+                    project_id: step.project_id.clone(),
+                    conflicts_with: other.clone(),
+                });
+            }
+        }
+    }
+    for resource in remaining {
+        for other in &resource.incompatible_with {
+            if steps.iter().any(|step| &step.project_id == other) {
+                issues.push(InstallIssue::Incompatible {
+                    project_id: other.clone(),
+                    conflicts_with: resource.project_id.clone(),
+                });
+            }
+        }
+    }
+    issues
+}
+
+/// 找出会覆盖未受管理文件的安装步骤。
+///
+/// 目标文件已存在、且不是 `installed` 中任何记录对应的文件时视为冲突。
+pub async fn file_conflicts(
+    plan: &InstallPlan,
+    server_dir: &Path,
+    installed: &[InstalledResource],
+) -> Vec<InstallIssue> {
+    let mut conflicts = Vec::new();
+    for step in &plan.steps {
+        let relative = Path::new(step.directory.dir_name()).join(&step.file.filename);
+        let managed = installed
+            .iter()
+            .any(|resource| resource.relative_path() == relative);
+        if !managed
+            && tokio::fs::try_exists(server_dir.join(&relative))
+                .await
+                .unwrap_or(true)
+        {
+            conflicts.push(InstallIssue::FileConflict {
+                project_id: step.project_id.clone(),
+                file_name: step.file.filename.clone(),
+            });
+        }
+    }
+    conflicts
+}
+
+/// 执行安装计划，返回应写入的安装记录。
+///
+/// 全部文件下载并校验通过后才移入目标目录；升级已安装的资源且文件名改变时删除旧文件。
+/// 目标文件已存在且不受安装记录管理时拒绝安装，不覆盖手动放入的文件。
+pub async fn execute_install(
+    fetcher: &dyn Fetcher,
+    plan: &InstallPlan,
+    server_dir: &Path,
+    installed: &[InstalledResource],
+) -> Result<Vec<InstalledResource>, InstallError> {
+    let mut blocking: Vec<InstallIssue> = plan
+        .issues
+        .iter()
+        .filter(|issue| issue.is_blocking())
+        .cloned()
+        .collect();
+    for conflict in file_conflicts(plan, server_dir, installed).await {
+        if !blocking.contains(&conflict) {
+            blocking.push(conflict);
+        }
+    }
+    if !blocking.is_empty() {
+        return Err(InstallError::Blocked(blocking));
+    }
+
+    let mut staged: Vec<(&InstallStep, PathBuf, PathBuf)> = Vec::new();
+    for step in &plan.steps {
+        let directory = server_dir.join(step.directory.dir_name());
+        let destination = directory.join(&step.file.filename);
+        let partial = directory.join(format!("{}.part", step.file.filename));
+        let result = async {
+            ensure_dir(&directory).await?;
+            download(fetcher, &step.file.url, &partial).await?;
+            verify_file(&partial, &step.file).await
+        }
+        .await;
+        staged.push((step, partial, destination));
+        if let Err(error) = result {
+            for (_, partial, _) in &staged {
+                let _ = remove_if_exists(partial).await;
+            }
+            return Err(error);
+        }
+    }
+
+    let installed_at_unix_secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs());
+    let mut records = Vec::with_capacity(staged.len());
+    for (step, partial, destination) in staged {
+        if let Some(previous) = installed
+            .iter()
+            .find(|resource| resource.is(plan.source, &step.project_id))
+        {
+            let previous_path = server_dir.join(previous.relative_path());
+            if previous_path != destination {
+                remove_if_exists(&previous_path).await?;
+            }
+        }
+        tokio::fs::rename(&partial, &destination)
+            .await
+            .map_err(|source| FsError::Io {
+                operation: "move installed file",
+                path: destination.clone(),
+                source,
+            })?;
+
+        let mut hashes = step.file.hashes.clone();
+        for algorithm in RECORDED_HASHES {
+            hashes.insert(
+                algorithm.name().to_owned(),
+                file_digest_hex(&destination, algorithm).await?,
+            );
+        }
+        records.push(InstalledResource {
+            source: plan.source,
+            project_id: step.project_id.clone(),
+            version_id: step.version_id.clone(),
+            version_number: step.version_number.clone(),
+            file_name: step.file.filename.clone(),
+            directory: step.directory,
+            hashes,
+            dependencies: step.dependencies.clone(),
+            incompatible_with: step.incompatible_with.clone(),
+            explicit: step.required_by.is_none(),
+            installed_at_unix_secs,
+        });
+    }
+    Ok(records)
+}
+
+/// 下载文件并等待完成。
+pub(crate) async fn download(
+    fetcher: &dyn Fetcher,
+    url: &str,
+    destination: &Path,
+) -> Result<(), InstallError> {
+    let destination = destination
+        .to_str()
+        .ok_or_else(|| MarketError::download("destination path is not valid UTF-8"))?;
+    let status = fetcher.download_resource(url, destination).await?;
+    wait_for_download(&status).await?;
+    Ok(())
+}
+
+async fn wait_for_download(status: &Arc<DownloadStatus>) -> Result<(), MarketError> {
+    loop {
+        let snapshot = status.snapshot().await;
+        if snapshot.is_finished {
+            return match snapshot.error {
+                Some(error) => Err(MarketError::download(error)),
+                None => Ok(()),
+            };
+        }
+        tokio::time::sleep(DOWNLOAD_POLL_INTERVAL).await;
+    }
+}
+
+/// 校验下载文件的大小与平台提供的最强摘要；平台未提供可用摘要时只校验大小。
+pub(crate) async fn verify_file(path: &Path, file: &VersionFile) -> Result<(), InstallError> {
+    let verification =
+        |reason: String| InstallError::Verification { file_name: file.filename.clone(), reason };
+    if file.size > 0 {
+        let actual = file_size(path).await?;
+        if actual != file.size {
+            return Err(verification(format!("expected {} bytes, got {actual}", file.size)));
+        }
+    }
+    let Some((algorithm, expected)) = HashAlgorithm::STRONGEST_FIRST
+        .into_iter()
+        .find_map(|algorithm| Some((algorithm, file.hashes.get(algorithm.name())?)))
+    else {
+        return Ok(());
+    };
+    let actual = file_digest_hex(path, algorithm).await?;
+    if !actual.eq_ignore_ascii_case(expected) {
+        return Err(verification(format!("{} mismatch", algorithm.name())));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::market::fetcher::{MemoryFetcher, VersionDependency};
+
+    fn version(
+        project_id: &str,
+        number: &str,
+        game_versions: &[&str],
+        loaders: &[&str],
+        dependencies: &[(&str, DependencyKind)],
+    ) -> Version {
+        let filename = format!("{project_id}-{number}.jar");
+        Version {
+            id: format!("{project_id}-{number}"),
+            name: number.to_owned(),
+            version_number: number.to_owned(),
+            game_versions: game_versions
+                .iter()
+                .map(|value| (*value).to_owned())
+                .collect(),
+            loaders: loaders.iter().map(|value| (*value).to_owned()).collect(),
+            downloads: 0,
+            files: vec![VersionFile {
+                url: format!("https://cdn.example/{filename}"),
+                filename,
+                size: 0,
+                primary: true,
+                hashes: BTreeMap::new(),
+            }],
+            dependencies: dependencies
+                .iter()
+                .map(|(id, kind)| VersionDependency {
+                    project_id: Some((*id).to_owned()),
+                    version_id: None,
+                    name: None,
+                    kind: *kind,
+                })
+                .collect(),
+        }
+    }
+
+    fn fabric_target() -> InstallTarget {
+        InstallTarget::detect("quilt", "1.20.1", None)
+    }
+
+    #[test]
+    fn detects_loaders_and_selects_versions() {
+        let purpur = InstallTarget::detect("Purpur", " 1.21 ", None);
+        assert_eq!(purpur.game_version.as_deref(), Some("1.21"));
+        assert_eq!(purpur.loaders, ["purpur", "paper", "spigot", "bukkit"]);
+        assert!(
+            InstallTarget::detect("vanilla", "1.21", None)
+                .loaders
+                .is_empty()
+        );
+
+        let target = fabric_target();
+        assert_eq!(target.loaders, ["quilt", "fabric"]);
+        let versions = [
+            version("sodium", "0.6", &["1.21"], &["fabric"], &[]),
+            version("sodium", "0.5-forge", &["1.20.1"], &["forge"], &[]),
+            version("sodium", "0.5", &["1.20.x"], &["Fabric"], &[]),
+        ];
+        let selected = select_version(&versions, &target).unwrap();
+        assert_eq!(selected.version_number, "0.5");
+        assert_eq!(target.directory_for(selected), InstallDirectory::Mods);
+        assert!(!game_version_matches("1.20.x", "1.201"));
+
+        let paper = InstallTarget::detect("paper", "1.21", None);
+        assert_eq!(
+            paper.directory_for(&version("luckperms", "5", &[], &["bukkit"], &[])),
+            InstallDirectory::Plugins
+        );
+    }
+
+    #[tokio::test]
+    async fn plans_required_dependencies_and_flags_incompatibilities() {
+        let mut fetcher = MemoryFetcher::default();
+        fetcher.versions.insert(
+            "addon".to_owned(),
+            vec![version(
+                "addon",
+                "2.0",
+                &["1.20.1"],
+                &["fabric"],
+                &[
+                    ("api", DependencyKind::Required),
+                    ("extras", DependencyKind::Optional),
+                    ("legacy", DependencyKind::Incompatible),
+                ],
+            )],
+        );
+        fetcher.versions.insert(
+            "api".to_owned(),
+            // 依赖环不会导致重复规划
+            vec![version(
+                "api",
+                "1.0",
+                &["1.20.1"],
+                &["fabric"],
+                &[("addon", DependencyKind::Required), ("lib", DependencyKind::Required)],
+            )],
+        );
+        fetcher
+            .versions
+            .insert("lib".to_owned(), vec![version("lib", "1.0", &["1.19"], &["fabric"], &[])]);
+
+        let plan =
+            plan_install(&fetcher, MarketSource::Modrinth, &fabric_target(), &[], "addon", None)
+                .await
+                .unwrap();
+        assert_eq!(
+            plan.steps
+                .iter()
+                .map(|step| (step.project_id.as_str(), step.required_by.as_deref()))
+                .collect::<Vec<_>>(),
+            [("addon", None), ("api", Some("addon"))]
+        );
+        assert_eq!(
+            plan.issues,
+            [InstallIssue::NoCompatibleVersion { project_id: "lib".to_owned() }]
+        );
+        assert!(plan.is_blocked());
+
+        // 已安装的依赖不再重复下载，但已安装的不兼容项会被标记
+        let legacy = InstalledResource {
+            source: MarketSource::Modrinth,
+            project_id: "legacy".to_owned(),
+            version_id: "legacy-1".to_owned(),
+            version_number: "1".to_owned(),
+            file_name: "legacy.jar".to_owned(),
+            directory: InstallDirectory::Mods,
+            hashes: BTreeMap::new(),
+            dependencies: Vec::new(),
+            incompatible_with: Vec::new(),
+            explicit: true,
+            installed_at_unix_secs: 0,
+        };
+        let api = InstalledResource {
+            project_id: "api".to_owned(),
+            ..legacy.clone()
+        };
+        let plan = plan_install(
+            &fetcher,
+            MarketSource::Modrinth,
+            &fabric_target(),
+            &[legacy, api],
+            "addon",
+            Some("addon-2.0"),
+        )
+        .await
+        .unwrap();
+        assert_eq!(plan.steps.len(), 1);
+        assert_eq!(
+            plan.issues,
+            [InstallIssue::Incompatible {
+                project_id: "addon".to_owned(),
+                conflicts_with: "legacy".to_owned(),
+            }]
+        );
+
+        let error = plan_install(
+            &fetcher,
+            MarketSource::Modrinth,
+            &InstallTarget::detect("vanilla", "1.20.1", None),
+            &[],
+            "addon",
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(error, InstallError::UnsupportedInstance));
+    }
+
+    #[tokio::test]
+    async fn installs_only_after_every_file_verifies() {
+        let server_dir = tempfile::tempdir().unwrap();
+        let mut fetcher = MemoryFetcher::default();
+        let mut addon =
+            version("addon", "1.0", &[], &["fabric"], &[("api", DependencyKind::Required)]);
+        addon.files[0].size = 5;
+        addon.files[0]
+            .hashes
+            .insert("sha256".to_owned(), sealantern_infra::fs::sha256_hex(b"addon"));
+        let mut api = version("api", "1.0", &[], &["fabric"], &[]);
+        api.files[0]
+            .hashes
+            .insert("sha1".to_owned(), "0".repeat(40));
+        fetcher
+            .contents
+            .insert(addon.files[0].url.clone(), b"addon".to_vec());
+        fetcher
+            .contents
+            .insert(api.files[0].url.clone(), b"api".to_vec());
+        fetcher.versions.insert("addon".to_owned(), vec![addon]);
+        fetcher.versions.insert("api".to_owned(), vec![api]);
+
+        let plan =
+            plan_install(&fetcher, MarketSource::Modrinth, &fabric_target(), &[], "addon", None)
+                .await
+                .unwrap();
+        let error = execute_install(&fetcher, &plan, server_dir.path(), &[])
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, InstallError::Verification { ref file_name, .. } if file_name == "api-1.0.jar")
+        );
+        assert_eq!(
+            std::fs::read_dir(server_dir.path().join("mods"))
+                .unwrap()
+                .count(),
+            0
+        );
+
+        let mut plan = plan;
+        plan.steps[1].file.hashes.clear();
+        let records = execute_install(&fetcher, &plan, server_dir.path(), &[])
+            .await
+            .unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records[0].explicit && !records[1].explicit);
+        assert_eq!(records[0].dependencies, ["api"]);
+        assert_eq!(
+            records[0].hashes["sha1"],
+            sealantern_infra::fs::file_digest_hex(
+                server_dir.path().join("mods/addon-1.0.jar"),
+                HashAlgorithm::Sha1
+            )
+            .await
+            .unwrap()
+        );
+        assert!(records[0].hashes.contains_key("sha512"));
+        assert_eq!(std::fs::read(server_dir.path().join("mods/api-1.0.jar")).unwrap(), b"api");
+
+        // 已记录的文件可以覆盖；手动放入的同名文件阻止安装且保持原样
+        let records = execute_install(&fetcher, &plan, server_dir.path(), &records)
+            .await
+            .unwrap();
+        assert_eq!(records.len(), 2);
+        let manual_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(manual_dir.path().join("mods")).unwrap();
+        std::fs::write(manual_dir.path().join("mods/api-1.0.jar"), b"manual").unwrap();
+        assert_eq!(
+            file_conflicts(&plan, manual_dir.path(), &[]).await,
+            [InstallIssue::FileConflict {
+                project_id: "api".to_owned(),
+                file_name: "api-1.0.jar".to_owned(),
+            }]
+        );
+        let error = execute_install(&fetcher, &plan, manual_dir.path(), &[])
+            .await
+            .unwrap_err();
+        assert!(matches!(error, InstallError::Blocked(ref issues) if issues.len() == 1));
+        assert_eq!(std::fs::read(manual_dir.path().join("mods/api-1.0.jar")).unwrap(), b"manual");
+        assert!(!manual_dir.path().join("mods/addon-1.0.jar").exists());
+    }
+}
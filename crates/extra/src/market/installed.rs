@@ -0,0 +1,244 @@
+//! 实例已安装市场资源的记录。
+//!
+//! 通过 [`install`](crate::market::install) 安装的每个资源（含自动安装的依赖）都会在
+//! 服务器目录下的 JSON 文件中留下一条记录，供后续的更新检查与卸载使用。
+//! 手动放入 `plugins/`、`mods/` 的文件不在记录之内。
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use sealantern_infra::fs::{DataLimit, FsError, read_json};
+use sealantern_infra::persistence::ConfigFile;
+use serde::{Deserialize, Serialize};
+
+use crate::market::models::MarketSource;
+
+/// 安装记录文件名（存放在服务器目录下）。
+pub const INSTALLED_RESOURCES_FILE: &str = "sea_lantern_installed_resources.json";
+
+/// 安装记录文件的读取上限。
+const INSTALLED_RESOURCES_READ_LIMIT: DataLimit = DataLimit::new(4 * 1024 * 1024);
+
+/// 资源文件安装到的服务器子目录。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallDirectory {
+    /// `plugins/`，Bukkit 系服务端与代理端的插件目录。
+    Plugins,
+    /// `mods/`，Fabric / Quilt / Forge / NeoForge 的模组目录。
+    Mods,
+}
+
+impl InstallDirectory {
+    /// 相对服务器目录的目录名。
+    pub const fn dir_name(self) -> &'static str {
+        match self {
+            Self::Plugins => "plugins",
+            Self::Mods => "mods",
+        }
+    }
+}
+
+/// 一条已安装资源的记录。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstalledResource {
+    pub source: MarketSource,
+    pub project_id: String,
+    pub version_id: String,
+    pub version_number: String,
+    /// 安装后的文件名（不含目录）。
+    pub file_name: String,
+    pub directory: InstallDirectory,
+    /// 安装时对落盘文件计算的摘要，键为小写算法名。
+    #[serde(default)]
+    pub hashes: BTreeMap<String, String>,
+    /// 该版本声明的必需依赖（同一平台的项目 ID）。
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+    /// 该版本声明不兼容的项目 ID。
+    #[serde(default)]
+    pub incompatible_with: Vec<String>,
+    /// 由用户直接安装为 `true`；作为依赖自动安装为 `false`。
+    pub explicit: bool,
+    pub installed_at_unix_secs: u64,
+}
+
+impl InstalledResource {
+    /// 安装文件相对服务器目录的路径。
+    pub fn relative_path(&self) -> PathBuf {
+        Path::new(self.directory.dir_name()).join(&self.file_name)
+    }
+
+    /// 是否为指定来源的指定项目。
+    pub fn is(&self, source: MarketSource, project_id: &str) -> bool {
+        self.source == source && self.project_id == project_id
+    }
+}
+
+/// 安装记录文件的根对象。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InstalledResourceList {
+    pub resources: Vec<InstalledResource>,
+}
+
+fn installed_resources_path(server_dir: &Path) -> PathBuf {
+    server_dir.join(INSTALLED_RESOURCES_FILE)
+}
+
+/// 读取服务器目录下的安装记录；记录文件不存在时返回空列表。
+pub async fn read_installed_resources(
+    server_dir: &Path,
+) -> Result<Vec<InstalledResource>, FsError> {
+    let path = installed_resources_path(server_dir);
+    if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+        return Ok(Vec::new());
+    }
+    let list: InstalledResourceList = read_json(&path, INSTALLED_RESOURCES_READ_LIMIT).await?;
+    Ok(list.resources)
+}
+
+/// 写入一批安装记录，同一来源的同一项目覆盖旧记录。
+///
+/// 旧记录为用户直接安装时，即使本次作为依赖重装也保留 `explicit`。
+pub async fn record_installed_resources(
+    server_dir: &Path,
+    installed: Vec<InstalledResource>,
+) -> Result<Vec<InstalledResource>, FsError> {
+    let list = ConfigFile::update_persisted(
+        installed_resources_path(server_dir),
+        InstalledResourceList::default(),
+        false,
+        |list| {
+            for mut resource in installed {
+                match list
+                    .resources
+                    .iter_mut()
+                    .find(|existing| existing.is(resource.source, &resource.project_id))
+                {
+                    Some(existing) => {
+                        resource.explicit |= existing.explicit;
+                        *existing = resource;
+                    }
+                    None => list.resources.push(resource),
+                }
+            }
+        },
+    )
+    .await?;
+    Ok(list.resources)
+}
+
+/// 删除一条安装记录，返回被删除的记录。
+pub async fn remove_installed_resource(
+    server_dir: &Path,
+    source: MarketSource,
+    project_id: &str,
+) -> Result<Option<InstalledResource>, FsError> {
+    let path = installed_resources_path(server_dir);
+    if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+        return Ok(None);
+    }
+    let mut removed = None;
+    ConfigFile::update_persisted_if_changed(
+        path,
+        InstalledResourceList::default(),
+        false,
+        |list| {
+            let index = list
+                .resources
+                .iter()
+                .position(|resource| resource.is(source, project_id));
+            removed = index.map(|index| list.resources.remove(index));
+            removed.is_some()
+        },
+    )
+    .await?;
+    Ok(removed)
+}
+
+/// 以必需依赖形式引用指定项目的其他记录。
+pub fn dependents<'a>(
+    resources: &'a [InstalledResource],
+    source: MarketSource,
+    project_id: &str,
+) -> Vec<&'a InstalledResource> {
+    resources
+        .iter()
+        .filter(|resource| {
+            resource.source == source
+                && resource.project_id != project_id
+                && resource
+                    .dependencies
+                    .iter()
+                    .any(|dependency| dependency == project_id)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resource(project_id: &str, dependencies: &[&str], explicit: bool) -> InstalledResource {
+        InstalledResource {
+            source: MarketSource::Modrinth,
+            project_id: project_id.to_owned(),
+            version_id: format!("{project_id}-v1"),
+            version_number: "1.0.0".to_owned(),
+            file_name: format!("{project_id}.jar"),
+            directory: InstallDirectory::Mods,
+            hashes: BTreeMap::new(),
+            dependencies: dependencies.iter().map(|id| (*id).to_owned()).collect(),
+            incompatible_with: Vec::new(),
+            explicit,
+            installed_at_unix_secs: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn records_replace_and_remove_installs() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(
+            read_installed_resources(dir.path())
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert!(
+            remove_installed_resource(dir.path(), MarketSource::Modrinth, "api")
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(!dir.path().join(INSTALLED_RESOURCES_FILE).exists());
+
+        record_installed_resources(
+            dir.path(),
+            vec![resource("api", &[], true), resource("addon", &["api"], true)],
+        )
+        .await
+        .unwrap();
+        // 作为依赖重装不会把用户直接安装的记录降级
+        let mut reinstalled = resource("api", &[], false);
+        reinstalled.version_id = "api-v2".to_owned();
+        let resources = record_installed_resources(dir.path(), vec![reinstalled])
+            .await
+            .unwrap();
+        assert_eq!(resources.len(), 2);
+        assert_eq!(resources[0].version_id, "api-v2");
+        assert!(resources[0].explicit);
+        assert_eq!(resources[0].relative_path(), Path::new("mods").join("api.jar"));
+
+        let dependents = dependents(&resources, MarketSource::Modrinth, "api");
+        assert_eq!(dependents.len(), 1);
+        assert_eq!(dependents[0].project_id, "addon");
+        assert!(super::dependents(&resources, MarketSource::Hangar, "api").is_empty());
+
+        let removed = remove_installed_resource(dir.path(), MarketSource::Modrinth, "addon")
+            .await
+            .unwrap();
+        assert_eq!(removed.unwrap().project_id, "addon");
+        let resources = read_installed_resources(dir.path()).await.unwrap();
+        assert_eq!(resources.len(), 1);
+    }
+}
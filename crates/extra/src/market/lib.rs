@@ -1,18 +1,46 @@
 //! 资源市场（Market）模块。
 //!
 //! 本模块提供了与 Minecraft 插件/模组资源市场交互的能力，
-//! 支持从 Spiget 和 Modrinth 等源获取资源信息、搜索资源、
+//! 支持从 Spiget、Modrinth、CurseForge 和 Hangar 等源获取资源信息、搜索资源、
 //! 查询版本详情及下载资源文件等功能。
 //!
 //! 模块包含以下子模块：
+//! - [`curseforge_pack`]：CurseForge 客户端整合包（`manifest.json`）的服务端安装。
 //! - [`error`]：市场操作相关的错误类型定义。
 //! - [`fetcher`]：与远程市场 API 交互的抓取器实现。
+//! - [`install`]：把资源及其依赖安装到实例的规划与执行。
+//! - [`installed`]：实例已安装资源的记录。
 //! - [`models`]：资源、版本、搜索结果等数据模型定义。
+//! - [`mrpack`]：Modrinth 整合包（`.mrpack`）的服务端安装。
+//! - [`update`]：已安装插件与模组的更新检查、替换与回滚。
 
+pub mod curseforge_pack;
 pub mod error;
 pub mod fetcher;
+pub mod install;
+pub mod installed;
 pub mod models;
+pub mod mrpack;
+pub mod update;
 
+pub use curseforge_pack::{CurseForgePackError, CurseForgePackInstall, install_curseforge_pack};
 pub use error::MarketError;
-pub use fetcher::{Fetcher, ModrinthFetcher, SpigetFetcher, VersionFile};
+pub use fetcher::{
+    CurseForgeFetcher, DependencyKind, Fetcher, HangarFetcher, HashedVersion, IdentifiedFile,
+    ModrinthFetcher, SpigetFetcher, VersionDependency, VersionFile,
+};
+pub use install::{
+    InstallError, InstallIssue, InstallPlan, InstallStep, InstallTarget, execute_install,
+    file_conflicts, plan_install, select_version,
+};
+pub use installed::{
+    InstallDirectory, InstalledResource, dependents, read_installed_resources,
+    record_installed_resources, remove_installed_resource,
+};
 pub use models::{MarketResource, MarketSource, ResourceInfo, SearchResult, Version};
+pub use mrpack::{MrpackError, install_mrpack};
+pub use update::{
+    AppliedUpdates, ExtensionUpdate, RollbackError, UpdateRollback, VersionSummary, apply_updates,
+    check_updates, read_update_rollbacks, record_update_rollbacks, rollback_update,
+    scan_extension_files,
+};
@@ -7,11 +7,11 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::market::fetcher::models::VersionFile;
+use crate::market::fetcher::models::{VersionDependency, VersionFile};
 
 /// 市场资源的基本信息。
 ///
-/// 代表在市场上（Spiget / Modrinth）发布的单个资源（插件或模组）
+/// 代表在市场上（Spiget / Modrinth / CurseForge / Hangar）发布的单个资源（插件或模组）
 /// 的概要信息，通常用于资源列表或搜索结果中展示。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketResource {
@@ -30,7 +30,7 @@ pub struct MarketResource {
     /// 资源已发布的版本数量。
     pub version_count: u64,
 
-    /// 资源来源市场（Spiget 或 Modrinth）。
+    /// 资源来源市场。
     pub source: MarketSource,
 }
 
@@ -45,6 +45,12 @@ pub enum MarketSource {
 
     /// 资源来自 Modrinth（模组与插件分发平台）。
     Modrinth,
+
+    /// 资源来自 CurseForge（模组与整合包分发平台，需要 API 密钥）。
+    CurseForge,
+
+    /// 资源来自 Hangar（PaperMC 官方插件平台）。
+    Hangar,
 }
 
 /// 资源的详细项目信息。
@@ -66,7 +72,7 @@ pub struct ResourceInfo {
     /// 资源的累计下载次数。
     pub download_count: u64,
 
-    /// 资源来源市场（Spiget 或 Modrinth）。
+    /// 资源来源市场。
     pub source: MarketSource,
 
     /// 资源的图标 URL，若资源未设置图标则为 `None`。
@@ -120,6 +126,10 @@ pub struct Version {
 
     /// 该版本关联的可下载文件列表。
     pub files: Vec<VersionFile>,
+
+    /// 该版本声明的依赖项。
+    #[serde(default)]
+    pub dependencies: Vec<VersionDependency>,
 }
 
 /// 搜索结果分页数据。
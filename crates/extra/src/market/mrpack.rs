@@ -0,0 +1,423 @@
+//! Modrinth 整合包（`.mrpack`）的服务端安装。
+//!
+//! [`install_mrpack`] 先把整合包解压到目标目录旁的临时目录，按索引下载服务端需要的
+//! 文件并校验大小与摘要；全部通过后才把文件移入目标目录，再依次应用 `overrides/`
+//! 与 `server-overrides/`。任一文件下载或校验失败时不改动目标目录。下载地址须为
+//! Modrinth 规范允许的域名（[`ALLOWED_DOWNLOAD_HOSTS`]），否则拒绝整个整合包。
+//!
+//! 解压、逐地址下载校验与覆盖目录合并也供 CurseForge 整合包安装复用。
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use sealantern_core::provisioning::{
+    MRPACK_INDEX_FILE, MRPACK_OVERRIDES_DIR, MRPACK_SERVER_OVERRIDES_DIR, MrpackIndex,
+    MrpackIndexError,
+};
+use sealantern_infra::archive::{ArchiveError, extract_zip};
+use sealantern_infra::fs::{
+    FsError, SafeRelativePath, ensure_dir, ensure_parent, remove_if_exists,
+};
+use tempfile::TempDir;
+
+use crate::market::fetcher::{Fetcher, VersionFile};
+use crate::market::install::{InstallError, download, verify_file};
+
+/// 临时目录中解压整合包的子目录。
+const STAGING_PACK_DIR: &str = "pack";
+/// 临时目录中存放已下载文件的子目录。
+const STAGING_DOWNLOADS_DIR: &str = "downloads";
+
+/// Modrinth 整合包规范允许的下载域名。
+pub const ALLOWED_DOWNLOAD_HOSTS: [&str; 4] =
+    ["cdn.modrinth.com", "github.com", "raw.githubusercontent.com", "gitlab.com"];
+
+/// 安装整合包失败的原因。
+#[derive(Debug)]
+pub enum MrpackError {
+    /// 整合包不是可安全解压的 zip。
+    Archive(ArchiveError),
+    /// 整合包缺少索引或索引无法使用。
+    Index(MrpackIndexError),
+    /// 下载地址不是 HTTPS 或不在允许的域名内。
+    UntrustedDownload { url: String },
+    /// 文件下载或校验失败（已尝试全部候选地址）。
+    Install(InstallError),
+    /// 文件读写失败，或索引中的路径不安全。
+    Storage(FsError),
+}
+
+impl fmt::Display for MrpackError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Archive(error) => write!(formatter, "{error}"),
+            Self::Index(error) => write!(formatter, "{error}"),
+            Self::UntrustedDownload { url } => {
+                write!(formatter, "modpack download {url} is not on an allowed host")
+            }
+            Self::Install(error) => write!(formatter, "{error}"),
+            Self::Storage(error) => write!(formatter, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for MrpackError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Archive(error) => Some(error),
+            Self::Index(error) => Some(error),
+            Self::Install(error) => Some(error),
+            Self::Storage(error) => Some(error),
+            Self::UntrustedDownload { .. } => None,
+        }
+    }
+}
+
+impl From<ArchiveError> for MrpackError {
+    fn from(error: ArchiveError) -> Self {
+        Self::Archive(error)
+    }
+}
+
+impl From<MrpackIndexError> for MrpackError {
+    fn from(error: MrpackIndexError) -> Self {
+        Self::Index(error)
+    }
+}
+
+impl From<InstallError> for MrpackError {
+    fn from(error: InstallError) -> Self {
+        match error {
+            InstallError::Storage(error) => Self::Storage(error),
+            error => Self::Install(error),
+        }
+    }
+}
+
+impl From<FsError> for MrpackError {
+    fn from(error: FsError) -> Self {
+        Self::Storage(error)
+    }
+}
+
+/// 把 `.mrpack` 安装到 `destination`，返回整合包索引供调用方设置加载器与游戏版本。
+///
+/// 目标目录不存在时会被创建；已存在的同名文件被整合包内容覆盖。
+pub async fn install_mrpack(
+    fetcher: &dyn Fetcher,
+    archive: &Path,
+    destination: &Path,
+) -> Result<MrpackIndex, MrpackError> {
+    let (staging, pack_dir) = stage_pack::<MrpackError>(archive, destination).await?;
+    let index_path = pack_dir.join(MRPACK_INDEX_FILE);
+    let content = tokio::fs::read_to_string(&index_path)
+        .await
+        .map_err(|source| FsError::Io {
+            operation: "read mrpack index",
+            path: index_path,
+            source,
+        })?;
+    let index = MrpackIndex::parse(&content)?;
+    if let Some(url) = index
+        .server_files()
+        .flat_map(|file| &file.downloads)
+        .find(|url| !is_allowed_download(url))
+    {
+        return Err(MrpackError::UntrustedDownload { url: url.clone() });
+    }
+
+    // 先全部下载并校验，再统一移入目标目录。
+    let downloads_dir = staging.path().join(STAGING_DOWNLOADS_DIR);
+    ensure_dir(&downloads_dir).await?;
+    let mut downloaded = Vec::new();
+    for (position, file) in index.server_files().enumerate() {
+        let relative = SafeRelativePath::parse(&file.path)?;
+        let partial = downloads_dir.join(format!("{position}.part"));
+        let expected = VersionFile {
+            url: String::new(),
+            filename: file.path.clone(),
+            size: file.file_size,
+            primary: true,
+            hashes: file.hashes.clone(),
+        };
+        download_verified(fetcher, &file.downloads, &expected, &partial).await?;
+        downloaded.push((partial, destination.join(relative.as_path())));
+    }
+    move_downloads(downloaded, destination).await?;
+    apply_overrides(
+        vec![pack_dir.join(MRPACK_OVERRIDES_DIR), pack_dir.join(MRPACK_SERVER_OVERRIDES_DIR)],
+        destination,
+    )
+    .await?;
+    Ok(index)
+}
+
+/// 下载地址是否为 HTTPS 且指向 [`ALLOWED_DOWNLOAD_HOSTS`] 之一。
+fn is_allowed_download(url: &str) -> bool {
+    reqwest::Url::parse(url).is_ok_and(|url| {
+        url.scheme() == "https"
+            && url
+                .host_str()
+                .is_some_and(|host| ALLOWED_DOWNLOAD_HOSTS.contains(&host))
+    })
+}
+
+/// 在目标目录旁创建临时目录并把整合包解压进去，返回临时目录（离开作用域时删除）与解压目录。
+///
+/// 临时目录与目标目录位于同一父目录，之后可直接 rename 而不跨文件系统。
+pub(crate) async fn stage_pack<E>(
+    archive: &Path,
+    destination: &Path,
+) -> Result<(TempDir, PathBuf), E>
+where
+    E: From<ArchiveError> + From<FsError>,
+{
+    let parent = destination.parent().unwrap_or(Path::new("."));
+    ensure_dir(parent).await?;
+    let staging = tempfile::Builder::new()
+        .prefix(".sealantern-modpack-")
+        .tempdir_in(parent)
+        .map_err(|source| FsError::Io {
+            operation: "create modpack staging directory",
+            path: parent.to_path_buf(),
+            source,
+        })?;
+    let pack_dir = staging.path().join(STAGING_PACK_DIR);
+    let (source, target) = (archive.to_path_buf(), pack_dir.clone());
+    tokio::task::spawn_blocking(move || extract_zip(source, target))
+        .await
+        .map_err(|error| FsError::Task {
+            operation: "extract modpack",
+            message: error.to_string(),
+        })??;
+    Ok((staging, pack_dir))
+}
+
+/// 依次尝试候选地址，直到下载结果通过 `expected` 的大小与摘要校验；全部失败时返回最后一个错误。
+pub(crate) async fn download_verified(
+    fetcher: &dyn Fetcher,
+    urls: &[String],
+    expected: &VersionFile,
+    partial: &Path,
+) -> Result<(), InstallError> {
+    let mut last_error = None;
+    for url in urls {
+        remove_if_exists(partial).await?;
+        let attempt = match download(fetcher, url, partial).await {
+            Ok(()) => verify_file(partial, expected).await,
+            Err(error) => Err(error),
+        };
+        match attempt {
+            Ok(()) => return Ok(()),
+            Err(error) => {
+                tracing::warn!(
+                    target: "sealantern.extra.market",
+                    url = %url,
+                    error = %error,
+                    "modpack file download failed"
+                );
+                last_error = Some(error);
+            }
+        }
+    }
+    Err(last_error.unwrap_or_else(|| InstallError::Verification {
+        file_name: expected.filename.clone(),
+        reason: "no download URL".to_owned(),
+    }))
+}
+
+/// 把已校验的下载文件（临时路径，目标路径）移入目标目录，覆盖同名文件。
+pub(crate) async fn move_downloads(
+    downloaded: Vec<(PathBuf, PathBuf)>,
+    destination: &Path,
+) -> Result<(), FsError> {
+    ensure_dir(destination).await?;
+    for (partial, target) in downloaded {
+        ensure_parent(&target).await?;
+        remove_if_exists(&target).await?;
+        tokio::fs::rename(&partial, &target)
+            .await
+            .map_err(|source| FsError::Io {
+                operation: "move modpack file",
+                path: target.clone(),
+                source,
+            })?;
+    }
+    Ok(())
+}
+
+/// 按顺序把覆盖目录合并进目标目录，后者覆盖前者；不存在的覆盖目录被跳过。
+pub(crate) async fn apply_overrides(
+    overrides: Vec<PathBuf>,
+    destination: &Path,
+) -> Result<(), FsError> {
+    let destination = destination.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        overrides
+            .iter()
+            .try_for_each(|source| merge_dir(source, &destination))
+    })
+    .await
+    .map_err(|error| FsError::Task {
+        operation: "apply modpack overrides",
+        message: error.to_string(),
+    })?
+}
+
+/// 把 `source` 目录的内容移入 `destination`，同名文件被覆盖；`source` 不存在时不做任何事。
+fn merge_dir(source: &Path, destination: &Path) -> Result<(), FsError> {
+    let io_error = |operation: &'static str, path: PathBuf| {
+        move |source: std::io::Error| FsError::Io { operation, path, source }
+    };
+    if !source.is_dir() {
+        return Ok(());
+    }
+    std::fs::create_dir_all(destination)
+        .map_err(io_error("create override directory", destination.to_path_buf()))?;
+    let entries = std::fs::read_dir(source)
+        .map_err(io_error("read override directory", source.to_path_buf()))?;
+    for entry in entries {
+        let entry = entry.map_err(io_error("read override directory", source.to_path_buf()))?;
+        let from = entry.path();
+        let to = destination.join(entry.file_name());
+        if from.is_dir() {
+            merge_dir(&from, &to)?;
+            continue;
+        }
+        if to.is_dir() {
+            std::fs::remove_dir_all(&to)
+                .map_err(io_error("replace override target", to.clone()))?;
+        } else if to.exists() {
+            std::fs::remove_file(&to).map_err(io_error("replace override target", to.clone()))?;
+        }
+        std::fs::rename(&from, &to).map_err(io_error("apply override file", to.clone()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use sealantern_infra::archive::create_zip;
+    use sealantern_infra::fs::{HashAlgorithm, file_digest_hex};
+
+    use super::*;
+    use crate::market::fetcher::MemoryFetcher;
+
+    async fn sha512(content: &[u8], scratch: &Path) -> String {
+        let path = scratch.join("hash-input");
+        std::fs::write(&path, content).unwrap();
+        file_digest_hex(&path, HashAlgorithm::Sha512).await.unwrap()
+    }
+
+    fn write_pack(root: &Path, index: &str) -> PathBuf {
+        let pack = root.join("pack-src");
+        std::fs::create_dir_all(pack.join("overrides/config")).unwrap();
+        std::fs::create_dir_all(pack.join("server-overrides/config")).unwrap();
+        std::fs::write(pack.join(MRPACK_INDEX_FILE), index).unwrap();
+        std::fs::write(pack.join("overrides/config/shared.toml"), "client = true").unwrap();
+        std::fs::write(pack.join("overrides/server.properties"), "motd=pack").unwrap();
+        std::fs::write(pack.join("server-overrides/config/shared.toml"), "client = false").unwrap();
+        let archive = root.join("Example.mrpack");
+        create_zip(&pack, &archive).unwrap();
+        archive
+    }
+
+    fn index(hash: &str) -> String {
+        format!(
+            r#"{{
+                "formatVersion": 1,
+                "game": "minecraft",
+                "versionId": "1.0.0",
+                "name": "Example",
+                "files": [
+                    {{
+                        "path": "mods/lithium.jar",
+                        "hashes": {{ "sha512": "{hash}" }},
+                        "env": {{ "client": "required", "server": "required" }},
+                        "downloads": ["https://github.com/dead/lithium.jar", "https://cdn.modrinth.com/data/lithium.jar"],
+                        "fileSize": 7
+                    }},
+                    {{
+                        "path": "mods/sodium.jar",
+                        "hashes": {{ "sha1": "00" }},
+                        "env": {{ "client": "required", "server": "unsupported" }},
+                        "downloads": ["https://cdn.example/sodium.jar"]
+                    }}
+                ],
+                "dependencies": {{ "minecraft": "1.20.1", "fabric-loader": "0.15.11" }}
+            }}"#
+        )
+    }
+
+    #[tokio::test]
+    async fn installs_server_files_and_overrides() {
+        let root = tempfile::tempdir().unwrap();
+        let hash = sha512(b"lithium", root.path()).await;
+        let archive = write_pack(root.path(), &index(&hash));
+        let fetcher = MemoryFetcher {
+            contents: HashMap::from([(
+                "https://cdn.modrinth.com/data/lithium.jar".to_owned(),
+                b"lithium".to_vec(),
+            )]),
+            ..MemoryFetcher::default()
+        };
+        let destination = root.path().join("server");
+
+        let index = install_mrpack(&fetcher, &archive, &destination)
+            .await
+            .unwrap();
+
+        assert_eq!(index.game_version(), Some("1.20.1"));
+        assert_eq!(std::fs::read(destination.join("mods/lithium.jar")).unwrap(), b"lithium");
+        assert!(!destination.join("mods/sodium.jar").exists());
+        assert_eq!(
+            std::fs::read_to_string(destination.join("config/shared.toml")).unwrap(),
+            "client = false"
+        );
+        assert_eq!(
+            std::fs::read_to_string(destination.join("server.properties")).unwrap(),
+            "motd=pack"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_mismatched_hash_without_touching_destination() {
+        let root = tempfile::tempdir().unwrap();
+        let archive = write_pack(root.path(), &index("ff"));
+        let fetcher = MemoryFetcher {
+            contents: HashMap::from([(
+                "https://cdn.modrinth.com/data/lithium.jar".to_owned(),
+                b"lithium".to_vec(),
+            )]),
+            ..MemoryFetcher::default()
+        };
+        let destination = root.path().join("server");
+
+        let error = install_mrpack(&fetcher, &archive, &destination)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, MrpackError::Install(InstallError::Verification { .. })));
+        assert!(!destination.exists());
+    }
+
+    #[tokio::test]
+    async fn rejects_downloads_outside_the_allowed_hosts() {
+        let root = tempfile::tempdir().unwrap();
+        let index = index("ff").replace("https://github.com/", "http://github.com/");
+        let archive = write_pack(root.path(), &index);
+        let destination = root.path().join("server");
+
+        let error = install_mrpack(&MemoryFetcher::default(), &archive, &destination)
+            .await
+            .unwrap_err();
+
+        assert!(matches!(error, MrpackError::UntrustedDownload { .. }));
+        assert!(!destination.exists());
+        assert!(is_allowed_download("https://raw.githubusercontent.com/a/b/c.jar"));
+        assert!(!is_allowed_download("https://cdn.modrinth.com.evil.example/c.jar"));
+        assert!(!is_allowed_download("https://evil.example/c.jar"));
+    }
+}
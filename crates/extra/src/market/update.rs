@@ -0,0 +1,750 @@
+//! 已安装插件与模组的更新检查与替换。
+//!
+//! [`check_updates`] 扫描服务器 `plugins/`、`mods/` 下的 jar，先按文件 SHA-512 / SHA-1
+//! 摘要向支持反查的市场（目前为 Modrinth）识别项目，再对安装记录中剩余的资源按版本列表
+//! 挑选最新的兼容版本。[`apply_updates`] 先下载并校验全部新文件，再把旧文件移入
+//! [`UPDATE_BACKUP_DIR`] 并换入新文件；每次替换留下一条 [`UpdateRollback`]，
+//! 可经 [`rollback_update`] 换回旧文件。
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use sealantern_infra::fs::{
+    DataLimit, FsError, HashAlgorithm, ensure_dir, file_digest_hex, read_json, remove_if_exists,
+};
+use sealantern_infra::persistence::ConfigFile;
+use serde::{Deserialize, Serialize};
+
+use crate::market::fetcher::{Fetcher, HashedVersion, VersionFile};
+use crate::market::install::{
+    InstallError, InstallTarget, RECORDED_HASHES, download, install_file, is_plain_file_name,
+    select_version, verify_file,
+};
+use crate::market::installed::{InstallDirectory, InstalledResource};
+use crate::market::models::{MarketSource, Version};
+
+/// 被替换的旧文件的备份目录（存放在服务器目录下，按 `plugins/`、`mods/` 分开）。
+pub const UPDATE_BACKUP_DIR: &str = "sea_lantern_update_backups";
+
+/// 可回滚的更新记录文件名（存放在服务器目录下）。
+pub const UPDATE_ROLLBACKS_FILE: &str = "sea_lantern_update_rollbacks.json";
+
+/// 回滚记录文件的读取上限。
+const UPDATE_ROLLBACKS_READ_LIMIT: DataLimit = DataLimit::new(1024 * 1024);
+
+/// 向市场反查文件时依次尝试的摘要算法。
+const LOOKUP_HASHES: [HashAlgorithm; 2] = [HashAlgorithm::Sha512, HashAlgorithm::Sha1];
+
+/// 扫描的扩展目录。
+const SCANNED_DIRECTORIES: [InstallDirectory; 2] =
+    [InstallDirectory::Plugins, InstallDirectory::Mods];
+
+/// 版本的简要信息。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionSummary {
+    pub version_id: String,
+    pub version_number: String,
+    /// 版本页面（含更新日志）地址，平台无稳定地址时为 `None`。
+    pub changelog_url: Option<String>,
+}
+
+/// 一个扩展文件的更新检查结果。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtensionUpdate {
+    pub directory: InstallDirectory,
+    pub file_name: String,
+    /// 识别出的来源市场；无安装记录且摘要未被任何市场收录时为 `None`。
+    pub source: Option<MarketSource>,
+    pub project_id: Option<String>,
+    pub current: Option<VersionSummary>,
+    /// 兼容实例的最新版本；没有兼容版本时为 `None`。
+    pub latest: Option<VersionSummary>,
+    /// 更新要下载的文件，仅在有可用更新时给出。
+    pub latest_file: Option<VersionFile>,
+    pub update_available: bool,
+    /// 查询该文件的市场信息失败时的原因；其余字段保留已识别的部分。
+    pub error: Option<String>,
+}
+
+impl ExtensionUpdate {
+    fn unidentified(directory: InstallDirectory, file_name: String) -> Self {
+        Self {
+            directory,
+            file_name,
+            source: None,
+            project_id: None,
+            current: None,
+            latest: None,
+            latest_file: None,
+            update_available: false,
+            error: None,
+        }
+    }
+
+    /// 是否为指定目录下的指定文件。
+    pub fn is_file(&self, directory: InstallDirectory, file_name: &str) -> bool {
+        self.directory == directory && self.file_name == file_name
+    }
+}
+
+/// 一次已执行更新的回滚信息。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateRollback {
+    pub directory: InstallDirectory,
+    /// 更新后的文件名。
+    pub file_name: String,
+    /// 被替换的旧文件名，备份在 [`UPDATE_BACKUP_DIR`] 的同名子目录下。
+    pub previous_file_name: String,
+    /// 更新前的安装记录；文件不在安装记录中时为 `None`。
+    pub previous_record: Option<InstalledResource>,
+    pub updated_at_unix_secs: u64,
+}
+
+impl UpdateRollback {
+    /// 旧文件备份相对服务器目录的路径。
+    pub fn backup_relative_path(&self) -> PathBuf {
+        backup_relative_path(self.directory, &self.previous_file_name)
+    }
+}
+
+/// 回滚记录文件的根对象。
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateRollbackList {
+    pub rollbacks: Vec<UpdateRollback>,
+}
+
+/// 更新执行结果：应写回的安装记录与新增的回滚信息。
+#[derive(Debug, Clone, Default)]
+pub struct AppliedUpdates {
+    pub records: Vec<InstalledResource>,
+    pub rollbacks: Vec<UpdateRollback>,
+}
+
+/// 回滚失败的原因。
+#[derive(Debug)]
+pub enum RollbackError {
+    /// 没有该文件的回滚记录。
+    NotFound,
+    /// 备份文件已不存在。
+    BackupMissing,
+    Storage(FsError),
+}
+
+impl fmt::Display for RollbackError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound => formatter.write_str("no rollback is recorded for this file"),
+            Self::BackupMissing => {
+                formatter.write_str("the backup of the previous file is missing")
+            }
+            Self::Storage(error) => write!(formatter, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for RollbackError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Storage(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<FsError> for RollbackError {
+    fn from(error: FsError) -> Self {
+        Self::Storage(error)
+    }
+}
+
+fn backup_relative_path(directory: InstallDirectory, file_name: &str) -> PathBuf {
+    Path::new(UPDATE_BACKUP_DIR)
+        .join(directory.dir_name())
+        .join(file_name)
+}
+
+fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+/// 列出服务器扩展目录下的 jar 文件，按目录与文件名排序。
+pub async fn scan_extension_files(
+    server_dir: &Path,
+) -> Result<Vec<(InstallDirectory, String)>, FsError> {
+    let mut files = Vec::new();
+    for directory in SCANNED_DIRECTORIES {
+        let path = server_dir.join(directory.dir_name());
+        let mut entries = match tokio::fs::read_dir(&path).await {
+            Ok(entries) => entries,
+            Err(source) if source.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(source) => {
+                return Err(FsError::Io {
+                    operation: "read extension directory",
+                    path,
+                    source,
+                });
+            }
+        };
+        let mut names = Vec::new();
+        while let Some(entry) = entries.next_entry().await.map_err(|source| FsError::Io {
+            operation: "read extension directory",
+            path: path.clone(),
+            source,
+        })? {
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            let is_file = entry
+                .file_type()
+                .await
+                .is_ok_and(|file_type| file_type.is_file());
+            if is_file && is_plain_file_name(&name) && name.to_ascii_lowercase().ends_with(".jar") {
+                names.push(name);
+            }
+        }
+        names.sort();
+        files.extend(names.into_iter().map(|name| (directory, name)));
+    }
+    Ok(files)
+}
+
+fn find_fetcher(
+    fetchers: &[(MarketSource, Arc<dyn Fetcher>)],
+    source: MarketSource,
+) -> Option<&dyn Fetcher> {
+    fetchers
+        .iter()
+        .find(|(candidate, _)| *candidate == source)
+        .map(|(_, fetcher)| fetcher.as_ref())
+}
+
+fn summary(fetcher: &dyn Fetcher, project_id: &str, version: &Version) -> VersionSummary {
+    VersionSummary {
+        version_id: version.id.clone(),
+        version_number: version.version_number.clone(),
+        changelog_url: fetcher.version_page_url(project_id, &version.id),
+    }
+}
+
+/// 检查服务器扩展目录下每个 jar 的可用更新。
+///
+/// 识别顺序：按摘要向支持反查的市场查询（对有安装记录的文件只查询记录的来源），
+/// 未命中但有安装记录的文件再按记录的项目拉取版本列表。两种方式都无法识别的文件
+/// 以 `source = None` 列出。市场查询失败只记录在受影响条目的 `error` 上，不中断扫描。
+pub async fn check_updates(
+    fetchers: &[(MarketSource, Arc<dyn Fetcher>)],
+    target: &InstallTarget,
+    server_dir: &Path,
+    installed: &[InstalledResource],
+) -> Result<Vec<ExtensionUpdate>, InstallError> {
+    let files = scan_extension_files(server_dir).await?;
+    let mut updates = Vec::with_capacity(files.len());
+    let mut digests = Vec::with_capacity(files.len());
+    let mut latest_versions: Vec<Option<Version>> = Vec::with_capacity(files.len());
+    let mut resolved = Vec::with_capacity(files.len());
+    for (directory, file_name) in files {
+        let path = server_dir.join(directory.dir_name()).join(&file_name);
+        let mut file_digests = Vec::with_capacity(LOOKUP_HASHES.len());
+        for algorithm in LOOKUP_HASHES {
+            file_digests.push(file_digest_hex(&path, algorithm).await?);
+        }
+        let mut update = ExtensionUpdate::unidentified(directory, file_name);
+        if let Some(record) = installed
+            .iter()
+            .find(|record| record.directory == directory && record.file_name == update.file_name)
+        {
+            update.source = Some(record.source);
+            update.project_id = Some(record.project_id.clone());
+            update.current = Some(VersionSummary {
+                version_id: record.version_id.clone(),
+                version_number: record.version_number.clone(),
+                changelog_url: find_fetcher(fetchers, record.source).and_then(|fetcher| {
+                    fetcher.version_page_url(&record.project_id, &record.version_id)
+                }),
+            });
+        }
+        updates.push(update);
+        digests.push(file_digests);
+        latest_versions.push(None);
+        resolved.push(false);
+    }
+
+    let game_versions: Vec<String> = target.game_version.iter().cloned().collect();
+    for (source, fetcher) in fetchers {
+        for (slot, algorithm) in LOOKUP_HASHES.into_iter().enumerate() {
+            let candidates: Vec<usize> = (0..updates.len())
+                .filter(|&index| {
+                    !resolved[index]
+                        && updates[index]
+                            .source
+                            .is_none_or(|recorded| recorded == *source)
+                })
+                .collect();
+            if candidates.is_empty() {
+                continue;
+            }
+            let hashes: Vec<String> = candidates
+                .iter()
+                .map(|&index| digests[index][slot].clone())
+                .collect();
+            let found = match fetcher.versions_by_hashes(algorithm, &hashes).await {
+                Ok(found) => found,
+                Err(error) => {
+                    let message = error.to_string();
+                    for index in candidates {
+                        updates[index].error.get_or_insert_with(|| message.clone());
+                    }
+                    continue;
+                }
+            };
+            let matched: Vec<usize> = candidates
+                .into_iter()
+                .filter(|&index| found.contains_key(&digests[index][slot]))
+                .collect();
+            if matched.is_empty() {
+                continue;
+            }
+            let matched_hashes: Vec<String> = matched
+                .iter()
+                .map(|&index| digests[index][slot].clone())
+                .collect();
+            let latest = fetcher
+                .latest_versions_by_hashes(
+                    algorithm,
+                    &matched_hashes,
+                    &target.loaders,
+                    &game_versions,
+                )
+                .await
+                .map_err(|error| error.to_string());
+            for index in matched {
+                let hash = &digests[index][slot];
+                let HashedVersion { project_id, version } = &found[hash];
+                let update = &mut updates[index];
+                update.source = Some(*source);
+                update.project_id = Some(project_id.clone());
+                update.current = Some(summary(fetcher.as_ref(), project_id, version));
+                match &latest {
+                    Ok(latest) => {
+                        update.error = None;
+                        latest_versions[index] = latest
+                            .get(hash)
+                            .filter(|latest| target.accepts(&latest.version))
+                            .map(|latest| latest.version.clone());
+                    }
+                    Err(message) => update.error = Some(message.clone()),
+                }
+                resolved[index] = true;
+            }
+        }
+    }
+
+    for (index, update) in updates.iter_mut().enumerate() {
+        if !resolved[index]
+            && let (Some(source), Some(project_id)) = (update.source, update.project_id.as_deref())
+            && let Some(fetcher) = find_fetcher(fetchers, source)
+        {
+            match fetcher.get_resource_versions(project_id).await {
+                Ok(versions) => {
+                    update.error = None;
+                    latest_versions[index] = select_version(&versions, target).cloned();
+                }
+                Err(error) => update.error = Some(error.to_string()),
+            }
+        }
+    }
+
+    for (update, latest) in updates.iter_mut().zip(latest_versions) {
+        let (Some(source), Some(project_id), Some(latest)) =
+            (update.source, update.project_id.clone(), latest)
+        else {
+            continue;
+        };
+        let Some(fetcher) = find_fetcher(fetchers, source) else {
+            continue;
+        };
+        update.update_available = update
+            .current
+            .as_ref()
+            .is_some_and(|current| current.version_id != latest.id);
+        if update.update_available {
+            match install_file(fetcher, source, &project_id, &latest).await {
+                Ok(file) => update.latest_file = file,
+                Err(error) => {
+                    update.update_available = false;
+                    update.error = Some(error.to_string());
+                }
+            }
+        }
+        update.latest = Some(summary(fetcher, &project_id, &latest));
+    }
+    Ok(updates)
+}
+
+/// 执行选中的更新。
+///
+/// 全部新文件下载并校验通过后才开始替换：旧文件移入备份目录，新文件换入原目录；
+/// 换入失败时把旧文件移回。不可更新的条目（无可用更新或无可下载文件）被忽略。
+pub async fn apply_updates(
+    fetchers: &[(MarketSource, Arc<dyn Fetcher>)],
+    server_dir: &Path,
+    updates: &[ExtensionUpdate],
+    installed: &[InstalledResource],
+) -> Result<AppliedUpdates, InstallError> {
+    let mut staged: Vec<(&ExtensionUpdate, &VersionFile, PathBuf)> = Vec::new();
+    for update in updates {
+        let (Some(source), Some(file), true) =
+            (update.source, update.latest_file.as_ref(), update.update_available)
+        else {
+            continue;
+        };
+        let Some(fetcher) = find_fetcher(fetchers, source) else {
+            continue;
+        };
+        let directory = server_dir.join(update.directory.dir_name());
+        let partial = directory.join(format!("{}.part", file.filename));
+        let result = async {
+            download(fetcher, &file.url, &partial).await?;
+            verify_file(&partial, file).await
+        }
+        .await;
+        staged.push((update, file, partial));
+        if let Err(error) = result {
+            for (_, _, partial) in &staged {
+                let _ = remove_if_exists(partial).await;
+            }
+            return Err(error);
+        }
+    }
+
+    let updated_at_unix_secs = now_unix_secs();
+    let mut applied = AppliedUpdates::default();
+    for (update, file, partial) in staged {
+        let directory = server_dir.join(update.directory.dir_name());
+        let current = directory.join(&update.file_name);
+        let destination = directory.join(&file.filename);
+        let backup = server_dir.join(backup_relative_path(update.directory, &update.file_name));
+        let swap = async {
+            if let Some(parent) = backup.parent() {
+                ensure_dir(parent).await?;
+            }
+            rename(&current, &backup, "back up replaced file").await?;
+            if let Err(error) = rename(&partial, &destination, "move updated file").await {
+                let _ = rename(&backup, &current, "restore replaced file").await;
+                return Err(error);
+            }
+            Ok(())
+        }
+        .await;
+        if let Err(error) = swap {
+            let _ = remove_if_exists(&partial).await;
+            return Err(error.into());
+        }
+
+        let previous_record = installed
+            .iter()
+            .find(|record| {
+                record.directory == update.directory && record.file_name == update.file_name
+            })
+            .cloned();
+        if let (Some(previous), Some(latest)) = (&previous_record, &update.latest) {
+            let mut hashes = file.hashes.clone();
+            for algorithm in RECORDED_HASHES {
+                hashes.insert(
+                    algorithm.name().to_owned(),
+                    file_digest_hex(&destination, algorithm).await?,
+                );
+            }
+            applied.records.push(InstalledResource {
+                version_id: latest.version_id.clone(),
+                version_number: latest.version_number.clone(),
+                file_name: file.filename.clone(),
+                hashes,
+                installed_at_unix_secs: updated_at_unix_secs,
+                ..previous.clone()
+            });
+        }
+        applied.rollbacks.push(UpdateRollback {
+            directory: update.directory,
+            file_name: file.filename.clone(),
+            previous_file_name: update.file_name.clone(),
+            previous_record,
+            updated_at_unix_secs,
+        });
+    }
+    Ok(applied)
+}
+
+async fn rename(from: &Path, to: &Path, operation: &'static str) -> Result<(), FsError> {
+    tokio::fs::rename(from, to)
+        .await
+        .map_err(|source| FsError::Io {
+            operation,
+            path: to.to_path_buf(),
+            source,
+        })
+}
+
+fn update_rollbacks_path(server_dir: &Path) -> PathBuf {
+    server_dir.join(UPDATE_ROLLBACKS_FILE)
+}
+
+/// 读取服务器目录下的回滚记录；记录文件不存在时返回空列表。
+pub async fn read_update_rollbacks(server_dir: &Path) -> Result<Vec<UpdateRollback>, FsError> {
+    let path = update_rollbacks_path(server_dir);
+    if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+        return Ok(Vec::new());
+    }
+    let list: UpdateRollbackList = read_json(&path, UPDATE_ROLLBACKS_READ_LIMIT).await?;
+    Ok(list.rollbacks)
+}
+
+/// 写入一批回滚记录，同一目录下的同一更新后文件覆盖旧记录。
+pub async fn record_update_rollbacks(
+    server_dir: &Path,
+    rollbacks: Vec<UpdateRollback>,
+) -> Result<Vec<UpdateRollback>, FsError> {
+    let list = ConfigFile::update_persisted(
+        update_rollbacks_path(server_dir),
+        UpdateRollbackList::default(),
+        false,
+        |list| {
+            for rollback in rollbacks {
+                list.rollbacks.retain(|existing| {
+                    !(existing.directory == rollback.directory
+                        && (existing.file_name == rollback.file_name
+                            || existing.file_name == rollback.previous_file_name))
+                });
+                list.rollbacks.push(rollback);
+            }
+        },
+    )
+    .await?;
+    Ok(list.rollbacks)
+}
+
+/// 回滚指定文件的最近一次更新：删除更新后的文件并换回备份的旧文件。
+///
+/// 返回被消费的回滚记录，调用方据此恢复 `previous_record`。
+pub async fn rollback_update(
+    server_dir: &Path,
+    directory: InstallDirectory,
+    file_name: &str,
+) -> Result<UpdateRollback, RollbackError> {
+    let rollback = read_update_rollbacks(server_dir)
+        .await?
+        .into_iter()
+        .find(|rollback| rollback.directory == directory && rollback.file_name == file_name)
+        .ok_or(RollbackError::NotFound)?;
+    let backup = server_dir.join(rollback.backup_relative_path());
+    if !tokio::fs::try_exists(&backup).await.unwrap_or(false) {
+        return Err(RollbackError::BackupMissing);
+    }
+
+    let extension_dir = server_dir.join(directory.dir_name());
+    remove_if_exists(extension_dir.join(&rollback.file_name)).await?;
+    rename(
+        &backup,
+        &extension_dir.join(&rollback.previous_file_name),
+        "restore replaced file",
+    )
+    .await?;
+    ConfigFile::update_persisted_if_changed(
+        update_rollbacks_path(server_dir),
+        UpdateRollbackList::default(),
+        false,
+        |list| {
+            let before = list.rollbacks.len();
+            list.rollbacks.retain(|existing| {
+                !(existing.directory == directory && existing.file_name == file_name)
+            });
+            list.rollbacks.len() != before
+        },
+    )
+    .await?;
+    Ok(rollback)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::market::fetcher::MemoryFetcher;
+
+    fn version(project_id: &str, version_id: &str, game_version: &str) -> Version {
+        Version {
+            id: version_id.to_owned(),
+            name: version_id.to_owned(),
+            version_number: version_id.to_owned(),
+            game_versions: vec![game_version.to_owned()],
+            loaders: vec!["fabric".to_owned()],
+            downloads: 0,
+            files: vec![VersionFile {
+                url: format!("https://cdn.example/{project_id}-{version_id}"),
+                filename: format!("{project_id}-{version_id}.jar"),
+                size: 0,
+                primary: true,
+                hashes: BTreeMap::new(),
+            }],
+            dependencies: Vec::new(),
+        }
+    }
+
+    fn fetchers() -> Vec<(MarketSource, Arc<dyn Fetcher>)> {
+        let mut modrinth = MemoryFetcher {
+            hash_lookup: true,
+            ..MemoryFetcher::default()
+        };
+        for version_id in ["0.6", "0.5.11", "0.5.8"] {
+            let game_version = if version_id == "0.6" {
+                "1.21"
+            } else {
+                "1.20.1"
+            };
+            modrinth.insert_version("sodium", version("sodium", version_id, game_version));
+        }
+        let mut hangar = MemoryFetcher::default();
+        for version_id in ["2.0", "1.0"] {
+            hangar.insert_version("lithium", version("lithium", version_id, "1.20.1"));
+        }
+        vec![
+            (MarketSource::Modrinth, Arc::new(modrinth)),
+            (MarketSource::Hangar, Arc::new(hangar)),
+        ]
+    }
+
+    fn lithium_record() -> InstalledResource {
+        InstalledResource {
+            source: MarketSource::Hangar,
+            project_id: "lithium".to_owned(),
+            version_id: "1.0".to_owned(),
+            version_number: "1.0".to_owned(),
+            file_name: "lithium.jar".to_owned(),
+            directory: InstallDirectory::Mods,
+            hashes: BTreeMap::new(),
+            dependencies: Vec::new(),
+            incompatible_with: Vec::new(),
+            explicit: true,
+            installed_at_unix_secs: 1,
+        }
+    }
+
+    #[tokio::test]
+    async fn checks_by_hash_and_record_then_swaps_with_rollback() {
+        let dir = tempfile::tempdir().unwrap();
+        let mods = dir.path().join("mods");
+        std::fs::create_dir_all(&mods).unwrap();
+        std::fs::write(mods.join("sodium.jar"), "sodium-0.5.8").unwrap();
+        std::fs::write(mods.join("lithium.jar"), "lithium-1.0").unwrap();
+        std::fs::write(mods.join("custom.jar"), "custom").unwrap();
+        std::fs::write(mods.join("notes.txt"), "ignored").unwrap();
+        let target = InstallTarget {
+            game_version: Some("1.20.1".to_owned()),
+            loaders: vec!["fabric".to_owned()],
+        };
+        let fetchers = fetchers();
+        let installed = vec![lithium_record()];
+
+        let updates = check_updates(&fetchers, &target, dir.path(), &installed)
+            .await
+            .unwrap();
+        assert_eq!(
+            updates
+                .iter()
+                .map(|update| update.file_name.as_str())
+                .collect::<Vec<_>>(),
+            ["custom.jar", "lithium.jar", "sodium.jar"]
+        );
+        assert_eq!(updates[0].source, None);
+        assert!(!updates[0].update_available);
+
+        // 有安装记录、平台不支持摘要反查时按版本列表检查
+        let lithium = &updates[1];
+        assert_eq!(lithium.source, Some(MarketSource::Hangar));
+        assert_eq!(lithium.latest.as_ref().unwrap().version_id, "2.0");
+        assert!(lithium.update_available);
+
+        // 无安装记录的文件按摘要识别，最新版本限定在实例的游戏版本
+        let sodium = &updates[2];
+        assert_eq!(sodium.source, Some(MarketSource::Modrinth));
+        assert_eq!(sodium.project_id.as_deref(), Some("sodium"));
+        assert_eq!(sodium.current.as_ref().unwrap().version_id, "0.5.8");
+        let latest = sodium.latest.as_ref().unwrap();
+        assert_eq!(latest.version_id, "0.5.11");
+        assert_eq!(latest.changelog_url.as_deref(), Some("https://market.example/sodium/0.5.11"));
+        assert_eq!(sodium.latest_file.as_ref().unwrap().filename, "sodium-0.5.11.jar");
+
+        let applied = apply_updates(&fetchers, dir.path(), &updates, &installed)
+            .await
+            .unwrap();
+        assert_eq!(applied.rollbacks.len(), 2);
+        assert_eq!(applied.records.len(), 1);
+        assert_eq!(applied.records[0].version_id, "2.0");
+        assert_eq!(applied.records[0].file_name, "lithium-2.0.jar");
+        assert!(applied.records[0].hashes.contains_key("sha512"));
+        assert!(!mods.join("sodium.jar").exists());
+        assert_eq!(
+            std::fs::read_to_string(mods.join("sodium-0.5.11.jar")).unwrap(),
+            "sodium-0.5.11"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join(UPDATE_BACKUP_DIR).join("mods/sodium.jar"))
+                .unwrap(),
+            "sodium-0.5.8"
+        );
+        record_update_rollbacks(dir.path(), applied.rollbacks)
+            .await
+            .unwrap();
+
+        let rollback = rollback_update(dir.path(), InstallDirectory::Mods, "lithium-2.0.jar")
+            .await
+            .unwrap();
+        assert_eq!(rollback.previous_record, Some(lithium_record()));
+        assert!(!mods.join("lithium-2.0.jar").exists());
+        assert_eq!(std::fs::read_to_string(mods.join("lithium.jar")).unwrap(), "lithium-1.0");
+        assert_eq!(read_update_rollbacks(dir.path()).await.unwrap().len(), 1);
+        assert!(matches!(
+            rollback_update(dir.path(), InstallDirectory::Mods, "lithium-2.0.jar").await,
+            Err(RollbackError::NotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn failing_project_is_reported_without_aborting_the_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        let mods = dir.path().join("mods");
+        std::fs::create_dir_all(&mods).unwrap();
+        std::fs::write(mods.join("lithium.jar"), "lithium-1.0").unwrap();
+        std::fs::write(mods.join("starlight.jar"), "starlight-1.0").unwrap();
+        let mut fetchers = fetchers();
+        let mut hangar = MemoryFetcher::default();
+        hangar.insert_version("lithium", version("lithium", "2.0", "1.20.1"));
+        hangar.failing.insert("starlight".to_owned());
+        fetchers[1].1 = Arc::new(hangar);
+        let starlight = InstalledResource {
+            project_id: "starlight".to_owned(),
+            file_name: "starlight.jar".to_owned(),
+            ..lithium_record()
+        };
+        let target = InstallTarget {
+            game_version: Some("1.20.1".to_owned()),
+            loaders: vec!["fabric".to_owned()],
+        };
+
+        let updates = check_updates(&fetchers, &target, dir.path(), &[lithium_record(), starlight])
+            .await
+            .unwrap();
+
+        assert_eq!(updates.len(), 2);
+        assert!(updates[0].update_available);
+        assert_eq!(updates[0].error, None);
+        assert_eq!(updates[1].project_id.as_deref(), Some("starlight"));
+        assert!(!updates[1].update_available);
+        assert!(updates[1].error.as_deref().unwrap().contains("unreachable"));
+    }
+}
@@ -71,6 +71,8 @@ pub struct AppSettings {
 
     /// 全局出站网络代理策略。
     pub proxy: ProxySettings,
+    /// CurseForge API 密钥；未配置时 CurseForge 市场不可用。
+    pub curseforge_api_key: Option<String>,
 
     pub default_max_memory: u32,
     pub default_min_memory: u32,
@@ -124,6 +126,7 @@ impl Default for AppSettings {
             close_action: "ask".into(),
             auto_lightweight_minutes: None,
             proxy: ProxySettings::default(),
+            curseforge_api_key: None,
             default_max_memory: 2048,
             default_min_memory: 512,
             default_port: 25565,
@@ -236,7 +239,7 @@ impl AppSettings {
             groups.push(SettingsGroup::General);
         }
 
-        if self.proxy != other.proxy {
+        if self.proxy != other.proxy || self.curseforge_api_key != other.curseforge_api_key {
             groups.push(SettingsGroup::Network);
         }
 
@@ -390,6 +393,12 @@ mod tests {
         };
 
         assert_eq!(current.changed_groups(&changed), vec![SettingsGroup::Network]);
+
+        let changed = AppSettings {
+            curseforge_api_key: Some("key".to_string()),
+            ..current.clone()
+        };
+        assert_eq!(current.changed_groups(&changed), vec![SettingsGroup::Network]);
     }
 
     #[test]
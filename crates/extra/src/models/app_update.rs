@@ -57,6 +57,8 @@ pub struct PartialAppSettings {
     pub auto_lightweight_minutes: NullablePatch<u32>,
 
     pub proxy: Option<ProxySettings>,
+    #[serde(default, skip_serializing_if = "NullablePatch::is_unchanged")]
+    pub curseforge_api_key: NullablePatch<String>,
 
     pub default_max_memory: Option<u32>,
     pub default_min_memory: Option<u32>,
@@ -127,6 +129,9 @@ impl PartialAppSettings {
         if let Some(value) = &self.proxy {
             target.proxy.clone_from(value);
         }
+        if let NullablePatch::Set(value) = &self.curseforge_api_key {
+            target.curseforge_api_key.clone_from(value);
+        }
         if let Some(value) = self.default_max_memory {
             target.default_max_memory = value;
         }
@@ -39,6 +39,10 @@ impl TaskProgressResponse {
             TaskStatus::Error { error }
         } else if snapshot.is_finished {
             TaskStatus::Simple("Completed".to_string())
+        } else if snapshot.paused {
+            TaskStatus::Simple("Paused".to_string())
+        } else if snapshot.queue_position.is_some() {
+            TaskStatus::Simple("Queued".to_string())
         } else if snapshot.downloaded > 0 {
             TaskStatus::Simple("Downloading".to_string())
         } else {
@@ -64,7 +68,7 @@ impl From<(String, DownloadSnapshot)> for TaskProgressResponse {
 
 #[cfg(test)]
 mod tests {
-    use sealantern_infra::download::DownloadSnapshot;
+    use sealantern_infra::download::{DownloadErrorKind, DownloadSnapshot};
 
     use super::{TaskProgressResponse, TaskStatus};
 
@@ -78,6 +82,9 @@ mod tests {
                 progress_percentage: 50.0,
                 is_finished: false,
                 error: None,
+                error_kind: None,
+                paused: false,
+                queue_position: None,
             },
         );
 
@@ -101,6 +108,9 @@ mod tests {
                 progress_percentage: 0.0,
                 is_finished: true,
                 error: Some("connection reset".to_string()),
+                error_kind: Some(DownloadErrorKind::Network),
+                paused: false,
+                queue_position: None,
             },
         );
 
@@ -0,0 +1,12 @@
+//! 控制台命令历史与用户宏的持久化存储。
+//!
+//! 按服务器目录持久化操作者发送过的命令（去重并记录使用次数）以及
+//! 用户定义的别名与宏；宏的校验与展开由 `core` 的
+//! [`CommandMacro`](sealantern_core::server::CommandMacro) 负责，本模块只做存取。
+
+mod store;
+
+pub use store::{
+    COMMAND_DATABASE_FILE, CommandHistoryEntry, MAX_HISTORY_ENTRIES, clear_history, delete_macro,
+    find_macro, list_macros, open_command_database, record_command, save_macro, search_history,
+};
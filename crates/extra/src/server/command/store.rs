@@ -0,0 +1,351 @@
+//! 命令库的存储与读取。
+//!
+//! `command_history` 以命令文本为主键，重复发送同一命令只刷新最近使用时刻并
+//! 累加次数，写入后按最近使用时刻裁剪到 [`MAX_HISTORY_ENTRIES`] 条；
+//! `command_macros` 以宏名为主键，参数与步骤以 JSON 保存。
+
+use std::path::Path;
+
+use sealantern_core::server::{CommandMacro, CommandMacroKind, MacroStep};
+use sealantern_infra::persistence::{PersistenceError, SqlValue, SqliteDatabase};
+use serde::{Deserialize, Serialize};
+
+/// 命令库文件名（存放在服务器目录下）。
+pub const COMMAND_DATABASE_FILE: &str = "sea_lantern_commands.sqlite";
+
+/// 每个实例保留的历史命令条数上限。
+pub const MAX_HISTORY_ENTRIES: i64 = 500;
+
+/// 命令库建表语句（幂等）。
+const COMMAND_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS command_history (\
+     command TEXT PRIMARY KEY,\
+     last_used_at INTEGER NOT NULL,\
+     use_count INTEGER NOT NULL DEFAULT 1\
+ );\
+ CREATE INDEX IF NOT EXISTS command_history_recent ON command_history (last_used_at);\
+ CREATE TABLE IF NOT EXISTS command_macros (\
+     name TEXT PRIMARY KEY,\
+     kind TEXT NOT NULL,\
+     params TEXT NOT NULL,\
+     steps TEXT NOT NULL,\
+     description TEXT NOT NULL DEFAULT '',\
+     updated_at INTEGER NOT NULL\
+ );";
+
+/// 一条去重后的历史命令。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandHistoryEntry {
+    pub command: String,
+    /// 最近一次发送时刻（Unix 秒）。
+    pub last_used_at: i64,
+    /// 累计发送次数。
+    pub use_count: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredStep {
+    command: String,
+    delay_ms: u64,
+}
+
+/// 打开（或创建）服务器命令库，并确保表结构存在。
+pub async fn open_command_database(server_path: &Path) -> Result<SqliteDatabase, PersistenceError> {
+    SqliteDatabase::open_with_schema(server_path.join(COMMAND_DATABASE_FILE), COMMAND_SCHEMA).await
+}
+
+/// 记录一次命令发送；已存在的命令刷新时刻并累加次数。
+pub async fn record_command(
+    database: &SqliteDatabase,
+    command: &str,
+    used_at: i64,
+) -> Result<(), PersistenceError> {
+    let command = command.trim().to_owned();
+    if command.is_empty() {
+        return Ok(());
+    }
+    database
+        .write("record command", move |transaction| {
+            transaction.execute(
+                "INSERT INTO command_history (command, last_used_at, use_count) VALUES (?1, ?2, 1) \
+                 ON CONFLICT(command) DO UPDATE SET \
+                 last_used_at = MAX(last_used_at, excluded.last_used_at), use_count = use_count + 1",
+                rusqlite::params![command, used_at],
+            )?;
+            transaction.execute(
+                "DELETE FROM command_history WHERE command NOT IN (\
+                 SELECT command FROM command_history ORDER BY last_used_at DESC LIMIT ?1)",
+                rusqlite::params![MAX_HISTORY_ENTRIES],
+            )?;
+            Ok(())
+        })
+        .await
+}
+
+/// 按最近使用时刻倒序读取历史；`query` 提供时只返回包含该文本的命令（不区分大小写）。
+pub async fn search_history(
+    database: &SqliteDatabase,
+    query: Option<&str>,
+    limit: i64,
+) -> Result<Vec<CommandHistoryEntry>, PersistenceError> {
+    if limit <= 0 {
+        return Err(PersistenceError::InvalidInput {
+            reason: format!("limit must be positive, got {limit}"),
+        });
+    }
+    let pattern = format!("%{}%", escape_like(query.unwrap_or_default().trim()));
+    database
+        .query(
+            "SELECT command, last_used_at, use_count FROM command_history \
+             WHERE command LIKE ?1 ESCAPE '\\' \
+             ORDER BY last_used_at DESC, use_count DESC LIMIT ?2",
+            [SqlValue::Text(pattern), SqlValue::Integer(limit)],
+            |row| {
+                Ok(CommandHistoryEntry {
+                    command: row.get(0)?,
+                    last_used_at: row.get(1)?,
+                    use_count: row.get(2)?,
+                })
+            },
+        )
+        .await
+}
+
+/// 清空历史，返回删除的条数。
+pub async fn clear_history(database: &SqliteDatabase) -> Result<usize, PersistenceError> {
+    database
+        .execute("DELETE FROM command_history", std::iter::empty())
+        .await
+}
+
+/// 按名称顺序读取全部宏。
+pub async fn list_macros(database: &SqliteDatabase) -> Result<Vec<CommandMacro>, PersistenceError> {
+    database
+        .query(
+            "SELECT name, kind, params, steps, description FROM command_macros ORDER BY name",
+            std::iter::empty(),
+            map_macro,
+        )
+        .await
+}
+
+/// 按名称读取宏。
+pub async fn find_macro(
+    database: &SqliteDatabase,
+    name: &str,
+) -> Result<Option<CommandMacro>, PersistenceError> {
+    database
+        .query_one(
+            "SELECT name, kind, params, steps, description FROM command_macros WHERE name = ?1",
+            std::iter::once(SqlValue::Text(name.to_owned())),
+            map_macro,
+        )
+        .await
+}
+
+/// 新建或覆盖同名宏（调用方负责先行校验）。
+pub async fn save_macro(
+    database: &SqliteDatabase,
+    definition: &CommandMacro,
+    updated_at: i64,
+) -> Result<(), PersistenceError> {
+    let steps: Vec<_> = definition
+        .steps
+        .iter()
+        .map(|step| StoredStep {
+            command: step.command.clone(),
+            delay_ms: step.delay_ms,
+        })
+        .collect();
+    let params = encode_json(&definition.params)?;
+    let steps = encode_json(&steps)?;
+    database
+        .execute(
+            "INSERT INTO command_macros (name, kind, params, steps, description, updated_at) \
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6) \
+             ON CONFLICT(name) DO UPDATE SET kind = excluded.kind, params = excluded.params, \
+             steps = excluded.steps, description = excluded.description, \
+             updated_at = excluded.updated_at",
+            [
+                SqlValue::Text(definition.name.clone()),
+                SqlValue::Text(definition.kind.as_str().to_owned()),
+                SqlValue::Text(params),
+                SqlValue::Text(steps),
+                SqlValue::Text(definition.description.clone()),
+                SqlValue::Integer(updated_at),
+            ],
+        )
+        .await
+        .map(|_| ())
+}
+
+/// 删除宏，返回是否存在。
+pub async fn delete_macro(database: &SqliteDatabase, name: &str) -> Result<bool, PersistenceError> {
+    database
+        .execute(
+            "DELETE FROM command_macros WHERE name = ?1",
+            std::iter::once(SqlValue::Text(name.to_owned())),
+        )
+        .await
+        .map(|deleted| deleted > 0)
+}
+
+fn encode_json(value: &impl Serialize) -> Result<String, PersistenceError> {
+    serde_json::to_string(value).map_err(|error| PersistenceError::InvalidInput {
+        reason: format!("failed to encode macro: {error}"),
+    })
+}
+
+fn decode_json<T: for<'de> Deserialize<'de>>(index: usize, raw: &str) -> rusqlite::Result<T> {
+    serde_json::from_str(raw).map_err(|error| {
+        rusqlite::Error::FromSqlConversionFailure(
+            index,
+            rusqlite::types::Type::Text,
+            Box::new(error),
+        )
+    })
+}
+
+fn map_macro(row: &rusqlite::Row<'_>) -> rusqlite::Result<CommandMacro> {
+    let kind: String = row.get(1)?;
+    let kind = CommandMacroKind::parse(&kind).ok_or_else(|| {
+        rusqlite::Error::FromSqlConversionFailure(
+            1,
+            rusqlite::types::Type::Text,
+            format!("unknown macro kind `{kind}`").into(),
+        )
+    })?;
+    let params: String = row.get(2)?;
+    let steps: String = row.get(3)?;
+    Ok(CommandMacro {
+        name: row.get(0)?,
+        kind,
+        params: decode_json(2, &params)?,
+        steps: decode_json::<Vec<StoredStep>>(3, &steps)?
+            .into_iter()
+            .map(|step| MacroStep {
+                command: step.command,
+                delay_ms: step.delay_ms,
+            })
+            .collect(),
+        description: row.get(4)?,
+    })
+}
+
+/// 转义 LIKE 通配符，使搜索词按字面匹配。
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for character in value.chars() {
+        if matches!(character, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(character);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn open_test_database() -> (tempfile::TempDir, SqliteDatabase) {
+        let directory = tempfile::tempdir().expect("临时目录应创建成功");
+        let database = open_command_database(directory.path())
+            .await
+            .expect("命令库应初始化成功");
+        (directory, database)
+    }
+
+    #[tokio::test]
+    async fn history_deduplicates_and_searches_literally() {
+        let (_directory, database) = open_test_database().await;
+        record_command(&database, "say hello", 100)
+            .await
+            .expect("记录");
+        record_command(&database, "give @p diamond 64", 200)
+            .await
+            .expect("记录");
+        record_command(&database, "say hello ", 300)
+            .await
+            .expect("记录");
+        record_command(&database, "say 100%_done", 400)
+            .await
+            .expect("记录");
+
+        let entries = search_history(&database, None, 10).await.expect("读取");
+        let commands: Vec<_> = entries
+            .iter()
+            .map(|entry| (entry.command.as_str(), entry.use_count))
+            .collect();
+        assert_eq!(commands, [("say 100%_done", 1), ("say hello", 2), ("give @p diamond 64", 1)]);
+
+        let matched = search_history(&database, Some("%_"), 10)
+            .await
+            .expect("搜索");
+        assert_eq!(matched.len(), 1);
+        let matched = search_history(&database, Some("SAY"), 10)
+            .await
+            .expect("搜索");
+        assert_eq!(matched.len(), 2);
+
+        assert_eq!(clear_history(&database).await.expect("清空"), 3);
+        assert!(matches!(
+            search_history(&database, None, 0).await,
+            Err(PersistenceError::InvalidInput { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn history_is_pruned_to_limit() {
+        let (_directory, database) = open_test_database().await;
+        for index in 0..MAX_HISTORY_ENTRIES + 5 {
+            record_command(&database, &format!("say {index}"), index)
+                .await
+                .expect("记录");
+        }
+        let entries = search_history(&database, None, MAX_HISTORY_ENTRIES * 2)
+            .await
+            .expect("读取");
+        assert_eq!(entries.len() as i64, MAX_HISTORY_ENTRIES);
+        assert_eq!(entries.last().unwrap().command, "say 5");
+    }
+
+    #[tokio::test]
+    async fn macros_round_trip() {
+        let (_directory, database) = open_test_database().await;
+        let definition = CommandMacro {
+            name: "restart-warn".to_owned(),
+            kind: CommandMacroKind::Macro,
+            params: vec!["minutes".to_owned()],
+            steps: vec![
+                MacroStep {
+                    command: "say Restart in {minutes} minutes".to_owned(),
+                    delay_ms: 0,
+                },
+                MacroStep {
+                    command: "save-all".to_owned(),
+                    delay_ms: 1_000,
+                },
+            ],
+            description: "重启预警".to_owned(),
+        };
+        save_macro(&database, &definition, 10).await.expect("保存");
+        assert_eq!(
+            find_macro(&database, "restart-warn").await.expect("读取"),
+            Some(definition.clone())
+        );
+
+        let mut updated = definition.clone();
+        updated.description = "更新".to_owned();
+        save_macro(&database, &updated, 20).await.expect("覆盖");
+        assert_eq!(list_macros(&database).await.expect("列表"), [updated]);
+
+        assert!(delete_macro(&database, "restart-warn").await.expect("删除"));
+        assert!(!delete_macro(&database, "restart-warn").await.expect("删除"));
+        assert!(
+            find_macro(&database, "restart-warn")
+                .await
+                .expect("读取")
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,954 @@
+//! 读取与启停实例的插件、模组与数据包。
+//!
+//! 扫描实例目录下的 `plugins/` 与 `mods/`，以 `.jar` 结尾的文件视为已启用，
+//! 以 `.jar.disabled` 结尾的视为已禁用；数据包位于 `<世界>/datapacks/`（世界目录
+//! 取自 `server.properties` 的 `level-name`），已禁用的数据包移到同级的
+//! `datapacks.disabled/`——游戏会加载 `datapacks/` 下任意带 `pack.mcmeta` 的目录，
+//! 改名不足以禁用。
+//!
+//! 每个 jar 内的描述文件经 [`read_archive_entries`] 读取（与服务端检查共用资源
+//! 预算），按以下格式解析：
+//!
+//! | 条目 | 格式 |
+//! |------|------|
+//! | `paper-plugin.yml` / `plugin.yml` | Paper / Bukkit 系插件 |
+//! | `velocity-plugin.json` | Velocity 插件 |
+//! | `fabric.mod.json` / `quilt.mod.json` | Fabric / Quilt 模组 |
+//! | `META-INF/neoforge.mods.toml` / `META-INF/mods.toml` | NeoForge / Forge 模组 |
+//!
+//! 读取尽力而为：无法打开或无法解析的文件仍会列出，只是没有元数据。
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use sealantern_core::instance::{
+    ExtensionDependency, ExtensionMetadata, ExtensionMetadataFormat, InstanceExtension,
+    InstanceExtensionError, InstanceExtensionKind,
+};
+use sealantern_core::provisioning::{InspectionOptions, read_archive_entries};
+use serde_json::Value as JsonValue;
+use serde_yaml::Value as YamlValue;
+use toml_edit::{DocumentMut, Item, TableLike};
+use tracing::warn;
+
+use crate::config::ServerPropertiesManager;
+use crate::market::install::is_plain_file_name;
+
+/// 已禁用的插件与模组追加的后缀。
+pub const DISABLED_EXTENSION_SUFFIX: &str = ".disabled";
+/// 已启用数据包所在目录，相对世界目录。
+pub const DATAPACKS_DIR: &str = "datapacks";
+/// 已禁用数据包所在目录，相对世界目录。
+pub const DISABLED_DATAPACKS_DIR: &str = "datapacks.disabled";
+
+/// `server.properties` 未设置 `level-name` 时的世界目录名。
+const DEFAULT_LEVEL_NAME: &str = "world";
+/// 目录形式数据包的标志文件。
+const PACK_METADATA_FILE: &str = "pack.mcmeta";
+
+const PAPER_PLUGIN_ENTRY: &str = "paper-plugin.yml";
+const BUKKIT_PLUGIN_ENTRY: &str = "plugin.yml";
+const VELOCITY_PLUGIN_ENTRY: &str = "velocity-plugin.json";
+const FABRIC_MOD_ENTRY: &str = "fabric.mod.json";
+const QUILT_MOD_ENTRY: &str = "quilt.mod.json";
+const NEOFORGE_MODS_ENTRY: &str = "META-INF/neoforge.mods.toml";
+const FORGE_MODS_ENTRY: &str = "META-INF/mods.toml";
+
+/// 插件目录中的 jar 优先按插件描述解析，模组目录反之。
+const PLUGIN_DESCRIPTOR_ORDER: [&str; 7] = [
+    PAPER_PLUGIN_ENTRY,
+    BUKKIT_PLUGIN_ENTRY,
+    VELOCITY_PLUGIN_ENTRY,
+    FABRIC_MOD_ENTRY,
+    QUILT_MOD_ENTRY,
+    NEOFORGE_MODS_ENTRY,
+    FORGE_MODS_ENTRY,
+];
+const MOD_DESCRIPTOR_ORDER: [&str; 7] = [
+    FABRIC_MOD_ENTRY,
+    QUILT_MOD_ENTRY,
+    NEOFORGE_MODS_ENTRY,
+    FORGE_MODS_ENTRY,
+    PAPER_PLUGIN_ENTRY,
+    BUKKIT_PLUGIN_ENTRY,
+    VELOCITY_PLUGIN_ENTRY,
+];
+
+/// 扫描的 jar 目录及其类型。
+const EXTENSION_DIRECTORIES: [(&str, InstanceExtensionKind); 2] =
+    [("plugins", InstanceExtensionKind::Plugin), ("mods", InstanceExtensionKind::Mod)];
+
+/// 列出实例目录下的插件、模组与数据包，按类型与文件名排序。
+///
+/// [`InstanceExtension::path`] 为相对实例目录的路径。
+pub fn read_instance_extensions(directory: impl AsRef<Path>) -> Vec<InstanceExtension> {
+    let directory = directory.as_ref();
+    let options = InspectionOptions::default();
+    let mut extensions = Vec::new();
+    for (dir_name, kind) in EXTENSION_DIRECTORIES {
+        let mut files: Vec<(String, bool)> = list_file_names(directory, Path::new(dir_name))
+            .into_iter()
+            .filter(|(_, is_file)| *is_file)
+            .filter_map(|(file_name, _)| {
+                let enabled = extension_file_state(&file_name)?;
+                Some((file_name, enabled))
+            })
+            .collect();
+        files.sort();
+        for (file_name, enabled) in files {
+            let relative = Path::new(dir_name).join(&file_name);
+            push_extension(
+                &mut extensions,
+                directory,
+                kind,
+                file_name,
+                relative,
+                enabled,
+                &options,
+            );
+        }
+    }
+
+    let world = world_dir_name(directory);
+    let mut datapacks = Vec::new();
+    for (dir_name, enabled) in [(DATAPACKS_DIR, true), (DISABLED_DATAPACKS_DIR, false)] {
+        let relative_dir = Path::new(&world).join(dir_name);
+        for (file_name, is_file) in list_file_names(directory, &relative_dir) {
+            if is_datapack(&directory.join(&relative_dir).join(&file_name), is_file) {
+                datapacks.push((file_name, enabled, relative_dir.clone()));
+            }
+        }
+    }
+    datapacks.sort();
+    for (file_name, enabled, relative_dir) in datapacks {
+        let relative = relative_dir.join(&file_name);
+        let kind = InstanceExtensionKind::Datapack;
+        push_extension(&mut extensions, directory, kind, file_name, relative, enabled, &options);
+    }
+    extensions
+}
+
+/// 列出目录下的条目名及其是否为文件；目录不存在时为空。
+fn list_file_names(directory: &Path, relative_dir: &Path) -> Vec<(String, bool)> {
+    let entries = match fs::read_dir(directory.join(relative_dir)) {
+        Ok(entries) => entries,
+        Err(error) => {
+            if error.kind() != io::ErrorKind::NotFound {
+                warn!(directory = %directory.display(), dir = %relative_dir.display(), error = %error, "读取扩展目录失败");
+            }
+            return Vec::new();
+        }
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| {
+            let is_file = entry.file_type().ok()?.is_file();
+            Some((entry.file_name().into_string().ok()?, is_file))
+        })
+        .collect()
+}
+
+/// 构造扩展描述并附加 jar 元数据；数据包不读取元数据。
+fn extension_at(
+    directory: &Path,
+    kind: InstanceExtensionKind,
+    file_name: String,
+    relative: PathBuf,
+    enabled: bool,
+    options: &InspectionOptions,
+) -> Result<InstanceExtension, InstanceExtensionError> {
+    let metadata = match kind {
+        InstanceExtensionKind::Datapack => None,
+        _ => read_jar_metadata(&directory.join(&relative), kind, options),
+    };
+    Ok(InstanceExtension::new(kind, file_name, relative, enabled)?.with_metadata(metadata))
+}
+
+fn push_extension(
+    extensions: &mut Vec<InstanceExtension>,
+    directory: &Path,
+    kind: InstanceExtensionKind,
+    file_name: String,
+    relative: PathBuf,
+    enabled: bool,
+    options: &InspectionOptions,
+) {
+    match extension_at(directory, kind, file_name, relative, enabled, options) {
+        Ok(extension) => extensions.push(extension),
+        Err(error) => warn!(error = %error, "跳过无效的扩展文件"),
+    }
+}
+
+/// 世界目录名：`server.properties` 的 `level-name`，缺省为 `world`。
+///
+/// 值不是单层目录名时同样回退到默认值，避免越出实例目录。
+pub fn world_dir_name(directory: &Path) -> String {
+    ServerPropertiesManager::new(directory)
+        .read()
+        .ok()
+        .and_then(|properties| {
+            properties
+                .raw
+                .get("level-name")
+                .map(|name| name.trim().to_owned())
+        })
+        .filter(|name| is_plain_file_name(name))
+        .unwrap_or_else(|| DEFAULT_LEVEL_NAME.to_owned())
+}
+
+fn is_datapack(path: &Path, is_file: bool) -> bool {
+    if is_file {
+        path.extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("zip"))
+    } else {
+        path.join(PACK_METADATA_FILE).is_file()
+    }
+}
+
+/// 文件名是否为扩展 jar：已启用返回 `Some(true)`，已禁用返回 `Some(false)`。
+pub fn extension_file_state(file_name: &str) -> Option<bool> {
+    let lower = file_name.to_ascii_lowercase();
+    if file_name.starts_with('.') {
+        None
+    } else if lower.ends_with(".jar") {
+        Some(true)
+    } else if lower.ends_with(".jar.disabled") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// 启停扩展失败的原因。
+#[derive(Debug)]
+pub enum ExtensionToggleError {
+    /// 文件名不是单层文件名，或不是该类型可管理的文件。
+    InvalidFileName,
+    /// 启用与禁用位置都不存在该扩展。
+    NotFound,
+    /// 启用与禁用位置同时存在同名文件，无法在不覆盖的情况下移动。
+    TargetExists {
+        path: PathBuf,
+    },
+    Io(io::Error),
+}
+
+impl fmt::Display for ExtensionToggleError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidFileName => formatter.write_str("invalid extension file name"),
+            Self::NotFound => formatter.write_str("extension not found"),
+            Self::TargetExists { path } => {
+                write!(formatter, "target already exists: {}", path.display())
+            }
+            Self::Io(error) => write!(formatter, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ExtensionToggleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ExtensionToggleError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+/// 启用或禁用一个扩展，返回移动后的扩展描述。
+///
+/// `file_name` 可以是启用或禁用状态下的文件名；扩展已处于目标状态时不做任何改动。
+/// 插件与模组通过追加 / 去掉 [`DISABLED_EXTENSION_SUFFIX`] 改名，数据包在
+/// [`DATAPACKS_DIR`] 与 [`DISABLED_DATAPACKS_DIR`] 之间移动。
+pub fn set_extension_enabled(
+    directory: impl AsRef<Path>,
+    kind: InstanceExtensionKind,
+    file_name: &str,
+    enabled: bool,
+) -> Result<InstanceExtension, ExtensionToggleError> {
+    let directory = directory.as_ref();
+    if !is_plain_file_name(file_name) {
+        return Err(ExtensionToggleError::InvalidFileName);
+    }
+    let (enabled_path, disabled_path) = match kind {
+        InstanceExtensionKind::Plugin | InstanceExtensionKind::Mod => {
+            let dir_name = if kind == InstanceExtensionKind::Plugin {
+                "plugins"
+            } else {
+                "mods"
+            };
+            let base = file_name
+                .strip_suffix(DISABLED_EXTENSION_SUFFIX)
+                .unwrap_or(file_name);
+            if extension_file_state(base) != Some(true) {
+                return Err(ExtensionToggleError::InvalidFileName);
+            }
+            let dir = Path::new(dir_name);
+            (dir.join(base), dir.join(format!("{base}{DISABLED_EXTENSION_SUFFIX}")))
+        }
+        InstanceExtensionKind::Datapack => {
+            let world = PathBuf::from(world_dir_name(directory));
+            (
+                world.join(DATAPACKS_DIR).join(file_name),
+                world.join(DISABLED_DATAPACKS_DIR).join(file_name),
+            )
+        }
+    };
+    let (from, to) = if enabled {
+        (disabled_path, enabled_path)
+    } else {
+        (enabled_path, disabled_path)
+    };
+
+    let from_exists = fs::symlink_metadata(directory.join(&from)).is_ok();
+    let to_exists = fs::symlink_metadata(directory.join(&to)).is_ok();
+    match (from_exists, to_exists) {
+        (false, false) => return Err(ExtensionToggleError::NotFound),
+        (true, true) => return Err(ExtensionToggleError::TargetExists { path: to }),
+        (false, true) => {}
+        (true, false) => {
+            if let Some(parent) = directory.join(&to).parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::rename(directory.join(&from), directory.join(&to))?;
+        }
+    }
+
+    let file_name = to
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(file_name)
+        .to_owned();
+    extension_at(directory, kind, file_name, to, enabled, &InspectionOptions::default())
+        .map_err(|_| ExtensionToggleError::InvalidFileName)
+}
+
+/// 读取单个 jar 的元数据；不含可识别的描述文件或读取失败时返回 `None`。
+pub fn read_jar_metadata(
+    path: &Path,
+    kind: InstanceExtensionKind,
+    options: &InspectionOptions,
+) -> Option<ExtensionMetadata> {
+    let order = match kind {
+        InstanceExtensionKind::Mod => MOD_DESCRIPTOR_ORDER,
+        InstanceExtensionKind::Plugin | InstanceExtensionKind::Datapack => PLUGIN_DESCRIPTOR_ORDER,
+    };
+    let entries = match read_archive_entries(path, &order, options) {
+        Ok(entries) => entries,
+        Err(error) => {
+            warn!(path = %path.display(), error = %error, "读取扩展描述文件失败");
+            return None;
+        }
+    };
+    order.iter().find_map(|name| {
+        let bytes = entries.entries.get(*name)?;
+        let content = String::from_utf8_lossy(bytes);
+        parse_descriptor(name, &content)
+    })
+}
+
+/// 按描述文件名解析内容。
+pub fn parse_descriptor(entry_name: &str, content: &str) -> Option<ExtensionMetadata> {
+    match entry_name {
+        PAPER_PLUGIN_ENTRY => parse_paper_plugin_yml(content),
+        BUKKIT_PLUGIN_ENTRY => parse_plugin_yml(content),
+        VELOCITY_PLUGIN_ENTRY => parse_velocity_plugin_json(content),
+        FABRIC_MOD_ENTRY => parse_fabric_mod_json(content),
+        QUILT_MOD_ENTRY => parse_quilt_mod_json(content),
+        NEOFORGE_MODS_ENTRY => parse_mods_toml(content, ExtensionMetadataFormat::NeoForge),
+        FORGE_MODS_ENTRY => parse_mods_toml(content, ExtensionMetadataFormat::Forge),
+        _ => None,
+    }
+}
+
+fn metadata(format: ExtensionMetadataFormat, id: String) -> ExtensionMetadata {
+    ExtensionMetadata {
+        format,
+        id,
+        name: None,
+        version: None,
+        authors: Vec::new(),
+        dependencies: Vec::new(),
+        provides: Vec::new(),
+        game_versions: None,
+        loader_versions: None,
+    }
+}
+
+fn dependency(
+    id: impl Into<String>,
+    required: bool,
+    version_range: Option<String>,
+) -> ExtensionDependency {
+    ExtensionDependency { id: id.into(), required, version_range }
+}
+
+fn non_empty(value: String) -> Option<String> {
+    let trimmed = value.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_owned())
+}
+
+// ─── plugin.yml / paper-plugin.yml ──────────────────────────────────────
+
+/// YAML 标量转字符串；版本号常被写成数字。
+fn yaml_string(value: &YamlValue) -> Option<String> {
+    match value {
+        YamlValue::String(text) => non_empty(text.clone()),
+        YamlValue::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+fn yaml_strings(value: Option<&YamlValue>) -> Vec<String> {
+    match value {
+        Some(YamlValue::Sequence(items)) => items.iter().filter_map(yaml_string).collect(),
+        Some(value) => yaml_string(value).into_iter().collect(),
+        None => Vec::new(),
+    }
+}
+
+/// 解析 YAML 描述中两种格式共有的字段。
+fn yaml_plugin_base(
+    format: ExtensionMetadataFormat,
+    content: &str,
+) -> Option<(ExtensionMetadata, YamlValue)> {
+    let document: YamlValue = serde_yaml::from_str(content).ok()?;
+    let name = document.get("name").and_then(yaml_string)?;
+    let mut plugin = metadata(format, name.clone());
+    plugin.name = Some(name);
+    plugin.version = document.get("version").and_then(yaml_string);
+    plugin.authors = yaml_strings(document.get("author"));
+    plugin.authors.extend(yaml_strings(document.get("authors")));
+    plugin.game_versions = document.get("api-version").and_then(yaml_string);
+    Some((plugin, document))
+}
+
+fn parse_plugin_yml(content: &str) -> Option<ExtensionMetadata> {
+    let (mut plugin, document) = yaml_plugin_base(ExtensionMetadataFormat::Bukkit, content)?;
+    for (key, required) in [("depend", true), ("softdepend", false)] {
+        plugin.dependencies.extend(
+            yaml_strings(document.get(key))
+                .into_iter()
+                .map(|id| dependency(id, required, None)),
+        );
+    }
+    Some(plugin)
+}
+
+/// `paper-plugin.yml` 的依赖写在 `dependencies.server`（或旧版的列表）中，`required` 默认为真。
+fn parse_paper_plugin_yml(content: &str) -> Option<ExtensionMetadata> {
+    let (mut plugin, document) = yaml_plugin_base(ExtensionMetadataFormat::Paper, content)?;
+    let required = |entry: &YamlValue| {
+        entry
+            .get("required")
+            .and_then(YamlValue::as_bool)
+            .unwrap_or(true)
+    };
+    match document.get("dependencies") {
+        Some(YamlValue::Mapping(groups)) => {
+            let server = groups
+                .get("server")
+                .and_then(YamlValue::as_mapping)
+                .into_iter()
+                .flatten();
+            for (id, entry) in server {
+                if let Some(id) = yaml_string(id) {
+                    plugin
+                        .dependencies
+                        .push(dependency(id, required(entry), None));
+                }
+            }
+        }
+        Some(YamlValue::Sequence(entries)) => {
+            for entry in entries {
+                if let Some(id) = entry.get("name").and_then(yaml_string) {
+                    plugin
+                        .dependencies
+                        .push(dependency(id, required(entry), None));
+                }
+            }
+        }
+        _ => {}
+    }
+    Some(plugin)
+}
+
+// ─── velocity-plugin.json / fabric.mod.json / quilt.mod.json ────────────
+
+fn json_string(value: &JsonValue) -> Option<String> {
+    value.as_str().map(str::to_owned).and_then(non_empty)
+}
+
+/// 作者列表：字符串或带 `name` 字段的对象。
+fn json_people(value: Option<&JsonValue>) -> Vec<String> {
+    value
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|person| {
+            json_string(person).or_else(|| person.get("name").and_then(json_string))
+        })
+        .collect()
+}
+
+/// 版本约束：字符串或字符串数组（任一满足即可）。
+fn json_version_range(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::Array(ranges) => {
+            let ranges: Vec<String> = ranges.iter().filter_map(json_string).collect();
+            non_empty(ranges.join(" || "))
+        }
+        value => json_string(value),
+    }
+}
+
+fn parse_velocity_plugin_json(content: &str) -> Option<ExtensionMetadata> {
+    let document: JsonValue = serde_json::from_str(content).ok()?;
+    let mut plugin =
+        metadata(ExtensionMetadataFormat::Velocity, document.get("id").and_then(json_string)?);
+    plugin.name = document.get("name").and_then(json_string);
+    plugin.version = document.get("version").and_then(json_string);
+    plugin.authors = json_people(document.get("authors"));
+    plugin.dependencies = document
+        .get("dependencies")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| {
+            let optional = entry
+                .get("optional")
+                .and_then(JsonValue::as_bool)
+                .unwrap_or(false);
+            Some(dependency(entry.get("id").and_then(json_string)?, !optional, None))
+        })
+        .collect();
+    Some(plugin)
+}
+
+/// Fabric 依赖表 `{ "id": 版本约束 }` 中的游戏本体与加载器单独记录，不计入依赖。
+fn parse_fabric_mod_json(content: &str) -> Option<ExtensionMetadata> {
+    let document: JsonValue = serde_json::from_str(content).ok()?;
+    let mut fabric_mod =
+        metadata(ExtensionMetadataFormat::Fabric, document.get("id").and_then(json_string)?);
+    fabric_mod.name = document.get("name").and_then(json_string);
+    fabric_mod.version = document.get("version").and_then(json_string);
+    fabric_mod.authors = json_people(document.get("authors"));
+    fabric_mod.provides = document
+        .get("provides")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(json_string)
+        .collect();
+    for (key, required) in [("depends", true), ("recommends", false), ("suggests", false)] {
+        let Some(entries) = document.get(key).and_then(JsonValue::as_object) else {
+            continue;
+        };
+        for (id, range) in entries {
+            let range = json_version_range(range);
+            match id.as_str() {
+                "minecraft" if required => fabric_mod.game_versions = range,
+                "fabricloader" if required => fabric_mod.loader_versions = range,
+                "minecraft" | "fabricloader" | "java" => {}
+                _ => fabric_mod
+                    .dependencies
+                    .push(dependency(id.clone(), required, range)),
+            }
+        }
+    }
+    Some(fabric_mod)
+}
+
+/// Quilt 的元数据位于 `quilt_loader` 下，依赖为字符串或 `{ id, versions, optional }`。
+fn parse_quilt_mod_json(content: &str) -> Option<ExtensionMetadata> {
+    let document: JsonValue = serde_json::from_str(content).ok()?;
+    let loader = document.get("quilt_loader")?;
+    let mut quilt_mod =
+        metadata(ExtensionMetadataFormat::Quilt, loader.get("id").and_then(json_string)?);
+    quilt_mod.version = loader.get("version").and_then(json_string);
+    if let Some(details) = loader.get("metadata") {
+        quilt_mod.name = details.get("name").and_then(json_string);
+        quilt_mod.authors = details
+            .get("contributors")
+            .and_then(JsonValue::as_object)
+            .map(|contributors| contributors.keys().cloned().collect())
+            .unwrap_or_default();
+    }
+    quilt_mod.provides = loader
+        .get("provides")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| json_string(entry).or_else(|| entry.get("id").and_then(json_string)))
+        .collect();
+    for entry in loader
+        .get("depends")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let (id, range, required) = match entry {
+            JsonValue::String(id) => (id.clone(), None, true),
+            entry => {
+                let Some(id) = entry.get("id").and_then(json_string) else {
+                    continue;
+                };
+                let optional = entry
+                    .get("optional")
+                    .and_then(JsonValue::as_bool)
+                    .unwrap_or(false);
+                (id, entry.get("versions").and_then(json_version_range), !optional)
+            }
+        };
+        match id.as_str() {
+            "minecraft" => quilt_mod.game_versions = range,
+            "quilt_loader" => quilt_mod.loader_versions = range,
+            "java" => {}
+            _ => quilt_mod.dependencies.push(dependency(id, required, range)),
+        }
+    }
+    Some(quilt_mod)
+}
+
+// ─── mods.toml / neoforge.mods.toml ─────────────────────────────────────
+
+fn toml_string(table: &dyn TableLike, key: &str) -> Option<String> {
+    table
+        .get(key)?
+        .as_str()
+        .map(str::to_owned)
+        .and_then(non_empty)
+        // `${file.jarVersion}` 等占位符在构建时才替换，视为未知
+        .filter(|value| !value.starts_with("${"))
+}
+
+/// `[[key]]` 表数组或内联表数组。
+fn toml_tables(item: Option<&Item>) -> Vec<&dyn TableLike> {
+    match item {
+        Some(Item::ArrayOfTables(tables)) => {
+            tables.iter().map(|table| table as &dyn TableLike).collect()
+        }
+        Some(Item::Value(value)) => value
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|value| value.as_inline_table())
+            .map(|table| table as &dyn TableLike)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// 解析 Forge / NeoForge 的 `mods.toml`；一个 jar 声明多个模组时取第一个。
+///
+/// Forge 用 `mandatory` 标记必需依赖，NeoForge 用 `type = "required"`；
+/// 依赖 `neoforge` 的 `mods.toml` 按 NeoForge 处理。
+fn parse_mods_toml(content: &str, format: ExtensionMetadataFormat) -> Option<ExtensionMetadata> {
+    let document: DocumentMut = content.parse().ok()?;
+    let tables = toml_tables(document.get("mods"));
+    let first = tables.first()?;
+    let mut forge_mod = metadata(format, toml_string(*first, "modId")?);
+    forge_mod.name = toml_string(*first, "displayName");
+    forge_mod.version = toml_string(*first, "version");
+    forge_mod.authors = toml_string(*first, "authors")
+        .map(|authors| {
+            authors
+                .split(',')
+                .filter_map(|author| non_empty(author.to_owned()))
+                .collect()
+        })
+        .unwrap_or_default();
+    forge_mod.loader_versions = toml_string(document.as_table(), "loaderVersion");
+
+    let dependencies = document
+        .get("dependencies")
+        .and_then(Item::as_table_like)
+        .and_then(|dependencies| dependencies.get(&forge_mod.id));
+    for entry in toml_tables(dependencies) {
+        let Some(id) = toml_string(entry, "modId") else {
+            continue;
+        };
+        let required = match entry.get("mandatory").and_then(Item::as_bool) {
+            Some(mandatory) => mandatory,
+            None => {
+                toml_string(entry, "type").is_none_or(|kind| kind.eq_ignore_ascii_case("required"))
+            }
+        };
+        if toml_string(entry, "type").is_some_and(|kind| {
+            kind.eq_ignore_ascii_case("incompatible") || kind.eq_ignore_ascii_case("discouraged")
+        }) {
+            continue;
+        }
+        let range = toml_string(entry, "versionRange");
+        match id.as_str() {
+            "minecraft" => forge_mod.game_versions = range,
+            "forge" | "neoforge" => {
+                if id == "neoforge" {
+                    forge_mod.format = ExtensionMetadataFormat::NeoForge;
+                }
+                forge_mod.loader_versions = range.or(forge_mod.loader_versions);
+            }
+            _ => forge_mod.dependencies.push(dependency(id, required, range)),
+        }
+    }
+    Some(forge_mod)
+}
+
+#[cfg(test)]
+mod tests {
+    use sealantern_infra::archive::create_zip;
+
+    use super::*;
+
+    fn write_jar(path: &Path, entries: &[(&str, &str)]) {
+        let staging = tempfile::tempdir().unwrap();
+        for (name, content) in entries {
+            let file = staging.path().join(name);
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, content).unwrap();
+        }
+        create_zip(staging.path(), path).unwrap();
+    }
+
+    #[test]
+    fn parses_plugin_descriptors() {
+        let bukkit = parse_descriptor(
+            BUKKIT_PLUGIN_ENTRY,
+            "name: Essentials\nversion: 2.20\nauthor: zenexer\nauthors: [ementalo, snowleo]\n\
+             api-version: '1.13'\ndepend: [Vault]\nsoftdepend: [LuckPerms]\n",
+        )
+        .unwrap();
+        assert_eq!(bukkit.format, ExtensionMetadataFormat::Bukkit);
+        assert_eq!(bukkit.id, "Essentials");
+        assert_eq!(bukkit.version.as_deref(), Some("2.2"));
+        assert_eq!(bukkit.authors, ["zenexer", "ementalo", "snowleo"]);
+        assert_eq!(bukkit.game_versions.as_deref(), Some("1.13"));
+        assert_eq!(
+            bukkit.dependencies,
+            [dependency("Vault", true, None), dependency("LuckPerms", false, None)]
+        );
+
+        let paper = parse_descriptor(
+            PAPER_PLUGIN_ENTRY,
+            "name: Shop\nversion: '1.0.0'\napi-version: '1.20'\ndependencies:\n  server:\n    \
+             Vault:\n      load: BEFORE\n    PlaceholderAPI:\n      required: false\n",
+        )
+        .unwrap();
+        assert_eq!(paper.format, ExtensionMetadataFormat::Paper);
+        assert_eq!(
+            paper.dependencies,
+            [dependency("Vault", true, None), dependency("PlaceholderAPI", false, None)]
+        );
+
+        let velocity = parse_descriptor(
+            VELOCITY_PLUGIN_ENTRY,
+            r#"{"id":"luckperms","name":"LuckPerms","version":"5.4","authors":["Luck"],
+                "dependencies":[{"id":"viaversion","optional":true}]}"#,
+        )
+        .unwrap();
+        assert_eq!(velocity.id, "luckperms");
+        assert_eq!(velocity.dependencies, [dependency("viaversion", false, None)]);
+        assert!(parse_descriptor(BUKKIT_PLUGIN_ENTRY, "version: 1\n").is_none());
+    }
+
+    #[test]
+    fn parses_mod_descriptors() {
+        let fabric = parse_descriptor(
+            FABRIC_MOD_ENTRY,
+            r#"{"schemaVersion":1,"id":"iris","version":"1.7.0","name":"Iris",
+                "authors":["coderbot",{"name":"IMS"}],"provides":["oculus"],
+                "depends":{"fabricloader":">=0.15","minecraft":["1.20.1","1.20.2"],"sodium":"0.5.x","java":">=17"},
+                "recommends":{"modmenu":"*"}}"#,
+        )
+        .unwrap();
+        assert_eq!(fabric.authors, ["coderbot", "IMS"]);
+        assert_eq!(fabric.provides, ["oculus"]);
+        assert_eq!(fabric.game_versions.as_deref(), Some("1.20.1 || 1.20.2"));
+        assert_eq!(fabric.loader_versions.as_deref(), Some(">=0.15"));
+        assert_eq!(
+            fabric.dependencies,
+            [
+                dependency("sodium", true, Some("0.5.x".to_owned())),
+                dependency("modmenu", false, Some("*".to_owned())),
+            ]
+        );
+
+        let quilt = parse_descriptor(
+            QUILT_MOD_ENTRY,
+            r#"{"quilt_loader":{"id":"qsl","version":"6.1.0","metadata":{"name":"QSL","contributors":{"Quilt":"Owner"}},
+                "depends":["quilt_base",{"id":"minecraft","versions":">=1.20"},{"id":"extra","optional":true}]}}"#,
+        )
+        .unwrap();
+        assert_eq!(quilt.format, ExtensionMetadataFormat::Quilt);
+        assert_eq!(quilt.authors, ["Quilt"]);
+        assert_eq!(quilt.game_versions.as_deref(), Some(">=1.20"));
+        assert_eq!(
+            quilt.dependencies,
+            [dependency("quilt_base", true, None), dependency("extra", false, None)]
+        );
+
+        let forge = parse_descriptor(
+            FORGE_MODS_ENTRY,
+            r#"modLoader = "javafml"
+loaderVersion = "[47,)"
+
+[[mods]]
+modId = "jei"
+version = "${file.jarVersion}"
+displayName = "Just Enough Items"
+authors = "mezz, Ranger"
+
+[[dependencies.jei]]
+modId = "forge"
+mandatory = true
+versionRange = "[47.1,)"
+
+[[dependencies.jei]]
+modId = "minecraft"
+mandatory = true
+versionRange = "[1.20.1,1.21)"
+
+[[dependencies.jei]]
+modId = "curios"
+mandatory = false
+versionRange = "*"
+"#,
+        )
+        .unwrap();
+        assert_eq!(forge.format, ExtensionMetadataFormat::Forge);
+        assert_eq!(forge.id, "jei");
+        assert_eq!(forge.version, None);
+        assert_eq!(forge.authors, ["mezz", "Ranger"]);
+        assert_eq!(forge.game_versions.as_deref(), Some("[1.20.1,1.21)"));
+        assert_eq!(forge.loader_versions.as_deref(), Some("[47.1,)"));
+        assert_eq!(forge.dependencies, [dependency("curios", false, Some("*".to_owned()))]);
+
+        let neoforge = parse_descriptor(
+            NEOFORGE_MODS_ENTRY,
+            r#"loaderVersion = "[4,)"
+mods = [{ modId = "ae2", version = "19.0.1" }]
+[[dependencies.ae2]]
+modId = "neoforge"
+type = "required"
+versionRange = "[21.1,)"
+[[dependencies.ae2]]
+modId = "guideme"
+type = "required"
+[[dependencies.ae2]]
+modId = "oldmod"
+type = "incompatible"
+"#,
+        )
+        .unwrap();
+        assert_eq!(neoforge.format, ExtensionMetadataFormat::NeoForge);
+        assert_eq!(neoforge.version.as_deref(), Some("19.0.1"));
+        assert_eq!(neoforge.loader_versions.as_deref(), Some("[21.1,)"));
+        assert_eq!(neoforge.dependencies, [dependency("guideme", true, None)]);
+    }
+
+    #[test]
+    fn lists_plugins_and_mods_with_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("plugins")).unwrap();
+        fs::create_dir_all(dir.path().join("mods")).unwrap();
+        write_jar(
+            &dir.path().join("plugins/Essentials.jar"),
+            &[(BUKKIT_PLUGIN_ENTRY, "name: Essentials\nversion: '2.20'\n")],
+        );
+        write_jar(
+            &dir.path().join("mods/sodium.jar.disabled"),
+            &[(FABRIC_MOD_ENTRY, r#"{"id":"sodium","version":"0.5.8"}"#)],
+        );
+        fs::write(dir.path().join("mods/broken.jar"), "not a zip").unwrap();
+        fs::write(dir.path().join("mods/readme.txt"), "ignored").unwrap();
+
+        let extensions = read_instance_extensions(dir.path());
+        assert_eq!(extensions.len(), 3);
+        assert_eq!(extensions[0].kind, InstanceExtensionKind::Plugin);
+        assert_eq!(extensions[0].path, Path::new("plugins").join("Essentials.jar"));
+        assert_eq!(extensions[0].metadata.as_ref().unwrap().id, "Essentials");
+        assert_eq!(extensions[1].file_name, "broken.jar");
+        assert!(extensions[1].metadata.is_none());
+        assert!(!extensions[2].enabled);
+        assert_eq!(extensions[2].metadata.as_ref().unwrap().version.as_deref(), Some("0.5.8"));
+    }
+
+    #[test]
+    fn toggles_jars_and_datapacks() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("server.properties"), "level-name=survival\n").unwrap();
+        fs::create_dir_all(dir.path().join("mods")).unwrap();
+        fs::write(dir.path().join("mods/lithium.jar"), "jar").unwrap();
+        let pack = dir.path().join("survival/datapacks/terralith");
+        fs::create_dir_all(&pack).unwrap();
+        fs::write(pack.join(PACK_METADATA_FILE), "{}").unwrap();
+        fs::write(dir.path().join("survival/datapacks/notes.txt"), "ignored").unwrap();
+
+        let disabled =
+            set_extension_enabled(dir.path(), InstanceExtensionKind::Mod, "lithium.jar", false)
+                .unwrap();
+        assert_eq!(disabled.file_name, "lithium.jar.disabled");
+        assert!(!disabled.enabled);
+        assert!(dir.path().join("mods/lithium.jar.disabled").is_file());
+        // 已处于目标状态时不做改动
+        let again =
+            set_extension_enabled(dir.path(), InstanceExtensionKind::Mod, "lithium.jar", false)
+                .unwrap();
+        assert_eq!(again, disabled);
+
+        let datapack =
+            set_extension_enabled(dir.path(), InstanceExtensionKind::Datapack, "terralith", false)
+                .unwrap();
+        assert_eq!(
+            datapack.path,
+            Path::new("survival")
+                .join(DISABLED_DATAPACKS_DIR)
+                .join("terralith")
+        );
+        assert!(!pack.exists());
+
+        let extensions = read_instance_extensions(dir.path());
+        let summary: Vec<_> = extensions
+            .iter()
+            .map(|extension| (extension.kind, extension.file_name.as_str(), extension.enabled))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (InstanceExtensionKind::Mod, "lithium.jar.disabled", false),
+                (InstanceExtensionKind::Datapack, "terralith", false),
+            ]
+        );
+
+        let enabled = set_extension_enabled(
+            dir.path(),
+            InstanceExtensionKind::Mod,
+            "lithium.jar.disabled",
+            true,
+        )
+        .unwrap();
+        assert_eq!(enabled.path, Path::new("mods").join("lithium.jar"));
+
+        fs::write(dir.path().join("mods/lithium.jar.disabled"), "old").unwrap();
+        assert!(matches!(
+            set_extension_enabled(dir.path(), InstanceExtensionKind::Mod, "lithium.jar", false),
+            Err(ExtensionToggleError::TargetExists { .. })
+        ));
+        assert!(matches!(
+            set_extension_enabled(dir.path(), InstanceExtensionKind::Mod, "../lithium.jar", true),
+            Err(ExtensionToggleError::InvalidFileName)
+        ));
+        assert!(matches!(
+            set_extension_enabled(dir.path(), InstanceExtensionKind::Plugin, "missing.jar", true),
+            Err(ExtensionToggleError::NotFound)
+        ));
+    }
+}
@@ -1,6 +1,14 @@
 //! 服务器扩展功能。
 
+pub mod command;
 pub mod cron_task;
+pub mod extensions;
 pub mod log;
+pub mod player;
+pub mod ports;
+pub mod probe;
+pub mod rcon;
 
+pub use extensions::{ExtensionToggleError, read_instance_extensions, set_extension_enabled};
 pub use log::{LOG_DATABASE_FILE, LogLine, LogSource, LogWriter};
+pub use ports::read_port_bindings;
@@ -0,0 +1,13 @@
+//! 服务器玩家会话的持久化存储。
+//!
+//! 按服务器目录持久化玩家在线会话（玩家、加入 / 离开时刻与时长），
+//! 会话由上层根据控制台玩家事件开启与收尾。数据访问复用 `infra` 的
+//! [`SqliteDatabase`](sealantern_infra::persistence::SqliteDatabase)，
+//! 本模块不绑定任何宿主。
+
+mod store;
+
+pub use store::{
+    PLAYER_DATABASE_FILE, PlayerSession, close_open_sessions, close_session, open_player_database,
+    open_session, read_sessions, update_session_uuid,
+};
@@ -0,0 +1,225 @@
+//! 玩家会话库的存储与读取。
+//!
+//! 会话表 `player_sessions` 以自增行号标识一次在线会话：加入时插入
+//! `left_at` 为空的行，离开时补齐离开时刻与时长。服务器停止或异常退出后
+//! 遗留的未收尾会话由 [`close_open_sessions`] 统一收尾。
+
+use std::path::Path;
+
+use sealantern_infra::persistence::{PersistenceError, SqlValue, SqliteDatabase};
+
+/// 玩家会话数据库文件名（存放在服务器目录下）。
+pub const PLAYER_DATABASE_FILE: &str = "sea_lantern_players.sqlite";
+
+/// 会话库建表语句（幂等）。
+const PLAYER_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS player_sessions (\
+     id INTEGER PRIMARY KEY AUTOINCREMENT,\
+     player TEXT NOT NULL,\
+     uuid TEXT,\
+     joined_at INTEGER NOT NULL,\
+     left_at INTEGER,\
+     duration_secs INTEGER\
+ );\
+ CREATE INDEX IF NOT EXISTS player_sessions_player ON player_sessions (player, joined_at);";
+
+/// 一次持久化的玩家在线会话。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlayerSession {
+    /// 会话行号（单调递增）。
+    pub id: i64,
+    /// 玩家名称。
+    pub player: String,
+    /// 玩家 UUID；离线模式或未解析时为 `None`。
+    pub uuid: Option<String>,
+    /// 加入时刻（Unix 秒）。
+    pub joined_at: i64,
+    /// 离开时刻（Unix 秒）；仍在线时为 `None`。
+    pub left_at: Option<i64>,
+    /// 在线时长（秒）；仍在线时为 `None`。
+    pub duration_secs: Option<i64>,
+}
+
+/// 打开（或创建）服务器玩家会话数据库，并确保表结构存在。
+pub async fn open_player_database(server_path: &Path) -> Result<SqliteDatabase, PersistenceError> {
+    SqliteDatabase::open_with_schema(server_path.join(PLAYER_DATABASE_FILE), PLAYER_SCHEMA).await
+}
+
+/// 开启一次会话，返回会话行号。
+pub async fn open_session(
+    database: &SqliteDatabase,
+    player: &str,
+    uuid: Option<&str>,
+    joined_at: i64,
+) -> Result<i64, PersistenceError> {
+    database
+        .insert(
+            "INSERT INTO player_sessions (player, uuid, joined_at) VALUES (?1, ?2, ?3)",
+            [
+                SqlValue::Text(player.to_owned()),
+                uuid.map_or(SqlValue::Null, |uuid| SqlValue::Text(uuid.to_owned())),
+                SqlValue::Integer(joined_at),
+            ],
+        )
+        .await
+}
+
+/// 为仍在线的会话补记 UUID（UUID 晚于加入事件解析时使用）。
+pub async fn update_session_uuid(
+    database: &SqliteDatabase,
+    session_id: i64,
+    uuid: &str,
+) -> Result<(), PersistenceError> {
+    database
+        .execute(
+            "UPDATE player_sessions SET uuid = ?2 WHERE id = ?1",
+            [SqlValue::Integer(session_id), SqlValue::Text(uuid.to_owned())],
+        )
+        .await
+        .map(|_| ())
+}
+
+/// 收尾一次会话；已收尾的会话保持不变。
+pub async fn close_session(
+    database: &SqliteDatabase,
+    session_id: i64,
+    left_at: i64,
+) -> Result<(), PersistenceError> {
+    database
+        .execute(
+            "UPDATE player_sessions \
+             SET left_at = ?2, duration_secs = MAX(?2 - joined_at, 0) \
+             WHERE id = ?1 AND left_at IS NULL",
+            [SqlValue::Integer(session_id), SqlValue::Integer(left_at)],
+        )
+        .await
+        .map(|_| ())
+}
+
+/// 收尾所有未结束的会话，返回受影响的会话数。
+///
+/// 用于服务器停止（所有玩家随之离线）与启动时清理上次异常退出遗留的会话。
+pub async fn close_open_sessions(
+    database: &SqliteDatabase,
+    left_at: i64,
+) -> Result<usize, PersistenceError> {
+    database
+        .execute(
+            "UPDATE player_sessions \
+             SET left_at = ?1, duration_secs = MAX(?1 - joined_at, 0) \
+             WHERE left_at IS NULL",
+            std::iter::once(SqlValue::Integer(left_at)),
+        )
+        .await
+}
+
+/// 按加入时刻倒序读取会话；`player` 提供时只返回该玩家的会话。
+pub async fn read_sessions(
+    database: &SqliteDatabase,
+    player: Option<&str>,
+    limit: i64,
+) -> Result<Vec<PlayerSession>, PersistenceError> {
+    if limit <= 0 {
+        return Err(PersistenceError::InvalidInput {
+            reason: format!("limit must be positive, got {limit}"),
+        });
+    }
+    match player {
+        Some(player) => {
+            database
+                .query(
+                    "SELECT id, player, uuid, joined_at, left_at, duration_secs \
+                     FROM player_sessions WHERE player = ?1 \
+                     ORDER BY joined_at DESC, id DESC LIMIT ?2",
+                    [SqlValue::Text(player.to_owned()), SqlValue::Integer(limit)],
+                    map_session,
+                )
+                .await
+        }
+        None => {
+            database
+                .query(
+                    "SELECT id, player, uuid, joined_at, left_at, duration_secs \
+                     FROM player_sessions ORDER BY joined_at DESC, id DESC LIMIT ?1",
+                    std::iter::once(SqlValue::Integer(limit)),
+                    map_session,
+                )
+                .await
+        }
+    }
+}
+
+fn map_session(row: &rusqlite::Row<'_>) -> rusqlite::Result<PlayerSession> {
+    Ok(PlayerSession {
+        id: row.get(0)?,
+        player: row.get(1)?,
+        uuid: row.get(2)?,
+        joined_at: row.get(3)?,
+        left_at: row.get(4)?,
+        duration_secs: row.get(5)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn open_test_database() -> (tempfile::TempDir, SqliteDatabase) {
+        let directory = tempfile::tempdir().expect("临时目录应创建成功");
+        let database = open_player_database(directory.path())
+            .await
+            .expect("会话库应初始化成功");
+        (directory, database)
+    }
+
+    #[tokio::test]
+    async fn session_lifecycle_records_duration() {
+        let (_directory, database) = open_test_database().await;
+        let id = open_session(&database, "Steve", None, 1000)
+            .await
+            .expect("开启会话");
+        update_session_uuid(&database, id, "069a79f4-44e9-4726-a5be-fca90e38aaf5")
+            .await
+            .expect("补记 UUID");
+        close_session(&database, id, 1600).await.expect("收尾会话");
+        // 重复收尾不覆盖已有离开时刻。
+        close_session(&database, id, 9999).await.expect("重复收尾");
+
+        let sessions = read_sessions(&database, Some("Steve"), 10)
+            .await
+            .expect("读取");
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].uuid.as_deref(), Some("069a79f4-44e9-4726-a5be-fca90e38aaf5"));
+        assert_eq!(sessions[0].left_at, Some(1600));
+        assert_eq!(sessions[0].duration_secs, Some(600));
+    }
+
+    #[tokio::test]
+    async fn close_open_sessions_only_touches_unfinished_rows() {
+        let (_directory, database) = open_test_database().await;
+        let finished = open_session(&database, "Alex", None, 100)
+            .await
+            .expect("开启");
+        close_session(&database, finished, 200).await.expect("收尾");
+        open_session(&database, "Steve", None, 300)
+            .await
+            .expect("开启");
+
+        assert_eq!(close_open_sessions(&database, 500).await.expect("收尾"), 1);
+
+        let sessions = read_sessions(&database, None, 10).await.expect("读取");
+        let durations: Vec<_> = sessions
+            .iter()
+            .map(|session| (session.player.as_str(), session.duration_secs))
+            .collect();
+        assert_eq!(durations, [("Steve", Some(200)), ("Alex", Some(100))]);
+    }
+
+    #[tokio::test]
+    async fn read_sessions_rejects_non_positive_limit() {
+        let (_directory, database) = open_test_database().await;
+        assert!(matches!(
+            read_sessions(&database, None, 0).await,
+            Err(PersistenceError::InvalidInput { .. })
+        ));
+    }
+}
@@ -0,0 +1,171 @@
+//! 读取实例实际占用的端口。
+//!
+//! 游戏、查询与 RCON 端口取自 server.properties；代理端的监听端口分别取自
+//! Velocity 的 `velocity.toml` 与 BungeeCord 系的 `config.yml`。读取尽力而为：
+//! 文件缺失或无法解析时跳过对应来源，游戏端口回退到实例记录的端口。
+
+use std::fs;
+use std::path::Path;
+
+use sealantern_core::instance::{PortBinding, PortKind};
+use tracing::warn;
+
+use crate::config::ServerPropertiesManager;
+use crate::server::rcon::read_rcon_settings;
+
+const VELOCITY_CONFIG_FILE: &str = "velocity.toml";
+const BUNGEE_CONFIG_FILE: &str = "config.yml";
+
+/// 读取实例目录下配置的全部端口，按游戏、查询、RCON、代理监听的顺序返回。
+///
+/// 目录中只有代理配置而没有 server.properties 时视为纯代理实例，不再回退游戏端口。
+pub fn read_port_bindings(directory: impl AsRef<Path>, fallback_port: u16) -> Vec<PortBinding> {
+    let directory = directory.as_ref();
+    let listeners = read_proxy_listeners(directory);
+    if !listeners.is_empty() && !directory.join("server.properties").is_file() {
+        return listeners;
+    }
+
+    let raw = match ServerPropertiesManager::new(directory).read() {
+        Ok(properties) => properties.raw,
+        Err(error) => {
+            warn!(directory = %directory.display(), error = %error, "读取 server.properties 端口失败");
+            Default::default()
+        }
+    };
+    let parse_port = |key: &str| {
+        raw.get(key)
+            .and_then(|port| port.trim().parse::<u16>().ok())
+    };
+
+    let server_port = parse_port("server-port").unwrap_or(fallback_port);
+    let mut bindings = vec![PortBinding {
+        kind: PortKind::Server,
+        port: server_port,
+    }];
+    if raw.get("enable-query").map(|value| value.trim()) == Some("true") {
+        bindings.push(PortBinding {
+            kind: PortKind::Query,
+            port: parse_port("query.port").unwrap_or(server_port),
+        });
+    }
+    if let Ok(Some(settings)) = read_rcon_settings(directory) {
+        bindings.push(PortBinding {
+            kind: PortKind::Rcon,
+            port: settings.port,
+        });
+    }
+    bindings.extend(listeners);
+    bindings
+}
+
+/// 读取代理端的监听端口；Velocity 开启查询时一并返回其查询端口。
+fn read_proxy_listeners(directory: &Path) -> Vec<PortBinding> {
+    let mut bindings = Vec::new();
+
+    if let Ok(content) = fs::read_to_string(directory.join(VELOCITY_CONFIG_FILE)) {
+        match content.parse::<toml_edit::DocumentMut>() {
+            Ok(document) => {
+                if let Some(port) = document
+                    .get("bind")
+                    .and_then(|bind| bind.as_str())
+                    .and_then(address_port)
+                {
+                    bindings.push(PortBinding { kind: PortKind::ProxyListener, port });
+                }
+                let query = document.get("query");
+                if query
+                    .and_then(|query| query.get("enabled"))
+                    .and_then(|enabled| enabled.as_bool())
+                    == Some(true)
+                    && let Some(port) = query
+                        .and_then(|query| query.get("port"))
+                        .and_then(|port| port.as_integer())
+                        .and_then(|port| u16::try_from(port).ok())
+                {
+                    bindings.push(PortBinding { kind: PortKind::Query, port });
+                }
+            }
+            Err(error) => warn!(error = %error, "解析 velocity.toml 失败"),
+        }
+    }
+
+    // config.yml 在插件服中也很常见，不是 BungeeCord 配置或解析失败时直接跳过
+    if let Ok(content) = fs::read_to_string(directory.join(BUNGEE_CONFIG_FILE))
+        && let Ok(document) = serde_yaml::from_str::<serde_yaml::Value>(&content)
+    {
+        let listeners = document
+            .get("listeners")
+            .and_then(|listeners| listeners.as_sequence())
+            .into_iter()
+            .flatten();
+        for listener in listeners {
+            if let Some(port) = listener
+                .get("host")
+                .and_then(|host| host.as_str())
+                .and_then(address_port)
+            {
+                bindings.push(PortBinding { kind: PortKind::ProxyListener, port });
+            }
+        }
+    }
+
+    bindings
+}
+
+/// 从 `host:port` 形式的监听地址中取出端口。
+fn address_port(address: &str) -> Option<u16> {
+    let (_, port) = address.trim().rsplit_once(':')?;
+    port.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+
+    fn binding(kind: PortKind, port: u16) -> PortBinding {
+        PortBinding { kind, port }
+    }
+
+    #[test]
+    fn reads_properties_and_proxy_listeners() {
+        let directory = tempdir().unwrap();
+        assert_eq!(read_port_bindings(directory.path(), 25565), [binding(PortKind::Server, 25565)]);
+
+        fs::write(
+            directory.path().join("server.properties"),
+            "server-port=25570\nenable-query=true\nenable-rcon=true\nrcon.password=secret\nrcon.port=25580\n",
+        )
+        .unwrap();
+        fs::write(
+            directory.path().join("config.yml"),
+            "listeners:\n- host: 0.0.0.0:25590\n  motd: test\n",
+        )
+        .unwrap();
+        assert_eq!(
+            read_port_bindings(directory.path(), 25565),
+            [
+                binding(PortKind::Server, 25570),
+                binding(PortKind::Query, 25570),
+                binding(PortKind::Rcon, 25580),
+                binding(PortKind::ProxyListener, 25590),
+            ]
+        );
+    }
+
+    #[test]
+    fn proxy_only_directories_skip_the_fallback_port() {
+        let directory = tempdir().unwrap();
+        fs::write(
+            directory.path().join("velocity.toml"),
+            "bind = \"[::]:25577\"\n\n[query]\nenabled = true\nport = 25578\n",
+        )
+        .unwrap();
+        assert_eq!(
+            read_port_bindings(directory.path(), 25565),
+            [binding(PortKind::ProxyListener, 25577), binding(PortKind::Query, 25578)]
+        );
+    }
+}
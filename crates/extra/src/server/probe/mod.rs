@@ -0,0 +1,136 @@
+//! 服务器状态探测。
+//!
+//! 不依赖控制台输出，直接以客户端身份询问服务器的公开状态：`slp` 实现
+//! Server List Ping（1.7+ 握手协议；旧服务端不认识新握手而断开连接或回复无法解析时，
+//! 回退到 1.4–1.6 的 `0xFE 0x01` 旧协议），`query` 实现 `enable-query=true` 时开放的 GameSpy4 UDP 查询。
+//! 两者都只做一次性短连接，超时由调用方给定。
+
+mod query;
+mod slp;
+
+use std::future::Future;
+use std::time::Duration;
+
+use serde::Serialize;
+use tracing::debug;
+
+/// Server List Ping 返回的服务器状态。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StatusResponse {
+    /// 去除格式代码后的 MOTD 纯文本。
+    pub motd: String,
+    /// 服务端报告的版本名（如 `Paper 1.20.4`）。
+    pub version_name: String,
+    /// 服务端协议号；旧协议的 Beta 格式不报告时为 `-1`。
+    pub protocol: i32,
+    /// 在线人数。
+    pub online_players: u32,
+    /// 最大人数。
+    pub max_players: u32,
+    /// 服务端给出的在线玩家样本（通常最多 12 人，旧协议为空）。
+    pub sample: Vec<SamplePlayer>,
+    /// 往返延迟（毫秒），优先取 ping/pong 包的往返时间。
+    pub latency_ms: u64,
+    /// 是否经由 1.6 及以前的旧协议获得。
+    pub legacy: bool,
+}
+
+/// 状态响应中的玩家样本。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SamplePlayer {
+    pub name: String,
+    pub id: String,
+}
+
+/// GameSpy4 完整查询（full stat）返回的服务器信息。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct QueryResponse {
+    pub motd: String,
+    pub game_type: String,
+    pub version: String,
+    /// 插件列表原文（`Paper on 1.20.4: Foo 1.0; Bar 2.1`），原版为空。
+    pub plugins: String,
+    pub map: String,
+    pub online_players: u32,
+    pub max_players: u32,
+    pub host_port: u16,
+    /// 完整在线玩家名单。
+    pub players: Vec<String>,
+}
+
+/// 状态探测失败。
+#[derive(Debug, thiserror::Error)]
+pub enum ProbeError {
+    #[error("IO 错误: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("探测超时")]
+    Timeout,
+
+    #[error("协议错误: {0}")]
+    Protocol(String),
+}
+
+impl ProbeError {
+    /// 现代握手失败后是否值得再用旧协议尝试一次。
+    ///
+    /// 1.6 及以前的服务端收到未知首包会直接断开，表现为连接被重置或提前 EOF；
+    /// 连接被拒绝与超时说明端口本身不可用，回退没有意义。
+    fn allows_legacy_fallback(&self) -> bool {
+        match self {
+            Self::Protocol(_) => true,
+            Self::Io(error) => matches!(
+                error.kind(),
+                std::io::ErrorKind::UnexpectedEof
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::BrokenPipe
+            ),
+            Self::Timeout => false,
+        }
+    }
+}
+
+/// 以 Server List Ping 探测服务器状态，必要时回退到旧协议。
+///
+/// 两次尝试各自独立计时；旧协议也失败时返回现代握手的错误。
+pub async fn ping(host: &str, port: u16, timeout: Duration) -> Result<StatusResponse, ProbeError> {
+    match with_timeout(timeout, slp::ping_modern(host, port)).await {
+        Ok(status) => Ok(status),
+        Err(error) if error.allows_legacy_fallback() => {
+            debug!("现代状态握手失败，回退旧协议: {}", error);
+            with_timeout(timeout, slp::ping_legacy(host, port))
+                .await
+                .map_err(|_| error)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// 以 GameSpy4 UDP 查询读取完整服务器信息（需服务端开启 `enable-query`）。
+pub async fn query(host: &str, port: u16, timeout: Duration) -> Result<QueryResponse, ProbeError> {
+    with_timeout(timeout, query::full_stat(host, port)).await
+}
+
+async fn with_timeout<T>(
+    timeout: Duration,
+    future: impl Future<Output = Result<T, ProbeError>>,
+) -> Result<T, ProbeError> {
+    tokio::time::timeout(timeout, future)
+        .await
+        .map_err(|_| ProbeError::Timeout)?
+}
+
+/// 去掉 `§` 格式代码（颜色、粗体等），保留可读文本。
+fn strip_formatting(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut characters = text.chars();
+    while let Some(character) = characters.next() {
+        if character == '§' {
+            characters.next();
+            continue;
+        }
+        output.push(character);
+    }
+    output
+}
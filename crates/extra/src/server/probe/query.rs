@@ -0,0 +1,215 @@
+//! GameSpy4 UDP 查询协议（`enable-query=true` 时由服务端开放）。
+//!
+//! 流程：握手包换取 challenge token → 携带 token 请求 full stat → 解析
+//! 键值段与玩家名单段。会话 ID 只用于匹配响应，按协议要求屏蔽每字节高 4 位。
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tokio::net::{UdpSocket, lookup_host};
+
+use super::{ProbeError, QueryResponse, strip_formatting};
+
+const MAGIC: [u8; 2] = [0xFE, 0xFD];
+const TYPE_HANDSHAKE: u8 = 0x09;
+const TYPE_STAT: u8 = 0x00;
+const SESSION_MASK: i32 = 0x0F0F_0F0F;
+/// full stat 键值段前的固定填充（`splitnum\0\x80\0`）。
+const KEY_VALUE_PADDING: &[u8] = b"splitnum\x00\x80\x00";
+/// 玩家名单段前的固定填充（`\x01player_\0\0`）。
+const PLAYER_PADDING: &[u8] = b"\x01player_\x00\x00";
+
+pub(super) async fn full_stat(host: &str, port: u16) -> Result<QueryResponse, ProbeError> {
+    let target = lookup_host((host, port))
+        .await?
+        .next()
+        .ok_or_else(|| ProbeError::Protocol(format!("无法解析查询地址 {host}")))?;
+    let bind: SocketAddr = if target.is_ipv4() {
+        ([0, 0, 0, 0], 0).into()
+    } else {
+        ([0u16; 8], 0).into()
+    };
+    let socket = UdpSocket::bind(bind).await?;
+    socket.connect(target).await?;
+
+    let session = session_id();
+    let mut buffer = vec![0u8; 65_535];
+
+    socket.send(&request(TYPE_HANDSHAKE, session, &[])).await?;
+    let length = socket.recv(&mut buffer).await?;
+    let token = parse_handshake(&buffer[..length], session)?;
+
+    let mut payload = token.to_be_bytes().to_vec();
+    payload.extend_from_slice(&[0, 0, 0, 0]);
+    socket.send(&request(TYPE_STAT, session, &payload)).await?;
+    let length = socket.recv(&mut buffer).await?;
+    parse_full_stat(&buffer[..length], session)
+}
+
+fn session_id() -> i32 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.subsec_nanos())
+        .unwrap_or(1);
+    nanos as i32 & SESSION_MASK
+}
+
+fn request(kind: u8, session: i32, payload: &[u8]) -> Vec<u8> {
+    let mut packet = Vec::with_capacity(7 + payload.len());
+    packet.extend_from_slice(&MAGIC);
+    packet.push(kind);
+    packet.extend_from_slice(&session.to_be_bytes());
+    packet.extend_from_slice(payload);
+    packet
+}
+
+/// 校验响应头（类型 + 会话 ID）并返回其后的内容。
+fn response_body(data: &[u8], kind: u8, session: i32) -> Result<&[u8], ProbeError> {
+    match data.split_first() {
+        Some((&head, rest)) if head == kind && rest.starts_with(&session.to_be_bytes()) => {
+            Ok(&rest[4..])
+        }
+        _ => Err(ProbeError::Protocol("查询响应头与请求不匹配".to_owned())),
+    }
+}
+
+fn parse_handshake(data: &[u8], session: i32) -> Result<i32, ProbeError> {
+    let body = response_body(data, TYPE_HANDSHAKE, session)?;
+    let (token, _) = read_cstring(body)?;
+    token
+        .trim()
+        .parse::<i64>()
+        .map(|token| token as i32)
+        .map_err(|_| ProbeError::Protocol("challenge token 不是整数".to_owned()))
+}
+
+fn parse_full_stat(data: &[u8], session: i32) -> Result<QueryResponse, ProbeError> {
+    let body = response_body(data, TYPE_STAT, session)?;
+    let mut rest = body
+        .strip_prefix(KEY_VALUE_PADDING)
+        .ok_or_else(|| ProbeError::Protocol("full stat 缺少键值段填充".to_owned()))?;
+
+    let mut values = HashMap::new();
+    loop {
+        let (key, after_key) = read_cstring(rest)?;
+        rest = after_key;
+        if key.is_empty() {
+            break;
+        }
+        let (value, after_value) = read_cstring(rest)?;
+        rest = after_value;
+        values.insert(key, value);
+    }
+
+    let mut players = Vec::new();
+    if let Some(mut section) = rest.strip_prefix(PLAYER_PADDING) {
+        loop {
+            let (name, after_name) = read_cstring(section)?;
+            section = after_name;
+            if name.is_empty() {
+                break;
+            }
+            players.push(name);
+        }
+    }
+
+    let mut take = |key: &str| values.remove(key).unwrap_or_default();
+    let count = |raw: String| raw.trim().parse().unwrap_or(0);
+    Ok(QueryResponse {
+        motd: strip_formatting(&take("hostname")),
+        game_type: take("gametype"),
+        version: take("version"),
+        plugins: take("plugins"),
+        map: take("map"),
+        online_players: count(take("numplayers")),
+        max_players: count(take("maxplayers")),
+        host_port: take("hostport").trim().parse().unwrap_or(0),
+        players,
+    })
+}
+
+/// 读取以 `\0` 结尾的字符串，返回字符串与剩余内容。
+fn read_cstring(data: &[u8]) -> Result<(String, &[u8]), ProbeError> {
+    let end = data
+        .iter()
+        .position(|&byte| byte == 0)
+        .ok_or_else(|| ProbeError::Protocol("查询响应字符串缺少结束符".to_owned()))?;
+    Ok((String::from_utf8_lossy(&data[..end]).into_owned(), &data[end + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::net::UdpSocket;
+
+    use super::*;
+    use crate::server::probe::query;
+
+    #[tokio::test]
+    async fn full_stat_round_trip_against_fake_server() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = server.local_addr().unwrap().port();
+        let task = tokio::spawn(async move {
+            let mut buffer = [0u8; 1024];
+            let (length, peer) = server.recv_from(&mut buffer).await.unwrap();
+            assert_eq!(&buffer[..3], [0xFE, 0xFD, TYPE_HANDSHAKE]);
+            assert_eq!(length, 7);
+            let session = buffer[3..7].to_vec();
+            let mut response = vec![TYPE_HANDSHAKE];
+            response.extend_from_slice(&session);
+            response.extend_from_slice(b"9513307\x00");
+            server.send_to(&response, peer).await.unwrap();
+
+            let (length, peer) = server.recv_from(&mut buffer).await.unwrap();
+            assert_eq!(length, 15);
+            assert_eq!(buffer[2], TYPE_STAT);
+            assert_eq!(&buffer[7..11], 9_513_307i32.to_be_bytes());
+            let mut response = vec![TYPE_STAT];
+            response.extend_from_slice(&session);
+            response.extend_from_slice(KEY_VALUE_PADDING);
+            for (key, value) in [
+                ("hostname", "§bA Minecraft Server"),
+                ("gametype", "SMP"),
+                ("game_id", "MINECRAFT"),
+                ("version", "1.20.4"),
+                ("plugins", "Paper on 1.20.4: WorldEdit 7.2"),
+                ("map", "world"),
+                ("numplayers", "2"),
+                ("maxplayers", "20"),
+                ("hostport", "25565"),
+                ("hostip", "127.0.0.1"),
+            ] {
+                response.extend_from_slice(key.as_bytes());
+                response.push(0);
+                response.extend_from_slice(value.as_bytes());
+                response.push(0);
+            }
+            response.push(0);
+            response.extend_from_slice(PLAYER_PADDING);
+            response.extend_from_slice(b"Steve\x00Alex\x00\x00");
+            server.send_to(&response, peer).await.unwrap();
+        });
+
+        let stats = query("127.0.0.1", port, Duration::from_secs(5))
+            .await
+            .unwrap();
+        task.await.unwrap();
+        assert_eq!(stats.motd, "A Minecraft Server");
+        assert_eq!(stats.game_type, "SMP");
+        assert_eq!(stats.plugins, "Paper on 1.20.4: WorldEdit 7.2");
+        assert_eq!((stats.online_players, stats.max_players), (2, 20));
+        assert_eq!(stats.host_port, 25565);
+        assert_eq!(stats.players, ["Steve", "Alex"]);
+    }
+
+    #[test]
+    fn rejects_response_for_another_session() {
+        let mut response = vec![TYPE_HANDSHAKE];
+        response.extend_from_slice(&1i32.to_be_bytes());
+        response.extend_from_slice(b"1\x00");
+        assert!(parse_handshake(&response, 2).is_err());
+        assert_eq!(parse_handshake(&response, 1).unwrap(), 1);
+    }
+}
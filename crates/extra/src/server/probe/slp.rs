@@ -0,0 +1,376 @@
+//! Server List Ping：1.7+ 握手协议与 1.4–1.6 旧协议。
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use super::{ProbeError, SamplePlayer, StatusResponse, strip_formatting};
+
+/// 状态响应包上限；带图标的响应通常在 100 KiB 以内。
+const MAX_PACKET_LENGTH: i32 = 2 * 1024 * 1024;
+/// 状态探测不关心协议版本，按惯例发送 `-1`。
+const PROBE_PROTOCOL_VERSION: i32 = -1;
+/// 握手后切换到 status 状态。
+const NEXT_STATE_STATUS: i32 = 1;
+
+/// 1.7+ 握手：handshake → status request → status response → ping/pong。
+pub(super) async fn ping_modern(host: &str, port: u16) -> Result<StatusResponse, ProbeError> {
+    let mut stream = TcpStream::connect((host, port)).await?;
+    let started = Instant::now();
+
+    let mut handshake = Vec::new();
+    write_varint(&mut handshake, 0x00);
+    write_varint(&mut handshake, PROBE_PROTOCOL_VERSION);
+    write_string(&mut handshake, host);
+    handshake.extend_from_slice(&port.to_be_bytes());
+    write_varint(&mut handshake, NEXT_STATE_STATUS);
+    write_packet(&mut stream, &handshake).await?;
+    write_packet(&mut stream, &[0x00]).await?;
+
+    let packet = read_packet(&mut stream).await?;
+    let mut body = packet.as_slice();
+    if read_varint_from(&mut body)? != 0x00 {
+        return Err(ProbeError::Protocol("状态响应包 ID 不匹配".to_owned()));
+    }
+    let json = read_string_from(&mut body)?;
+    let status_latency = started.elapsed();
+
+    // 部分代理端在发出状态后直接断开，ping/pong 失败时以状态往返近似延迟。
+    let latency = ping_pong(&mut stream).await.unwrap_or(status_latency);
+    parse_status_json(&json, latency)
+}
+
+/// 1.4–1.6 旧协议：发送 `0xFE 0x01`，服务端以 `0xFF` 踢出包携带状态。
+pub(super) async fn ping_legacy(host: &str, port: u16) -> Result<StatusResponse, ProbeError> {
+    let mut stream = TcpStream::connect((host, port)).await?;
+    let started = Instant::now();
+    stream.write_all(&[0xFE, 0x01]).await?;
+
+    if stream.read_u8().await? != 0xFF {
+        return Err(ProbeError::Protocol("旧协议响应不是踢出包".to_owned()));
+    }
+    let length = usize::from(stream.read_u16().await?);
+    let mut raw = vec![0u8; length * 2];
+    stream.read_exact(&mut raw).await?;
+    let latency = started.elapsed();
+
+    let units: Vec<u16> = raw
+        .chunks_exact(2)
+        .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+        .collect();
+    let text = String::from_utf16(&units)
+        .map_err(|_| ProbeError::Protocol("旧协议响应不是合法 UTF-16".to_owned()))?;
+    parse_legacy_response(&text, latency)
+}
+
+async fn ping_pong(stream: &mut TcpStream) -> Result<Duration, ProbeError> {
+    let payload = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as i64)
+        .unwrap_or(0);
+    let mut ping = Vec::with_capacity(9);
+    write_varint(&mut ping, 0x01);
+    ping.extend_from_slice(&payload.to_be_bytes());
+
+    let started = Instant::now();
+    write_packet(stream, &ping).await?;
+    let packet = read_packet(stream).await?;
+    let elapsed = started.elapsed();
+
+    let mut body = packet.as_slice();
+    if read_varint_from(&mut body)? != 0x01 || body != payload.to_be_bytes() {
+        return Err(ProbeError::Protocol("pong 包与 ping 不匹配".to_owned()));
+    }
+    Ok(elapsed)
+}
+
+fn parse_status_json(json: &str, latency: Duration) -> Result<StatusResponse, ProbeError> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|error| ProbeError::Protocol(format!("状态 JSON 无法解析: {error}")))?;
+
+    let mut motd = String::new();
+    if let Some(description) = value.get("description") {
+        flatten_text(description, &mut motd);
+    }
+    let version = value.get("version");
+    let players = value.get("players");
+    let count = |key: &str| {
+        players
+            .and_then(|players| players.get(key))
+            .and_then(Value::as_u64)
+            .map_or(0, |count| u32::try_from(count).unwrap_or(u32::MAX))
+    };
+    let sample = players
+        .and_then(|players| players.get("sample"))
+        .and_then(Value::as_array)
+        .map(|sample| {
+            sample
+                .iter()
+                .filter_map(|player| {
+                    Some(SamplePlayer {
+                        name: player.get("name")?.as_str()?.to_owned(),
+                        id: player.get("id")?.as_str()?.to_owned(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(StatusResponse {
+        motd: strip_formatting(&motd),
+        version_name: version
+            .and_then(|version| version.get("name"))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_owned(),
+        protocol: version
+            .and_then(|version| version.get("protocol"))
+            .and_then(Value::as_i64)
+            .and_then(|protocol| i32::try_from(protocol).ok())
+            .unwrap_or(-1),
+        online_players: count("online"),
+        max_players: count("max"),
+        sample,
+        latency_ms: latency.as_millis() as u64,
+        legacy: false,
+    })
+}
+
+/// 拼接聊天组件中的全部文本（`text` 与递归的 `extra`）。
+fn flatten_text(value: &Value, output: &mut String) {
+    match value {
+        Value::String(text) => output.push_str(text),
+        Value::Array(items) => items.iter().for_each(|item| flatten_text(item, output)),
+        Value::Object(component) => {
+            if let Some(text) = component.get("text") {
+                flatten_text(text, output);
+            }
+            if let Some(extra) = component.get("extra") {
+                flatten_text(extra, output);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// 解析旧协议踢出包中的状态文本。
+///
+/// 1.4+ 格式：`§1\0协议\0版本\0MOTD\0在线\0最大`；
+/// Beta 1.8–1.3 格式：`MOTD§在线§最大`（不含版本信息）。
+fn parse_legacy_response(text: &str, latency: Duration) -> Result<StatusResponse, ProbeError> {
+    let malformed = || ProbeError::Protocol("旧协议状态字段不完整".to_owned());
+    let parse_count = |raw: &str| raw.trim().parse::<u32>().map_err(|_| malformed());
+
+    let (protocol, version_name, motd, online, max) = if let Some(rest) = text.strip_prefix("§1\0")
+    {
+        let fields: Vec<&str> = rest.split('\0').collect();
+        let [protocol, version, motd, online, max] = fields[..] else {
+            return Err(malformed());
+        };
+        let protocol = protocol.trim().parse().map_err(|_| malformed())?;
+        (protocol, version.to_owned(), motd, online, max)
+    } else {
+        let mut fields = text.rsplitn(3, '§');
+        let max = fields.next().ok_or_else(malformed)?;
+        let online = fields.next().ok_or_else(malformed)?;
+        let motd = fields.next().ok_or_else(malformed)?;
+        (-1, String::new(), motd, online, max)
+    };
+
+    Ok(StatusResponse {
+        motd: strip_formatting(motd),
+        version_name,
+        protocol,
+        online_players: parse_count(online)?,
+        max_players: parse_count(max)?,
+        sample: Vec::new(),
+        latency_ms: latency.as_millis() as u64,
+        legacy: true,
+    })
+}
+
+async fn write_packet(stream: &mut TcpStream, body: &[u8]) -> Result<(), ProbeError> {
+    let mut packet = Vec::with_capacity(body.len() + 5);
+    write_varint(&mut packet, body.len() as i32);
+    packet.extend_from_slice(body);
+    stream.write_all(&packet).await?;
+    Ok(())
+}
+
+async fn read_packet<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Vec<u8>, ProbeError> {
+    let length = read_varint(reader).await?;
+    if !(1..=MAX_PACKET_LENGTH).contains(&length) {
+        return Err(ProbeError::Protocol(format!("包长度 {length} 超出范围")));
+    }
+    let mut body = vec![0u8; length as usize];
+    reader.read_exact(&mut body).await?;
+    Ok(body)
+}
+
+fn write_varint(buffer: &mut Vec<u8>, value: i32) {
+    let mut value = value as u32;
+    loop {
+        if value & !0x7F == 0 {
+            buffer.push(value as u8);
+            return;
+        }
+        buffer.push(((value & 0x7F) as u8) | 0x80);
+        value >>= 7;
+    }
+}
+
+fn write_string(buffer: &mut Vec<u8>, value: &str) {
+    write_varint(buffer, value.len() as i32);
+    buffer.extend_from_slice(value.as_bytes());
+}
+
+async fn read_varint<R: AsyncRead + Unpin>(reader: &mut R) -> Result<i32, ProbeError> {
+    let mut value = 0u32;
+    for position in 0..5 {
+        let byte = reader.read_u8().await?;
+        value |= u32::from(byte & 0x7F) << (7 * position);
+        if byte & 0x80 == 0 {
+            return Ok(value as i32);
+        }
+    }
+    Err(ProbeError::Protocol("VarInt 超过 5 字节".to_owned()))
+}
+
+fn read_varint_from(data: &mut &[u8]) -> Result<i32, ProbeError> {
+    let mut value = 0u32;
+    for position in 0..5 {
+        let Some((&byte, rest)) = data.split_first() else {
+            return Err(ProbeError::Protocol("VarInt 被截断".to_owned()));
+        };
+        *data = rest;
+        value |= u32::from(byte & 0x7F) << (7 * position);
+        if byte & 0x80 == 0 {
+            return Ok(value as i32);
+        }
+    }
+    Err(ProbeError::Protocol("VarInt 超过 5 字节".to_owned()))
+}
+
+fn read_string_from(data: &mut &[u8]) -> Result<String, ProbeError> {
+    let length = usize::try_from(read_varint_from(data)?)
+        .map_err(|_| ProbeError::Protocol("字符串长度为负".to_owned()))?;
+    if data.len() < length {
+        return Err(ProbeError::Protocol("字符串被截断".to_owned()));
+    }
+    let (raw, rest) = data.split_at(length);
+    *data = rest;
+    String::from_utf8(raw.to_vec())
+        .map_err(|_| ProbeError::Protocol("字符串不是合法 UTF-8".to_owned()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::server::probe::ping;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    #[test]
+    fn varint_round_trips_edge_values() {
+        for value in [0, 1, 127, 128, 25565, i32::MAX, -1, i32::MIN] {
+            let mut buffer = Vec::new();
+            write_varint(&mut buffer, value);
+            let mut data = buffer.as_slice();
+            assert_eq!(read_varint_from(&mut data).unwrap(), value);
+            assert!(data.is_empty());
+        }
+        let mut buffer = Vec::new();
+        write_varint(&mut buffer, -1);
+        assert_eq!(buffer, [0xFF, 0xFF, 0xFF, 0xFF, 0x0F]);
+    }
+
+    #[tokio::test]
+    async fn modern_ping_reads_status_and_pong() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let handshake = read_packet(&mut socket).await.unwrap();
+            let mut body = handshake.as_slice();
+            assert_eq!(read_varint_from(&mut body).unwrap(), 0x00);
+            assert_eq!(read_varint_from(&mut body).unwrap(), -1);
+            assert_eq!(read_string_from(&mut body).unwrap(), "127.0.0.1");
+            assert_eq!(&body[..2], port.to_be_bytes());
+            assert_eq!(read_packet(&mut socket).await.unwrap(), [0x00]);
+
+            let json = r#"{
+                "version": {"name": "Paper 1.20.4", "protocol": 765},
+                "players": {"max": 20, "online": 2, "sample": [
+                    {"name": "Steve", "id": "069a79f4-44e9-4726-a5be-fca90e38aaf5"}
+                ]},
+                "description": {"text": "§aHello ", "extra": [{"text": "World"}]}
+            }"#;
+            let mut response = Vec::new();
+            write_varint(&mut response, 0x00);
+            write_string(&mut response, json);
+            write_packet(&mut socket, &response).await.unwrap();
+
+            let ping = read_packet(&mut socket).await.unwrap();
+            write_packet(&mut socket, &ping).await.unwrap();
+        });
+
+        let status = ping("127.0.0.1", port, TIMEOUT).await.unwrap();
+        server.await.unwrap();
+        assert_eq!(status.motd, "Hello World");
+        assert_eq!(status.version_name, "Paper 1.20.4");
+        assert_eq!(status.protocol, 765);
+        assert_eq!((status.online_players, status.max_players), (2, 20));
+        assert_eq!(status.sample.len(), 1);
+        assert_eq!(status.sample[0].name, "Steve");
+        assert!(!status.legacy);
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_legacy_ping_when_handshake_is_rejected() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            // 旧服务端不认识握手包，读到首包后直接断开。
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut first = [0u8; 1];
+            socket.read_exact(&mut first).await.unwrap();
+            drop(socket);
+
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 2];
+            socket.read_exact(&mut request).await.unwrap();
+            assert_eq!(request, [0xFE, 0x01]);
+            let text = "§1\u{0}78\u{0}1.6.4\u{0}§eOld Server\u{0}3\u{0}10";
+            let units: Vec<u16> = text.encode_utf16().collect();
+            let mut response = vec![0xFF];
+            response.extend_from_slice(&(units.len() as u16).to_be_bytes());
+            for unit in units {
+                response.extend_from_slice(&unit.to_be_bytes());
+            }
+            socket.write_all(&response).await.unwrap();
+        });
+
+        let status = ping("127.0.0.1", port, TIMEOUT).await.unwrap();
+        server.await.unwrap();
+        assert!(status.legacy);
+        assert_eq!(status.motd, "Old Server");
+        assert_eq!(status.version_name, "1.6.4");
+        assert_eq!(status.protocol, 78);
+        assert_eq!((status.online_players, status.max_players), (3, 10));
+    }
+
+    #[test]
+    fn parses_beta_legacy_format() {
+        let status = parse_legacy_response("A server§1§8", Duration::ZERO).unwrap();
+        assert_eq!(status.motd, "A server");
+        assert_eq!((status.online_players, status.max_players), (1, 8));
+        assert!(parse_legacy_response("garbage", Duration::ZERO).is_err());
+    }
+}
@@ -0,0 +1,235 @@
+//! Source RCON 协议客户端。
+//!
+//! 包格式（小端）：`长度 i32 | 请求 ID i32 | 类型 i32 | 正文 \0 | \0`。
+//! 服务端对超过 4096 字节的响应会拆成多个包且不标记结束，这里在命令后紧跟
+//! 一个未知类型的哨兵包：服务端按序处理，哨兵的回包到达即说明命令响应已收齐。
+
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+use super::RconError;
+
+/// 服务端接受的单条命令上限（字节）。
+pub const MAX_COMMAND_LENGTH: usize = 1446;
+
+const TYPE_RESPONSE: i32 = 0;
+/// 执行命令；也是认证回包的类型。
+const TYPE_EXEC_COMMAND: i32 = 2;
+const TYPE_AUTH: i32 = 3;
+/// 认证失败时服务端回包的请求 ID。
+const AUTH_FAILED_ID: i32 = -1;
+/// 请求 ID + 类型 + 两个结束符。
+const PACKET_OVERHEAD: i32 = 10;
+const MAX_PACKET_LENGTH: i32 = 64 * 1024;
+
+/// 已认证的 RCON 连接。
+///
+/// 每次请求分配递增的请求 ID，单个连接上的命令串行执行。
+pub struct RconClient {
+    stream: TcpStream,
+    next_id: i32,
+    timeout: Duration,
+}
+
+impl RconClient {
+    /// 连接并认证；`timeout` 同时作为之后每条命令的超时。
+    pub async fn connect(
+        host: &str,
+        port: u16,
+        password: &str,
+        timeout: Duration,
+    ) -> Result<Self, RconError> {
+        let stream = within(timeout, async { Ok(TcpStream::connect((host, port)).await?) }).await?;
+        let mut client = Self { stream, next_id: 0, timeout };
+        client.authenticate(password).await?;
+        Ok(client)
+    }
+
+    /// 执行一条命令并返回完整响应文本（可能为空）。
+    pub async fn execute(&mut self, command: &str) -> Result<String, RconError> {
+        if command.len() > MAX_COMMAND_LENGTH {
+            return Err(RconError::CommandTooLong);
+        }
+        let id = self.allocate_id();
+        let sentinel = self.allocate_id();
+        let stream = &mut self.stream;
+        within(self.timeout, async move {
+            write_packet(stream, id, TYPE_EXEC_COMMAND, command).await?;
+            write_packet(stream, sentinel, TYPE_RESPONSE, "").await?;
+
+            let mut output = String::new();
+            loop {
+                let packet = read_packet(stream).await?;
+                if packet.id == id {
+                    output.push_str(&packet.body);
+                } else if packet.id == sentinel {
+                    return Ok(output);
+                } else if packet.id == AUTH_FAILED_ID {
+                    return Err(RconError::AuthenticationFailed);
+                }
+            }
+        })
+        .await
+    }
+
+    async fn authenticate(&mut self, password: &str) -> Result<(), RconError> {
+        let id = self.allocate_id();
+        let stream = &mut self.stream;
+        within(self.timeout, async move {
+            write_packet(stream, id, TYPE_AUTH, password).await?;
+            loop {
+                let packet = read_packet(stream).await?;
+                // 部分实现会在认证回包前先发一个空的响应包。
+                if packet.kind != TYPE_EXEC_COMMAND {
+                    continue;
+                }
+                return match packet.id {
+                    AUTH_FAILED_ID => Err(RconError::AuthenticationFailed),
+                    packet_id if packet_id == id => Ok(()),
+                    packet_id => {
+                        Err(RconError::Protocol(format!("认证回包 ID {packet_id} 不匹配")))
+                    }
+                };
+            }
+        })
+        .await
+    }
+
+    fn allocate_id(&mut self) -> i32 {
+        self.next_id = self.next_id.checked_add(1).unwrap_or(1);
+        self.next_id
+    }
+}
+
+struct Packet {
+    id: i32,
+    kind: i32,
+    body: String,
+}
+
+async fn within<T>(
+    timeout: Duration,
+    future: impl Future<Output = Result<T, RconError>>,
+) -> Result<T, RconError> {
+    tokio::time::timeout(timeout, future)
+        .await
+        .map_err(|_| RconError::Timeout)?
+}
+
+async fn write_packet(
+    stream: &mut TcpStream,
+    id: i32,
+    kind: i32,
+    body: &str,
+) -> Result<(), RconError> {
+    let length = body.len() as i32 + PACKET_OVERHEAD;
+    let mut packet = Vec::with_capacity(length as usize + 4);
+    packet.extend_from_slice(&length.to_le_bytes());
+    packet.extend_from_slice(&id.to_le_bytes());
+    packet.extend_from_slice(&kind.to_le_bytes());
+    packet.extend_from_slice(body.as_bytes());
+    packet.extend_from_slice(&[0, 0]);
+    stream.write_all(&packet).await?;
+    Ok(())
+}
+
+async fn read_packet(stream: &mut TcpStream) -> Result<Packet, RconError> {
+    let length = stream.read_i32_le().await?;
+    if !(PACKET_OVERHEAD..=MAX_PACKET_LENGTH).contains(&length) {
+        return Err(RconError::Protocol(format!("包长度 {length} 超出范围")));
+    }
+    let id = stream.read_i32_le().await?;
+    let kind = stream.read_i32_le().await?;
+    let mut body = vec![0u8; (length - 8) as usize];
+    stream.read_exact(&mut body).await?;
+    // 去掉正文结束符与包尾填充。
+    while body.last() == Some(&0) {
+        body.pop();
+    }
+    Ok(Packet {
+        id,
+        kind,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// 模拟原版服务端：认证后回显命令，`big` 命令分两个包返回，未知类型回 `Unknown request`。
+    async fn spawn_mock_server(password: &'static str) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            while let Ok(packet) = read_packet(&mut socket).await {
+                match packet.kind {
+                    TYPE_AUTH => {
+                        let id = if packet.body == password {
+                            packet.id
+                        } else {
+                            AUTH_FAILED_ID
+                        };
+                        write_packet(&mut socket, id, TYPE_EXEC_COMMAND, "")
+                            .await
+                            .unwrap();
+                    }
+                    TYPE_EXEC_COMMAND if packet.body == "big" => {
+                        write_packet(&mut socket, packet.id, TYPE_RESPONSE, "first half, ")
+                            .await
+                            .unwrap();
+                        write_packet(&mut socket, packet.id, TYPE_RESPONSE, "second half")
+                            .await
+                            .unwrap();
+                    }
+                    TYPE_EXEC_COMMAND => {
+                        let reply = format!("ran {}", packet.body);
+                        write_packet(&mut socket, packet.id, TYPE_RESPONSE, &reply)
+                            .await
+                            .unwrap();
+                    }
+                    other => {
+                        let reply = format!("Unknown request {other:x}");
+                        write_packet(&mut socket, packet.id, TYPE_RESPONSE, &reply)
+                            .await
+                            .unwrap();
+                    }
+                }
+            }
+        });
+        port
+    }
+
+    #[tokio::test]
+    async fn executes_commands_and_joins_fragmented_responses() {
+        let port = spawn_mock_server("secret").await;
+        let mut client = RconClient::connect("127.0.0.1", port, "secret", TIMEOUT)
+            .await
+            .unwrap();
+
+        assert_eq!(client.execute("list").await.unwrap(), "ran list");
+        assert_eq!(client.execute("big").await.unwrap(), "first half, second half");
+        assert_eq!(client.execute("say 你好").await.unwrap(), "ran say 你好");
+        assert!(matches!(
+            client.execute(&"x".repeat(MAX_COMMAND_LENGTH + 1)).await,
+            Err(RconError::CommandTooLong)
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_wrong_password() {
+        let port = spawn_mock_server("secret").await;
+        assert!(matches!(
+            RconClient::connect("127.0.0.1", port, "wrong", TIMEOUT).await,
+            Err(RconError::AuthenticationFailed)
+        ));
+    }
+}
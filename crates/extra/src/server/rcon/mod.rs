@@ -0,0 +1,96 @@
+//! RCON 远程控制台。
+//!
+//! 与经 stdin 写入控制台不同，RCON 走独立 TCP 连接，既适用于非 Sea Lantern
+//! 拉起的服务器，也能以请求 ID 关联到命令的响应文本。`client` 实现 Source
+//! RCON 协议，本模块负责从 server.properties 读取连接配置。
+
+mod client;
+
+use std::path::Path;
+
+pub use client::{MAX_COMMAND_LENGTH, RconClient};
+
+use crate::config::{ServerPropertiesError, ServerPropertiesManager};
+
+/// 服务端默认的 RCON 端口。
+pub const DEFAULT_RCON_PORT: u16 = 25575;
+
+/// server.properties 中的 RCON 连接配置。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RconSettings {
+    pub port: u16,
+    pub password: String,
+}
+
+/// 读取实例目录下的 RCON 配置。
+///
+/// `enable-rcon` 不为 `true` 或密码为空（服务端此时拒绝启动 RCON）时返回 `None`。
+pub fn read_rcon_settings(
+    server_path: impl AsRef<Path>,
+) -> Result<Option<RconSettings>, ServerPropertiesError> {
+    let raw = ServerPropertiesManager::new(server_path).read()?.raw;
+    if raw.get("enable-rcon").map(|value| value.trim()) != Some("true") {
+        return Ok(None);
+    }
+    let password = raw
+        .get("rcon.password")
+        .map(|value| value.trim())
+        .unwrap_or_default();
+    if password.is_empty() {
+        return Ok(None);
+    }
+    let port = raw
+        .get("rcon.port")
+        .and_then(|port| port.trim().parse().ok())
+        .unwrap_or(DEFAULT_RCON_PORT);
+    Ok(Some(RconSettings { port, password: password.to_owned() }))
+}
+
+/// RCON 通信失败。
+#[derive(Debug, thiserror::Error)]
+pub enum RconError {
+    #[error("IO 错误: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("RCON 请求超时")]
+    Timeout,
+
+    #[error("RCON 密码错误")]
+    AuthenticationFailed,
+
+    #[error("命令超过 {MAX_COMMAND_LENGTH} 字节上限")]
+    CommandTooLong,
+
+    #[error("协议错误: {0}")]
+    Protocol(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_settings_only_when_enabled_with_password() {
+        let temp = tempfile::tempdir().unwrap();
+        let properties = temp.path().join("server.properties");
+
+        std::fs::write(&properties, "enable-rcon=false\nrcon.password=secret\n").unwrap();
+        assert_eq!(read_rcon_settings(temp.path()).unwrap(), None);
+
+        std::fs::write(&properties, "enable-rcon=true\nrcon.password=\n").unwrap();
+        assert_eq!(read_rcon_settings(temp.path()).unwrap(), None);
+
+        std::fs::write(&properties, "enable-rcon=true\nrcon.password=secret\n").unwrap();
+        assert_eq!(
+            read_rcon_settings(temp.path()).unwrap(),
+            Some(RconSettings {
+                port: DEFAULT_RCON_PORT,
+                password: "secret".to_owned()
+            })
+        );
+
+        std::fs::write(&properties, "enable-rcon=true\nrcon.port=25580\nrcon.password=secret\n")
+            .unwrap();
+        assert_eq!(read_rcon_settings(temp.path()).unwrap().unwrap().port, 25580);
+    }
+}
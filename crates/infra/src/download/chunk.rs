@@ -1,104 +1,218 @@
 //! 单块下载。
 //!
 //! 发送 `Range` 请求并将响应流式写入指定文件位置。
-//! 通过 `tokio::select!` 支持取消信号。
+//! 通过 `tokio::select!` 支持取消信号；请求失败、响应截断或镜像停滞时
+//! 从已写入的位置起轮换到下一个候选地址重试。
+//! 已知远端校验头时请求携带 `If-Range`，远端文件变化后不会把新旧内容拼在一起。
+//! 每次请求占用一个全局连接名额，写入的数据先经过任务与全局两级令牌桶限速。
 
 use std::sync::Arc;
+use std::time::Instant;
 
 use reqwest::StatusCode;
 use tokio::fs::OpenOptions;
 use tokio::io::{AsyncSeekExt, AsyncWriteExt, BufWriter, SeekFrom};
+use tokio_util::sync::CancellationToken;
 
+use crate::download::resume::{ChunkProgress, RemoteValidator};
 use crate::download::status::{DownloadError, DownloadStatus};
-use crate::net::client::NetClient;
+use crate::download::tasks::DownloadJob;
 use crate::observability;
 
+/// 累计多少字节后落盘并上报一次进度。
+const FLUSH_THRESHOLD: u64 = 512 * 1024;
+
 /// 下载单个块。
 ///
-/// 向服务器请求 `bytes=start-end` 范围内的数据，
-/// 定位到文件对应位置并写入流式数据。
+/// 从块的已落盘位置起向服务器请求 `bytes=offset-end` 范围内的数据，
+/// 定位到文件对应位置并写入流式数据；每次落盘后推进块进度。
 ///
 /// # Parameters
 ///
-/// - `client`: 配置好的 HTTP 客户端
-/// - `url`: 下载地址
-/// - `path`: 本地保存路径
-/// - `start`: 块起始位置
-/// - `end`: 块结束位置
-/// - `status`: 共享下载状态（用于进度报告和取消检测）
+/// - `job`: 下载任务上下文（候选地址、临时文件、块进度、镜像统计与故障转移策略）
+/// - `index`: 块序号
+/// - `status`: 共享下载状态（用于进度报告和任务限速）
+/// - `stop`: 本次运行的停止令牌（取消或暂停时触发）
 ///
 /// # Returns
 ///
-/// 成功时返回 `Ok(())`，失败时返回 `DownloadError`。
+/// 成功时返回 `Ok(())`；全部尝试失败时返回最后一次的 `DownloadError`。
+///
+/// # Failover Behavior
+///
+/// 第 `n` 次尝试使用第 `n % 候选数` 个地址（候选地址已按镜像健康度排序），
+/// 最多尝试 [`FailoverPolicy::max_attempts`](super::mirror::FailoverPolicy) 次。
+/// 已落盘的字节不会重新下载：重试从上次落盘的位置继续请求剩余范围。
+/// 远端文件已变化（[`DownloadError::RemoteChanged`]）时不再重试，
+/// 由任务丢弃全部块进度后从头下载。
 ///
 /// # Cancellation Behavior
 ///
-/// 使用 `tokio::select!` 同时等待下载和取消信号：
-/// - 下载过程中收到取消信号 → select! 触发取消分支，立即返回 `Cancelled`
-/// - async 块执行完毕（含错误路径）后检测到取消 → 返回 `Cancelled`
+/// 使用 `tokio::select!` 同时等待下载（包括等待连接名额）和停止信号：
+/// - 下载过程中收到停止信号 → select! 触发停止分支，立即返回 `Cancelled`
+/// - 尝试失败后检测到停止 → 返回 `Cancelled`，不再重试
 pub(super) async fn download_chunk(
-    client: &NetClient,
-    url: &str,
-    path: &str,
-    start: u64,
-    end: u64,
+    job: &DownloadJob,
+    index: usize,
     status: Arc<DownloadStatus>,
+    stop: CancellationToken,
 ) -> Result<(), DownloadError> {
-    observability::chunk_started(url, start, end);
-
-    tokio::select! {
-        result = async {
-            let range = format!("bytes={}-{}", start, end);
-            let mut response = client.get_reqwest_client().get(url).header("Range", range).send().await?;
-
-            validate_chunk_response(&response, start)?;
-
-            let file = OpenOptions::new().write(true).open(path).await?;
-            let mut writer = BufWriter::with_capacity(128 * 1024, file);
-            writer.seek(SeekFrom::Start(start)).await?;
-
-            let mut local_downloaded = 0u64;
-            while let Some(chunk) = response.chunk().await? {
-                if status.cancelled() {
-                    return Err(DownloadError::Cancelled("任务已取消".to_string()));
-                }
-
-                let len = chunk.len() as u64;
-                writer.write_all(&chunk).await?;
-
-                local_downloaded += len;
-                if local_downloaded > 512 * 1024 {
-                    status
-                        .downloaded
-                        .fetch_add(local_downloaded, std::sync::atomic::Ordering::Relaxed);
-                    local_downloaded = 0;
-                }
+    let ChunkProgress { start, end, mut offset } = job.chunk(index);
+    let mut last_error = None;
+
+    for attempt in 0..job.policy.max_attempts.max(1) {
+        let url = &job.candidates[attempt % job.candidates.len()];
+        if let Some(error) = &last_error {
+            let previous = &job.candidates[(attempt - 1) % job.candidates.len()];
+            observability::mirror_failover(previous, url, error);
+        }
+        let _connection = tokio::select! {
+            permit = job.scheduler.connection() => permit,
+            _ = stop.cancelled() => {
+                return Err(DownloadError::Cancelled("任务已取消".to_string()));
             }
+        };
+        observability::chunk_started(url, offset, end);
+
+        let started = Instant::now();
+        let resumed_from = offset;
+        let result = tokio::select! {
+            result = fetch_range(job, index, url, &mut offset, end, &status) => result,
+            _ = stop.cancelled() => {
+                return Err(DownloadError::Cancelled("任务已取消".to_string()));
+            }
+        };
+
+        match result {
+            Ok(()) => {
+                job.health
+                    .record_success(url, offset - resumed_from, started.elapsed());
+                observability::chunk_completed(url, start, end);
+                return Ok(());
+            }
+            Err(_) if stop.is_cancelled() => {
+                return Err(DownloadError::Cancelled("任务已取消".to_string()));
+            }
+            Err(err @ DownloadError::RemoteChanged(_)) => {
+                observability::chunk_failed(url, offset, end, &err);
+                return Err(err);
+            }
+            Err(err) => {
+                observability::chunk_failed(url, offset, end, &err);
+                job.health.record_failure(url);
+                last_error = Some(err);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| DownloadError::Message("没有可用的下载地址".to_string())))
+}
+
+/// 从一个地址下载 `offset..=end` 范围，每次落盘后推进 `offset`。
+///
+/// 停止信号由调用方的 `select!` 处理；限速等待的时间不计入吞吐量窗口，
+/// 限速不会被误判为镜像停滞。
+async fn fetch_range(
+    job: &DownloadJob,
+    index: usize,
+    url: &str,
+    offset: &mut u64,
+    end: u64,
+    status: &DownloadStatus,
+) -> Result<(), DownloadError> {
+    let policy = &job.policy;
+    let range = format!("bytes={}-{}", *offset, end);
+    let mut request = job
+        .client
+        .get_reqwest_client()
+        .get(url)
+        .header("Range", range);
+    // 校验头只对提供它的镜像有效。
+    let if_range = job
+        .validator
+        .as_ref()
+        .filter(|validator| validator.applies_to(url))
+        .and_then(RemoteValidator::if_range);
+    if let Some(value) = if_range {
+        request = request.header("If-Range", value);
+    }
+    let mut response = tokio::time::timeout(policy.stall_timeout, request.send())
+        .await
+        .map_err(|_| DownloadError::Stalled(url.to_string()))??;
+
+    validate_chunk_response(&response, *offset, if_range.is_some())?;
+
+    let file = OpenOptions::new().write(true).open(&job.partial).await?;
+    let mut writer = BufWriter::with_capacity(128 * 1024, file);
+    writer.seek(SeekFrom::Start(*offset)).await?;
 
+    // 只有一个候选地址时慢也只能等，不按吞吐量判定停滞。
+    let check_throughput = job.candidates.len() > 1;
+    let mut pending = 0u64;
+    let mut window_started = Instant::now();
+    let mut window_bytes = 0u64;
+
+    loop {
+        let next = tokio::time::timeout(policy.stall_timeout, response.chunk())
+            .await
+            .map_err(|_| DownloadError::Stalled(url.to_string()))??;
+        let Some(chunk) = next else {
+            break;
+        };
+        let len = chunk.len() as u64;
+        let throttled = Instant::now();
+        status.bandwidth.acquire(len).await;
+        job.scheduler.throttle(len).await;
+        window_started += throttled.elapsed();
+
+        writer.write_all(&chunk).await?;
+        pending += len;
+        window_bytes += len;
+
+        if pending > FLUSH_THRESHOLD {
             writer.flush().await?;
-            status
-                .downloaded
-                .fetch_add(local_downloaded, std::sync::atomic::Ordering::Relaxed);
-
-            observability::chunk_completed(url, start, end);
-            Ok(())
-        } => {
-            match result {
-                Ok(ok) => Ok(ok),
-                Err(err) => {
-                    if status.cancelled() {
-                        Err(DownloadError::Cancelled("任务已取消".to_string()))
-                    } else {
-                        observability::chunk_failed(url, start, end, &err);
-                        Err(err)
-                    }
-                }
+            commit(job, index, offset, &mut pending, status);
+        }
+
+        let elapsed = window_started.elapsed();
+        if check_throughput && elapsed >= policy.throughput_window {
+            if (window_bytes as f64) < policy.min_throughput as f64 * elapsed.as_secs_f64() {
+                writer.flush().await?;
+                commit(job, index, offset, &mut pending, status);
+                return Err(DownloadError::Stalled(url.to_string()));
             }
-        },
-        _ = status.cancel_token.cancelled() => {
-            Err(DownloadError::Cancelled("任务已取消".to_string()))
+            window_started = Instant::now();
+            window_bytes = 0;
         }
     }
+
+    writer.flush().await?;
+    commit(job, index, offset, &mut pending, status);
+
+    if *offset != end + 1 {
+        return Err(DownloadError::Message(format!(
+            "响应提前结束: 已写到 {}，预期到 {}",
+            *offset,
+            end + 1
+        )));
+    }
+    Ok(())
+}
+
+/// 将已落盘的字节计入进度并推进写入位置与块进度。
+fn commit(
+    job: &DownloadJob,
+    index: usize,
+    offset: &mut u64,
+    pending: &mut u64,
+    status: &DownloadStatus,
+) {
+    *offset += *pending;
+    job.advance(index, *offset);
+    status
+        .downloaded
+        .fetch_add(*pending, std::sync::atomic::Ordering::Relaxed);
+    *pending = 0;
 }
 
 /// 验证块响应。
@@ -107,7 +221,16 @@ pub(super) async fn download_chunk(
 ///
 /// - `response`: 服务器响应
 /// - `start`: 块起始位置（如果大于 0，必须返回 206）
-fn validate_chunk_response(response: &reqwest::Response, start: u64) -> Result<(), DownloadError> {
+/// - `conditional`: 请求是否携带了 `If-Range`（此时 200 表示远端文件已变化）
+fn validate_chunk_response(
+    response: &reqwest::Response,
+    start: u64,
+    conditional: bool,
+) -> Result<(), DownloadError> {
+    if conditional && response.status() == StatusCode::OK {
+        return Err(DownloadError::RemoteChanged(response.url().to_string()));
+    }
+
     if start > 0 && response.status() != StatusCode::PARTIAL_CONTENT {
         return Err(DownloadError::Response(
             response.status().as_u16(),
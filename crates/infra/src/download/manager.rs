@@ -5,6 +5,11 @@
 //! 使用 `create()` 或 `create_with_handle()` 启动下载，
 //! 通过 `get_progress()` / `cancel()` 查询和取消任务。
 //! 已结束的任务在查询时自动清理。
+//! 以 [`TaskOptions::resumable`] 创建的任务写入续传记录，进程退出时未完成的任务
+//! 可通过 `incomplete()` 列出、`resume()` 续传。
+//!
+//! 任务创建后先进入全局 [`DownloadScheduler`] 的队列，获得运行名额后才开始探测与下载；
+//! 排队或运行中的任务可通过 `pause()` 暂停、`resume()` 恢复。
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -13,8 +18,13 @@ use std::sync::OnceLock;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+use crate::download::mirror::MirrorHealth;
 use crate::download::multi::Downloader;
+use crate::download::resume::{ResumeRecord, ResumeStore};
+use crate::download::scheduler::{DownloadPriority, DownloadScheduler, SchedulerLimits, TaskSlot};
 use crate::download::status::{DownloadError, DownloadSnapshot, DownloadStatus};
+use crate::download::tasks::{DownloadJob, RunOutcome};
+use crate::download::verify::{DownloadIntegrity, partial_path};
 use crate::net::client::NetClient;
 use crate::net::{ClientProvider, global_client_provider};
 use crate::observability;
@@ -39,6 +49,34 @@ pub struct DownloadManager {
     tasks: Arc<RwLock<HashMap<Uuid, Arc<DownloadStatus>>>>,
 }
 
+/// 创建任务时的调度选项。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TaskOptions {
+    /// 调度优先级
+    pub priority: DownloadPriority,
+    /// 任务自身的带宽上限（字节/秒）；为 `None` 时只受全局限制
+    pub bandwidth_limit: Option<u64>,
+    /// 远端支持 Range 时是否写入续传记录，供进程重启后续传。
+    ///
+    /// 只有用户发起、需要出现在未完成列表中的下载才应开启；市场安装等内部下载
+    /// 由调用方自行重试，不留下续传记录。
+    pub resumable: bool,
+}
+
+/// 获得运行名额后如何开始下载。
+enum Launch {
+    /// 从头下载
+    Fresh {
+        candidates: Vec<String>,
+        output_path: String,
+        thread_count: usize,
+        integrity: DownloadIntegrity,
+        resumable: bool,
+    },
+    /// 按续传记录继续
+    Resume(ResumeRecord),
+}
+
 impl DownloadManager {
     /// 创建下载任务管理器（供上层显式持有与注入）。
     ///
@@ -62,6 +100,24 @@ impl DownloadManager {
         }
     }
 
+    /// 替换镜像健康统计（默认使用持久化到应用数据目录的进程级统计）。
+    pub fn with_mirror_health(mut self, health: Arc<MirrorHealth>) -> Self {
+        self.downloader = self.downloader.with_mirror_health(health);
+        self
+    }
+
+    /// 替换续传记录存储（默认使用应用数据目录下的进程级存储）。
+    pub fn with_resume_store(mut self, store: Arc<ResumeStore>) -> Self {
+        self.downloader = self.downloader.with_resume_store(store);
+        self
+    }
+
+    /// 替换调度器（默认使用限制持久化到应用数据目录的进程级调度器）。
+    pub fn with_scheduler(mut self, scheduler: Arc<DownloadScheduler>) -> Self {
+        self.downloader = self.downloader.with_scheduler(scheduler);
+        self
+    }
+
     /// 创建一个下载任务。
     ///
     /// 启动下载后立即返回任务 UUID，下载在后台异步进行。
@@ -97,20 +153,218 @@ impl DownloadManager {
         output_path: &str,
         thread_count: usize,
     ) -> Result<(Uuid, Arc<DownloadStatus>), DownloadError> {
-        let status = self
-            .downloader
-            .download(url, output_path, thread_count)
-            .await?;
-        let id = Uuid::new_v4();
+        self.create_from_mirrors(
+            &[url.to_string()],
+            output_path,
+            thread_count,
+            DownloadIntegrity::default(),
+        )
+        .await
+    }
+
+    /// 从多个镜像创建带完整性校验的下载任务，返回任务 UUID 和状态句柄。
+    ///
+    /// `candidates` 为同一文件的候选地址，按调用方偏好排列；实际顺序由镜像健康统计
+    /// 调整，探测或分块下载失败、停滞时自动改用下一个地址。
+    ///
+    /// 下载结束后按 `integrity` 校验大小与摘要，通过后才出现在 `output_path`；
+    /// 校验失败时任务以错误结束，错误信息来自 [`DownloadError::SizeMismatch`] 或
+    /// [`DownloadError::ChecksumMismatch`]。远端声明的大小与预期不符时直接返回错误。
+    ///
+    /// 任务不写入续传记录；需要跨进程续传时使用 [`Self::create_scheduled`] 并开启
+    /// [`TaskOptions::resumable`]。
+    pub async fn create_from_mirrors(
+        &self,
+        candidates: &[String],
+        output_path: &str,
+        thread_count: usize,
+        integrity: DownloadIntegrity,
+    ) -> Result<(Uuid, Arc<DownloadStatus>), DownloadError> {
+        self.create_scheduled(
+            candidates,
+            output_path,
+            thread_count,
+            integrity,
+            TaskOptions::default(),
+        )
+        .await
+    }
 
-        let mut tasks = self.tasks.write().await;
-        tasks.insert(id, status.clone());
+    /// 按调度选项创建下载任务，其余行为同 [`Self::create_from_mirrors`]。
+    ///
+    /// 有空闲名额时立即探测并开始下载，探测失败直接返回错误；否则任务进入队列，
+    /// 快照的 `queue_position` 给出排队位置，轮到时再探测，失败时以错误结束。
+    ///
+    /// 开启 [`TaskOptions::resumable`] 且远端支持 Range 时任务进度会写入续传记录，
+    /// 进程退出后可通过 [`Self::resume`] 续传。
+    pub async fn create_scheduled(
+        &self,
+        candidates: &[String],
+        output_path: &str,
+        thread_count: usize,
+        integrity: DownloadIntegrity,
+        options: TaskOptions,
+    ) -> Result<(Uuid, Arc<DownloadStatus>), DownloadError> {
+        Downloader::validate(candidates, thread_count)?;
+
+        let id = Uuid::new_v4();
+        let status = Arc::new(DownloadStatus::new(0));
+        status.set_priority(options.priority);
+        status.set_bandwidth_limit(options.bandwidth_limit);
+        let launch = Launch::Fresh {
+            candidates: candidates.to_vec(),
+            output_path: output_path.to_owned(),
+            thread_count,
+            integrity,
+            resumable: options.resumable,
+        };
+        self.schedule(id, &status, launch).await?;
 
-        observability::task_created(&id, url);
+        observability::task_created(&id, candidates.first().map_or("", String::as_str));
 
         Ok((id, status))
     }
 
+    /// 列出有续传记录、但当前不在运行的任务（如上次退出时中断或出错的下载），
+    /// 按最近保存时间从早到晚排列。
+    pub async fn incomplete(&self) -> Vec<ResumeRecord> {
+        let records = self.downloader.resume_store().list().await;
+        let tasks = self.tasks.read().await;
+        records
+            .into_iter()
+            .filter(|record| !tasks.contains_key(&record.id))
+            .collect()
+    }
+
+    /// 恢复已暂停的任务，或按续传记录继续下载，沿用原任务 UUID 并返回状态句柄。
+    ///
+    /// 任务已暂停时重新排队（运行中暂停的任务从已落盘位置继续）；任务正在运行或
+    /// 排队时直接返回其状态句柄。不在管理器中的任务按续传记录排队，远端文件已变化
+    /// 或临时文件丢失时从头下载（仍使用同一 UUID）。
+    ///
+    /// # Errors
+    ///
+    /// 没有该任务的续传记录，或立即开始时所有候选地址都不可用时返回错误；
+    /// 后者保留续传记录。
+    pub async fn resume(&self, id: Uuid) -> Result<Arc<DownloadStatus>, DownloadError> {
+        if let Some(status) = self.tasks.read().await.get(&id).cloned() {
+            if status.is_paused() {
+                self.downloader.scheduler().set_paused(id, false);
+                status.unpause();
+                observability::task_resumed(&id);
+            }
+            return Ok(status);
+        }
+        let record = self
+            .downloader
+            .resume_store()
+            .load(id)
+            .await
+            .ok_or_else(|| DownloadError::Message(format!("没有可续传的下载任务: {id}")))?;
+        let url = record.candidates.first().cloned().unwrap_or_default();
+
+        let status = Arc::new(DownloadStatus::new(record.total_size));
+        self.schedule(id, &status, Launch::Resume(record)).await?;
+
+        observability::task_created(&id, &url);
+
+        Ok(status)
+    }
+
+    /// 暂停任务：排队中的任务不再被调度，运行中的任务停止并保留已下载的部分。
+    ///
+    /// # Errors
+    ///
+    /// 任务不存在或已结束，或任务正在运行但远端不支持 Range（无法从中途继续）时返回错误。
+    pub async fn pause(&self, id: Uuid) -> Result<(), DownloadError> {
+        let status = self
+            .tasks
+            .read()
+            .await
+            .get(&id)
+            .cloned()
+            .ok_or_else(|| DownloadError::Message(format!("下载任务不存在: {id}")))?;
+        if status.snapshot().await.is_finished {
+            return Err(DownloadError::Message(format!("下载任务已结束: {id}")));
+        }
+        let queued = self.downloader.scheduler().set_paused(id, true);
+        if !queued && !status.resumable.load(std::sync::atomic::Ordering::Relaxed) {
+            return Err(DownloadError::Message("远端不支持断点续传，下载无法暂停".to_string()));
+        }
+        status.pause();
+        observability::task_paused(&id);
+        Ok(())
+    }
+
+    /// 修改任务优先级：排队中的任务立即按新优先级调整位置，
+    /// 运行中的任务在暂停后重新排队时生效。任务不存在时返回 `false`。
+    pub async fn set_priority(&self, id: Uuid, priority: DownloadPriority) -> bool {
+        let Some(status) = self.tasks.read().await.get(&id).cloned() else {
+            return false;
+        };
+        status.set_priority(priority);
+        self.downloader.scheduler().set_priority(id, priority);
+        true
+    }
+
+    /// 当前全局调度限制。
+    pub fn limits(&self) -> SchedulerLimits {
+        self.downloader.scheduler().limits()
+    }
+
+    /// 修改全局调度限制并持久化，立即对所有任务生效，返回收敛后的限制。
+    pub async fn set_limits(
+        &self,
+        limits: SchedulerLimits,
+    ) -> Result<SchedulerLimits, DownloadError> {
+        let scheduler = self.downloader.scheduler();
+        let applied = scheduler.set_limits(limits);
+        scheduler.save().await.map_err(DownloadError::Fs)?;
+        Ok(applied)
+    }
+
+    /// 让任务排队；有空闲名额时立即开始，错误直接返回，否则在后台等待名额。
+    async fn schedule(
+        &self,
+        id: Uuid,
+        status: &Arc<DownloadStatus>,
+        launch: Launch,
+    ) -> Result<(), DownloadError> {
+        let scheduler = self.downloader.scheduler();
+        let mut ticket = scheduler.enqueue(id, status.priority(), status.is_paused());
+
+        if let Some(slot) = ticket.try_admit() {
+            drop(ticket);
+            let job = prepare(&self.downloader, id, status, launch).await?;
+            self.tasks.write().await.insert(id, Arc::clone(status));
+            tokio::spawn(drive(self.downloader.clone(), id, job, Arc::clone(status), slot));
+            return Ok(());
+        }
+
+        self.tasks.write().await.insert(id, Arc::clone(status));
+        let downloader = self.downloader.clone();
+        let status = Arc::clone(status);
+        tokio::spawn(async move {
+            let slot = tokio::select! {
+                slot = ticket.admit() => slot,
+                _ = status.cancel_token.cancelled() => {
+                    // 尚未开始即被取消：续传任务的记录与临时文件一并删除。
+                    if let Launch::Resume(record) = launch {
+                        downloader.resume_store().remove(id).await;
+                        let partial = partial_path(std::path::Path::new(&record.output_path));
+                        let _ = tokio::fs::remove_file(partial).await;
+                    }
+                    return;
+                }
+            };
+            match prepare(&downloader, id, &status, launch).await {
+                Ok(job) => drive(downloader, id, job, status, slot).await,
+                Err(error) => status.fail(error).await,
+            }
+        });
+        Ok(())
+    }
+
     /// 查询单个任务的进度。
     ///
     /// 任务完成时自动从管理器中移除。
@@ -128,7 +382,8 @@ impl DownloadManager {
             tasks.get(&id).cloned()?
         };
 
-        let snap = status.snapshot().await;
+        let mut snap = status.snapshot().await;
+        snap.queue_position = self.downloader.scheduler().queue_position(id);
 
         // 自动移除已完成的任务以释放管理资源。
         // 可接受的竞态条件：两个并发调用可能同时看到任务已完成，
@@ -154,7 +409,8 @@ impl DownloadManager {
         let mut to_remove = Vec::new();
 
         for (id, status) in snapshot {
-            let snap = status.snapshot().await;
+            let mut snap = status.snapshot().await;
+            snap.queue_position = self.downloader.scheduler().queue_position(id);
             if snap.is_finished {
                 to_remove.push(id);
             }
@@ -171,9 +427,9 @@ impl DownloadManager {
         results
     }
 
-    /// 取消一个下载任务。
+    /// 取消一个下载任务（包括排队中与已暂停的任务）。
     ///
-    /// 取消后任务将从管理器中移除。
+    /// 取消后任务将从管理器中移除，其续传记录与临时文件随之删除。
     ///
     /// # Parameters
     ///
@@ -200,6 +456,89 @@ impl DownloadManager {
     }
 }
 
+/// 获得运行名额后探测远端并构造任务上下文；已流式下载完毕时为 `None`。
+async fn prepare(
+    downloader: &Downloader,
+    id: Uuid,
+    status: &Arc<DownloadStatus>,
+    launch: Launch,
+) -> Result<Option<Arc<DownloadJob>>, DownloadError> {
+    match launch {
+        Launch::Fresh {
+            candidates,
+            output_path,
+            thread_count,
+            integrity,
+            resumable,
+        } => {
+            downloader
+                .prepare(id, &candidates, &output_path, thread_count, integrity, resumable, status)
+                .await
+        }
+        Launch::Resume(record) => downloader.prepare_resume(record, status).await,
+    }
+}
+
+/// 同一任务因远端文件变化从头下载的最多次数。
+const MAX_REMOTE_RESTARTS: usize = 3;
+
+/// 运行任务直到结束；运行中被暂停时让出名额，恢复后重新排队并从已落盘位置继续。
+///
+/// 下载途中远端文件变化时保留名额，丢弃已下载的部分并在同一任务内从头下载；
+/// 连续变化超过 [`MAX_REMOTE_RESTARTS`] 次后任务以错误结束。
+async fn drive(
+    downloader: Downloader,
+    id: Uuid,
+    job: Option<Arc<DownloadJob>>,
+    status: Arc<DownloadStatus>,
+    mut slot: TaskSlot,
+) {
+    let Some(mut job) = job else {
+        return;
+    };
+    let mut restarts = 0;
+    loop {
+        let outcome = downloader.run(&job, &status).await;
+        if let Ok(RunOutcome::RemoteChanged(error)) = outcome {
+            if restarts == MAX_REMOTE_RESTARTS {
+                status.fail(error).await;
+                return;
+            }
+            restarts += 1;
+            match downloader.restart(&job, &status).await {
+                Ok(Some(next)) => {
+                    job = next;
+                    continue;
+                }
+                Ok(None) => return,
+                Err(error) => {
+                    status.fail(error).await;
+                    return;
+                }
+            }
+        }
+        drop(slot);
+        if !status.is_paused() || status.snapshot().await.is_finished {
+            return;
+        }
+
+        if !status.wait_unpaused().await {
+            job.discard().await;
+            return;
+        }
+        let ticket = downloader
+            .scheduler()
+            .enqueue(id, status.priority(), status.is_paused());
+        slot = tokio::select! {
+            slot = ticket.admit() => slot,
+            _ = status.cancel_token.cancelled() => {
+                job.discard().await;
+                return;
+            }
+        };
+    }
+}
+
 static GLOBAL_DOWNLOAD_MANAGER: OnceLock<DownloadManager> = OnceLock::new();
 
 impl DownloadManager {
@@ -239,4 +578,87 @@ mod tests {
         let snap = manager.get_progress(Uuid::nil()).await;
         assert!(snap.is_none());
     }
+
+    async fn queue_position(manager: &DownloadManager, id: Uuid) -> Option<usize> {
+        manager
+            .get_progress(id)
+            .await
+            .and_then(|snap| snap.queue_position)
+    }
+
+    #[tokio::test]
+    async fn queues_tasks_beyond_the_active_limit_by_priority() {
+        let config = crate::net::ClientConfig {
+            retry_policy: crate::net::RetryPolicy { max_retries: 0, ..Default::default() },
+            ..Default::default()
+        };
+        let scheduler = Arc::new(DownloadScheduler::new(SchedulerLimits {
+            max_active_tasks: 1,
+            ..Default::default()
+        }));
+        let dir =
+            std::env::temp_dir().join(format!("sealantern-infra-queue-{}", uuid::Uuid::new_v4()));
+        let manager = DownloadManager::new(NetClient::from_config(&config).unwrap())
+            .with_mirror_health(Arc::new(MirrorHealth::in_memory()))
+            .with_resume_store(Arc::new(ResumeStore::new(dir.join("resume"))))
+            .with_scheduler(Arc::clone(&scheduler));
+        // 占住唯一的名额，后续任务只能排队。
+        let mut holder = scheduler.enqueue(Uuid::new_v4(), DownloadPriority::High, false);
+        let slot = holder.try_admit().expect("free slot");
+        // 没有服务监听的端口：轮到时探测立即失败。
+        let unreachable = ["http://127.0.0.1:9/server.jar".to_owned()];
+        let output = dir.join("server.jar").to_string_lossy().into_owned();
+        let create = |priority| {
+            manager.create_scheduled(
+                &unreachable,
+                &output,
+                2,
+                DownloadIntegrity::default(),
+                TaskOptions { priority, ..Default::default() },
+            )
+        };
+
+        let (low, _) = create(DownloadPriority::Low).await.expect("queued");
+        let (high, high_status) = create(DownloadPriority::High).await.expect("queued");
+        assert_eq!(queue_position(&manager, high).await, Some(1));
+        assert_eq!(queue_position(&manager, low).await, Some(2));
+
+        manager.pause(high).await.expect("pause queued task");
+        let paused = manager.get_progress(high).await.unwrap();
+        assert!(paused.paused && !paused.is_finished);
+        assert_eq!(paused.queue_position, None);
+        assert_eq!(queue_position(&manager, low).await, Some(1));
+
+        manager.resume(high).await.expect("resume queued task");
+        assert!(!high_status.is_paused());
+        assert_eq!(queue_position(&manager, high).await, Some(1));
+
+        manager.cancel(high).await;
+        for _ in 0..100 {
+            if queue_position(&manager, low).await == Some(1) {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(queue_position(&manager, low).await, Some(1));
+
+        // 让出名额后排队的任务开始探测，失败记录在任务状态中。
+        drop(slot);
+        let mut finished = None;
+        for _ in 0..200 {
+            match manager.get_progress(low).await {
+                Some(snap) if snap.is_finished => {
+                    finished = Some(snap);
+                    break;
+                }
+                _ => tokio::time::sleep(std::time::Duration::from_millis(25)).await,
+            }
+        }
+        assert!(finished.expect("queued task ran").error.is_some());
+
+        // 有空闲名额时探测失败直接返回错误。
+        assert!(create(DownloadPriority::Normal).await.is_err());
+        assert!(manager.pause(Uuid::nil()).await.is_err());
+        assert_eq!(manager.task_count().await, 0);
+    }
 }
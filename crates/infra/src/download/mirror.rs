@@ -0,0 +1,274 @@
+//! 镜像健康统计与故障转移策略。
+//!
+//! 下载任务可以携带多个候选地址（同一文件的不同镜像）。[`MirrorHealth`] 按镜像主机
+//! 记录成功、失败次数与吞吐量，并据此为候选地址排序；统计持久化到应用数据目录，
+//! 跨进程保留。[`FailoverPolicy`] 决定何时认为一个镜像已停滞、每个分块最多尝试几次。
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::fs::{FsError, write_json_atomic};
+use crate::observability;
+use crate::platform::get_app_data_dir;
+
+/// 镜像健康统计文件名（位于应用数据目录）。
+const MIRROR_HEALTH_FILE: &str = "download_mirrors.json";
+
+/// 尚未测得吞吐量的镜像按此速度（1 MiB/s）参与排序，保证新镜像有机会被尝试。
+const DEFAULT_THROUGHPUT: f64 = 1024.0 * 1024.0;
+
+/// 吞吐量指数滑动平均中新样本的权重。
+const THROUGHPUT_SMOOTHING: f64 = 0.3;
+
+/// 单个镜像主机的健康统计。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MirrorStats {
+    /// 成功完成的请求数。
+    pub successes: u64,
+    /// 失败或停滞的请求数。
+    pub failures: u64,
+    /// 最近连续失败次数，成功一次即清零。
+    pub consecutive_failures: u32,
+    /// 吞吐量的指数滑动平均（字节/秒）；尚无样本时为 `None`。
+    pub throughput_bytes_per_sec: Option<f64>,
+    /// 最近一次失败的时间（Unix 秒）。
+    pub last_failure_unix_secs: Option<u64>,
+}
+
+impl MirrorStats {
+    /// 排序分数：可靠性（带平滑的成功率）乘以吞吐量。
+    pub fn score(&self) -> f64 {
+        let reliability =
+            (self.successes as f64 + 1.0) / (self.successes as f64 + self.failures as f64 + 2.0);
+        reliability * self.throughput_bytes_per_sec.unwrap_or(DEFAULT_THROUGHPUT)
+    }
+}
+
+/// 按镜像主机记录的健康统计，可持久化。
+///
+/// 内部以互斥锁保护，可在多个下载任务、多个分块之间共享。
+#[derive(Debug, Default)]
+pub struct MirrorHealth {
+    /// 持久化路径；为 `None` 时只保存在内存中。
+    path: Option<PathBuf>,
+    stats: Mutex<HashMap<String, MirrorStats>>,
+}
+
+static GLOBAL_MIRROR_HEALTH: OnceLock<Arc<MirrorHealth>> = OnceLock::new();
+
+impl MirrorHealth {
+    /// 只保存在内存中的统计（便于测试）。
+    pub fn in_memory() -> Self {
+        Self::default()
+    }
+
+    /// 从文件加载统计；文件不存在或无法解析时从空统计开始，之后保存到同一路径。
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let stats = std::fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        Self {
+            path: Some(path),
+            stats: Mutex::new(stats),
+        }
+    }
+
+    /// 进程级共享的统计，持久化到应用数据目录。
+    pub fn global() -> Arc<Self> {
+        GLOBAL_MIRROR_HEALTH
+            .get_or_init(|| Arc::new(Self::open(get_app_data_dir().join(MIRROR_HEALTH_FILE))))
+            .clone()
+    }
+
+    /// 记录一次成功请求及其吞吐量。
+    pub fn record_success(&self, url: &str, bytes: u64, elapsed: Duration) {
+        let mut stats = self.lock();
+        let entry = stats.entry(mirror_key(url)).or_default();
+        entry.successes += 1;
+        entry.consecutive_failures = 0;
+        // 过小的样本（如探测请求）不足以反映吞吐量。
+        if bytes >= 64 * 1024 && !elapsed.is_zero() {
+            let sample = bytes as f64 / elapsed.as_secs_f64();
+            entry.throughput_bytes_per_sec = Some(match entry.throughput_bytes_per_sec {
+                Some(previous) => {
+                    previous * (1.0 - THROUGHPUT_SMOOTHING) + sample * THROUGHPUT_SMOOTHING
+                }
+                None => sample,
+            });
+        }
+    }
+
+    /// 记录一次失败或停滞。
+    pub fn record_failure(&self, url: &str) {
+        let mut stats = self.lock();
+        let entry = stats.entry(mirror_key(url)).or_default();
+        entry.failures += 1;
+        entry.consecutive_failures = entry.consecutive_failures.saturating_add(1);
+        entry.last_failure_unix_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .map(|duration| duration.as_secs());
+    }
+
+    /// 镜像主机的统计；从未记录过时为 `None`。
+    pub fn stats(&self, url: &str) -> Option<MirrorStats> {
+        self.lock().get(&mirror_key(url)).cloned()
+    }
+
+    /// 排列候选地址：连续失败次数少的在前，其次按分数从高到低；
+    /// 两者都相同（如都未记录过）时保持原有顺序。
+    pub fn order(&self, candidates: &[String]) -> Vec<String> {
+        let stats = self.lock();
+        let mut ranked: Vec<(MirrorStats, &String)> = candidates
+            .iter()
+            .map(|url| (stats.get(&mirror_key(url)).cloned().unwrap_or_default(), url))
+            .collect();
+        ranked.sort_by(|(left, _), (right, _)| {
+            left.consecutive_failures
+                .cmp(&right.consecutive_failures)
+                .then_with(|| right.score().total_cmp(&left.score()))
+        });
+        ranked.into_iter().map(|(_, url)| url.clone()).collect()
+    }
+
+    /// 将统计写回持久化路径；内存统计直接返回。
+    pub async fn save(&self) -> Result<(), FsError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let snapshot = self.lock().clone();
+        write_json_atomic(path, &snapshot).await
+    }
+
+    /// 保存统计，失败只记录日志（统计丢失不影响下载结果）。
+    pub(super) async fn save_or_log(&self) {
+        if let Err(error) = self.save().await {
+            let path = self.path.as_deref().unwrap_or(Path::new(""));
+            observability::mirror_health_save_failed(path, &error);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, MirrorStats>> {
+        self.stats
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// 统计使用的镜像标识：`scheme://host[:port]`，无法解析时为原地址。
+pub fn mirror_key(url: &str) -> String {
+    match url::Url::parse(url) {
+        Ok(parsed) => match (parsed.host_str(), parsed.port()) {
+            (Some(host), Some(port)) => format!("{}://{host}:{port}", parsed.scheme()),
+            (Some(host), None) => format!("{}://{host}", parsed.scheme()),
+            _ => url.to_owned(),
+        },
+        Err(_) => url.to_owned(),
+    }
+}
+
+/// 分段下载的故障转移策略。
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FailoverPolicy {
+    /// 超过该时长没有收到任何数据即视为停滞。
+    pub stall_timeout: Duration,
+    /// 存在其他候选地址时，吞吐量低于该值（字节/秒）也视为停滞。
+    pub min_throughput: u64,
+    /// 计算吞吐量的时间窗口。
+    pub throughput_window: Duration,
+    /// 每个分块的最大尝试次数（含首次），依次轮换候选地址。
+    pub max_attempts: usize,
+}
+
+impl Default for FailoverPolicy {
+    fn default() -> Self {
+        Self {
+            stall_timeout: Duration::from_secs(20),
+            min_throughput: 16 * 1024,
+            throughput_window: Duration::from_secs(15),
+            max_attempts: 4,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| (*value).to_owned()).collect()
+    }
+
+    #[test]
+    fn orders_candidates_by_reliability_and_throughput() {
+        let health = MirrorHealth::in_memory();
+        let candidates = urls(&[
+            "https://slow.example/file.jar",
+            "https://broken.example/file.jar",
+            "https://fresh.example/file.jar",
+            "https://fast.example:8443/file.jar",
+        ]);
+
+        health.record_success("https://slow.example/a", 256 * 1024, Duration::from_secs(8));
+        health.record_failure("https://broken.example/a");
+        health.record_failure("https://broken.example/b");
+        health.record_success("https://fast.example:8443/a", 8 << 20, Duration::from_secs(1));
+
+        assert_eq!(
+            health.order(&candidates),
+            urls(&[
+                "https://fast.example:8443/file.jar",
+                "https://fresh.example/file.jar",
+                "https://slow.example/file.jar",
+                "https://broken.example/file.jar",
+            ])
+        );
+        let broken = health
+            .stats("https://broken.example/other")
+            .expect("recorded");
+        assert_eq!((broken.failures, broken.consecutive_failures), (2, 2));
+
+        health.record_success("https://broken.example/a", 0, Duration::ZERO);
+        assert_eq!(
+            health
+                .stats("https://broken.example/")
+                .unwrap()
+                .consecutive_failures,
+            0
+        );
+    }
+
+    #[tokio::test]
+    async fn persists_stats_across_instances() {
+        let dir =
+            std::env::temp_dir().join(format!("sealantern-infra-mirror-{}", uuid::Uuid::new_v4()));
+        let path = dir.join(MIRROR_HEALTH_FILE);
+
+        let health = MirrorHealth::open(&path);
+        health.record_failure("https://mirror.example/file.jar");
+        health.save().await.expect("save stats");
+
+        let reopened = MirrorHealth::open(&path);
+        assert_eq!(
+            reopened
+                .stats("https://mirror.example/x")
+                .map(|stats| stats.failures),
+            Some(1)
+        );
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn mirror_key_keeps_scheme_host_and_explicit_port() {
+        assert_eq!(mirror_key("https://Example.com/a/b?c"), "https://example.com");
+        assert_eq!(mirror_key("http://127.0.0.1:8080/file"), "http://127.0.0.1:8080");
+        assert_eq!(mirror_key("not a url"), "not a url");
+    }
+}
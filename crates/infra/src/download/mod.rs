@@ -1,16 +1,29 @@
 //! 文件下载模块。
 //!
 //! 提供多线程分段下载（通过 `DownloadManager`）和单线程流式下载。
+//! 两者都先写入 `.part` 临时文件，按 [`DownloadIntegrity`] 校验后再重命名为目标文件。
+//! 下载任务可携带多个镜像地址，按 [`MirrorHealth`] 排序并在失败或停滞时自动切换。
+//! 要求续传的分段下载把进度写入 [`ResumeStore`]，进程重启后可借助 `Range` / `If-Range` 续传。
+//! 所有任务经全局 [`DownloadScheduler`] 排队：限制同时运行的任务数、连接总数与带宽，
+//! 按优先级调度，并支持暂停与恢复。
 //! 调用方通过 `DownloadManager::instance()` 获取全局下载管理器实例，
 //! 使用 `create()` 或 `create_with_handle()` 启动下载任务。
 
 pub(crate) mod chunk;
 pub mod manager;
+pub mod mirror;
 pub(crate) mod multi;
+pub mod resume;
+pub mod scheduler;
 pub(crate) mod single;
 pub mod status;
 pub(crate) mod tasks;
+pub mod verify;
 
-pub use manager::DownloadManager;
-pub use single::{fetch_to_bytes, fetch_to_string, stream_download};
-pub use status::{DownloadError, DownloadSnapshot, DownloadStatus};
+pub use manager::{DownloadManager, TaskOptions};
+pub use mirror::{MirrorHealth, MirrorStats, mirror_key};
+pub use resume::{ChunkProgress, RemoteValidator, ResumeRecord, ResumeStore};
+pub use scheduler::{DownloadPriority, DownloadScheduler, SchedulerLimits, TokenBucket};
+pub use single::{fetch_to_bytes, fetch_to_string, stream_download, stream_download_verified};
+pub use status::{DownloadError, DownloadErrorKind, DownloadSnapshot, DownloadStatus};
+pub use verify::{DownloadIntegrity, partial_path};
@@ -1,106 +1,439 @@
 //! 多线程文件下载器实现。
 //!
 //! `Downloader` 不对外公开，多线程下载请通过 `DownloadManager` 使用。
+//! 下载分两步：`prepare` / `prepare_resume` 探测远端并构造任务上下文，
+//! `run` 启动一次运行；暂停后恢复时对同一上下文再次调用 `run`，下载途中远端文件
+//! 变化时由 `restart` 重新探测并构造新的上下文。
 
-use std::sync::Arc;
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
 
-use crate::download::single::stream_download;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::download::mirror::{FailoverPolicy, MirrorHealth};
+use crate::download::resume::{ChunkProgress, RemoteValidator, ResumeRecord, ResumeStore};
+use crate::download::scheduler::DownloadScheduler;
+use crate::download::single::stream_into;
 use crate::download::status::{DownloadError, DownloadStatus};
-use crate::download::tasks::{spawn_download_tasks, spawn_task_monitor};
+use crate::download::tasks::{
+    DownloadJob, RunOutcome, spawn_download_tasks, spawn_task_monitor, split_ranges,
+};
+use crate::download::verify::{DownloadIntegrity, partial_path};
 use crate::net::ClientProvider;
+use crate::net::client::{NetClient, RemoteFileInfo};
 use crate::observability;
 
 /// 多线程下载器。
 ///
 /// 持有一个客户端获取器（provider），在每次下载开始时获取当前
 /// 全局客户端，避免缓存固定客户端导致代理更新不生效。
+/// 候选地址按共享的镜像健康统计排序，失败或停滞时自动切换。
+/// 要求续传且远端支持 Range 的下载把进度写入续传记录，可在进程重启后续传。
+/// 连接数与带宽受共享的 [`DownloadScheduler`] 限制。
+#[derive(Clone)]
 pub(crate) struct Downloader {
-    client_provider: ClientProvider,
+    client_provider: Arc<ClientProvider>,
+    health: Arc<MirrorHealth>,
+    store: Arc<ResumeStore>,
+    scheduler: Arc<DownloadScheduler>,
+    policy: FailoverPolicy,
 }
 
 impl Downloader {
-    /// 创建一个下载器。
+    /// 创建一个下载器，使用进程级镜像健康统计、续传记录存储与调度器。
     ///
     /// # Parameters
     ///
     /// - `client_provider`: 每次下载开始时调用的客户端获取器
     pub(crate) fn new(client_provider: ClientProvider) -> Self {
-        Self { client_provider }
+        Self {
+            client_provider: Arc::new(client_provider),
+            health: MirrorHealth::global(),
+            store: ResumeStore::global(),
+            scheduler: DownloadScheduler::global(),
+            policy: FailoverPolicy::default(),
+        }
+    }
+
+    /// 替换镜像健康统计（便于测试注入内存统计）。
+    pub(crate) fn with_mirror_health(mut self, health: Arc<MirrorHealth>) -> Self {
+        self.health = health;
+        self
+    }
+
+    /// 替换续传记录存储（便于测试使用临时目录）。
+    pub(crate) fn with_resume_store(mut self, store: Arc<ResumeStore>) -> Self {
+        self.store = store;
+        self
+    }
+
+    /// 替换调度器（便于测试使用独立的限制）。
+    pub(crate) fn with_scheduler(mut self, scheduler: Arc<DownloadScheduler>) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
+    /// 续传记录存储。
+    pub(crate) fn resume_store(&self) -> &Arc<ResumeStore> {
+        &self.store
+    }
+
+    /// 调度器。
+    pub(crate) fn scheduler(&self) -> &Arc<DownloadScheduler> {
+        &self.scheduler
     }
 
-    /// 下载文件并返回一个可查询进度的状态句柄。
+    /// 探测远端并准备下载，进度写入 `status`。
     ///
     /// 流程：
-    /// 1. 获取当前全局客户端并探测远端文件信息（是否支持 Range、文件大小）
-    /// 2. 创建并预分配本地文件
-    /// 3. 根据 Range 支持情况选择分段或单线程下载
-    /// 4. 启动后台监控任务以汇总各段结果
+    /// 1. 获取当前全局客户端，按镜像健康度排列候选地址并依次探测远端文件信息
+    ///    （是否支持 Range、文件大小），直到有一个地址可用；远端大小与预期不符时直接失败
+    /// 2. 创建并预分配 `.part` 临时文件；`resumable` 且远端支持 Range 时写入续传记录
+    /// 3. 根据 Range 支持情况构造分段或单线程的任务上下文，交给 [`Self::run`] 启动
+    ///
+    /// 远端未提供文件大小时无法分段，直接单线程流式下载到结束。
     ///
     /// # Parameters
     ///
-    /// - `url`: 下载地址
+    /// - `id`: 任务 ID（续传记录以此命名）
+    /// - `candidates`: 候选下载地址（同一文件的不同镜像），按调用方偏好排列
     /// - `output_path`: 本地保存路径
     /// - `thread_count`: 下载线程数
+    /// - `integrity`: 预期大小与摘要
+    /// - `resumable`: 是否写入续传记录
+    /// - `status`: 任务状态句柄
     ///
     /// # Returns
     ///
-    /// 返回 `Arc<DownloadStatus>`，可通过 `snapshot()` 查询实时进度。
-    pub async fn download(
+    /// 返回待运行的任务上下文；已流式下载完毕时为 `None`。
+    #[allow(clippy::too_many_arguments)]
+    pub(super) async fn prepare(
         &self,
-        url: &str,
+        id: Uuid,
+        candidates: &[String],
         output_path: &str,
         thread_count: usize,
-    ) -> Result<Arc<DownloadStatus>, DownloadError> {
+        integrity: DownloadIntegrity,
+        resumable: bool,
+        status: &Arc<DownloadStatus>,
+    ) -> Result<Option<Arc<DownloadJob>>, DownloadError> {
+        Self::validate(candidates, thread_count)?;
+
+        let client = (self.client_provider)()?;
+        let (candidates, remote) = self.probe_candidates(&client, candidates).await?;
+
+        self.start(
+            id,
+            client,
+            candidates,
+            remote,
+            output_path,
+            thread_count,
+            integrity,
+            resumable,
+            status,
+        )
+        .await
+    }
+
+    /// 检查下载参数（排队前调用，避免参数错误的任务进入队列）。
+    pub(crate) fn validate(
+        candidates: &[String],
+        thread_count: usize,
+    ) -> Result<(), DownloadError> {
         if thread_count == 0 {
             return Err(DownloadError::Message("Thread count must be positive".to_string()));
         }
+        if candidates.is_empty() {
+            return Err(DownloadError::Message("至少需要一个下载地址".to_string()));
+        }
+        Ok(())
+    }
 
+    /// 按续传记录准备下载，沿用记录中的任务 ID。
+    ///
+    /// 重新探测候选地址后，以下任一情况说明已下载的内容不可信，删除旧记录并从头下载：
+    /// 远端不再支持 Range、文件大小或同一镜像的校验头（`ETag` / `Last-Modified`）
+    /// 发生变化、`.part` 临时文件缺失或大小不符。否则只请求各块剩余的范围，
+    /// 请求携带 `If-Range`，下载途中远端变化时运行以 [`RunOutcome::RemoteChanged`]
+    /// 结束，由 [`Self::restart`] 从头下载。
+    ///
+    /// # Errors
+    ///
+    /// 所有候选地址都不可用时返回最后一次探测错误，续传记录保留。
+    pub(super) async fn prepare_resume(
+        &self,
+        record: ResumeRecord,
+        status: &Arc<DownloadStatus>,
+    ) -> Result<Option<Arc<DownloadJob>>, DownloadError> {
         let client = (self.client_provider)()?;
-        let remote = client.probe(url).await?;
+        let (candidates, remote) = self.probe_candidates(&client, &record.candidates).await?;
+        let current = RemoteValidator::from_probe(&candidates[0], &remote);
+        let partial = partial_path(Path::new(&record.output_path));
+        let partial_size = tokio::fs::metadata(&partial)
+            .await
+            .map(|metadata| metadata.len())
+            .ok();
 
+        let stale = if !remote.supports_range {
+            Some("远端不再支持 Range")
+        } else if remote.total_size != record.total_size {
+            Some("远端文件大小已变化")
+        } else if record.validator.changed_since(&current) {
+            Some("远端文件校验头已变化")
+        } else if partial_size != Some(record.total_size) {
+            Some("临时文件缺失或大小不符")
+        } else {
+            None
+        };
+        if let Some(reason) = stale {
+            observability::download_restarted(&candidates[0], reason);
+            self.store.remove(record.id).await;
+            return self
+                .start(
+                    record.id,
+                    client,
+                    candidates,
+                    remote,
+                    &record.output_path,
+                    record.thread_count,
+                    record.integrity,
+                    true,
+                    status,
+                )
+                .await;
+        }
+
+        observability::download_resumed(&candidates[0], record.downloaded(), record.total_size);
+        let job = DownloadJob {
+            id: record.id,
+            client,
+            candidates,
+            partial,
+            output_path: record.output_path,
+            total_size: record.total_size,
+            thread_count: record.thread_count,
+            integrity: record.integrity,
+            chunks: Mutex::new(record.chunks),
+            validator: Some(current),
+            store: Some(Arc::clone(&self.store)),
+            health: Arc::clone(&self.health),
+            policy: self.policy,
+            scheduler: Arc::clone(&self.scheduler),
+        };
+        status.set_total_size(job.total_size);
+        Ok(Some(Arc::new(job)))
+    }
+
+    /// 运行途中远端文件已变化：丢弃已下载的块进度，重新探测候选地址后从头下载。
+    ///
+    /// 沿用原任务的 ID、目标路径、线程数、完整性要求与是否写入续传记录；
+    /// 新的上下文使用新的远端校验头。
+    pub(super) async fn restart(
+        &self,
+        job: &DownloadJob,
+        status: &Arc<DownloadStatus>,
+    ) -> Result<Option<Arc<DownloadJob>>, DownloadError> {
+        observability::download_restarted(job.url(), "下载途中远端文件已变化");
+        let (candidates, remote) = self.probe_candidates(&job.client, &job.candidates).await?;
+        self.start(
+            job.id,
+            job.client.clone(),
+            candidates,
+            remote,
+            &job.output_path,
+            job.thread_count,
+            job.integrity.clone(),
+            job.store.is_some(),
+            status,
+        )
+        .await
+    }
+
+    /// 在探测成功后从头开始下载。
+    #[allow(clippy::too_many_arguments)]
+    async fn start(
+        &self,
+        id: Uuid,
+        client: NetClient,
+        candidates: Vec<String>,
+        remote: RemoteFileInfo,
+        output_path: &str,
+        thread_count: usize,
+        integrity: DownloadIntegrity,
+        resumable: bool,
+        status: &Arc<DownloadStatus>,
+    ) -> Result<Option<Arc<DownloadJob>>, DownloadError> {
         // 服务器未提供 Content-Length（如 chunked 响应）：多线程分段无法预分配，
         // 改用单线程流式下载，避免「建空文件 + 立即标记完成」的状态失效。
         if remote.total_size == 0 {
-            return stream_download(&client, url, output_path).await;
+            self.stream_with_failover(&client, &candidates, output_path, &integrity, status)
+                .await?;
+            return Ok(None);
+        }
+        if let Err(error) = integrity.check_size(remote.total_size) {
+            observability::download_failed(&candidates[0], &error);
+            return Err(error);
         }
 
-        let actual_thread_count = if remote.supports_range {
-            thread_count
+        // 不支持 Range 时只能单线程从头下载，也就无法续传。
+        let (actual_thread_count, validator, store) = if remote.supports_range {
+            (
+                thread_count,
+                Some(RemoteValidator::from_probe(&candidates[0], &remote)),
+                resumable.then(|| Arc::clone(&self.store)),
+            )
         } else {
-            1
+            (1, None, None)
         };
 
         if let Some(parent) = std::path::Path::new(output_path).parent() {
             tokio::fs::create_dir_all(parent).await?;
         }
 
-        let file = tokio::fs::File::create(output_path).await?;
+        let partial = partial_path(Path::new(output_path));
+        let file = tokio::fs::File::create(&partial).await?;
 
         file.set_len(remote.total_size).await?;
 
         drop(file);
 
-        let status = Arc::new(DownloadStatus::new(remote.total_size));
+        observability::download_started(&candidates[0], remote.total_size, actual_thread_count);
 
-        observability::download_started(url, remote.total_size, actual_thread_count);
+        let chunks = split_ranges(remote.total_size, actual_thread_count)
+            .into_iter()
+            .map(|(start, end)| ChunkProgress::new(start, end))
+            .collect();
+        let job = DownloadJob {
+            id,
+            client,
+            candidates,
+            partial,
+            output_path: output_path.to_owned(),
+            total_size: remote.total_size,
+            thread_count,
+            integrity,
+            chunks: Mutex::new(chunks),
+            validator,
+            store,
+            health: Arc::clone(&self.health),
+            policy: self.policy,
+            scheduler: Arc::clone(&self.scheduler),
+        };
+        // 先写一次续传记录：进程在首个检查点之前退出也能续传。
+        job.checkpoint().await;
+        status.set_total_size(job.total_size);
 
-        let tasks = spawn_download_tasks(
-            client.clone(),
-            url.to_string(),
-            output_path.to_string(),
-            actual_thread_count,
-            remote.total_size,
-            &status,
-        );
+        Ok(Some(Arc::new(job)))
+    }
 
-        spawn_task_monitor(tasks, &status, url.to_string(), remote.total_size);
+    /// 启动一次运行：为未完成的块生成任务并启动监控任务。
+    ///
+    /// 返回监控任务的 `JoinHandle`，本次运行结束（完成、出错、取消、暂停或远端变化）
+    /// 后完成。
+    pub(super) fn run(
+        &self,
+        job: &Arc<DownloadJob>,
+        status: &Arc<DownloadStatus>,
+    ) -> JoinHandle<RunOutcome> {
+        status
+            .resumable
+            .store(job.validator.is_some(), Ordering::Relaxed);
+        status.downloaded.store(job.downloaded(), Ordering::Relaxed);
+        let stop = status.begin_run();
 
-        Ok(status)
+        let tasks = spawn_download_tasks(job, status, &stop);
+        spawn_task_monitor(tasks, Arc::clone(job), status, stop)
+    }
+
+    /// 按健康度依次探测候选地址，返回以首个可用地址开头的候选列表与远端文件信息。
+    async fn probe_candidates(
+        &self,
+        client: &NetClient,
+        candidates: &[String],
+    ) -> Result<(Vec<String>, RemoteFileInfo), DownloadError> {
+        let mut ordered = self.health.order(candidates);
+        let mut last_error = None;
+        for index in 0..ordered.len() {
+            let url = &ordered[index];
+            let started = Instant::now();
+            match client.probe(url).await {
+                Ok(remote) => {
+                    self.health.record_success(url, 0, started.elapsed());
+                    ordered.rotate_left(index);
+                    return Ok((ordered, remote));
+                }
+                Err(error) => {
+                    self.health.record_failure(url);
+                    if let Some(next) = ordered.get(index + 1) {
+                        observability::mirror_failover(url, next, &error);
+                    }
+                    last_error = Some(error);
+                }
+            }
+        }
+        self.health.save_or_log().await;
+        Err(last_error
+            .map(DownloadError::from)
+            .unwrap_or_else(|| DownloadError::Message("没有可用的下载地址".to_string())))
+    }
+
+    /// 单线程流式下载，失败时依次改用下一个候选地址从头下载。
+    ///
+    /// 每次尝试占用一个连接名额，写入的数据受任务与全局带宽限制。
+    async fn stream_with_failover(
+        &self,
+        client: &NetClient,
+        candidates: &[String],
+        output_path: &str,
+        integrity: &DownloadIntegrity,
+        status: &DownloadStatus,
+    ) -> Result<(), DownloadError> {
+        let mut last_error = None;
+        for (index, url) in candidates.iter().enumerate() {
+            let _connection = tokio::select! {
+                permit = self.scheduler.connection() => permit,
+                _ = status.cancel_token.cancelled() => {
+                    return Err(DownloadError::Cancelled("下载已取消".to_string()));
+                }
+            };
+            let started = Instant::now();
+            let result =
+                stream_into(client, url, output_path, integrity, status, Some(&self.scheduler))
+                    .await;
+            match result {
+                Ok(()) => {
+                    let bytes = status.snapshot().await.downloaded;
+                    self.health.record_success(url, bytes, started.elapsed());
+                    self.health.save_or_log().await;
+                    return Ok(());
+                }
+                Err(error @ DownloadError::Cancelled(_)) => return Err(error),
+                Err(error) => {
+                    self.health.record_failure(url);
+                    if let Some(next) = candidates.get(index + 1) {
+                        observability::mirror_failover(url, next, &error);
+                    }
+                    last_error = Some(error);
+                }
+            }
+        }
+        self.health.save_or_log().await;
+        Err(last_error.unwrap_or_else(|| DownloadError::Message("没有可用的下载地址".to_string())))
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
     use super::*;
     use crate::net::NetClient;
 
@@ -108,12 +441,505 @@ mod tests {
         Box::new(|| NetClient::from_config(&Default::default()))
     }
 
+    /// 本地镜像替身的行为。
+    #[derive(Clone, Copy)]
+    enum Mirror {
+        /// 正常支持 Range。
+        Healthy,
+        /// 所有请求返回 500。
+        Broken,
+        /// 探测正常；下载请求只发送前若干字节后挂起连接。
+        StallsAfter(usize),
+        /// 支持 Range 并返回该 `ETag`；`If-Range` 不匹配时按 200 返回完整文件。
+        Versioned(&'static str),
+    }
+
+    /// 启动一个本地 HTTP 镜像替身，返回文件地址与请求计数。
+    async fn spawn_mirror(body: Arc<Vec<u8>>, mirror: Mirror) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&requests);
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let body = Arc::clone(&body);
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buffer = [0_u8; 1024];
+                    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                        match socket.read(&mut buffer).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(read) => request.extend_from_slice(&buffer[..read]),
+                        }
+                    }
+                    let request = String::from_utf8_lossy(&request).to_ascii_lowercase();
+                    let range = request.lines().find_map(|line| {
+                        let (start, end) = line.strip_prefix("range: bytes=")?.split_once('-')?;
+                        let start: usize = start.trim().parse().ok()?;
+                        let end = end.trim().parse().unwrap_or(body.len() - 1);
+                        Some((start, end.min(body.len() - 1)))
+                    });
+                    if let Mirror::Broken = mirror {
+                        let _ = socket
+                            .write_all(b"HTTP/1.1 500 Internal Server Error\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+                            .await;
+                        return;
+                    }
+                    let if_range = request
+                        .lines()
+                        .find_map(|line| line.strip_prefix("if-range: ").map(str::trim));
+                    let (head, slice) = match (mirror, if_range) {
+                        (Mirror::Versioned(etag), Some(tag))
+                            if tag != etag.to_ascii_lowercase() =>
+                        {
+                            (
+                                format!(
+                                    "HTTP/1.1 200 OK\r\netag: {etag}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                                    body.len()
+                                ),
+                                &body[..],
+                            )
+                        }
+                        _ => {
+                            let (start, end) = range.unwrap_or((0, body.len() - 1));
+                            let etag = match mirror {
+                                Mirror::Versioned(etag) => format!("etag: {etag}\r\n"),
+                                _ => String::new(),
+                            };
+                            (
+                                format!(
+                                    "HTTP/1.1 206 Partial Content\r\ncontent-range: bytes {start}-{end}/{}\r\n{etag}content-length: {}\r\nconnection: close\r\n\r\n",
+                                    body.len(),
+                                    end - start + 1
+                                ),
+                                &body[start..=end],
+                            )
+                        }
+                    };
+                    let _ = socket.write_all(head.as_bytes()).await;
+                    match mirror {
+                        Mirror::StallsAfter(sent) if slice.len() > 1 => {
+                            let _ = socket.write_all(&slice[..sent.min(slice.len())]).await;
+                            tokio::time::sleep(Duration::from_secs(30)).await;
+                        }
+                        _ => {
+                            let _ = socket.write_all(slice).await;
+                        }
+                    }
+                });
+            }
+        });
+        (format!("http://{address}/server.jar"), requests)
+    }
+
+    fn test_body() -> Arc<Vec<u8>> {
+        Arc::new(
+            (0..2 * 1024 * 1024_u32)
+                .map(|index| (index * 31 % 251) as u8)
+                .collect(),
+        )
+    }
+
+    fn test_downloader(health: &Arc<MirrorHealth>, dir: &Path) -> Downloader {
+        // 镜像替身的 500 是确定的，不需要客户端自身的退避重试。
+        let config = crate::net::ClientConfig {
+            retry_policy: crate::net::RetryPolicy { max_retries: 0, ..Default::default() },
+            ..Default::default()
+        };
+        let mut downloader = Downloader::new(Box::new(move || NetClient::from_config(&config)))
+            .with_mirror_health(Arc::clone(health))
+            .with_resume_store(Arc::new(ResumeStore::new(dir.join("resume"))))
+            .with_scheduler(Arc::new(DownloadScheduler::new(Default::default())));
+        downloader.policy.stall_timeout = Duration::from_millis(300);
+        downloader
+    }
+
+    /// 准备并启动一个新任务，返回状态句柄。
+    async fn download(
+        downloader: &Downloader,
+        candidates: &[String],
+        output: &Path,
+        thread_count: usize,
+        integrity: DownloadIntegrity,
+    ) -> Result<Arc<DownloadStatus>, DownloadError> {
+        let status = Arc::new(DownloadStatus::new(0));
+        let job = downloader
+            .prepare(
+                Uuid::new_v4(),
+                candidates,
+                &output.to_string_lossy(),
+                thread_count,
+                integrity,
+                true,
+                &status,
+            )
+            .await?;
+        if let Some(job) = job {
+            downloader.run(&job, &status);
+        }
+        Ok(status)
+    }
+
+    /// 按续传记录准备并启动任务，返回状态句柄。
+    async fn resume(
+        downloader: &Downloader,
+        record: ResumeRecord,
+    ) -> Result<Arc<DownloadStatus>, DownloadError> {
+        let status = Arc::new(DownloadStatus::new(0));
+        if let Some(job) = downloader.prepare_resume(record, &status).await? {
+            downloader.run(&job, &status);
+        }
+        Ok(status)
+    }
+
+    async fn wait_finished(status: &DownloadStatus) -> crate::download::DownloadSnapshot {
+        for _ in 0..200 {
+            let snapshot = status.snapshot().await;
+            if snapshot.is_finished {
+                return snapshot;
+            }
+            tokio::time::sleep(Duration::from_millis(25)).await;
+        }
+        panic!("download did not finish in time");
+    }
+
+    fn test_output(label: &str) -> (std::path::PathBuf, std::path::PathBuf) {
+        let dir = std::env::temp_dir()
+            .join(format!("sealantern-infra-mirror-{label}-{}", uuid::Uuid::new_v4()));
+        let output = dir.join("server.jar");
+        (dir, output)
+    }
+
     #[tokio::test]
     async fn downloader_creation() {
         let downloader = Downloader::new(test_client_provider());
-        let result = downloader
-            .download("https://example.com/test", "/tmp/test", 0)
-            .await;
+        let result = download(
+            &downloader,
+            &["https://example.com/test".to_owned()],
+            Path::new("/tmp/test"),
+            0,
+            DownloadIntegrity::default(),
+        )
+        .await;
         assert!(result.is_err());
     }
+
+    #[tokio::test]
+    async fn fails_over_to_the_next_mirror_and_remembers_the_broken_one() {
+        let body = test_body();
+        let (broken, _) = spawn_mirror(Arc::clone(&body), Mirror::Broken).await;
+        let (healthy, _) = spawn_mirror(Arc::clone(&body), Mirror::Healthy).await;
+        let health = Arc::new(MirrorHealth::in_memory());
+        let (dir, output) = test_output("failover");
+        let integrity = DownloadIntegrity {
+            size: Some(body.len() as u64),
+            sha256: Some(crate::fs::sha256_hex(body.as_slice())),
+            ..Default::default()
+        };
+
+        let candidates = [broken.clone(), healthy.clone()];
+        let status = download(&test_downloader(&health, &dir), &candidates, &output, 4, integrity)
+            .await
+            .expect("healthy mirror must be used");
+        let snapshot = wait_finished(&status).await;
+
+        assert_eq!(snapshot.error, None);
+        assert_eq!(std::fs::read(&output).unwrap(), *body);
+        assert!(!partial_path(&output).exists());
+        assert_eq!(health.stats(&broken).map(|stats| stats.failures), Some(1));
+        assert_eq!(health.order(&candidates), [healthy, broken]);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn retries_stalled_chunks_on_another_mirror_from_the_written_offset() {
+        let body = test_body();
+        let (stalling, _) = spawn_mirror(Arc::clone(&body), Mirror::StallsAfter(600 * 1024)).await;
+        let (healthy, healthy_requests) = spawn_mirror(Arc::clone(&body), Mirror::Healthy).await;
+        let health = Arc::new(MirrorHealth::in_memory());
+        let (dir, output) = test_output("stall");
+
+        let status = download(
+            &test_downloader(&health, &dir),
+            &[stalling.clone(), healthy],
+            &output,
+            2,
+            DownloadIntegrity {
+                sha256: Some(crate::fs::sha256_hex(body.as_slice())),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("probe succeeds on the stalling mirror");
+        let snapshot = wait_finished(&status).await;
+
+        assert_eq!(snapshot.error, None);
+        assert_eq!(snapshot.downloaded, body.len() as u64);
+        assert_eq!(std::fs::read(&output).unwrap(), *body);
+        assert_eq!(healthy_requests.load(Ordering::SeqCst), 2);
+        assert_eq!(health.stats(&stalling).map(|stats| stats.failures), Some(2));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn reports_the_last_error_when_every_mirror_is_down() {
+        let body = test_body();
+        let (first, _) = spawn_mirror(Arc::clone(&body), Mirror::Broken).await;
+        let (second, _) = spawn_mirror(body, Mirror::Broken).await;
+        let health = Arc::new(MirrorHealth::in_memory());
+        let (dir, output) = test_output("down");
+
+        let error = download(
+            &test_downloader(&health, &dir),
+            &[first, second],
+            &output,
+            4,
+            DownloadIntegrity::default(),
+        )
+        .await
+        .err()
+        .expect("no mirror is usable");
+
+        assert!(matches!(error, DownloadError::Net(_)), "unexpected error: {error}");
+        assert!(!dir.exists());
+    }
+
+    #[tokio::test]
+    async fn paused_run_keeps_progress_and_continues_from_it() {
+        let body = test_body();
+        let (url, _) = spawn_mirror(Arc::clone(&body), Mirror::Versioned("\"v1\"")).await;
+        let health = Arc::new(MirrorHealth::in_memory());
+        let (dir, output) = test_output("pause");
+        let downloader = test_downloader(&health, &dir);
+        let status = Arc::new(DownloadStatus::new(0));
+        // 令牌桶初始为一秒的容量，之后按限速补充：暂停时必然只下载了一部分。
+        status.set_bandwidth_limit(Some(512 * 1024));
+        let job = downloader
+            .prepare(
+                Uuid::new_v4(),
+                std::slice::from_ref(&url),
+                &output.to_string_lossy(),
+                2,
+                DownloadIntegrity::default(),
+                true,
+                &status,
+            )
+            .await
+            .expect("prepare")
+            .expect("ranged download");
+
+        let run = downloader.run(&job, &status);
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        status.pause();
+        run.await.unwrap();
+
+        let paused = status.snapshot().await;
+        assert!(paused.paused && !paused.is_finished && paused.error.is_none());
+        assert!(paused.downloaded < body.len() as u64);
+        let saved = downloader
+            .resume_store()
+            .load(job.id)
+            .await
+            .expect("progress saved on pause");
+        assert_eq!(saved.downloaded(), job.downloaded());
+
+        status.unpause();
+        status.set_bandwidth_limit(None);
+        downloader.run(&job, &status).await.unwrap();
+        let snapshot = wait_finished(&status).await;
+
+        assert_eq!(snapshot.error, None);
+        assert_eq!(snapshot.downloaded, body.len() as u64);
+        assert_eq!(std::fs::read(&output).unwrap(), *body);
+        assert!(downloader.resume_store().load(job.id).await.is_none());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn downloads_without_resume_leave_no_record() {
+        let body = test_body();
+        let (url, _) = spawn_mirror(Arc::clone(&body), Mirror::Versioned("\"v1\"")).await;
+        let health = Arc::new(MirrorHealth::in_memory());
+        let (dir, output) = test_output("internal");
+        let downloader = test_downloader(&health, &dir);
+        let status = Arc::new(DownloadStatus::new(0));
+        let job = downloader
+            .prepare(
+                Uuid::new_v4(),
+                std::slice::from_ref(&url),
+                &output.to_string_lossy(),
+                2,
+                DownloadIntegrity::default(),
+                false,
+                &status,
+            )
+            .await
+            .expect("prepare")
+            .expect("ranged download");
+
+        assert!(downloader.resume_store().load(job.id).await.is_none());
+        downloader.run(&job, &status).await.unwrap();
+        let snapshot = wait_finished(&status).await;
+
+        assert_eq!(snapshot.error, None);
+        assert_eq!(std::fs::read(&output).unwrap(), *body);
+        // 仍支持 Range：进程内可以暂停，只是不跨进程续传。
+        assert!(status.resumable.load(Ordering::Relaxed));
+        assert!(downloader.resume_store().list().await.is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    /// 模拟上次退出时的状态：两个块各写了一部分，已写部分填充为标记字节。
+    fn interrupted_record(
+        url: &str,
+        etag: &str,
+        body: &[u8],
+        output: &Path,
+    ) -> (ResumeRecord, usize, usize) {
+        let total = body.len();
+        let (first_written, second_written) = (256 * 1024, 128 * 1024);
+        let mut partial = vec![0_u8; total];
+        partial[..first_written].fill(0xAA);
+        partial[total / 2..total / 2 + second_written].fill(0xAA);
+        std::fs::create_dir_all(output.parent().unwrap()).unwrap();
+        std::fs::write(partial_path(output), partial).unwrap();
+
+        let record = ResumeRecord {
+            id: Uuid::new_v4(),
+            candidates: vec![url.to_owned()],
+            output_path: output.to_string_lossy().into_owned(),
+            thread_count: 2,
+            total_size: total as u64,
+            integrity: DownloadIntegrity::default(),
+            validator: RemoteValidator {
+                source: url.to_owned(),
+                etag: Some(etag.to_owned()),
+                last_modified: None,
+            },
+            chunks: vec![
+                ChunkProgress {
+                    start: 0,
+                    end: total as u64 / 2 - 1,
+                    offset: first_written as u64,
+                },
+                ChunkProgress {
+                    start: total as u64 / 2,
+                    end: total as u64 - 1,
+                    offset: (total / 2 + second_written) as u64,
+                },
+            ],
+            updated_at_unix_secs: 0,
+        };
+        (record, first_written, second_written)
+    }
+
+    #[tokio::test]
+    async fn resumes_each_chunk_from_its_saved_offset() {
+        let body = test_body();
+        let (url, _) = spawn_mirror(Arc::clone(&body), Mirror::Versioned("\"v1\"")).await;
+        let health = Arc::new(MirrorHealth::in_memory());
+        let (dir, output) = test_output("resume");
+        let (record, first_written, second_written) =
+            interrupted_record(&url, "\"v1\"", &body, &output);
+        let downloader = test_downloader(&health, &dir);
+        downloader.resume_store().save(&record).await.unwrap();
+
+        let status = resume(&downloader, record).await.expect("resume");
+        assert!(status.snapshot().await.downloaded >= (first_written + second_written) as u64);
+        let snapshot = wait_finished(&status).await;
+
+        assert_eq!(snapshot.error, None);
+        assert_eq!(snapshot.downloaded, body.len() as u64);
+        // 已落盘的字节没有重新下载，其余部分来自远端。
+        let written = std::fs::read(&output).unwrap();
+        let half = body.len() / 2;
+        assert!(written[..first_written].iter().all(|byte| *byte == 0xAA));
+        assert_eq!(written[first_written..half], body[first_written..half]);
+        assert!(
+            written[half..half + second_written]
+                .iter()
+                .all(|byte| *byte == 0xAA)
+        );
+        assert_eq!(written[half + second_written..], body[half + second_written..]);
+        assert!(downloader.resume_store().list().await.is_empty());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn restarts_from_scratch_when_the_remote_file_changed() {
+        let body = test_body();
+        let (url, _) = spawn_mirror(Arc::clone(&body), Mirror::Versioned("\"v2\"")).await;
+        let health = Arc::new(MirrorHealth::in_memory());
+        let (dir, output) = test_output("changed");
+        let (record, _, _) = interrupted_record(&url, "\"v1\"", &body, &output);
+        let id = record.id;
+        let downloader = test_downloader(&health, &dir);
+
+        let status = resume(&downloader, record).await.expect("restart");
+        let snapshot = wait_finished(&status).await;
+
+        assert_eq!(snapshot.error, None);
+        assert_eq!(snapshot.downloaded, body.len() as u64);
+        assert_eq!(std::fs::read(&output).unwrap(), *body);
+        assert!(downloader.resume_store().load(id).await.is_none());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn stale_if_range_restarts_from_scratch_instead_of_mixing_versions() {
+        let body = test_body();
+        let (url, requests) = spawn_mirror(Arc::clone(&body), Mirror::Versioned("\"v2\"")).await;
+        let health = Arc::new(MirrorHealth::in_memory());
+        let (dir, output) = test_output("if-range");
+        let (record, _, _) = interrupted_record(&url, "\"v1\"", &body, &output);
+        let downloader = test_downloader(&health, &dir);
+        downloader.resume_store().save(&record).await.unwrap();
+        let client = (downloader.client_provider)().unwrap();
+
+        // 探测之后远端才变化：块请求携带旧的 If-Range，远端返回完整的新文件。
+        let status = Arc::new(DownloadStatus::new(record.total_size));
+        let job = Arc::new(DownloadJob {
+            id: record.id,
+            client,
+            candidates: record.candidates.clone(),
+            partial: partial_path(&output),
+            output_path: record.output_path.clone(),
+            total_size: record.total_size,
+            thread_count: record.thread_count,
+            integrity: DownloadIntegrity::default(),
+            chunks: Mutex::new(record.chunks.clone()),
+            validator: Some(record.validator.clone()),
+            store: Some(Arc::clone(downloader.resume_store())),
+            health: Arc::clone(&health),
+            policy: downloader.policy,
+            scheduler: Arc::clone(downloader.scheduler()),
+        });
+        let outcome = downloader.run(&job, &status).await.unwrap();
+
+        let RunOutcome::RemoteChanged(error) = outcome else {
+            panic!("remote change must end the run");
+        };
+        assert!(error.to_string().contains("远端文件已变化"));
+        // 远端变化不会在其他镜像或同一镜像上重试，过期的块进度也不会保存。
+        assert!(requests.load(Ordering::SeqCst) <= 2);
+        let changed = status.snapshot().await;
+        assert!(!changed.is_finished && changed.error.is_none());
+        assert!(downloader.resume_store().load(record.id).await.is_none());
+
+        let restarted = downloader
+            .restart(&job, &status)
+            .await
+            .expect("restart")
+            .expect("ranged download");
+        assert_eq!(restarted.id, record.id);
+        assert_eq!(restarted.downloaded(), 0);
+        downloader.run(&restarted, &status);
+        let snapshot = wait_finished(&status).await;
+
+        assert_eq!(snapshot.error, None);
+        assert_eq!(std::fs::read(&output).unwrap(), *body);
+        assert!(downloader.resume_store().load(record.id).await.is_none());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
 }
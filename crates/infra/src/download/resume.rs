@@ -0,0 +1,297 @@
+//! 可续传下载的持久化状态。
+//!
+//! 支持 Range 的分段下载在进行中定期把任务状态（候选地址、目标路径、远端校验头、
+//! 各块已落盘的位置）写入 [`ResumeStore`]，每个任务一个 JSON 文件。进程退出后
+//! 可据此从 `.part` 临时文件续传；任务完成、取消或校验失败时删除对应记录。
+
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::download::mirror::mirror_key;
+use crate::download::verify::DownloadIntegrity;
+use crate::fs::{DataLimit, FsError, read_json, write_json_atomic};
+use crate::net::client::RemoteFileInfo;
+use crate::observability;
+use crate::platform::get_app_data_dir;
+
+/// 续传状态目录名（位于应用数据目录）。
+const RESUME_DIR: &str = "downloads";
+
+/// 单个续传记录的读取上限。
+const RECORD_LIMIT: DataLimit = DataLimit::new(1024 * 1024);
+
+/// 远端文件的校验头，用于判断续传前后是否为同一文件。
+///
+/// 不同镜像的 `ETag` 不可互相比较，因此记录提供校验头的地址，只在同一镜像主机上使用。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RemoteValidator {
+    /// 提供校验头的地址
+    pub source: String,
+    /// `ETag` 响应头
+    pub etag: Option<String>,
+    /// `Last-Modified` 响应头
+    pub last_modified: Option<String>,
+}
+
+impl RemoteValidator {
+    /// 从探测结果构造。
+    pub fn from_probe(url: &str, remote: &RemoteFileInfo) -> Self {
+        Self {
+            source: url.to_owned(),
+            etag: remote.etag.clone(),
+            last_modified: remote.last_modified.clone(),
+        }
+    }
+
+    /// `If-Range` 请求头的取值：强 `ETag` 优先，其次 `Last-Modified`。
+    ///
+    /// 弱 `ETag`（`W/` 前缀）不能用于 `If-Range`。
+    pub fn if_range(&self) -> Option<&str> {
+        self.etag
+            .as_deref()
+            .filter(|etag| !etag.starts_with("W/"))
+            .or(self.last_modified.as_deref())
+    }
+
+    /// 校验头是否适用于该地址（同一镜像主机）。
+    pub fn applies_to(&self, url: &str) -> bool {
+        mirror_key(url) == mirror_key(&self.source)
+    }
+
+    /// 与当前探测结果相比，远端文件是否已变化。
+    ///
+    /// 仅在同一镜像主机上比较：优先比较 `ETag`，两边都没有时比较 `Last-Modified`；
+    /// 无法比较时视为未变化（由文件大小与完成后的摘要校验兜底）。
+    pub fn changed_since(&self, current: &RemoteValidator) -> bool {
+        if !self.applies_to(&current.source) {
+            return false;
+        }
+        if let (Some(saved), Some(current)) = (&self.etag, &current.etag) {
+            return saved != current;
+        }
+        match (&self.last_modified, &current.last_modified) {
+            (Some(saved), Some(current)) => saved != current,
+            _ => false,
+        }
+    }
+}
+
+/// 单个块的下载进度。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChunkProgress {
+    /// 块起始位置
+    pub start: u64,
+    /// 块结束位置（含）
+    pub end: u64,
+    /// 下一个待写入的位置；此前的字节均已落盘
+    pub offset: u64,
+}
+
+impl ChunkProgress {
+    /// 尚未开始的块。
+    pub fn new(start: u64, end: u64) -> Self {
+        Self { start, end, offset: start }
+    }
+
+    /// 已落盘的字节数。
+    pub fn written(&self) -> u64 {
+        self.offset - self.start
+    }
+
+    /// 块是否已下载完毕。
+    pub fn is_complete(&self) -> bool {
+        self.offset > self.end
+    }
+}
+
+/// 一个未完成下载任务的持久化记录。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResumeRecord {
+    /// 任务 ID，续传后沿用
+    pub id: Uuid,
+    /// 候选地址（同一文件的不同镜像）
+    pub candidates: Vec<String>,
+    /// 校验通过后的目标路径
+    pub output_path: String,
+    /// 下载线程数（远端变化需要从头下载时使用）
+    pub thread_count: usize,
+    /// 文件总大小
+    pub total_size: u64,
+    /// 预期大小与摘要
+    pub integrity: DownloadIntegrity,
+    /// 开始下载时的远端校验头
+    pub validator: RemoteValidator,
+    /// 各块进度
+    pub chunks: Vec<ChunkProgress>,
+    /// 最近一次保存的时间（Unix 秒）
+    pub updated_at_unix_secs: u64,
+}
+
+impl ResumeRecord {
+    /// 已落盘的字节数。
+    pub fn downloaded(&self) -> u64 {
+        self.chunks.iter().map(ChunkProgress::written).sum()
+    }
+}
+
+/// 续传记录存储：目录下每个任务一个 `<id>.json` 文件。
+#[derive(Debug)]
+pub struct ResumeStore {
+    dir: PathBuf,
+}
+
+static GLOBAL_RESUME_STORE: OnceLock<Arc<ResumeStore>> = OnceLock::new();
+
+impl ResumeStore {
+    /// 使用指定目录存储续传记录。
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// 进程级共享的存储，位于应用数据目录。
+    pub fn global() -> Arc<Self> {
+        GLOBAL_RESUME_STORE
+            .get_or_init(|| Arc::new(Self::new(get_app_data_dir().join(RESUME_DIR))))
+            .clone()
+    }
+
+    /// 任务记录的文件路径。
+    pub fn path(&self, id: Uuid) -> PathBuf {
+        self.dir.join(format!("{id}.json"))
+    }
+
+    /// 保存记录（覆盖同一任务的旧记录），同时刷新保存时间。
+    pub async fn save(&self, record: &ResumeRecord) -> Result<(), FsError> {
+        let mut record = record.clone();
+        record.updated_at_unix_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        write_json_atomic(self.path(record.id), &record).await
+    }
+
+    /// 读取任务记录；不存在或无法解析时为 `None`。
+    pub async fn load(&self, id: Uuid) -> Option<ResumeRecord> {
+        let path = self.path(id);
+        if !tokio::fs::try_exists(&path).await.unwrap_or(false) {
+            return None;
+        }
+        match read_json(&path, RECORD_LIMIT).await {
+            Ok(record) => Some(record),
+            Err(error) => {
+                observability::resume_state_failed(&path, &error);
+                None
+            }
+        }
+    }
+
+    /// 列出全部记录，按保存时间从早到晚排列；无法解析的文件被跳过。
+    pub async fn list(&self) -> Vec<ResumeRecord> {
+        let Ok(mut entries) = tokio::fs::read_dir(&self.dir).await else {
+            return Vec::new();
+        };
+        let mut records = Vec::new();
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let id = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.strip_suffix(".json"))
+                .and_then(|stem| Uuid::parse_str(stem).ok());
+            if let Some(id) = id
+                && let Some(record) = self.load(id).await
+            {
+                records.push(record);
+            }
+        }
+        records.sort_by_key(|record: &ResumeRecord| record.updated_at_unix_secs);
+        records
+    }
+
+    /// 删除任务记录；记录不存在时什么也不做。
+    pub async fn remove(&self, id: Uuid) {
+        let path = self.path(id);
+        if let Err(error) = tokio::fs::remove_file(&path).await
+            && error.kind() != std::io::ErrorKind::NotFound
+        {
+            observability::resume_state_failed(&path, &error);
+        }
+    }
+
+    /// 保存记录，失败只记录日志（最坏情况是下次从更早的位置续传）。
+    pub(super) async fn save_or_log(&self, record: &ResumeRecord) {
+        if let Err(error) = self.save(record).await {
+            observability::resume_state_failed(&self.path(record.id), &error);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validator(source: &str, etag: Option<&str>, last_modified: Option<&str>) -> RemoteValidator {
+        RemoteValidator {
+            source: source.to_owned(),
+            etag: etag.map(str::to_owned),
+            last_modified: last_modified.map(str::to_owned),
+        }
+    }
+
+    #[test]
+    fn compares_validators_only_on_the_same_mirror() {
+        let saved = validator("https://a.example/x.jar", Some("\"v1\""), Some("Mon"));
+
+        assert!(!saved.changed_since(&validator("https://a.example/x.jar", Some("\"v1\""), None)));
+        assert!(saved.changed_since(&validator("https://a.example/x.jar", Some("\"v2\""), None)));
+        assert!(saved.changed_since(&validator("https://a.example/x.jar", None, Some("Tue"))));
+        assert!(!saved.changed_since(&validator("https://b.example/x.jar", Some("\"v2\""), None)));
+        assert!(!saved.changed_since(&validator("https://a.example/x.jar", None, None)));
+
+        assert_eq!(saved.if_range(), Some("\"v1\""));
+        let weak = validator("https://a.example/x.jar", Some("W/\"v1\""), Some("Mon"));
+        assert_eq!(weak.if_range(), Some("Mon"));
+    }
+
+    #[tokio::test]
+    async fn saves_lists_and_removes_records() {
+        let dir =
+            std::env::temp_dir().join(format!("sealantern-infra-resume-{}", uuid::Uuid::new_v4()));
+        let store = ResumeStore::new(&dir);
+        let record = ResumeRecord {
+            id: Uuid::new_v4(),
+            candidates: vec!["https://a.example/x.jar".to_owned()],
+            output_path: dir.join("x.jar").to_string_lossy().into_owned(),
+            thread_count: 2,
+            total_size: 100,
+            integrity: DownloadIntegrity { size: Some(100), ..Default::default() },
+            validator: validator("https://a.example/x.jar", Some("\"v1\""), None),
+            chunks: vec![
+                ChunkProgress { start: 0, end: 49, offset: 20 },
+                ChunkProgress::new(50, 99),
+            ],
+            updated_at_unix_secs: 0,
+        };
+        assert_eq!(record.downloaded(), 20);
+        assert!(store.list().await.is_empty());
+
+        store.save(&record).await.expect("save record");
+        std::fs::write(dir.join(format!("{}.json", Uuid::new_v4())), b"{broken").unwrap();
+        std::fs::write(dir.join("notes.txt"), b"ignored").unwrap();
+
+        let listed = store.list().await;
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].chunks, record.chunks);
+        assert!(listed[0].updated_at_unix_secs > 0);
+        assert_eq!(store.load(record.id).await.map(|loaded| loaded.id), Some(record.id));
+
+        store.remove(record.id).await;
+        store.remove(record.id).await;
+        assert!(store.load(record.id).await.is_none());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
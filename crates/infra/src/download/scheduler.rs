@@ -0,0 +1,519 @@
+//! 全局下载调度。
+//!
+//! [`DownloadScheduler`] 在所有经 `DownloadManager` 创建的任务之间分配资源：同时运行的
+//! 任务数与连接总数都有上限，超出名额的任务按优先级排队（同优先级先到先得）；带宽由
+//! [`TokenBucket`] 限制，调度器持有全局桶，每个任务的状态持有自己的桶。排队中的任务
+//! 可以暂停，暂停期间保留排队顺序但不会被调度。限制持久化到应用数据目录。
+
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::Notify;
+use uuid::Uuid;
+
+use crate::fs::{FsError, write_json_atomic};
+use crate::platform::get_app_data_dir;
+
+/// 调度限制文件名（位于应用数据目录）。
+const LIMITS_FILE: &str = "download_limits.json";
+
+/// 下载任务优先级；高优先级的任务先获得运行名额。
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadPriority {
+    /// 低
+    Low,
+    /// 普通
+    #[default]
+    Normal,
+    /// 高
+    High,
+}
+
+/// 全局调度限制。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SchedulerLimits {
+    /// 同时运行的任务数上限（至少为 1）
+    pub max_active_tasks: usize,
+    /// 所有任务合计的连接数上限（至少为 1）
+    pub max_connections: usize,
+    /// 全局带宽上限（字节/秒）；为 `None` 时不限速
+    pub bandwidth_limit: Option<u64>,
+}
+
+impl Default for SchedulerLimits {
+    fn default() -> Self {
+        Self {
+            max_active_tasks: 3,
+            max_connections: 32,
+            bandwidth_limit: None,
+        }
+    }
+}
+
+impl SchedulerLimits {
+    /// 把上限收敛到有效范围：名额与连接数至少为 1，带宽为 0 视为不限速。
+    fn normalized(self) -> Self {
+        Self {
+            max_active_tasks: self.max_active_tasks.max(1),
+            max_connections: self.max_connections.max(1),
+            bandwidth_limit: self.bandwidth_limit.filter(|limit| *limit > 0),
+        }
+    }
+}
+
+/// 令牌桶限速器。
+///
+/// 每秒补充 `rate` 个令牌（字节），容量为一秒的流量。取令牌时允许透支，
+/// 调用方按欠额等待，多个调用方共享同一个桶时合计速率不超过 `rate`。
+#[derive(Debug)]
+pub struct TokenBucket {
+    state: Mutex<Bucket>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    rate: Option<u64>,
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl TokenBucket {
+    /// 创建限速器；`rate` 为 `None` 或 0 时不限速。
+    pub fn new(rate: Option<u64>) -> Self {
+        let rate = rate.filter(|rate| *rate > 0);
+        Self {
+            state: Mutex::new(Bucket {
+                rate,
+                tokens: rate.unwrap_or_default() as f64,
+                refilled: Instant::now(),
+            }),
+        }
+    }
+
+    /// 当前速率（字节/秒）。
+    pub fn rate(&self) -> Option<u64> {
+        self.lock().rate
+    }
+
+    /// 修改速率；已透支的令牌保留，新增令牌不超过新容量。
+    pub fn set_rate(&self, rate: Option<u64>) {
+        let mut bucket = self.lock();
+        bucket.refill();
+        bucket.rate = rate.filter(|rate| *rate > 0);
+        bucket.tokens = bucket.tokens.min(bucket.rate.unwrap_or_default() as f64);
+    }
+
+    /// 取走 `bytes` 个令牌，不足时等待补足欠额。
+    pub async fn acquire(&self, bytes: u64) {
+        let wait = {
+            let mut bucket = self.lock();
+            let Some(rate) = bucket.rate else {
+                return;
+            };
+            bucket.refill();
+            bucket.tokens -= bytes as f64;
+            (bucket.tokens < 0.0).then(|| Duration::from_secs_f64(-bucket.tokens / rate as f64))
+        };
+        if let Some(wait) = wait {
+            tokio::time::sleep(wait).await;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Bucket> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Bucket {
+    fn refill(&mut self) {
+        let now = Instant::now();
+        if let Some(rate) = self.rate {
+            let added = now.duration_since(self.refilled).as_secs_f64() * rate as f64;
+            self.tokens = (self.tokens + added).min(rate as f64);
+        }
+        self.refilled = now;
+    }
+}
+
+/// 全局下载调度器。
+#[derive(Debug)]
+pub struct DownloadScheduler {
+    /// 持久化路径；为 `None` 时限制只保存在内存中。
+    path: Option<PathBuf>,
+    state: Mutex<SchedulerState>,
+    /// 名额、连接、队列或限制变化时唤醒等待者。
+    changed: Notify,
+    bandwidth: TokenBucket,
+}
+
+#[derive(Debug, Default)]
+struct SchedulerState {
+    limits: SchedulerLimits,
+    queue: Vec<Waiting>,
+    active: usize,
+    connections: usize,
+    next_seq: u64,
+}
+
+#[derive(Debug)]
+struct Waiting {
+    id: Uuid,
+    priority: DownloadPriority,
+    seq: u64,
+    paused: bool,
+}
+
+impl SchedulerState {
+    /// 未暂停的排队任务按调度顺序排列。
+    fn schedulable(&self) -> Vec<&Waiting> {
+        let mut waiting: Vec<&Waiting> = self.queue.iter().filter(|entry| !entry.paused).collect();
+        waiting.sort_by(|left, right| {
+            right
+                .priority
+                .cmp(&left.priority)
+                .then(left.seq.cmp(&right.seq))
+        });
+        waiting
+    }
+}
+
+static GLOBAL_SCHEDULER: OnceLock<Arc<DownloadScheduler>> = OnceLock::new();
+
+impl DownloadScheduler {
+    /// 使用给定限制创建只保存在内存中的调度器。
+    pub fn new(limits: SchedulerLimits) -> Self {
+        let limits = limits.normalized();
+        Self {
+            path: None,
+            state: Mutex::new(SchedulerState { limits, ..Default::default() }),
+            changed: Notify::new(),
+            bandwidth: TokenBucket::new(limits.bandwidth_limit),
+        }
+    }
+
+    /// 从文件加载限制；文件不存在或无法解析时使用默认限制，之后保存到同一路径。
+    pub fn open(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let limits = std::fs::read(&path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        Self { path: Some(path), ..Self::new(limits) }
+    }
+
+    /// 进程级共享的调度器，限制持久化到应用数据目录。
+    pub fn global() -> Arc<Self> {
+        GLOBAL_SCHEDULER
+            .get_or_init(|| Arc::new(Self::open(get_app_data_dir().join(LIMITS_FILE))))
+            .clone()
+    }
+
+    /// 当前限制。
+    pub fn limits(&self) -> SchedulerLimits {
+        self.lock().limits
+    }
+
+    /// 修改限制并立即生效，返回收敛后的限制。
+    ///
+    /// 调低上限不会中断已在运行的任务或连接，之后的调度按新上限进行。
+    pub fn set_limits(&self, limits: SchedulerLimits) -> SchedulerLimits {
+        let limits = limits.normalized();
+        self.lock().limits = limits;
+        self.bandwidth.set_rate(limits.bandwidth_limit);
+        self.changed.notify_waiters();
+        limits
+    }
+
+    /// 将限制写回持久化路径；内存调度器直接返回。
+    pub async fn save(&self) -> Result<(), FsError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        write_json_atomic(path, &self.limits()).await
+    }
+
+    /// 任务在队列中的位置（从 1 开始）；未排队或已暂停时为 `None`。
+    pub fn queue_position(&self, id: Uuid) -> Option<usize> {
+        self.lock()
+            .schedulable()
+            .iter()
+            .position(|entry| entry.id == id)
+            .map(|index| index + 1)
+    }
+
+    /// 暂停或恢复排队中的任务；任务不在队列中时返回 `false`。
+    pub(crate) fn set_paused(&self, id: Uuid, paused: bool) -> bool {
+        let found = self
+            .lock()
+            .queue
+            .iter_mut()
+            .find(|entry| entry.id == id)
+            .map(|entry| entry.paused = paused)
+            .is_some();
+        self.changed.notify_waiters();
+        found
+    }
+
+    /// 修改排队中任务的优先级；任务不在队列中时返回 `false`。
+    pub(crate) fn set_priority(&self, id: Uuid, priority: DownloadPriority) -> bool {
+        let found = self
+            .lock()
+            .queue
+            .iter_mut()
+            .find(|entry| entry.id == id)
+            .map(|entry| entry.priority = priority)
+            .is_some();
+        self.changed.notify_waiters();
+        found
+    }
+
+    /// 让任务加入队列，返回用于等待运行名额的凭据。
+    pub(crate) fn enqueue(
+        self: &Arc<Self>,
+        id: Uuid,
+        priority: DownloadPriority,
+        paused: bool,
+    ) -> Ticket {
+        let mut state = self.lock();
+        let seq = state.next_seq;
+        state.next_seq += 1;
+        state.queue.push(Waiting { id, priority, seq, paused });
+        Ticket {
+            scheduler: Arc::clone(self),
+            id,
+            queued: true,
+        }
+    }
+
+    /// 等待一个连接名额；返回的凭据释放时归还。
+    pub(crate) async fn connection(self: &Arc<Self>) -> ConnectionPermit {
+        self.wait_until(|state| {
+            (state.connections < state.limits.max_connections).then(|| {
+                state.connections += 1;
+            })
+        })
+        .await;
+        ConnectionPermit { scheduler: Arc::clone(self) }
+    }
+
+    /// 按全局带宽限制取走 `bytes` 个令牌。
+    pub(crate) async fn throttle(&self, bytes: u64) {
+        self.bandwidth.acquire(bytes).await;
+    }
+
+    /// 反复检查状态直到 `check` 返回 `Some`，期间不会错过状态变化通知。
+    async fn wait_until<T>(&self, mut check: impl FnMut(&mut SchedulerState) -> Option<T>) -> T {
+        loop {
+            let notified = self.changed.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if let Some(value) = check(&mut self.lock()) {
+                return value;
+            }
+            notified.await;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, SchedulerState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// 排队凭据；未获得名额就被丢弃时自动退出队列。
+pub(crate) struct Ticket {
+    scheduler: Arc<DownloadScheduler>,
+    id: Uuid,
+    queued: bool,
+}
+
+impl Ticket {
+    /// 排在最前且有空闲名额时立即获得名额。
+    pub(crate) fn try_admit(&mut self) -> Option<TaskSlot> {
+        let admitted = self.check(&mut self.scheduler.lock());
+        admitted.then(|| self.admitted())
+    }
+
+    /// 等待轮到本任务并获得运行名额。
+    pub(crate) async fn admit(mut self) -> TaskSlot {
+        let scheduler = Arc::clone(&self.scheduler);
+        scheduler
+            .wait_until(|state| self.check(state).then_some(()))
+            .await;
+        self.admitted()
+    }
+
+    /// 在持锁状态下检查并占用名额。
+    fn check(&self, state: &mut SchedulerState) -> bool {
+        let first = state.schedulable().first().map(|entry| entry.id);
+        if first != Some(self.id) || state.active >= state.limits.max_active_tasks {
+            return false;
+        }
+        state.queue.retain(|entry| entry.id != self.id);
+        state.active += 1;
+        true
+    }
+
+    fn admitted(&mut self) -> TaskSlot {
+        self.queued = false;
+        // 队首变化，下一个任务可能也有名额。
+        self.scheduler.changed.notify_waiters();
+        TaskSlot { scheduler: Arc::clone(&self.scheduler) }
+    }
+}
+
+impl Drop for Ticket {
+    fn drop(&mut self) {
+        if self.queued {
+            self.scheduler
+                .lock()
+                .queue
+                .retain(|entry| entry.id != self.id);
+            self.scheduler.changed.notify_waiters();
+        }
+    }
+}
+
+/// 运行名额；释放时让出给下一个排队任务。
+pub(crate) struct TaskSlot {
+    scheduler: Arc<DownloadScheduler>,
+}
+
+impl Drop for TaskSlot {
+    fn drop(&mut self) {
+        self.scheduler.lock().active -= 1;
+        self.scheduler.changed.notify_waiters();
+    }
+}
+
+/// 连接名额；释放时归还。
+pub(crate) struct ConnectionPermit {
+    scheduler: Arc<DownloadScheduler>,
+}
+
+impl Drop for ConnectionPermit {
+    fn drop(&mut self) {
+        self.scheduler.lock().connections -= 1;
+        self.scheduler.changed.notify_waiters();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(max_active_tasks: usize, max_connections: usize) -> SchedulerLimits {
+        SchedulerLimits {
+            max_active_tasks,
+            max_connections,
+            bandwidth_limit: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn admits_by_priority_then_arrival_and_skips_paused_tasks() {
+        let scheduler = Arc::new(DownloadScheduler::new(limits(1, 4)));
+        let (first, low, normal, high, paused) =
+            (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+
+        let mut running = scheduler.enqueue(first, DownloadPriority::Low, false);
+        let slot = running.try_admit().expect("free slot");
+        let mut low = scheduler.enqueue(low, DownloadPriority::Low, false);
+        let normal_ticket = scheduler.enqueue(normal, DownloadPriority::Normal, false);
+        let high_ticket = scheduler.enqueue(high, DownloadPriority::High, false);
+        let _paused = scheduler.enqueue(paused, DownloadPriority::High, true);
+
+        assert!(low.try_admit().is_none());
+        assert_eq!(scheduler.queue_position(high), Some(1));
+        assert_eq!(scheduler.queue_position(normal), Some(2));
+        assert_eq!(scheduler.queue_position(paused), None);
+
+        let waiter = tokio::spawn(high_ticket.admit());
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+        drop(slot);
+        let high_slot = waiter.await.unwrap();
+        assert_eq!(scheduler.queue_position(normal), Some(1));
+
+        assert!(scheduler.set_priority(normal, DownloadPriority::Low));
+        assert_eq!(scheduler.queue_position(normal), Some(2));
+        drop(normal_ticket);
+        assert_eq!(scheduler.queue_position(normal), None);
+        assert!(!scheduler.set_paused(normal, true));
+
+        drop(high_slot);
+        assert!(low.try_admit().is_some());
+    }
+
+    #[tokio::test]
+    async fn caps_connections_across_tasks() {
+        let scheduler = Arc::new(DownloadScheduler::new(limits(3, 2)));
+        let first = scheduler.connection().await;
+        let _second = scheduler.connection().await;
+
+        let third = tokio::spawn({
+            let scheduler = Arc::clone(&scheduler);
+            async move { scheduler.connection().await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(!third.is_finished());
+
+        drop(first);
+        tokio::time::timeout(Duration::from_secs(1), third)
+            .await
+            .expect("released connection is handed over")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn token_bucket_spreads_bytes_over_time() {
+        let bucket = TokenBucket::new(Some(200 * 1024));
+        let started = Instant::now();
+        bucket.acquire(200 * 1024).await;
+        assert!(started.elapsed() < Duration::from_millis(100));
+        bucket.acquire(100 * 1024).await;
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(400), "waited only {elapsed:?}");
+        assert!(elapsed < Duration::from_millis(900), "waited {elapsed:?}");
+
+        bucket.set_rate(None);
+        let started = Instant::now();
+        bucket.acquire(u64::MAX / 2).await;
+        assert!(started.elapsed() < Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn persists_normalized_limits() {
+        let dir = std::env::temp_dir()
+            .join(format!("sealantern-infra-scheduler-{}", uuid::Uuid::new_v4()));
+        let path = dir.join(LIMITS_FILE);
+
+        let scheduler = DownloadScheduler::open(&path);
+        assert_eq!(scheduler.limits(), SchedulerLimits::default());
+        let applied = scheduler.set_limits(SchedulerLimits {
+            max_active_tasks: 0,
+            max_connections: 8,
+            bandwidth_limit: Some(0),
+        });
+        assert_eq!(applied, limits(1, 8));
+        scheduler.set_limits(SchedulerLimits {
+            bandwidth_limit: Some(1 << 20),
+            ..applied
+        });
+        scheduler.save().await.expect("save limits");
+
+        let reopened = DownloadScheduler::open(&path);
+        assert_eq!(reopened.limits().bandwidth_limit, Some(1 << 20));
+        assert_eq!(reopened.bandwidth.rate(), Some(1 << 20));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -3,18 +3,21 @@
 //! 提供流式文件下载和远程文本获取能力，不支持分块或预分配。
 //! 适用于小文件下载、API 请求等场景。
 
+use std::path::Path;
 use std::sync::Arc;
 
 use futures::StreamExt;
 use tokio::io::AsyncWriteExt;
 
+use crate::download::scheduler::DownloadScheduler;
 use crate::download::status::{DownloadError, DownloadStatus};
+use crate::download::verify::{DownloadIntegrity, finalize, partial_path};
 use crate::net::client::NetClient;
 use crate::observability;
 
 /// 使用单线程流式下载文件。
 ///
-/// 发送 GET 请求并将响应主体边读边写入文件。
+/// 发送 GET 请求并将响应主体边读边写入 `.part` 临时文件，结束后重命名为目标文件。
 /// 不分段、不预分配；适用于不支持 Range 的服务器或小文件。
 ///
 /// # Parameters
@@ -32,6 +35,37 @@ pub async fn stream_download(
     url: &str,
     output_path: &str,
 ) -> Result<Arc<DownloadStatus>, DownloadError> {
+    stream_download_verified(client, url, output_path, &DownloadIntegrity::default()).await
+}
+
+/// 使用单线程流式下载文件，并在重命名前按 `integrity` 校验大小与摘要。
+///
+/// 服务器声明的 `Content-Length` 与预期大小不符时不开始下载；
+/// 校验失败时删除临时文件并返回 [`DownloadError::SizeMismatch`] 或
+/// [`DownloadError::ChecksumMismatch`]，目标路径上已有的文件保持不变。
+pub async fn stream_download_verified(
+    client: &NetClient,
+    url: &str,
+    output_path: &str,
+    integrity: &DownloadIntegrity,
+) -> Result<Arc<DownloadStatus>, DownloadError> {
+    let status = Arc::new(DownloadStatus::new(0));
+    stream_into(client, url, output_path, integrity, &status, None).await?;
+    Ok(status)
+}
+
+/// 流式下载到给定的状态句柄，供调度中的任务使用。
+///
+/// 每次调用都从头下载并重置已下载字节数；传入 `scheduler` 时写入的数据
+/// 先经过任务与全局两级令牌桶限速。
+pub(super) async fn stream_into(
+    client: &NetClient,
+    url: &str,
+    output_path: &str,
+    integrity: &DownloadIntegrity,
+    status: &DownloadStatus,
+    scheduler: Option<&DownloadScheduler>,
+) -> Result<(), DownloadError> {
     tracing::info!(
         target: observability::DOWNLOAD_TARGET,
         event_name = observability::EVENT_DOWNLOAD_STARTED,
@@ -52,19 +86,34 @@ pub async fn stream_download(
     }
 
     let total_size = response.content_length().unwrap_or(0);
-    let status = Arc::new(DownloadStatus::new(total_size));
+    if total_size > 0
+        && let Err(error) = integrity.check_size(total_size)
+    {
+        observability::download_failed(url, &error);
+        return Err(error);
+    }
+    status.set_total_size(total_size);
+    status
+        .downloaded
+        .store(0, std::sync::atomic::Ordering::Relaxed);
 
-    let mut file = tokio::fs::File::create(output_path).await?;
+    let output = Path::new(output_path);
+    let partial = partial_path(output);
+    let mut file = tokio::fs::File::create(&partial).await?;
 
     let mut stream = response.bytes_stream();
     while let Some(item) = stream.next().await {
         if status.cancelled() {
-            let _ = tokio::fs::remove_file(output_path).await;
+            let _ = tokio::fs::remove_file(&partial).await;
             return Err(DownloadError::Cancelled("下载已取消".to_string()));
         }
 
         let chunk = item?;
         let len = chunk.len() as u64;
+        if let Some(scheduler) = scheduler {
+            status.bandwidth.acquire(len).await;
+            scheduler.throttle(len).await;
+        }
 
         file.write_all(&chunk).await?;
 
@@ -72,6 +121,10 @@ pub async fn stream_download(
             .downloaded
             .fetch_add(len, std::sync::atomic::Ordering::Relaxed);
     }
+    file.flush().await?;
+    drop(file);
+
+    finalize(url, &partial, output, integrity).await?;
 
     tracing::info!(
         target: observability::DOWNLOAD_TARGET,
@@ -82,7 +135,7 @@ pub async fn stream_download(
     );
     status.mark_completed();
 
-    Ok(status)
+    Ok(())
 }
 
 /// 获取远程文本内容。
@@ -3,11 +3,14 @@
 //! 提供下载任务的共享状态 `DownloadStatus`、进度快照 `DownloadSnapshot`
 //! 和统一的错误类型 `DownloadError`。
 
+use std::sync::Mutex;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 
-use tokio::sync::RwLock;
+use tokio::sync::{Notify, RwLock};
 use tokio_util::sync::CancellationToken;
 
+use crate::download::scheduler::{DownloadPriority, TokenBucket};
+use crate::fs::{FsError, HashAlgorithm};
 use crate::net::error::NetError;
 use crate::observability;
 
@@ -26,6 +29,107 @@ pub enum DownloadError {
     Cancelled(String),
     /// 其他错误信息
     Message(String),
+    /// 镜像长时间没有数据或吞吐量过低
+    Stalled(String),
+    /// 远端文件在下载过程中发生变化（`If-Range` 校验未通过）
+    RemoteChanged(String),
+    /// 下载文件的大小与预期不符（如镜像返回了截断的响应）
+    SizeMismatch {
+        /// 预期大小（字节）
+        expected: u64,
+        /// 实际大小（字节）
+        actual: u64,
+    },
+    /// 下载文件的摘要与预期不符
+    ChecksumMismatch {
+        /// 校验所用算法
+        algorithm: HashAlgorithm,
+        /// 预期摘要（小写十六进制）
+        expected: String,
+        /// 实际摘要（小写十六进制）
+        actual: String,
+    },
+    /// 校验时读取文件失败
+    Fs(FsError),
+    /// 保留类别与信息的下载失败（底层错误不可复制时交给等待方）
+    Failed {
+        /// 错误类别
+        kind: DownloadErrorKind,
+        /// 错误信息
+        message: String,
+    },
+}
+
+impl DownloadError {
+    /// 是否为完整性校验失败（大小或摘要不符）。
+    pub fn is_integrity_failure(&self) -> bool {
+        matches!(self, Self::SizeMismatch { .. } | Self::ChecksumMismatch { .. })
+    }
+
+    /// 错误所属的类别。
+    pub fn kind(&self) -> DownloadErrorKind {
+        match self {
+            Self::Reqwest(_) | Self::Response(..) | Self::Net(_) | Self::Stalled(_) => {
+                DownloadErrorKind::Network
+            }
+            Self::Io(_) | Self::Fs(_) => DownloadErrorKind::Storage,
+            Self::Cancelled(_) => DownloadErrorKind::Cancelled,
+            Self::RemoteChanged(_) => DownloadErrorKind::RemoteChanged,
+            Self::SizeMismatch { .. } => DownloadErrorKind::SizeMismatch,
+            Self::ChecksumMismatch { .. } => DownloadErrorKind::ChecksumMismatch,
+            Self::Message(_) => DownloadErrorKind::Other,
+            Self::Failed { kind, .. } => *kind,
+        }
+    }
+
+    /// 复制错误，供多个等待方共享同一任务的失败。
+    ///
+    /// 可复制的变体原样复制（大小与摘要不符保留预期值和实际值）；请求、网络与文件
+    /// 错误不可复制，以 [`DownloadError::Failed`] 保留类别与信息。
+    fn duplicate(&self) -> Self {
+        match self {
+            Self::Io(e) => Self::Io(std::io::Error::new(e.kind(), e.to_string())),
+            Self::Response(code, body) => Self::Response(*code, body.clone()),
+            Self::Cancelled(msg) => Self::Cancelled(msg.clone()),
+            Self::Message(msg) => Self::Message(msg.clone()),
+            Self::Stalled(url) => Self::Stalled(url.clone()),
+            Self::RemoteChanged(url) => Self::RemoteChanged(url.clone()),
+            Self::SizeMismatch { expected, actual } => {
+                Self::SizeMismatch { expected: *expected, actual: *actual }
+            }
+            Self::ChecksumMismatch { algorithm, expected, actual } => Self::ChecksumMismatch {
+                algorithm: *algorithm,
+                expected: expected.clone(),
+                actual: actual.clone(),
+            },
+            Self::Failed { kind, message } => {
+                Self::Failed { kind: *kind, message: message.clone() }
+            }
+            Self::Reqwest(_) | Self::Net(_) | Self::Fs(_) => Self::Failed {
+                kind: self.kind(),
+                message: self.to_string(),
+            },
+        }
+    }
+}
+
+/// 下载失败的类别，随错误信息一起保留在任务状态中。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadErrorKind {
+    /// 请求失败、服务端返回错误状态或下载停滞
+    Network,
+    /// 本地文件读写失败
+    Storage,
+    /// 下载被取消
+    Cancelled,
+    /// 远端文件在下载过程中发生变化
+    RemoteChanged,
+    /// 文件大小与预期不符
+    SizeMismatch,
+    /// 文件摘要与预期不符
+    ChecksumMismatch,
+    /// 其他错误
+    Other,
 }
 
 impl std::fmt::Display for DownloadError {
@@ -37,6 +141,16 @@ impl std::fmt::Display for DownloadError {
             DownloadError::Net(e) => write!(f, "网络错误: {}", e),
             DownloadError::Cancelled(msg) => write!(f, "下载取消: {}", msg),
             DownloadError::Message(msg) => write!(f, "{}", msg),
+            DownloadError::Stalled(url) => write!(f, "下载停滞: {}", url),
+            DownloadError::RemoteChanged(url) => write!(f, "远端文件已变化: {}", url),
+            DownloadError::SizeMismatch { expected, actual } => {
+                write!(f, "文件大小校验失败: 预期 {} 字节，实际 {} 字节", expected, actual)
+            }
+            DownloadError::ChecksumMismatch { algorithm, expected, actual } => {
+                write!(f, "{} 校验失败: 预期 {}，实际 {}", algorithm.name(), expected, actual)
+            }
+            DownloadError::Fs(e) => write!(f, "文件错误: {}", e),
+            DownloadError::Failed { message, .. } => write!(f, "{}", message),
         }
     }
 }
@@ -73,26 +187,49 @@ pub struct DownloadSnapshot {
     pub is_finished: bool,
     /// 错误信息（取消时包含取消消息）
     pub error: Option<String>,
+    /// 错误类别，与 `error` 同时出现
+    pub error_kind: Option<DownloadErrorKind>,
+    /// 是否已暂停
+    pub paused: bool,
+    /// 在全局下载队列中的位置（从 1 开始）；运行中、暂停或已结束时为 `None`
+    pub queue_position: Option<usize>,
 }
 
 /// 单个下载任务的共享状态。
 ///
 /// 使用 `AtomicU64` 跟踪已下载字节数，多个分段线程可安全地并发累加。
-/// 通过 `CancellationToken` 实现取消信号。
+/// 通过 `CancellationToken` 实现取消信号；每次运行（首次启动或暂停后恢复）
+/// 使用取消令牌的子令牌，暂停只停止当前运行而不取消任务。
 ///
 /// 字段在 `download` 模块内可见（由分段层和传输层直接访问），
 /// 外部代码通过 `snapshot()` / `cancel()` 等公共方法交互。
 pub struct DownloadStatus {
-    /// 文件总大小
-    pub(super) total_size: u64,
+    /// 文件总大小（排队中的任务在探测后才知道）
+    pub(super) total_size: AtomicU64,
     /// 已下载的字节数（原子计数器，线程安全）
     pub(super) downloaded: AtomicU64,
     /// 是否已下载完成（下载主体结束后置位，与 total_size 无关）
     pub(super) completed: AtomicBool,
-    /// 错误信息
-    pub(super) error_message: RwLock<Option<String>>,
+    /// 错误类别与错误信息
+    pub(super) error_message: RwLock<Option<(DownloadErrorKind, String)>>,
+    /// 结束任务的下载错误，每个等待任务结束的调用方得到它的副本
+    pub(super) failure: Mutex<Option<DownloadError>>,
+    /// 任务结束或暂停时唤醒 [`Self::wait`]
+    pub(super) settled: Notify,
     /// 取消令牌
     pub(super) cancel_token: CancellationToken,
+    /// 当前运行的停止令牌（取消令牌的子令牌）
+    pub(super) run: Mutex<CancellationToken>,
+    /// 是否已暂停
+    pub(super) paused: AtomicBool,
+    /// 暂停状态变化时唤醒等待恢复的调度任务
+    pub(super) resumed: Notify,
+    /// 运行中暂停后能否从已落盘位置继续（远端支持 Range）
+    pub(super) resumable: AtomicBool,
+    /// 调度优先级
+    pub(super) priority: Mutex<DownloadPriority>,
+    /// 任务自身的带宽限制
+    pub(super) bandwidth: TokenBucket,
 }
 
 impl DownloadStatus {
@@ -102,24 +239,40 @@ impl DownloadStatus {
     ///
     /// - `total_size`: 文件总大小
     pub fn new(total_size: u64) -> Self {
+        let cancel_token = CancellationToken::new();
         Self {
-            total_size,
+            total_size: AtomicU64::new(total_size),
             downloaded: AtomicU64::new(0),
             completed: AtomicBool::new(false),
             error_message: RwLock::new(None),
-            cancel_token: CancellationToken::new(),
+            failure: Mutex::new(None),
+            settled: Notify::new(),
+            run: Mutex::new(cancel_token.child_token()),
+            cancel_token,
+            paused: AtomicBool::new(false),
+            resumed: Notify::new(),
+            resumable: AtomicBool::new(false),
+            priority: Mutex::new(DownloadPriority::default()),
+            bandwidth: TokenBucket::new(None),
         }
     }
 
+    /// 设置文件总大小（探测到远端文件信息后调用）。
+    pub(super) fn set_total_size(&self, total_size: u64) {
+        self.total_size.store(total_size, Ordering::Relaxed);
+    }
+
     /// 标记下载已完成（下载主体结束后调用，供进度查询判定）。
     pub fn mark_completed(&self) {
         self.completed.store(true, Ordering::Relaxed);
+        self.settled.notify_waiters();
     }
 
     /// 取消下载。
     pub fn cancel(&self) {
         observability::download_cancelled();
         self.cancel_token.cancel();
+        self.settled.notify_waiters();
     }
 
     /// 检查下载是否已被取消。
@@ -127,15 +280,155 @@ impl DownloadStatus {
         self.cancel_token.is_cancelled()
     }
 
-    /// 设置错误信息。
+    /// 开始一次新的运行，返回本次运行的停止令牌。
+    ///
+    /// 任务已暂停时返回已停止的令牌，本次运行会立即保存进度并结束。
+    /// 不可续传的任务不能在运行中暂停，开始运行时清除暂停标记。
+    pub(super) fn begin_run(&self) -> CancellationToken {
+        let stop = self.cancel_token.child_token();
+        if self.is_paused() {
+            if self.resumable.load(Ordering::Relaxed) {
+                stop.cancel();
+            } else {
+                self.unpause();
+            }
+        }
+        *self.lock_run() = stop.clone();
+        stop
+    }
+
+    /// 暂停：停止当前运行，已落盘的进度保留。
+    pub(super) fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+        self.lock_run().cancel();
+        self.resumed.notify_waiters();
+        self.settled.notify_waiters();
+    }
+
+    /// 取消暂停并唤醒等待恢复的调度任务。
+    pub(super) fn unpause(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+        self.resumed.notify_waiters();
+    }
+
+    /// 是否已暂停。
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// 等待任务恢复；任务被取消时返回 `false`。
+    pub(super) async fn wait_unpaused(&self) -> bool {
+        loop {
+            let notified = self.resumed.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.cancelled() {
+                return false;
+            }
+            if !self.is_paused() {
+                return true;
+            }
+            tokio::select! {
+                _ = notified => {}
+                _ = self.cancel_token.cancelled() => return false,
+            }
+        }
+    }
+
+    /// 调度优先级。
+    pub fn priority(&self) -> DownloadPriority {
+        *self
+            .priority
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// 修改调度优先级（下次排队时生效）。
+    pub(super) fn set_priority(&self, priority: DownloadPriority) {
+        *self
+            .priority
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = priority;
+    }
+
+    /// 任务自身的带宽限制（字节/秒）。
+    pub fn bandwidth_limit(&self) -> Option<u64> {
+        self.bandwidth.rate()
+    }
+
+    /// 修改任务自身的带宽限制，立即生效；`None` 或 0 表示不限速。
+    pub fn set_bandwidth_limit(&self, limit: Option<u64>) {
+        self.bandwidth.set_rate(limit);
+    }
+
+    fn lock_run(&self) -> std::sync::MutexGuard<'_, CancellationToken> {
+        self.run
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// 设置错误信息，类别记为 [`DownloadErrorKind::Other`]。
     ///
     /// # Parameters
     ///
     /// - `msg`: 错误描述
     pub async fn set_error(&self, msg: String) {
+        self.record_error(DownloadErrorKind::Other, msg).await;
+    }
+
+    /// 以下载错误结束任务，保留错误类别（如大小或摘要不符）。
+    pub async fn fail(&self, error: DownloadError) {
+        let (kind, message) = (error.kind(), error.to_string());
+        // 先保存原始错误：等待方看到任务结束时总能取到它。
+        *self
+            .failure
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(error);
+        self.record_error(kind, message).await;
+    }
+
+    /// 等待任务结束。
+    ///
+    /// 任务以错误结束时返回该错误：每个调用方都得到 [`Self::fail`] 记录的错误的副本
+    /// （类别相同），只有错误信息的任务返回 [`DownloadError::Message`]；任务被取消时
+    /// 返回 [`DownloadError::Cancelled`]。
+    ///
+    /// 任务被暂停时同样以 [`DownloadError::Cancelled`] 返回，不等待恢复：暂停只能由
+    /// 用户发起，等待方（如安装流程）不应无限期挂起；任务本身保留在下载列表中，
+    /// 恢复后照常下载完成。
+    pub async fn wait(&self) -> Result<(), DownloadError> {
+        loop {
+            let settled = self.settled.notified();
+            tokio::pin!(settled);
+            settled.as_mut().enable();
+            let snapshot = self.snapshot().await;
+            if snapshot.is_finished {
+                let Some(message) = snapshot.error else {
+                    return Ok(());
+                };
+                let recorded = self
+                    .failure
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .as_ref()
+                    .map(DownloadError::duplicate);
+                return Err(match (recorded, snapshot.error_kind) {
+                    (Some(error), _) => error,
+                    (None, Some(DownloadErrorKind::Cancelled)) => DownloadError::Cancelled(message),
+                    (None, _) => DownloadError::Message(message),
+                });
+            }
+            if snapshot.paused {
+                return Err(DownloadError::Cancelled("下载已暂停".to_string()));
+            }
+            settled.await;
+        }
+    }
+
+    async fn record_error(&self, kind: DownloadErrorKind, msg: String) {
         observability::download_error(&msg);
-        let mut lock = self.error_message.write().await;
-        *lock = Some(msg);
+        *self.error_message.write().await = Some((kind, msg));
+        self.settled.notify_waiters();
     }
 
     /// 获取当前进度快照。
@@ -144,15 +437,16 @@ impl DownloadStatus {
     /// 确保前端可以通过 `is_finished` 检测到已取消的状态。
     pub async fn snapshot(&self) -> DownloadSnapshot {
         let downloaded = self.downloaded.load(Ordering::Relaxed);
+        let total_size = self.total_size.load(Ordering::Relaxed);
         let error = self.error_message.read().await.clone();
 
         let is_cancelled = self.cancelled() && error.is_none();
 
         DownloadSnapshot {
             downloaded,
-            total_size: self.total_size,
-            progress_percentage: if self.total_size > 0 {
-                (downloaded as f64 / self.total_size as f64) * 100.0
+            total_size,
+            progress_percentage: if total_size > 0 {
+                (downloaded as f64 / total_size as f64) * 100.0
             } else {
                 // 未知总大小时：已完成显示 100，否则显示 0（避免瞬时误判完成）。
                 if self.completed.load(Ordering::Relaxed) {
@@ -162,17 +456,26 @@ impl DownloadStatus {
                 }
             },
             is_finished: self.completed.load(Ordering::Relaxed) || error.is_some() || is_cancelled,
+            error_kind: if is_cancelled {
+                Some(DownloadErrorKind::Cancelled)
+            } else {
+                error.as_ref().map(|(kind, _)| *kind)
+            },
             error: if is_cancelled {
                 Some("下载已取消".to_string())
             } else {
-                error
+                error.map(|(_, message)| message)
             },
+            paused: self.is_paused(),
+            queue_position: None,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use super::*;
 
     #[tokio::test]
@@ -199,9 +502,81 @@ mod tests {
         status.cancel();
         let snap = status.snapshot().await;
         assert!(snap.is_finished);
+        assert_eq!(snap.error_kind, Some(DownloadErrorKind::Cancelled));
         assert_eq!(snap.error.unwrap(), "下载已取消");
     }
 
+    #[tokio::test]
+    async fn failure_keeps_the_error_kind() {
+        let status = DownloadStatus::new(100);
+        status
+            .fail(DownloadError::SizeMismatch { expected: 100, actual: 40 })
+            .await;
+        let snap = status.snapshot().await;
+        assert!(snap.is_finished);
+        assert_eq!(snap.error_kind, Some(DownloadErrorKind::SizeMismatch));
+        assert!(snap.error.unwrap().contains("40"));
+    }
+
+    #[tokio::test]
+    async fn wait_returns_the_recorded_failure() {
+        let status = Arc::new(DownloadStatus::new(100));
+        let waiter = tokio::spawn({
+            let status = Arc::clone(&status);
+            async move { status.wait().await }
+        });
+        status
+            .fail(DownloadError::SizeMismatch { expected: 100, actual: 40 })
+            .await;
+
+        let error = waiter.await.unwrap().unwrap_err();
+        assert!(matches!(error, DownloadError::SizeMismatch { expected: 100, actual: 40 }));
+        // 之后的等待方得到同样类别的错误。
+        assert!(matches!(
+            status.wait().await,
+            Err(DownloadError::SizeMismatch { expected: 100, actual: 40 })
+        ));
+
+        let network = DownloadStatus::new(100);
+        network
+            .fail(DownloadError::Net(NetError::Request("connection refused".to_string())))
+            .await;
+        for _ in 0..2 {
+            let error = network.wait().await.unwrap_err();
+            assert_eq!(error.kind(), DownloadErrorKind::Network);
+        }
+
+        let cancelled = DownloadStatus::new(100);
+        cancelled.cancel();
+        assert!(matches!(cancelled.wait().await, Err(DownloadError::Cancelled(_))));
+        let completed = DownloadStatus::new(100);
+        completed.mark_completed();
+        assert!(completed.wait().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn wait_wakes_on_completion_and_pause() {
+        let status = Arc::new(DownloadStatus::new(100));
+        let waiter = tokio::spawn({
+            let status = Arc::clone(&status);
+            async move { status.wait().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+        status.mark_completed();
+        assert!(waiter.await.unwrap().is_ok());
+
+        // 暂停的任务不会让等待方挂起。
+        let paused = Arc::new(DownloadStatus::new(100));
+        let waiter = tokio::spawn({
+            let paused = Arc::clone(&paused);
+            async move { paused.wait().await }
+        });
+        tokio::task::yield_now().await;
+        paused.pause();
+        assert!(matches!(waiter.await.unwrap(), Err(DownloadError::Cancelled(_))));
+    }
+
     #[tokio::test]
     async fn snapshot_partial_download_then_cancel() {
         let status = DownloadStatus::new(1000);
@@ -215,4 +590,35 @@ mod tests {
         assert_eq!(snap_after.error.unwrap(), "下载已取消");
         assert_eq!(snap_after.downloaded, 300);
     }
+
+    #[tokio::test]
+    async fn pause_stops_the_current_run_only() {
+        let status = Arc::new(DownloadStatus::new(100));
+        status.pause();
+        assert!(!status.begin_run().is_cancelled(), "不可续传的任务不能在运行中暂停");
+        assert!(!status.is_paused());
+
+        status.resumable.store(true, Ordering::Relaxed);
+        let first = status.begin_run();
+        status.pause();
+        assert!(first.is_cancelled());
+        assert!(!status.cancelled());
+        assert!(status.begin_run().is_cancelled());
+
+        let waiter = tokio::spawn({
+            let status = Arc::clone(&status);
+            async move { status.wait_unpaused().await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!waiter.is_finished());
+        status.unpause();
+        assert!(waiter.await.unwrap());
+        assert!(!status.begin_run().is_cancelled());
+
+        let snap = status.snapshot().await;
+        assert!(!snap.paused && !snap.is_finished);
+        status.pause();
+        status.cancel();
+        assert!(!status.wait_unpaused().await);
+    }
 }
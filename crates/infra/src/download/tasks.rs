@@ -1,14 +1,139 @@
 //! 分块任务管理。
 //!
 //! 负责将文件按线程数拆分为多个块，生成下载任务，并启动后台监控。
+//! 可续传的任务由监控任务定期把各块进度写入续传记录。
+//! 同一个任务上下文可以多次运行：暂停停止当前运行，恢复时只为未完成的块重新生成任务。
 
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
 
 use crate::download::chunk::download_chunk;
+use crate::download::mirror::{FailoverPolicy, MirrorHealth};
+use crate::download::resume::{ChunkProgress, RemoteValidator, ResumeRecord, ResumeStore};
+use crate::download::scheduler::DownloadScheduler;
 use crate::download::status::{DownloadError, DownloadStatus};
+use crate::download::verify::{DownloadIntegrity, finalize};
 use crate::net::client::NetClient;
 use crate::observability;
 
+/// 可续传任务保存进度的间隔。
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(2);
+
+/// 一次运行的结束方式。
+pub(super) enum RunOutcome {
+    /// 完成、出错、取消或暂停，结果已写入 `DownloadStatus`
+    Ended,
+    /// 下载途中远端文件已变化：已下载的内容作废，需要重新探测后从头下载
+    RemoteChanged(DownloadError),
+}
+
+/// 全部块任务结束后的汇总结果。
+enum Joined {
+    /// 全部成功，或本次运行被停止
+    Done,
+    /// 有块失败，错误已写入 `DownloadStatus`
+    Failed,
+    /// 有块发现远端文件已变化，其余块已中止
+    RemoteChanged(DownloadError),
+}
+
+/// 一次分段下载的共享上下文，由各块任务与监控任务共同持有。
+pub(super) struct DownloadJob {
+    /// 任务 ID（续传记录以此命名）
+    pub id: Uuid,
+    /// 配置好的 HTTP 客户端
+    pub client: NetClient,
+    /// 候选地址，已按镜像健康度排序且非空；首个为探测成功的地址
+    pub candidates: Vec<String>,
+    /// 各块写入的临时文件
+    pub partial: PathBuf,
+    /// 校验通过后的目标路径
+    pub output_path: String,
+    /// 文件总大小
+    pub total_size: u64,
+    /// 下载线程数
+    pub thread_count: usize,
+    /// 预期大小与摘要
+    pub integrity: DownloadIntegrity,
+    /// 各块进度，块任务每次落盘后推进
+    pub chunks: Mutex<Vec<ChunkProgress>>,
+    /// 远端校验头；存在时分块请求携带 `If-Range`（仅远端支持 Range 时）
+    pub validator: Option<RemoteValidator>,
+    /// 续传记录存储；为 `None` 时任务不可续传
+    pub store: Option<Arc<ResumeStore>>,
+    /// 镜像健康统计
+    pub health: Arc<MirrorHealth>,
+    /// 故障转移策略
+    pub policy: FailoverPolicy,
+    /// 全局调度器（连接数与带宽限制）
+    pub scheduler: Arc<DownloadScheduler>,
+}
+
+impl DownloadJob {
+    /// 日志使用的下载地址（首个候选地址）。
+    pub fn url(&self) -> &str {
+        &self.candidates[0]
+    }
+
+    /// 指定块的当前进度。
+    pub fn chunk(&self, index: usize) -> ChunkProgress {
+        self.lock_chunks()[index]
+    }
+
+    /// 推进指定块的已落盘位置。
+    pub fn advance(&self, index: usize, offset: u64) {
+        self.lock_chunks()[index].offset = offset;
+    }
+
+    /// 全部块已落盘的字节数。
+    pub fn downloaded(&self) -> u64 {
+        self.lock_chunks().iter().map(ChunkProgress::written).sum()
+    }
+
+    /// 将当前进度写入续传记录；不可续传的任务什么也不做。
+    pub async fn checkpoint(&self) {
+        let (Some(store), Some(validator)) = (&self.store, &self.validator) else {
+            return;
+        };
+        let record = ResumeRecord {
+            id: self.id,
+            candidates: self.candidates.clone(),
+            output_path: self.output_path.clone(),
+            thread_count: self.thread_count,
+            total_size: self.total_size,
+            integrity: self.integrity.clone(),
+            validator: validator.clone(),
+            chunks: self.lock_chunks().clone(),
+            updated_at_unix_secs: 0,
+        };
+        store.save_or_log(&record).await;
+    }
+
+    /// 删除续传记录（任务完成、取消或校验失败后不再续传）。
+    pub async fn forget(&self) {
+        if let Some(store) = &self.store {
+            store.remove(self.id).await;
+        }
+    }
+
+    /// 删除续传记录与临时文件（任务被取消）。
+    pub async fn discard(&self) {
+        self.forget().await;
+        let _ = tokio::fs::remove_file(&self.partial).await;
+    }
+
+    fn lock_chunks(&self) -> std::sync::MutexGuard<'_, Vec<ChunkProgress>> {
+        self.chunks
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
 /// 将文件拆分为多个块范围。
 ///
 /// # Parameters
@@ -41,42 +166,40 @@ pub(super) fn split_ranges(total_size: u64, thread_count: usize) -> Vec<(u64, u6
     ranges
 }
 
-/// 生成所有块下载任务。
+/// 生成所有未完成块的下载任务。
 ///
-/// 每个块生成一个 tokio 任务，所有任务共享同一个 `DownloadStatus`。
+/// 每个块生成一个 tokio 任务，所有任务共享同一个 `DownloadJob` 与 `DownloadStatus`。
+/// 续传或暂停后恢复时已下载完毕的块被跳过。
 ///
 /// # Parameters
 ///
-/// - `client`: 配置好的 HTTP 客户端（克隆后在任务间共享）
-/// - `url`: 下载地址
-/// - `path`: 本地保存路径
-/// - `thread_count`: 块数量
-/// - `total_size`: 文件总大小
+/// - `job`: 下载任务上下文
 /// - `status`: 共享下载状态
+/// - `stop`: 本次运行的停止令牌（取消或暂停时触发）
 ///
 /// # Returns
 ///
 /// 返回所有块任务的 `JoinHandle` 列表。
 pub(super) fn spawn_download_tasks(
-    client: NetClient,
-    url: String,
-    path: String,
-    thread_count: usize,
-    total_size: u64,
+    job: &Arc<DownloadJob>,
     status: &Arc<DownloadStatus>,
-) -> Vec<tokio::task::JoinHandle<Result<(), DownloadError>>> {
-    let ranges = split_ranges(total_size, thread_count);
-    let mut tasks = Vec::with_capacity(ranges.len());
-
-    for (start, end) in ranges {
-        let client = client.clone();
-        let url = url.clone();
-        let path = path.clone();
+    stop: &CancellationToken,
+) -> Vec<JoinHandle<Result<(), DownloadError>>> {
+    let pending: Vec<usize> = job
+        .lock_chunks()
+        .iter()
+        .enumerate()
+        .filter(|(_, chunk)| !chunk.is_complete())
+        .map(|(index, _)| index)
+        .collect();
+    let mut tasks = Vec::with_capacity(pending.len());
+
+    for index in pending {
+        let job = Arc::clone(job);
         let status = Arc::clone(status);
+        let stop = stop.clone();
 
-        tasks.push(tokio::spawn(async move {
-            download_chunk(&client, &url, &path, start, end, status).await
-        }));
+        tasks.push(tokio::spawn(async move { download_chunk(&job, index, status, stop).await }));
     }
 
     tasks
@@ -84,59 +207,134 @@ pub(super) fn spawn_download_tasks(
 
 /// 启动后台监控任务。
 ///
-/// 在后台等待所有块任务完成，将错误汇总到 `DownloadStatus` 中。
-/// 当所有块成功时，调用 `download_completed()` 记录完成事件。
+/// 在后台等待所有块任务完成，将错误汇总到 `DownloadStatus` 中；等待期间每隔
+/// [`CHECKPOINT_INTERVAL`] 保存一次续传记录。当所有块成功时，校验临时文件并
+/// 重命名为目标文件，再调用 `download_completed()` 记录完成事件。无论结果如何，
+/// 结束时都会保存镜像健康统计。
+///
+/// 结束后的续传记录：完成、校验失败或取消时删除（取消还会删除临时文件）；
+/// 暂停与其他错误保存最终进度，供之后续传。远端文件在下载途中变化时各块进度
+/// 已不可信，删除续传记录且不写入错误，由调用方重新准备任务后从头下载。
 ///
 /// # Parameters
 ///
 /// - `tasks`: 块任务的 `JoinHandle` 列表
+/// - `job`: 下载任务上下文
 /// - `status`: 共享下载状态
-/// - `url`: 下载地址（用于完成事件日志）
-/// - `total_size`: 文件总大小（用于完成事件日志）
+/// - `stop`: 本次运行的停止令牌
+///
+/// # Returns
+///
+/// 返回监控任务的 `JoinHandle`，本次运行结束（包括暂停）后完成并给出结束方式。
 pub(super) fn spawn_task_monitor(
-    tasks: Vec<tokio::task::JoinHandle<Result<(), DownloadError>>>,
+    tasks: Vec<JoinHandle<Result<(), DownloadError>>>,
+    job: Arc<DownloadJob>,
     status: &Arc<DownloadStatus>,
-    url: String,
-    total_size: u64,
-) {
+    stop: CancellationToken,
+) -> JoinHandle<RunOutcome> {
     let status = Arc::clone(status);
     tokio::spawn(async move {
         let start = std::time::Instant::now();
-        let mut has_error = false;
-
-        for task in tasks {
-            match task.await {
-                Ok(Ok(_)) => {}
-                Ok(Err(err)) => {
-                    has_error = true;
-                    // chunk_failed() 已经在 error 级别记录了此错误；
-                    // 此处仅传播到 DownloadStatus，避免重复日志。
-                    if !status.cancelled() {
-                        status.set_error(err.to_string()).await;
-                    }
+
+        let joined = join_tasks(tasks, &status, &stop);
+        tokio::pin!(joined);
+        let mut checkpoints = tokio::time::interval(CHECKPOINT_INTERVAL);
+        checkpoints.tick().await;
+        let joined = loop {
+            tokio::select! {
+                joined = &mut joined => break joined,
+                _ = checkpoints.tick() => job.checkpoint().await,
+            }
+        };
+
+        let finalized = match joined {
+            Joined::RemoteChanged(error) => {
+                job.forget().await;
+                job.health.save_or_log().await;
+                return RunOutcome::RemoteChanged(error);
+            }
+            Joined::Failed => None,
+            Joined::Done if stop.is_cancelled() => None,
+            Joined::Done => Some(
+                finalize(job.url(), &job.partial, Path::new(&job.output_path), &job.integrity)
+                    .await,
+            ),
+        };
+
+        job.health.save_or_log().await;
+
+        match finalized {
+            Some(Ok(())) => {
+                job.forget().await;
+                let elapsed = start.elapsed().as_millis() as u64;
+                observability::download_completed(job.url(), job.total_size, elapsed);
+                // 全部块成功：标记完成，供进度查询判定（不依赖 total_size 比较）。
+                status.mark_completed();
+            }
+            Some(Err(err)) => {
+                // 校验失败时临时文件已被删除，续传没有意义。
+                job.forget().await;
+                status.fail(err).await;
+            }
+            None if status.cancelled() => job.discard().await,
+            None => job.checkpoint().await,
+        }
+        RunOutcome::Ended
+    })
+}
+
+/// 等待全部块任务结束并汇总结果。
+///
+/// 取消或暂停导致的块失败不写入错误信息。某个块发现远端文件已变化且此前没有
+/// 其他块失败时，中止其余块（它们的请求携带同一个过期的 `If-Range`）。
+async fn join_tasks(
+    tasks: Vec<JoinHandle<Result<(), DownloadError>>>,
+    status: &DownloadStatus,
+    stop: &CancellationToken,
+) -> Joined {
+    let mut has_error = false;
+    let mut tasks = tasks.into_iter();
+
+    while let Some(task) = tasks.next() {
+        match task.await {
+            Ok(Ok(_)) => {}
+            Ok(Err(err @ DownloadError::RemoteChanged(_)))
+                if !has_error && !stop.is_cancelled() =>
+            {
+                for rest in tasks {
+                    rest.abort();
+                    let _ = rest.await;
                 }
-                Err(err) => {
-                    has_error = true;
-                    tracing::error!(
-                        target: observability::DOWNLOAD_TARGET,
-                        event_name = "chunk_thread_crashed",
-                        error = %err,
-                        "chunk thread crashed"
-                    );
-                    status
-                        .set_error(format!("chunk thread crashed: {}", err))
-                        .await;
+                return Joined::RemoteChanged(err);
+            }
+            Ok(Err(err)) => {
+                has_error = true;
+                // chunk_failed() 已经在 error 级别记录了此错误；
+                // 此处仅传播到 DownloadStatus，避免重复日志。
+                if !stop.is_cancelled() {
+                    status.fail(err).await;
                 }
             }
+            Err(err) => {
+                has_error = true;
+                tracing::error!(
+                    target: observability::DOWNLOAD_TARGET,
+                    event_name = "chunk_thread_crashed",
+                    error = %err,
+                    "chunk thread crashed"
+                );
+                status
+                    .set_error(format!("chunk thread crashed: {}", err))
+                    .await;
+            }
         }
+    }
 
-        if !has_error && !status.cancelled() {
-            let elapsed = start.elapsed().as_millis() as u64;
-            observability::download_completed(&url, total_size, elapsed);
-            // 全部块成功：标记完成，供进度查询判定（不依赖 total_size 比较）。
-            status.mark_completed();
-        }
-    });
+    if has_error {
+        Joined::Failed
+    } else {
+        Joined::Done
+    }
 }
 
 #[cfg(test)]
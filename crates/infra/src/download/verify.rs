@@ -0,0 +1,153 @@
+//! 下载完整性校验。
+//!
+//! 下载先写入同目录的 `.part` 临时文件，主体结束后按 [`DownloadIntegrity`]
+//! 校验大小与摘要，全部通过才重命名为目标文件；校验失败时删除临时文件，
+//! 目标路径上已有的文件保持不变。
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::download::status::DownloadError;
+use crate::fs::{HashAlgorithm, file_digest_hex, file_size};
+
+/// 下载文件的预期大小与摘要，均可省略。
+///
+/// 摘要为十六进制字符串，比较时忽略大小写；提供多个摘要时逐一校验。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DownloadIntegrity {
+    /// 预期文件大小（字节）。
+    pub size: Option<u64>,
+    /// 预期 SHA-1 摘要。
+    pub sha1: Option<String>,
+    /// 预期 SHA-256 摘要。
+    pub sha256: Option<String>,
+    /// 预期 SHA-512 摘要。
+    pub sha512: Option<String>,
+}
+
+impl DownloadIntegrity {
+    /// 是否没有任何预期值（无需校验）。
+    pub fn is_empty(&self) -> bool {
+        self.size.is_none() && self.sha1.is_none() && self.sha256.is_none() && self.sha512.is_none()
+    }
+
+    /// 指定算法的预期摘要。
+    pub fn digest(&self, algorithm: HashAlgorithm) -> Option<&str> {
+        match algorithm {
+            HashAlgorithm::Sha1 => self.sha1.as_deref(),
+            HashAlgorithm::Sha256 => self.sha256.as_deref(),
+            HashAlgorithm::Sha512 => self.sha512.as_deref(),
+        }
+    }
+
+    /// 比较预期大小与实际大小；未提供预期大小时总是通过。
+    pub fn check_size(&self, actual: u64) -> Result<(), DownloadError> {
+        match self.size {
+            Some(expected) if expected != actual => {
+                Err(DownloadError::SizeMismatch { expected, actual })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// 校验文件的大小与全部已提供的摘要（由强到弱）。
+    pub async fn verify(&self, path: &Path) -> Result<(), DownloadError> {
+        if self.size.is_some() {
+            self.check_size(file_size(path).await.map_err(DownloadError::Fs)?)?;
+        }
+        for algorithm in HashAlgorithm::STRONGEST_FIRST {
+            let Some(expected) = self.digest(algorithm) else {
+                continue;
+            };
+            let actual = file_digest_hex(path, algorithm)
+                .await
+                .map_err(DownloadError::Fs)?;
+            if !actual.eq_ignore_ascii_case(expected.trim()) {
+                return Err(DownloadError::ChecksumMismatch {
+                    algorithm,
+                    expected: expected.trim().to_ascii_lowercase(),
+                    actual,
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// 下载进行中使用的临时文件路径（`<目标文件名>.part`）。
+pub fn partial_path(output_path: &Path) -> PathBuf {
+    let mut name = output_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    output_path.with_file_name(name)
+}
+
+/// 校验临时文件并重命名为目标文件；校验失败时删除临时文件。
+pub(super) async fn finalize(
+    url: &str,
+    partial: &Path,
+    output_path: &Path,
+    integrity: &DownloadIntegrity,
+) -> Result<(), DownloadError> {
+    if let Err(error) = integrity.verify(partial).await {
+        crate::observability::download_failed(url, &error);
+        let _ = tokio::fs::remove_file(partial).await;
+        return Err(error);
+    }
+    tokio::fs::rename(partial, output_path).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fs::test_dir;
+
+    #[tokio::test]
+    async fn renames_only_after_every_expectation_holds() {
+        let dir = test_dir("verify");
+        let output = dir.join("server.jar");
+        let partial = partial_path(&output);
+        assert_eq!(partial, dir.join("server.jar.part"));
+        std::fs::write(&output, b"old").unwrap();
+
+        std::fs::write(&partial, b"abc").unwrap();
+        let wrong_digest = DownloadIntegrity {
+            size: Some(3),
+            sha1: Some("a9993e364706816aba3e25717850c26c9cd0d89d".into()),
+            sha256: Some("00".repeat(32)),
+            sha512: None,
+        };
+        let error =
+            finalize("https://example.invalid/server.jar", &partial, &output, &wrong_digest)
+                .await
+                .expect_err("sha256 mismatch must fail");
+        assert!(matches!(
+            error,
+            DownloadError::ChecksumMismatch { algorithm: HashAlgorithm::Sha256, .. }
+        ));
+        assert!(!partial.exists());
+        assert_eq!(std::fs::read(&output).unwrap(), b"old");
+
+        std::fs::write(&partial, b"ab").unwrap();
+        let error =
+            finalize("https://example.invalid/server.jar", &partial, &output, &wrong_digest)
+                .await
+                .expect_err("truncated file must fail");
+        assert!(matches!(error, DownloadError::SizeMismatch { expected: 3, actual: 2 }));
+
+        std::fs::write(&partial, b"abc").unwrap();
+        let matching = DownloadIntegrity {
+            size: Some(3),
+            sha1: Some("A9993E364706816ABA3E25717850C26C9CD0D89D".into()),
+            ..Default::default()
+        };
+        finalize("https://example.invalid/server.jar", &partial, &output, &matching)
+            .await
+            .expect("matching file must be renamed");
+        assert!(!partial.exists());
+        assert_eq!(std::fs::read(&output).unwrap(), b"abc");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,12 +1,13 @@
 //! 哈希计算与校验和解析工具。
 //!
-//! 提供 SHA-256 文件摘要计算、内存数据快速哈希、校验和文件内容解析等功能。
+//! 提供 SHA-256 / SHA-1 / SHA-512 文件摘要计算、内存数据快速哈希、校验和文件内容解析等功能。
 //!
 //! # 功能概览
 //!
 //! | 函数 | 用途 |
 //! |------|------|
 //! | [`sha256_file`] | 异步流式计算文件的 SHA-256 摘要，内存占用恒定 |
+//! | [`file_digest_hex`] | 按 [`HashAlgorithm`] 流式计算文件的小写十六进制摘要 |
 //! | [`sha256_hex`] | 计算内存数据的小写十六进制 SHA-256 |
 //! | [`is_sha256_hex`] | 检查字符串是否为有效的 64 位十六进制哈希值 |
 //! | [`find_sha256_in_line`] | 在文本行中查找 SHA-256 哈希值 |
@@ -14,21 +15,79 @@
 
 use std::path::Path;
 
-use sha2::{Digest, Sha256};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
 use tokio::io::AsyncReadExt;
 
 use crate::observability;
 
 use super::FsError;
 
+/// 文件摘要算法。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HashAlgorithm {
+    Sha1,
+    Sha256,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    /// 按强度从高到低排列的全部算法，便于挑选可用的最强摘要。
+    pub const STRONGEST_FIRST: [Self; 3] = [Self::Sha512, Self::Sha256, Self::Sha1];
+
+    /// 解析小写或大写的算法名（`sha1`、`sha-256` 等）。
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().replace('-', "").as_str() {
+            "sha1" => Some(Self::Sha1),
+            "sha256" => Some(Self::Sha256),
+            "sha512" => Some(Self::Sha512),
+            _ => None,
+        }
+    }
+
+    /// 小写算法名。
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Sha1 => "sha1",
+            Self::Sha256 => "sha256",
+            Self::Sha512 => "sha512",
+        }
+    }
+}
+
 /// 计算文件的 SHA-256 摘要，无需将整个文件加载到内存中。
 pub async fn sha256_file(path: impl AsRef<Path>) -> Result<[u8; 32], FsError> {
+    let digest = digest_file::<Sha256>(path.as_ref(), "calculate SHA-256").await?;
+    Ok(digest.into())
+}
+
+/// 按指定算法流式计算文件摘要，返回小写十六进制字符串。
+pub async fn file_digest_hex(
+    path: impl AsRef<Path>,
+    algorithm: HashAlgorithm,
+) -> Result<String, FsError> {
     let path = path.as_ref();
+    let hex = match algorithm {
+        HashAlgorithm::Sha1 => format!("{:x}", digest_file::<Sha1>(path, "calculate SHA-1").await?),
+        HashAlgorithm::Sha256 => {
+            format!("{:x}", digest_file::<Sha256>(path, "calculate SHA-256").await?)
+        }
+        HashAlgorithm::Sha512 => {
+            format!("{:x}", digest_file::<Sha512>(path, "calculate SHA-512").await?)
+        }
+    };
+    Ok(hex)
+}
+
+async fn digest_file<D: Digest>(
+    path: &Path,
+    operation: &'static str,
+) -> Result<sha2::digest::Output<D>, FsError> {
     let result = async {
         let mut file = tokio::fs::File::open(path)
             .await
             .map_err(|error| FsError::io("open file for hashing", path, error))?;
-        let mut digest = Sha256::new();
+        let mut digest = D::new();
         let mut buffer = [0_u8; 16 * 1024];
         loop {
             let read = file
@@ -40,11 +99,11 @@ pub async fn sha256_file(path: impl AsRef<Path>) -> Result<[u8; 32], FsError> {
             }
             digest.update(&buffer[..read]);
         }
-        Ok(digest.finalize().into())
+        Ok(digest.finalize())
     }
     .await;
     if let Err(error) = &result {
-        observability::operation_failed("calculate SHA-256", path, error);
+        observability::operation_failed(operation, path, error);
     }
     result
 }
@@ -141,6 +200,31 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn digests_files_with_each_algorithm() {
+        let dir = crate::fs::test_dir("hash-algorithms");
+        let path = dir.join("abc.txt");
+        std::fs::write(&path, b"abc").unwrap();
+
+        assert_eq!(
+            file_digest_hex(&path, HashAlgorithm::Sha1).await.unwrap(),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        assert_eq!(
+            file_digest_hex(&path, HashAlgorithm::Sha256).await.unwrap(),
+            sha256_hex(b"abc")
+        );
+        assert!(
+            file_digest_hex(&path, HashAlgorithm::Sha512)
+                .await
+                .unwrap()
+                .starts_with("ddaf35a193617aba")
+        );
+        assert_eq!(HashAlgorithm::from_name("SHA-512"), Some(HashAlgorithm::Sha512));
+        assert_eq!(HashAlgorithm::from_name("md5"), None);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
     #[test]
     fn is_sha256_hex_validates_length_and_chars() {
         assert!(is_sha256_hex(
@@ -207,7 +207,8 @@ impl NetClient {
     ///
     /// # Returns
     ///
-    /// 返回 `RemoteFileInfo`，包含文件总大小以及是否支持 Range。
+    /// 返回 `RemoteFileInfo`，包含文件总大小、是否支持 Range 以及
+    /// `ETag` / `Last-Modified` 校验头（供续传时判断远端文件是否变化）。
     ///
     /// # Errors
     ///
@@ -231,17 +232,33 @@ impl NetClient {
                 .ok_or_else(|| NetError::Parse("服务器未返回 Content-Length".into()))?
         };
 
-        Ok(RemoteFileInfo { total_size, supports_range })
+        let header = |name| {
+            resp.headers()
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned)
+        };
+
+        Ok(RemoteFileInfo {
+            total_size,
+            supports_range,
+            etag: header(reqwest::header::ETAG),
+            last_modified: header(reqwest::header::LAST_MODIFIED),
+        })
     }
 }
 
 /// 远程文件信息。
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct RemoteFileInfo {
     /// 文件总大小（字节）
     pub total_size: u64,
     /// 服务器是否支持 Range 请求
     pub supports_range: bool,
+    /// `ETag` 响应头
+    pub etag: Option<String>,
+    /// `Last-Modified` 响应头
+    pub last_modified: Option<String>,
 }
 
 /// 从 `Content-Range` 头部解析文件总大小。
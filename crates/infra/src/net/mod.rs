@@ -1,6 +1,7 @@
 pub mod client;
 pub mod error;
 mod plugin;
+pub mod port;
 pub mod proxy;
 pub mod request;
 mod runtime;
@@ -14,6 +15,7 @@ pub use plugin::{
     PluginNetworkScope, PluginNetworkTrace, PluginRequestHeaders, PluginTransportErrorKind,
     PluginTransportStage, ResolvedNetworkTarget,
 };
+pub use port::{tcp_port_in_use, udp_port_in_use};
 pub use proxy::{
     EffectiveProxy, ProxyConfigError, ProxyController, ProxyMode, ProxyMonitor, ProxySettings,
     ProxyUpdate, SystemProxyProvider, SystemProxySnapshot,
@@ -0,0 +1,42 @@
+//! 本机端口占用探测。
+//!
+//! 通过尝试在通配地址上绑定端口判断端口是否已被其他进程占用。
+//! 只有绑定失败且错误为 `AddrInUse` 时才视为占用；权限不足等其他错误
+//! 交由实际启动时报告，避免误判。
+
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, TcpListener, UdpSocket};
+
+/// TCP 端口是否已被本机其他进程占用。
+pub fn tcp_port_in_use(port: u16) -> bool {
+    match TcpListener::bind((Ipv4Addr::UNSPECIFIED, port)) {
+        Ok(_) => false,
+        Err(error) => error.kind() == ErrorKind::AddrInUse,
+    }
+}
+
+/// UDP 端口是否已被本机其他进程占用。
+pub fn udp_port_in_use(port: u16) -> bool {
+    match UdpSocket::bind((Ipv4Addr::UNSPECIFIED, port)) {
+        Ok(_) => false,
+        Err(error) => error.kind() == ErrorKind::AddrInUse,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_bound_ports_as_in_use() {
+        let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, 0)).unwrap();
+        let port = listener.local_addr().unwrap().port();
+        assert!(tcp_port_in_use(port));
+        drop(listener);
+        assert!(!tcp_port_in_use(port));
+
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).unwrap();
+        let port = socket.local_addr().unwrap().port();
+        assert!(udp_port_in_use(port));
+    }
+}
@@ -418,10 +418,83 @@ pub fn chunk_failed(url: &str, start: u64, end: u64, error: &dyn Display) {
     );
 }
 
+/// Event: 镜像失败或停滞，改用下一个候选地址。
+pub const EVENT_MIRROR_FAILOVER: &str = "mirror_failover";
+/// Event: 镜像健康统计保存失败。
+pub const EVENT_MIRROR_HEALTH_SAVE_FAILED: &str = "mirror_health_save_failed";
+
+/// 记录镜像切换事件。
+pub fn mirror_failover(failed_url: &str, next_url: &str, error: &dyn Display) {
+    tracing::warn!(
+        target: DOWNLOAD_TARGET,
+        event_name = EVENT_MIRROR_FAILOVER,
+        failed_url,
+        next_url,
+        error = %error,
+        "mirror failed, switching to next candidate"
+    );
+}
+
+/// 记录镜像健康统计保存失败事件。
+pub fn mirror_health_save_failed(path: &std::path::Path, error: &dyn Display) {
+    tracing::warn!(
+        target: DOWNLOAD_TARGET,
+        event_name = EVENT_MIRROR_HEALTH_SAVE_FAILED,
+        path = %path.display(),
+        error = %error,
+        "failed to save mirror health stats"
+    );
+}
+
+/// Event: 从持久化状态续传下载。
+pub const EVENT_DOWNLOAD_RESUMED: &str = "download_resumed";
+/// Event: 远端文件已变化或临时文件丢失，续传改为从头下载。
+pub const EVENT_DOWNLOAD_RESTARTED: &str = "download_restarted";
+/// Event: 续传状态读写失败。
+pub const EVENT_RESUME_STATE_FAILED: &str = "resume_state_failed";
+
+/// 记录续传开始事件。
+pub fn download_resumed(url: &str, downloaded: u64, total_size: u64) {
+    tracing::info!(
+        target: DOWNLOAD_TARGET,
+        event_name = EVENT_DOWNLOAD_RESUMED,
+        url,
+        downloaded,
+        total_size,
+        "resuming download from saved state"
+    );
+}
+
+/// 记录续传改为从头下载事件。
+pub fn download_restarted(url: &str, reason: &str) {
+    tracing::warn!(
+        target: DOWNLOAD_TARGET,
+        event_name = EVENT_DOWNLOAD_RESTARTED,
+        url,
+        reason,
+        "saved download state is stale, restarting from scratch"
+    );
+}
+
+/// 记录续传状态读写失败事件。
+pub fn resume_state_failed(path: &std::path::Path, error: &dyn Display) {
+    tracing::warn!(
+        target: DOWNLOAD_TARGET,
+        event_name = EVENT_RESUME_STATE_FAILED,
+        path = %path.display(),
+        error = %error,
+        "failed to access resumable download state"
+    );
+}
+
 /// Event: 下载任务已创建。
 pub const EVENT_TASK_CREATED: &str = "task_created";
 /// Event: 下载任务已取消。
 pub const EVENT_TASK_CANCELLED: &str = "task_cancelled";
+/// Event: 下载任务已暂停。
+pub const EVENT_TASK_PAUSED: &str = "task_paused";
+/// Event: 下载任务已恢复。
+pub const EVENT_TASK_RESUMED: &str = "task_resumed";
 /// Event: 下载已被用户取消。
 pub const EVENT_DOWNLOAD_CANCELLED: &str = "download_cancelled";
 /// Event: 下载遇到错误。
@@ -448,6 +521,26 @@ pub fn task_cancelled(task_id: &uuid::Uuid) {
     );
 }
 
+/// 记录任务暂停事件。
+pub fn task_paused(task_id: &uuid::Uuid) {
+    tracing::info!(
+        target: DOWNLOAD_TARGET,
+        event_name = EVENT_TASK_PAUSED,
+        task_id = %task_id,
+        "download task paused"
+    );
+}
+
+/// 记录任务恢复事件。
+pub fn task_resumed(task_id: &uuid::Uuid) {
+    tracing::info!(
+        target: DOWNLOAD_TARGET,
+        event_name = EVENT_TASK_RESUMED,
+        task_id = %task_id,
+        "download task resumed"
+    );
+}
+
 /// 记录下载取消事件。
 pub fn download_cancelled() {
     tracing::warn!(
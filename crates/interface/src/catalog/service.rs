@@ -2,23 +2,41 @@
 
 use crate::error::ServerCatalogServiceError;
 use async_trait::async_trait;
+use sealantern_extra::catalog::{CatalogBuild, CatalogSnapshot};
 use sealantern_extra::download_link::DownloadLink;
 
 /// 服务器核心下载目录宿主能力端口。
 ///
 /// 方法均为异步：下载目录数据可能来自远程配置。实现方组合 `infra` 或 `extra` 的
 /// 下载链接能力，不依赖任何具体宿主。
+///
+/// 列表查询返回 [`CatalogSnapshot`]：结果在有效期内来自缓存；离线时返回最后一次
+/// 成功的结果并置 `stale`。
 #[async_trait]
 pub trait ServerCatalogService: Send + Sync {
     /// 返回全部可用的服务器核心类型。
-    async fn server_types(&self) -> Result<Vec<String>, ServerCatalogServiceError>;
-    /// 返回指定服务器核心类型的可用版本列表。
-    async fn versions(&self, server_type: String)
-    -> Result<Vec<String>, ServerCatalogServiceError>;
-    /// 返回指定服务器核心类型、指定版本的下载链接。
+    async fn server_types(&self)
+    -> Result<CatalogSnapshot<Vec<String>>, ServerCatalogServiceError>;
+    /// 返回指定服务器核心类型的可用版本列表，从新到旧。
+    async fn versions(
+        &self,
+        server_type: String,
+    ) -> Result<CatalogSnapshot<Vec<String>>, ServerCatalogServiceError>;
+    /// 返回指定服务器核心类型、指定版本的构建，从新到旧。
+    ///
+    /// 构建携带发布渠道、上游摘要与所需的最低 Java 版本；上游不可用时来自镜像，
+    /// 此时只有一个不含摘要的构建。
+    async fn builds(
+        &self,
+        server_type: String,
+        server_version: String,
+    ) -> Result<CatalogSnapshot<Vec<CatalogBuild>>, ServerCatalogServiceError>;
+    /// 返回指定服务器核心类型、指定版本推荐构建的下载链接。
     async fn details(
         &self,
         server_type: String,
         server_version: String,
     ) -> Result<DownloadLink, ServerCatalogServiceError>;
+    /// 使缓存的目录数据过期并重新查询，返回最新的服务器核心类型。
+    async fn refresh(&self) -> Result<CatalogSnapshot<Vec<String>>, ServerCatalogServiceError>;
 }
@@ -0,0 +1,7 @@
+//! 控制台命令历史、别名与宏相关模型与服务端口。
+
+mod models;
+mod service;
+
+pub use models::{CommandHistoryEntry, CommandMacroDefinition, CommandMacroKind, CommandMacroStep};
+pub use service::CommandLibraryService;
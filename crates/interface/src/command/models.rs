@@ -0,0 +1,50 @@
+//! 命令库契约模型。
+//!
+//! 定义宿主消费的命令历史条目与用户宏定义；宏定义同时作为保存请求体，
+//! 因此同时实现序列化与反序列化。
+
+use serde::{Deserialize, Serialize};
+
+/// 一条去重后的历史命令（宿主消费的契约模型）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommandHistoryEntry {
+    pub command: String,
+    /// 最近一次发送时刻（Unix 秒）。
+    pub last_used_at: i64,
+    /// 累计发送次数。
+    pub use_count: i64,
+}
+
+/// 用户宏的种类。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandMacroKind {
+    /// 单条命令前缀，调用参数追加在后。
+    Alias,
+    /// 带 `{参数}` 占位符与可选延迟的命令序列。
+    Macro,
+}
+
+/// 宏中的一步。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandMacroStep {
+    /// 命令模板（可含 `{参数}` 占位符）。
+    pub command: String,
+    /// 发送本步前等待的毫秒数。
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+/// 用户定义的别名或宏（宿主消费的契约模型）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandMacroDefinition {
+    /// 调用名；在控制台输入首词与之相同时展开。
+    pub name: String,
+    pub kind: CommandMacroKind,
+    /// 按顺序绑定调用参数的参数名，最后一个参数吸收剩余文本。
+    #[serde(default)]
+    pub params: Vec<String>,
+    pub steps: Vec<CommandMacroStep>,
+    #[serde(default)]
+    pub description: String,
+}
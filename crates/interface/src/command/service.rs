@@ -0,0 +1,48 @@
+//! 命令库服务端口。
+
+use async_trait::async_trait;
+use sealantern_core::instance::InstanceId;
+
+use crate::error::CommandLibraryServiceError;
+
+use super::models::{CommandHistoryEntry, CommandMacroDefinition};
+
+/// 控制台命令历史与用户宏宿主能力端口。
+///
+/// 数据按实例目录持久化。宏在服务器进程服务发送控制台命令时于服务端展开，
+/// 本端口只负责维护历史与宏定义。
+#[async_trait]
+pub trait CommandLibraryService: Send + Sync {
+    /// 按最近使用时刻倒序读取历史命令。
+    ///
+    /// `query` 提供时只返回包含该文本的命令；`limit` 缺省时使用实现方默认窗口。
+    async fn history(
+        &self,
+        id: &InstanceId,
+        query: Option<String>,
+        limit: Option<i64>,
+    ) -> Result<Vec<CommandHistoryEntry>, CommandLibraryServiceError>;
+
+    /// 清空实例的历史命令。
+    async fn clear_history(&self, id: &InstanceId) -> Result<(), CommandLibraryServiceError>;
+
+    /// 按名称顺序列出实例的全部别名与宏。
+    async fn macros(
+        &self,
+        id: &InstanceId,
+    ) -> Result<Vec<CommandMacroDefinition>, CommandLibraryServiceError>;
+
+    /// 校验并保存宏，同名时覆盖。
+    async fn save_macro(
+        &self,
+        id: &InstanceId,
+        definition: CommandMacroDefinition,
+    ) -> Result<CommandMacroDefinition, CommandLibraryServiceError>;
+
+    /// 删除宏。
+    async fn delete_macro(
+        &self,
+        id: &InstanceId,
+        name: &str,
+    ) -> Result<(), CommandLibraryServiceError>;
+}
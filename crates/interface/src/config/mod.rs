@@ -0,0 +1,8 @@
+//! 服务器配置文件修订历史契约。
+//!
+//! 修订模型直接使用 `extra` 的 [`ConfigRevisionSummary`] 与 [`ConfigRevision`]。
+
+mod service;
+
+pub use sealantern_extra::config::{ConfigRevision, ConfigRevisionSummary};
+pub use service::ConfigRevisionService;
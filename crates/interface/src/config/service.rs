@@ -0,0 +1,46 @@
+//! 配置修订服务端口。
+
+use async_trait::async_trait;
+use sealantern_core::app_plugin::ExecutionPrincipal;
+use sealantern_core::instance::InstanceId;
+use sealantern_extra::config::{ConfigRevision, ConfigRevisionSummary};
+
+use crate::error::ConfigServiceError;
+
+/// 配置修订宿主能力端口。
+///
+/// 实现方负责定位实例目录，并在所有经 Sea Lantern 写入配置文件的路径上记录修订；
+/// 本端口只暴露修订的查询、比较与恢复。
+#[async_trait]
+pub trait ConfigRevisionService: Send + Sync {
+    /// 按时间倒序列出修订；`file` 提供时只列出该文件的修订，`limit` 缺省使用服务默认条数。
+    async fn list_revisions(
+        &self,
+        id: &InstanceId,
+        file: Option<&str>,
+        limit: Option<i64>,
+    ) -> Result<Vec<ConfigRevisionSummary>, ConfigServiceError>;
+
+    /// 读取一条修订及其完整快照。
+    async fn get_revision(
+        &self,
+        id: &InstanceId,
+        revision: i64,
+    ) -> Result<ConfigRevision, ConfigServiceError>;
+
+    /// 比较任意两条修订，返回统一差异。
+    async fn diff_revisions(
+        &self,
+        id: &InstanceId,
+        from: i64,
+        to: i64,
+    ) -> Result<String, ConfigServiceError>;
+
+    /// 将配置文件恢复为指定修订，恢复修订归属 `principal`；内容未变化时返回 `None`。
+    async fn restore_revision(
+        &self,
+        id: &InstanceId,
+        revision: i64,
+        principal: &ExecutionPrincipal,
+    ) -> Result<Option<ConfigRevisionSummary>, ConfigServiceError>;
+}
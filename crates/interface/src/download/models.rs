@@ -5,14 +5,18 @@
 /// 下载任务状态（对齐前端 `TaskStatus` 形状）。
 ///
 /// 手写序列化以匹配前端期望：unit 变体输出为字符串
-/// （`"Pending"`/`"Downloading"`/`"Completed"`），错误变体输出为
+/// （`"Queued"`/`"Pending"`/`"Downloading"`/`"Paused"`/`"Completed"`），错误变体输出为
 /// `{ "Error": "..." }` 对象。
 #[derive(Debug, Clone, PartialEq)]
 pub enum DownloadTaskStatus {
-    /// 排队中（尚未开始）。
+    /// 在全局下载队列中等待运行名额。
+    Queued,
+    /// 已开始但尚未收到数据。
     Pending,
     /// 下载中。
     Downloading,
+    /// 已暂停（保留已下载的部分）。
+    Paused,
     /// 已完成。
     Completed,
     /// 失败（携带错误信息）。
@@ -26,8 +30,10 @@ impl serde::Serialize for DownloadTaskStatus {
     {
         use serde::ser::SerializeMap;
         match self {
+            Self::Queued => serializer.serialize_str("Queued"),
             Self::Pending => serializer.serialize_str("Pending"),
             Self::Downloading => serializer.serialize_str("Downloading"),
+            Self::Paused => serializer.serialize_str("Paused"),
             Self::Completed => serializer.serialize_str("Completed"),
             Self::Error(message) => {
                 let mut map = serializer.serialize_map(Some(1))?;
@@ -56,8 +62,57 @@ pub struct DownloadTaskInfo {
     pub progress: f64,
     /// 任务状态。
     pub status: DownloadTaskStatus,
+    /// 失败类别，仅在 `status` 为错误时给出。
+    pub error_kind: Option<DownloadErrorKind>,
     /// 是否已结束（完成、出错或取消）。
     pub is_finished: bool,
+    /// 在全局下载队列中的位置（从 1 开始）；运行中、暂停或已结束时为 `None`。
+    pub queue_position: Option<usize>,
+}
+
+/// 下载任务失败的类别，供宿主区分校验失败与网络错误等。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadErrorKind {
+    /// 请求失败、服务端返回错误状态或下载停滞。
+    Network,
+    /// 本地文件读写失败。
+    Storage,
+    /// 任务被取消。
+    Cancelled,
+    /// 远端文件在下载过程中发生变化。
+    RemoteChanged,
+    /// 文件大小与预期不符。
+    SizeMismatch,
+    /// 文件摘要与预期不符。
+    ChecksumMismatch,
+    /// 其他错误。
+    Other,
+}
+
+/// 下载任务优先级；高优先级的任务先获得运行名额。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadPriority {
+    /// 低。
+    Low,
+    /// 普通。
+    #[default]
+    Normal,
+    /// 高。
+    High,
+}
+
+/// 全局下载限制。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub struct DownloadLimits {
+    /// 同时运行的任务数上限，超出的任务排队。
+    pub max_active_tasks: usize,
+    /// 所有任务合计的连接数上限。
+    pub max_connections: usize,
+    /// 全局带宽上限（字节/秒）；为 `None` 时不限速。
+    pub bandwidth_limit: Option<u64>,
 }
 
 /// 创建下载任务的输入。
@@ -65,10 +120,36 @@ pub struct DownloadTaskInfo {
 pub struct DownloadRequest {
     /// 下载 URL。
     pub url: String,
+    /// 同一文件的备用镜像地址，按优先级排列；下载失败或停滞时自动切换。
+    pub mirrors: Vec<String>,
     /// 本地保存路径。
     pub save_path: String,
     /// 下载线程数。
     pub thread_count: usize,
+    /// 预期文件大小（字节），不符时任务失败。
+    pub expected_size: Option<u64>,
+    /// 预期 SHA-1 摘要（十六进制）。
+    pub sha1: Option<String>,
+    /// 预期 SHA-256 摘要（十六进制）。
+    pub sha256: Option<String>,
+    /// 预期 SHA-512 摘要（十六进制）。
+    pub sha512: Option<String>,
+    /// 调度优先级。
+    pub priority: DownloadPriority,
+    /// 任务自身的带宽上限（字节/秒）；为 `None` 时只受全局限制。
+    pub bandwidth_limit: Option<u64>,
+}
+
+/// 启动时续传的下载任务（任务信息附带来源地址与保存路径，供宿主恢复展示）。
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ResumedDownload {
+    /// 任务信息；续传失败时状态为错误且已结束。
+    pub task: DownloadTaskInfo,
+    /// 首选下载 URL。
+    pub url: String,
+    /// 本地保存路径。
+    pub save_path: String,
 }
 
 #[cfg(test)]
@@ -84,6 +165,8 @@ mod tests {
             "\"Downloading\""
         );
         assert_eq!(serde_json::to_string(&DownloadTaskStatus::Completed).unwrap(), "\"Completed\"");
+        assert_eq!(serde_json::to_string(&DownloadTaskStatus::Queued).unwrap(), "\"Queued\"");
+        assert_eq!(serde_json::to_string(&DownloadTaskStatus::Paused).unwrap(), "\"Paused\"");
         // 错误变体输出为 { "Error": "..." } 对象（前端 errorMessage 判定 "Error" in status）。
         assert_eq!(
             serde_json::to_string(&DownloadTaskStatus::Error("boom".into())).unwrap(),
@@ -99,13 +182,29 @@ mod tests {
             total_size: 100,
             downloaded: 60,
             progress: 60.0,
-            status: DownloadTaskStatus::Downloading,
+            status: DownloadTaskStatus::Error("size".into()),
+            error_kind: Some(DownloadErrorKind::SizeMismatch),
             is_finished: false,
+            queue_position: None,
         };
         let json = serde_json::to_string(&info).unwrap();
         assert!(json.contains("\"total_size\":100"), "missing total_size: {json}");
         assert!(json.contains("\"is_finished\":false"), "missing is_finished: {json}");
         assert!(!json.contains("totalSize"), "camelCase leaked: {json}");
         assert!(!json.contains("isFinished"), "camelCase leaked: {json}");
+        assert!(json.contains("\"queue_position\":null"), "missing queue_position: {json}");
+        assert!(json.contains("\"error_kind\":\"size_mismatch\""), "missing error_kind: {json}");
+    }
+
+    #[test]
+    fn priority_and_limits_use_snake_case() {
+        let priority: DownloadPriority = serde_json::from_str("\"high\"").unwrap();
+        assert_eq!(priority, DownloadPriority::High);
+        let limits: DownloadLimits = serde_json::from_str(
+            r#"{"max_active_tasks":2,"max_connections":16,"bandwidth_limit":null}"#,
+        )
+        .unwrap();
+        assert_eq!(limits.max_active_tasks, 2);
+        assert_eq!(limits.bandwidth_limit, None);
     }
 }
@@ -4,12 +4,13 @@ use async_trait::async_trait;
 
 use crate::error::DownloadServiceError;
 
-use super::models::{DownloadRequest, DownloadTaskInfo};
+use super::models::{DownloadLimits, DownloadRequest, DownloadTaskInfo, ResumedDownload};
 
 /// 下载任务管理宿主能力端口。
 ///
-/// 管理下载任务的创建、进度查询与取消。实现方组合 `infra` 的下载执行能力，
-/// 不依赖任何具体宿主。
+/// 管理下载任务的创建、进度查询、暂停与恢复、取消与跨进程续传。所有任务经全局队列
+/// 调度，同时运行的任务数、连接总数与带宽受 [`DownloadLimits`] 限制。实现方组合
+/// `infra` 的下载执行能力，不依赖任何具体宿主。
 #[async_trait]
 pub trait DownloadService: Send + Sync {
     /// 创建下载任务，返回任务 ID。
@@ -18,6 +19,29 @@ pub trait DownloadService: Send + Sync {
     /// 查询任务进度；任务不存在时返回 `None`。
     async fn poll(&self, id: &str) -> Result<Option<DownloadTaskInfo>, DownloadServiceError>;
 
-    /// 取消下载任务。
+    /// 取消下载任务（包括排队中与已暂停的任务）。
     async fn cancel(&self, id: &str) -> Result<(), DownloadServiceError>;
+
+    /// 暂停下载任务：排队中的任务不再被调度，运行中的任务停止并保留已下载的部分。
+    ///
+    /// 远端不支持断点续传的任务在开始下载后不能暂停，返回 `Unsupported`。
+    async fn pause(&self, id: &str) -> Result<(), DownloadServiceError>;
+
+    /// 恢复已暂停的任务（重新排队），或按续传记录继续上次未完成的任务，返回任务信息。
+    async fn resume(&self, id: &str) -> Result<DownloadTaskInfo, DownloadServiceError>;
+
+    /// 查询全局下载限制。
+    async fn limits(&self) -> Result<DownloadLimits, DownloadServiceError>;
+
+    /// 修改全局下载限制并持久化，立即对所有任务生效，返回生效后的限制。
+    async fn set_limits(
+        &self,
+        limits: DownloadLimits,
+    ) -> Result<DownloadLimits, DownloadServiceError>;
+
+    /// 列出上次运行中未完成的下载任务并逐一续传（宿主启动时调用）。
+    ///
+    /// 续传的任务沿用原任务 ID，可继续通过 [`Self::poll`] 查询；远端文件已变化时
+    /// 从头下载。无法续传（如所有镜像都不可用）的任务以错误状态返回，保留进度供下次重试。
+    async fn resume_incomplete(&self) -> Result<Vec<ResumedDownload>, DownloadServiceError>;
 }
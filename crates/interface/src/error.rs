@@ -49,6 +49,8 @@ pub enum InstanceServiceError {
     InstanceNotFound,
     /// 目标实例标识已存在（创建冲突）。
     AlreadyExists,
+    /// 实例端口与其他受管实例或主机上的进程冲突。
+    PortConflict,
     /// 客户端提供的输入不合法（如空 ID、格式错误）。
     InvalidInput,
     /// 实例当前状态不允许该操作（如未运行时停止、已运行时重复启动）。
@@ -82,6 +84,7 @@ impl std::fmt::Display for InstanceServiceError {
         let message = match self {
             Self::InstanceNotFound => "server instance not found",
             Self::AlreadyExists => "server instance already exists",
+            Self::PortConflict => "server port is already used by another instance or process",
             Self::InvalidInput => "invalid input",
             Self::InvalidState => "server instance is in an invalid state",
             Self::SourceUnavailable => "import source directory is unavailable",
@@ -119,6 +122,8 @@ pub enum ServerServiceError {
     InvalidState,
     /// 客户端提供的输入不合法。
     InvalidInput,
+    /// 命令（或宏展开后的某一行）被插件命令过滤规则拒绝。
+    CommandRejected,
     /// 底层进程 / IO 操作失败。
     OperationFailed,
     /// 该能力尚未实现（占位）。
@@ -131,6 +136,7 @@ impl std::fmt::Display for ServerServiceError {
             Self::InstanceNotFound => "server instance not found",
             Self::InvalidState => "server is in an invalid state for this operation",
             Self::InvalidInput => "invalid input",
+            Self::CommandRejected => "command rejected by command filter",
             Self::OperationFailed => "server operation failed",
             Self::Unsupported => "operation not supported",
         };
@@ -174,6 +180,8 @@ pub enum DownloadServiceError {
     InvalidInput,
     /// 底层网络 / IO 操作失败。
     OperationFailed,
+    /// 下载文件的大小或摘要与预期不符。
+    VerificationFailed,
     /// 该能力尚未实现（占位）。
     Unsupported,
 }
@@ -198,6 +206,7 @@ impl std::fmt::Display for DownloadServiceError {
             Self::TaskNotFound => "download task not found",
             Self::InvalidInput => "invalid input",
             Self::OperationFailed => "download operation failed",
+            Self::VerificationFailed => "download verification failed",
             Self::Unsupported => "operation not supported",
         };
         formatter.write_str(message)
@@ -273,6 +282,295 @@ impl std::fmt::Display for ConsoleServiceError {
 
 impl std::error::Error for ConsoleServiceError {}
 
+/// 在线玩家服务失败的契约错误类别。
+///
+/// 分类风格与 [`ConsoleServiceError`] 一致：不携带主机路径等敏感细节，
+/// 底层失败详情由应用层写入受控日志。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlayerServiceError {
+    /// 指定的实例不存在。
+    InstanceNotFound,
+    /// 客户端提供的输入不合法（如玩家名或窗口大小非法）。
+    InvalidInput,
+    /// 底层会话数据库操作失败。
+    OperationFailed,
+}
+
+impl std::fmt::Display for PlayerServiceError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str(match self {
+            Self::InstanceNotFound => "server instance not found",
+            Self::InvalidInput => "invalid player query input",
+            Self::OperationFailed => "player tracking operation failed",
+        })
+    }
+}
+
+impl std::error::Error for PlayerServiceError {}
+
+/// 命令库服务失败的契约错误类别。
+///
+/// 分类风格与 [`PlayerServiceError`] 一致：不携带主机路径等敏感细节，
+/// 底层失败详情由应用层写入受控日志。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandLibraryServiceError {
+    /// 指定的实例不存在。
+    InstanceNotFound,
+    /// 指定的宏不存在。
+    MacroNotFound,
+    /// 客户端提供的宏定义或查询参数不合法。
+    InvalidInput,
+    /// 底层命令库操作失败。
+    OperationFailed,
+}
+
+impl std::fmt::Display for CommandLibraryServiceError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str(match self {
+            Self::InstanceNotFound => "server instance not found",
+            Self::MacroNotFound => "command macro not found",
+            Self::InvalidInput => "invalid command library input",
+            Self::OperationFailed => "command library operation failed",
+        })
+    }
+}
+
+impl std::error::Error for CommandLibraryServiceError {}
+
+/// 实例配置模板操作失败的契约错误类别。
+///
+/// 批量套用时也作为单个实例的失败原因写入报告，因此不携带主机路径等细节。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceTemplateServiceError {
+    /// 指定的模板不存在。
+    TemplateNotFound,
+    /// 指定的实例不存在。
+    InstanceNotFound,
+    /// 模板内容或请求参数不合法，或模板与实例现有配置冲突。
+    InvalidInput,
+    /// 端口策略无法为实例分配可用端口。
+    PortConflict,
+    /// 模板指定的配置文件不存在、无法解析或修改。
+    ConfigFailed,
+    /// 模板 JSON 持久化读写失败。
+    StorageFailed,
+    /// 未分类的内部操作失败。
+    OperationFailed,
+}
+
+impl std::fmt::Display for InstanceTemplateServiceError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str(match self {
+            Self::TemplateNotFound => "instance template not found",
+            Self::InstanceNotFound => "server instance not found",
+            Self::InvalidInput => "invalid instance template input",
+            Self::PortConflict => "no available port for instance template",
+            Self::ConfigFailed => "instance template config update failed",
+            Self::StorageFailed => "instance template storage failed",
+            Self::OperationFailed => "instance template operation failed",
+        })
+    }
+}
+
+impl std::error::Error for InstanceTemplateServiceError {}
+
+/// 配置修订操作失败的契约错误类别。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigServiceError {
+    /// 指定的实例不存在。
+    InstanceNotFound,
+    /// 指定的修订不存在。
+    RevisionNotFound,
+    /// 文件路径、条数等请求参数不合法。
+    InvalidInput,
+    /// 修订库或配置文件读写失败。
+    StorageFailed,
+    /// 未分类的内部操作失败。
+    OperationFailed,
+}
+
+impl std::fmt::Display for ConfigServiceError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str(match self {
+            Self::InstanceNotFound => "server instance not found",
+            Self::RevisionNotFound => "config revision not found",
+            Self::InvalidInput => "invalid config revision input",
+            Self::StorageFailed => "config revision storage failed",
+            Self::OperationFailed => "config revision operation failed",
+        })
+    }
+}
+
+impl std::error::Error for ConfigServiceError {}
+
+/// 扩展启停失败的契约错误类别。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtensionServiceError {
+    /// 指定的实例不存在。
+    InstanceNotFound,
+    /// 实例中没有该扩展。
+    ExtensionNotFound,
+    /// 文件名不合法，或不是该类型可管理的文件。
+    InvalidInput,
+    /// 服务器运行中，且请求未强制执行。
+    ServerRunning,
+    /// 启用与禁用位置同时存在同名文件。
+    TargetExists,
+    /// 文件移动失败。
+    StorageFailed,
+    /// 未分类的内部操作失败。
+    OperationFailed,
+}
+
+impl std::fmt::Display for ExtensionServiceError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str(match self {
+            Self::InstanceNotFound => "server instance not found",
+            Self::ExtensionNotFound => "extension not found",
+            Self::InvalidInput => "invalid extension input",
+            Self::ServerRunning => "server is running",
+            Self::TargetExists => "an extension with the same name already exists",
+            Self::StorageFailed => "extension storage failed",
+            Self::OperationFailed => "extension operation failed",
+        })
+    }
+}
+
+impl std::error::Error for ExtensionServiceError {}
+
+/// 加载器安装器执行失败的契约错误类别。
+///
+/// 只覆盖开始安装时的拒绝；安装过程中的失败记录在任务信息中。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallerServiceError {
+    /// 指定的实例不存在。
+    InstanceNotFound,
+    /// 核心类型不需要或不支持安装器。
+    UnsupportedCore,
+    /// 缺少游戏版本等必要输入。
+    InvalidInput,
+    /// 服务器运行中。
+    ServerRunning,
+    /// 该实例已有未结束的安装任务。
+    AlreadyInstalling,
+    /// 未分类的内部操作失败。
+    OperationFailed,
+}
+
+impl std::fmt::Display for InstallerServiceError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str(match self {
+            Self::InstanceNotFound => "server instance not found",
+            Self::UnsupportedCore => "core type does not use an installer",
+            Self::InvalidInput => "invalid installer input",
+            Self::ServerRunning => "server is running",
+            Self::AlreadyInstalling => "an installation is already in progress",
+            Self::OperationFailed => "installer operation failed",
+        })
+    }
+}
+
+impl std::error::Error for InstallerServiceError {}
+
+/// 服务端核心原地升级失败的契约错误类别。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerUpgradeServiceError {
+    /// 指定的实例不存在。
+    InstanceNotFound,
+    /// 请求参数不合法。
+    InvalidInput,
+    /// 核心需要执行安装器，或实例不是以 jar 直接启动。
+    UnsupportedCore,
+    /// 服务器运行中。
+    ServerRunning,
+    /// 目录中没有目标构建。
+    BuildNotFound,
+    /// 目录不可用且没有离线缓存。
+    CatalogUnavailable,
+    /// 实例使用的 Java 不满足目标构建的要求。
+    JavaRequirementUnmet,
+    /// 升级前的自动备份失败。
+    BackupFailed,
+    /// 下载目标构建失败，或下载文件的大小、摘要与目录不符。
+    DownloadFailed,
+    /// 未分类的内部操作失败。
+    OperationFailed,
+}
+
+impl std::fmt::Display for ServerUpgradeServiceError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str(match self {
+            Self::InstanceNotFound => "server instance not found",
+            Self::InvalidInput => "invalid upgrade input",
+            Self::UnsupportedCore => "server core cannot be upgraded in place",
+            Self::ServerRunning => "server is running",
+            Self::BuildNotFound => "target build not found",
+            Self::CatalogUnavailable => "server catalog unavailable",
+            Self::JavaRequirementUnmet => "Java does not meet the target build requirement",
+            Self::BackupFailed => "backup before upgrade failed",
+            Self::DownloadFailed => "failed to download target build",
+            Self::OperationFailed => "server upgrade operation failed",
+        })
+    }
+}
+
+impl std::error::Error for ServerUpgradeServiceError {}
+
+/// 市场资源安装、卸载失败的契约错误类别。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarketInstallServiceError {
+    /// 指定的实例不存在。
+    InstanceNotFound,
+    /// 实例上没有该资源的安装记录。
+    ResourceNotInstalled,
+    /// 请求参数不合法。
+    InvalidInput,
+    /// 实例无法加载插件或模组（如原版服务端）。
+    UnsupportedInstance,
+    /// 安装计划存在缺失版本、不兼容等阻止安装的问题。
+    PlanBlocked,
+    /// 仍有其他已安装资源依赖该资源。
+    DependencyInUse,
+    /// 市场请求或文件下载失败。
+    MarketUnavailable,
+    /// 下载文件的大小或摘要与市场声明不符。
+    VerificationFailed,
+    /// 该文件没有可回滚的更新，或旧文件备份已丢失。
+    RollbackUnavailable,
+    /// 资源文件或安装记录读写失败。
+    StorageFailed,
+    /// 未分类的内部操作失败。
+    OperationFailed,
+}
+
+impl std::fmt::Display for MarketInstallServiceError {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter.write_str(match self {
+            Self::InstanceNotFound => "server instance not found",
+            Self::ResourceNotInstalled => "resource is not installed on this instance",
+            Self::InvalidInput => "invalid market install input",
+            Self::UnsupportedInstance => "instance does not support plugins or mods",
+            Self::PlanBlocked => "install plan has blocking issues",
+            Self::DependencyInUse => "resource is required by other installed resources",
+            Self::MarketUnavailable => "market request failed",
+            Self::VerificationFailed => "downloaded file failed verification",
+            Self::RollbackUnavailable => "no rollback is available for this file",
+            Self::StorageFailed => "market install storage failed",
+            Self::OperationFailed => "market install operation failed",
+        })
+    }
+}
+
+impl std::error::Error for MarketInstallServiceError {}
+
 /// 应用更新检查失败的契约错误类别。
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -414,12 +712,17 @@ mod tests {
         let cases = [
             (serde_json::to_string(&CronTaskServiceError::TaskNotFound), "\"task_not_found\""),
             (serde_json::to_string(&InstanceServiceError::InvalidInput), "\"invalid_input\""),
+            (serde_json::to_string(&InstanceServiceError::PortConflict), "\"port_conflict\""),
             (serde_json::to_string(&ServerServiceError::InvalidState), "\"invalid_state\""),
             (
                 serde_json::to_string(&SettingsServiceError::StorageFailed),
                 "\"storage_failed\"",
             ),
             (serde_json::to_string(&DownloadServiceError::TaskNotFound), "\"task_not_found\""),
+            (
+                serde_json::to_string(&DownloadServiceError::VerificationFailed),
+                "\"verification_failed\"",
+            ),
             (
                 serde_json::to_string(&SystemServiceError::ProcessNotFound),
                 "\"process_not_found\"",
@@ -439,6 +742,38 @@ mod tests {
                 "\"operation_failed\"",
             ),
             (serde_json::to_string(&OnlineTunnelServiceError::NotRunning), "\"not_running\""),
+            (
+                serde_json::to_string(&PlayerServiceError::InstanceNotFound),
+                "\"instance_not_found\"",
+            ),
+            (
+                serde_json::to_string(&CommandLibraryServiceError::MacroNotFound),
+                "\"macro_not_found\"",
+            ),
+            (
+                serde_json::to_string(&InstanceTemplateServiceError::PortConflict),
+                "\"port_conflict\"",
+            ),
+            (
+                serde_json::to_string(&ExtensionServiceError::ServerRunning),
+                "\"server_running\"",
+            ),
+            (
+                serde_json::to_string(&InstallerServiceError::AlreadyInstalling),
+                "\"already_installing\"",
+            ),
+            (
+                serde_json::to_string(&ServerUpgradeServiceError::JavaRequirementUnmet),
+                "\"java_requirement_unmet\"",
+            ),
+            (
+                serde_json::to_string(&MarketInstallServiceError::DependencyInUse),
+                "\"dependency_in_use\"",
+            ),
+            (
+                serde_json::to_string(&ServerServiceError::CommandRejected),
+                "\"command_rejected\"",
+            ),
         ];
 
         for (serialized, expected) in cases {
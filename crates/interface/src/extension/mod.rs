@@ -0,0 +1,7 @@
+//! 实例插件、模组与数据包启停契约。
+
+mod models;
+mod service;
+
+pub use models::ExtensionToggleRequest;
+pub use service::ExtensionService;
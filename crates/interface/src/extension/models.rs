@@ -0,0 +1,19 @@
+//! 扩展启停契约模型。
+//!
+//! 扩展描述直接使用 `core` 的
+//! [`InstanceExtension`](sealantern_core::instance::InstanceExtension)；本模块只定义请求。
+
+use sealantern_core::instance::InstanceExtensionKind;
+use serde::Deserialize;
+
+/// 启用或禁用一个扩展的请求。
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ExtensionToggleRequest {
+    pub kind: InstanceExtensionKind,
+    /// 扩展当前（启用或禁用状态下）的文件名或数据包名。
+    pub file_name: String,
+    pub enabled: bool,
+    /// 服务器运行中也执行；改动在下次启动后生效。
+    #[serde(default)]
+    pub force: bool,
+}
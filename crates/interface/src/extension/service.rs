@@ -0,0 +1,23 @@
+//! 扩展启停服务端口。
+
+use async_trait::async_trait;
+use sealantern_core::instance::{InstanceExtension, InstanceId};
+
+use crate::error::ExtensionServiceError;
+
+use super::models::ExtensionToggleRequest;
+
+/// 实例扩展启停宿主能力端口。
+///
+/// 禁用不删除文件：插件与模组改名为 `.disabled` 后缀，数据包移到世界目录下的
+/// 同级禁用目录。服务器运行时默认拒绝，以免与服务端持有的文件句柄冲突；
+/// 请求带 `force` 时照常移动，改动在下次启动后生效。
+#[async_trait]
+pub trait ExtensionService: Send + Sync {
+    /// 启用或禁用一个扩展，返回移动后的扩展描述；已处于目标状态时不做改动。
+    async fn set_enabled(
+        &self,
+        id: &InstanceId,
+        request: ExtensionToggleRequest,
+    ) -> Result<InstanceExtension, ExtensionServiceError>;
+}
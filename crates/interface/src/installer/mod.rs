@@ -0,0 +1,7 @@
+//! 加载器安装器执行契约。
+
+mod models;
+mod service;
+
+pub use models::{InstallerRequest, InstallerStage, InstallerTaskInfo};
+pub use service::InstallerService;
@@ -0,0 +1,65 @@
+//! 加载器安装器执行契约模型。
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// 为实例执行加载器安装器的请求。
+///
+/// 字段均可省略：核心类型与游戏版本默认取自实例记录，加载器版本默认为推荐构建，
+/// Java 默认为实例配置的 Java，未配置时为本机满足构建要求的 Java。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct InstallerRequest {
+    /// 核心类型（`forge`、`neoforge` 或 `fabric`）。
+    #[serde(default)]
+    pub core_type: Option<String>,
+    #[serde(default)]
+    pub game_version: Option<String>,
+    /// 加载器版本或目录构建号（如 `47.2.0`、`1.20.1-47.2.0`）。
+    #[serde(default)]
+    pub loader_version: Option<String>,
+    /// 执行安装器并写入启动配置的 Java 可执行文件。
+    #[serde(default)]
+    pub java_path: Option<PathBuf>,
+}
+
+/// 安装任务所处的阶段。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallerStage {
+    /// 从目录解析安装器构建。
+    Resolving,
+    /// 下载安装器。
+    Downloading,
+    /// 执行安装器，输出写入实例控制台日志。
+    Installing,
+    /// 重新检查实例目录并写回启动配置。
+    Inspecting,
+    Completed,
+    Failed,
+}
+
+/// 安装任务信息（宿主轮询的契约模型）。
+///
+/// 安装器的输出不在此返回：与服务器进程输出一样写入实例控制台日志，宿主通过
+/// 控制台日志的增量读取或实时事件展示。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct InstallerTaskInfo {
+    pub instance_id: String,
+    pub stage: InstallerStage,
+    pub core_type: String,
+    pub game_version: String,
+    /// 解析出的加载器版本或构建号；解析完成前为 `None`。
+    pub loader_version: Option<String>,
+    /// 安装器文件名；解析完成前为 `None`。
+    pub installer: Option<String>,
+    /// 安装器进程的退出码。
+    pub exit_code: Option<i32>,
+    /// 安装完成后采纳的启动方式标识；没有可用的启动方式时为 `None`。
+    pub adopted_launch_profile: Option<String>,
+    /// 失败原因。
+    pub error: Option<String>,
+    /// 是否已结束（完成或失败）。
+    pub is_finished: bool,
+}
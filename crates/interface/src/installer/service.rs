@@ -0,0 +1,31 @@
+//! 加载器安装器执行服务端口。
+
+use async_trait::async_trait;
+use sealantern_core::instance::InstanceId;
+
+use crate::error::InstallerServiceError;
+
+use super::models::{InstallerRequest, InstallerTaskInfo};
+
+/// 加载器安装器执行宿主能力端口。
+///
+/// 为 Forge、NeoForge 与 Fabric 实例下载对应的安装器，以选定的 Java 在实例目录中
+/// 无头执行，完成后重新检查实例目录，采纳生成的 `run.sh` / `run.bat` 或参数文件作为
+/// 启动配置。每个实例同时只有一个安装任务。
+#[async_trait]
+pub trait InstallerService: Send + Sync {
+    /// 在后台开始安装，返回任务的初始信息。
+    ///
+    /// 服务器运行中、已有未结束的安装任务或核心类型不需要安装器时拒绝。
+    async fn start(
+        &self,
+        id: &InstanceId,
+        request: InstallerRequest,
+    ) -> Result<InstallerTaskInfo, InstallerServiceError>;
+
+    /// 查询实例最近一次安装任务；没有任务时返回 `None`。
+    async fn status(
+        &self,
+        id: &InstanceId,
+    ) -> Result<Option<InstallerTaskInfo>, InstallerServiceError>;
+}
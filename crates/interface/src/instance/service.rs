@@ -1,6 +1,8 @@
 use crate::error::InstanceServiceError;
 use async_trait::async_trait;
-use sealantern_core::instance::{Instance, InstanceId, InstanceSpec};
+use sealantern_core::instance::{
+    Instance, InstanceExtensionReport, InstanceId, InstanceListEntry, InstanceSpec, PortConflict,
+};
 use sealantern_core::provisioning::{ImportExistingServerRequest, ImportModpackRequest};
 
 /// 管理服务器实例记录的宿主能力端口。
@@ -18,6 +20,8 @@ pub trait InstanceService: Send + Sync {
     async fn find(&self, id: &InstanceId) -> Result<Option<Instance>, InstanceServiceError>;
 
     /// 创建新实例并持久化。
+    ///
+    /// 端口与其他实例或主机上的进程冲突时返回 [`InstanceServiceError::PortConflict`]。
     async fn create(&self, spec: InstanceSpec) -> Result<Instance, InstanceServiceError>;
 
     /// 删除实例；实例不存在时返回 [`InstanceServiceError::InstanceNotFound`]。
@@ -29,8 +33,27 @@ pub trait InstanceService: Send + Sync {
     /// 更新实例目录路径。
     async fn update_path(&self, id: &InstanceId, path: &str) -> Result<(), InstanceServiceError>;
 
+    /// 检查全部实例配置的端口（游戏、查询、RCON 与代理监听），返回实例之间的冲突。
+    ///
+    /// 运行中的实例本就占用自己的端口，因此这里不探测主机占用；主机探测只在
+    /// 创建与导入时进行。
+    async fn port_conflicts(&self) -> Result<Vec<PortConflict>, InstanceServiceError>;
+
+    /// 列出全部实例，并附带各实例涉及的端口冲突，供实例列表标注。
+    async fn list_entries(&self) -> Result<Vec<InstanceListEntry>, InstanceServiceError> {
+        let conflicts = self.port_conflicts().await?;
+        Ok(InstanceListEntry::collect(self.list().await?, &conflicts))
+    }
+
+    /// 列出实例的插件、模组（含 jar 内元数据）与数据包，并报告重复 ID 与缺失的必需依赖。
+    async fn extensions(
+        &self,
+        id: &InstanceId,
+    ) -> Result<InstanceExtensionReport, InstanceServiceError>;
+
     /// 导入已有服务器目录：校验源目录 → 去重 → 构建导入规格 → 供给计划 → 持久化登记。
     ///
+    /// 游戏端口或 RCON 端口冲突时自动改用下一个可用端口并写回 server.properties。
     /// 返回薄契约错误（无主机路径载荷）；底层失败详情由实现层写入受控日志。
     async fn import_existing_server(
         &self,
@@ -40,7 +63,7 @@ pub trait InstanceService: Send + Sync {
     /// 导入整合包为受管实例。
     ///
     /// 支持三种来源：压缩包解压到运行目录、jar 单文件复制到运行目录、文件夹直接引用。
-    /// 文件操作与实例注册均在此方法内完成。
+    /// 文件操作与实例注册均在此方法内完成；端口冲突的处理同 `import_existing_server`。
     async fn import_modpack(
         &self,
         request: ImportModpackRequest,
@@ -127,6 +150,19 @@ mod tests {
             Ok(())
         }
 
+        async fn port_conflicts(&self) -> Result<Vec<PortConflict>, InstanceServiceError> {
+            self.calls.lock().expect("lock").push("port_conflicts");
+            Ok(Vec::new())
+        }
+
+        async fn extensions(
+            &self,
+            _id: &InstanceId,
+        ) -> Result<InstanceExtensionReport, InstanceServiceError> {
+            self.calls.lock().expect("lock").push("extensions");
+            Ok(InstanceExtensionReport::default())
+        }
+
         async fn import_existing_server(
             &self,
             _request: ImportExistingServerRequest,
@@ -161,10 +197,34 @@ mod tests {
             .update_path(&id, "/new/path")
             .await
             .expect("update_path");
+        assert!(
+            service
+                .port_conflicts()
+                .await
+                .expect("port_conflicts")
+                .is_empty()
+        );
+        assert!(
+            service
+                .extensions(&id)
+                .await
+                .expect("extensions")
+                .issues
+                .is_empty()
+        );
 
         assert_eq!(
             *service.calls.lock().expect("lock"),
-            vec!["list", "find", "create", "delete", "rename", "update_path"]
+            vec![
+                "list",
+                "find",
+                "create",
+                "delete",
+                "rename",
+                "update_path",
+                "port_conflicts",
+                "extensions"
+            ]
         );
     }
 }
@@ -7,6 +7,10 @@
 
 /// 服务器核心下载目录相关模型与服务端口。
 pub mod catalog;
+/// 控制台命令历史、别名与宏相关模型与服务端口。
+pub mod command;
+/// 服务器配置文件修订历史相关模型与服务端口。
+pub mod config;
 /// 服务器控制台日志相关模型与服务端口。
 pub mod console;
 /// 服务器定时任务相关模型与服务端口。
@@ -15,12 +19,20 @@ pub mod cron;
 pub mod download;
 /// 接口契约错误类型。
 pub mod error;
+/// 实例插件、模组与数据包启停相关模型与服务端口。
+pub mod extension;
+/// 加载器安装器执行相关模型与服务端口。
+pub mod installer;
 /// 服务器实例记录相关模型与服务端口。
 pub mod instance;
 /// Java 检测与校验相关模型与服务端口。
 pub mod java;
+/// 市场资源安装、卸载相关模型与服务端口。
+pub mod market;
 /// 在线隧道相关模型与服务端口。
 pub mod online;
+/// 服务器在线玩家与会话历史相关模型与服务端口。
+pub mod player;
 /// 服务端检查与实例供给计划相关服务端口。
 pub mod provisioning;
 /// 服务器进程管理相关模型与服务端口。
@@ -29,35 +41,59 @@ pub mod server;
 pub mod settings;
 /// 系统资源信息相关模型与服务端口。
 pub mod system;
+/// 实例配置模板与批量套用相关模型与服务端口。
+pub mod template;
 /// 应用更新检查相关模型与服务端口。
 pub mod update;
+/// 服务端核心原地升级相关模型与服务端口。
+pub mod upgrade;
 
 /// 服务器核心下载目录服务端口。
 pub use catalog::ServerCatalogService;
+/// 控制台命令历史与宏服务端口。
+pub use command::CommandLibraryService;
+/// 配置修订服务端口。
+pub use config::ConfigRevisionService;
 /// 服务器控制台日志服务端口。
 pub use console::ConsoleService;
 /// 服务器定时任务服务端口。
 pub use cron::CronTaskService;
 /// 下载任务管理服务端口。
 pub use download::DownloadService;
+/// 控制台命令历史与宏错误枚举。
+pub use error::CommandLibraryServiceError;
+/// 配置修订错误枚举。
+pub use error::ConfigServiceError;
 /// 服务器控制台日志错误枚举。
 pub use error::ConsoleServiceError;
 /// 服务器定时任务错误枚举。
 pub use error::CronTaskServiceError;
 /// 下载任务管理错误枚举。
 pub use error::DownloadServiceError;
+/// 扩展启停错误枚举。
+pub use error::ExtensionServiceError;
+/// 加载器安装器执行错误枚举。
+pub use error::InstallerServiceError;
 /// 服务器实例管理错误枚举。
 pub use error::InstanceServiceError;
+/// 实例配置模板错误枚举。
+pub use error::InstanceTemplateServiceError;
 /// Java 检测与校验错误枚举。
 pub use error::JavaServiceError;
+/// 市场资源安装错误枚举。
+pub use error::MarketInstallServiceError;
 /// 在线隧道服务错误枚举。
 pub use error::OnlineTunnelServiceError;
+/// 在线玩家服务错误枚举。
+pub use error::PlayerServiceError;
 /// 服务端检查与实例供给计划失败类别。
 pub use error::ProvisioningServiceError;
 /// 服务器核心下载目录错误枚举。
 pub use error::ServerCatalogServiceError;
 /// 服务器进程管理错误枚举。
 pub use error::ServerServiceError;
+/// 服务端核心原地升级错误枚举。
+pub use error::ServerUpgradeServiceError;
 /// 设置信息服务错误枚举。
 pub use error::SettingsServiceError;
 /// 系统资源信息服务错误枚举。
@@ -66,15 +102,23 @@ pub use error::SystemServiceError;
 pub use error::UpdateCheckServiceError;
 /// 应用更新安装错误枚举。
 pub use error::UpdateInstallServiceError;
+/// 扩展启停服务端口。
+pub use extension::ExtensionService;
+/// 加载器安装器执行服务端口。
+pub use installer::InstallerService;
 /// 服务器实例记录管理服务端口。
 pub use instance::InstanceService;
 /// Java 检测与校验服务端口。
 pub use java::JavaService;
+/// 市场资源安装服务端口。
+pub use market::MarketInstallService;
 /// 在线隧道模型与服务端口。
 pub use online::{
     OnlineTunnelConnection, OnlineTunnelEvent, OnlineTunnelHostRequest, OnlineTunnelJoinRequest,
     OnlineTunnelMode, OnlineTunnelService, OnlineTunnelStatus,
 };
+/// 在线玩家服务端口。
+pub use player::PlayerService;
 /// 服务端检查与实例供给计划服务端口。
 pub use provisioning::ProvisioningService;
 /// 服务器进程管理服务端口。
@@ -83,7 +127,11 @@ pub use server::ServerService;
 pub use settings::SettingsService;
 /// 系统资源信息服务端口。
 pub use system::SystemService;
+/// 实例配置模板服务端口。
+pub use template::InstanceTemplateService;
 /// 应用更新检查服务端口。
 pub use update::UpdateCheckService;
 /// 应用更新安装服务端口。
 pub use update::UpdateInstallService;
+/// 服务端核心原地升级服务端口。
+pub use upgrade::ServerUpgradeService;
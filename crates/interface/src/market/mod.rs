@@ -0,0 +1,7 @@
+//! 市场资源安装、卸载与更新契约。
+
+mod models;
+mod service;
+
+pub use models::{MarketExtensionFile, MarketInstallRequest, MarketUpdateRequest};
+pub use service::MarketInstallService;
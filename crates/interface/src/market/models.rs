@@ -0,0 +1,33 @@
+//! 市场资源安装契约模型。
+//!
+//! 安装计划与安装记录直接使用 `extra` 的
+//! [`InstallPlan`](sealantern_extra::market::InstallPlan) 与
+//! [`InstalledResource`](sealantern_extra::market::InstalledResource)，更新检查结果与回滚记录
+//! 直接使用 [`ExtensionUpdate`](sealantern_extra::market::ExtensionUpdate) 与
+//! [`UpdateRollback`](sealantern_extra::market::UpdateRollback)；本模块只定义请求。
+
+use sealantern_extra::market::{InstallDirectory, MarketSource};
+use serde::Deserialize;
+
+/// 安装（或规划安装）一个市场资源的请求。
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct MarketInstallRequest {
+    pub source: MarketSource,
+    pub project_id: String,
+    /// 指定版本；为空时挑选适用于实例的最新版本。
+    #[serde(default)]
+    pub version_id: Option<String>,
+}
+
+/// 服务器扩展目录下的一个文件。
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct MarketExtensionFile {
+    pub directory: InstallDirectory,
+    pub file_name: String,
+}
+
+/// 更新选中文件的请求。
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct MarketUpdateRequest {
+    pub files: Vec<MarketExtensionFile>,
+}
@@ -0,0 +1,77 @@
+//! 市场资源安装服务端口。
+
+use async_trait::async_trait;
+use sealantern_core::instance::InstanceId;
+use sealantern_extra::market::{
+    ExtensionUpdate, InstallPlan, InstalledResource, MarketSource, UpdateRollback,
+};
+
+use crate::error::MarketInstallServiceError;
+
+use super::models::{MarketExtensionFile, MarketInstallRequest, MarketUpdateRequest};
+
+/// 市场资源安装宿主能力端口。
+///
+/// 实现方按实例的游戏版本与加载器挑选资源版本、递归解析必需依赖，把校验通过的文件
+/// 安装到 `plugins/` 或 `mods/`，并在服务器目录中记录安装结果供后续更新与卸载；
+/// 更新检查覆盖扩展目录下的全部 jar，未经安装记录的文件按摘要向市场识别。
+#[async_trait]
+pub trait MarketInstallService: Send + Sync {
+    /// 生成安装计划，不下载或写入任何内容。
+    async fn plan(
+        &self,
+        instance_id: &InstanceId,
+        request: MarketInstallRequest,
+    ) -> Result<InstallPlan, MarketInstallServiceError>;
+
+    /// 安装资源及其必需依赖，返回本次写入的安装记录。
+    async fn install(
+        &self,
+        instance_id: &InstanceId,
+        request: MarketInstallRequest,
+    ) -> Result<Vec<InstalledResource>, MarketInstallServiceError>;
+
+    /// 列出实例的安装记录。
+    async fn installed(
+        &self,
+        instance_id: &InstanceId,
+    ) -> Result<Vec<InstalledResource>, MarketInstallServiceError>;
+
+    /// 卸载资源，并一并移除不再被需要的自动安装依赖，返回被移除的记录。
+    ///
+    /// 仍有其他已安装资源依赖该资源时拒绝卸载。
+    async fn uninstall(
+        &self,
+        instance_id: &InstanceId,
+        source: MarketSource,
+        project_id: &str,
+    ) -> Result<Vec<InstalledResource>, MarketInstallServiceError>;
+
+    /// 检查实例 `plugins/`、`mods/` 下每个 jar 的当前版本与最新兼容版本。
+    async fn check_updates(
+        &self,
+        instance_id: &InstanceId,
+    ) -> Result<Vec<ExtensionUpdate>, MarketInstallServiceError>;
+
+    /// 更新选中的文件，返回本次产生的回滚记录。
+    ///
+    /// 新文件全部下载并校验通过后才替换，旧文件保留在备份目录中。
+    async fn update(
+        &self,
+        instance_id: &InstanceId,
+        request: MarketUpdateRequest,
+    ) -> Result<Vec<UpdateRollback>, MarketInstallServiceError>;
+
+    /// 列出实例可回滚的更新。
+    async fn update_rollbacks(
+        &self,
+        instance_id: &InstanceId,
+    ) -> Result<Vec<UpdateRollback>, MarketInstallServiceError>;
+
+    /// 回滚指定文件的最近一次更新，返回被消费的回滚记录。
+    async fn rollback_update(
+        &self,
+        instance_id: &InstanceId,
+        file: MarketExtensionFile,
+    ) -> Result<UpdateRollback, MarketInstallServiceError>;
+}
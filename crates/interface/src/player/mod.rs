@@ -0,0 +1,7 @@
+//! 服务器在线玩家与会话历史相关模型与服务端口。
+
+mod models;
+mod service;
+
+pub use models::{OnlinePlayer, PlayerSessionRecord};
+pub use service::PlayerService;
@@ -0,0 +1,33 @@
+//! 在线玩家契约模型。
+//!
+//! 定义宿主消费的在线玩家与会话历史记录，全部可序列化，供跨传输面传递。
+
+use serde::Serialize;
+
+/// 当前在线的玩家（宿主消费的契约模型）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OnlinePlayer {
+    /// 玩家名称。
+    pub name: String,
+    /// 玩家 UUID；离线模式或尚未解析时为 `None`。
+    pub uuid: Option<String>,
+    /// 本次加入时刻（Unix 秒）。
+    pub joined_at: i64,
+}
+
+/// 一次玩家在线会话记录（宿主消费的契约模型）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlayerSessionRecord {
+    /// 会话行号（单调递增）。
+    pub id: i64,
+    /// 玩家名称。
+    pub player: String,
+    /// 玩家 UUID；离线模式或未解析时为 `None`。
+    pub uuid: Option<String>,
+    /// 加入时刻（Unix 秒）。
+    pub joined_at: i64,
+    /// 离开时刻（Unix 秒）；仍在线时为 `None`。
+    pub left_at: Option<i64>,
+    /// 在线时长（秒）；仍在线时为 `None`。
+    pub duration_secs: Option<i64>,
+}
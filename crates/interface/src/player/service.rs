@@ -0,0 +1,29 @@
+//! 在线玩家服务端口。
+
+use async_trait::async_trait;
+use sealantern_core::instance::InstanceId;
+
+use crate::error::PlayerServiceError;
+
+use super::models::{OnlinePlayer, PlayerSessionRecord};
+
+/// 在线玩家与会话历史宿主能力端口。
+///
+/// 数据来源于服务器控制台输出（加入 / 离开、`list` 与 UUID 解析行），
+/// 无需服务端插件。在线集合仅对由 Sea Lantern 启动的进程可用；会话历史
+/// 按实例目录持久化，停止后仍可查询。
+#[async_trait]
+pub trait PlayerService: Send + Sync {
+    /// 查询实例当前在线的玩家，按加入时刻升序返回；未运行时为空。
+    async fn online(&self, id: &InstanceId) -> Result<Vec<OnlinePlayer>, PlayerServiceError>;
+
+    /// 按加入时刻倒序读取会话历史。
+    ///
+    /// `player` 提供时只返回该玩家的会话；`limit` 缺省时使用实现方默认窗口。
+    async fn sessions(
+        &self,
+        id: &InstanceId,
+        player: Option<String>,
+        limit: Option<i64>,
+    ) -> Result<Vec<PlayerSessionRecord>, PlayerServiceError>;
+}
@@ -2,7 +2,9 @@
 //!
 //! 定义宿主消费的服务器进程状态快照等模型，全部可序列化，供跨传输面传递。
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+
+use crate::console::ConsoleLogLine;
 
 /// 服务器进程运行状态。
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
@@ -32,3 +34,124 @@ pub struct ServerSnapshot {
     /// 异常退出信息；正常状态为 `None`。
     pub error_message: Option<String>,
 }
+
+/// 控制台命令的传输通道。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandTransport {
+    /// 写入受管进程的标准输入（仅限 Sea Lantern 拉起的进程，无响应文本）。
+    #[default]
+    Stdin,
+    /// 经 server.properties 中配置的 RCON 端口发送，可取回响应文本。
+    Rcon,
+}
+
+/// 控制台命令的发送结果。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CommandResponse {
+    /// 实际使用的传输通道。
+    pub transport: CommandTransport,
+    /// 命令的响应文本；stdin 通道无法关联响应，恒为 `None`。
+    pub output: Option<String>,
+}
+
+/// 发送命令并捕获其控制台输出的参数；字段缺省时由实现方取默认值。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct CaptureOptions {
+    /// 整体等待上限（毫秒）。
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// 收到首行输出后，连续无新输出多久视为结束（毫秒）。
+    #[serde(default)]
+    pub quiet_ms: Option<u64>,
+    /// 结束匹配的正则表达式；某行命中后立即结束（命中行包含在结果中）。
+    #[serde(default)]
+    pub until: Option<String>,
+    /// 命令来源；宿主请求恒为操作者，不从请求体读取。
+    #[serde(skip)]
+    pub origin: CommandOrigin,
+}
+
+/// 控制台命令的来源，决定是否套用插件命令过滤与是否记入命令历史。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CommandOrigin {
+    /// 操作者经宿主界面发送；记入命令历史。
+    #[default]
+    Operator,
+    /// 插件经能力调用发送；每条实际写入的命令须通过插件命令过滤。
+    Plugin,
+}
+
+/// 命令输出捕获的结束原因。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CaptureCompletion {
+    /// 某行命中了 `until` 匹配。
+    Matched,
+    /// 输出进入静默期。
+    Quiet,
+    /// 达到整体等待上限。
+    Timeout,
+}
+
+/// 命令发送后捕获到的控制台输出。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CommandCapture {
+    /// 命令发送后服务器输出的行（按行号游标升序）。
+    pub lines: Vec<ConsoleLogLine>,
+    /// 捕获的结束原因。
+    pub completion: CaptureCompletion,
+}
+
+/// 服务器状态探测结果（以客户端身份经 Server List Ping 获得）。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ServerProbe {
+    /// 实例标识。
+    pub instance_id: String,
+    /// 实际探测的主机。
+    pub host: String,
+    /// 实际探测的游戏端口。
+    pub port: u16,
+    /// 去除格式代码后的 MOTD。
+    pub motd: String,
+    /// 服务端报告的版本名。
+    pub version: String,
+    /// 服务端协议号；无法获知时为 `-1`。
+    pub protocol: i32,
+    /// 在线人数。
+    pub online_players: u32,
+    /// 最大人数。
+    pub max_players: u32,
+    /// 服务端给出的在线玩家样本。
+    pub sample: Vec<ProbePlayer>,
+    /// 往返延迟（毫秒）。
+    pub latency_ms: u64,
+    /// 是否经由 1.6 及以前的旧协议获得。
+    pub legacy: bool,
+    /// GameSpy4 查询结果；未开启 `enable-query` 或查询失败时为 `None`。
+    pub query: Option<ServerQueryInfo>,
+}
+
+/// 状态探测中的玩家样本。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ProbePlayer {
+    /// 玩家名。
+    pub name: String,
+    /// 玩家 UUID（离线服可能为占位值）。
+    pub id: String,
+}
+
+/// GameSpy4 查询得到的补充信息。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ServerQueryInfo {
+    /// 查询端口。
+    pub port: u16,
+    /// 游戏类型（通常为 `SMP`）。
+    pub game_type: String,
+    /// 主世界名。
+    pub map: String,
+    /// 插件列表原文，原版为空。
+    pub plugins: String,
+    /// 完整在线玩家名单。
+    pub players: Vec<String>,
+}
@@ -5,7 +5,9 @@ use sealantern_core::instance::InstanceId;
 
 use crate::error::ServerServiceError;
 
-use super::models::ServerSnapshot;
+use super::models::{
+    CaptureOptions, CommandCapture, CommandResponse, CommandTransport, ServerProbe, ServerSnapshot,
+};
 
 /// 服务器进程管理宿主能力端口。
 ///
@@ -36,4 +38,35 @@ pub trait ServerService: Send + Sync {
 
     /// 向服务器控制台发送单行命令。
     async fn send_command(&self, id: &InstanceId, command: &str) -> Result<(), ServerServiceError>;
+
+    /// 经指定传输通道发送单行命令，并返回能关联到的响应文本。
+    ///
+    /// [`CommandTransport::Stdin`] 等价于 [`send_command`](Self::send_command)；
+    /// [`CommandTransport::Rcon`] 读取实例 server.properties 的 RCON 配置建立连接，
+    /// 不要求进程由本服务拉起，未开启 RCON 时返回 [`ServerServiceError::InvalidState`]。
+    async fn send_command_via(
+        &self,
+        id: &InstanceId,
+        command: &str,
+        transport: CommandTransport,
+    ) -> Result<CommandResponse, ServerServiceError>;
+
+    /// 向服务器控制台发送单行命令，并捕获其后输出的控制台行。
+    ///
+    /// 以日志行号游标界定“命令之后”：收集发送后新落库的服务器输出，直到某行命中
+    /// `until`、首行输出后进入静默期或达到整体等待上限。控制台输出没有请求关联，
+    /// 同期其他来源的输出（玩家聊天等）也会被收入结果。
+    async fn send_command_and_capture(
+        &self,
+        id: &InstanceId,
+        command: &str,
+        options: CaptureOptions,
+    ) -> Result<CommandCapture, ServerServiceError>;
+
+    /// 以客户端身份探测服务器公开状态（MOTD、版本、在线人数与延迟）。
+    ///
+    /// 不依赖控制台输出；实例开启 `enable-query` 时附带 GameSpy4 查询结果。
+    /// 服务器未运行返回 [`ServerServiceError::InvalidState`]，探测超时或握手
+    /// 失败返回 [`ServerServiceError::OperationFailed`]。
+    async fn probe(&self, id: &InstanceId) -> Result<ServerProbe, ServerServiceError>;
 }
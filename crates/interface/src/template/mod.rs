@@ -0,0 +1,7 @@
+//! 实例配置模板与批量套用契约。
+
+mod models;
+mod service;
+
+pub use models::{TemplateFieldChange, TemplateFileDiff, TemplateInstanceReport};
+pub use service::InstanceTemplateService;
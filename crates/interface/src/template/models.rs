@@ -0,0 +1,44 @@
+//! 模板批量套用契约模型。
+//!
+//! 模板本身直接使用 `core` 的
+//! [`InstanceTemplate`](sealantern_core::instance::InstanceTemplate)；本模块定义
+//! 预览与套用时按实例返回的报告。
+
+use serde::Serialize;
+
+use crate::error::InstanceTemplateServiceError;
+
+/// 实例记录或备份设置中单个字段的变化。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateFieldChange {
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// 单个配置文件的修改预览。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateFileDiff {
+    /// 相对服务器根目录的文件路径。
+    pub file: String,
+    /// 统一差异格式（unified diff）文本。
+    pub diff: String,
+}
+
+/// 模板套用到单个实例的预览或结果。
+///
+/// `error` 非空时该实例未被修改（预览时表示套用将失败），其余实例不受影响。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TemplateInstanceReport {
+    pub instance_id: String,
+    pub instance_name: String,
+    /// 内存、JVM 参数、Java 路径与端口的变化。
+    pub launch_changes: Vec<TemplateFieldChange>,
+    /// 有实际变化的配置文件。
+    pub file_diffs: Vec<TemplateFileDiff>,
+    /// 将创建的定时任务名称（同名任务已存在时跳过）。
+    pub cron_tasks: Vec<String>,
+    /// 备份设置的变化。
+    pub backup_changes: Vec<TemplateFieldChange>,
+    pub error: Option<InstanceTemplateServiceError>,
+}
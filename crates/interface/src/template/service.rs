@@ -0,0 +1,41 @@
+//! 实例配置模板服务端口。
+
+use async_trait::async_trait;
+use sealantern_core::instance::{InstanceId, InstanceTemplate};
+
+use crate::error::InstanceTemplateServiceError;
+
+use super::models::TemplateInstanceReport;
+
+/// 实例配置模板宿主能力端口。
+///
+/// 实现方负责模板持久化，以及把模板中的启动默认值、配置文件修改、定时任务与
+/// 备份策略批量套用到已有实例。套用按实例逐个进行，单个实例失败不影响其余实例。
+#[async_trait]
+pub trait InstanceTemplateService: Send + Sync {
+    /// 列出全部模板。
+    async fn list(&self) -> Result<Vec<InstanceTemplate>, InstanceTemplateServiceError>;
+
+    /// 校验并保存模板；ID 为空时新建，否则覆盖同 ID 模板。
+    async fn save(
+        &self,
+        template: InstanceTemplate,
+    ) -> Result<InstanceTemplate, InstanceTemplateServiceError>;
+
+    /// 删除模板。
+    async fn delete(&self, id: &str) -> Result<(), InstanceTemplateServiceError>;
+
+    /// 预览模板套用到各实例后的变化，不写入任何内容。
+    async fn preview_apply(
+        &self,
+        id: &str,
+        instance_ids: &[InstanceId],
+    ) -> Result<Vec<TemplateInstanceReport>, InstanceTemplateServiceError>;
+
+    /// 把模板套用到各实例，返回每个实例实际写入的变化或失败原因。
+    async fn apply(
+        &self,
+        id: &str,
+        instance_ids: &[InstanceId],
+    ) -> Result<Vec<TemplateInstanceReport>, InstanceTemplateServiceError>;
+}
@@ -0,0 +1,7 @@
+//! 服务端核心原地升级契约。
+
+mod models;
+mod service;
+
+pub use models::{ServerUpgradeOutcome, ServerUpgradePlan, ServerUpgradeRequest, UpgradeJavaCheck};
+pub use service::ServerUpgradeService;
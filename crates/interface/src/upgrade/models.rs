@@ -0,0 +1,66 @@
+//! 服务端核心原地升级契约模型。
+
+use sealantern_core::instance::Instance;
+use sealantern_core::provisioning::ExtensionVersionMismatch;
+use sealantern_extra::backup::BackupItem;
+use sealantern_extra::catalog::CatalogBuild;
+use serde::{Deserialize, Serialize};
+
+/// 原地升级服务端核心的请求。
+///
+/// 字段均可省略：游戏版本默认为实例当前版本（只更新构建），构建默认为推荐构建。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct ServerUpgradeRequest {
+    /// 目标 Minecraft 版本。
+    #[serde(default)]
+    pub game_version: Option<String>,
+    /// 目标构建号（如 Paper 的 `15`）。
+    #[serde(default)]
+    pub build: Option<String>,
+    /// Java 不满足目标构建的要求时仍然升级。
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// 目标构建的 Java 要求与实例实际使用的 Java 的比较结果。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct UpgradeJavaCheck {
+    /// 目标构建要求的最低 Java 主版本；目录未声明时为 `None`。
+    pub required_major: Option<u16>,
+    /// 实例启动时使用的 Java（实例配置的，未配置时为本机最高版本）。
+    pub current_path: Option<String>,
+    /// `current_path` 的主版本；未在本机探测结果中找到时为 `None`。
+    pub current_major: Option<u32>,
+    pub satisfied: bool,
+    /// 本机满足要求的 Java 中主版本最低的一个；已满足或没有满足的 Java 时为 `None`。
+    pub suggested_path: Option<String>,
+}
+
+/// 升级前的检查结果，不修改实例。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ServerUpgradePlan {
+    pub instance_id: String,
+    pub core_type: String,
+    pub current_core_version: String,
+    pub current_game_version: String,
+    /// 目录解析出的目标构建。
+    pub target: CatalogBuild,
+    /// 为真表示目录查询失败，目标构建来自离线缓存。
+    pub catalog_stale: bool,
+    pub java: UpgradeJavaCheck,
+    /// 声明的游戏版本不包含目标版本的已启用插件与模组。
+    pub extension_warnings: Vec<ExtensionVersionMismatch>,
+}
+
+/// 升级完成后的结果。
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub struct ServerUpgradeOutcome {
+    pub plan: ServerUpgradePlan,
+    /// 升级前自动创建的备份。
+    pub backup: BackupItem,
+    /// 写回后的实例记录。
+    pub instance: Instance,
+}
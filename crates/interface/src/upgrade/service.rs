@@ -0,0 +1,32 @@
+//! 服务端核心原地升级服务端口。
+
+use async_trait::async_trait;
+use sealantern_core::instance::InstanceId;
+
+use crate::error::ServerUpgradeServiceError;
+
+use super::models::{ServerUpgradeOutcome, ServerUpgradePlan, ServerUpgradeRequest};
+
+/// 服务端核心原地升级宿主能力端口。
+///
+/// 从目录解析目标构建，检查 Java 要求与扩展声明的游戏版本；升级时先自动备份实例，
+/// 再替换服务端文件、更新实例的核心与游戏版本并刷新服务端元数据。只支持以 jar
+/// 直接启动的核心，Forge 等安装器核心由安装器服务处理。
+#[async_trait]
+pub trait ServerUpgradeService: Send + Sync {
+    /// 检查升级目标，不修改实例。
+    async fn plan(
+        &self,
+        id: &InstanceId,
+        request: ServerUpgradeRequest,
+    ) -> Result<ServerUpgradePlan, ServerUpgradeServiceError>;
+
+    /// 执行升级。
+    ///
+    /// 服务器未停止时拒绝；Java 不满足要求且未强制时拒绝。
+    async fn upgrade(
+        &self,
+        id: &InstanceId,
+        request: ServerUpgradeRequest,
+    ) -> Result<ServerUpgradeOutcome, ServerUpgradeServiceError>;
+}
@@ -10,9 +10,11 @@ use axum::response::{IntoResponse, Response};
 use serde::Serialize;
 
 use sealantern_interface::{
-    ConsoleServiceError, CronTaskServiceError, DownloadServiceError, InstanceServiceError,
-    ProvisioningServiceError, ServerServiceError, SettingsServiceError, SystemServiceError,
-    UpdateCheckServiceError,
+    CommandLibraryServiceError, ConfigServiceError, ConsoleServiceError, CronTaskServiceError,
+    DownloadServiceError, ExtensionServiceError, InstallerServiceError, InstanceServiceError,
+    InstanceTemplateServiceError, MarketInstallServiceError, PlayerServiceError,
+    ProvisioningServiceError, ServerServiceError, ServerUpgradeServiceError, SettingsServiceError,
+    SystemServiceError, UpdateCheckServiceError,
 };
 
 /// 展平的 HTTP 错误响应体。
@@ -101,6 +103,11 @@ impl HttpError {
                 code: "instance_already_exists",
                 message: error.to_string(),
             },
+            InstanceServiceError::PortConflict => Self {
+                status: StatusCode::CONFLICT,
+                code: "instance_port_conflict",
+                message: error.to_string(),
+            },
             InstanceServiceError::InvalidInput => Self {
                 status: StatusCode::BAD_REQUEST,
                 code: "invalid_input",
@@ -182,6 +189,11 @@ impl HttpError {
                 code: "invalid_input",
                 message: error.to_string(),
             },
+            ServerServiceError::CommandRejected => Self {
+                status: StatusCode::FORBIDDEN,
+                code: "command_rejected",
+                message: error.to_string(),
+            },
             ServerServiceError::OperationFailed => Self {
                 status: StatusCode::INTERNAL_SERVER_ERROR,
                 code: "server_operation_failed",
@@ -195,6 +207,319 @@ impl HttpError {
         }
     }
 
+    /// 由在线玩家服务契约错误构建 HTTP 错误。
+    pub fn from_player_error(error: PlayerServiceError) -> Self {
+        match error {
+            PlayerServiceError::InstanceNotFound => Self {
+                status: StatusCode::NOT_FOUND,
+                code: "instance_not_found",
+                message: error.to_string(),
+            },
+            PlayerServiceError::InvalidInput => Self {
+                status: StatusCode::BAD_REQUEST,
+                code: "invalid_player_query",
+                message: error.to_string(),
+            },
+            PlayerServiceError::OperationFailed => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                code: "player_operation_failed",
+                message: error.to_string(),
+            },
+        }
+    }
+
+    /// 由命令库服务契约错误构建 HTTP 错误。
+    pub fn from_command_library_error(error: CommandLibraryServiceError) -> Self {
+        match error {
+            CommandLibraryServiceError::InstanceNotFound => Self {
+                status: StatusCode::NOT_FOUND,
+                code: "instance_not_found",
+                message: error.to_string(),
+            },
+            CommandLibraryServiceError::MacroNotFound => Self {
+                status: StatusCode::NOT_FOUND,
+                code: "command_macro_not_found",
+                message: error.to_string(),
+            },
+            CommandLibraryServiceError::InvalidInput => Self {
+                status: StatusCode::BAD_REQUEST,
+                code: "invalid_command_library_input",
+                message: error.to_string(),
+            },
+            CommandLibraryServiceError::OperationFailed => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                code: "command_library_operation_failed",
+                message: error.to_string(),
+            },
+        }
+    }
+
+    /// 由实例配置模板服务契约错误构建 HTTP 错误。
+    pub fn from_template_error(error: InstanceTemplateServiceError) -> Self {
+        match error {
+            InstanceTemplateServiceError::TemplateNotFound => Self {
+                status: StatusCode::NOT_FOUND,
+                code: "instance_template_not_found",
+                message: error.to_string(),
+            },
+            InstanceTemplateServiceError::InstanceNotFound => Self {
+                status: StatusCode::NOT_FOUND,
+                code: "instance_not_found",
+                message: error.to_string(),
+            },
+            InstanceTemplateServiceError::InvalidInput => Self {
+                status: StatusCode::BAD_REQUEST,
+                code: "invalid_instance_template",
+                message: error.to_string(),
+            },
+            InstanceTemplateServiceError::PortConflict => Self {
+                status: StatusCode::CONFLICT,
+                code: "instance_template_port_conflict",
+                message: error.to_string(),
+            },
+            InstanceTemplateServiceError::ConfigFailed => Self {
+                status: StatusCode::UNPROCESSABLE_ENTITY,
+                code: "instance_template_config_failed",
+                message: error.to_string(),
+            },
+            InstanceTemplateServiceError::StorageFailed => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                code: "instance_template_storage_failed",
+                message: error.to_string(),
+            },
+            InstanceTemplateServiceError::OperationFailed => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                code: "instance_template_operation_failed",
+                message: error.to_string(),
+            },
+        }
+    }
+
+    /// 由配置修订服务契约错误构建 HTTP 错误。
+    pub fn from_config_error(error: ConfigServiceError) -> Self {
+        match error {
+            ConfigServiceError::InstanceNotFound => Self {
+                status: StatusCode::NOT_FOUND,
+                code: "instance_not_found",
+                message: error.to_string(),
+            },
+            ConfigServiceError::RevisionNotFound => Self {
+                status: StatusCode::NOT_FOUND,
+                code: "config_revision_not_found",
+                message: error.to_string(),
+            },
+            ConfigServiceError::InvalidInput => Self {
+                status: StatusCode::BAD_REQUEST,
+                code: "invalid_config_revision_input",
+                message: error.to_string(),
+            },
+            ConfigServiceError::StorageFailed => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                code: "config_revision_storage_failed",
+                message: error.to_string(),
+            },
+            ConfigServiceError::OperationFailed => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                code: "config_revision_operation_failed",
+                message: error.to_string(),
+            },
+        }
+    }
+
+    /// 由扩展启停服务契约错误构建 HTTP 错误。
+    pub fn from_extension_error(error: ExtensionServiceError) -> Self {
+        match error {
+            ExtensionServiceError::InstanceNotFound => Self {
+                status: StatusCode::NOT_FOUND,
+                code: "instance_not_found",
+                message: error.to_string(),
+            },
+            ExtensionServiceError::ExtensionNotFound => Self {
+                status: StatusCode::NOT_FOUND,
+                code: "extension_not_found",
+                message: error.to_string(),
+            },
+            ExtensionServiceError::InvalidInput => Self {
+                status: StatusCode::BAD_REQUEST,
+                code: "invalid_extension_input",
+                message: error.to_string(),
+            },
+            ExtensionServiceError::ServerRunning => Self {
+                status: StatusCode::CONFLICT,
+                code: "server_running",
+                message: error.to_string(),
+            },
+            ExtensionServiceError::TargetExists => Self {
+                status: StatusCode::CONFLICT,
+                code: "extension_target_exists",
+                message: error.to_string(),
+            },
+            ExtensionServiceError::StorageFailed => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                code: "extension_storage_failed",
+                message: error.to_string(),
+            },
+            ExtensionServiceError::OperationFailed => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                code: "extension_operation_failed",
+                message: error.to_string(),
+            },
+        }
+    }
+
+    /// 由加载器安装器执行服务契约错误构建 HTTP 错误。
+    pub fn from_installer_error(error: InstallerServiceError) -> Self {
+        match error {
+            InstallerServiceError::InstanceNotFound => Self {
+                status: StatusCode::NOT_FOUND,
+                code: "instance_not_found",
+                message: error.to_string(),
+            },
+            InstallerServiceError::UnsupportedCore => Self {
+                status: StatusCode::BAD_REQUEST,
+                code: "installer_unsupported_core",
+                message: error.to_string(),
+            },
+            InstallerServiceError::InvalidInput => Self {
+                status: StatusCode::BAD_REQUEST,
+                code: "invalid_installer_input",
+                message: error.to_string(),
+            },
+            InstallerServiceError::ServerRunning => Self {
+                status: StatusCode::CONFLICT,
+                code: "server_running",
+                message: error.to_string(),
+            },
+            InstallerServiceError::AlreadyInstalling => Self {
+                status: StatusCode::CONFLICT,
+                code: "installer_already_running",
+                message: error.to_string(),
+            },
+            InstallerServiceError::OperationFailed => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                code: "installer_operation_failed",
+                message: error.to_string(),
+            },
+        }
+    }
+
+    /// 由服务端核心原地升级服务契约错误构建 HTTP 错误。
+    pub fn from_upgrade_error(error: ServerUpgradeServiceError) -> Self {
+        match error {
+            ServerUpgradeServiceError::InstanceNotFound => Self {
+                status: StatusCode::NOT_FOUND,
+                code: "instance_not_found",
+                message: error.to_string(),
+            },
+            ServerUpgradeServiceError::InvalidInput => Self {
+                status: StatusCode::BAD_REQUEST,
+                code: "invalid_upgrade_input",
+                message: error.to_string(),
+            },
+            ServerUpgradeServiceError::UnsupportedCore => Self {
+                status: StatusCode::BAD_REQUEST,
+                code: "upgrade_unsupported_core",
+                message: error.to_string(),
+            },
+            ServerUpgradeServiceError::ServerRunning => Self {
+                status: StatusCode::CONFLICT,
+                code: "server_running",
+                message: error.to_string(),
+            },
+            ServerUpgradeServiceError::BuildNotFound => Self {
+                status: StatusCode::NOT_FOUND,
+                code: "upgrade_build_not_found",
+                message: error.to_string(),
+            },
+            ServerUpgradeServiceError::CatalogUnavailable => Self {
+                status: StatusCode::BAD_GATEWAY,
+                code: "catalog_unavailable",
+                message: error.to_string(),
+            },
+            ServerUpgradeServiceError::JavaRequirementUnmet => Self {
+                status: StatusCode::CONFLICT,
+                code: "java_requirement_unmet",
+                message: error.to_string(),
+            },
+            ServerUpgradeServiceError::BackupFailed => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                code: "upgrade_backup_failed",
+                message: error.to_string(),
+            },
+            ServerUpgradeServiceError::DownloadFailed => Self {
+                status: StatusCode::BAD_GATEWAY,
+                code: "upgrade_download_failed",
+                message: error.to_string(),
+            },
+            ServerUpgradeServiceError::OperationFailed => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                code: "upgrade_operation_failed",
+                message: error.to_string(),
+            },
+        }
+    }
+
+    /// 由市场资源安装服务契约错误构建 HTTP 错误。
+    pub fn from_market_install_error(error: MarketInstallServiceError) -> Self {
+        match error {
+            MarketInstallServiceError::InstanceNotFound => Self {
+                status: StatusCode::NOT_FOUND,
+                code: "instance_not_found",
+                message: error.to_string(),
+            },
+            MarketInstallServiceError::ResourceNotInstalled => Self {
+                status: StatusCode::NOT_FOUND,
+                code: "market_resource_not_installed",
+                message: error.to_string(),
+            },
+            MarketInstallServiceError::InvalidInput => Self {
+                status: StatusCode::BAD_REQUEST,
+                code: "invalid_market_install_input",
+                message: error.to_string(),
+            },
+            MarketInstallServiceError::UnsupportedInstance => Self {
+                status: StatusCode::UNPROCESSABLE_ENTITY,
+                code: "market_install_unsupported_instance",
+                message: error.to_string(),
+            },
+            MarketInstallServiceError::PlanBlocked => Self {
+                status: StatusCode::CONFLICT,
+                code: "market_install_plan_blocked",
+                message: error.to_string(),
+            },
+            MarketInstallServiceError::DependencyInUse => Self {
+                status: StatusCode::CONFLICT,
+                code: "market_resource_dependency_in_use",
+                message: error.to_string(),
+            },
+            MarketInstallServiceError::MarketUnavailable => Self {
+                status: StatusCode::BAD_GATEWAY,
+                code: "market_unavailable",
+                message: error.to_string(),
+            },
+            MarketInstallServiceError::VerificationFailed => Self {
+                status: StatusCode::BAD_GATEWAY,
+                code: "market_download_verification_failed",
+                message: error.to_string(),
+            },
+            MarketInstallServiceError::RollbackUnavailable => Self {
+                status: StatusCode::CONFLICT,
+                code: "market_update_rollback_unavailable",
+                message: error.to_string(),
+            },
+            MarketInstallServiceError::StorageFailed => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                code: "market_install_storage_failed",
+                message: error.to_string(),
+            },
+            MarketInstallServiceError::OperationFailed => Self {
+                status: StatusCode::INTERNAL_SERVER_ERROR,
+                code: "market_install_operation_failed",
+                message: error.to_string(),
+            },
+        }
+    }
+
     /// 由设置信息服务契约错误构建 HTTP 错误。
     pub fn from_settings_error(error: SettingsServiceError) -> Self {
         match error {
@@ -301,6 +626,11 @@ impl HttpError {
                 code: "download_operation_failed",
                 message: error.to_string(),
             },
+            DownloadServiceError::VerificationFailed => Self {
+                status: StatusCode::BAD_GATEWAY,
+                code: "download_verification_failed",
+                message: error.to_string(),
+            },
             DownloadServiceError::Unsupported => Self {
                 status: StatusCode::NOT_IMPLEMENTED,
                 code: "operation_unsupported",
@@ -373,6 +703,54 @@ impl From<ConsoleServiceError> for HttpError {
     }
 }
 
+impl From<PlayerServiceError> for HttpError {
+    fn from(error: PlayerServiceError) -> Self {
+        Self::from_player_error(error)
+    }
+}
+
+impl From<CommandLibraryServiceError> for HttpError {
+    fn from(error: CommandLibraryServiceError) -> Self {
+        Self::from_command_library_error(error)
+    }
+}
+
+impl From<InstanceTemplateServiceError> for HttpError {
+    fn from(error: InstanceTemplateServiceError) -> Self {
+        Self::from_template_error(error)
+    }
+}
+
+impl From<ConfigServiceError> for HttpError {
+    fn from(error: ConfigServiceError) -> Self {
+        Self::from_config_error(error)
+    }
+}
+
+impl From<ExtensionServiceError> for HttpError {
+    fn from(error: ExtensionServiceError) -> Self {
+        Self::from_extension_error(error)
+    }
+}
+
+impl From<InstallerServiceError> for HttpError {
+    fn from(error: InstallerServiceError) -> Self {
+        Self::from_installer_error(error)
+    }
+}
+
+impl From<ServerUpgradeServiceError> for HttpError {
+    fn from(error: ServerUpgradeServiceError) -> Self {
+        Self::from_upgrade_error(error)
+    }
+}
+
+impl From<MarketInstallServiceError> for HttpError {
+    fn from(error: MarketInstallServiceError) -> Self {
+        Self::from_market_install_error(error)
+    }
+}
+
 impl From<SettingsServiceError> for HttpError {
     fn from(error: SettingsServiceError) -> Self {
         Self::from_settings_error(error)
@@ -0,0 +1,96 @@
+//! 控制台命令历史与宏 REST handler。
+//!
+//! 提供命令历史检索与别名 / 宏维护接口，薄转发到
+//! [`CoreCommandLibraryService`](sealantern_application::service::CoreCommandLibraryService)
+//! 并收敛错误为 [`HttpError`](super::super::error::HttpError)。
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use serde::Deserialize;
+
+use sealantern_core::instance::InstanceId;
+use sealantern_interface::CommandLibraryService;
+use sealantern_interface::command::{CommandHistoryEntry, CommandMacroDefinition};
+
+use super::super::error::HttpError;
+use super::super::state::AppState;
+
+/// 命令历史的查询参数。
+#[derive(Debug, Default, Deserialize)]
+pub struct CommandHistoryQuery {
+    /// 只返回包含该文本的命令（缺省返回全部）。
+    pub query: Option<String>,
+    /// 最近 N 条命令（缺省使用服务默认窗口）。
+    pub limit: Option<i64>,
+}
+
+/// 解析路径参数中的实例 ID，非法输入视为客户端错误。
+fn parse_id(raw: &str) -> Result<InstanceId, HttpError> {
+    InstanceId::new(raw.to_owned())
+        .map_err(|_| HttpError::bad_request("invalid_instance_id", "invalid instance id"))
+}
+
+/// `GET /api/instances/{id}/command-history?query=&limit=` — 检索命令历史。
+pub async fn command_history(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<CommandHistoryQuery>,
+) -> Result<Json<Vec<CommandHistoryEntry>>, HttpError> {
+    let id = parse_id(&id)?;
+    state
+        .command_library()
+        .history(&id, query.query, query.limit)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `DELETE /api/instances/{id}/command-history` — 清空命令历史。
+pub async fn clear_command_history(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, HttpError> {
+    let id = parse_id(&id)?;
+    state.command_library().clear_history(&id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `GET /api/instances/{id}/command-macros` — 列出别名与宏。
+pub async fn list_command_macros(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<CommandMacroDefinition>>, HttpError> {
+    let id = parse_id(&id)?;
+    state
+        .command_library()
+        .macros(&id)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `PUT /api/instances/{id}/command-macros` — 新建或覆盖同名别名 / 宏。
+pub async fn save_command_macro(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(definition): Json<CommandMacroDefinition>,
+) -> Result<Json<CommandMacroDefinition>, HttpError> {
+    let id = parse_id(&id)?;
+    state
+        .command_library()
+        .save_macro(&id, definition)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `DELETE /api/instances/{id}/command-macros/{name}` — 删除别名 / 宏。
+pub async fn delete_command_macro(
+    State(state): State<AppState>,
+    Path((id, name)): Path<(String, String)>,
+) -> Result<StatusCode, HttpError> {
+    let id = parse_id(&id)?;
+    state.command_library().delete_macro(&id, &name).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,91 @@
+//! 配置修订 REST handler。
+//!
+//! 提供实例配置文件修订的列表、详情、比较与恢复接口，薄转发到
+//! [`CoreConfigService`](sealantern_application::service::CoreConfigService)
+//! 并收敛错误为 [`HttpError`](super::super::error::HttpError)。
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use serde::Deserialize;
+
+use sealantern_core::app_plugin::ExecutionPrincipal;
+use sealantern_core::instance::InstanceId;
+use sealantern_interface::ConfigRevisionService;
+use sealantern_interface::config::{ConfigRevision, ConfigRevisionSummary};
+
+use super::super::error::HttpError;
+use super::super::state::AppState;
+
+/// 修订列表的查询参数。
+#[derive(Debug, Default, Deserialize)]
+pub struct ConfigRevisionQuery {
+    /// 只返回该文件（相对服务器目录）的修订。
+    pub file: Option<String>,
+    /// 最多返回的条数（缺省使用服务默认条数）。
+    pub limit: Option<i64>,
+}
+
+/// 修订比较的查询参数。
+#[derive(Debug, Deserialize)]
+pub struct ConfigRevisionDiffQuery {
+    pub from: i64,
+    pub to: i64,
+}
+
+/// 解析路径参数中的实例 ID，非法输入视为客户端错误。
+fn parse_id(raw: &str) -> Result<InstanceId, HttpError> {
+    InstanceId::new(raw.to_owned())
+        .map_err(|_| HttpError::bad_request("invalid_instance_id", "invalid instance id"))
+}
+
+/// `GET /api/instances/{id}/config-revisions` — 按时间倒序列出配置修订。
+pub async fn list_config_revisions(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<ConfigRevisionQuery>,
+) -> Result<Json<Vec<ConfigRevisionSummary>>, HttpError> {
+    let id = parse_id(&id)?;
+    let revisions = state
+        .config()
+        .list_revisions(&id, query.file.as_deref(), query.limit)
+        .await?;
+    Ok(Json(revisions))
+}
+
+/// `GET /api/instances/{id}/config-revisions/diff?from=&to=` — 比较两条修订。
+pub async fn diff_config_revisions(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<ConfigRevisionDiffQuery>,
+) -> Result<Json<String>, HttpError> {
+    let id = parse_id(&id)?;
+    let diff = state
+        .config()
+        .diff_revisions(&id, query.from, query.to)
+        .await?;
+    Ok(Json(diff))
+}
+
+/// `GET /api/instances/{id}/config-revisions/{revision}` — 读取修订及其完整快照。
+pub async fn get_config_revision(
+    State(state): State<AppState>,
+    Path((id, revision)): Path<(String, i64)>,
+) -> Result<Json<ConfigRevision>, HttpError> {
+    let id = parse_id(&id)?;
+    let revision = state.config().get_revision(&id, revision).await?;
+    Ok(Json(revision))
+}
+
+/// `POST /api/instances/{id}/config-revisions/{revision}/restore` — 恢复到指定修订；
+/// 内容未变化时返回 `null`。
+pub async fn restore_config_revision(
+    State(state): State<AppState>,
+    Path((id, revision)): Path<(String, i64)>,
+) -> Result<Json<Option<ConfigRevisionSummary>>, HttpError> {
+    let id = parse_id(&id)?;
+    let restored = state
+        .config()
+        .restore_revision(&id, revision, &ExecutionPrincipal::BuiltInHost)
+        .await?;
+    Ok(Json(restored))
+}
@@ -1,6 +1,6 @@
 //! 下载任务管理 REST handler。
 //!
-//! 提供下载任务创建、进度查询与取消接口，薄转发到
+//! 提供下载任务创建、进度查询、暂停与恢复、取消与全局限制接口，薄转发到
 //! [`CoreDownloadService`](sealantern_application::service::CoreDownloadService)
 //! 并收敛错误为 [`HttpError`](super::super::error::HttpError)。
 
@@ -8,7 +8,9 @@ use axum::Json;
 use axum::extract::{Path, State};
 
 use sealantern_interface::DownloadService;
-use sealantern_interface::download::{DownloadRequest, DownloadTaskInfo};
+use sealantern_interface::download::{
+    DownloadLimits, DownloadPriority, DownloadRequest, DownloadTaskInfo, ResumedDownload,
+};
 
 use super::super::error::HttpError;
 use super::super::state::AppState;
@@ -18,9 +20,23 @@ use super::super::state::AppState;
 #[serde(rename_all = "snake_case")]
 pub struct CreateDownloadBody {
     url: String,
+    #[serde(default)]
+    mirrors: Vec<String>,
     save_path: String,
     #[serde(default = "default_thread_count")]
     thread_count: usize,
+    #[serde(default)]
+    expected_size: Option<u64>,
+    #[serde(default)]
+    sha1: Option<String>,
+    #[serde(default)]
+    sha256: Option<String>,
+    #[serde(default)]
+    sha512: Option<String>,
+    #[serde(default)]
+    priority: DownloadPriority,
+    #[serde(default)]
+    bandwidth_limit: Option<u64>,
 }
 
 fn default_thread_count() -> usize {
@@ -36,8 +52,15 @@ pub async fn create_download(
         .download()
         .create(DownloadRequest {
             url: body.url,
+            mirrors: body.mirrors,
             save_path: body.save_path,
             thread_count: body.thread_count,
+            expected_size: body.expected_size,
+            sha1: body.sha1,
+            sha256: body.sha256,
+            sha512: body.sha512,
+            priority: body.priority,
+            bandwidth_limit: body.bandwidth_limit,
         })
         .await
         .map_err(HttpError::from)?;
@@ -69,3 +92,61 @@ pub async fn cancel_download(
 ) -> Result<(), HttpError> {
     state.download().cancel(&id).await.map_err(HttpError::from)
 }
+
+/// `POST /api/downloads/{id}/pause` — 暂停下载任务。
+pub async fn pause_download(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<(), HttpError> {
+    state.download().pause(&id).await.map_err(HttpError::from)
+}
+
+/// `POST /api/downloads/{id}/resume` — 恢复已暂停或上次未完成的下载任务。
+pub async fn resume_download(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<DownloadTaskInfo>, HttpError> {
+    state
+        .download()
+        .resume(&id)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `GET /api/downloads/limits` — 查询全局下载限制。
+pub async fn get_download_limits(
+    State(state): State<AppState>,
+) -> Result<Json<DownloadLimits>, HttpError> {
+    state
+        .download()
+        .limits()
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `PUT /api/downloads/limits` — 修改全局下载限制。
+pub async fn update_download_limits(
+    State(state): State<AppState>,
+    Json(limits): Json<DownloadLimits>,
+) -> Result<Json<DownloadLimits>, HttpError> {
+    state
+        .download()
+        .set_limits(limits)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `POST /api/downloads/resume` — 续传上次运行中未完成的下载任务。
+pub async fn resume_downloads(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<ResumedDownload>>, HttpError> {
+    state
+        .download()
+        .resume_incomplete()
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
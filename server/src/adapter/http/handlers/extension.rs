@@ -0,0 +1,33 @@
+//! 扩展启停 REST handler。
+//!
+//! 提供插件、模组与数据包的启用 / 禁用接口，薄转发到
+//! [`CoreExtensionService`](sealantern_application::service::CoreExtensionService)
+//! 并收敛错误为 [`HttpError`](super::super::error::HttpError)。
+
+use axum::Json;
+use axum::extract::{Path, State};
+
+use sealantern_core::instance::{InstanceExtension, InstanceId};
+use sealantern_interface::ExtensionService;
+use sealantern_interface::extension::ExtensionToggleRequest;
+
+use super::super::error::HttpError;
+use super::super::state::AppState;
+
+/// 解析路径参数中的实例 ID，非法输入视为客户端错误。
+fn parse_id(raw: &str) -> Result<InstanceId, HttpError> {
+    InstanceId::new(raw.to_owned())
+        .map_err(|_| HttpError::bad_request("invalid_instance_id", "invalid instance id"))
+}
+
+/// `POST /api/instances/{id}/extensions/toggle` — 启用或禁用一个扩展；
+/// 服务器运行中且未强制时返回 409。
+pub async fn toggle_instance_extension(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<ExtensionToggleRequest>,
+) -> Result<Json<InstanceExtension>, HttpError> {
+    let id = parse_id(&id)?;
+    let extension = state.extension().set_enabled(&id, request).await?;
+    Ok(Json(extension))
+}
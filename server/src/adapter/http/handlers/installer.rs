@@ -0,0 +1,44 @@
+//! 加载器安装器 REST handler。
+//!
+//! 提供 Forge / NeoForge / Fabric 安装器的启动与状态查询接口，薄转发到
+//! [`CoreInstallerService`](sealantern_application::service::CoreInstallerService)
+//! 并收敛错误为 [`HttpError`](super::super::error::HttpError)。
+
+use axum::Json;
+use axum::extract::{Path, State};
+
+use sealantern_core::instance::InstanceId;
+use sealantern_interface::InstallerService;
+use sealantern_interface::installer::{InstallerRequest, InstallerTaskInfo};
+
+use super::super::error::HttpError;
+use super::super::state::AppState;
+
+/// 解析路径参数中的实例 ID，非法输入视为客户端错误。
+fn parse_id(raw: &str) -> Result<InstanceId, HttpError> {
+    InstanceId::new(raw.to_owned())
+        .map_err(|_| HttpError::bad_request("invalid_instance_id", "invalid instance id"))
+}
+
+/// `POST /api/instances/{id}/installer` — 在后台下载并执行安装器；
+/// 服务器运行中或已有安装任务时返回 409。
+pub async fn start_installer(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<InstallerRequest>,
+) -> Result<Json<InstallerTaskInfo>, HttpError> {
+    let id = parse_id(&id)?;
+    let task = state.installer().start(&id, request).await?;
+    Ok(Json(task))
+}
+
+/// `GET /api/instances/{id}/installer` — 查询实例最近一次安装任务，
+/// 从未安装过时返回 `null`。
+pub async fn installer_status(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Option<InstallerTaskInfo>>, HttpError> {
+    let id = parse_id(&id)?;
+    let task = state.installer().status(&id).await?;
+    Ok(Json(task))
+}
@@ -8,7 +8,9 @@ use axum::Json;
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
 
-use sealantern_core::instance::{Instance, InstanceId, InstanceSpec};
+use sealantern_core::instance::{
+    Instance, InstanceExtensionReport, InstanceId, InstanceListEntry, InstanceSpec, PortConflict,
+};
 use sealantern_core::provisioning::ImportExistingServerRequest;
 use sealantern_interface::InstanceService;
 
@@ -29,19 +31,31 @@ pub struct UpdatePathRequest {
     pub path: String,
 }
 
-/// `GET /api/instances` — 列出全部实例。
+/// `GET /api/instances` — 列出全部实例，附带各实例涉及的端口冲突。
 pub async fn list_instances(
     State(state): State<AppState>,
-) -> Result<Json<Vec<Instance>>, HttpError> {
+) -> Result<Json<Vec<InstanceListEntry>>, HttpError> {
     state
         .instance()
-        .list()
+        .list_entries()
         .await
         .map(Json)
         .map_err(HttpError::from)
 }
 
-/// `POST /api/instances` — 创建新实例。
+/// `GET /api/instances/port-conflicts` — 列出实例之间的端口冲突。
+pub async fn list_port_conflicts(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<PortConflict>>, HttpError> {
+    state
+        .instance()
+        .port_conflicts()
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `POST /api/instances` — 创建新实例；端口冲突时返回 409。
 pub async fn create_instance(
     State(state): State<AppState>,
     Json(spec): Json<InstanceSpec>,
@@ -64,6 +78,16 @@ pub async fn get_instance(
     Ok(Json(instance))
 }
 
+/// `GET /api/instances/{id}/extensions` — 列出插件、模组与数据包及其一致性问题。
+pub async fn list_instance_extensions(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<InstanceExtensionReport>, HttpError> {
+    let id = parse_id(&id)?;
+    let report = state.instance().extensions(&id).await?;
+    Ok(Json(report))
+}
+
 /// `DELETE /api/instances/{id}` — 删除实例；不存在时返回 404。
 pub async fn delete_instance(
     State(state): State<AppState>,
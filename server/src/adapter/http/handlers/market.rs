@@ -0,0 +1,151 @@
+//! 市场资源安装 REST handler。
+//!
+//! 提供实例资源的安装规划、安装、卸载、安装记录查询以及更新检查、更新与回滚接口，薄转发到
+//! [`CoreMarketInstallService`](sealantern_application::service::CoreMarketInstallService)
+//! 并收敛错误为 [`HttpError`](super::super::error::HttpError)。
+
+use axum::Json;
+use axum::extract::{Path, State};
+use serde::Deserialize;
+
+use sealantern_core::instance::InstanceId;
+use sealantern_extra::market::{
+    ExtensionUpdate, InstallPlan, InstalledResource, MarketSource, UpdateRollback,
+};
+use sealantern_interface::MarketInstallService;
+use sealantern_interface::market::{
+    MarketExtensionFile, MarketInstallRequest, MarketUpdateRequest,
+};
+
+use super::super::error::HttpError;
+use super::super::state::AppState;
+
+/// 卸载资源的请求体。
+#[derive(Debug, Deserialize)]
+pub struct MarketUninstallRequest {
+    pub source: MarketSource,
+    pub project_id: String,
+}
+
+/// 解析路径参数中的实例 ID，非法输入视为客户端错误。
+fn parse_id(raw: &str) -> Result<InstanceId, HttpError> {
+    InstanceId::new(raw.to_owned())
+        .map_err(|_| HttpError::bad_request("invalid_instance_id", "invalid instance id"))
+}
+
+/// `GET /api/instances/{id}/market/installed` — 列出实例的安装记录。
+pub async fn list_installed_resources(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<InstalledResource>>, HttpError> {
+    let id = parse_id(&id)?;
+    state
+        .market_install()
+        .installed(&id)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `POST /api/instances/{id}/market/plan` — 生成安装计划，不写入任何内容。
+pub async fn plan_market_install(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<MarketInstallRequest>,
+) -> Result<Json<InstallPlan>, HttpError> {
+    let id = parse_id(&id)?;
+    state
+        .market_install()
+        .plan(&id, request)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `POST /api/instances/{id}/market/install` — 安装资源及其必需依赖。
+pub async fn install_market_resource(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<MarketInstallRequest>,
+) -> Result<Json<Vec<InstalledResource>>, HttpError> {
+    let id = parse_id(&id)?;
+    state
+        .market_install()
+        .install(&id, request)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `POST /api/instances/{id}/market/uninstall` — 卸载资源并清理不再需要的依赖。
+pub async fn uninstall_market_resource(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<MarketUninstallRequest>,
+) -> Result<Json<Vec<InstalledResource>>, HttpError> {
+    let id = parse_id(&id)?;
+    state
+        .market_install()
+        .uninstall(&id, request.source, &request.project_id)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `GET /api/instances/{id}/market/updates` — 检查扩展目录下每个 jar 的可用更新。
+pub async fn check_market_updates(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<ExtensionUpdate>>, HttpError> {
+    let id = parse_id(&id)?;
+    state
+        .market_install()
+        .check_updates(&id)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `POST /api/instances/{id}/market/updates` — 更新选中的文件，旧文件保留备份。
+pub async fn apply_market_updates(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<MarketUpdateRequest>,
+) -> Result<Json<Vec<UpdateRollback>>, HttpError> {
+    let id = parse_id(&id)?;
+    state
+        .market_install()
+        .update(&id, request)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `GET /api/instances/{id}/market/updates/rollbacks` — 列出可回滚的更新。
+pub async fn list_market_update_rollbacks(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<UpdateRollback>>, HttpError> {
+    let id = parse_id(&id)?;
+    state
+        .market_install()
+        .update_rollbacks(&id)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `POST /api/instances/{id}/market/updates/rollback` — 换回指定文件更新前的旧文件。
+pub async fn rollback_market_update(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(file): Json<MarketExtensionFile>,
+) -> Result<Json<UpdateRollback>, HttpError> {
+    let id = parse_id(&id)?;
+    state
+        .market_install()
+        .rollback_update(&id, file)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
@@ -2,31 +2,63 @@
 //!
 //! handler 只做传输层薄转发：解析请求 → 调用应用层服务 → 收敛错误。
 
+pub mod command;
+pub mod config;
 pub mod console;
 pub mod cron;
 pub mod download;
+pub mod extension;
+pub mod installer;
 pub mod instance;
+pub mod market;
+pub mod player;
 pub mod provisioning;
 pub mod server;
 pub mod settings;
 pub mod system;
+pub mod template;
 pub mod update;
+pub mod upgrade;
 
+pub use command::{
+    clear_command_history, command_history, delete_command_macro, list_command_macros,
+    save_command_macro,
+};
+pub use config::{
+    diff_config_revisions, get_config_revision, list_config_revisions, restore_config_revision,
+};
 pub use console::console_logs;
 pub use cron::{
     create_cron_task, delete_cron_task, list_cron_tasks, run_cron_task, set_cron_task_enabled,
     update_cron_task,
 };
-pub use download::{cancel_download, create_download, query_download};
+pub use download::{
+    cancel_download, create_download, get_download_limits, pause_download, query_download,
+    resume_download, resume_downloads, update_download_limits,
+};
+pub use extension::toggle_instance_extension;
+pub use installer::{installer_status, start_installer};
 pub use instance::{
-    create_instance, delete_instance, get_instance, import_existing_instance, list_instances,
-    rename_instance, update_instance_path,
+    create_instance, delete_instance, get_instance, import_existing_instance,
+    list_instance_extensions, list_instances, list_port_conflicts, rename_instance,
+    update_instance_path,
 };
+pub use market::{
+    apply_market_updates, check_market_updates, install_market_resource, list_installed_resources,
+    list_market_update_rollbacks, plan_market_install, rollback_market_update,
+    uninstall_market_resource,
+};
+pub use player::{online_players, player_sessions};
 pub use provisioning::inspect_server;
 pub use server::{
-    force_stop_server, restart_server, send_server_command, server_status, start_server,
-    stop_server,
+    capture_server_command, force_stop_server, probe_server, restart_server, send_server_command,
+    server_status, start_server, stop_server,
 };
 pub use settings::{get_settings, settings_overview};
 pub use system::{default_run_path, server_resource_usage, system_snapshot};
+pub use template::{
+    apply_instance_template, delete_instance_template, list_instance_templates,
+    preview_instance_template, save_instance_template,
+};
 pub use update::check_update;
+pub use upgrade::{plan_server_upgrade, upgrade_server_core};
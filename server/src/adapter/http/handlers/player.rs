@@ -0,0 +1,60 @@
+//! 在线玩家 REST handler。
+//!
+//! 提供在线玩家与会话历史的查询接口，薄转发到
+//! [`CorePlayerService`](sealantern_application::service::CorePlayerService)
+//! 并收敛错误为 [`HttpError`](super::super::error::HttpError)。
+
+use axum::Json;
+use axum::extract::{Path, Query, State};
+use serde::Deserialize;
+
+use sealantern_core::instance::InstanceId;
+use sealantern_interface::PlayerService;
+use sealantern_interface::player::{OnlinePlayer, PlayerSessionRecord};
+
+use super::super::error::HttpError;
+use super::super::state::AppState;
+
+/// 会话历史的查询参数。
+#[derive(Debug, Default, Deserialize)]
+pub struct PlayerSessionQuery {
+    /// 只返回该玩家的会话（缺省返回全部玩家）。
+    pub player: Option<String>,
+    /// 最近 N 条会话（缺省使用服务默认窗口）。
+    pub limit: Option<i64>,
+}
+
+/// 解析路径参数中的实例 ID，非法输入视为客户端错误。
+fn parse_id(raw: &str) -> Result<InstanceId, HttpError> {
+    InstanceId::new(raw.to_owned())
+        .map_err(|_| HttpError::bad_request("invalid_instance_id", "invalid instance id"))
+}
+
+/// `GET /api/instances/{id}/players` — 查询当前在线玩家。
+pub async fn online_players(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<Vec<OnlinePlayer>>, HttpError> {
+    let id = parse_id(&id)?;
+    state
+        .player()
+        .online(&id)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `GET /api/instances/{id}/player-sessions?player=&limit=` — 读取玩家会话历史。
+pub async fn player_sessions(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<PlayerSessionQuery>,
+) -> Result<Json<Vec<PlayerSessionRecord>>, HttpError> {
+    let id = parse_id(&id)?;
+    state
+        .player()
+        .sessions(&id, query.player, query.limit)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
@@ -1,6 +1,6 @@
 //! 服务器进程管理 REST handler。
 //!
-//! 提供服务器进程生命周期（状态/启动/重启/停止/强制停止/控制台命令）接口，
+//! 提供服务器进程生命周期（状态/启动/重启/停止/强制停止/控制台命令/状态探测）接口，
 //! 薄转发到 [`CoreServerService`](sealantern_application::service::CoreServerService)
 //! 并收敛错误为 [`HttpError`](super::super::error::HttpError)。
 
@@ -9,7 +9,9 @@ use axum::extract::{Path, State};
 
 use sealantern_core::instance::InstanceId;
 use sealantern_interface::ServerService;
-use sealantern_interface::server::ServerSnapshot;
+use sealantern_interface::server::{
+    CaptureOptions, CommandCapture, CommandResponse, CommandTransport, ServerProbe, ServerSnapshot,
+};
 
 use super::super::error::HttpError;
 use super::super::state::AppState;
@@ -99,6 +101,9 @@ pub async fn force_stop_server(
 pub struct SendCommandRequest {
     /// 要发送的控制台命令。
     pub command: String,
+    /// 传输通道，缺省为 stdin。
+    #[serde(default)]
+    pub transport: CommandTransport,
 }
 
 /// `POST /api/instances/{id}/command` — 向服务器控制台发送命令。
@@ -106,11 +111,50 @@ pub async fn send_server_command(
     State(state): State<AppState>,
     Path(id): Path<String>,
     Json(request): Json<SendCommandRequest>,
-) -> Result<Json<()>, HttpError> {
+) -> Result<Json<CommandResponse>, HttpError> {
     let id = parse_id(&id)?;
     state
         .server()
-        .send_command(&id, &request.command)
+        .send_command_via(&id, &request.command, request.transport)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// 发送命令并捕获输出的请求体。
+#[derive(Debug, serde::Deserialize)]
+pub struct CaptureCommandRequest {
+    /// 要发送的控制台命令。
+    pub command: String,
+    /// 捕获参数（`timeout_ms` / `quiet_ms` / `until`），缺省取服务默认值。
+    #[serde(flatten)]
+    pub options: CaptureOptions,
+}
+
+/// `POST /api/instances/{id}/command/capture` — 发送命令并返回其后的控制台输出。
+pub async fn capture_server_command(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<CaptureCommandRequest>,
+) -> Result<Json<CommandCapture>, HttpError> {
+    let id = parse_id(&id)?;
+    state
+        .server()
+        .send_command_and_capture(&id, &request.command, request.options)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `GET /api/instances/{id}/probe` — 以 Server List Ping 探测服务器公开状态。
+pub async fn probe_server(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<ServerProbe>, HttpError> {
+    let id = parse_id(&id)?;
+    state
+        .server()
+        .probe(&id)
         .await
         .map(Json)
         .map_err(HttpError::from)
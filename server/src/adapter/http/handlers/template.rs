@@ -0,0 +1,101 @@
+//! 实例配置模板 REST handler。
+//!
+//! 提供模板维护与批量套用接口，薄转发到
+//! [`CoreInstanceTemplateService`](sealantern_application::service::CoreInstanceTemplateService)
+//! 并收敛错误为 [`HttpError`](super::super::error::HttpError)。
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use serde::Deserialize;
+
+use sealantern_core::instance::{InstanceId, InstanceTemplate};
+use sealantern_interface::InstanceTemplateService;
+use sealantern_interface::template::TemplateInstanceReport;
+
+use super::super::error::HttpError;
+use super::super::state::AppState;
+
+/// 预览 / 套用模板的请求体。
+#[derive(Debug, Deserialize)]
+pub struct ApplyTemplateRequest {
+    /// 目标实例 ID，报告按此顺序返回。
+    pub instance_ids: Vec<String>,
+}
+
+impl ApplyTemplateRequest {
+    fn parse_ids(self) -> Result<Vec<InstanceId>, HttpError> {
+        self.instance_ids
+            .into_iter()
+            .map(|id| {
+                InstanceId::new(id).map_err(|_| {
+                    HttpError::bad_request("invalid_instance_id", "invalid instance id")
+                })
+            })
+            .collect()
+    }
+}
+
+/// `GET /api/instance-templates` — 列出全部模板。
+pub async fn list_instance_templates(
+    State(state): State<AppState>,
+) -> Result<Json<Vec<InstanceTemplate>>, HttpError> {
+    state
+        .template()
+        .list()
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `PUT /api/instance-templates` — 新建（ID 为空）或覆盖模板。
+pub async fn save_instance_template(
+    State(state): State<AppState>,
+    Json(template): Json<InstanceTemplate>,
+) -> Result<Json<InstanceTemplate>, HttpError> {
+    state
+        .template()
+        .save(template)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `DELETE /api/instance-templates/{id}` — 删除模板。
+pub async fn delete_instance_template(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<StatusCode, HttpError> {
+    state.template().delete(&id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// `POST /api/instance-templates/{id}/preview` — 预览模板套用到各实例的变化。
+pub async fn preview_instance_template(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<ApplyTemplateRequest>,
+) -> Result<Json<Vec<TemplateInstanceReport>>, HttpError> {
+    let instance_ids = request.parse_ids()?;
+    state
+        .template()
+        .preview_apply(&id, &instance_ids)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
+
+/// `POST /api/instance-templates/{id}/apply` — 把模板套用到各实例。
+pub async fn apply_instance_template(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<ApplyTemplateRequest>,
+) -> Result<Json<Vec<TemplateInstanceReport>>, HttpError> {
+    let instance_ids = request.parse_ids()?;
+    state
+        .template()
+        .apply(&id, &instance_ids)
+        .await
+        .map(Json)
+        .map_err(HttpError::from)
+}
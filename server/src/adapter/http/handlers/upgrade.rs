@@ -0,0 +1,47 @@
+//! 服务端核心原地升级 REST handler。
+//!
+//! 提供升级预检与执行接口，薄转发到
+//! [`CoreServerUpgradeService`](sealantern_application::service::CoreServerUpgradeService)
+//! 并收敛错误为 [`HttpError`](super::super::error::HttpError)。
+
+use axum::Json;
+use axum::extract::{Path, State};
+
+use sealantern_core::instance::InstanceId;
+use sealantern_interface::ServerUpgradeService;
+use sealantern_interface::upgrade::{
+    ServerUpgradeOutcome, ServerUpgradePlan, ServerUpgradeRequest,
+};
+
+use super::super::error::HttpError;
+use super::super::state::AppState;
+
+/// 解析路径参数中的实例 ID，非法输入视为客户端错误。
+fn parse_id(raw: &str) -> Result<InstanceId, HttpError> {
+    InstanceId::new(raw.to_owned())
+        .map_err(|_| HttpError::bad_request("invalid_instance_id", "invalid instance id"))
+}
+
+/// `POST /api/instances/{id}/upgrade/plan` — 解析目标构建并给出 Java 与扩展
+/// 兼容性检查结果，不做任何改动。
+pub async fn plan_server_upgrade(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<ServerUpgradeRequest>,
+) -> Result<Json<ServerUpgradePlan>, HttpError> {
+    let id = parse_id(&id)?;
+    let plan = state.upgrade().plan(&id, request).await?;
+    Ok(Json(plan))
+}
+
+/// `POST /api/instances/{id}/upgrade` — 备份后替换服务端核心；服务器运行中
+/// 或 Java 不满足要求（且未强制）时返回 409。
+pub async fn upgrade_server_core(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Json(request): Json<ServerUpgradeRequest>,
+) -> Result<Json<ServerUpgradeOutcome>, HttpError> {
+    let id = parse_id(&id)?;
+    let outcome = state.upgrade().upgrade(&id, request).await?;
+    Ok(Json(outcome))
+}
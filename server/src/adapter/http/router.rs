@@ -42,6 +42,7 @@ pub fn build_router(services: AppServices, config: ViteConfig) -> Router {
         .route("/instances", get(handlers::list_instances))
         .route("/instances", post(handlers::create_instance))
         .route("/instances/import-existing", post(handlers::import_existing_instance))
+        .route("/instances/port-conflicts", get(handlers::list_port_conflicts))
         .route("/instances/{id}", get(handlers::get_instance))
         .route("/instances/{id}", delete(handlers::delete_instance))
         .route("/instances/{id}", patch(handlers::rename_instance))
@@ -58,10 +59,65 @@ pub fn build_router(services: AppServices, config: ViteConfig) -> Router {
             "/instances/{id}/command",
             post(handlers::send_server_command),
         )
+        .route(
+            "/instances/{id}/command/capture",
+            post(handlers::capture_server_command),
+        )
+        .route("/instances/{id}/probe", get(handlers::probe_server))
+        .route("/instances/{id}/command-history", get(handlers::command_history))
+        .route(
+            "/instances/{id}/command-history",
+            delete(handlers::clear_command_history),
+        )
+        .route("/instances/{id}/command-macros", get(handlers::list_command_macros))
+        .route("/instances/{id}/command-macros", put(handlers::save_command_macro))
+        .route(
+            "/instances/{id}/command-macros/{name}",
+            delete(handlers::delete_command_macro),
+        )
         .route("/instances/{id}/logs", get(handlers::console_logs))
+        .route("/instances/{id}/config-revisions", get(handlers::list_config_revisions))
+        .route(
+            "/instances/{id}/config-revisions/diff",
+            get(handlers::diff_config_revisions),
+        )
+        .route(
+            "/instances/{id}/config-revisions/{revision}",
+            get(handlers::get_config_revision),
+        )
+        .route(
+            "/instances/{id}/config-revisions/{revision}/restore",
+            post(handlers::restore_config_revision),
+        )
+        .route("/instances/{id}/players", get(handlers::online_players))
+        .route("/instances/{id}/player-sessions", get(handlers::player_sessions))
+        .route("/instances/{id}/market/installed", get(handlers::list_installed_resources))
+        .route("/instances/{id}/market/plan", post(handlers::plan_market_install))
+        .route("/instances/{id}/market/install", post(handlers::install_market_resource))
+        .route("/instances/{id}/market/uninstall", post(handlers::uninstall_market_resource))
+        .route(
+            "/instances/{id}/market/updates",
+            get(handlers::check_market_updates).post(handlers::apply_market_updates),
+        )
+        .route(
+            "/instances/{id}/market/updates/rollbacks",
+            get(handlers::list_market_update_rollbacks),
+        )
+        .route(
+            "/instances/{id}/market/updates/rollback",
+            post(handlers::rollback_market_update),
+        )
         // ── 嵌套子资源（后续扩展） ──
         // 示例：.route("/instances/{id}/logs", get(handlers::instance_logs))
-        .route("/instances/{id}/path", put(handlers::update_instance_path));
+        .route("/instances/{id}/path", put(handlers::update_instance_path))
+        .route("/instances/{id}/extensions", get(handlers::list_instance_extensions))
+        .route("/instances/{id}/extensions/toggle", post(handlers::toggle_instance_extension))
+        .route(
+            "/instances/{id}/installer",
+            get(handlers::installer_status).post(handlers::start_installer),
+        )
+        .route("/instances/{id}/upgrade/plan", post(handlers::plan_server_upgrade))
+        .route("/instances/{id}/upgrade", post(handlers::upgrade_server_core));
 
     let settings_routes = Router::new()
         .route("/settings", get(handlers::settings_overview))
@@ -80,6 +136,13 @@ pub fn build_router(services: AppServices, config: ViteConfig) -> Router {
         .route("/cron-tasks/{id}/enabled", put(handlers::set_cron_task_enabled))
         .route("/cron-tasks/{id}/run", post(handlers::run_cron_task));
 
+    let template_routes = Router::new()
+        .route("/instance-templates", get(handlers::list_instance_templates))
+        .route("/instance-templates", put(handlers::save_instance_template))
+        .route("/instance-templates/{id}", delete(handlers::delete_instance_template))
+        .route("/instance-templates/{id}/preview", post(handlers::preview_instance_template))
+        .route("/instance-templates/{id}/apply", post(handlers::apply_instance_template));
+
     let update_routes = Router::new().route("/update", get(handlers::check_update));
 
     let provisioning_routes =
@@ -87,8 +150,15 @@ pub fn build_router(services: AppServices, config: ViteConfig) -> Router {
 
     let download_routes = Router::new()
         .route("/downloads", post(handlers::create_download))
+        .route("/downloads/resume", post(handlers::resume_downloads))
+        .route(
+            "/downloads/limits",
+            get(handlers::get_download_limits).put(handlers::update_download_limits),
+        )
         .route("/downloads/{id}", get(handlers::query_download))
-        .route("/downloads/{id}", axum::routing::delete(handlers::cancel_download));
+        .route("/downloads/{id}", axum::routing::delete(handlers::cancel_download))
+        .route("/downloads/{id}/pause", post(handlers::pause_download))
+        .route("/downloads/{id}/resume", post(handlers::resume_download));
 
     Router::new()
         .nest(API_PREFIX, instance_routes)
@@ -96,6 +166,7 @@ pub fn build_router(services: AppServices, config: ViteConfig) -> Router {
         .nest(API_PREFIX, settings_routes)
         .nest(API_PREFIX, system_routes)
         .nest(API_PREFIX, cron_routes)
+        .nest(API_PREFIX, template_routes)
         .nest(API_PREFIX, update_routes)
         .nest(API_PREFIX, download_routes)
         .merge(plugin_rpc_routes)
@@ -10,9 +10,11 @@
 use std::sync::Arc;
 
 use sealantern_application::service::{
-    CoreConsoleService, CoreCronTaskService, CoreDownloadService, CoreInstanceService,
-    CoreProvisioningService, CoreServerService, CoreSettingsService, CoreSystemService,
-    CoreUpdateCheckService,
+    CoreCommandLibraryService, CoreConfigService, CoreConsoleService, CoreCronTaskService,
+    CoreDownloadService, CoreExtensionService, CoreInstallerService, CoreInstanceService,
+    CoreInstanceTemplateService, CoreMarketInstallService, CorePlayerService,
+    CoreProvisioningService, CoreServerService, CoreServerUpgradeService, CoreSettingsService,
+    CoreSystemService, CoreUpdateCheckService,
 };
 use sealantern_application::services::AppServices;
 
@@ -50,6 +52,46 @@ impl AppState {
         self.services.console().clone()
     }
 
+    /// 访问在线玩家服务（`Arc` 共享句柄，clone 廉价）。
+    pub fn player(&self) -> Arc<CorePlayerService> {
+        self.services.player().clone()
+    }
+
+    /// 访问控制台命令历史与宏服务（`Arc` 共享句柄，clone 廉价）。
+    pub fn command_library(&self) -> Arc<CoreCommandLibraryService> {
+        self.services.command_library().clone()
+    }
+
+    /// 访问配置文件写入与修订服务（`Arc` 共享句柄，clone 廉价）。
+    pub fn config(&self) -> Arc<CoreConfigService> {
+        self.services.config().clone()
+    }
+
+    /// 访问实例配置模板服务（`Arc` 共享句柄，clone 廉价）。
+    pub fn template(&self) -> Arc<CoreInstanceTemplateService> {
+        self.services.template().clone()
+    }
+
+    /// 访问扩展启停服务（`Arc` 共享句柄，clone 廉价）。
+    pub fn extension(&self) -> Arc<CoreExtensionService> {
+        self.services.extension().clone()
+    }
+
+    /// 访问加载器安装器执行服务（`Arc` 共享句柄，clone 廉价）。
+    pub fn installer(&self) -> Arc<CoreInstallerService> {
+        self.services.installer().clone()
+    }
+
+    /// 访问服务端核心原地升级服务（`Arc` 共享句柄，clone 廉价）。
+    pub fn upgrade(&self) -> Arc<CoreServerUpgradeService> {
+        self.services.upgrade().clone()
+    }
+
+    /// 访问市场资源安装服务（`Arc` 共享句柄，clone 廉价）。
+    pub fn market_install(&self) -> Arc<CoreMarketInstallService> {
+        self.services.market_install().clone()
+    }
+
     /// 访问设置信息服务（`Arc` 共享句柄，clone 廉价）。
     pub fn settings(&self) -> Arc<CoreSettingsService> {
         self.services.settings().clone()
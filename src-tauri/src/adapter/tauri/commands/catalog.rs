@@ -1,18 +1,20 @@
 //! 服务器类型目录 Tauri 命令。
 //!
 //! 前端通过 `invoke` 调用这些命令，命令内部经应用装配层拿到
-//! [`ServerCatalogService`] 查询服务器核心类型、可用版本与下载链接。
+//! [`ServerCatalogService`] 查询服务器核心类型、可用版本、构建与下载链接，或刷新缓存的目录数据。
 //!
 //! 错误统一为接口契约错误 [`ServerCatalogServiceError`]，可序列化回前端，
 //! 不携带底层敏感细节。
 
 use sealantern_application::services::AppServices;
+use sealantern_extra::catalog::{CatalogBuild, CatalogSnapshot};
 use sealantern_extra::download_link::DownloadLink;
 use sealantern_interface::{ServerCatalogService, ServerCatalogServiceError};
 
 /// 查询全部可用的服务器核心类型。
 #[tauri::command(rename_all = "snake_case")]
-pub async fn catalog_server_types() -> Result<Vec<String>, ServerCatalogServiceError> {
+pub async fn catalog_server_types()
+-> Result<CatalogSnapshot<Vec<String>>, ServerCatalogServiceError> {
     AppServices::get()
         .await
         .map_err(|_| ServerCatalogServiceError::OperationFailed)?
@@ -25,7 +27,7 @@ pub async fn catalog_server_types() -> Result<Vec<String>, ServerCatalogServiceE
 #[tauri::command(rename_all = "snake_case")]
 pub async fn catalog_versions(
     server_type: String,
-) -> Result<Vec<String>, ServerCatalogServiceError> {
+) -> Result<CatalogSnapshot<Vec<String>>, ServerCatalogServiceError> {
     AppServices::get()
         .await
         .map_err(|_| ServerCatalogServiceError::OperationFailed)?
@@ -34,7 +36,21 @@ pub async fn catalog_versions(
         .await
 }
 
-/// 查询指定服务器核心类型、指定版本的下载链接。
+/// 查询指定服务器核心类型、指定版本的构建（渠道、摘要与 Java 要求），从新到旧。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn catalog_builds(
+    server_type: String,
+    server_version: String,
+) -> Result<CatalogSnapshot<Vec<CatalogBuild>>, ServerCatalogServiceError> {
+    AppServices::get()
+        .await
+        .map_err(|_| ServerCatalogServiceError::OperationFailed)?
+        .catalog()
+        .builds(server_type, server_version)
+        .await
+}
+
+/// 查询指定服务器核心类型、指定版本推荐构建的下载链接。
 #[tauri::command(rename_all = "snake_case")]
 pub async fn catalog_details(
     server_type: String,
@@ -47,3 +63,14 @@ pub async fn catalog_details(
         .details(server_type, server_version)
         .await
 }
+
+/// 使缓存的目录数据过期并重新查询服务器核心类型。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn catalog_refresh() -> Result<CatalogSnapshot<Vec<String>>, ServerCatalogServiceError> {
+    AppServices::get()
+        .await
+        .map_err(|_| ServerCatalogServiceError::OperationFailed)?
+        .catalog()
+        .refresh()
+        .await
+}
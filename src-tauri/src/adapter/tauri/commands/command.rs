@@ -0,0 +1,82 @@
+//! 控制台命令历史与宏 Tauri 命令。
+//!
+//! 前端通过 `invoke` 调用这些命令，命令内部经应用装配层拿到
+//! [`CommandLibraryService`] 检索命令历史、维护别名与宏；宏本身在发送控制台
+//! 命令时于服务端展开。
+//!
+//! 错误统一为接口契约错误 [`CommandLibraryServiceError`]，可序列化回前端，
+//! 不携带底层敏感细节。
+
+use sealantern_application::services::AppServices;
+use sealantern_core::instance::InstanceId;
+use sealantern_interface::command::{CommandHistoryEntry, CommandMacroDefinition};
+use sealantern_interface::{CommandLibraryService, CommandLibraryServiceError};
+
+/// 解析 Tauri 命令传入的实例 ID 字符串。
+///
+/// 统一映射解析错误为 [`CommandLibraryServiceError::InvalidInput`]。
+fn parse_id_for_tauri(id: String) -> Result<InstanceId, CommandLibraryServiceError> {
+    InstanceId::new(id).map_err(|_| CommandLibraryServiceError::InvalidInput)
+}
+
+/// 检索实例的命令历史。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_command_history(
+    id: String,
+    query: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<CommandHistoryEntry>, CommandLibraryServiceError> {
+    let service = AppServices::command_library_service()
+        .await
+        .map_err(|_| CommandLibraryServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.history(&id, query, limit).await
+}
+
+/// 清空实例的命令历史。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn clear_command_history(id: String) -> Result<(), CommandLibraryServiceError> {
+    let service = AppServices::command_library_service()
+        .await
+        .map_err(|_| CommandLibraryServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.clear_history(&id).await
+}
+
+/// 列出实例的别名与宏。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_command_macros(
+    id: String,
+) -> Result<Vec<CommandMacroDefinition>, CommandLibraryServiceError> {
+    let service = AppServices::command_library_service()
+        .await
+        .map_err(|_| CommandLibraryServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.macros(&id).await
+}
+
+/// 新建或覆盖同名别名 / 宏。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn save_command_macro(
+    id: String,
+    definition: CommandMacroDefinition,
+) -> Result<CommandMacroDefinition, CommandLibraryServiceError> {
+    let service = AppServices::command_library_service()
+        .await
+        .map_err(|_| CommandLibraryServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.save_macro(&id, definition).await
+}
+
+/// 删除别名 / 宏。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_command_macro(
+    id: String,
+    name: String,
+) -> Result<(), CommandLibraryServiceError> {
+    let service = AppServices::command_library_service()
+        .await
+        .map_err(|_| CommandLibraryServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.delete_macro(&id, &name).await
+}
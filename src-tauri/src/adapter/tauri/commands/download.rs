@@ -8,7 +8,9 @@ use serde::Deserialize;
 
 use sealantern_application::service::CoreDownloadService;
 use sealantern_application::services::AppServices;
-use sealantern_interface::download::{DownloadRequest, DownloadTaskInfo};
+use sealantern_interface::download::{
+    DownloadLimits, DownloadPriority, DownloadRequest, DownloadTaskInfo, ResumedDownload,
+};
 use sealantern_interface::{DownloadService, DownloadServiceError};
 
 /// 获取全局下载任务管理服务句柄（惰性初始化容器）。
@@ -27,10 +29,31 @@ async fn download_service() -> Result<Arc<CoreDownloadService>, DownloadServiceE
 pub struct CreateDownloadRequest {
     /// 下载 URL。
     pub url: String,
+    /// 备用镜像地址，按优先级排列。
+    #[serde(default)]
+    pub mirrors: Vec<String>,
     /// 本地保存路径。
     pub save_path: String,
     /// 下载线程数。
     pub thread_count: usize,
+    /// 预期文件大小（字节），不符时任务失败。
+    #[serde(default)]
+    pub expected_size: Option<u64>,
+    /// 预期 SHA-1 摘要（十六进制）。
+    #[serde(default)]
+    pub sha1: Option<String>,
+    /// 预期 SHA-256 摘要（十六进制）。
+    #[serde(default)]
+    pub sha256: Option<String>,
+    /// 预期 SHA-512 摘要（十六进制）。
+    #[serde(default)]
+    pub sha512: Option<String>,
+    /// 调度优先级（`low`/`normal`/`high`）。
+    #[serde(default)]
+    pub priority: DownloadPriority,
+    /// 任务自身的带宽上限（字节/秒）。
+    #[serde(default)]
+    pub bandwidth_limit: Option<u64>,
 }
 
 /// 创建下载任务，返回任务信息。
@@ -42,8 +65,15 @@ pub async fn download_create(
     let id = service
         .create(DownloadRequest {
             url: request.url,
+            mirrors: request.mirrors,
             save_path: request.save_path,
             thread_count: request.thread_count,
+            expected_size: request.expected_size,
+            sha1: request.sha1,
+            sha256: request.sha256,
+            sha512: request.sha512,
+            priority: request.priority,
+            bandwidth_limit: request.bandwidth_limit,
         })
         .await?;
     service
@@ -68,3 +98,40 @@ pub async fn download_cancel(id: String) -> Result<(), DownloadServiceError> {
     let service = download_service().await?;
     service.cancel(&id).await
 }
+
+/// 暂停下载任务。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn download_pause(id: String) -> Result<(), DownloadServiceError> {
+    let service = download_service().await?;
+    service.pause(&id).await
+}
+
+/// 恢复已暂停或上次未完成的下载任务。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn download_resume(id: String) -> Result<DownloadTaskInfo, DownloadServiceError> {
+    let service = download_service().await?;
+    service.resume(&id).await
+}
+
+/// 查询全局下载限制。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn download_limits() -> Result<DownloadLimits, DownloadServiceError> {
+    let service = download_service().await?;
+    service.limits().await
+}
+
+/// 修改全局下载限制。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn download_set_limits(
+    limits: DownloadLimits,
+) -> Result<DownloadLimits, DownloadServiceError> {
+    let service = download_service().await?;
+    service.set_limits(limits).await
+}
+
+/// 续传上次运行中未完成的下载任务。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn download_resume_incomplete() -> Result<Vec<ResumedDownload>, DownloadServiceError> {
+    let service = download_service().await?;
+    service.resume_incomplete().await
+}
@@ -0,0 +1,33 @@
+//! 扩展启停 Tauri 命令。
+//!
+//! 前端通过 `invoke` 调用这些命令，命令内部经应用装配层拿到
+//! [`ExtensionService`] 启用或禁用实例的插件、模组与数据包。
+//!
+//! 错误统一为接口契约错误 [`ExtensionServiceError`]，可序列化回前端，
+//! 不携带底层敏感细节。
+
+use sealantern_application::services::AppServices;
+use sealantern_core::instance::{InstanceExtension, InstanceId};
+use sealantern_interface::extension::ExtensionToggleRequest;
+use sealantern_interface::{ExtensionService, ExtensionServiceError};
+
+/// 解析 Tauri 命令传入的实例 ID 字符串。
+///
+/// 统一映射解析错误为 [`ExtensionServiceError::InvalidInput`]。
+fn parse_id_for_tauri(id: String) -> Result<InstanceId, ExtensionServiceError> {
+    InstanceId::new(id).map_err(|_| ExtensionServiceError::InvalidInput)
+}
+
+/// 启用或禁用一个扩展；服务器运行中且未强制时返回
+/// [`ExtensionServiceError::ServerRunning`]。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn toggle_instance_extension(
+    id: String,
+    request: ExtensionToggleRequest,
+) -> Result<InstanceExtension, ExtensionServiceError> {
+    let service = AppServices::extension_service()
+        .await
+        .map_err(|_| ExtensionServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.set_enabled(&id, request).await
+}
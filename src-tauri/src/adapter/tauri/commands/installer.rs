@@ -0,0 +1,44 @@
+//! 加载器安装器 Tauri 命令。
+//!
+//! 前端通过 `invoke` 调用这些命令，命令内部经应用装配层拿到
+//! [`InstallerService`] 为实例执行 Forge / NeoForge / Fabric 安装器并查询进度。
+//!
+//! 错误统一为接口契约错误 [`InstallerServiceError`]，可序列化回前端，
+//! 不携带底层敏感细节。
+
+use sealantern_application::services::AppServices;
+use sealantern_core::instance::InstanceId;
+use sealantern_interface::installer::{InstallerRequest, InstallerTaskInfo};
+use sealantern_interface::{InstallerService, InstallerServiceError};
+
+/// 解析 Tauri 命令传入的实例 ID 字符串。
+///
+/// 统一映射解析错误为 [`InstallerServiceError::InvalidInput`]。
+fn parse_id_for_tauri(id: String) -> Result<InstanceId, InstallerServiceError> {
+    InstanceId::new(id).map_err(|_| InstallerServiceError::InvalidInput)
+}
+
+/// 在后台下载并执行安装器；安装器输出写入实例控制台日志。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn installer_start(
+    id: String,
+    request: InstallerRequest,
+) -> Result<InstallerTaskInfo, InstallerServiceError> {
+    let service = AppServices::installer_service()
+        .await
+        .map_err(|_| InstallerServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.start(&id, request).await
+}
+
+/// 查询实例最近一次安装任务。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn installer_status(
+    id: String,
+) -> Result<Option<InstallerTaskInfo>, InstallerServiceError> {
+    let service = AppServices::installer_service()
+        .await
+        .map_err(|_| InstallerServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.status(&id).await
+}
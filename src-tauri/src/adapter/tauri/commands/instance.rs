@@ -12,7 +12,9 @@ use std::sync::Arc;
 use sealantern_application::error::InstanceError;
 use sealantern_application::service::CoreInstanceService;
 use sealantern_application::services::AppServices;
-use sealantern_core::instance::{Instance, InstanceId, InstanceSpec};
+use sealantern_core::instance::{
+    Instance, InstanceExtensionReport, InstanceId, InstanceListEntry, InstanceSpec, PortConflict,
+};
 use sealantern_core::provisioning::{ImportExistingServerRequest, ImportModpackRequest};
 use sealantern_interface::{InstanceService, InstanceServiceError};
 
@@ -34,11 +36,18 @@ fn parse_id_for_tauri(id: String) -> Result<InstanceId, InstanceServiceError> {
         .map_err(InstanceServiceError::from)
 }
 
-/// 列出全部实例。
+/// 列出全部实例，附带各实例涉及的端口冲突供实例列表标注。
 #[tauri::command(rename_all = "snake_case")]
-pub async fn list_instances() -> Result<Vec<Instance>, InstanceServiceError> {
+pub async fn list_instances() -> Result<Vec<InstanceListEntry>, InstanceServiceError> {
     let service = instance_service().await?;
-    service.list().await
+    service.list_entries().await
+}
+
+/// 列出实例之间的端口冲突。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_port_conflicts() -> Result<Vec<PortConflict>, InstanceServiceError> {
+    let service = instance_service().await?;
+    service.port_conflicts().await
 }
 
 /// 按 ID 查找实例，不存在时返回 `None`。
@@ -49,7 +58,17 @@ pub async fn get_instance(id: String) -> Result<Option<Instance>, InstanceServic
     service.find(&id).await
 }
 
-/// 创建新实例并持久化。
+/// 列出实例的插件、模组与数据包及其一致性问题（重复 ID、缺失依赖）。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_instance_extensions(
+    id: String,
+) -> Result<InstanceExtensionReport, InstanceServiceError> {
+    let service = instance_service().await?;
+    let id = parse_id_for_tauri(id)?;
+    service.extensions(&id).await
+}
+
+/// 创建新实例并持久化；端口冲突时返回 [`InstanceServiceError::PortConflict`]。
 #[tauri::command(rename_all = "snake_case")]
 pub async fn create_instance(spec: InstanceSpec) -> Result<Instance, InstanceServiceError> {
     let service = instance_service().await?;
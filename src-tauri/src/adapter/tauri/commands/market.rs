@@ -0,0 +1,127 @@
+//! 市场资源安装 Tauri 命令。
+//!
+//! 前端通过 `invoke` 调用这些命令，命令内部经应用装配层拿到
+//! [`MarketInstallService`] 规划并执行资源安装、卸载与安装记录查询，
+//! 以及扩展文件的更新检查、更新与回滚。
+//!
+//! 错误统一为接口契约错误 [`MarketInstallServiceError`]，可序列化回前端，
+//! 不携带底层敏感细节。
+
+use sealantern_application::services::AppServices;
+use sealantern_core::instance::InstanceId;
+use sealantern_extra::market::{
+    ExtensionUpdate, InstallPlan, InstalledResource, MarketSource, UpdateRollback,
+};
+use sealantern_interface::market::{
+    MarketExtensionFile, MarketInstallRequest, MarketUpdateRequest,
+};
+use sealantern_interface::{MarketInstallService, MarketInstallServiceError};
+
+/// 解析 Tauri 命令传入的实例 ID 字符串。
+///
+/// 统一映射解析错误为 [`MarketInstallServiceError::InvalidInput`]。
+fn parse_id_for_tauri(id: String) -> Result<InstanceId, MarketInstallServiceError> {
+    InstanceId::new(id).map_err(|_| MarketInstallServiceError::InvalidInput)
+}
+
+/// 列出实例的市场资源安装记录。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_installed_resources(
+    id: String,
+) -> Result<Vec<InstalledResource>, MarketInstallServiceError> {
+    let service = AppServices::market_install_service()
+        .await
+        .map_err(|_| MarketInstallServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.installed(&id).await
+}
+
+/// 生成安装计划，不写入任何内容。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn plan_market_install(
+    id: String,
+    request: MarketInstallRequest,
+) -> Result<InstallPlan, MarketInstallServiceError> {
+    let service = AppServices::market_install_service()
+        .await
+        .map_err(|_| MarketInstallServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.plan(&id, request).await
+}
+
+/// 安装资源及其必需依赖。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn install_market_resource(
+    id: String,
+    request: MarketInstallRequest,
+) -> Result<Vec<InstalledResource>, MarketInstallServiceError> {
+    let service = AppServices::market_install_service()
+        .await
+        .map_err(|_| MarketInstallServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.install(&id, request).await
+}
+
+/// 卸载资源并清理不再需要的依赖。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn uninstall_market_resource(
+    id: String,
+    source: MarketSource,
+    project_id: String,
+) -> Result<Vec<InstalledResource>, MarketInstallServiceError> {
+    let service = AppServices::market_install_service()
+        .await
+        .map_err(|_| MarketInstallServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.uninstall(&id, source, &project_id).await
+}
+
+/// 检查扩展目录下每个 jar 的可用更新。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn check_market_updates(
+    id: String,
+) -> Result<Vec<ExtensionUpdate>, MarketInstallServiceError> {
+    let service = AppServices::market_install_service()
+        .await
+        .map_err(|_| MarketInstallServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.check_updates(&id).await
+}
+
+/// 更新选中的文件，旧文件保留备份。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn apply_market_updates(
+    id: String,
+    request: MarketUpdateRequest,
+) -> Result<Vec<UpdateRollback>, MarketInstallServiceError> {
+    let service = AppServices::market_install_service()
+        .await
+        .map_err(|_| MarketInstallServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.update(&id, request).await
+}
+
+/// 列出可回滚的更新。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_market_update_rollbacks(
+    id: String,
+) -> Result<Vec<UpdateRollback>, MarketInstallServiceError> {
+    let service = AppServices::market_install_service()
+        .await
+        .map_err(|_| MarketInstallServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.update_rollbacks(&id).await
+}
+
+/// 换回指定文件更新前的旧文件。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn rollback_market_update(
+    id: String,
+    file: MarketExtensionFile,
+) -> Result<UpdateRollback, MarketInstallServiceError> {
+    let service = AppServices::market_install_service()
+        .await
+        .map_err(|_| MarketInstallServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.rollback_update(&id, file).await
+}
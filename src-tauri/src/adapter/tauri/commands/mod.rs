@@ -5,18 +5,25 @@
 
 pub mod backup;
 pub mod catalog;
+pub mod command;
 pub mod console;
 pub mod cron;
 pub mod download;
+pub mod extension;
+pub mod installer;
 pub mod instance;
 pub mod java;
 pub mod logging;
+pub mod market;
 pub mod online_tunnel;
+pub mod player;
 pub mod plugin;
 pub mod provisioning;
 pub mod server;
 pub mod server_config;
 pub mod settings;
 pub mod system;
+pub mod template;
 pub mod update;
 pub mod update_install;
+pub mod upgrade;
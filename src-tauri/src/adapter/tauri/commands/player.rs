@@ -0,0 +1,44 @@
+//! 在线玩家 Tauri 命令。
+//!
+//! 前端通过 `invoke` 调用这些命令，命令内部经应用装配层拿到
+//! [`PlayerService`] 查询在线玩家与会话历史；实时变更经
+//! `server-player-event` 事件推送。
+//!
+//! 错误统一为接口契约错误 [`PlayerServiceError`]，可序列化回前端，
+//! 不携带底层敏感细节。
+
+use sealantern_application::services::AppServices;
+use sealantern_core::instance::InstanceId;
+use sealantern_interface::player::{OnlinePlayer, PlayerSessionRecord};
+use sealantern_interface::{PlayerService, PlayerServiceError};
+
+/// 解析 Tauri 命令传入的实例 ID 字符串。
+///
+/// 统一映射解析错误为 [`PlayerServiceError::InvalidInput`]。
+fn parse_id_for_tauri(id: String) -> Result<InstanceId, PlayerServiceError> {
+    InstanceId::new(id).map_err(|_| PlayerServiceError::InvalidInput)
+}
+
+/// 查询实例当前在线的玩家。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_online_players(id: String) -> Result<Vec<OnlinePlayer>, PlayerServiceError> {
+    let service = AppServices::player_service()
+        .await
+        .map_err(|_| PlayerServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.online(&id).await
+}
+
+/// 读取实例的玩家会话历史。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn get_player_sessions(
+    id: String,
+    player: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<PlayerSessionRecord>, PlayerServiceError> {
+    let service = AppServices::player_service()
+        .await
+        .map_err(|_| PlayerServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.sessions(&id, player, limit).await
+}
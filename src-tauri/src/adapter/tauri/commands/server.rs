@@ -5,7 +5,9 @@ use std::sync::Arc;
 use sealantern_application::service::CoreServerService;
 use sealantern_application::services::AppServices;
 use sealantern_core::instance::InstanceId;
-use sealantern_interface::server::ServerSnapshot;
+use sealantern_interface::server::{
+    CaptureOptions, CommandCapture, CommandResponse, CommandTransport, ServerProbe, ServerSnapshot,
+};
 use sealantern_interface::{ServerService, ServerServiceError};
 
 /// 获取全局服务器进程管理服务句柄（惰性初始化容器）。
@@ -63,10 +65,38 @@ pub async fn force_stop_server(id: String) -> Result<(), ServerServiceError> {
     service.force_stop(&id).await
 }
 
-/// 向服务器控制台发送单行命令。
+/// 向服务器控制台发送单行命令；`transport` 缺省为 stdin，选 RCON 时返回响应文本。
 #[tauri::command(rename_all = "snake_case")]
-pub async fn send_server_command(id: String, command: String) -> Result<(), ServerServiceError> {
+pub async fn send_server_command(
+    id: String,
+    command: String,
+    transport: Option<CommandTransport>,
+) -> Result<CommandResponse, ServerServiceError> {
     let service = server_service().await?;
     let id = parse_id_for_tauri(id)?;
-    service.send_command(&id, &command).await
+    service
+        .send_command_via(&id, &command, transport.unwrap_or_default())
+        .await
+}
+
+/// 发送控制台命令并返回其后捕获到的输出；`options` 缺省取服务默认值。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn send_server_command_and_capture(
+    id: String,
+    command: String,
+    options: Option<CaptureOptions>,
+) -> Result<CommandCapture, ServerServiceError> {
+    let service = server_service().await?;
+    let id = parse_id_for_tauri(id)?;
+    service
+        .send_command_and_capture(&id, &command, options.unwrap_or_default())
+        .await
+}
+
+/// 探测服务器公开状态（MOTD、版本、在线人数与延迟）。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn probe_server(id: String) -> Result<ServerProbe, ServerServiceError> {
+    let service = server_service().await?;
+    let id = parse_id_for_tauri(id)?;
+    service.probe(&id).await
 }
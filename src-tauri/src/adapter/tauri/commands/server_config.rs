@@ -1,24 +1,55 @@
 //! 服务器配置管理 Tauri 命令。
 
 use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
 
-use sealantern_extra::config::{ServerProperties, ServerPropertiesManager};
+use sealantern_application::service::CoreConfigService;
+use sealantern_application::services::AppServices;
+use sealantern_core::app_plugin::ExecutionPrincipal;
+use sealantern_extra::config::{
+    ConfigDocumentFile, ConfigDocumentManager, ConfigDocumentPreview, ConfigDocumentView,
+    ConfigRevision, ConfigRevisionSummary, DocumentPatch, ServerProperties,
+    ServerPropertiesManager,
+};
+
+/// 取配置写入与修订服务；写入与修订记录统一由应用层完成
+async fn config_service() -> Result<Arc<CoreConfigService>, String> {
+    AppServices::config_service()
+        .await
+        .map_err(|e| e.to_string())
+}
 
 /// 读取服务器配置文件 (server.properties)
+///
+/// `game_version` 用于挑选对应版本的字段模式，缺省按最新版本标注。
 #[tauri::command]
-pub async fn read_server_properties(server_path: String) -> Result<ServerProperties, String> {
-    let manager = ServerPropertiesManager::new(&server_path);
+pub async fn read_server_properties(
+    server_path: String,
+    game_version: Option<String>,
+) -> Result<ServerProperties, String> {
+    let manager =
+        ServerPropertiesManager::new(&server_path).with_game_version(game_version.as_deref());
     manager.read().map_err(|e| e.to_string())
 }
 
-/// 写入服务器配置文件
+/// 写入服务器配置文件（取值不符合字段模式时拒绝写入）
 #[tauri::command]
 pub async fn write_server_properties(
     server_path: String,
     values: BTreeMap<String, String>,
+    game_version: Option<String>,
 ) -> Result<(), String> {
-    let manager = ServerPropertiesManager::new(&server_path);
-    manager.write(&values).map_err(|e| e.to_string())
+    config_service()
+        .await?
+        .write_server_properties(
+            Path::new(&server_path),
+            values,
+            game_version,
+            &ExecutionPrincipal::BuiltInHost,
+        )
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// 读取 server.properties 原始文本
@@ -34,14 +65,25 @@ pub async fn write_server_properties_source(
     server_path: String,
     source: String,
 ) -> Result<(), String> {
-    let manager = ServerPropertiesManager::new(&server_path);
-    manager.write_source(&source).map_err(|e| e.to_string())
+    config_service()
+        .await?
+        .write_server_properties_source(
+            Path::new(&server_path),
+            source,
+            &ExecutionPrincipal::BuiltInHost,
+        )
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// 将原始文本解析为可视化配置结构
 #[tauri::command]
-pub async fn parse_server_properties_source(source: String) -> Result<ServerProperties, String> {
-    ServerPropertiesManager::parse_source(&source).map_err(|e| e.to_string())
+pub async fn parse_server_properties_source(
+    source: String,
+    game_version: Option<String>,
+) -> Result<ServerProperties, String> {
+    ServerPropertiesManager::parse_source(&source, game_version.as_deref())
+        .map_err(|e| e.to_string())
 }
 
 /// 预览可视化配置写回后最终文本
@@ -49,8 +91,10 @@ pub async fn parse_server_properties_source(source: String) -> Result<ServerProp
 pub async fn preview_server_properties_write(
     server_path: String,
     values: BTreeMap<String, String>,
+    game_version: Option<String>,
 ) -> Result<String, String> {
-    let manager = ServerPropertiesManager::new(&server_path);
+    let manager =
+        ServerPropertiesManager::new(&server_path).with_game_version(game_version.as_deref());
     manager.preview_write(&values).map_err(|e| e.to_string())
 }
 
@@ -59,6 +103,107 @@ pub async fn preview_server_properties_write(
 pub async fn preview_server_properties_write_from_source(
     source: String,
     values: BTreeMap<String, String>,
+    game_version: Option<String>,
+) -> Result<String, String> {
+    ServerPropertiesManager::preview_write_from_source(&source, &values, game_version.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// 列出服务器目录下可编辑的 YAML / TOML / JSON5 配置文件
+#[tauri::command]
+pub async fn list_config_documents(server_path: String) -> Result<Vec<ConfigDocumentFile>, String> {
+    ConfigDocumentManager::new(&server_path)
+        .discover()
+        .map_err(|e| e.to_string())
+}
+
+/// 读取配置文档（`path` 为相对服务器目录的路径）
+#[tauri::command]
+pub async fn read_config_document(
+    server_path: String,
+    path: String,
+) -> Result<ConfigDocumentView, String> {
+    ConfigDocumentManager::new(&server_path)
+        .read(&path)
+        .map_err(|e| e.to_string())
+}
+
+/// 预览按键路径修改配置文档后的文本与差异
+#[tauri::command]
+pub async fn preview_config_document_write(
+    server_path: String,
+    path: String,
+    patches: Vec<DocumentPatch>,
+) -> Result<ConfigDocumentPreview, String> {
+    ConfigDocumentManager::new(&server_path)
+        .preview(&path, &patches)
+        .map_err(|e| e.to_string())
+}
+
+/// 按键路径修改配置文档并原子写回（保留注释与顺序）
+#[tauri::command]
+pub async fn write_config_document(
+    server_path: String,
+    path: String,
+    patches: Vec<DocumentPatch>,
+) -> Result<ConfigDocumentPreview, String> {
+    config_service()
+        .await?
+        .write_document(Path::new(&server_path), path, patches, &ExecutionPrincipal::BuiltInHost)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 列出配置修订（时间倒序）；`file` 提供时只列出该文件的修订
+#[tauri::command]
+pub async fn list_config_revisions(
+    server_path: String,
+    file: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<ConfigRevisionSummary>, String> {
+    config_service()
+        .await?
+        .list_revisions_in(Path::new(&server_path), file.as_deref(), limit)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 读取一条配置修订及其完整快照
+#[tauri::command]
+pub async fn get_config_revision(
+    server_path: String,
+    id: i64,
+) -> Result<Option<ConfigRevision>, String> {
+    config_service()
+        .await?
+        .get_revision_in(Path::new(&server_path), id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 比较任意两条配置修订，返回统一差异
+#[tauri::command]
+pub async fn diff_config_revisions(
+    server_path: String,
+    from: i64,
+    to: i64,
 ) -> Result<String, String> {
-    ServerPropertiesManager::preview_write_from_source(&source, &values).map_err(|e| e.to_string())
+    config_service()
+        .await?
+        .diff_revisions_in(Path::new(&server_path), from, to)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 将配置文件恢复为指定修订；内容未变化时返回 `None`
+#[tauri::command]
+pub async fn restore_config_revision(
+    server_path: String,
+    id: i64,
+) -> Result<Option<ConfigRevisionSummary>, String> {
+    config_service()
+        .await?
+        .restore_revision_in(Path::new(&server_path), id, &ExecutionPrincipal::BuiltInHost)
+        .await
+        .map_err(|e| e.to_string())
 }
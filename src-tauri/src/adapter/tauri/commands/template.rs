@@ -0,0 +1,77 @@
+//! 实例配置模板 Tauri 命令。
+//!
+//! 前端通过 `invoke` 调用这些命令，命令内部经应用装配层拿到
+//! [`InstanceTemplateService`] 维护模板，并预览或执行批量套用。
+//!
+//! 错误统一为接口契约错误 [`InstanceTemplateServiceError`]，可序列化回前端，
+//! 不携带底层敏感细节。
+
+use sealantern_application::services::AppServices;
+use sealantern_core::instance::{InstanceId, InstanceTemplate};
+use sealantern_interface::template::TemplateInstanceReport;
+use sealantern_interface::{InstanceTemplateService, InstanceTemplateServiceError};
+
+/// 解析 Tauri 命令传入的实例 ID 列表。
+///
+/// 统一映射解析错误为 [`InstanceTemplateServiceError::InvalidInput`]。
+fn parse_ids_for_tauri(ids: Vec<String>) -> Result<Vec<InstanceId>, InstanceTemplateServiceError> {
+    ids.into_iter()
+        .map(|id| InstanceId::new(id).map_err(|_| InstanceTemplateServiceError::InvalidInput))
+        .collect()
+}
+
+/// 列出全部实例配置模板。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn list_instance_templates() -> Result<Vec<InstanceTemplate>, InstanceTemplateServiceError>
+{
+    let service = AppServices::template_service()
+        .await
+        .map_err(|_| InstanceTemplateServiceError::OperationFailed)?;
+    service.list().await
+}
+
+/// 新建（ID 为空）或覆盖实例配置模板。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn save_instance_template(
+    template: InstanceTemplate,
+) -> Result<InstanceTemplate, InstanceTemplateServiceError> {
+    let service = AppServices::template_service()
+        .await
+        .map_err(|_| InstanceTemplateServiceError::OperationFailed)?;
+    service.save(template).await
+}
+
+/// 删除实例配置模板。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn delete_instance_template(id: String) -> Result<(), InstanceTemplateServiceError> {
+    let service = AppServices::template_service()
+        .await
+        .map_err(|_| InstanceTemplateServiceError::OperationFailed)?;
+    service.delete(&id).await
+}
+
+/// 预览模板套用到各实例的变化。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn preview_instance_template(
+    id: String,
+    instance_ids: Vec<String>,
+) -> Result<Vec<TemplateInstanceReport>, InstanceTemplateServiceError> {
+    let service = AppServices::template_service()
+        .await
+        .map_err(|_| InstanceTemplateServiceError::OperationFailed)?;
+    let instance_ids = parse_ids_for_tauri(instance_ids)?;
+    service.preview_apply(&id, &instance_ids).await
+}
+
+/// 把模板套用到各实例。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn apply_instance_template(
+    id: String,
+    instance_ids: Vec<String>,
+) -> Result<Vec<TemplateInstanceReport>, InstanceTemplateServiceError> {
+    let service = AppServices::template_service()
+        .await
+        .map_err(|_| InstanceTemplateServiceError::OperationFailed)?;
+    let instance_ids = parse_ids_for_tauri(instance_ids)?;
+    service.apply(&id, &instance_ids).await
+}
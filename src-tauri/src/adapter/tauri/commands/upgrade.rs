@@ -0,0 +1,47 @@
+//! 服务端核心原地升级 Tauri 命令。
+//!
+//! 前端通过 `invoke` 调用这些命令，命令内部经应用装配层拿到
+//! [`ServerUpgradeService`] 预检并执行实例服务端核心的原地升级。
+//!
+//! 错误统一为接口契约错误 [`ServerUpgradeServiceError`]，可序列化回前端，
+//! 不携带底层敏感细节。
+
+use sealantern_application::services::AppServices;
+use sealantern_core::instance::InstanceId;
+use sealantern_interface::upgrade::{
+    ServerUpgradeOutcome, ServerUpgradePlan, ServerUpgradeRequest,
+};
+use sealantern_interface::{ServerUpgradeService, ServerUpgradeServiceError};
+
+/// 解析 Tauri 命令传入的实例 ID 字符串。
+///
+/// 统一映射解析错误为 [`ServerUpgradeServiceError::InvalidInput`]。
+fn parse_id_for_tauri(id: String) -> Result<InstanceId, ServerUpgradeServiceError> {
+    InstanceId::new(id).map_err(|_| ServerUpgradeServiceError::InvalidInput)
+}
+
+/// 解析目标构建并检查 Java 与已装扩展的兼容性，不做任何改动。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn server_upgrade_plan(
+    id: String,
+    request: ServerUpgradeRequest,
+) -> Result<ServerUpgradePlan, ServerUpgradeServiceError> {
+    let service = AppServices::upgrade_service()
+        .await
+        .map_err(|_| ServerUpgradeServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.plan(&id, request).await
+}
+
+/// 备份实例后下载目标构建并替换服务端核心。
+#[tauri::command(rename_all = "snake_case")]
+pub async fn server_upgrade(
+    id: String,
+    request: ServerUpgradeRequest,
+) -> Result<ServerUpgradeOutcome, ServerUpgradeServiceError> {
+    let service = AppServices::upgrade_service()
+        .await
+        .map_err(|_| ServerUpgradeServiceError::OperationFailed)?;
+    let id = parse_id_for_tauri(id)?;
+    service.upgrade(&id, request).await
+}
@@ -2,7 +2,8 @@
 //!
 //! 订阅 application 层的全局日志事件广播（[`subscribe_log_events`]），
 //! 将日志行转发为 Tauri 前端事件 `server-log-line`，使前端控制台能
-//! 实时展示服务器输出，无需轮询。
+//! 实时展示服务器输出，无需轮询。由日志派生的在线玩家变更
+//! （[`subscribe_player_events`]）在同一任务中转发为 `server-player-event`。
 //!
 //! [`LogSenderState`] 负责转发任务的生命周期：
 //! - `start` 惰性启动单个转发任务（幂等，重复调用不会叠加任务）；
@@ -10,7 +11,7 @@
 //! - 任务内以 `tokio::select!` 同时监听停止信号与日志事件，避免
 //!   阻塞在事件接收上无法响应停止请求。
 
-use sealantern_application::service::subscribe_log_events;
+use sealantern_application::service::{subscribe_log_events, subscribe_player_events};
 use std::sync::Arc;
 use tauri::async_runtime::JoinHandle;
 use tauri::{AppHandle, Emitter, async_runtime::spawn};
@@ -61,6 +62,7 @@ impl LogSenderState {
 
         let handle = spawn(async move {
             let mut receiver = subscribe_log_events();
+            let mut player_receiver = subscribe_player_events();
             loop {
                 tokio::select! {
                     // 停止信号：`stop()` 已调用，任务自行退出。
@@ -86,6 +88,24 @@ impl LogSenderState {
                         Err(RecvError::Lagged(n)) => {
                             tracing::warn!(target: "sealantern.tauri.server_log", skipped = n, "server log subscriber lagged")
                         }
+                    },
+                    // 在线玩家变更：转发到前端；落后时前端可重新查询在线集合。
+                    event = player_receiver.recv() => match event {
+                        Ok(event) => {
+                            if let Err(e) = app_handle.emit("server-player-event", &event) {
+                                tracing::error!(
+                                    target: "sealantern.tauri.server_log",
+                                    error = e.to_string(),
+                                    "failed to emit server player event"
+                                )
+                            }
+                        }
+                        Err(RecvError::Closed) => {
+                            break;
+                        }
+                        Err(RecvError::Lagged(n)) => {
+                            tracing::warn!(target: "sealantern.tauri.server_log", skipped = n, "server player event subscriber lagged")
+                        }
                     }
                 }
             }
@@ -14,23 +14,41 @@ use adapter::tauri::commands::backup::{
     create_backup, delete_backup, get_backup_list, get_backup_settings, restore_backup,
     update_backup_settings,
 };
-use adapter::tauri::commands::catalog::{catalog_details, catalog_server_types, catalog_versions};
+use adapter::tauri::commands::catalog::{
+    catalog_builds, catalog_details, catalog_refresh, catalog_server_types, catalog_versions,
+};
+use adapter::tauri::commands::command::{
+    clear_command_history, delete_command_macro, get_command_history, list_command_macros,
+    save_command_macro,
+};
 use adapter::tauri::commands::console::get_server_logs;
 use adapter::tauri::commands::cron::{
     create_cron_task, delete_cron_task, list_cron_tasks, run_cron_task, set_cron_task_enabled,
     update_cron_task,
 };
-use adapter::tauri::commands::download::{download_cancel, download_create, download_query};
+use adapter::tauri::commands::download::{
+    download_cancel, download_create, download_limits, download_pause, download_query,
+    download_resume, download_resume_incomplete, download_set_limits,
+};
+use adapter::tauri::commands::extension::toggle_instance_extension;
+use adapter::tauri::commands::installer::{installer_start, installer_status};
 use adapter::tauri::commands::instance::{
     create_instance, delete_instance, get_instance, import_existing_server, import_modpack,
-    list_instances, rename_instance, update_instance_path,
+    list_instance_extensions, list_instances, list_port_conflicts, rename_instance,
+    update_instance_path,
 };
 use adapter::tauri::commands::java::{java_detect, java_validate};
 use adapter::tauri::commands::logging::share_logs;
+use adapter::tauri::commands::market::{
+    apply_market_updates, check_market_updates, install_market_resource, list_installed_resources,
+    list_market_update_rollbacks, plan_market_install, rollback_market_update,
+    uninstall_market_resource,
+};
 use adapter::tauri::commands::online_tunnel::{
     OnlineTunnelEventForwarder, online_tunnel_host, online_tunnel_join, online_tunnel_status,
     online_tunnel_stop,
 };
+use adapter::tauri::commands::player::{get_online_players, get_player_sessions};
 use adapter::tauri::commands::plugin::{
     plugin_v2_approve_session, plugin_v2_audit, plugin_v2_disable, plugin_v2_discover,
     plugin_v2_enable, plugin_v2_end_session, plugin_v2_grant_persistent, plugin_v2_grant_session,
@@ -42,13 +60,15 @@ use adapter::tauri::commands::provisioning::{
     plan_modpack_provision,
 };
 use adapter::tauri::commands::server::{
-    force_stop_server, restart_server, send_server_command, server_status, start_server,
-    stop_server,
+    force_stop_server, probe_server, restart_server, send_server_command,
+    send_server_command_and_capture, server_status, start_server, stop_server,
 };
 use adapter::tauri::commands::server_config::{
-    parse_server_properties_source, preview_server_properties_write,
-    preview_server_properties_write_from_source, read_server_properties,
-    read_server_properties_source, write_server_properties, write_server_properties_source,
+    diff_config_revisions, get_config_revision, list_config_documents, list_config_revisions,
+    parse_server_properties_source, preview_config_document_write, preview_server_properties_write,
+    preview_server_properties_write_from_source, read_config_document, read_server_properties,
+    read_server_properties_source, restore_config_revision, write_config_document,
+    write_server_properties, write_server_properties_source,
 };
 use adapter::tauri::commands::settings::{
     export_settings, get_settings, import_settings, reset_settings, settings_overview,
@@ -57,10 +77,15 @@ use adapter::tauri::commands::settings::{
 use adapter::tauri::commands::system::{
     get_default_run_path, get_server_resource_usage, get_system_snapshot,
 };
+use adapter::tauri::commands::template::{
+    apply_instance_template, delete_instance_template, list_instance_templates,
+    preview_instance_template, save_instance_template,
+};
 use adapter::tauri::commands::update::check_update;
 use adapter::tauri::commands::update_install::{
     update_clear_pending, update_download, update_install, update_pending,
 };
+use adapter::tauri::commands::upgrade::{server_upgrade, server_upgrade_plan};
 use adapter::tauri::events::LogSenderState;
 use desktop::{
     AutoLightweightState, DesktopAppearanceState, MainWindowState, apply_acrylic,
@@ -129,11 +154,19 @@ fn main() {
             restore_backup,
             update_backup_settings,
             //服务器配置管理契约命令
+            diff_config_revisions,
+            get_config_revision,
+            list_config_documents,
+            list_config_revisions,
             parse_server_properties_source,
+            preview_config_document_write,
             preview_server_properties_write,
             preview_server_properties_write_from_source,
+            read_config_document,
             read_server_properties,
             read_server_properties_source,
+            restore_config_revision,
+            write_config_document,
             write_server_properties,
             write_server_properties_source,
             //服务器定时任务契约命令
@@ -144,11 +177,45 @@ fn main() {
             set_cron_task_enabled,
             update_cron_task,
             //服务器类型目录契约命令
+            catalog_builds,
             catalog_details,
+            catalog_refresh,
             catalog_server_types,
             catalog_versions,
             //服务器控制台日志契约命令
             get_server_logs,
+            //在线玩家与会话历史契约命令
+            get_online_players,
+            get_player_sessions,
+            //控制台命令历史与宏契约命令
+            clear_command_history,
+            delete_command_macro,
+            get_command_history,
+            list_command_macros,
+            save_command_macro,
+            //实例配置模板契约命令
+            apply_instance_template,
+            delete_instance_template,
+            list_instance_templates,
+            preview_instance_template,
+            save_instance_template,
+            //市场资源安装与更新契约命令
+            apply_market_updates,
+            check_market_updates,
+            install_market_resource,
+            list_installed_resources,
+            list_market_update_rollbacks,
+            plan_market_install,
+            rollback_market_update,
+            uninstall_market_resource,
+            //扩展启停契约命令
+            toggle_instance_extension,
+            //加载器安装器契约命令
+            installer_start,
+            installer_status,
+            //服务端核心原地升级契约命令
+            server_upgrade_plan,
+            server_upgrade,
             //系统资源能力（由adapter/tauri/commands接入application）
             get_default_run_path,
             get_server_resource_usage,
@@ -161,7 +228,9 @@ fn main() {
             get_instance,
             import_existing_server,
             import_modpack,
+            list_instance_extensions,
             list_instances,
+            list_port_conflicts,
             rename_instance,
             update_instance_path,
             //Java 运行时检测与校验
@@ -173,15 +242,22 @@ fn main() {
             online_tunnel_status,
             online_tunnel_stop,
             force_stop_server,
+            probe_server,
             restart_server,
             send_server_command,
+            send_server_command_and_capture,
             server_status,
             start_server,
             stop_server,
             //下载与设置服务
             download_cancel,
             download_create,
+            download_limits,
+            download_pause,
             download_query,
+            download_resume,
+            download_resume_incomplete,
+            download_set_limits,
             export_settings,
             get_settings,
             import_settings,
@@ -305,6 +381,31 @@ mod tests {
         "run_cron_task",
         "set_cron_task_enabled",
         "update_cron_task",
+        "get_online_players",
+        "get_player_sessions",
+        "clear_command_history",
+        "delete_command_macro",
+        "get_command_history",
+        "list_command_macros",
+        "save_command_macro",
+        "apply_instance_template",
+        "delete_instance_template",
+        "list_instance_templates",
+        "preview_instance_template",
+        "save_instance_template",
+        "apply_market_updates",
+        "check_market_updates",
+        "install_market_resource",
+        "list_installed_resources",
+        "list_market_update_rollbacks",
+        "plan_market_install",
+        "rollback_market_update",
+        "uninstall_market_resource",
+        "toggle_instance_extension",
+        "installer_start",
+        "installer_status",
+        "server_upgrade_plan",
+        "server_upgrade",
         "get_default_run_path",
         "get_server_resource_usage",
         "get_system_snapshot",
@@ -312,6 +413,8 @@ mod tests {
         "delete_instance",
         "get_instance",
         "list_instances",
+        "list_port_conflicts",
+        "list_instance_extensions",
         "rename_instance",
         "update_instance_path",
         "import_existing_server",
@@ -321,14 +424,21 @@ mod tests {
         "online_tunnel_status",
         "online_tunnel_stop",
         "force_stop_server",
+        "probe_server",
         "restart_server",
         "send_server_command",
+        "send_server_command_and_capture",
         "server_status",
         "start_server",
         "stop_server",
         "download_cancel",
         "download_create",
+        "download_limits",
+        "download_pause",
         "download_query",
+        "download_resume",
+        "download_resume_incomplete",
+        "download_set_limits",
         "export_settings",
         "get_settings",
         "import_settings",
@@ -10,6 +10,12 @@ export interface ConfigEntry {
   value_type: string;
   default_value: string;
   category: string;
+  allowed_values?: string[];
+  min?: number | null;
+  max?: number | null;
+  restart_required?: boolean;
+  unknown?: boolean;
+  invalid_reason?: string | null;
 }
 
 /**
@@ -18,6 +24,88 @@ export interface ConfigEntry {
 export interface ServerProperties {
   entries: ConfigEntry[];
   raw: Record<string, string>;
+  unknown_keys?: string[];
+}
+
+/**
+ * 通用配置文档格式
+ */
+export type DocumentFormat = "yaml" | "toml" | "json5";
+
+/**
+ * 可编辑的配置文件
+ */
+export interface ConfigDocumentFile {
+  path: string;
+  format: DocumentFormat;
+  size: number;
+}
+
+/**
+ * 展开后的叶子配置项
+ */
+export interface DocumentEntry {
+  path: string[];
+  value: unknown;
+  value_type: string;
+}
+
+/**
+ * 读取到的配置文档
+ */
+export interface ConfigDocumentView {
+  path: string;
+  format: DocumentFormat;
+  entries: DocumentEntry[];
+  source: string;
+}
+
+/**
+ * 对单个键路径的修改
+ */
+export interface DocumentPatch {
+  path: string[];
+  value: boolean | number | string | null;
+}
+
+/**
+ * 修改前后的一行差异
+ */
+export interface DiffLine {
+  kind: "added" | "removed";
+  old_line: number | null;
+  new_line: number | null;
+  text: string;
+}
+
+/**
+ * 配置文档修改预览
+ */
+export interface ConfigDocumentPreview {
+  path: string;
+  format: DocumentFormat;
+  source: string;
+  diff: DiffLine[];
+}
+
+/**
+ * 配置修订摘要
+ */
+export interface ConfigRevisionSummary {
+  id: number;
+  file: string;
+  principal_kind: string;
+  principal_id: string;
+  created_at: number;
+  diff: string;
+  restored_from: number | null;
+}
+
+/**
+ * 含完整快照的配置修订
+ */
+export interface ConfigRevision extends ConfigRevisionSummary {
+  snapshot: string;
 }
 
 /**
@@ -34,20 +122,27 @@ export interface SLStartupConfig {
 export const configApi = {
   /**
    * 读取服务器配置文件 (server.properties)
+   * gameVersion 用于挑选对应版本的字段模式，缺省按最新版本
    */
-  async readServerProperties(serverPath: string): Promise<ServerProperties> {
+  async readServerProperties(serverPath: string, gameVersion?: string): Promise<ServerProperties> {
     return tauriInvoke("read_server_properties", {
       serverPath,
+      gameVersion,
     });
   },
 
   /**
    * 写入服务器配置文件
    */
-  async writeServerProperties(serverPath: string, values: Record<string, string>): Promise<void> {
+  async writeServerProperties(
+    serverPath: string,
+    values: Record<string, string>,
+    gameVersion?: string,
+  ): Promise<void> {
     return tauriInvoke("write_server_properties", {
       serverPath,
       values,
+      gameVersion,
     });
   },
 
@@ -73,9 +168,13 @@ export const configApi = {
   /**
    * 将原始文本解析为可视化配置结构
    */
-  async parseServerPropertiesSource(source: string): Promise<ServerProperties> {
+  async parseServerPropertiesSource(
+    source: string,
+    gameVersion?: string,
+  ): Promise<ServerProperties> {
     return tauriInvoke("parse_server_properties_source", {
       source,
+      gameVersion,
     });
   },
 
@@ -85,10 +184,12 @@ export const configApi = {
   async previewServerPropertiesWrite(
     serverPath: string,
     values: Record<string, string>,
+    gameVersion?: string,
   ): Promise<string> {
     return tauriInvoke("preview_server_properties_write", {
       serverPath,
       values,
+      gameVersion,
     });
   },
 
@@ -98,13 +199,86 @@ export const configApi = {
   async previewServerPropertiesWriteFromSource(
     source: string,
     values: Record<string, string>,
+    gameVersion?: string,
   ): Promise<string> {
     return tauriInvoke("preview_server_properties_write_from_source", {
       source,
       values,
+      gameVersion,
     });
   },
 
+  /**
+   * 列出可编辑的 YAML / TOML / JSON5 配置文件
+   */
+  async listConfigDocuments(serverPath: string): Promise<ConfigDocumentFile[]> {
+    return tauriInvoke("list_config_documents", { serverPath });
+  },
+
+  /**
+   * 读取配置文档
+   */
+  async readConfigDocument(serverPath: string, path: string): Promise<ConfigDocumentView> {
+    return tauriInvoke("read_config_document", { serverPath, path });
+  },
+
+  /**
+   * 预览按键路径修改配置文档后的文本与差异
+   */
+  async previewConfigDocumentWrite(
+    serverPath: string,
+    path: string,
+    patches: DocumentPatch[],
+  ): Promise<ConfigDocumentPreview> {
+    return tauriInvoke("preview_config_document_write", { serverPath, path, patches });
+  },
+
+  /**
+   * 按键路径修改配置文档并写回（保留注释与顺序）
+   */
+  async writeConfigDocument(
+    serverPath: string,
+    path: string,
+    patches: DocumentPatch[],
+  ): Promise<ConfigDocumentPreview> {
+    return tauriInvoke("write_config_document", { serverPath, path, patches });
+  },
+
+  /**
+   * 列出配置修订（时间倒序），可按文件过滤
+   */
+  async listConfigRevisions(
+    serverPath: string,
+    file?: string,
+    limit?: number,
+  ): Promise<ConfigRevisionSummary[]> {
+    return tauriInvoke("list_config_revisions", { serverPath, file, limit });
+  },
+
+  /**
+   * 读取一条配置修订及其完整快照
+   */
+  async getConfigRevision(serverPath: string, id: number): Promise<ConfigRevision | null> {
+    return tauriInvoke("get_config_revision", { serverPath, id });
+  },
+
+  /**
+   * 比较任意两条配置修订，返回统一差异
+   */
+  async diffConfigRevisions(serverPath: string, from: number, to: number): Promise<string> {
+    return tauriInvoke("diff_config_revisions", { serverPath, from, to });
+  },
+
+  /**
+   * 将配置文件恢复为指定修订
+   */
+  async restoreConfigRevision(
+    serverPath: string,
+    id: number,
+  ): Promise<ConfigRevisionSummary | null> {
+    return tauriInvoke("restore_config_revision", { serverPath, id });
+  },
+
   /**
    * 读取通用配置文件
    */
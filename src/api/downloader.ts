@@ -2,7 +2,16 @@ import { reactive, onUnmounted, computed } from "vue";
 import { invoke } from "./invoke";
 import { i18n } from "@language";
 
-export type TaskStatus = "Pending" | "Downloading" | "Completed" | { Error: string };
+// Queued：在全局下载队列中等待运行名额；Paused：已暂停，保留已下载的部分
+export type TaskStatus =
+  | "Queued"
+  | "Pending"
+  | "Downloading"
+  | "Paused"
+  | "Completed"
+  | { Error: string };
+
+export type DownloadPriority = "low" | "normal" | "high";
 
 export interface DownloadTaskInfo {
   id: string;
@@ -10,13 +19,48 @@ export interface DownloadTaskInfo {
   downloaded: number;
   progress: number;
   status: TaskStatus;
+  error_kind: DownloadErrorKind | null; // 失败类别，仅在 status 为 Error 时给出
   is_finished: boolean;
+  queue_position: number | null; // 队列位置（从 1 开始），运行中、暂停或已结束时为 null
+}
+
+// 下载失败类别：size_mismatch / checksum_mismatch 表示文件未通过完整性校验
+export type DownloadErrorKind =
+  | "network"
+  | "storage"
+  | "cancelled"
+  | "remote_changed"
+  | "size_mismatch"
+  | "checksum_mismatch"
+  | "other";
+
+// 全局下载限制：超出 max_active_tasks 的任务排队
+export interface DownloadLimits {
+  max_active_tasks: number;
+  max_connections: number; // 所有任务合计的连接数
+  bandwidth_limit: number | null; // 字节/秒，null 表示不限速
 }
 
 export interface DownloadOptions {
   url: string;
+  // 同一文件的备用镜像，按优先级排列；后端按镜像健康度排序并在失败或停滞时切换
+  mirrors?: string[];
   save_path: string;
   thread_count?: number;
+  // 预期大小与摘要；下载完成后校验，不符时任务以错误结束且不会覆盖 save_path
+  expected_size?: number;
+  sha1?: string;
+  sha256?: string;
+  sha512?: string;
+  priority?: DownloadPriority;
+  bandwidth_limit?: number; // 任务自身的带宽上限（字节/秒），同时受全局限制
+}
+
+// 启动时续传的任务；续传失败时 task.status 为 Error，进度保留到下次启动
+export interface ResumedDownload {
+  task: DownloadTaskInfo;
+  url: string;
+  save_path: string;
 }
 
 export interface DownloadLink {
@@ -25,6 +69,28 @@ export interface DownloadLink {
   url: string; // 下载URL
 }
 
+// 目录查询结果；stale 为 true 表示离线，data 为缓存中最后一次成功的结果
+export interface CatalogSnapshot<T> {
+  data: T;
+  stale: boolean;
+  fetched_at: number; // 获取时间（Unix 秒）
+}
+
+// 服务器核心构建
+export interface CatalogBuild {
+  server_type: string; // 服务器核心类型（小写）
+  game_version: string; // Minecraft 版本
+  build: string; // 构建号或加载器版本
+  channel: "stable" | "beta" | "alpha" | "snapshot"; // 发布渠道
+  artifact: "server_jar" | "installer"; // 文件类型
+  url: string; // 下载URL
+  file_name: string; // 文件名
+  size: number | null; // 文件大小（字节）
+  hashes: Record<string, string>; // 摘要，键为算法名
+  java_major: number | null; // 最低 Java 主版本
+  provider: string; // 目录来源
+}
+
 // 类型下载链接集合
 export interface TypeDownloadLinks {
   server_type: string; // 服务器类型名称
@@ -47,8 +113,15 @@ export const downloadApi = {
     const task = await invoke<DownloadTaskInfo>("download_create", {
       request: {
         url: options.url,
+        mirrors: options.mirrors ?? [],
         save_path: options.save_path,
         thread_count: options.thread_count || 32,
+        expected_size: options.expected_size,
+        sha1: options.sha1,
+        sha256: options.sha256,
+        sha512: options.sha512,
+        priority: options.priority ?? "normal",
+        bandwidth_limit: options.bandwidth_limit,
       },
     });
     return task.id;
@@ -68,6 +141,41 @@ export const downloadApi = {
     return invoke<void>("download_cancel", { id });
   },
 
+  /**
+   * 暂停下载任务；远端不支持断点续传的任务开始下载后不能暂停
+   */
+  async pauseTask(id: string): Promise<void> {
+    return invoke<void>("download_pause", { id });
+  },
+
+  /**
+   * 恢复已暂停的任务（重新排队）
+   */
+  async resumeTask(id: string): Promise<DownloadTaskInfo> {
+    return invoke<DownloadTaskInfo>("download_resume", { id });
+  },
+
+  /**
+   * 查询全局下载限制
+   */
+  async getLimits(): Promise<DownloadLimits> {
+    return invoke<DownloadLimits>("download_limits");
+  },
+
+  /**
+   * 修改全局下载限制，立即对所有任务生效
+   */
+  async setLimits(limits: DownloadLimits): Promise<DownloadLimits> {
+    return invoke<DownloadLimits>("download_set_limits", { limits });
+  },
+
+  /**
+   * 续传上次运行中未完成的下载任务，任务沿用原 ID，可继续 pollTask
+   */
+  async resumeIncomplete(): Promise<ResumedDownload[]> {
+    return invoke<ResumedDownload[]>("download_resume_incomplete");
+  },
+
   /**
    * 启动并自动轮询
    */
@@ -78,7 +186,9 @@ export const downloadApi = {
       downloaded: 0,
       progress: 0,
       status: "Pending",
+      error_kind: null,
       is_finished: false,
+      queue_position: null,
     });
 
     const errorMessage = computed(() => {
@@ -100,6 +210,7 @@ export const downloadApi = {
       taskInfo.is_finished = false;
       taskInfo.progress = 0;
       taskInfo.status = "Pending";
+      taskInfo.error_kind = null;
 
       try {
         const id = await this.downloadFile(options);
@@ -168,7 +279,9 @@ export const downloadApi = {
       taskInfo.downloaded = 0;
       taskInfo.progress = 0;
       taskInfo.status = "Pending";
+      taskInfo.error_kind = null;
       taskInfo.is_finished = false;
+      taskInfo.queue_position = null;
     };
 
     onUnmounted(stop);
@@ -178,12 +291,23 @@ export const downloadApi = {
 };
 
 export const downloadServerApi = {
-  async getServerTypes(): Promise<string[]> {
-    return invoke<string[]>("catalog_server_types");
+  async getServerTypes(): Promise<CatalogSnapshot<string[]>> {
+    return invoke<CatalogSnapshot<string[]>>("catalog_server_types");
+  },
+
+  async refreshCatalog(): Promise<CatalogSnapshot<string[]>> {
+    return invoke<CatalogSnapshot<string[]>>("catalog_refresh");
   },
 
-  async getVersionsByType(serverType: string): Promise<string[]> {
-    return invoke<string[]>("catalog_versions", { server_type: serverType });
+  async getVersionsByType(serverType: string): Promise<CatalogSnapshot<string[]>> {
+    return invoke<CatalogSnapshot<string[]>>("catalog_versions", { server_type: serverType });
+  },
+
+  async getBuilds(serverType: string, version: string): Promise<CatalogSnapshot<CatalogBuild[]>> {
+    return invoke<CatalogSnapshot<CatalogBuild[]>>("catalog_builds", {
+      server_type: serverType,
+      server_version: version,
+    });
   },
 
   async getDownloadInfo(serverType: string, version: string): Promise<DownloadLink> {
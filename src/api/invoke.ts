@@ -28,7 +28,36 @@ interface AxumRoute {
 // 仅含后端已实现的接口，缺失方法浏览器模式抛 NotImplemented
 const axumRouteMap: Record<string, AxumRoute> = {
   list_instances: { method: "GET", path: () => "/instances" },
+  list_port_conflicts: { method: "GET", path: () => "/instances/port-conflicts" },
   get_instance: { method: "GET", path: (a) => `/instances/${encodeURIComponent(String(a.id))}` },
+  list_instance_extensions: {
+    method: "GET",
+    path: (a) => `/instances/${encodeURIComponent(String(a.id))}/extensions`,
+  },
+  toggle_instance_extension: {
+    method: "POST",
+    path: (a) => `/instances/${encodeURIComponent(String(a.id))}/extensions/toggle`,
+    body: (a) => a.request,
+  },
+  installer_start: {
+    method: "POST",
+    path: (a) => `/instances/${encodeURIComponent(String(a.id))}/installer`,
+    body: (a) => a.request,
+  },
+  installer_status: {
+    method: "GET",
+    path: (a) => `/instances/${encodeURIComponent(String(a.id))}/installer`,
+  },
+  server_upgrade_plan: {
+    method: "POST",
+    path: (a) => `/instances/${encodeURIComponent(String(a.id))}/upgrade/plan`,
+    body: (a) => a.request,
+  },
+  server_upgrade: {
+    method: "POST",
+    path: (a) => `/instances/${encodeURIComponent(String(a.id))}/upgrade`,
+    body: (a) => a.request,
+  },
   create_instance: { method: "POST", path: () => "/instances", body: (a) => a.spec },
   delete_instance: {
     method: "DELETE",
@@ -68,7 +97,7 @@ const axumRouteMap: Record<string, AxumRoute> = {
   send_server_command: {
     method: "POST",
     path: (a) => `/instances/${encodeURIComponent(String(a.id))}/command`,
-    body: (a) => ({ command: a.command }),
+    body: (a) => ({ command: a.command, transport: a.transport }),
   },
   get_system_snapshot: { method: "GET", path: () => "/system" },
   get_default_run_path: { method: "GET", path: () => "/system/default-run-path" },
@@ -113,6 +142,21 @@ const axumRouteMap: Record<string, AxumRoute> = {
     method: "DELETE",
     path: (a) => `/downloads/${encodeURIComponent(String(a.id))}`,
   },
+  download_pause: {
+    method: "POST",
+    path: (a) => `/downloads/${encodeURIComponent(String(a.id))}/pause`,
+  },
+  download_resume: {
+    method: "POST",
+    path: (a) => `/downloads/${encodeURIComponent(String(a.id))}/resume`,
+  },
+  download_limits: { method: "GET", path: () => "/downloads/limits" },
+  download_set_limits: {
+    method: "PUT",
+    path: () => "/downloads/limits",
+    body: (a) => a.limits,
+  },
+  download_resume_incomplete: { method: "POST", path: () => "/downloads/resume" },
   // 服务器检测
   inspect_server: {
     method: "POST",
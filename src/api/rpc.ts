@@ -51,7 +51,14 @@ interface AxumRoute {
 const tauriCommandMap: Record<string, string> = {
   // 实例管理
   "instance.list": "list_instances",
+  "instance.portConflicts": "list_port_conflicts",
   "instance.get": "get_instance",
+  "instance.extensions": "list_instance_extensions",
+  "instance.toggleExtension": "toggle_instance_extension",
+  "instance.startInstaller": "installer_start",
+  "instance.installerStatus": "installer_status",
+  "instance.upgradePlan": "server_upgrade_plan",
+  "instance.upgrade": "server_upgrade",
   "instance.create": "create_instance",
   "instance.delete": "delete_instance",
   "instance.rename": "rename_instance",
@@ -84,10 +91,17 @@ const tauriCommandMap: Record<string, string> = {
   "catalog.serverTypes": "catalog_server_types",
   "catalog.versions": "catalog_versions",
   "catalog.details": "catalog_details",
+  "catalog.builds": "catalog_builds",
+  "catalog.refresh": "catalog_refresh",
   // 下载任务
   "download.create": "download_create",
   "download.query": "download_query",
   "download.cancel": "download_cancel",
+  "download.pause": "download_pause",
+  "download.resume": "download_resume",
+  "download.limits": "download_limits",
+  "download.setLimits": "download_set_limits",
+  "download.resumeIncomplete": "download_resume_incomplete",
   // 在线隧道
   "tunnel.host": "online_tunnel_host",
   "tunnel.join": "online_tunnel_join",
@@ -129,7 +143,36 @@ const tauriCommandMap: Record<string, string> = {
 // Axum 路由映射：仅含后端已实现的接口，缺失方法浏览器模式抛 NotImplemented
 const axumRouteMap: Record<string, AxumRoute> = {
   "instance.list": { method: "GET", path: () => "/instances" },
+  "instance.portConflicts": { method: "GET", path: () => "/instances/port-conflicts" },
   "instance.get": { method: "GET", path: (a) => `/instances/${encodeURIComponent(String(a.id))}` },
+  "instance.extensions": {
+    method: "GET",
+    path: (a) => `/instances/${encodeURIComponent(String(a.id))}/extensions`,
+  },
+  "instance.toggleExtension": {
+    method: "POST",
+    path: (a) => `/instances/${encodeURIComponent(String(a.id))}/extensions/toggle`,
+    body: (a) => a.request,
+  },
+  "instance.startInstaller": {
+    method: "POST",
+    path: (a) => `/instances/${encodeURIComponent(String(a.id))}/installer`,
+    body: (a) => a.request,
+  },
+  "instance.installerStatus": {
+    method: "GET",
+    path: (a) => `/instances/${encodeURIComponent(String(a.id))}/installer`,
+  },
+  "instance.upgradePlan": {
+    method: "POST",
+    path: (a) => `/instances/${encodeURIComponent(String(a.id))}/upgrade/plan`,
+    body: (a) => a.request,
+  },
+  "instance.upgrade": {
+    method: "POST",
+    path: (a) => `/instances/${encodeURIComponent(String(a.id))}/upgrade`,
+    body: (a) => a.request,
+  },
   "instance.create": { method: "POST", path: () => "/instances", body: (a) => a.spec },
   "instance.delete": {
     method: "DELETE",
@@ -1,6 +1,8 @@
 import { tauriInvoke, isBrowserEnv, HTTP_API_BASE } from "@api/tauri";
 import { invoke } from "@api/invoke";
 import type { ServerInstance } from "@type/server";
+import type { BackupItem } from "@api/backup";
+import type { CatalogBuild } from "@api/downloader";
 import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 
 export interface ServerStatusInfo {
@@ -37,6 +39,129 @@ export interface ConsoleLogLine {
   line: string;
 }
 
+/** 多个实例（或同一实例的多个用途）占用同一端口 */
+export interface PortConflict {
+  port: number;
+  protocol: "tcp" | "udp";
+  holders: { instance_id: string; kind: "server" | "query" | "rcon" | "proxy_listener" }[];
+  host_in_use: boolean;
+}
+
+/** 插件或模组 jar 内读取的元数据 */
+export interface ExtensionMetadata {
+  format: "bukkit" | "paper" | "fabric" | "quilt" | "forge" | "neo_forge" | "velocity";
+  id: string;
+  name: string | null;
+  version: string | null;
+  authors: string[];
+  dependencies: ExtensionDependency[];
+  provides: string[];
+  game_versions: string | null;
+  loader_versions: string | null;
+}
+
+export interface ExtensionDependency {
+  id: string;
+  required: boolean;
+  version_range: string | null;
+}
+
+/** 实例中的插件、模组或数据包 */
+export interface InstanceExtension {
+  kind: "plugin" | "mod" | "datapack";
+  file_name: string;
+  /** 相对实例目录的路径 */
+  path: string;
+  enabled: boolean;
+  metadata: ExtensionMetadata | null;
+}
+
+/** 扩展之间的一致性问题 */
+export type ExtensionIssue =
+  | {
+      kind: "duplicate_id";
+      extension_kind: InstanceExtension["kind"];
+      id: string;
+      file_names: string[];
+    }
+  | { kind: "missing_dependency"; file_name: string; id: string; dependency: ExtensionDependency };
+
+/** 启用或禁用扩展的请求；`file_name` 可为启用或禁用状态下的名称 */
+export interface ExtensionToggleRequest {
+  kind: InstanceExtension["kind"];
+  file_name: string;
+  enabled: boolean;
+  /** 服务器运行中也执行，改动在下次启动后生效 */
+  force?: boolean;
+}
+
+export interface InstanceExtensionReport {
+  extensions: InstanceExtension[];
+  issues: ExtensionIssue[];
+}
+
+/** 执行加载器安装器的请求；省略的字段取自实例记录或推荐构建 */
+export interface InstallerRequest {
+  core_type?: "forge" | "neoforge" | "fabric";
+  game_version?: string;
+  loader_version?: string;
+  java_path?: string;
+}
+
+/** 安装任务信息；安装器输出写入实例控制台日志 */
+export interface InstallerTaskInfo {
+  instance_id: string;
+  stage: "resolving" | "downloading" | "installing" | "inspecting" | "completed" | "failed";
+  core_type: string;
+  game_version: string;
+  loader_version: string | null;
+  installer: string | null;
+  exit_code: number | null;
+  adopted_launch_profile: string | null;
+  error: string | null;
+  is_finished: boolean;
+}
+
+/** 原地升级服务端核心的请求；省略游戏版本时只更新构建，省略构建时取推荐构建 */
+export interface ServerUpgradeRequest {
+  game_version?: string;
+  build?: string;
+  /** Java 不满足目标构建要求时仍然升级 */
+  force?: boolean;
+}
+
+/** 升级前检查结果，不修改实例 */
+export interface ServerUpgradePlan {
+  instance_id: string;
+  core_type: string;
+  current_core_version: string;
+  current_game_version: string;
+  target: CatalogBuild;
+  /** 目录查询失败，目标构建来自离线缓存 */
+  catalog_stale: boolean;
+  java: {
+    required_major: number | null;
+    current_path: string | null;
+    current_major: number | null;
+    satisfied: boolean;
+    suggested_path: string | null;
+  };
+  /** 声明的游戏版本不包含目标版本的已启用插件与模组 */
+  extension_warnings: {
+    kind: InstanceExtension["kind"];
+    file_name: string;
+    id: string;
+    declared_game_versions: string;
+  }[];
+}
+
+export interface ServerUpgradeOutcome {
+  plan: ServerUpgradePlan;
+  /** 升级前自动创建的备份 */
+  backup: BackupItem;
+  instance: ServerInstance;
+}
+
 export interface ForceStopPreparation {
   token: string;
   expiresAt: number;
@@ -200,6 +325,8 @@ interface InstanceRaw {
   last_started_at_unix_secs: number | null;
   server_metadata: unknown;
   launch: LocalLaunchRaw;
+  /** 仅实例列表返回：该实例涉及的端口冲突 */
+  port_conflicts?: PortConflict[];
 }
 
 /** 后端启动配置，前端把部分字段平铺到 ServerInstance */
@@ -241,6 +368,7 @@ function toServerInstance(i: InstanceRaw): ServerInstance {
     port: i.port,
     created_at: i.created_at_unix_secs,
     last_started_at: i.last_started_at_unix_secs,
+    port_conflicts: i.port_conflicts ?? [],
   };
 }
 
@@ -334,6 +462,7 @@ export const serverApi = {
     startupFilePath?: string;
     coreType?: string;
     mcVersion?: string;
+    curseforgeApiKey?: string;
   }): Promise<ServerInstance> {
     return tauriInvoke("import_modpack", {
       request: {
@@ -350,6 +479,7 @@ export const serverApi = {
         startupFilePath: params.startupFilePath,
         coreType: params.coreType,
         mcVersion: params.mcVersion,
+        curseforgeApiKey: params.curseforgeApiKey,
       },
     });
   },
@@ -520,6 +650,34 @@ export const serverApi = {
     return raw.map(toServerInstance);
   },
 
+  async getExtensions(id: string): Promise<InstanceExtensionReport> {
+    return invoke<InstanceExtensionReport>("list_instance_extensions", { id });
+  },
+
+  async toggleExtension(id: string, request: ExtensionToggleRequest): Promise<InstanceExtension> {
+    return invoke<InstanceExtension>("toggle_instance_extension", { id, request });
+  },
+
+  async startInstaller(id: string, request: InstallerRequest = {}): Promise<InstallerTaskInfo> {
+    return invoke<InstallerTaskInfo>("installer_start", { id, request });
+  },
+
+  async getInstallerStatus(id: string): Promise<InstallerTaskInfo | null> {
+    return invoke<InstallerTaskInfo | null>("installer_status", { id });
+  },
+
+  async planUpgrade(id: string, request: ServerUpgradeRequest = {}): Promise<ServerUpgradePlan> {
+    return invoke<ServerUpgradePlan>("server_upgrade_plan", { id, request });
+  },
+
+  async upgradeCore(id: string, request: ServerUpgradeRequest = {}): Promise<ServerUpgradeOutcome> {
+    const raw = await invoke<Omit<ServerUpgradeOutcome, "instance"> & { instance: InstanceRaw }>(
+      "server_upgrade",
+      { id, request },
+    );
+    return { ...raw, instance: toServerInstance(raw.instance) };
+  },
+
   async getStatus(id: string): Promise<ServerStatusInfo> {
     const raw = await invoke<ServerSnapshotRaw>("server_status", { id });
     return toServerStatusInfo(raw);
@@ -52,6 +52,8 @@ export interface AppSettings {
   developer_mode: boolean;
   close_action: string;
   proxy: ProxySettings;
+  curseforge_api_key?: string | null;
+  curseforge_api_key?: string | null;
   last_run_path: string;
   minimal_mode: boolean;
   agreed_to_terms: boolean;
@@ -91,6 +93,8 @@ export interface PartialSettings {
   developer_mode?: boolean;
   close_action?: string;
   proxy?: ProxySettings;
+  curseforge_api_key?: string | null;
+  curseforge_api_key?: string | null;
   last_run_path?: string;
   minimal_mode?: boolean;
   agreed_to_terms?: boolean;
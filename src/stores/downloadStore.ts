@@ -66,6 +66,12 @@ export const useDownloadStore = defineStore("download", () => {
     return null;
   });
 
+  /** 已暂停 */
+  const isPaused = computed(() => currentTask.value?.status === "Paused");
+
+  /** 在全局下载队列中等待，给出排队位置（从 1 开始），没排队返回 null */
+  const queuePosition = computed(() => currentTask.value?.queue_position ?? null);
+
   /** 出错终止，isFinished 且有错误 */
   const isError = computed(() => isFinished.value && !!taskError.value);
 
@@ -75,10 +81,12 @@ export const useDownloadStore = defineStore("download", () => {
   /** 下载进度百分比 0-100 */
   const progress = computed(() => currentTask.value?.progress ?? 0);
 
-  /** 状态文本，下载中/已完成/失败，给按钮等场景用 */
+  /** 状态文本，排队中/已暂停/下载中/已完成/失败，给按钮等场景用 */
   const statusLabel = computed(() => {
     if (taskError.value) return i18n.t("download-file.failed");
     if (isCompleted.value) return i18n.t("downloadServerView.status.finished");
+    if (isPaused.value) return i18n.t("download-file.paused");
+    if (queuePosition.value !== null) return i18n.t("download-file.queued");
     return i18n.t("download-file.downloading");
   });
 
@@ -150,6 +158,66 @@ export const useDownloadStore = defineStore("download", () => {
     lastTimestamp = 0;
   }
 
+  /**
+   * 轮询任务进度直到结束，会话变了就停
+   * @param id 任务 ID
+   * @param session 发起轮询时的会话号
+   */
+  function startPolling(id: string, session: number) {
+    let pollingInFlight = false;
+    pollTimer = window.setInterval(async () => {
+      if (session !== activeSession) {
+        stopPolling();
+        return;
+      }
+      const task = currentTask.value;
+      if (!task || pollingInFlight || task.id !== id || task.is_finished) {
+        if (!task || task.id !== id || task.is_finished) stopPolling();
+        return;
+      }
+
+      pollingInFlight = true;
+      try {
+        const data = await downloadApi.pollTask(id);
+        if (session !== activeSession || !currentTask.value || currentTask.value.id !== id)
+          return;
+
+        // 算瞬时速度
+        const now = Date.now();
+        if (lastTimestamp > 0 && now > lastTimestamp) {
+          const deltaBytes = data.downloaded - lastDownloaded;
+          const deltaSec = (now - lastTimestamp) / 1000;
+          if (deltaSec > 0) speed.value = Math.max(0, deltaBytes / deltaSec);
+        }
+        lastDownloaded = data.downloaded;
+        lastTimestamp = now;
+
+        Object.assign(currentTask.value, data);
+        if (data.is_finished) {
+          currentTask.value.progress = 100;
+          speed.value = 0;
+          stopPolling();
+          startAutoDismissTimer();
+        }
+      } catch (err) {
+        if (
+          session === activeSession &&
+          currentTask.value &&
+          currentTask.value.id === id &&
+          !currentTask.value.is_finished
+        ) {
+          currentTask.value.status = { Error: i18n.t("downloader.connection_lost") };
+          currentTask.value.is_finished = true;
+          speed.value = 0;
+          startAutoDismissTimer();
+        }
+        stopPolling();
+      } finally {
+        pollingInFlight = false;
+      }
+    }, POLL_INTERVAL_MS);
+  }
+
   // ========== Actions ==========
   /**
    * 启动下载任务，替代原来 useDownload().start
@@ -172,7 +240,9 @@ export const useDownloadStore = defineStore("download", () => {
       downloaded: 0,
       progress: 0,
       status: "Pending",
+      error_kind: null,
       is_finished: false,
+      queue_position: null,
     };
     taskOriginTab.value = meta.origin;
     filename.value = meta.filename;
@@ -190,58 +260,7 @@ export const useDownloadStore = defineStore("download", () => {
       if (!currentTask.value) return;
       currentTask.value.id = id;
 
-      let pollingInFlight = false;
-      pollTimer = window.setInterval(async () => {
-        if (session !== activeSession) {
-          stopPolling();
-          return;
-        }
-        const task = currentTask.value;
-        if (!task || pollingInFlight || task.id !== id || task.is_finished) {
-          if (!task || task.id !== id || task.is_finished) stopPolling();
-          return;
-        }
-
-        pollingInFlight = true;
-        try {
-          const data = await downloadApi.pollTask(id);
-          if (session !== activeSession || !currentTask.value || currentTask.value.id !== id)
-            return;
-
-          // 算瞬时速度
-          const now = Date.now();
-          if (lastTimestamp > 0 && now > lastTimestamp) {
-            const deltaBytes = data.downloaded - lastDownloaded;
-            const deltaSec = (now - lastTimestamp) / 1000;
-            if (deltaSec > 0) speed.value = Math.max(0, deltaBytes / deltaSec);
-          }
-          lastDownloaded = data.downloaded;
-          lastTimestamp = now;
-
-          Object.assign(currentTask.value, data);
-          if (data.is_finished) {
-            currentTask.value.progress = 100;
-            speed.value = 0;
-            stopPolling();
-            startAutoDismissTimer();
-          }
-        } catch (err) {
-          if (
-            session === activeSession &&
-            currentTask.value &&
-            currentTask.value.id === id &&
-            !currentTask.value.is_finished
-          ) {
-            currentTask.value.status = { Error: i18n.t("downloader.connection_lost") };
-            currentTask.value.is_finished = true;
-            speed.value = 0;
-            startAutoDismissTimer();
-          }
-          stopPolling();
-        } finally {
-          pollingInFlight = false;
-        }
-      }, POLL_INTERVAL_MS);
+      startPolling(id, session);
     } catch (err: any) {
       if (currentTask.value) {
         currentTask.value.status = { Error: err.toString() };
@@ -251,6 +270,60 @@ export const useDownloadStore = defineStore("download", () => {
     }
   }
 
+  /**
+   * 启动时续传上次没下完的任务
+   * 单任务模型，只接管第一个续传成功的任务；已有任务时不动
+   */
+  async function resumeIncompleteTasks(): Promise<void> {
+    if (currentTask.value) return;
+    const resumed = await downloadApi.resumeIncomplete();
+    for (const item of resumed) {
+      if (item.task.is_finished) {
+        console.warn("Failed to resume download:", item.url, item.task.status);
+      }
+    }
+    const next = resumed.find((item) => !item.task.is_finished);
+    if (!next || currentTask.value) return;
+
+    stopPolling();
+    clearDismissTimer();
+    activeSession += 1;
+    currentTask.value = { ...next.task };
+    taskOriginTab.value = "file";
+    filename.value = next.save_path.split(/[\\/]/).pop() ?? "";
+    savePath.value = next.save_path;
+    viewed.value = false;
+    panelOpen.value = false;
+    speed.value = 0;
+    lastDownloaded = next.task.downloaded;
+    lastTimestamp = Date.now();
+    startPolling(next.task.id, activeSession);
+  }
+
+  /** 暂停当前任务，轮询继续跑，状态会变成 Paused */
+  async function pauseTask(): Promise<void> {
+    const task = currentTask.value;
+    if (!task?.id || task.is_finished) return;
+    await downloadApi.pauseTask(task.id);
+    if (currentTask.value?.id === task.id) {
+      currentTask.value.status = "Paused";
+      currentTask.value.queue_position = null;
+      speed.value = 0;
+    }
+  }
+
+  /** 恢复暂停的任务，重新排队 */
+  async function resumeTask(): Promise<void> {
+    const task = currentTask.value;
+    if (!task?.id || task.is_finished) return;
+    const data = await downloadApi.resumeTask(task.id);
+    if (currentTask.value?.id === task.id) {
+      Object.assign(currentTask.value, data);
+      lastDownloaded = data.downloaded;
+      lastTimestamp = Date.now();
+    }
+  }
+
   /** 撅掉下载任务 */
   async function cancelTask(): Promise<void> {
     if (currentTask.value?.id) {
@@ -316,12 +389,17 @@ export const useDownloadStore = defineStore("download", () => {
     isFinished,
     isCompleted,
     isError,
+    isPaused,
+    queuePosition,
     taskError,
     progress,
     statusLabel,
     shouldShowPill,
     // 操作
     startTask,
+    resumeIncompleteTasks,
+    pauseTask,
+    resumeTask,
     cancelTask,
     resetTask,
     markViewed,
@@ -71,7 +71,7 @@ export const useServerStore = defineStore("server", () => {
       if (!serverPath) return;
 
       // 读取 server.properties 文件
-      const data = await configApi.readServerProperties(serverPath);
+      const data = await configApi.readServerProperties(serverPath, server.mc_version);
       if (data.raw && data.raw["server-port"]) {
         const port = parseInt(data.raw["server-port"]);
         if (!isNaN(port)) {
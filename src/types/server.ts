@@ -1,3 +1,5 @@
+import type { PortConflict } from "@api/server";
+
 /**
  * 服务器实例类型
  */
@@ -18,6 +20,8 @@ export interface ServerInstance {
   port: number;
   created_at: number;
   last_started_at: number | null;
+  /** 与其他实例共用的端口（游戏、查询、RCON、代理监听） */
+  port_conflicts: PortConflict[];
 }
 
 /**
@@ -94,6 +94,7 @@ export function useConfigCompare(options: UseConfigCompareOptions) {
     () => options.servers.value.find((s) => s.id === compareTargetServerId.value) || null,
   );
   const compareTargetPath = computed(() => compareTargetServer.value?.path || "");
+  const compareTargetGameVersion = computed(() => compareTargetServer.value?.mc_version);
   const compareTargetServerPropertiesPath = computed(() =>
     buildServerPropertiesPath(compareTargetPath.value),
   );
@@ -187,7 +188,10 @@ export function useConfigCompare(options: UseConfigCompareOptions) {
   );
 
   async function applyParsedCompareTargetState(sourceText: string) {
-    const parsed = await configApi.parseServerPropertiesSource(sourceText);
+    const parsed = await configApi.parseServerPropertiesSource(
+      sourceText,
+      compareTargetGameVersion.value,
+    );
     compareTargetEntries.value = parsed.entries as ConfigEntryType[];
     compareTargetDraftValues.value = { ...parsed.raw };
     compareTargetLoadedValues.value = { ...parsed.raw };
@@ -198,7 +202,10 @@ export function useConfigCompare(options: UseConfigCompareOptions) {
   }
 
   async function applyCompareTargetSourceDraftToVisualState(sourceText: string) {
-    const parsed = await configApi.parseServerPropertiesSource(sourceText);
+    const parsed = await configApi.parseServerPropertiesSource(
+      sourceText,
+      compareTargetGameVersion.value,
+    );
     compareTargetEntries.value = parsed.entries as ConfigEntryType[];
     compareTargetDraftValues.value = { ...parsed.raw };
     compareTargetVisualModeBaseValues.value = { ...parsed.raw };
@@ -221,10 +228,15 @@ export function useConfigCompare(options: UseConfigCompareOptions) {
       return configApi.previewServerPropertiesWriteFromSource(
         compareTargetSourceDraftText.value,
         changedValues,
+        compareTargetGameVersion.value,
       );
     }
 
-    return configApi.previewServerPropertiesWrite(compareTargetPath.value, changedValues);
+    return configApi.previewServerPropertiesWrite(
+      compareTargetPath.value,
+      changedValues,
+      compareTargetGameVersion.value,
+    );
   }
 
   async function prepareCompareTargetSourceDraftForSourceMode() {
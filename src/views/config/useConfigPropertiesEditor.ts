@@ -38,6 +38,8 @@ const COMPARE_DIFFERENCE_CATEGORY = "difference";
 
 interface UseConfigPropertiesEditorOptions {
   serverPath: Ref<string>;
+  // 实例的游戏版本，用于挑选对应版本的字段模式
+  gameVersion: Ref<string | undefined>;
   serverPropertiesPath: Ref<string>;
   currentServerId: Ref<string | null>;
   currentServerName: ComputedRef<string>;
@@ -240,17 +242,28 @@ export function useConfigPropertiesEditor(options: UseConfigPropertiesEditorOpti
     const changedValues = getChangedPropertyValues();
 
     if (sourceDraftText.value !== loadedSourceText.value) {
-      return configApi.previewServerPropertiesWriteFromSource(sourceDraftText.value, changedValues);
+      return configApi.previewServerPropertiesWriteFromSource(
+        sourceDraftText.value,
+        changedValues,
+        options.gameVersion.value,
+      );
     }
 
-    return configApi.previewServerPropertiesWrite(options.serverPath.value, changedValues);
+    return configApi.previewServerPropertiesWrite(
+      options.serverPath.value,
+      changedValues,
+      options.gameVersion.value,
+    );
   }
 
   async function applyParsedSourceState(
     sourceText: string,
     targetMode: "visual" | "source" = "visual",
   ) {
-    const parsed = await configApi.parseServerPropertiesSource(sourceText);
+    const parsed = await configApi.parseServerPropertiesSource(
+      sourceText,
+      options.gameVersion.value,
+    );
     entries.value = parsed.entries as ConfigEntryType[];
     editValues.value = { ...parsed.raw };
     loadedValues.value = { ...parsed.raw };
@@ -465,7 +478,10 @@ export function useConfigPropertiesEditor(options: UseConfigPropertiesEditorOpti
         return;
       }
 
-      const parsed = await configApi.parseServerPropertiesSource(sourceDraftText.value);
+      const parsed = await configApi.parseServerPropertiesSource(
+        sourceDraftText.value,
+        options.gameVersion.value,
+      );
       entries.value = parsed.entries as ConfigEntryType[];
       editValues.value = { ...parsed.raw };
       visualModeBaseValues.value = { ...parsed.raw };